@@ -25,6 +25,7 @@
 
 //! Matcher.
 
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 mod error;
@@ -33,6 +34,7 @@ mod route;
 
 pub use error::{Error, Result};
 pub use params::Params;
+use route::Constraint;
 pub use route::Route;
 
 // ----------------------------------------------------------------------------
@@ -44,11 +46,33 @@ pub use route::Route;
 /// This is a thin wrapper around the [`Router`][] data type of the [`matchit`]
 /// crate to shield against unforeseen changes in the crate's implementation.
 ///
+/// Since the [`matchit`] crate doesn't support parameter constraints, they're
+/// checked after matching. Precedence is deterministic: the [`matchit`] crate
+/// selects the most specific pattern, where static segments take precedence
+/// over parameters, and parameters over catch-all parameters. Routes sharing
+/// the same pattern apart from constraints are tried in the order they were
+/// added, so all of them except for the last must be constrained. If none of
+/// them is satisfied, the path doesn't match, and isn't retried against less
+/// specific patterns, so requests fall through to the next handler.
+///
 /// [`Router`]: matchit::Router
 #[derive(Debug, Default)]
 pub struct Matcher<T = ()> {
-    /// Matcher implementation.
-    inner: matchit::Router<T>,
+    /// Matcher implementation, mapping patterns to candidates.
+    inner: matchit::Router<usize>,
+    /// Map patterns to candidates.
+    patterns: BTreeMap<String, usize>,
+    /// Candidates for each pattern, tried in order.
+    candidates: Vec<Vec<Candidate<T>>>,
+}
+
+/// Matcher candidate.
+#[derive(Debug)]
+struct Candidate<T> {
+    /// Parameter constraints.
+    constraints: Vec<(String, Constraint)>,
+    /// Associated data.
+    data: T,
 }
 
 /// Match.
@@ -75,7 +99,11 @@ impl<T> Matcher<T> {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        Self { inner: matchit::Router::new() }
+        Self {
+            inner: matchit::Router::new(),
+            patterns: BTreeMap::new(),
+            candidates: Vec::new(),
+        }
     }
 
     /// Adds a route to the matcher.
@@ -83,7 +111,9 @@ impl<T> Matcher<T> {
     /// # Errors
     ///
     /// This method returns [`Error::Insert`], if the route could not be added
-    /// to the matcher, including the reason for the failure.
+    /// to the matcher, including the reason for the failure, which is also
+    /// the case when adding a route after an unconstrained route with the same
+    /// pattern, as it could never match.
     ///
     /// # Examples
     ///
@@ -102,9 +132,31 @@ impl<T> Matcher<T> {
     /// ```
     #[allow(clippy::needless_pass_by_value)]
     pub fn add(&mut self, route: Route, value: T) -> Result {
-        self.inner
-            .insert(route.to_string(), value)
-            .map_err(Into::into)
+        let (pattern, constraints) = route.split()?;
+
+        // Routes with the same pattern share an entry in the matcher, as long
+        // as all previously added routes are constrained - otherwise, we try
+        // to insert the pattern again, so the matcher reports the conflict
+        let index = match self.patterns.get(&pattern) {
+            Some(&index)
+                if self.candidates[index]
+                    .iter()
+                    .all(|candidate| !candidate.constraints.is_empty()) =>
+            {
+                index
+            }
+            _ => {
+                let index = self.candidates.len();
+                self.inner.insert(pattern.clone(), index)?;
+                self.patterns.insert(pattern, index);
+                self.candidates.push(Vec::new());
+                index
+            }
+        };
+
+        // Add candidate to pattern
+        self.candidates[index].push(Candidate { constraints, data: value });
+        Ok(())
     }
 
     /// Attempts to resolve and match the given path.
@@ -129,9 +181,23 @@ impl<T> Matcher<T> {
     /// # }
     /// ```
     pub fn resolve<'v>(&self, path: &'v str) -> Option<Match<'_, 'v, &T>> {
-        self.inner.at(path).ok().map(|route| Match {
-            params: Params::new(route.params),
-            data: route.value,
+        let route = self.inner.at(path).ok()?;
+        let params = Params::new(route.params);
+
+        // Return the first candidate whose constraints are satisfied
+        self.candidates[*route.value]
+            .iter()
+            .find(|candidate| candidate.is_match(&params))
+            .map(|candidate| Match { params, data: &candidate.data })
+    }
+}
+
+impl<T> Candidate<T> {
+    /// Returns whether the given parameters satisfy all constraints.
+    fn is_match(&self, params: &Params) -> bool {
+        self.constraints.iter().all(|(name, constraint)| {
+            let value = params.get(name.as_str());
+            value.is_some_and(|value| constraint.is_match(value))
         })
     }
 }
@@ -171,3 +237,86 @@ impl FromStr for Matcher {
             .map(|()| matcher)
     }
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Error, Matcher, Route};
+
+    fn matcher(routes: &[&str]) -> Matcher<usize> {
+        let mut matcher = Matcher::new();
+        for (i, route) in routes.iter().enumerate() {
+            matcher.add(Route::from_str(route).unwrap(), i).unwrap();
+        }
+        matcher
+    }
+
+    #[test]
+    fn matches_int_constraint() {
+        let matcher = matcher(&["/items/{id:int}"]);
+        let res = matcher.resolve("/items/123").unwrap();
+        assert_eq!(res.params.get("id"), Some("123"));
+        assert!(matcher.resolve("/items/abc").is_none());
+        assert!(matcher.resolve("/items/-1").is_none());
+    }
+
+    #[test]
+    fn matches_uuid_constraint() {
+        let matcher = matcher(&["/items/{id:uuid}"]);
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        assert!(matcher.resolve(&format!("/items/{id}")).is_some());
+        assert!(matcher.resolve("/items/67e55044-10b1-426f").is_none());
+        assert!(matcher
+            .resolve("/items/67e55044x10b1x426fx9247xbb680e5fe0c8")
+            .is_none());
+    }
+
+    #[test]
+    fn matches_regex_constraint() {
+        let matcher = matcher(&[r"/v/{version:\d+\.\d{1,2}}/{*path}"]);
+        let res = matcher.resolve("/v/1.2/index.html").unwrap();
+        assert_eq!(res.params.get("version"), Some("1.2"));
+        assert_eq!(res.params.get("path"), Some("index.html"));
+        assert!(matcher.resolve("/v/1.234/index.html").is_none());
+        assert!(matcher.resolve("/v/x1.2/index.html").is_none());
+    }
+
+    #[test]
+    fn matches_in_order_of_precedence() {
+        let matcher = matcher(&[
+            "/v/{version:int}",
+            "/v/{version:uuid}",
+            "/v/{version}",
+            "/v/latest",
+        ]);
+        assert_eq!(matcher.resolve("/v/latest").unwrap().data, &3);
+        assert_eq!(matcher.resolve("/v/1").unwrap().data, &0);
+        let id = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        assert_eq!(matcher.resolve(&format!("/v/{id}")).unwrap().data, &1);
+        assert_eq!(matcher.resolve("/v/next").unwrap().data, &2);
+    }
+
+    #[test]
+    fn falls_through_without_match() {
+        let matcher = matcher(&["/v/{version:int}", "/{*path}"]);
+        assert!(matcher.resolve("/v/latest").is_none());
+        assert_eq!(matcher.resolve("/other").unwrap().data, &1);
+    }
+
+    #[test]
+    fn rejects_route_after_unconstrained_route() {
+        let mut matcher = matcher(&["/v/{version}"]);
+        let route = Route::from_str("/v/{version:int}").unwrap();
+        assert!(matches!(matcher.add(route, 1), Err(Error::Insert(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_constraint() {
+        assert!(Route::from_str("/v/{version:(}").is_err());
+    }
+}
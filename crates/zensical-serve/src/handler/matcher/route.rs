@@ -28,8 +28,10 @@
 use std::fmt;
 use std::str::FromStr;
 
+mod constraint;
 mod error;
 
+pub use constraint::Constraint;
 pub use error::{Error, Result};
 
 // ----------------------------------------------------------------------------
@@ -41,6 +43,11 @@ pub use error::{Error, Result};
 /// Routes are just non-empty strings that have been confirmed to start with `/`
 /// and not end with `/`, which makes joining them significantly easier. Routes
 /// might contain parameters, which are denoted by `{...}` brackets.
+///
+/// Parameters can be constrained by adding a [`Constraint`] after a colon,
+/// e.g., `{id:int}`, `{id:uuid}`, or a regular expression like
+/// `{version:\d+\.\d+}`, which must match the entire value. Braces inside
+/// of regular expressions are allowed, as long as they are balanced.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Route {
     /// Route path.
@@ -88,6 +95,57 @@ impl Route {
             Self { path }
         }
     }
+
+    /// Splits the route into a pattern and parameter constraints.
+    ///
+    /// The returned pattern is the route without constraints, which is what
+    /// is passed to the [`matchit`] crate, as it doesn't support constraints.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Constraint`], if a constraint is invalid.
+    pub(crate) fn split(&self) -> Result<(String, Vec<(String, Constraint)>)> {
+        let mut pattern = String::with_capacity(self.path.len());
+        let mut constraints = Vec::new();
+
+        // Extract constraints from parameters, skipping escaped braces
+        let mut rest = self.path.as_str();
+        while let Some(start) = rest.find('{') {
+            pattern.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if let Some(tail) = rest.strip_prefix("{{") {
+                pattern.push_str("{{");
+                rest = tail;
+                continue;
+            }
+
+            // Find the closing brace of the parameter, which might be preceded
+            // by balanced braces inside of a regular expression - if there's
+            // none, we leave the route as is, so the matcher reports it
+            let Some(end) = closing_brace(rest) else {
+                break;
+            };
+
+            // Remove the constraint from the parameter, if any
+            let param = &rest[1..end];
+            if let Some((name, value)) = param.split_once(':') {
+                let key = name.trim_start_matches('*');
+                let constraint = Constraint::from_str(value)
+                    .map_err(|err| Error::Constraint(key.to_string(), err))?;
+                constraints.push((key.to_string(), constraint));
+                pattern.push('{');
+                pattern.push_str(name);
+                pattern.push('}');
+            } else {
+                pattern.push_str(&rest[..=end]);
+            }
+            rest = &rest[end + 1..];
+        }
+
+        // Append remaining part of route
+        pattern.push_str(rest);
+        Ok((pattern, constraints))
+    }
 }
 
 #[allow(clippy::must_use_candidate)]
@@ -139,8 +197,9 @@ impl FromStr for Route {
             return Err(Error::Trailing(value.to_string()));
         }
 
-        // No errors occurred
-        Ok(Self { path: value.to_string() })
+        // Ensure all parameter constraints are valid
+        let route = Self { path: value.to_string() };
+        route.split().map(|_| route)
     }
 }
 
@@ -180,3 +239,29 @@ impl fmt::Display for Route {
         f.write_str(&self.path)
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the index of the brace closing the parameter at the start of the
+/// given string, skipping balanced and escaped braces in between.
+fn closing_brace(value: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, char) in value.char_indices() {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Route parameter constraint.
+
+use regex::Regex;
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Route parameter constraint.
+///
+/// Constraints are given after a colon inside the braces of a parameter, e.g.,
+/// `{id:int}`, and restrict the values a parameter may match. Besides the
+/// built-in classes `int` and `uuid`, any other constraint is compiled into a
+/// regular expression, which must match the entire value of the parameter.
+#[derive(Clone, Debug)]
+pub enum Constraint {
+    /// Non-empty sequence of ASCII digits.
+    Int,
+    /// UUID in hyphenated form, e.g., `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Uuid,
+    /// Regular expression.
+    Regex(Regex),
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Constraint {
+    /// Returns whether the given value satisfies the constraint.
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            Constraint::Int => {
+                !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+            }
+            Constraint::Uuid => {
+                value.len() == 36
+                    && value.bytes().enumerate().all(|(i, b)| match i {
+                        8 | 13 | 18 | 23 => b == b'-',
+                        _ => b.is_ascii_hexdigit(),
+                    })
+            }
+            Constraint::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl FromStr for Constraint {
+    type Err = regex::Error;
+
+    /// Attempts to create a constraint from a string.
+    ///
+    /// Regular expressions are anchored, so they must match the entire value.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "int" => Ok(Constraint::Int),
+            "uuid" => Ok(Constraint::Uuid),
+            _ => Regex::new(&format!("^(?:{value})$")).map(Constraint::Regex),
+        }
+    }
+}
@@ -46,6 +46,10 @@ pub enum Error {
     /// Route must not be empty.
     #[error("route must not be empty")]
     Empty,
+
+    /// Route parameter constraint is invalid.
+    #[error("invalid constraint for parameter '{0}': {1}")]
+    Constraint(String, #[source] regex::Error),
 }
 
 // ----------------------------------------------------------------------------
@@ -25,6 +25,8 @@
 
 //! HTTP protocol.
 
+#[cfg(feature = "http-compat")]
+pub mod compat;
 pub mod component;
 pub mod request;
 pub mod response;
@@ -0,0 +1,490 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! HTTP compatibility with the [`http`] crate.
+//!
+//! This module provides conversions between our HTTP types and the types of
+//! the [`http`] crate, so handlers and middlewares can be shared with other
+//! libraries in the ecosystem. It's only available with the `http-compat`
+//! feature. Note that some conversions are lossy, as we deliberately keep our
+//! own types small and simple:
+//!
+//! - Unknown headers are dropped, as [`Header`] only represents well-known
+//!   headers, which is consistent with how [`Request::from_bytes`] works.
+//!   The same goes for header values that are not valid UTF-8.
+//!
+//! - Headers that appear multiple times are collapsed, keeping the last value,
+//!   as our header maps only support setting each header once.
+//!
+//! - Methods and status codes unknown to [`Method`] and [`Status`] can't be
+//!   represented, and fail to convert with an [`Error`].
+//!
+//! - [`Response::route`] is not sent to clients, and thus is not carried over
+//!   to the [`http`] crate, and extensions of the [`http`] crate are dropped.
+//!
+//! Query strings are preserved, but might be normalized in the process, as
+//! they are parsed into a [`Query`][] and percent-encoded again.
+//!
+//! [`Query`]: crate::http::Query
+
+use std::borrow::Cow;
+
+use super::request::Extensions;
+use super::{Header, Method, Request, Response, Status, Uri};
+
+mod error;
+
+pub use error::{Error, Result};
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl TryFrom<http::Method> for Method {
+    type Error = Error;
+
+    /// Attempts to create a method from an [`http::Method`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Component`], if the method is not one of
+    /// the known methods, e.g., `CONNECT` or extension methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::Method;
+    ///
+    /// // Create method from http crate method
+    /// let method = Method::try_from(http::Method::GET)?;
+    /// assert_eq!(method, Method::Get);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(method: http::Method) -> Result<Self> {
+        method.as_str().parse().map_err(Into::into)
+    }
+}
+
+impl From<Method> for http::Method {
+    /// Creates an [`http::Method`] from a method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::Method;
+    ///
+    /// // Create http crate method from method
+    /// let method = http::Method::from(Method::Get);
+    /// assert_eq!(method, http::Method::GET);
+    /// ```
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Get => http::Method::GET,
+            Method::Head => http::Method::HEAD,
+            Method::Post => http::Method::POST,
+            Method::Put => http::Method::PUT,
+            Method::Delete => http::Method::DELETE,
+            Method::Options => http::Method::OPTIONS,
+            Method::Trace => http::Method::TRACE,
+            Method::Patch => http::Method::PATCH,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl TryFrom<&http::HeaderName> for Header {
+    type Error = Error;
+
+    /// Attempts to create a header from an [`http::HeaderName`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Component`], if the header is not one of
+    /// the known headers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::Header;
+    ///
+    /// // Create header from http crate header name
+    /// let header = Header::try_from(&http::header::CONTENT_TYPE)?;
+    /// assert_eq!(header, Header::ContentType);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(name: &http::HeaderName) -> Result<Self> {
+        name.as_str().parse().map_err(Into::into)
+    }
+}
+
+impl From<Header> for http::HeaderName {
+    /// Creates an [`http::HeaderName`] from a header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::Header;
+    ///
+    /// // Create http crate header name from header
+    /// let name = http::HeaderName::from(Header::ContentType);
+    /// assert_eq!(name, http::header::CONTENT_TYPE);
+    /// ```
+    fn from(header: Header) -> Self {
+        let name = header.name().as_bytes();
+        http::HeaderName::from_bytes(name).expect("invariant")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl TryFrom<http::StatusCode> for Status {
+    type Error = Error;
+
+    /// Attempts to create a status from an [`http::StatusCode`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Status`], if the status code is not one
+    /// of the known status codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::Status;
+    ///
+    /// // Create status from http crate status code
+    /// let status = Status::try_from(http::StatusCode::NOT_FOUND)?;
+    /// assert_eq!(status, Status::NotFound);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(code: http::StatusCode) -> Result<Self> {
+        let code = code.as_u16();
+        Status::from_code(code).ok_or(Error::Status(code))
+    }
+}
+
+impl From<Status> for http::StatusCode {
+    /// Creates an [`http::StatusCode`] from a status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::Status;
+    ///
+    /// // Create http crate status code from status
+    /// let code = http::StatusCode::from(Status::NotFound);
+    /// assert_eq!(code, http::StatusCode::NOT_FOUND);
+    /// ```
+    fn from(status: Status) -> Self {
+        http::StatusCode::from_u16(status as u16).expect("invariant")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'a> TryFrom<&'a http::Request<Vec<u8>>> for Request<'a> {
+    type Error = Error;
+
+    /// Attempts to create a request from an [`http::Request`].
+    ///
+    /// The returned [`Request`] borrows URI, header values and body from the
+    /// given request, mirroring [`Request::from_bytes`]. Unknown headers and
+    /// header values that are not valid UTF-8 are dropped.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Component`], if the request method is not
+    /// one of the known methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::{Method, Request};
+    ///
+    /// // Create http crate request
+    /// let req = http::Request::get("/path?key=value").body(Vec::new())?;
+    ///
+    /// // Create request from http crate request
+    /// let req = Request::try_from(&req)?;
+    /// assert_eq!(req.method, Method::Get);
+    /// assert_eq!(req.uri.query.get("key"), Some("value"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(req: &'a http::Request<Vec<u8>>) -> Result<Self> {
+        let method = Method::try_from(req.method().clone())?;
+        let uri = req
+            .uri()
+            .path_and_query()
+            .map_or_else(Uri::default, |path| Uri::from(path.as_str()));
+
+        // Convert headers, skipping any unknown headers and header values that
+        // are not valid UTF-8, just like we do when parsing requests
+        let headers = req.headers().iter().filter_map(|(name, value)| {
+            let header = Header::try_from(name).ok()?;
+            value.to_str().ok().map(|value| (header, value))
+        });
+
+        // Create request
+        Ok(Request {
+            method,
+            uri,
+            headers: headers.collect(),
+            body: Cow::Borrowed(req.body()),
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+impl TryFrom<Request<'_>> for http::Request<Vec<u8>> {
+    type Error = Error;
+
+    /// Attempts to create an [`http::Request`] from a request.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Http`], if the [`http`] crate rejects the
+    /// request URI or a header value, e.g., because it contains newlines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::{Method, Request};
+    ///
+    /// // Create request
+    /// let req = Request::new()
+    ///     .method(Method::Get)
+    ///     .uri("/path?key=value");
+    ///
+    /// // Create http crate request from request
+    /// let req = http::Request::try_from(req)?;
+    /// assert_eq!(req.uri(), "/path?key=value");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(req: Request<'_>) -> Result<Self> {
+        let mut builder = http::Request::builder()
+            .method(http::Method::from(req.method))
+            .uri(req.uri.to_string());
+
+        // Copy headers
+        for (header, value) in &req.headers {
+            builder = builder.header(*header, value.as_ref());
+        }
+
+        // Create request
+        builder.body(req.body.into_owned()).map_err(Into::into)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl TryFrom<http::Response<Vec<u8>>> for Response {
+    type Error = Error;
+
+    /// Attempts to create a response from an [`http::Response`].
+    ///
+    /// Unknown headers and header values that are not valid UTF-8 are dropped.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Status`], if the response status code is
+    /// not one of the known status codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::{Response, Status};
+    ///
+    /// // Create http crate response
+    /// let res = http::Response::builder()
+    ///     .status(http::StatusCode::NOT_FOUND)
+    ///     .body(Vec::new())?;
+    ///
+    /// // Create response from http crate response
+    /// let res = Response::try_from(res)?;
+    /// assert_eq!(res.status, Status::NotFound);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(res: http::Response<Vec<u8>>) -> Result<Self> {
+        let (parts, body) = res.into_parts();
+        let mut res = Response::new()
+            .status(Status::try_from(parts.status)?)
+            .body(body);
+
+        // Convert headers, skipping any unknown headers and header values that
+        // are not valid UTF-8, just like we do when parsing requests
+        for (name, value) in &parts.headers {
+            if let Ok(header) = Header::try_from(name) {
+                if let Ok(value) = value.to_str() {
+                    res.headers.insert(header, value);
+                }
+            }
+        }
+
+        // Return response
+        Ok(res)
+    }
+}
+
+impl TryFrom<Response> for http::Response<Vec<u8>> {
+    type Error = Error;
+
+    /// Attempts to create an [`http::Response`] from a response.
+    ///
+    /// Note that [`Response::route`] is dropped, as it's not sent to clients.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`Error::Http`], if the [`http`] crate rejects a
+    /// header value, e.g., because it contains newlines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::http::{Response, Status};
+    ///
+    /// // Create response
+    /// let res = Response::new().status(Status::NotFound);
+    ///
+    /// // Create http crate response from response
+    /// let res = http::Response::try_from(res)?;
+    /// assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_from(res: Response) -> Result<Self> {
+        let mut builder = http::Response::builder()
+            .status(http::StatusCode::from(res.status));
+
+        // Copy headers
+        for (header, value) in &res.headers {
+            builder = builder.header(*header, value.as_str());
+        }
+
+        // Create response
+        builder.body(res.body).map_err(Into::into)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::http::{Header, Method, Request, Response, Status};
+
+    use super::Error;
+
+    #[test]
+    fn converts_request_round_trip() -> Result<(), Error> {
+        let req = Request::new()
+            .method(Method::Post)
+            .uri("/path/to%20page?query=a%20b&flag")
+            .header(Header::ContentType, "text/plain")
+            .header(Header::AcceptLanguage, "en")
+            .body("Hello, world!");
+
+        // Convert to http crate request and back again
+        let req = http::Request::try_from(req)?;
+        assert_eq!(req.uri(), "/path/to%20page?query=a%20b&flag");
+        let req = Request::try_from(&req)?;
+        assert_eq!(req.method, Method::Post);
+        assert_eq!(req.uri.path, "/path/to page");
+        assert_eq!(req.uri.query.get("query"), Some("a b"));
+        assert!(req.uri.query.contains("flag"));
+        assert_eq!(req.headers.get(Header::ContentType), Some("text/plain"));
+        assert_eq!(req.headers.get(Header::AcceptLanguage), Some("en"));
+        assert_eq!(req.body.as_ref(), b"Hello, world!");
+        Ok(())
+    }
+
+    #[test]
+    fn converts_response_round_trip() -> Result<(), Error> {
+        let mut res = Response::new()
+            .status(Status::NotModified)
+            .header(Header::ETag, "\"abc\"")
+            .body("Hello, world!");
+        res.route = Some(String::from("/items/{id}"));
+
+        // Convert to http crate response and back again
+        let res = http::Response::try_from(res)?;
+        assert_eq!(res.status(), http::StatusCode::NOT_MODIFIED);
+        let res = Response::try_from(res)?;
+        assert_eq!(res.status, Status::NotModified);
+        assert_eq!(res.headers.get(Header::ETag), Some("\"abc\""));
+        assert_eq!(res.body, b"Hello, world!");
+        assert_eq!(res.route, None);
+        Ok(())
+    }
+
+    #[test]
+    fn drops_unknown_headers() -> Result<(), Error> {
+        let req = http::Request::get("/")
+            .header("x-unknown", "value")
+            .header("accept", "text/html")
+            .body(Vec::new())?;
+
+        // Convert to request
+        let req = Request::try_from(&req)?;
+        assert_eq!(req.headers.len(), 1);
+        assert_eq!(req.headers.get(Header::Accept), Some("text/html"));
+        Ok(())
+    }
+
+    #[test]
+    fn handles_unknown_method() -> Result<(), Error> {
+        let req = http::Request::connect("example.com:443").body(Vec::new())?;
+        assert!(matches!(Request::try_from(&req), Err(Error::Component(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn handles_unknown_status() -> Result<(), Error> {
+        let res = http::Response::builder().status(299).body(Vec::new())?;
+        assert!(matches!(Response::try_from(res), Err(Error::Status(299))));
+        Ok(())
+    }
+}
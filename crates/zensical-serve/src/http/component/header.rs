@@ -274,6 +274,8 @@ define_and_impl_header! {
         RetryAfter = "Retry-After",
         /// Server
         Server = "Server",
+        /// Server-Timing
+        ServerTiming = "Server-Timing",
         /// Set-Cookie
         SetCookie = "Set-Cookie",
         /// Strict-Transport-Security
@@ -109,6 +109,24 @@ macro_rules! define_and_impl_status {
                     )+
                 }
             }
+
+            /// Returns the status for the given code, if known.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use zensical_serve::http::Status;
+            ///
+            /// // Obtain status from code
+            /// let status = Status::from_code(304);
+            /// assert_eq!(status, Some(Status::NotModified));
+            /// ```
+            #[must_use]
+            pub fn from_code(code: u16) -> Option<Self> {
+                [$($(Status::$name,)+)+]
+                    .into_iter()
+                    .find(|status| *status as u16 == code)
+            }
         }
     };
 }
@@ -33,12 +33,14 @@ use std::str::{self, FromStr};
 use super::component::{Header, Method, Status};
 
 mod error;
+mod extensions;
 mod headers;
 mod uri;
 
 pub use error::{Error, Result};
+pub use extensions::Extensions;
 pub use headers::Headers;
-pub use uri::{Query, Uri};
+pub use uri::{Query, QueryError, Uri};
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -73,6 +75,8 @@ pub struct Request<'a> {
     pub headers: Headers<'a>,
     /// Request body.
     pub body: Cow<'a, [u8]>,
+    /// Request extensions, which are not part of the message.
+    pub extensions: Extensions,
 }
 
 // ----------------------------------------------------------------------------
@@ -108,6 +112,11 @@ impl<'a> Request<'a> {
     /// traversal attempts. Note that NUL characters are already rejected by
     /// [`httparse`], so we don't need to handle them again.
     ///
+    /// Besides origin-form targets, e.g., `/path`, the asterisk-form target `*`
+    /// is accepted for `OPTIONS` requests, and absolute-form targets, e.g.,
+    /// `http://localhost/path`, are normalized into origin-form, but only if
+    /// their authority matches the `Host` header.
+    ///
     /// # Errors
     ///
     /// This method returns [`Error::Incomplete`], if the given buffer contained
@@ -156,12 +165,15 @@ impl<'a> Request<'a> {
                     return Err(Error::Validation(Status::UriTooLong));
                 }
 
-                // Ensure that the request URI path starts with a slash, as we
-                // do not support proxy requests, and probably never will
-                let uri = Uri::from(path);
-                if !uri.path.starts_with('/') {
-                    return Err(Error::Validation(Status::BadRequest));
-                }
+                // Normalize the request target into origin-form, so that the
+                // request URI path starts with a slash, unless it's a server-
+                // wide OPTIONS request. We do not support proxy requests, and
+                // probably never will, so absolute-form targets must be for us.
+                let host = req.headers.iter().find_map(|header| {
+                    let name = header.name.eq_ignore_ascii_case("host");
+                    name.then_some(header.value)
+                });
+                let uri = parse_target(method, path, host)?;
 
                 // Ensure that the request URI path doesn't attempt a traversal,
                 // but do a quick check first to see whether the percent-decoded
@@ -201,7 +213,13 @@ impl<'a> Request<'a> {
 
                 // Collect headers, parsing URI and return request
                 let headers = iter.collect::<Result<_>>()?;
-                Ok(Request { method, uri, headers, body })
+                Ok(Request {
+                    method,
+                    uri,
+                    headers,
+                    body,
+                    extensions: Extensions::new(),
+                })
             }
         }
     }
@@ -313,6 +331,7 @@ impl Default for Request<'_> {
             uri: Uri::default(),
             headers: Headers::default(),
             body: Cow::Borrowed(&[]),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -327,3 +346,119 @@ impl fmt::Display for Request<'_> {
         write!(f, "[Body: {} bytes]\r\n", self.body.len())
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses the given request target into a request URI.
+///
+/// Origin-form targets are passed through, and the asterisk-form target `*` is
+/// only allowed for `OPTIONS` requests. Absolute-form targets are normalized
+/// into origin-form by stripping scheme and authority, which must match the
+/// given `Host` header, as we'd otherwise serve a request for another host.
+fn parse_target<'a>(
+    method: Method, target: &'a str, host: Option<&[u8]>,
+) -> Result<Uri<'a>> {
+    if target.starts_with('/') {
+        return Ok(Uri::from(target));
+    }
+
+    // Asterisk-form targets are only valid for server-wide OPTIONS requests
+    if target == "*" {
+        return match method {
+            Method::Options => Ok(Uri::from(target)),
+            _ => Err(Error::Validation(Status::BadRequest)),
+        };
+    }
+
+    // Absolute-form targets must use a HTTP scheme and have an authority
+    let Some(rest) = target.split_once("://").and_then(|(scheme, rest)| {
+        let http = scheme.eq_ignore_ascii_case("http")
+            || scheme.eq_ignore_ascii_case("https");
+        http.then_some(rest)
+    }) else {
+        return Err(Error::Validation(Status::BadRequest));
+    };
+
+    // Split authority from path and query, and ensure that the authority
+    // matches the host header, so the request can't address another host
+    let n = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, rest) = rest.split_at(n);
+    if authority.is_empty()
+        || !host
+            .is_some_and(|host| host.eq_ignore_ascii_case(authority.as_bytes()))
+    {
+        return Err(Error::Validation(Status::BadRequest));
+    }
+
+    // Normalize into origin-form, as the path might be empty
+    match rest.strip_prefix('?') {
+        Some(query) => Ok(Uri::from_parts("/", query)),
+        None if rest.is_empty() => Ok(Uri::default()),
+        None => Ok(Uri::from(rest)),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_asterisk_form_for_options() {
+        let req = Request::from_bytes(b"OPTIONS * HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(req.method, Method::Options);
+        assert_eq!(req.uri.path, "*");
+    }
+
+    #[test]
+    fn rejects_asterisk_form_for_other_methods() {
+        let res = Request::from_bytes(b"GET * HTTP/1.1\r\n\r\n");
+        assert!(matches!(res, Err(Error::Validation(Status::BadRequest))));
+    }
+
+    #[test]
+    fn normalizes_absolute_form_with_matching_host() {
+        let req = Request::from_bytes(
+            b"GET http://localhost:8000/path?key=value HTTP/1.1\r\n\
+              Host: localhost:8000\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(req.uri.path, "/path");
+        assert_eq!(req.uri.to_string(), "/path?key=value");
+    }
+
+    #[test]
+    fn normalizes_absolute_form_without_path() {
+        let req = Request::from_bytes(
+            b"GET HTTPS://LOCALHOST HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(req.uri.path, "/");
+    }
+
+    #[test]
+    fn rejects_absolute_form_with_mismatched_host() {
+        for bytes in [
+            &b"GET http://example.com/ HTTP/1.1\r\nHost: localhost\r\n\r\n"[..],
+            b"GET http://localhost@example.com/ HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            b"GET http://localhost/ HTTP/1.1\r\n\r\n",
+            b"GET ftp://localhost/ HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        ] {
+            let res = Request::from_bytes(bytes);
+            assert!(matches!(res, Err(Error::Validation(Status::BadRequest))));
+        }
+    }
+
+    #[test]
+    fn rejects_traversal_in_absolute_form() {
+        let res = Request::from_bytes(
+            b"GET http://localhost/../etc HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        );
+        assert!(matches!(res, Err(Error::Validation(Status::BadRequest))));
+    }
+}
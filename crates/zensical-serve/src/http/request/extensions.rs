@@ -0,0 +1,161 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! HTTP request extensions.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// HTTP request extensions.
+///
+/// Extensions allow middlewares to attach arbitrary data to a [`Request`][],
+/// which is passed on to inner middlewares and handlers. Values are keyed by
+/// their type, so there can be at most one value of each type. Values are
+/// reference counted, which means that cloned requests share them.
+///
+/// [`Request`]: crate::http::Request
+///
+/// # Examples
+///
+/// ```
+/// use zensical_serve::http::request::Extensions;
+///
+/// // Create extensions and add value
+/// let mut extensions = Extensions::new();
+/// extensions.insert(42_u32);
+/// assert_eq!(extensions.get::<u32>(), Some(&42));
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions {
+    /// Values keyed by type.
+    inner: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Extensions {
+    /// Creates extensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::request::Extensions;
+    ///
+    /// // Create extensions
+    /// let extensions = Extensions::new();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a reference to the value of the given type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::request::Extensions;
+    ///
+    /// // Create extensions and add value
+    /// let mut extensions = Extensions::new();
+    /// extensions.insert(String::from("value"));
+    ///
+    /// // Obtain reference to value
+    /// let value = extensions.get::<String>();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Any + Send + Sync,
+    {
+        let value = self.inner.get(&TypeId::of::<T>())?;
+        value.downcast_ref()
+    }
+
+    /// Inserts the given value, replacing the value of the same type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::request::Extensions;
+    ///
+    /// // Create extensions and add value
+    /// let mut extensions = Extensions::new();
+    /// extensions.insert(42_u32);
+    /// ```
+    #[inline]
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: Any + Send + Sync,
+    {
+        self.inner.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// Removes the value of the given type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::request::Extensions;
+    ///
+    /// // Create extensions and add value
+    /// let mut extensions = Extensions::new();
+    /// extensions.insert(42_u32);
+    ///
+    /// // Remove value
+    /// extensions.remove::<u32>();
+    /// assert!(extensions.get::<u32>().is_none());
+    /// ```
+    #[inline]
+    pub fn remove<T>(&mut self)
+    where
+        T: Any + Send + Sync,
+    {
+        self.inner.remove(&TypeId::of::<T>());
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for Extensions {
+    /// Formats the extensions for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
@@ -26,6 +26,7 @@
 //! HTTP request headers.
 
 use std::borrow::Cow;
+use std::collections::btree_map::Iter;
 use std::collections::BTreeMap;
 use std::fmt;
 
@@ -170,6 +171,28 @@ impl<'a> Headers<'a> {
     pub fn remove(&mut self, header: Header) {
         self.inner.remove(&header);
     }
+
+    /// Returns an iterator over the header map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::request::Headers;
+    /// use zensical_serve::http::Header;
+    ///
+    /// // Create header map and add header
+    /// let mut headers = Headers::new();
+    /// headers.insert(Header::Accept, "text/plain");
+    ///
+    /// // Iterate over header map
+    /// for (header, value) in headers.iter() {
+    ///    println!("{header}: {value}");
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, Header, Cow<'a, str>> {
+        self.inner.iter()
+    }
 }
 
 #[allow(clippy::must_use_candidate)]
@@ -220,6 +243,35 @@ impl<'a> FromIterator<(Header, &'a str)> for Headers<'a> {
 
 // ----------------------------------------------------------------------------
 
+impl<'a, 'b> IntoIterator for &'b Headers<'a> {
+    type Item = (&'b Header, &'b Cow<'a, str>);
+    type IntoIter = Iter<'b, Header, Cow<'a, str>>;
+
+    /// Creates an iterator over the header map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::request::Headers;
+    /// use zensical_serve::http::Header;
+    ///
+    /// // Create header map and add header
+    /// let mut headers = Headers::new();
+    /// headers.insert(Header::Accept, "text/plain");
+    ///
+    /// // Iterate over header map
+    /// for (header, value) in &headers {
+    ///    println!("{header}: {value}");
+    /// }
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 impl fmt::Display for Headers<'_> {
     /// Formats the header map for display.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -32,7 +32,7 @@ mod encoding;
 mod query;
 
 use encoding::{decode, encode};
-pub use query::Query;
+pub use query::{Error as QueryError, Query};
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -46,7 +46,11 @@ pub use query::Query;
 /// executable, and increase churn of dependencies for no immediate upside.
 ///
 /// For now, we just assume that paths always start with a `/`, which is sane
-/// to assume for a local web server that is not intended for proxying.
+/// to assume for a local web server that is not intended for proxying. The
+/// only exception is `*` for server-wide `OPTIONS` requests, as absolute-form
+/// targets are normalized into origin-form by [`Request::from_bytes`][].
+///
+/// [`Request::from_bytes`]: crate::http::Request::from_bytes
 ///
 /// [`url`]: https://crates.io/crates/url
 #[derive(Clone, Debug, PartialEq, Eq)]
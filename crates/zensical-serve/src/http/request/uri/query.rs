@@ -25,12 +25,17 @@
 
 //! HTTP query string.
 
+use serde::Deserialize;
 use std::borrow::Cow;
 use std::{fmt, iter, str};
 
+mod de;
 mod encoding;
+mod error;
 
+use de::Deserializer;
 use encoding::{decode, encode};
+pub use error::{Error, Result};
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -46,15 +51,19 @@ use encoding::{decode, encode};
 /// When parsing a query string with [`Query::from`], the keys and values will
 /// be percent-decoded and stored decoded in a parameter list, as query strings
 /// might have multiple values for the same key, and ordering always needs to
-/// be preserved when formatting with [`fmt::Display`]. Note that only those
+/// be preserved when formatting with [`fmt::Display`]. The original string
+/// is retained and printed verbatim, as signed URLs might depend on the exact
+/// percent-encoding, until parameters are added or removed. Only then, those
 /// characters for which percent-encoding is required will be percent-encoded
 /// when printing the query string.
 ///
 /// [`Request`]: crate::http::Request
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default)]
 pub struct Query<'a> {
     /// List of parameters.
     inner: Vec<Param<'a>>,
+    /// Raw query string, unless modified.
+    raw: Option<Cow<'a, str>>,
 }
 
 /// HTTP query string parameter.
@@ -164,6 +173,77 @@ impl<'a> Query<'a> {
         self.inner.iter().any(|param| param.key == key.as_ref())
     }
 
+    /// Deserializes the parameters into the given type.
+    ///
+    /// Parameters are deserialized as a map, so structs can be used to define
+    /// the parameters that are expected. Repeated keys can be deserialized into
+    /// sequences like [`Vec`], and keys without values into booleans, which
+    /// are `true` if present. Strings can be borrowed from the query string.
+    ///
+    /// # Errors
+    ///
+    /// If a parameter is missing or has an invalid value, an [`Error`] naming
+    /// the key is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use serde::Deserialize;
+    /// use zensical_serve::http::Query;
+    ///
+    /// // Define parameters
+    /// #[derive(Deserialize)]
+    /// struct Filter<'a> {
+    ///     page: u32,
+    ///     #[serde(borrow)]
+    ///     tags: Vec<&'a str>,
+    /// }
+    ///
+    /// // Create query string and deserialize parameters
+    /// let query = Query::from("page=2&tags=a&tags=b");
+    /// let filter: Filter = query.deserialize()?;
+    /// assert_eq!(filter.page, 2);
+    /// assert_eq!(filter.tags, ["a", "b"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deserialize<'de, T>(&'de self) -> Result<T>
+    where
+        T: Deserialize<'de>,
+    {
+        let iter = self
+            .inner
+            .iter()
+            .map(|param| (param.key.as_ref(), param.value.as_ref()));
+        T::deserialize(Deserializer::new(iter))
+    }
+
+    /// Returns the raw query string, if it wasn't modified.
+    ///
+    /// This is the query string exactly as it was parsed with [`Query::from`],
+    /// which is discarded once parameters are added or removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::Query;
+    ///
+    /// // Create query string from string
+    /// let mut query = Query::from("key=a+b%2fc");
+    /// assert_eq!(query.raw(), Some("key=a+b%2fc"));
+    ///
+    /// // Add parameter, which discards the raw query string
+    /// query.add("other", "value");
+    /// assert_eq!(query.raw(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn raw(&self) -> Option<&str> {
+        self.raw.as_deref()
+    }
+
     /// Adds the given key-value pair as a parameter.
     ///
     /// # Examples
@@ -180,6 +260,7 @@ impl<'a> Query<'a> {
         K: Into<Cow<'a, str>>,
         V: Into<Cow<'a, str>>,
     {
+        self.raw = None;
         self.inner.push(Param {
             key: key.into(),
             value: value.into(),
@@ -204,7 +285,11 @@ impl<'a> Query<'a> {
     where
         K: AsRef<str>,
     {
+        let len = self.inner.len();
         self.inner.retain(|param| param.key != key.as_ref());
+        if self.inner.len() != len {
+            self.raw = None;
+        }
     }
 }
 
@@ -233,7 +318,8 @@ impl<'a> From<&'a str> for Query<'a> {
     /// The query string is parsed from the given string, which is expected to
     /// be in the format of a query string, i.e., a sequence of key-value pairs
     /// connected with `&`, but with the initial `?` separator removed. Both
-    /// keys and values are percent-decoded and stored.
+    /// keys and values are percent-decoded and stored, alongside the string,
+    /// which is retained for printing the query string verbatim.
     ///
     /// Note that we can't implement [`FromStr`][] for [`Query`] because of the
     /// required `&'a str` lifetime, which is not compatible with the trait.
@@ -273,20 +359,22 @@ impl<'a> From<&'a str> for Query<'a> {
                 }
 
                 // If the current character is a `&` separator, we consumed a
-                // key-value pair, or just a key, both of which might be empty
-                '&' if start != i.saturating_sub(1) => {
-                    if index < pairs.len() && pairs[index].1.is_empty() {
+                // key-value pair, or just a key - the value might be empty,
+                // but empty segments, e.g., from `&&`, are skipped entirely
+                '&' => {
+                    if index < pairs.len() {
                         pairs[index].1 = decode(&value[start..i]);
-                    } else {
+                        index += 1;
+                    } else if start != i {
                         pairs.push((
                             decode(&value[start..i]),
                             Cow::Borrowed(""),
                         ));
+                        index += 1;
                     }
 
                     // Continue after separator
                     start = i + 1;
-                    index += 1;
                 }
 
                 // Consume all other characters
@@ -294,8 +382,10 @@ impl<'a> From<&'a str> for Query<'a> {
             }
         }
 
-        // Create query string from key-value pairs
-        Query::from_iter(pairs)
+        // Create query string from key-value pairs, and retain raw string
+        let mut query = Query::from_iter(pairs);
+        query.raw = Some(Cow::Borrowed(value));
+        query
     }
 }
 
@@ -336,6 +426,11 @@ where
 impl fmt::Display for Query<'_> {
     /// Formats the query string for display.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(raw) = &self.raw {
+            return f.write_str(raw);
+        }
+
+        // Write parameters, percent-encoding them where necessary
         for (i, param) in self.inner.iter().enumerate() {
             if i > 0 {
                 f.write_str("&")?;
@@ -353,3 +448,82 @@ impl fmt::Display for Query<'_> {
         Ok(())
     }
 }
+
+// ----------------------------------------------------------------------------
+
+impl PartialEq for Query<'_> {
+    /// Compares two query strings by their parameters.
+    ///
+    /// The raw query string is ignored, so query strings that only differ in
+    /// percent-encoding, e.g., `q=a%20b` and `q=a+b`, are considered equal.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for Query<'_> {}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::Query;
+    use crate::http::Uri;
+
+    #[test]
+    fn parses_single_character_keys_and_values() {
+        let query = Query::from("a=1&b&c=2");
+        assert_eq!(query.len(), 3);
+        assert_eq!(query.get("a"), Some("1"));
+        assert_eq!(query.get("b"), Some(""));
+        assert_eq!(query.get("c"), Some("2"));
+    }
+
+    #[test]
+    fn skips_empty_segments() {
+        let query = Query::from("&a=&&b=value&");
+        assert_eq!(query.len(), 2);
+        assert_eq!(query.get("a"), Some(""));
+        assert_eq!(query.get("b"), Some("value"));
+    }
+
+    #[test]
+    fn preserves_raw_form() {
+        for value in [
+            "q=a+b&r=a%20b",
+            "sig=%2F%2b%3d&exp=1",
+            "url=https%3A%2F%2Fexample.com%2F%3Fa%3D1%26b%3D2",
+        ] {
+            let query = Query::from(value);
+            assert_eq!(query.raw(), Some(value));
+            assert_eq!(query.to_string(), value);
+        }
+    }
+
+    #[test]
+    fn preserves_raw_form_in_uri() {
+        let uri = Uri::from("/path?sig=ab%2Fcd%2bef&q=a+b");
+        assert_eq!(uri.query.get("sig"), Some("ab/cd+ef"));
+        assert_eq!(uri.to_string(), "/path?sig=ab%2Fcd%2bef&q=a+b");
+    }
+
+    #[test]
+    fn encodes_after_modification() {
+        let mut query = Query::from("a=x%2fy");
+        query.remove("b");
+        assert_eq!(query.to_string(), "a=x%2fy");
+        query.add("b", "1");
+        assert_eq!(query.raw(), None);
+        assert_eq!(query.to_string(), "a=x/y&b=1");
+    }
+
+    #[test]
+    fn compares_parameters_only() {
+        assert_eq!(Query::from("q=a%20b"), Query::from("q=a+b"));
+        assert_eq!(Query::from("q=a+b"), Query::from_iter([("q", "a b")]));
+        assert_ne!(Query::from("a=1&b=2"), Query::from("b=2&a=1"));
+    }
+}
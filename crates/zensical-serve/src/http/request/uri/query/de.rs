@@ -0,0 +1,448 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Query string deserializer.
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use std::str::FromStr;
+use std::vec;
+
+use super::error::{Error, Result};
+
+// ----------------------------------------------------------------------------
+// Macros
+// ----------------------------------------------------------------------------
+
+/// Implements deserialization of numbers and characters from values.
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident($ty:ty, $expected:literal);)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse::<$ty>($expected)?)
+            }
+        )*
+    };
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Query string deserializer.
+///
+/// Parameters are grouped by key in the order of their first appearance, and
+/// deserialized as a map, so structs can be deserialized from query strings.
+pub struct Deserializer<'de> {
+    /// Parameters grouped by key.
+    params: Vec<(&'de str, Vec<&'de str>)>,
+}
+
+/// Query string parameter access.
+struct Params<'de> {
+    /// Iterator over parameters.
+    iter: vec::IntoIter<(&'de str, Vec<&'de str>)>,
+    /// Current parameter, if any.
+    param: Option<(&'de str, Vec<&'de str>)>,
+}
+
+/// Query string parameter value deserializer.
+///
+/// Each value holds all values of a parameter, so it can be deserialized into
+/// a sequence, or, if a single value is expected, from the first value. Note
+/// that an empty value means that the parameter is present without a value,
+/// which is deserialized as `true` for booleans.
+struct Value<'de> {
+    /// Parameter values.
+    values: Vec<&'de str>,
+}
+
+/// Query string parameter value access.
+struct Values<'de> {
+    /// Iterator over values.
+    iter: vec::IntoIter<&'de str>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<'de> Deserializer<'de> {
+    /// Creates a query string deserializer from the given parameters.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (&'de str, &'de str)>,
+    {
+        let mut params: Vec<(&str, Vec<&str>)> = Vec::new();
+        for (key, value) in iter {
+            match params.iter_mut().find(|(other, _)| *other == key) {
+                Some((_, values)) => values.push(value),
+                None => params.push((key, vec![value])),
+            }
+        }
+        Self { params }
+    }
+}
+
+impl<'de> Value<'de> {
+    /// Returns the first value.
+    fn first(&self) -> &'de str {
+        self.values.first().copied().unwrap_or_default()
+    }
+
+    /// Parses the first value, naming the expected type on failure.
+    fn parse<T>(&self, expected: &str) -> Result<T>
+    where
+        T: FromStr,
+    {
+        let value = self.first();
+        value.parse().map_err(|_| {
+            Error::Custom(format!("expected {expected}, found '{value}'"))
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    /// Deserializes the parameters as a map.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Params {
+            iter: self.params.into_iter(),
+            param: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for Params<'de> {
+    type Error = Error;
+
+    /// Deserializes the next parameter key.
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some((key, values)) = self.iter.next() else {
+            return Ok(None);
+        };
+        self.param = Some((key, values));
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    /// Deserializes the values of the current parameter.
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, values) = self.param.take().expect("invariant");
+        seed.deserialize(Value { values })
+            .map_err(|err| err.with_key(key))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value<'de> {
+    type Error = Error;
+
+    /// Deserializes a single value as a string, and multiple as a sequence.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.values.len() > 1 {
+            self.deserialize_seq(visitor)
+        } else {
+            visitor.visit_borrowed_str(self.first())
+        }
+    }
+
+    /// Deserializes a boolean, which is `true` if the value is empty.
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.first() {
+            "" | "true" | "1" | "on" | "yes" => visitor.visit_bool(true),
+            "false" | "0" | "off" | "no" => visitor.visit_bool(false),
+            value => {
+                Err(Error::Custom(format!("expected boolean, found '{value}'")))
+            }
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8(i8, "integer");
+        deserialize_i16 => visit_i16(i16, "integer");
+        deserialize_i32 => visit_i32(i32, "integer");
+        deserialize_i64 => visit_i64(i64, "integer");
+        deserialize_i128 => visit_i128(i128, "integer");
+        deserialize_u8 => visit_u8(u8, "unsigned integer");
+        deserialize_u16 => visit_u16(u16, "unsigned integer");
+        deserialize_u32 => visit_u32(u32, "unsigned integer");
+        deserialize_u64 => visit_u64(u64, "unsigned integer");
+        deserialize_u128 => visit_u128(u128, "unsigned integer");
+        deserialize_f32 => visit_f32(f32, "number");
+        deserialize_f64 => visit_f64(f64, "number");
+        deserialize_char => visit_char(char, "character");
+    }
+
+    /// Deserializes the first value as a borrowed string.
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.first())
+    }
+
+    /// Deserializes the first value as a string.
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    /// Deserializes the first value as borrowed bytes.
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.first().as_bytes())
+    }
+
+    /// Deserializes the first value as bytes.
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    /// Deserializes an option, which is always present for parameters.
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    /// Deserializes a unit.
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    /// Deserializes a unit struct.
+    fn deserialize_unit_struct<V>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    /// Deserializes a newtype struct.
+    fn deserialize_newtype_struct<V>(
+        self, _name: &'static str, visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Deserializes all values as a sequence.
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Values { iter: self.values.into_iter() })
+    }
+
+    /// Deserializes all values as a tuple.
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    /// Deserializes all values as a tuple struct.
+    fn deserialize_tuple_struct<V>(
+        self, _name: &'static str, _len: usize, visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    /// Deserializes the first value as a unit variant.
+    fn deserialize_enum<V>(
+        self, _name: &'static str, _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(BorrowedStrDeserializer::new(self.first()))
+    }
+
+    /// Ignores the values.
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        map struct identifier
+    }
+}
+
+impl<'de> SeqAccess<'de> for Values<'de> {
+    type Error = Error;
+
+    /// Deserializes the next value.
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.iter
+            .next()
+            .map(|value| seed.deserialize(Value { values: vec![value] }))
+            .transpose()
+    }
+
+    /// Returns the number of remaining values.
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::http::Query;
+
+    use super::Error;
+
+    /// Filter parameters.
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Filter<'a> {
+        /// Page number.
+        page: u32,
+        /// Search term.
+        #[serde(borrow)]
+        search: Option<&'a str>,
+        /// Tags.
+        #[serde(default)]
+        tags: Vec<String>,
+        /// Whether to include drafts.
+        #[serde(default)]
+        drafts: bool,
+    }
+
+    #[test]
+    fn deserializes_optional_repeated_and_numeric_fields() {
+        let query = Query::from("page=2&tags=a&drafts&tags=b");
+        let filter: Filter = query.deserialize().unwrap();
+        assert_eq!(
+            filter,
+            Filter {
+                page: 2,
+                search: None,
+                tags: vec!["a".into(), "b".into()],
+                drafts: true,
+            }
+        );
+    }
+
+    #[test]
+    fn borrows_strings_from_query() {
+        let query = Query::from("page=1&search=coffee&drafts=off");
+        let filter: Filter = query.deserialize().unwrap();
+        assert_eq!(filter.search, Some("coffee"));
+        assert!(!filter.drafts);
+    }
+
+    #[test]
+    fn fails_on_invalid_number() {
+        let query = Query::from("page=two");
+        let err = query.deserialize::<Filter>().unwrap_err();
+        assert_eq!(
+            err,
+            Error::Parameter {
+                key: "page".into(),
+                message: "expected unsigned integer, found 'two'".into(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid query parameter 'page': expected unsigned integer, \
+             found 'two'"
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_boolean() {
+        let query = Query::from("page=1&drafts=maybe");
+        let err = query.deserialize::<Filter>().unwrap_err();
+        assert!(matches!(err, Error::Parameter { key, .. } if key == "drafts"));
+    }
+
+    #[test]
+    fn fails_on_missing_field() {
+        let query = Query::from("tags=a");
+        let err = query.deserialize::<Filter>().unwrap_err();
+        assert_eq!(err, Error::Custom("missing field `page`".into()));
+    }
+}
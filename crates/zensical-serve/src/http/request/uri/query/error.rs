@@ -0,0 +1,91 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Query string error.
+
+use serde::de;
+use std::fmt;
+use std::result;
+use thiserror::Error;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Query string error.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    /// Invalid parameter.
+    #[error("invalid query parameter '{key}': {message}")]
+    Parameter {
+        /// Parameter key.
+        key: String,
+        /// Error message.
+        message: String,
+    },
+
+    /// Custom error.
+    #[error("invalid query string: {0}")]
+    Custom(String),
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Error {
+    /// Associates the error with the given parameter key, if it has none.
+    #[must_use]
+    pub(crate) fn with_key(self, key: &str) -> Self {
+        match self {
+            Error::Custom(message) => {
+                Error::Parameter { key: key.to_string(), message }
+            }
+            err @ Error::Parameter { .. } => err,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl de::Error for Error {
+    /// Creates a custom error.
+    #[inline]
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::Custom(msg.to_string())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Query string result.
+pub type Result<T = ()> = result::Result<T, Error>;
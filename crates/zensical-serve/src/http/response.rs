@@ -69,6 +69,12 @@ pub struct Response {
     pub headers: Headers,
     /// Response body.
     pub body: Vec<u8>,
+    /// Matched route pattern, if the response was created by a route.
+    ///
+    /// Routers record the pattern of the matched route, e.g., `/items/{id}`,
+    /// so outer middlewares can group requests by route instead of by path,
+    /// which is essential for logging and metrics. It's not sent to clients.
+    pub route: Option<String>,
 }
 
 // ----------------------------------------------------------------------------
@@ -235,6 +241,7 @@ impl Default for Response {
             status: Status::Ok,
             headers: Headers::default(),
             body: Vec::default(),
+            route: None,
         }
     }
 }
@@ -32,12 +32,18 @@ use super::http::{Request, Response};
 
 mod convert;
 mod files;
+mod inject;
+mod options;
 mod path;
+mod timing;
 mod websocket;
 
 pub use convert::TryIntoMiddleware;
-pub use files::StaticFiles;
+pub use files::{Lookup, StaticFiles};
+pub use inject::InjectScript;
+pub use options::ServerOptions;
 pub use path::{BasePath, NormalizePath, TrailingSlash};
+pub use timing::{ServerTiming, Timing};
 pub use websocket::WebSocketHandshake;
 
 // ----------------------------------------------------------------------------
@@ -28,14 +28,45 @@
 use httpdate::parse_http_date;
 use std::fs;
 use std::io::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use crate::handler::Handler;
 use crate::http::response::ResponseExt;
-use crate::http::{Header, Method, Request, Response, Status};
+use crate::http::{Header, Method, Request, Response, Status, Uri};
 use crate::middleware::Middleware;
 
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Lookup of files for request paths without extension and trailing slash.
+///
+/// If no file exists at the exact path, the lookups configured on the static
+/// files middleware are tried in order, e.g., to serve `/changelog` from
+/// `changelog.html`, as most static hosts do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lookup {
+    /// Serve `<path>.html`.
+    Html,
+    /// Redirect to `<path>/`, if `<path>/index.html` exists.
+    ///
+    /// The file is not served directly, since relative links in the document
+    /// would be resolved against the parent directory otherwise. This is the
+    /// same redirect that [`NormalizePath`] sends for all paths, but only if
+    /// the directory index exists, so both can't redirect back and forth.
+    ///
+    /// [`NormalizePath`]: crate::middleware::NormalizePath
+    Index,
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Cache control policy, mapping request path and content type to a value.
+type Policy = Box<dyn Fn(&str, &str) -> Option<String>>;
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
@@ -49,6 +80,10 @@ use crate::middleware::Middleware;
 pub struct StaticFiles {
     /// Base path.
     base: PathBuf,
+    /// Lookups for paths without extension and trailing slash.
+    lookup: Vec<Lookup>,
+    /// Cache control policy, if any.
+    cache_control: Option<Policy>,
 }
 
 // ----------------------------------------------------------------------------
@@ -62,7 +97,91 @@ impl StaticFiles {
         P: Into<PathBuf>,
     {
         let path = path.into();
-        path.canonicalize().map(|base| Self { base })
+        path.canonicalize().map(|base| Self {
+            base,
+            lookup: Vec::new(),
+            cache_control: None,
+        })
+    }
+
+    /// Sets the lookups for paths without extension and trailing slash.
+    ///
+    /// By default, files are only served from their exact path, and paths with
+    /// a trailing slash from the `index.html` file in the directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::middleware::{Lookup, StaticFiles};
+    ///
+    /// // Create middleware, trying `.html` files and directory indexes
+    /// let middleware = StaticFiles::new(".")?
+    ///     .with_lookup([Lookup::Html, Lookup::Index]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_lookup<I>(mut self, lookup: I) -> Self
+    where
+        I: IntoIterator<Item = Lookup>,
+    {
+        self.lookup = lookup.into_iter().collect();
+        self
+    }
+
+    /// Sets the cache control policy.
+    ///
+    /// The policy is called with the request path and the content type of the
+    /// file, and returns the value of the `Cache-Control` header, if any. It's
+    /// applied to files and to `304 Not Modified` responses, so revalidation
+    /// keeps the same policy. By default, no `Cache-Control` header is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::middleware::StaticFiles;
+    ///
+    /// // Create middleware, requiring revalidation of all files
+    /// let middleware = StaticFiles::new(".")?
+    ///     .with_cache_control(|_, _| Some(String::from("no-cache")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_cache_control<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, &str) -> Option<String> + 'static,
+    {
+        self.cache_control = Some(Box::new(f));
+        self
+    }
+
+    /// Resolves the configured lookups for the given request path.
+    ///
+    /// Returns the first lookup that matches, together with the file it found.
+    /// Paths with an extension or trailing slash are never resolved, as they
+    /// must match exactly.
+    fn resolve(&self, path: &str) -> Option<(Lookup, PathBuf)> {
+        let path = path.trim_start_matches('/');
+        if path.is_empty()
+            || path.ends_with('/')
+            || Path::new(path).extension().is_some()
+        {
+            return None;
+        }
+
+        // Try lookups in order, and return the first match
+        self.lookup.iter().find_map(|&lookup| {
+            let full = match lookup {
+                Lookup::Html => self.base.join(format!("{path}.html")),
+                Lookup::Index => self.base.join(path).join("index.html"),
+            };
+            full.is_file().then_some((lookup, full))
+        })
     }
 
     /// Handle fallback cases (file not found, wrong method, etc.)
@@ -101,6 +220,22 @@ impl Middleware for StaticFiles {
             full.push("index.html");
         }
 
+        // If the file doesn't exist, try the configured lookups - directory
+        // indexes are redirected to relative to the request path, as it might
+        // have been stripped of a base path by preceding middlewares
+        if !full.is_file() {
+            match self.resolve(&req.uri.path) {
+                Some((Lookup::Html, path)) => full = path,
+                Some((Lookup::Index, _)) => {
+                    let name = req.uri.path.rsplit('/').next();
+                    let path = format!("{}/", name.unwrap_or_default());
+                    let uri = Uri::from_parts(path, req.uri.query);
+                    return Response::redirect(uri);
+                }
+                None => {}
+            }
+        }
+
         // Attempt to load file, or delegate to fallback
         let Ok(mut res) = Response::from_file(&full) else {
             return self.fallback(req, next);
@@ -110,6 +245,16 @@ impl Middleware for StaticFiles {
         res.headers
             .insert(Header::Date, httpdate::fmt_http_date(SystemTime::now()));
 
+        // Apply cache control policy, if any, which is also sent along with
+        // `304 Not Modified` responses, as they must carry the same headers
+        let cache_control = self.cache_control.as_ref().and_then(|policy| {
+            let mime = res.headers.get(Header::ContentType).unwrap_or_default();
+            policy(&req.uri.path, mime)
+        });
+        if let Some(value) = &cache_control {
+            res.headers.insert(Header::CacheControl, value);
+        }
+
         // In case we received a head request, remove body - we should rather
         // make this more granular by just checking for the file
         if req.method == Method::Head {
@@ -129,9 +274,13 @@ impl Middleware for StaticFiles {
                 // Subtract one second to account for rounding issues
                 last -= Duration::from_secs(1);
                 if date >= last {
-                    return Response::new()
+                    let mut res = Response::new()
                         .status(Status::NotModified)
                         .header(Header::ContentLength, 0);
+                    if let Some(value) = cache_control {
+                        res.headers.insert(Header::CacheControl, value);
+                    }
+                    return res;
                 }
             }
         }
@@ -140,3 +289,123 @@ impl Middleware for StaticFiles {
         res
     }
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use crate::handler::{Handler, Stack, TryIntoHandler};
+    use crate::http::{Header, Method, Request, Response, Status};
+    use crate::middleware::NormalizePath;
+
+    use super::{Lookup, StaticFiles};
+
+    /// Returns the status, and the location of redirects or the body of files.
+    fn summary(res: &Response) -> (Status, String) {
+        let text = match res.status {
+            Status::Ok => String::from_utf8_lossy(&res.body).into_owned(),
+            Status::Found => res.headers.get(Header::Location).unwrap().into(),
+            _ => String::new(),
+        };
+        (res.status, text)
+    }
+
+    #[test]
+    fn lookup_matches_url_mode() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("guide")).unwrap();
+        for (path, content) in [
+            ("index.html", "home"),
+            ("changelog.html", "changelog"),
+            ("guide/index.html", "guide"),
+        ] {
+            fs::write(dir.path().join(path), content).unwrap();
+        }
+
+        // Directory URLs append a trailing slash to all paths, while file URLs
+        // try the `.html` suffix, and only redirect to existing directories
+        let directory = Stack::new()
+            .with(NormalizePath::default())
+            .with(StaticFiles::new(dir.path()).unwrap())
+            .try_into_handler()
+            .unwrap();
+        let file = Stack::new()
+            .with(
+                StaticFiles::new(dir.path())
+                    .unwrap()
+                    .with_lookup([Lookup::Html, Lookup::Index]),
+            )
+            .try_into_handler()
+            .unwrap();
+
+        // Request path, and expected responses with directory and file URLs
+        let ok = Status::Ok;
+        let found = Status::Found;
+        let missing = Status::NotFound;
+        let fixtures = [
+            ("/", (ok, "home"), (ok, "home")),
+            ("/changelog", (found, "/changelog/"), (ok, "changelog")),
+            ("/changelog.html", (ok, "changelog"), (ok, "changelog")),
+            ("/changelog/", (missing, ""), (missing, "")),
+            ("/guide", (found, "/guide/"), (found, "guide/")),
+            ("/guide/", (ok, "guide"), (ok, "guide")),
+            ("/missing", (found, "/missing/"), (missing, "")),
+            ("/missing/", (missing, ""), (missing, "")),
+        ];
+        for (path, in_directory, in_file) in fixtures {
+            for (handler, (status, text)) in
+                [(&directory, in_directory), (&file, in_file)]
+            {
+                let req = Request::new().method(Method::Get).uri(path);
+                let res = handler.handle(req);
+                assert_eq!(summary(&res), (status, text.to_string()), "{path}");
+            }
+        }
+    }
+
+    #[test]
+    fn applies_cache_control_policy() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("index.html"), "home").unwrap();
+        fs::write(dir.path().join("style.css"), "body {}").unwrap();
+
+        // Cache stylesheets, but require revalidation of everything else
+        let handler = Stack::new()
+            .with(StaticFiles::new(dir.path()).unwrap().with_cache_control(
+                |path, mime| {
+                    assert!(path.starts_with('/'));
+                    let value = if mime.starts_with("text/css") {
+                        "max-age=3600"
+                    } else {
+                        "no-cache"
+                    };
+                    Some(value.to_string())
+                },
+            ))
+            .try_into_handler()
+            .unwrap();
+
+        // Both files and revalidated files carry the header
+        let cases = [("/", "no-cache"), ("/style.css", "max-age=3600")];
+        for (path, expected) in cases {
+            let req = Request::new().method(Method::Get).uri(path);
+            let res = handler.handle(req);
+            assert_eq!(res.headers.get(Header::CacheControl), Some(expected));
+
+            // Revalidate with the date of the last modification
+            let date = res.headers.get(Header::LastModified).unwrap();
+            let req = Request::new()
+                .method(Method::Get)
+                .uri(path)
+                .header(Header::IfModifiedSince, date);
+            let res = handler.handle(req);
+            assert_eq!(res.status, Status::NotModified);
+            assert_eq!(res.headers.get(Header::CacheControl), Some(expected));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Middleware for injecting scripts into HTML responses.
+
+use crate::handler::Handler;
+use crate::http::{Header, Request, Response, Status};
+
+use super::Middleware;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Middleware for injecting scripts into HTML responses.
+///
+/// This middleware inserts a `<script>` element right before the closing
+/// `</body>` tag of HTML responses, or appends it if there's none. The script
+/// is created on every injection, so it can depend on state that is only
+/// known after the middleware was created, e.g., the address of the server.
+/// Responses that already contain the marker, which is used as the `id` of
+/// the script element, as well as non-HTML and partial responses are left
+/// untouched, and the `Content-Length` header is corrected after injection.
+///
+/// # Examples
+///
+/// ```
+/// use zensical_serve::middleware::InjectScript;
+///
+/// // Create middleware
+/// let middleware = InjectScript::new("reload", || {
+///     String::from("console.log(\"Hello\")")
+/// });
+/// ```
+pub struct InjectScript<F>
+where
+    F: Fn() -> String + 'static,
+{
+    /// Marker identifying the script.
+    marker: String,
+    /// Function creating the script.
+    script: F,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<F> InjectScript<F>
+where
+    F: Fn() -> String + 'static,
+{
+    /// Creates a middleware for injecting scripts into HTML responses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::middleware::InjectScript;
+    ///
+    /// // Create middleware
+    /// let middleware = InjectScript::new("reload", || {
+    ///     String::from("console.log(\"Hello\")")
+    /// });
+    /// ```
+    pub fn new<M>(marker: M, script: F) -> Self
+    where
+        M: Into<String>,
+    {
+        Self { marker: marker.into(), script }
+    }
+
+    /// Returns whether the script should be injected into the response.
+    fn accepts(&self, res: &Response) -> bool {
+        let is_html = res
+            .headers
+            .get(Header::ContentType)
+            .is_some_and(|value| value.contains("text/html"));
+
+        // Partial responses only carry a slice of the document, so injecting
+        // into them would corrupt the document when it's reassembled
+        is_html
+            && res.status != Status::PartialContent
+            && res.headers.get(Header::ContentRange).is_none()
+            && !contains(&res.body, self.marker.as_bytes())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<F> Middleware for InjectScript<F>
+where
+    F: Fn() -> String + 'static,
+{
+    /// Processes the given request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::{Header, Request, Response};
+    /// use zensical_serve::middleware::{InjectScript, Middleware};
+    ///
+    /// // Create middleware
+    /// let middleware = InjectScript::new("reload", || {
+    ///     String::from("console.log(\"Hello\")")
+    /// });
+    ///
+    /// // Handle request with middleware
+    /// let res = middleware.process(Request::new(), &|_: Request| {
+    ///     Response::new()
+    ///         .header(Header::ContentType, "text/html")
+    ///         .body("<body></body>")
+    /// });
+    /// assert_eq!(
+    ///     res.body,
+    ///     b"<body><script type=\"module\" id=\"reload\">\
+    ///         console.log(\"Hello\")</script></body>"
+    /// );
+    /// ```
+    fn process(&self, req: Request, next: &dyn Handler) -> Response {
+        let mut res = next.handle(req);
+        if !self.accepts(&res) {
+            return res;
+        }
+
+        // Create script element, and insert it right before the closing body
+        // tag, or append it to the end of the document if there's none
+        let script = format!(
+            "<script type=\"module\" id=\"{}\">{}</script>",
+            self.marker,
+            (self.script)()
+        );
+        let index = rfind_closing_body(&res.body).unwrap_or(res.body.len());
+        res.body.splice(index..index, script.into_bytes());
+
+        // Update content length
+        res.headers.insert(Header::ContentLength, res.body.len());
+        res
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns whether the haystack contains the given needle.
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty()
+        || haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+/// Returns the index of the last closing body tag, ignoring case.
+fn rfind_closing_body(body: &[u8]) -> Option<usize> {
+    const TAG: &[u8] = b"</body>";
+    body.windows(TAG.len())
+        .rposition(|window| window.eq_ignore_ascii_case(TAG))
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::http::{Request, Response};
+
+    fn middleware() -> InjectScript<impl Fn() -> String> {
+        InjectScript::new("__reload", || String::from("connect()"))
+    }
+
+    #[test]
+    fn injects_into_html_before_closing_body() {
+        let res = middleware().process(Request::new(), &|_: Request| {
+            Response::new()
+                .header(Header::ContentType, "text/html; charset=utf-8")
+                .header(Header::ContentLength, 26)
+                .body("<html><BODY>Hi</BODY></html>")
+        });
+
+        let body = String::from_utf8(res.body.clone()).expect("invariant");
+        assert_eq!(
+            body,
+            "<html><BODY>Hi<script type=\"module\" id=\"__reload\">\
+             connect()</script></BODY></html>"
+        );
+        assert_eq!(
+            res.headers.get(Header::ContentLength),
+            Some(body.len().to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn appends_to_html_without_closing_body() {
+        let res = middleware().process(Request::new(), &|_: Request| {
+            Response::new()
+                .header(Header::ContentType, "text/html")
+                .body("<p>Hi</p>")
+        });
+
+        assert_eq!(
+            res.body,
+            b"<p>Hi</p><script type=\"module\" id=\"__reload\">\
+              connect()</script>"
+        );
+    }
+
+    #[test]
+    fn skips_json() {
+        let res = middleware().process(Request::new(), &|_: Request| {
+            Response::new()
+                .header(Header::ContentType, "application/json")
+                .header(Header::ContentLength, 13)
+                .body("{\"body\":true}")
+        });
+
+        assert_eq!(res.body, b"{\"body\":true}");
+        assert_eq!(res.headers.get(Header::ContentLength), Some("13"));
+    }
+
+    #[test]
+    fn skips_html_containing_marker() {
+        let html = "<body><script id=\"__reload\"></script></body>";
+        let res = middleware().process(Request::new(), &|_: Request| {
+            Response::new()
+                .header(Header::ContentType, "text/html")
+                .body(html)
+        });
+
+        assert_eq!(res.body, html.as_bytes());
+    }
+
+    #[test]
+    fn skips_partial_content() {
+        let res = middleware().process(Request::new(), &|_: Request| {
+            Response::new()
+                .status(Status::PartialContent)
+                .header(Header::ContentType, "text/html")
+                .header(Header::ContentRange, "bytes 0-12/100")
+                .body("<body></body>")
+        });
+
+        assert_eq!(res.body, b"<body></body>");
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Middleware for server-wide `OPTIONS` requests.
+
+use crate::handler::Handler;
+use crate::http::response::ResponseExt;
+use crate::http::{Header, Method, Request, Response, Status};
+
+use super::Middleware;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Middleware for server-wide `OPTIONS` requests.
+///
+/// This middleware answers `OPTIONS *` requests, which some health checkers
+/// send to determine whether the server is alive, with the methods that the
+/// server supports in the `Allow` header. All other requests are forwarded to
+/// the next handler. It should be placed before middlewares that operate on
+/// paths, as the asterisk is not a path.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use zensical_serve::handler::{Handler, Stack, TryIntoHandler};
+/// use zensical_serve::http::{Header, Request, Status};
+/// use zensical_serve::middleware::ServerOptions;
+///
+/// // Create stack with middleware
+/// let stack = Stack::new()
+///     .with(ServerOptions::default())
+///     .try_into_handler()?;
+///
+/// // Create request
+/// let req = Request::from_bytes(b"OPTIONS * HTTP/1.1\r\n\r\n")?;
+///
+/// // Handle request with stack
+/// let res = stack.handle(req);
+/// assert_eq!(res.status, Status::NoContent);
+/// assert_eq!(res.headers.get(Header::Allow), Some("GET, HEAD, OPTIONS"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ServerOptions {
+    /// Methods supported by the server.
+    allow: String,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl ServerOptions {
+    /// Creates a middleware for server-wide `OPTIONS` requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::Method;
+    /// use zensical_serve::middleware::ServerOptions;
+    ///
+    /// // Create middleware
+    /// let middleware = ServerOptions::new([
+    ///     Method::Get,
+    ///     Method::Post,
+    ///     Method::Options,
+    /// ]);
+    /// ```
+    pub fn new<I>(methods: I) -> Self
+    where
+        I: IntoIterator<Item = Method>,
+    {
+        let iter = methods.into_iter().map(|method| method.name());
+        Self {
+            allow: iter.collect::<Vec<_>>().join(", "),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Middleware for ServerOptions {
+    /// Processes the given request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::handler::NotFound;
+    /// use zensical_serve::http::{Header, Request, Status};
+    /// use zensical_serve::middleware::{Middleware, ServerOptions};
+    ///
+    /// // Create middleware
+    /// let middleware = ServerOptions::default();
+    ///
+    /// // Create request
+    /// let req = Request::from_bytes(b"OPTIONS * HTTP/1.1\r\n\r\n")?;
+    ///
+    /// // Handle request with middleware
+    /// let res = middleware.process(req, &NotFound);
+    /// assert_eq!(res.status, Status::NoContent);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn process(&self, req: Request, next: &dyn Handler) -> Response {
+        if req.method != Method::Options || req.uri.path != "*" {
+            return next.handle(req);
+        }
+
+        // Return supported methods
+        Response::from_status(Status::NoContent)
+            .header(Header::Allow, &self.allow)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Default for ServerOptions {
+    /// Creates a middleware for server-wide `OPTIONS` requests.
+    ///
+    /// By default, only methods for reading resources are allowed, which is
+    /// what a static site server supports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::middleware::ServerOptions;
+    ///
+    /// // Create middleware
+    /// let middleware = ServerOptions::default();
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new([Method::Get, Method::Head, Method::Options])
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::handler::NotFound;
+
+    #[test]
+    fn answers_asterisk_form() {
+        let req = Request::from_bytes(b"OPTIONS * HTTP/1.1\r\n\r\n").unwrap();
+        let res = ServerOptions::new([Method::Get]).process(req, &NotFound);
+        assert_eq!(res.status, Status::NoContent);
+        assert_eq!(res.headers.get(Header::Allow), Some("GET"));
+    }
+
+    #[test]
+    fn forwards_origin_form() {
+        let req = Request::from_bytes(b"OPTIONS / HTTP/1.1\r\n\r\n").unwrap();
+        let res = ServerOptions::default().process(req, &NotFound);
+        assert_eq!(res.status, Status::NotFound);
+    }
+}
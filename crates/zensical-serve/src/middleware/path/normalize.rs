@@ -151,8 +151,12 @@ impl Middleware for NormalizePath {
         // check if it has an extension, regardless of which slashes are used in
         // file system paths. If it doesn't have an extension, it's either a
         // directory on the filesystem, or may point to a registered route.
+        // Server-wide OPTIONS requests use an asterisk, which is not a path.
         let path = Path::new(req.uri.path.as_ref());
-        if req.uri.path == "/" || path.extension().is_some() {
+        if req.uri.path == "/"
+            || req.uri.path == "*"
+            || path.extension().is_some()
+        {
             return next.handle(req);
         }
 
@@ -0,0 +1,374 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Middleware for measuring server timings.
+
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::handler::Handler;
+use crate::http::{Header, Request, Response};
+
+use super::Middleware;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Middleware for measuring server timings.
+///
+/// This middleware measures the duration of the inner handler, and appends it
+/// to the `Server-Timing` header of the response under the given metric name,
+/// so it shows up in the developer tools of the browser. If inner layers have
+/// already added entries, the timing is appended to them, which means that
+/// entries are ordered from the innermost to the outermost layer.
+///
+/// Handlers can add their own timings with [`ServerTiming::record`], which are
+/// collected via the request extensions, and added before the timing of the
+/// innermost middleware.
+///
+/// # Examples
+///
+/// ```
+/// use zensical_serve::middleware::ServerTiming;
+///
+/// // Create middleware
+/// let middleware = ServerTiming::new("total");
+/// ```
+#[derive(Debug)]
+pub struct ServerTiming {
+    /// Metric name.
+    name: String,
+    /// Metric description.
+    description: Option<String>,
+}
+
+/// Server timing.
+///
+/// Timings are formatted according to the `Server-Timing` header syntax, e.g.,
+/// `db;dur=12.3;desc="Query"`, where the duration is given in milliseconds.
+/// Note that the name must be a valid token, i.e., it must not contain any
+/// whitespace or delimiters, which is not validated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timing {
+    /// Metric name.
+    name: String,
+    /// Metric duration.
+    duration: Duration,
+    /// Metric description.
+    description: Option<String>,
+}
+
+/// Server timings recorded by handlers.
+///
+/// Timings are shared between all middlewares and handlers processing the
+/// same request via the request extensions.
+#[derive(Clone, Debug, Default)]
+struct Timings {
+    /// Recorded timings.
+    inner: Arc<Mutex<Vec<Timing>>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl ServerTiming {
+    /// Creates a middleware for measuring server timings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::middleware::ServerTiming;
+    ///
+    /// // Create middleware
+    /// let middleware = ServerTiming::new("total");
+    /// ```
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+        }
+    }
+
+    /// Sets the description of the metric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::middleware::ServerTiming;
+    ///
+    /// // Create middleware with description
+    /// let middleware = ServerTiming::new("total")
+    ///     .description("Total time");
+    /// ```
+    #[must_use]
+    pub fn description<D>(mut self, description: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Records the given timing for the given request.
+    ///
+    /// The timing is added to the `Server-Timing` header by the innermost
+    /// middleware, and is ignored if no middleware is processing the request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use zensical_serve::handler::Handler;
+    /// use zensical_serve::http::{Header, Request, Response};
+    /// use zensical_serve::middleware::{Middleware, ServerTiming, Timing};
+    ///
+    /// // Create middleware
+    /// let middleware = ServerTiming::new("total");
+    ///
+    /// // Handle request with middleware, recording a timing in the handler
+    /// let res = middleware.process(Request::new(), &|req: Request| {
+    ///     let start = Instant::now();
+    ///     ServerTiming::record(&req, Timing::new("db", start.elapsed()));
+    ///     Response::new()
+    /// });
+    ///
+    /// // Timing of handler is added before timing of middleware
+    /// let value = res.headers.get(Header::ServerTiming).unwrap();
+    /// assert!(value.starts_with("db;dur="));
+    /// ```
+    pub fn record(req: &Request, timing: Timing) {
+        if let Some(timings) = req.extensions.get::<Timings>() {
+            timings.add(timing);
+        }
+    }
+}
+
+impl Timing {
+    /// Creates a timing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zensical_serve::middleware::Timing;
+    ///
+    /// // Create timing
+    /// let timing = Timing::new("db", Duration::from_millis(12));
+    /// assert_eq!(timing.to_string(), "db;dur=12.0");
+    /// ```
+    pub fn new<N>(name: N, duration: Duration) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            duration,
+            description: None,
+        }
+    }
+
+    /// Sets the description of the timing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use zensical_serve::middleware::Timing;
+    ///
+    /// // Create timing with description
+    /// let timing = Timing::new("db", Duration::from_millis(12))
+    ///     .description("Query");
+    /// assert_eq!(timing.to_string(), "db;dur=12.0;desc=\"Query\"");
+    /// ```
+    #[must_use]
+    pub fn description<D>(mut self, description: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl Timings {
+    /// Adds a timing.
+    fn add(&self, timing: Timing) {
+        let mut inner =
+            self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.push(timing);
+    }
+
+    /// Takes all recorded timings.
+    fn take(&self) -> Vec<Timing> {
+        let mut inner =
+            self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        mem::take(&mut *inner)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Middleware for ServerTiming {
+    /// Processes the given request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::{Header, Request, Response};
+    /// use zensical_serve::middleware::{Middleware, ServerTiming};
+    ///
+    /// // Create middleware
+    /// let middleware = ServerTiming::new("total");
+    ///
+    /// // Handle request with middleware
+    /// let res = middleware.process(Request::new(), &|_: Request| {
+    ///     Response::new()
+    /// });
+    /// assert!(res.headers.contains(Header::ServerTiming));
+    /// ```
+    fn process(&self, mut req: Request, next: &dyn Handler) -> Response {
+        // Share timings with inner layers and handlers, reusing the timings of
+        // outer layers, so all of them are collected in the same place
+        let timings = req.extensions.get::<Timings>().cloned();
+        let timings = timings.unwrap_or_else(|| {
+            let timings = Timings::default();
+            req.extensions.insert(timings.clone());
+            timings
+        });
+
+        // Measure inner layers and handler
+        let start = Instant::now();
+        let mut res = next.handle(req);
+        let mut timing = Timing::new(self.name.clone(), start.elapsed());
+        timing.description.clone_from(&self.description);
+
+        // Append timings recorded by handlers, followed by our own timing, to
+        // the entries that inner layers already added, if any
+        let mut entries = timings.take();
+        entries.push(timing);
+        let value = entries
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = match res.headers.get(Header::ServerTiming) {
+            Some(prior) => format!("{prior}, {value}"),
+            None => value,
+        };
+        res.headers.insert(Header::ServerTiming, value);
+        res
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Timing {
+    /// Formats the timing for display.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self.duration.as_secs_f64() * 1000.0;
+        write!(f, "{};dur={millis:.1}", self.name)?;
+
+        // Descriptions are quoted strings, so quotes and backslashes must
+        // be escaped to produce a valid header value
+        if let Some(description) = &self.description {
+            f.write_str(";desc=\"")?;
+            for char in description.chars() {
+                if char == '"' || char == '\\' {
+                    f.write_str("\\")?;
+                }
+                write!(f, "{char}")?;
+            }
+            f.write_str("\"")?;
+        }
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::handler::{Stack, TryIntoHandler};
+
+    #[test]
+    fn appends_entries_of_stacked_instances_in_order() {
+        let handler = Stack::new()
+            .with(ServerTiming::new("outer").description("Outer"))
+            .with(ServerTiming::new("inner"))
+            .with(|req: Request, _: &dyn Handler| {
+                let timing = Timing::new("db", Duration::from_millis(5));
+                ServerTiming::record(&req, timing.description("Query"));
+                Response::new()
+            })
+            .try_into_handler()
+            .unwrap();
+
+        // Entries are ordered from the handler to the outermost layer
+        let res = handler.handle(Request::new());
+        let value = res.headers.get(Header::ServerTiming).unwrap();
+        let names = value
+            .split(", ")
+            .map(|entry| entry.split(';').next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["db", "inner", "outer"]);
+        assert!(value.starts_with("db;dur=5.0;desc=\"Query\", inner;dur="));
+        assert!(value.ends_with(";desc=\"Outer\""));
+    }
+
+    #[test]
+    fn appends_to_existing_entries() {
+        let res = ServerTiming::new("total").process(
+            Request::new(),
+            &|_: Request| {
+                Response::new().header(Header::ServerTiming, "cache;desc=hit")
+            },
+        );
+        let value = res.headers.get(Header::ServerTiming).unwrap();
+        assert!(value.starts_with("cache;desc=hit, total;dur="));
+    }
+
+    #[test]
+    fn escapes_descriptions() {
+        let timing = Timing::new("app", Duration::from_micros(12_345))
+            .description(r#"say "hi" \o/"#);
+        assert_eq!(
+            timing.to_string(),
+            r#"app;dur=12.3;desc="say \"hi\" \\o/""#
+        );
+    }
+}
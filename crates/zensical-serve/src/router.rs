@@ -30,16 +30,19 @@ use std::str::FromStr;
 use super::handler::matcher::Route;
 use super::handler::stack::{self, Stack};
 use super::handler::{Error, Result, Scope, TryIntoHandler};
-use super::http::Method;
+use super::http::{Method, Response};
 use super::middleware::{Middleware, TryIntoMiddleware};
 
 // Re-export for convenient usage with routers
 pub use super::handler::matcher::Params;
 
 mod action;
+mod error;
 mod routes;
 
-pub use action::Action;
+pub use action::{Action, IntoActionResult};
+use error::ErrorMapper;
+pub use error::ErrorResponse;
 use routes::Routes;
 
 // ----------------------------------------------------------------------------
@@ -74,6 +77,8 @@ pub struct Router {
     builders: Vec<Builder>,
     /// Base path.
     path: String,
+    /// Error mapper for errors returned by actions.
+    map_err: ErrorMapper,
 }
 
 // ----------------------------------------------------------------------------
@@ -101,6 +106,7 @@ impl Router {
         Self {
             builders: Vec::new(),
             path: path.into(),
+            map_err: ErrorMapper::default(),
         }
     }
 
@@ -296,6 +302,43 @@ impl Router {
         self.route(Method::Trace, path, action)
     }
 
+    /// Sets the error mapper of the router.
+    ///
+    /// Actions can return a [`Result`] with any error that can be converted
+    /// into an [`ErrorResponse`], which is mapped to a response with the given
+    /// function. By default, the response carries the status code of the error
+    /// response, and its message as a plain text body. The error mapper applies
+    /// to all routes of the router, including those added before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs;
+    /// use zensical_serve::http::{Header, Request, Response};
+    /// use zensical_serve::router::{ErrorResponse, Router, Params};
+    ///
+    /// // Create router with error mapper responding with JSON
+    /// let router = Router::default()
+    ///     .get("/", |req: Request, params: Params| {
+    ///         let content = fs::read_to_string("index.html")?;
+    ///         Ok::<_, ErrorResponse>(Response::new().body(content))
+    ///     })
+    ///     .map_err(|err: ErrorResponse| {
+    ///         Response::new()
+    ///             .status(err.status)
+    ///             .header(Header::ContentType, "application/json")
+    ///             .body(format!(r#"{{"status":{}}}"#, err.status as u16))
+    ///     });
+    /// ```
+    #[must_use]
+    pub fn map_err<F>(mut self, f: F) -> Self
+    where
+        F: Fn(ErrorResponse) -> Response + 'static,
+    {
+        self.map_err = ErrorMapper::new(f);
+        self
+    }
+
     /// Adds a middleware to the router.
     ///
     /// Middlewares can be added at any point in the router stack, including
@@ -418,10 +461,14 @@ impl TryIntoMiddleware for Router {
                 .try_into_middleware(&scope)
                 .map(|middleware| Box::new(middleware) as Box<dyn Middleware>),
 
-            // Convert routes into middleware
-            Builder::Routes(builder) => builder
-                .try_into_middleware(&scope)
-                .map(|middleware| Box::new(middleware) as Box<dyn Middleware>),
+            // Convert routes into middleware, mapping errors of actions with
+            // the error mapper of the router
+            Builder::Routes(mut builder) => {
+                builder.map_err(self.map_err.clone());
+                builder.try_into_middleware(&scope).map(|middleware| {
+                    Box::new(middleware) as Box<dyn Middleware>
+                })
+            }
         });
 
         // Collect middlewares into a stack
@@ -484,6 +531,105 @@ impl Default for Router {
         Self {
             builders: Vec::default(),
             path: String::from("/"),
+            map_err: ErrorMapper::default(),
         }
     }
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io;
+
+    use crate::handler::{Handler, TryIntoHandler};
+    use crate::http::{Header, Request, Response, Status};
+
+    #[test]
+    fn records_matched_route_pattern() {
+        let router = Router::new("/api")
+            .with(|req: Request, next: &dyn Handler| {
+                let res = next.handle(req);
+                let route = res.route.clone().unwrap_or_default();
+                res.body(route)
+            })
+            .get("/items/{id}", |_: Request, _: Params| Response::new())
+            .try_into_handler()
+            .expect("invariant");
+
+        let res = router.handle(Request::new().uri("/api/items/123"));
+        assert_eq!(res.body, b"/api/items/{id}");
+        let res = router.handle(Request::new().uri("/api/other"));
+        assert_eq!(res.body, b"");
+    }
+
+    #[test]
+    fn falls_through_to_later_routes_on_constraint_mismatch() {
+        let router = Router::default()
+            .get("/v/{version:int}", |_: Request, _: Params| {
+                Response::new().body("int")
+            })
+            .with(|req: Request, next: &dyn Handler| next.handle(req))
+            .get("/v/{name}", |_: Request, _: Params| {
+                Response::new().body("name")
+            })
+            .try_into_handler()
+            .expect("invariant");
+
+        let res = router.handle(Request::new().uri("/v/2"));
+        assert_eq!(res.body, b"int");
+        let res = router.handle(Request::new().uri("/v/latest"));
+        assert_eq!(res.body, b"name");
+    }
+
+    #[test]
+    fn maps_errors_of_actions_to_responses() {
+        let router = Router::default()
+            .get("/missing", |_: Request, _: Params| {
+                let err = io::Error::new(io::ErrorKind::NotFound, "missing");
+                Err::<Response, _>(err)
+            })
+            .try_into_handler()
+            .expect("invariant");
+
+        let res = router.handle(Request::new().uri("/missing"));
+        assert_eq!(res.status, Status::InternalServerError);
+        assert_eq!(res.body, b"missing");
+        assert_eq!(res.route.as_deref(), Some("/missing"));
+    }
+
+    #[test]
+    fn maps_errors_with_custom_error_mapper() {
+        let router = Router::default()
+            .get("/items/{id}", |_: Request, params: Params| {
+                let id = params.get("id").unwrap_or_default();
+                match id {
+                    "1" => Ok(Response::new().body("item")),
+                    _ => Err(ErrorResponse::new(Status::NotFound, id)),
+                }
+            })
+            .map_err(|err: ErrorResponse| {
+                Response::new()
+                    .status(err.status)
+                    .header(Header::ContentType, "application/json")
+                    .body(format!(r#"{{"error":"{}"}}"#, err.message))
+            })
+            .try_into_handler()
+            .expect("invariant");
+
+        let res = router.handle(Request::new().uri("/items/1"));
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.body, b"item");
+        let res = router.handle(Request::new().uri("/items/2"));
+        assert_eq!(res.status, Status::NotFound);
+        assert_eq!(
+            res.headers.get(Header::ContentType),
+            Some("application/json")
+        );
+        assert_eq!(res.body, br#"{"error":"2"}"#);
+    }
+}
@@ -26,10 +26,13 @@
 //! Action.
 
 use std::fmt;
+use std::result::Result;
 
 use crate::http::{Request, Response};
 use crate::router::Params;
 
+use super::error::ErrorResponse;
+
 // ----------------------------------------------------------------------------
 // Traits
 // ----------------------------------------------------------------------------
@@ -37,9 +40,9 @@ use crate::router::Params;
 /// Action.
 ///
 /// If a route is matched, the registered action is called with the [`Request`]
-/// and [`Params`], which were extracted from the route, if any. Currently, an
-/// action is required to always return a [`Response`], which means it will be
-/// considered the end of the processing chain.
+/// and [`Params`], which were extracted from the route, if any. An action is
+/// required to return a [`Response`] or an [`ErrorResponse`], which the router
+/// maps to a response, so it will be considered the end of the processing chain.
 ///
 /// Of course it's possible to add middlewares after routes, but it's important
 /// to understand that they are only executed if none of the routes matched.
@@ -47,10 +50,15 @@ pub trait Action: 'static {
     /// Handles the given request with parameters.
     ///
     /// This method is invoked with a request and parameters and is required to
-    /// return a response. It must be infallible and should not panic. Note that
+    /// return a response or an error response. It should not panic. Note that
     /// actions are rather an internal concept, which are automatically created
     /// when registering routes in a [`Router`].
     ///
+    /// # Errors
+    ///
+    /// In case the action fails, an [`ErrorResponse`] is returned, which the
+    /// router maps to a response with its error mapper.
+    ///
     /// # Examples
     ///
     /// This example shows how to implement a teapot route responding with
@@ -85,7 +93,23 @@ pub trait Action: 'static {
     /// # Ok(())
     /// # }
     /// ```
-    fn handle(&self, req: Request, params: Params) -> Response;
+    fn handle(
+        &self, req: Request, params: Params,
+    ) -> Result<Response, ErrorResponse>;
+}
+
+/// Conversion into the result of an [`Action`].
+///
+/// This is implemented for [`Response`], as well as for [`Result`] with any
+/// error that can be converted into an [`ErrorResponse`], which allows actions
+/// to return either, and to use the `?` operator for error handling.
+pub trait IntoActionResult {
+    /// Converts into the result of an action.
+    ///
+    /// # Errors
+    ///
+    /// In case of an error, an [`ErrorResponse`] is returned.
+    fn into_action_result(self) -> Result<Response, ErrorResponse>;
 }
 
 // ----------------------------------------------------------------------------
@@ -106,10 +130,31 @@ impl fmt::Debug for Box<dyn Action> {
 impl<F, R> Action for F
 where
     F: Fn(Request, Params) -> R + 'static,
-    R: Into<Response>,
+    R: IntoActionResult,
+{
+    #[inline]
+    fn handle(
+        &self, req: Request, params: Params,
+    ) -> Result<Response, ErrorResponse> {
+        self(req, params).into_action_result()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl IntoActionResult for Response {
+    #[inline]
+    fn into_action_result(self) -> Result<Response, ErrorResponse> {
+        Ok(self)
+    }
+}
+
+impl<E> IntoActionResult for Result<Response, E>
+where
+    E: Into<ErrorResponse>,
 {
     #[inline]
-    fn handle(&self, req: Request, params: Params) -> Response {
-        self(req, params).into()
+    fn into_action_result(self) -> Result<Response, ErrorResponse> {
+        self.map_err(Into::into)
     }
 }
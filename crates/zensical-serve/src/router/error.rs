@@ -0,0 +1,155 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Router error.
+
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::http::response::ResponseExt;
+use crate::http::{Response, Status};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Error response.
+///
+/// Actions can return a [`Result`] with any error that can be converted into
+/// an error response, which is then mapped to a [`Response`] by the router.
+/// All errors implementing [`Error`] are converted into error responses with
+/// the "500 Internal Server Error" status code and the error as a message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorResponse {
+    /// Status code.
+    pub status: Status,
+    /// Error message.
+    pub message: String,
+}
+
+/// Error mapper.
+///
+/// Routers map error responses returned by actions to responses using an error
+/// mapper, which can be overridden with [`Router::map_err`][]. By default, the
+/// response carries the status code and the message as a plain text body.
+///
+/// [`Router::map_err`]: crate::router::Router::map_err
+#[derive(Clone)]
+pub struct ErrorMapper {
+    /// Mapping function.
+    function: Rc<dyn Fn(ErrorResponse) -> Response>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl ErrorResponse {
+    /// Creates an error response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_serve::http::Status;
+    /// use zensical_serve::router::ErrorResponse;
+    ///
+    /// // Create error response
+    /// let err = ErrorResponse::new(Status::NotFound, "Page not found");
+    /// ```
+    pub fn new<M>(status: Status, message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl ErrorMapper {
+    /// Creates an error mapper from the given function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(ErrorResponse) -> Response + 'static,
+    {
+        Self { function: Rc::new(f) }
+    }
+
+    /// Maps the given error response to a response.
+    #[inline]
+    #[must_use]
+    pub fn map(&self, err: ErrorResponse) -> Response {
+        (self.function)(err)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<E> From<E> for ErrorResponse
+where
+    E: Error,
+{
+    /// Creates an error response from an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Error, ErrorKind};
+    /// use zensical_serve::http::Status;
+    /// use zensical_serve::router::ErrorResponse;
+    ///
+    /// // Create error response from error
+    /// let err = ErrorResponse::from(Error::new(ErrorKind::Other, "failed"));
+    /// assert_eq!(err.status, Status::InternalServerError);
+    /// assert_eq!(err.message, "failed");
+    /// ```
+    fn from(err: E) -> Self {
+        Self::new(Status::InternalServerError, err.to_string())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Default for ErrorMapper {
+    /// Creates an error mapper responding with the message as plain text.
+    fn default() -> Self {
+        Self::new(|err: ErrorResponse| {
+            Response::new() // fmt
+                .status(err.status)
+                .text(err.message)
+        })
+    }
+}
+
+impl fmt::Debug for ErrorMapper {
+    /// Formats the error mapper for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorMapper")
+    }
+}
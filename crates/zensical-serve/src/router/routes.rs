@@ -33,6 +33,7 @@ use crate::http::{Method, Request, Response};
 use crate::middleware::Middleware;
 
 use super::action::Action;
+use super::error::ErrorMapper;
 
 mod builder;
 
@@ -50,8 +51,11 @@ pub use builder::Builder;
 /// check for when a request is received.
 #[derive(Debug)]
 pub struct Routes {
-    /// Map methods to matchers.
-    matchers: BTreeMap<Method, Matcher<Box<dyn Action>>>,
+    /// Map methods to matchers with route patterns and actions.
+    #[allow(clippy::type_complexity)]
+    matchers: BTreeMap<Method, Matcher<(String, Box<dyn Action>)>>,
+    /// Error mapper for errors returned by actions.
+    map_err: ErrorMapper,
 }
 
 // ----------------------------------------------------------------------------
@@ -77,6 +81,13 @@ impl Middleware for Routes {
     /// a match is found, the corresponding action is called. If not, it is
     /// forwarded to the next handler, which can be another middleware or the
     /// final handler in the processing chain.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(method = %req.method, path = %req.uri.path)
+        )
+    )]
     fn process(&self, req: Request, next: &dyn Handler) -> Response {
         if let Some(routes) = self.matchers.get(&req.method) {
             // If path is borrowed, which is the normal case for parsing, this
@@ -94,9 +105,16 @@ impl Middleware for Routes {
             };
 
             // Finally, we resolve the path against the matcher, and invoke the
-            // corresponding action if it matches a registered route
-            if let Some(Match { data: action, params }) = routes.resolve(path) {
-                return action.handle(req, params);
+            // corresponding action if it matches a registered route, mapping
+            // errors to responses, and recording the route pattern, so outer
+            // middlewares can group by route
+            if let Some(Match { data, params }) = routes.resolve(path) {
+                let (pattern, action) = data;
+                let mut res = action
+                    .handle(req, params)
+                    .unwrap_or_else(|err| self.map_err.map(err));
+                res.route = Some(pattern.clone());
+                return res;
             }
         }
 
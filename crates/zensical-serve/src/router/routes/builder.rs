@@ -31,6 +31,7 @@ use std::str::FromStr;
 use crate::handler::{Error, Matcher, Result, Scope};
 use crate::http::Method;
 use crate::middleware::TryIntoMiddleware;
+use crate::router::error::ErrorMapper;
 use crate::router::{Action, Route};
 
 use super::Routes;
@@ -45,6 +46,8 @@ use super::Routes;
 pub struct Builder {
     /// Map methods to routes.
     routes: BTreeMap<Method, Vec<(String, Box<dyn Action>)>>,
+    /// Error mapper for errors returned by actions.
+    map_err: ErrorMapper,
 }
 
 // ----------------------------------------------------------------------------
@@ -56,7 +59,10 @@ impl Builder {
     #[allow(clippy::new_without_default)]
     #[must_use]
     pub fn new() -> Self {
-        Self { routes: BTreeMap::new() }
+        Self {
+            routes: BTreeMap::new(),
+            map_err: ErrorMapper::default(),
+        }
     }
 
     /// Adds a route to the routes.
@@ -75,6 +81,11 @@ impl Builder {
             .or_default()
             .push((path.into(), Box::new(action)));
     }
+
+    /// Sets the error mapper for errors returned by actions.
+    pub fn map_err(&mut self, mapper: ErrorMapper) {
+        self.map_err = mapper;
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -104,14 +115,18 @@ impl TryIntoMiddleware for Builder {
                     .map_err(|err| Error::Matcher(err.into()))?;
 
                 // Join the matcher's base path with the route path and add it
-                // to the matcher, associating it with the registered action
-                matcher.add(base.append(path), action)?;
+                // to the matcher, associating it with the registered action and
+                // the resulting pattern, which is recorded on matching
+                let route = base.append(path);
+                let pattern = route.to_string();
+                matcher.add(route, (pattern, action))?;
             }
             Ok((method, matcher))
         });
 
         // Collect methods and routes into an ordered map
+        let map_err = self.map_err;
         iter.collect::<Result<BTreeMap<_, _>>>()
-            .map(|routes| Routes { matchers: routes })
+            .map(|routes| Routes { matchers: routes, map_err })
     }
 }
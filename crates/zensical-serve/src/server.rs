@@ -26,27 +26,43 @@
 //! HTTP server.
 
 use crossbeam::channel::{Receiver, TryRecvError};
-use mio::net::{TcpListener, TcpStream};
 use mio::{Interest, Token, Waker};
 use slab::Slab;
 use std::io::ErrorKind;
-use std::net::ToSocketAddrs;
+use std::net::{SocketAddr, ToSocketAddrs};
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tungstenite::protocol::Role;
 use tungstenite::{Message, WebSocket};
 
 use super::handler::{Handler, TryIntoHandler};
-use super::server::connection::{Connection, Signal, Upgrade};
+use super::server::connection::{
+    Connection, Dispatch, Limits, Signal, Upgrade,
+};
 
 mod builder;
 mod connection;
 mod error;
+mod metrics;
 mod poller;
+mod pool;
+mod socket;
 
 pub use builder::Builder;
 pub use error::{Error, Result};
+pub use metrics::Metrics;
 use poller::Poller;
+use pool::Pool;
+use socket::{Address, Listener, Stream};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Timeout for polling events.
+const TIMEOUT: Duration = Duration::from_secs(10);
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -63,15 +79,25 @@ where
     H: Handler,
 {
     /// Handler for incoming requests.
-    handler: H,
+    handler: Arc<H>,
+    /// Worker pool, if requests are handled in parallel.
+    pool: Option<Pool>,
     /// Poller for I/O events.
     events: Poller,
     /// Acceptors for incoming connections.
-    acceptors: Vec<TcpListener>,
+    acceptors: Vec<Listener>,
     /// HTTP connections.
     connections: Slab<Connection>,
     /// WebSocket clients.
-    clients: Slab<WebSocket<TcpStream>>,
+    clients: Slab<WebSocket<Stream>>,
+    /// Time connections are given to finish when shutting down.
+    drain_deadline: Duration,
+    /// Read limits for connections.
+    limits: Limits,
+    /// Deadline for draining connections, once shutting down.
+    deadline: Option<Instant>,
+    /// Metrics.
+    metrics: Metrics,
 }
 
 // ----------------------------------------------------------------------------
@@ -131,13 +157,27 @@ where
 
     /// Polls the server for incoming events.
     ///
-    /// The receiver is used to get notifications about file changes.
+    /// The receiver is used to get notifications about file changes. Returns
+    /// `false` once the server was shut down, and all connections are closed.
     #[allow(clippy::too_many_lines)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     #[inline]
     pub fn poll(
         &mut self, receiver: Option<&Receiver<String>>,
     ) -> Result<bool> {
-        if let Err(err) = self.events.poll(Some(Duration::from_secs(10))) {
+        // When draining, there's nothing left to wait for once all
+        // connections are closed, so we can stop right away
+        if self.deadline.is_some() && self.connections.is_empty() {
+            return Ok(false);
+        }
+
+        // Wait for events, but not past the deadline when draining
+        let timeout = self.deadline.map_or(TIMEOUT, |deadline| {
+            deadline
+                .saturating_duration_since(Instant::now())
+                .min(TIMEOUT)
+        });
+        if let Err(err) = self.events.poll(Some(timeout)) {
             if !matches!(
                 err,
                 Error::Io(ref io) if io.kind() == ErrorKind::Interrupted
@@ -146,7 +186,14 @@ where
             }
         }
 
-        // Check if we need to clean up timed out connections
+        // Propagate errors of workers, which terminate if they fail to wake
+        // the event loop, since their responses would never be written
+        if let Some(pool) = &mut self.pool {
+            pool.check()?;
+        }
+
+        // Check if we need to clean up timed out connections, which includes
+        // idle connections, as well as connections receiving requests slowly
         let now = Instant::now();
         let mut timed_out = Vec::new();
 
@@ -160,6 +207,9 @@ where
         // Clean up timed out connections
         for n in timed_out {
             if let Some(conn) = self.connections.try_remove(n) {
+                if self.deadline.is_some() {
+                    self.metrics.aborted += 1;
+                }
                 let mut socket = conn.into_socket();
                 self.events.deregister(&mut socket)?;
             }
@@ -173,6 +223,25 @@ where
 
             // Received a waker event
             if n == usize::MAX {
+                // Write responses handed back by workers - the connection is
+                // guaranteed to still exist, as it can't time out or be closed
+                // while waiting for a worker, so the token identifies it
+                if let Some(pool) = &self.pool {
+                    for (token, res) in pool.responses() {
+                        let n: usize = token.into();
+                        if let Some(conn) = self.connections.get_mut(n - start)
+                        {
+                            conn.respond(res);
+                            self.events.reregister(
+                                conn.socket(),
+                                token,
+                                Interest::WRITABLE,
+                            )?;
+                        }
+                    }
+                }
+
+                // Forward notifications about file changes to clients
                 if let Some(receiver) = receiver {
                     loop {
                         match receiver.try_recv() {
@@ -201,10 +270,12 @@ where
                 // loop, as browsers might open several new connections at once
                 loop {
                     match acceptor.accept() {
-                        Ok((socket, _addr)) => {
-                            let n = self
-                                .connections
-                                .insert(Connection::new(socket));
+                        Ok(socket) => {
+                            let n = self.connections.insert(Connection::new(
+                                socket,
+                                self.limits,
+                                Instant::now(),
+                            ));
                             self.events.register(
                                 self.connections[n].socket(),
                                 Token(start + n),
@@ -226,7 +297,11 @@ where
                 // events in order make the borrow checker happy
                 let mut signals = Vec::new();
                 if event.is_readable() {
-                    signals.push((conn.read(&self.handler)?, n));
+                    let dispatch = match &self.pool {
+                        Some(pool) => Dispatch::Pool(pool, token),
+                        None => Dispatch::Inline(self.handler.as_ref()),
+                    };
+                    signals.push((conn.read(&dispatch, Instant::now())?, n));
                 }
                 if event.is_writable() {
                     signals.push((conn.write()?, n));
@@ -254,9 +329,18 @@ where
                             }
                         }
 
-                        // Close connection and deregister from poller
+                        // Close connection and deregister from poller - when
+                        // draining, the connection was drained, if it wasn't
+                        // closed in the middle of writing the response
                         Signal::Close => {
                             let conn = self.connections.remove(n - start);
+                            if self.deadline.is_some() {
+                                if conn.is_writing() {
+                                    self.metrics.aborted += 1;
+                                } else {
+                                    self.metrics.drained += 1;
+                                }
+                            }
                             let mut socket = conn.into_socket();
                             self.events.deregister(&mut socket)?;
                         }
@@ -286,12 +370,489 @@ where
             }
         }
 
+        // When draining, stop once all connections are closed, or abort the
+        // remaining connections once the deadline has passed
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                for conn in self.connections.drain() {
+                    let mut socket = conn.into_socket();
+                    self.events.deregister(&mut socket)?;
+                    self.metrics.aborted += 1;
+                }
+            }
+            return Ok(!self.connections.is_empty());
+        }
+
         // Keep on polling
         Ok(true)
     }
 
+    /// Shuts the server down gracefully.
+    ///
+    /// The server stops accepting connections, and closes idle connections
+    /// right away, as well as connections that are in the middle of reading
+    /// a request, which are told to retry once the drain deadline passed, if
+    /// possible. Responses that are currently written or handled by workers
+    /// are drained, i.e., they're sent with `Connection: close`, if not yet
+    /// written, and the server keeps polling until they're finished, or the
+    /// deadline has passed, in which case [`Server::poll`] returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if deregistering from the poller fails.
+    pub fn shutdown(&mut self) -> Result {
+        if self.deadline.is_some() {
+            return Ok(());
+        }
+
+        // Stop accepting connections, and set the deadline for draining
+        for acceptor in &mut self.acceptors {
+            self.events.deregister(acceptor)?;
+        }
+        self.deadline = Some(Instant::now() + self.drain_deadline);
+
+        // Close all connections that are reading, and drain all others
+        let retry_after = self.drain_deadline.as_secs().max(1);
+        let mut reading = Vec::new();
+        for (n, conn) in &mut self.connections {
+            if conn.is_reading() {
+                reading.push(n);
+            } else {
+                conn.close_after_response();
+            }
+        }
+        for n in reading {
+            let mut conn = self.connections.remove(n);
+            if conn.is_partial() {
+                conn.reject(retry_after);
+                self.metrics.aborted += 1;
+            }
+            let mut socket = conn.into_socket();
+            self.events.deregister(&mut socket)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the metrics of the server.
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        self.metrics
+    }
+
+    /// Returns the addresses the server is bound to.
+    ///
+    /// When binding to port `0`, the operating system picks a free port, so
+    /// this is the only way to learn the address clients can connect to.
+    #[must_use]
+    pub fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.acceptors
+            .iter()
+            .filter_map(|acceptor| match acceptor.local_addr() {
+                Ok(Address::Tcp(addr)) => Some(addr),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the Unix domain socket paths the server is bound to.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn local_paths(&self) -> Vec<PathBuf> {
+        self.acceptors
+            .iter()
+            .filter_map(|acceptor| match acceptor.local_addr() {
+                Ok(Address::Unix(path)) => Some(path),
+                _ => None,
+            })
+            .collect()
+    }
+
     // Return waker for waking server from poll loop
+    #[must_use]
     pub fn waker(&self) -> Arc<Waker> {
         self.events.waker().clone()
     }
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crossbeam::channel::unbounded;
+    use std::fs;
+    use std::io::{ErrorKind, Read, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+    use std::sync::{Condvar, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    use crate::handler::{Handler, Stack, Teapot};
+    use crate::http::response::ResponseExt;
+    use crate::http::{Request, Response, Status};
+    use crate::middleware::WebSocketHandshake;
+
+    use super::{Builder, Error, Metrics, Receiver, Server};
+
+    /// Polls the server until the client received data, and returns it.
+    fn exchange<H>(
+        server: &mut Server<H>, client: &mut UnixStream,
+        receiver: Option<&Receiver<String>>,
+    ) -> Vec<u8>
+    where
+        H: Handler,
+    {
+        client.set_nonblocking(true).unwrap();
+        let mut data = Vec::new();
+        for _ in 0..16 {
+            server.poll(receiver).unwrap();
+            let mut buffer = [0u8; 1024];
+            match client.read(&mut buffer) {
+                Ok(bytes) => {
+                    data.extend_from_slice(&buffer[..bytes]);
+                    break;
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                Err(err) => panic!("{err}"),
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn serves_over_unix_socket() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let mut server = Server::builder(Teapot)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen)
+            .unwrap();
+        assert_eq!(server.local_paths(), vec![path.clone()]);
+        assert!(server.local_addrs().is_empty());
+
+        // Send request over socket and read response
+        let mut client = UnixStream::connect(&path).unwrap();
+        client
+            .write_all(b"GET /coffee HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let res = exchange(&mut server, &mut client, None);
+        assert!(res.starts_with(b"HTTP/1.1 418"));
+
+        // Socket file is removed once the server is dropped
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn upgrades_to_websocket_over_unix_socket() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let stack = Stack::new().with(WebSocketHandshake);
+        let mut server = Server::builder(stack)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen)
+            .unwrap();
+
+        // Perform handshake over socket
+        let mut client = UnixStream::connect(&path).unwrap();
+        client
+            .write_all(
+                b"GET / HTTP/1.1\r\n\
+                  Host: localhost\r\n\
+                  Connection: Upgrade\r\n\
+                  Upgrade: websocket\r\n\
+                  Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                  Sec-WebSocket-Version: 13\r\n\r\n",
+            )
+            .unwrap();
+        let res = exchange(&mut server, &mut client, None);
+        assert!(res.starts_with(b"HTTP/1.1 101"));
+
+        // Notify clients, which must arrive as a text frame
+        let (sender, receiver) = unbounded();
+        sender.send(String::from("/index.html")).unwrap();
+        server.waker().wake().unwrap();
+        let frame = exchange(&mut server, &mut client, Some(&receiver));
+        assert_eq!(frame[0], 0x81);
+        assert_eq!(&frame[2..], b"/index.html");
+    }
+
+    /// Handler that sleeps before responding.
+    struct Sleepy;
+
+    impl Handler for Sleepy {
+        fn handle(&self, _: Request) -> Response {
+            thread::sleep(Duration::from_millis(200));
+            Response::new()
+        }
+    }
+
+    /// Handler that waits until the expected number of requests are handled.
+    ///
+    /// Requests are only answered successfully if they all arrive before the
+    /// timeout, which is only possible if they're handled concurrently.
+    struct Rendezvous {
+        /// Number of requests handled so far.
+        count: Mutex<usize>,
+        /// Condition variable signaling arrival of a request.
+        arrived: Condvar,
+        /// Number of requests to wait for.
+        expected: usize,
+    }
+
+    impl Handler for Rendezvous {
+        fn handle(&self, _: Request) -> Response {
+            let mut count = self.count.lock().unwrap();
+            *count += 1;
+            self.arrived.notify_all();
+            let (_count, result) = self
+                .arrived
+                .wait_timeout_while(count, Duration::from_secs(5), |count| {
+                    *count < self.expected
+                })
+                .unwrap();
+            if result.timed_out() {
+                Response::from_status(Status::ServiceUnavailable)
+            } else {
+                Response::new()
+            }
+        }
+    }
+
+    #[test]
+    fn handles_requests_in_parallel_with_workers() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let handler = Rendezvous {
+            count: Mutex::new(0),
+            arrived: Condvar::new(),
+            expected: 4,
+        };
+        let mut server = Server::builder(handler)
+            .and_then(|builder| builder.workers(4).bind_unix(&path))
+            .and_then(Builder::listen)
+            .unwrap();
+
+        // Send requests over several connections at once
+        let mut clients = (0..4)
+            .map(|_| {
+                let mut client = UnixStream::connect(&path).unwrap();
+                client.set_nonblocking(true).unwrap();
+                client
+                    .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                    .unwrap();
+                client
+            })
+            .collect::<Vec<_>>();
+
+        // Poll until all clients received their responses, which only succeed
+        // if all requests were handled concurrently, not one after another
+        let start = Instant::now();
+        let mut pending = clients.len();
+        while pending > 0 && start.elapsed() < Duration::from_secs(10) {
+            server.poll(None).unwrap();
+            for client in &mut clients {
+                let mut buffer = [0u8; 1024];
+                match client.read(&mut buffer) {
+                    Ok(bytes) => {
+                        assert!(buffer[..bytes].starts_with(b"HTTP/1.1 200"));
+                        pending -= 1;
+                    }
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                    Err(err) => panic!("{err}"),
+                }
+            }
+        }
+        assert_eq!(pending, 0);
+    }
+
+    /// Handler that responds with a body larger than socket buffers.
+    struct Large;
+
+    impl Handler for Large {
+        fn handle(&self, _: Request) -> Response {
+            Response::new().body(vec![b'x'; 1 << 22])
+        }
+    }
+
+    /// Connects a client and polls until the server writes the response.
+    fn connect_slow_reader<H>(server: &mut Server<H>, path: &Path) -> UnixStream
+    where
+        H: Handler,
+    {
+        let mut client = UnixStream::connect(path).unwrap();
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        while !server.connections.iter().any(|(_, conn)| conn.is_writing()) {
+            server.poll(None).unwrap();
+        }
+        client
+    }
+
+    #[test]
+    fn drains_slow_writer_on_shutdown() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let mut server = Server::builder(Large)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen)
+            .unwrap();
+
+        // Start writing the response, then shut down before the client reads
+        let mut client = connect_slow_reader(&mut server, &path);
+        server.shutdown().unwrap();
+
+        // New connections are not accepted anymore
+        let _other = UnixStream::connect(&path).unwrap();
+        server.poll(None).unwrap();
+        assert_eq!(server.connections.len(), 1);
+
+        // Read slowly until the server finished draining the connection
+        client.set_nonblocking(true).unwrap();
+        let mut data = Vec::new();
+        loop {
+            let mut buffer = [0u8; 16384];
+            match client.read(&mut buffer) {
+                Ok(bytes) => {
+                    data.extend_from_slice(&buffer[..bytes]);
+                    continue;
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+                Err(err) => panic!("{err}"),
+            }
+            if !server.poll(None).unwrap() {
+                break;
+            }
+        }
+        client.set_nonblocking(false).unwrap();
+        client.read_to_end(&mut data).unwrap();
+        assert!(data.starts_with(b"HTTP/1.1 200"));
+        assert!(data.ends_with(&vec![b'x'; 1 << 22]));
+        assert_eq!(server.metrics(), Metrics { drained: 1, aborted: 0 });
+    }
+
+    #[test]
+    fn aborts_slow_writer_after_deadline() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let mut server = Server::builder(Large)
+            .and_then(|builder| {
+                builder
+                    .drain_deadline(Duration::from_millis(50))
+                    .bind_unix(&path)
+            })
+            .and_then(Builder::listen)
+            .unwrap();
+
+        // Start writing the response, but never read it
+        let _client = connect_slow_reader(&mut server, &path);
+        let start = Instant::now();
+        server.shutdown().unwrap();
+        while server.poll(None).unwrap() {}
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(server.metrics(), Metrics { drained: 0, aborted: 1 });
+    }
+
+    #[test]
+    fn rejects_partial_request_on_shutdown() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let mut server = Server::builder(Teapot)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen)
+            .unwrap();
+
+        // Send part of a request, and poll until the server read it
+        let mut client = UnixStream::connect(&path).unwrap();
+        client.write_all(b"GET /coffee HTTP/1.1\r\n").unwrap();
+        while !server.connections.iter().any(|(_, conn)| conn.is_partial()) {
+            server.poll(None).unwrap();
+        }
+
+        // Connection is closed right away, telling the client to retry
+        server.shutdown().unwrap();
+        assert!(!server.poll(None).unwrap());
+        let mut data = String::new();
+        client.read_to_string(&mut data).unwrap();
+        assert!(data.starts_with("HTTP/1.1 503"));
+        assert!(data.contains("Retry-After: 5\r\n"));
+        assert!(data.contains("Connection: close\r\n"));
+        assert_eq!(server.metrics(), Metrics { drained: 0, aborted: 1 });
+    }
+
+    #[test]
+    fn closes_connection_after_pending_response() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let mut server = Server::builder(Sleepy)
+            .and_then(|builder| builder.workers(1).bind_unix(&path))
+            .and_then(Builder::listen)
+            .unwrap();
+
+        // Send request, and poll until it's handed to a worker
+        let mut client = UnixStream::connect(&path).unwrap();
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        while server.connections.iter().all(|(_, conn)| conn.is_reading()) {
+            server.poll(None).unwrap();
+        }
+
+        // Response is sent once the worker is done, and the connection closed
+        server.shutdown().unwrap();
+        while server.poll(None).unwrap() {}
+        let mut data = String::new();
+        client.read_to_string(&mut data).unwrap();
+        assert!(data.starts_with("HTTP/1.1 200"));
+        assert!(data.contains("Connection: close\r\n"));
+        assert_eq!(server.metrics(), Metrics { drained: 1, aborted: 0 });
+    }
+
+    #[test]
+    fn replaces_stale_socket_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        // Binding succeeds, as nobody listens on the socket anymore
+        let server = Server::builder(Teapot)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen);
+        assert!(server.is_ok());
+    }
+
+    #[test]
+    fn refuses_to_replace_socket_in_use() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        let _listener = UnixListener::bind(&path).unwrap();
+
+        // Binding fails, as another server listens on the socket
+        let server = Server::builder(Teapot)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen);
+        assert!(matches!(
+            server,
+            Err(Error::Io(err)) if err.kind() == ErrorKind::AddrInUse
+        ));
+    }
+
+    #[test]
+    fn refuses_to_replace_regular_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("zensical.sock");
+        fs::write(&path, "content").unwrap();
+
+        // Binding fails, and the file is left untouched
+        let server = Server::builder(Teapot)
+            .and_then(|builder| builder.bind_unix(&path))
+            .and_then(Builder::listen);
+        assert!(server.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "content");
+    }
+}
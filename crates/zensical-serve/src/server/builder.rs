@@ -25,15 +25,41 @@
 
 //! HTTP server builder.
 
-use mio::net::TcpListener;
-use mio::{Interest, Token};
+use mio::{Interest, Token, Waker};
 use slab::Slab;
-use std::net::{SocketAddr, ToSocketAddrs};
+use std::net::ToSocketAddrs;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::handler::{Handler, TryIntoHandler};
 
+use super::connection::Limits;
 use super::poller::Poller;
-use super::{Error, Result, Server};
+use super::pool::Pool;
+use super::socket::{Address, Listener};
+use super::{Error, Metrics, Result, Server};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Default drain deadline.
+const DRAIN_DEADLINE: Duration = Duration::from_secs(5);
+
+/// Default header deadline.
+const HEADER_DEADLINE: Duration = Duration::from_secs(20);
+
+/// Default minimum read rate, in bytes per second.
+const MIN_READ_RATE: u64 = 100;
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Worker pool factory.
+type Spawn<H> = fn(&Arc<H>, usize, &Arc<Waker>) -> Result<Pool>;
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -44,7 +70,13 @@ pub struct Builder<H> {
     /// Handler for incoming requests.
     handler: H,
     /// Socket addresses to bind to.
-    addrs: Vec<SocketAddr>,
+    addrs: Vec<Address>,
+    /// Number of workers and worker pool factory.
+    workers: Option<(usize, Spawn<H>)>,
+    /// Time connections are given to finish when shutting down.
+    drain_deadline: Duration,
+    /// Read limits for connections.
+    limits: Limits,
 }
 
 // ----------------------------------------------------------------------------
@@ -82,7 +114,16 @@ where
         handler
             .try_into_handler()
             .map_err(Into::into)
-            .map(|handler| Self { handler, addrs: Vec::new() })
+            .map(|handler| Self {
+                handler,
+                addrs: Vec::new(),
+                workers: None,
+                drain_deadline: DRAIN_DEADLINE,
+                limits: Limits {
+                    header_deadline: HEADER_DEADLINE,
+                    min_read_rate: MIN_READ_RATE,
+                },
+            })
     }
 
     /// Adds a socket address to bind to.
@@ -109,7 +150,7 @@ where
         // The underlying system call might returned the same socket address
         // multiple times, which is why we need to deduplicate them
         let addrs = addr.to_socket_addrs()?;
-        for addr in addrs {
+        for addr in addrs.map(Address::Tcp) {
             if !self.addrs.contains(&addr) {
                 self.addrs.push(addr);
             }
@@ -117,6 +158,122 @@ where
         Ok(self)
     }
 
+    /// Adds a Unix domain socket path to bind to.
+    ///
+    /// The socket file is created when the server starts listening, replacing
+    /// a stale socket file left behind by a previous server, and is removed
+    /// again once the server is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::handler::Teapot;
+    /// use zensical_serve::server::Builder;
+    ///
+    /// // Create server builder and add socket path
+    /// let mut builder = Builder::new(Teapot)?;
+    /// builder.bind_unix("/tmp/zensical.sock")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    #[inline]
+    pub fn bind_unix<P>(mut self, path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let addr = Address::Unix(path.as_ref().to_path_buf());
+        if !self.addrs.contains(&addr) {
+            self.addrs.push(addr);
+        }
+        Ok(self)
+    }
+
+    /// Sets the time connections are given to finish when shutting down.
+    ///
+    /// When the server is shut down with [`Server::shutdown`], responses that
+    /// are currently written or handled by workers are drained, i.e., they're
+    /// allowed to finish until this deadline passes, after which all remaining
+    /// connections are closed. Defaults to 5 seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    /// use zensical_serve::handler::Teapot;
+    /// use zensical_serve::server::Builder;
+    ///
+    /// // Create server builder with drain deadline
+    /// let builder = Builder::new(Teapot)?
+    ///     .drain_deadline(Duration::from_secs(10));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn drain_deadline(mut self, deadline: Duration) -> Self {
+        self.drain_deadline = deadline;
+        self
+    }
+
+    /// Sets the time until the headers of a request must be complete.
+    ///
+    /// Connections are closed when a request is not complete within this
+    /// deadline, which protects against clients that keep connections open
+    /// by sending requests slowly. It's measured from accepting the connection
+    /// for the first request, and from the first byte for all subsequent ones.
+    /// Defaults to 20 seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::time::Duration;
+    /// use zensical_serve::handler::Teapot;
+    /// use zensical_serve::server::Builder;
+    ///
+    /// // Create server builder with header deadline
+    /// let builder = Builder::new(Teapot)?
+    ///     .header_deadline(Duration::from_secs(10));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn header_deadline(mut self, deadline: Duration) -> Self {
+        self.limits.header_deadline = deadline;
+        self
+    }
+
+    /// Sets the minimum rate at which requests must be received.
+    ///
+    /// Connections are closed when the cumulative rate at which a request is
+    /// received stays below this rate, given in bytes per second. The rate is
+    /// only checked after a grace period of 5 seconds, so that a slow start is
+    /// tolerated. Passing `0` disables the check. Defaults to 100 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::handler::Teapot;
+    /// use zensical_serve::server::Builder;
+    ///
+    /// // Create server builder with minimum read rate
+    /// let builder = Builder::new(Teapot)?.min_read_rate(500);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn min_read_rate(mut self, rate: u64) -> Self {
+        self.limits.min_read_rate = rate;
+        self
+    }
+
     /// Creates the server and binds to the configured addresses.
     ///
     /// # Examples
@@ -142,10 +299,16 @@ where
 
         // Create a new poller, then bind listeners to all configured addresses,
         // register them for event notifications, and create and return server
+        let handler = Arc::new(self.handler);
         Poller::new().and_then(|poller| {
+            let pool = self
+                .workers
+                .map(|(n, spawn)| spawn(&handler, n, &poller.waker()))
+                .transpose()?;
+
             let iter = self.addrs.into_iter().enumerate();
             let iter = iter.map(|(n, addr)| {
-                let mut listener = TcpListener::bind(addr)?;
+                let mut listener = Listener::bind(&addr)?;
                 poller
                     .register(&mut listener, Token(n), Interest::READABLE)
                     .map(|()| listener)
@@ -153,12 +316,78 @@ where
 
             // Collect listeners from iterator and return server
             iter.collect::<Result<_>>().map(|acceptors: Vec<_>| Server {
-                handler: self.handler,
+                handler,
+                pool,
                 events: poller,
                 acceptors,
                 connections: Slab::new(),
                 clients: Slab::new(),
+                drain_deadline: self.drain_deadline,
+                limits: self.limits,
+                deadline: None,
+                metrics: Metrics::default(),
             })
         })
     }
 }
+
+impl<H> Builder<H>
+where
+    H: Handler + Send + Sync + 'static,
+{
+    /// Sets the number of workers to handle requests in parallel.
+    ///
+    /// By default, requests are handled on the thread running the event loop,
+    /// which means that a single slow request blocks all other clients. With
+    /// workers, complete requests are handed to a pool of threads, and their
+    /// responses are written by the event loop once they're ready. Passing `0`
+    /// restores the default behavior.
+    ///
+    /// Since the handler is shared among all workers, this method is only
+    /// available for handlers that are [`Send`] and [`Sync`]. Handlers that
+    /// don't satisfy those bounds can still be used without workers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use zensical_serve::handler::Teapot;
+    /// use zensical_serve::server::Builder;
+    ///
+    /// // Create server builder with workers
+    /// let builder = Builder::new(Teapot)?.workers(4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Handlers that can't be shared among threads are rejected at compile
+    /// time, as the method doesn't exist for them:
+    ///
+    /// ```compile_fail
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::rc::Rc;
+    /// use zensical_serve::handler::Handler;
+    /// use zensical_serve::http::{Request, Response};
+    /// use zensical_serve::server::Builder;
+    ///
+    /// // Create handler that is neither Send nor Sync
+    /// struct Local(Rc<()>);
+    /// impl Handler for Local {
+    ///     fn handle(&self, _: Request) -> Response {
+    ///         Response::new()
+    ///     }
+    /// }
+    ///
+    /// // Create server builder with workers
+    /// let builder = Builder::new(Local(Rc::new(())))?.workers(4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = (workers > 0).then_some((workers, Pool::new::<H>));
+        self
+    }
+}
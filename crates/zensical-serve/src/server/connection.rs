@@ -25,19 +25,33 @@
 
 //! HTTP connection.
 
-use mio::net::TcpStream;
-use mio::Interest;
+use mio::{Interest, Token};
+use std::any::Any;
 use std::io::{Cursor, ErrorKind, Read, Write};
 use std::mem;
-use std::time::Instant;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
 use tungstenite::protocol::WebSocketConfig;
 
 use crate::handler::Handler;
 use crate::http::request::Error;
 use crate::http::response::ResponseExt;
-use crate::http::{Request, Response, Status};
+use crate::http::{Header, Request, Response, Status};
 use crate::server::Result;
 
+use super::pool::Pool;
+use super::socket::Stream;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Time after which idle connections are closed.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time after which the read rate of a request is checked.
+const RATE_GRACE: Duration = Duration::from_secs(5);
+
 // ----------------------------------------------------------------------------
 // Enums
 // ----------------------------------------------------------------------------
@@ -61,6 +75,14 @@ pub enum Upgrade {
     WebSocket(WebSocketConfig),
 }
 
+/// Request dispatch.
+pub enum Dispatch<'a, H> {
+    /// Handle requests inline on the event loop.
+    Inline(&'a H),
+    /// Hand requests to the worker pool, using the token as correlation key.
+    Pool(&'a Pool, Token),
+}
+
 // ----------------------------------------------------------------------------
 
 /// Internal buffer state.
@@ -68,6 +90,8 @@ pub enum Upgrade {
 enum Buffer {
     /// Currently reading data.
     Reading(Vec<u8>),
+    /// Currently waiting for a worker to handle the request.
+    Handling,
     /// Currently writing data, with optional upgrade.
     Writing(Cursor<Vec<u8>>, Option<Upgrade>),
 }
@@ -79,12 +103,33 @@ enum Buffer {
 /// HTTP connection.
 #[derive(Debug)]
 pub struct Connection {
-    /// TCP socket.
-    socket: TcpStream,
+    /// Socket, either TCP or Unix domain socket.
+    socket: Stream,
     /// Read/write buffer.
     buffer: Buffer,
+    /// Read limits.
+    limits: Limits,
     /// Last activity time.
     time: Instant,
+    /// Start time of the current request, if any.
+    start: Option<Instant>,
+    /// Whether to close the connection after the response.
+    closing: bool,
+}
+
+/// Read limits, protecting against clients that send requests slowly.
+///
+/// Since the idle timeout is reset on every read, a client sending a single
+/// byte every few seconds could otherwise keep a connection open forever.
+/// Limits are measured from the start of a request, which is the time the
+/// connection was accepted for the first request, and the time the first
+/// byte was received for all subsequent requests.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// Time until the headers of a request must be complete.
+    pub header_deadline: Duration,
+    /// Minimum rate at which requests must be received, in bytes per second.
+    pub min_read_rate: u64,
 }
 
 // ----------------------------------------------------------------------------
@@ -92,36 +137,48 @@ pub struct Connection {
 // ----------------------------------------------------------------------------
 
 impl Connection {
-    /// Creates a connection.
-    pub fn new(socket: TcpStream) -> Self {
+    /// Creates a connection accepted at the given time.
+    pub fn new(socket: Stream, limits: Limits, now: Instant) -> Self {
         Connection {
             socket,
             buffer: Buffer::Reading(Vec::new()),
-            time: Instant::now(),
+            limits,
+            time: now,
+            start: Some(now),
+            closing: false,
         }
     }
 
     /// Consumes the connection and returns the underlying socket.
-    pub fn into_socket(self) -> TcpStream {
+    pub fn into_socket(self) -> Stream {
         self.socket
     }
 
     /// Returns a mutable reference to the underlying socket.
-    pub fn socket(&mut self) -> &mut TcpStream {
+    pub fn socket(&mut self) -> &mut Stream {
         &mut self.socket
     }
 
     /// Attempt to read data from the socket.
-    #[allow(clippy::unnecessary_wraps)]
-    pub fn read<H>(&mut self, handler: &H) -> Result<Signal>
+    ///
+    /// Complete requests are either handled inline, or handed to the worker
+    /// pool, in which case the connection waits until [`Connection::respond`]
+    /// is called with the response, and doesn't read any further data.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn read<H>(
+        &mut self, dispatch: &Dispatch<'_, H>, now: Instant,
+    ) -> Result<Signal>
     where
         H: Handler,
     {
+        if matches!(self.buffer, Buffer::Handling) {
+            return Ok(Signal::Continue);
+        }
         if let Buffer::Reading(buffer) = &mut self.buffer {
-            self.time = Instant::now();
+            self.time = now;
             // We try to read all remaining data - if the connection would
             // block, we return and wait for the next readable event
-            let (res, upgrade) = {
+            let res = {
                 let mut temp = [0u8; 1024];
                 match self.socket.read(&mut temp) {
                     Ok(0) => {
@@ -131,21 +188,24 @@ impl Connection {
                     // If we successfully read (some) bytes, try to parse and
                     // handle the request, or otherwise continue reading
                     Ok(bytes) => {
+                        self.start.get_or_insert(now);
                         buffer.extend_from_slice(&temp[..bytes]);
                         match Request::from_bytes(buffer) {
                             // Request was parsed successfully, which means we
                             // process it, and switch to writing in order to
-                            // return the response to the client. We also check
-                            // if we need to switch protocols.
-                            Ok(req) => {
-                                let res = handler.handle(req);
-                                let upgrade = (res.status
-                                    == Status::SwitchingProtocols)
-                                    .then_some(Upgrade::WebSocket(
-                                        WebSocketConfig::default(),
-                                    ));
-                                (res, upgrade)
-                            }
+                            // return the response to the client. If a worker
+                            // pool is used, we wait for the response instead.
+                            Ok(req) => match *dispatch {
+                                Dispatch::Inline(handler) => {
+                                    handle(handler, req)
+                                }
+                                Dispatch::Pool(pool, token) => {
+                                    drop(req);
+                                    pool.submit(token, mem::take(buffer))?;
+                                    self.buffer = Buffer::Handling;
+                                    return Ok(Signal::Continue);
+                                }
+                            },
 
                             // Request could not be parsed, as it is incomplete,
                             // so we keep reading
@@ -157,16 +217,11 @@ impl Connection {
 
                             // In case there was a validation error, return it
                             Err(Error::Validation(status)) => {
-                                let res = Response::from_status(status);
-                                (res, None)
+                                Response::from_status(status)
                             }
 
                             // If there was another parsing error, return 400
-                            Err(_) => {
-                                let res =
-                                    Response::from_status(Status::BadRequest);
-                                (res, None)
-                            }
+                            Err(_) => Response::from_status(Status::BadRequest),
                         }
                     }
 
@@ -196,22 +251,43 @@ impl Connection {
                 }
             };
 
-            // If we've processed all data, check if the request was an upgrade,
-            // and if so, remember it to switch to the WebSocket protocol.
-            let _ = mem::replace(
-                &mut self.buffer,
-                Buffer::Writing(Cursor::new(res.into_bytes()), upgrade),
-            );
+            // If we've processed all data, switch to writing the response
+            self.respond(res);
         }
 
         // Switch back to writing state
         Ok(Signal::Interest(Interest::WRITABLE))
     }
 
+    /// Prepares the connection for writing the given response.
+    ///
+    /// If the response switches protocols, the upgrade is remembered, so we
+    /// can switch to the WebSocket protocol after the response was written.
+    /// If the connection is closing, the client is told so, and the upgrade
+    /// is never performed.
+    pub fn respond(&mut self, res: Response) {
+        let res = if self.closing {
+            res.header(Header::Connection, "close")
+        } else {
+            res
+        };
+        let upgrade = (res.status == Status::SwitchingProtocols
+            && !self.closing)
+            .then_some(Upgrade::WebSocket(WebSocketConfig::default()));
+        let _ = mem::replace(
+            &mut self.buffer,
+            Buffer::Writing(Cursor::new(res.into_bytes()), upgrade),
+        );
+    }
+
     /// Attempt to write data to the socket.
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::unnecessary_wraps)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn write(&mut self) -> Result<Signal> {
+        if matches!(self.buffer, Buffer::Handling) {
+            return Ok(Signal::Continue);
+        }
         if let Buffer::Writing(cursor, _) = &mut self.buffer {
             self.time = Instant::now();
             // We try to write all remaining data - if the connection would
@@ -256,6 +332,7 @@ impl Connection {
                                 eprintln!("Error: {err}");
                             }
                         }
+                        return Ok(Signal::Close);
                     }
                 }
             }
@@ -269,6 +346,15 @@ impl Connection {
             return Ok(Signal::Upgrade(upgrade));
         }
 
+        // The next request starts once its first byte is received, so the
+        // connection is governed by the idle timeout until then
+        self.start = None;
+
+        // Close the connection, if it's closing, instead of reading again
+        if self.closing {
+            return Ok(Signal::Close);
+        }
+
         // Switch back to reading state
         Ok(Signal::Interest(Interest::READABLE))
     }
@@ -278,8 +364,210 @@ impl Connection {
         matches!(self.buffer, Buffer::Writing(_, _))
     }
 
+    /// Returns whether the connection is currently reading data.
+    pub fn is_reading(&self) -> bool {
+        matches!(self.buffer, Buffer::Reading(_))
+    }
+
+    /// Returns whether the connection received part of a request.
+    pub fn is_partial(&self) -> bool {
+        matches!(&self.buffer, Buffer::Reading(buffer) if !buffer.is_empty())
+    }
+
+    /// Marks the connection to be closed after the current response.
+    pub fn close_after_response(&mut self) {
+        self.closing = true;
+    }
+
+    /// Rejects the request that is currently read, before closing.
+    ///
+    /// This makes a single attempt to tell the client to retry after the given
+    /// number of seconds, which is enough for the small response, since the
+    /// connection is closed right after, whether the attempt succeeded or not.
+    pub fn reject(&mut self, retry_after: u64) {
+        let res = Response::from_status(Status::ServiceUnavailable)
+            .header(Header::RetryAfter, retry_after)
+            .header(Header::Connection, "close");
+        let _ = self.socket.write_all(&res.into_bytes());
+    }
+
     /// Check if connection has timed out
+    ///
+    /// Connections waiting for a worker never time out, as their token must
+    /// stay valid until the response is handed back to the connection. Apart
+    /// from being idle, connections that received part of a request time out
+    /// when the header deadline passed, or when the request is received at a
+    /// rate below the minimum, which is checked after a short grace period.
     pub fn is_timed_out(&self, now: Instant) -> bool {
-        now.duration_since(self.time).as_secs() > 30
+        match (&self.buffer, self.start) {
+            (Buffer::Handling, _) => false,
+            (Buffer::Reading(buffer), Some(start)) if !buffer.is_empty() => {
+                let elapsed = now.saturating_duration_since(start);
+                let expected = u128::from(self.limits.min_read_rate)
+                    * elapsed.as_millis()
+                    / 1000;
+                self.is_idle(now)
+                    || elapsed > self.limits.header_deadline
+                    || (elapsed >= RATE_GRACE
+                        && (buffer.len() as u128) < expected)
+            }
+            _ => self.is_idle(now),
+        }
+    }
+
+    /// Check if connection has been idle for too long.
+    fn is_idle(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.time) > IDLE_TIMEOUT
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Handles the given request, converting panics into error responses.
+///
+/// Handlers are user-provided and might panic, which must neither abort the
+/// process nor poison the poll loop. Panics are caught and answered with a
+/// `500 Internal Server Error`, and the panic message is printed, but never
+/// sent to the client. Handlers are asserted to be unwind safe, since they're
+/// only borrowed immutably, so the panic can't leave them half-mutated, unless
+/// they use interior mutability, in which case they must recover themselves.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        skip_all,
+        fields(method = %req.method, path = %req.uri.path)
+    )
+)]
+pub fn handle<H>(handler: &H, req: Request) -> Response
+where
+    H: Handler,
+{
+    panic::catch_unwind(AssertUnwindSafe(|| handler.handle(req)))
+        .unwrap_or_else(|payload| {
+            let message = panic_message(payload.as_ref());
+            eprintln!("Error: handler panicked: {message}");
+            Response::from_status(Status::InternalServerError)
+        })
+}
+
+/// Returns the message of the given panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str()
+    } else {
+        "unknown panic"
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::handler::{Teapot, TryIntoHandler};
+    use crate::router::{Params, Router};
+
+    /// Limits used in tests.
+    const LIMITS: Limits = Limits {
+        header_deadline: Duration::from_secs(20),
+        min_read_rate: 100,
+    };
+
+    /// Creates a connection accepted at the given time, and its client.
+    #[cfg(unix)]
+    fn connect(now: Instant) -> (Connection, mio::net::UnixStream) {
+        let (socket, client) = mio::net::UnixStream::pair().unwrap();
+        (Connection::new(Stream::Unix(socket), LIMITS, now), client)
+    }
+
+    /// Sends the given bytes, and lets the connection read them.
+    #[cfg(unix)]
+    fn trickle(
+        conn: &mut Connection, client: &mut mio::net::UnixStream, bytes: &[u8],
+        now: Instant,
+    ) {
+        client.write_all(bytes).unwrap();
+        conn.read(&Dispatch::Inline(&Teapot), now).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn times_out_when_header_deadline_passes() {
+        let start = Instant::now();
+        let (mut conn, mut client) = connect(start);
+
+        // Send enough bytes to satisfy the minimum rate, but never finish
+        let mut now = start;
+        trickle(&mut conn, &mut client, b"GET / HTTP/1.1\r\n", now);
+        for _ in 0..20 {
+            now += Duration::from_secs(1);
+            let header = format!("X-Pad: {}\r\n", "a".repeat(119));
+            trickle(&mut conn, &mut client, header.as_bytes(), now);
+            assert!(!conn.is_timed_out(now));
+        }
+        assert!(conn.is_timed_out(now + Duration::from_secs(1)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn times_out_when_read_rate_is_below_minimum() {
+        let start = Instant::now();
+        let (mut conn, mut client) = connect(start);
+
+        // Send a single byte per second, resetting the idle timeout, which is
+        // tolerated during the grace period, but not after
+        let mut now = start;
+        for _ in 0..4 {
+            now += Duration::from_secs(1);
+            trickle(&mut conn, &mut client, b"G", now);
+            assert!(!conn.is_timed_out(now));
+        }
+        now += Duration::from_secs(1);
+        trickle(&mut conn, &mut client, b"E", now);
+        assert!(conn.is_timed_out(now));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn measures_next_request_from_first_byte() {
+        let start = Instant::now();
+        let (mut conn, mut client) = connect(start);
+        trickle(&mut conn, &mut client, b"GET / HTTP/1.1\r\n\r\n", start);
+        assert!(matches!(conn.write().unwrap(), Signal::Interest(_)));
+
+        // Idle connections are only subject to the idle timeout
+        let now = start + Duration::from_secs(25);
+        assert!(!conn.is_timed_out(now));
+
+        // Next request is measured from its first byte
+        trickle(&mut conn, &mut client, b"GET / HTTP/1.1\r\n", now);
+        assert!(!conn.is_timed_out(now + Duration::from_secs(1)));
+        assert!(conn.is_timed_out(now + Duration::from_secs(21)));
+    }
+
+    #[test]
+    fn converts_panic_into_internal_server_error() {
+        let router = Router::default()
+            .get("/panic", |_: Request, _: Params| -> Response {
+                panic!("secret details")
+            })
+            .get("/ok", |_: Request, _: Params| Response::new())
+            .try_into_handler()
+            .expect("invariant");
+
+        let res = handle(&router, Request::new().uri("/panic"));
+        assert_eq!(res.status, Status::InternalServerError);
+        assert!(!String::from_utf8_lossy(&res.body).contains("secret"));
+
+        // Handler keeps working after a panic
+        let res = handle(&router, Request::new().uri("/ok"));
+        assert_eq!(res.status, Status::Ok);
     }
 }
@@ -0,0 +1,164 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Worker pool for request handling.
+
+use crossbeam::channel::{unbounded, Receiver, Sender, TryIter};
+use mio::{Token, Waker};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::handler::Handler;
+use crate::http::response::ResponseExt;
+use crate::http::{Request, Response, Status};
+
+use super::connection::handle;
+use super::{Error, Result};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Worker pool for request handling.
+///
+/// Workers receive complete requests as raw bytes, together with the token of
+/// the connection they were read from, which serves as the correlation key for
+/// the response. After handling a request, workers hand the response back and
+/// wake the event loop, which then writes the response to the connection.
+pub struct Pool {
+    /// Sender for requests.
+    sender: Option<Sender<(Token, Vec<u8>)>>,
+    /// Receiver for responses.
+    receiver: Receiver<(Token, Response)>,
+    /// Worker threads.
+    threads: Vec<JoinHandle<Result>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Pool {
+    /// Creates a worker pool with the given number of workers.
+    ///
+    /// The handler is shared among all workers, which is why it must be both
+    /// [`Send`] and [`Sync`]. The waker is used to notify the event loop once
+    /// a response is ready to be written.
+    pub fn new<H>(
+        handler: &Arc<H>, workers: usize, waker: &Arc<Waker>,
+    ) -> Result<Self>
+    where
+        H: Handler + Send + Sync + 'static,
+    {
+        let (sender, requests) = unbounded::<(Token, Vec<u8>)>();
+        let (responses, receiver) = unbounded();
+
+        // Spawn workers, each of which handles requests until the sender is
+        // dropped, which happens when the pool is dropped
+        let iter = (0..workers).map(|n| {
+            let handler = Arc::clone(handler);
+            let requests = requests.clone();
+            let responses = responses.clone();
+            let waker = Arc::clone(waker);
+            let builder = thread::Builder::new();
+            let builder = builder.name(format!("zensical-worker-{n}"));
+            builder.spawn(move || {
+                for (token, buffer) in requests {
+                    // Requests were already parsed by the event loop, so they
+                    // are complete, but we still handle errors gracefully
+                    let res = match Request::from_bytes(&buffer) {
+                        Ok(req) => handle(handler.as_ref(), req),
+                        Err(_) => Response::from_status(Status::BadRequest),
+                    };
+
+                    // Hand response back, and wake the event loop - if the
+                    // receiver is gone, the server was dropped, so we're done
+                    if responses.send((token, res)).is_err() {
+                        break;
+                    }
+                    waker.wake()?;
+                }
+                Ok(())
+            })
+        });
+
+        // Collect worker threads and return pool
+        iter.collect::<std::io::Result<_>>()
+            .map(|threads| Self {
+                sender: Some(sender),
+                receiver,
+                threads,
+            })
+            .map_err(Into::into)
+    }
+
+    /// Submits a request read from the connection with the given token.
+    pub fn submit(&self, token: Token, buffer: Vec<u8>) -> Result {
+        self.sender
+            .as_ref()
+            .ok_or(Error::Disconnected)?
+            .send((token, buffer))
+            .map_err(|_| Error::Disconnected)
+    }
+
+    /// Returns the error of a worker that terminated, if any.
+    ///
+    /// Workers only terminate on their own if they fail to wake the event loop,
+    /// as the responses they hand back would never be written, which is why
+    /// the error is propagated to the event loop.
+    pub fn check(&mut self) -> Result {
+        for n in (0..self.threads.len()).rev() {
+            if self.threads[n].is_finished() {
+                let thread = self.threads.swap_remove(n);
+                if let Ok(Err(err)) = thread.join() {
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over all responses that are ready to be written.
+    pub fn responses(&self) -> TryIter<'_, (Token, Response)> {
+        self.receiver.try_iter()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Drop for Pool {
+    /// Stops all workers, and waits for them to finish.
+    ///
+    /// Dropping the sender closes the channel, so workers finish handling the
+    /// requests they're currently processing, and then terminate.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Socket abstractions for TCP and Unix domain sockets.
+
+use mio::event::Source;
+use mio::net::{TcpListener, TcpStream};
+use mio::{Interest, Registry, Token};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+
+#[cfg(unix)]
+use mio::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::fs;
+#[cfg(unix)]
+use std::io::ErrorKind;
+#[cfg(unix)]
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Socket address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Address {
+    /// TCP socket address.
+    Tcp(SocketAddr),
+    /// Unix domain socket path.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Listener for incoming connections.
+#[derive(Debug)]
+pub enum Listener {
+    /// TCP listener.
+    Tcp(TcpListener),
+    /// Unix domain socket listener.
+    #[cfg(unix)]
+    Unix(UnixListener, Socket),
+}
+
+/// Stream of an accepted connection.
+#[derive(Debug)]
+pub enum Stream {
+    /// TCP stream.
+    Tcp(TcpStream),
+    /// Unix domain socket stream.
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Socket file of a Unix domain socket listener.
+///
+/// The socket file is removed when the listener is dropped, but only if it's
+/// still the file that was created when binding, as another server might have
+/// replaced it in the meantime, e.g., when the server is restarted.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct Socket {
+    /// Socket path.
+    path: PathBuf,
+    /// Device and inode of the socket file.
+    id: (u64, u64),
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Listener {
+    /// Binds a listener to the given address.
+    ///
+    /// When binding to a Unix domain socket, a stale socket file left behind
+    /// by a server that didn't shut down cleanly is removed before binding,
+    /// which is detected by trying to connect to it. If another server still
+    /// listens on the socket, binding fails with [`ErrorKind::AddrInUse`].
+    pub fn bind(addr: &Address) -> io::Result<Self> {
+        match addr {
+            Address::Tcp(addr) => TcpListener::bind(*addr).map(Listener::Tcp),
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                remove_stale(path)?;
+                let listener = UnixListener::bind(path)?;
+                fs::metadata(path).map(|metadata| {
+                    let id = (metadata.dev(), metadata.ino());
+                    let path = path.clone();
+                    Listener::Unix(listener, Socket { path, id })
+                })
+            }
+        }
+    }
+
+    /// Accepts a new incoming connection.
+    pub fn accept(&self) -> io::Result<Stream> {
+        match self {
+            Listener::Tcp(listener) => {
+                listener.accept().map(|(stream, _)| Stream::Tcp(stream))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => {
+                listener.accept().map(|(stream, _)| Stream::Unix(stream))
+            }
+        }
+    }
+
+    /// Returns the address the listener is bound to.
+    pub fn local_addr(&self) -> io::Result<Address> {
+        match self {
+            Listener::Tcp(listener) => listener.local_addr().map(Address::Tcp),
+            #[cfg(unix)]
+            Listener::Unix(_, socket) => Ok(Address::Unix(socket.path.clone())),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Address {
+    /// Formats the address for display.
+    ///
+    /// Unix domain socket paths are prefixed with `unix:`, which is the same
+    /// notation as accepted by the preview server's `dev_addr` setting.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Address::Tcp(addr) => addr.fmt(f),
+            #[cfg(unix)]
+            Address::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Source for Listener {
+    fn register(
+        &mut self, registry: &Registry, token: Token, interest: Interest,
+    ) -> io::Result<()> {
+        match self {
+            Listener::Tcp(listener) => {
+                listener.register(registry, token, interest)
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => {
+                listener.register(registry, token, interest)
+            }
+        }
+    }
+
+    fn reregister(
+        &mut self, registry: &Registry, token: Token, interest: Interest,
+    ) -> io::Result<()> {
+        match self {
+            Listener::Tcp(listener) => {
+                listener.reregister(registry, token, interest)
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => {
+                listener.reregister(registry, token, interest)
+            }
+        }
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        match self {
+            Listener::Tcp(listener) => listener.deregister(registry),
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => listener.deregister(registry),
+        }
+    }
+}
+
+impl Source for Stream {
+    fn register(
+        &mut self, registry: &Registry, token: Token, interest: Interest,
+    ) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.register(registry, token, interest),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.register(registry, token, interest),
+        }
+    }
+
+    fn reregister(
+        &mut self, registry: &Registry, token: Token, interest: Interest,
+    ) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.reregister(registry, token, interest),
+            #[cfg(unix)]
+            Stream::Unix(stream) => {
+                stream.reregister(registry, token, interest)
+            }
+        }
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.deregister(registry),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.deregister(registry),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(unix)]
+impl Drop for Socket {
+    /// Removes the socket file, unless it was replaced in the meantime.
+    fn drop(&mut self) {
+        if let Ok(metadata) = fs::metadata(&self.path) {
+            if (metadata.dev(), metadata.ino()) == self.id {
+                let _ = fs::remove_file(&self.path);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Removes the socket file at the given path, if it's stale.
+///
+/// A socket file is stale if no server accepts connections on it anymore. We
+/// only ever remove sockets, so that a misconfigured path can't delete files.
+#[cfg(unix)]
+fn remove_stale(path: &Path) -> io::Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    // Only remove the file if it's a socket nobody listens on
+    if !metadata.file_type().is_socket() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        ));
+    }
+    match std::os::unix::net::UnixStream::connect(path) {
+        Ok(_) => Err(io::Error::new(
+            ErrorKind::AddrInUse,
+            format!("{} is in use by another server", path.display()),
+        )),
+        Err(err) if err.kind() == ErrorKind::ConnectionRefused => {
+            fs::remove_file(path)
+        }
+        Err(err) => Err(err),
+    }
+}
@@ -83,6 +83,23 @@ impl Agent {
     ///
     /// Panics if thread creation fails.
     pub fn new<F>(timeout: Duration, mode: bool, f: F) -> Self
+    where
+        F: FnMut(Result<Event>) -> Result + Send + 'static,
+    {
+        Self::with_manager(timeout, mode, Manager::default(), f)
+    }
+
+    /// Creates a file agent with the given file manager.
+    ///
+    /// This allows to configure the file manager, e.g., to walk specific
+    /// hidden directories, which are skipped by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if thread creation fails.
+    pub fn with_manager<F>(
+        timeout: Duration, mode: bool, manager: Manager, f: F,
+    ) -> Self
     where
         F: FnMut(Result<Event>) -> Result + Send + 'static,
     {
@@ -92,6 +109,7 @@ impl Agent {
                 .receiver(receiver)
                 .handler(f)
                 .monitor(Monitor::default())
+                .manager(manager)
                 .build()?;
 
             // Start event loop, which will automatically exit when the file
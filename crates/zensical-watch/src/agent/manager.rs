@@ -31,7 +31,7 @@ use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::{fs, io};
+use std::{fmt, fs, io};
 use walkdir::{DirEntry, WalkDir};
 
 use super::event::{Event, Kind};
@@ -94,8 +94,18 @@ pub struct Manager {
     links: BTreeMap<Arc<PathBuf>, Vec<Arc<PathBuf>>>,
     /// File identifiers map.
     ids: HashMap<FileId, Arc<PathBuf>>,
+    /// Filter for hidden directories.
+    hidden: Hidden,
 }
 
+/// Filter for hidden directories.
+///
+/// Hidden directories are skipped when walking directory trees, unless the
+/// filter admits them, which allows to watch specific hidden directories, e.g.,
+/// `.well-known`, without watching all of them, like `.git`.
+#[derive(Default)]
+struct Hidden(Option<Box<dyn Fn(&Path) -> bool + Send>>);
+
 // ----------------------------------------------------------------------------
 // Implementations
 // ----------------------------------------------------------------------------
@@ -116,6 +126,31 @@ impl Manager {
         Self::default()
     }
 
+    /// Sets the filter for hidden directories.
+    ///
+    /// The filter receives the path of each hidden directory that is found
+    /// when walking directory trees, and returns whether it should be walked.
+    /// By default, all hidden directories are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zensical_watch::agent::Manager;
+    ///
+    /// // Create file manager, which walks `.well-known` directories
+    /// let manager = Manager::new().with_hidden(|path| {
+    ///     path.file_name().is_some_and(|name| name == ".well-known")
+    /// });
+    /// ```
+    #[must_use]
+    pub fn with_hidden<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + 'static,
+    {
+        self.hidden = Hidden(Some(Box::new(f)));
+        self
+    }
+
     /// Handles a set of paths and generates events.
     ///
     /// This method takes an iterator of paths, and then, depending on whether
@@ -150,6 +185,7 @@ impl Manager {
     ///     println!("{:?}", result);
     /// }
     /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn handle<T>(&mut self, paths: T) -> Vec<Result<Event>>
     where
         T: IntoIterator,
@@ -294,7 +330,7 @@ impl Manager {
 
     /// Handles a creation event.
     fn handle_create(&mut self, root: &PathBuf) -> Vec<Result<Event>> {
-        let iter = walk(root).filter_map(|item| {
+        let iter = walk(root, &self.hidden).filter_map(|item| {
             item.and_then(|entry| {
                 let kind = entry.file_type();
                 let path = entry.into_path();
@@ -355,7 +391,7 @@ impl Manager {
 
     /// Handles a rename event.
     fn handle_rename(&mut self, root: &PathBuf) -> Vec<Result<Event>> {
-        let iter = walk(root).filter_map(|item| {
+        let iter = walk(root, &self.hidden).filter_map(|item| {
             item.and_then(|entry| {
                 let path = entry.path();
 
@@ -741,6 +777,26 @@ impl Manager {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+impl Hidden {
+    /// Returns whether the given hidden directory should be walked.
+    fn admits(&self, path: &Path) -> bool {
+        self.0.as_ref().is_some_and(|f| f(path))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for Hidden {
+    /// Formats the filter for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Hidden").field(&self.0.is_some()).finish()
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
@@ -758,7 +814,9 @@ impl Manager {
 /// defined. Although it's possible to sort the files inside of a folder before
 /// yielding, it would be a significant performance hit for a merely cosmetic
 /// benefit, as the order of files inside of a folder is not relevant for us.
-fn walk<P>(path: P) -> impl Iterator<Item = Result<DirEntry>>
+fn walk<P>(
+    path: P, hidden: &Hidden,
+) -> impl Iterator<Item = Result<DirEntry>> + '_
 where
     P: AsRef<Path>,
 {
@@ -769,10 +827,12 @@ where
         // For now we skip hidden directories to speed up the build, since we
         // do not need to watch icons, but in general we need to find a better
         // method in the future when we integrate large asset directories and
-        // libraries that include thousands of icons.
+        // libraries that include thousands of icons. Hidden directories that
+        // are admitted by the filter are walked, e.g., `.well-known`.
         .filter_entry(|item| {
             !(item.file_type().is_dir()
                 && item.file_name().to_str().unwrap_or("").starts_with('.'))
+                || hidden.admits(item.path())
         })
         .map(|item| item.map_err(Into::into))
 }
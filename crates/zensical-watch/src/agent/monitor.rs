@@ -391,6 +391,10 @@ impl Monitor {
     /// a path is added or removed, as the set of watched paths might change,
     /// because a path that is added might cover an actively watched path.
     #[allow(clippy::bool_comparison)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(paths = self.paths.len()))
+    )]
     fn configure(&mut self) -> Result<bool> {
         let mut defer = Vec::new();
 
@@ -0,0 +1,430 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Directory cleaning.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+mod error;
+
+pub use error::{Error, Result};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Number of attempts to remove a file or directory.
+const ATTEMPTS: u32 = 4;
+
+/// Initial delay between attempts, which is doubled after each attempt.
+const BACKOFF: Duration = Duration::from_millis(50);
+
+// ----------------------------------------------------------------------------
+// Traits
+// ----------------------------------------------------------------------------
+
+/// Removal of files and directories.
+///
+/// This abstracts over the file system, so failures can be simulated in tests,
+/// e.g., files that are held open by a browser or virus scanner on Windows.
+pub trait Remove {
+    /// Removes the file at the given path.
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    /// Removes the empty directory at the given path.
+    fn remove_dir(&self, path: &Path) -> io::Result<()>;
+
+    /// Removes the directory at the given path with all of its contents.
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()>;
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// File system.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileSystem;
+
+/// Path that was skipped, as it couldn't be removed.
+#[derive(Debug)]
+pub struct Skipped {
+    /// Path of the file or directory.
+    pub path: PathBuf,
+    /// Underlying I/O error.
+    pub source: io::Error,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Remove for FileSystem {
+    /// Removes the file at the given path.
+    #[inline]
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    /// Removes the empty directory at the given path.
+    #[inline]
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir(path)
+    }
+
+    /// Removes the directory at the given path with all of its contents.
+    #[inline]
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir_all(path)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Skipped {
+    /// Formats the skipped path for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Skipping '{}': {}", self.path.display(), self.source)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Clears the contents of a directory without removing the directory itself.
+///
+/// Only non-hidden paths (not starting with `.`) are removed to match MkDocs'
+/// behavior. This allows users to track the (empty) site folder by adding a
+/// `.gitkeep` file within it. Files that can't be removed, e.g., because they
+/// are locked, are skipped, and returned, so the caller can warn about them.
+///
+/// # Errors
+///
+/// This function returns an error if the directory can't be read.
+pub fn clear_dir(dir: &Path) -> Result<Vec<Skipped>> {
+    let mut skipped = Vec::new();
+    clear_dir_with(&FileSystem, dir, true, &mut skipped)?;
+    Ok(skipped)
+}
+
+/// Removes a directory and all of its contents.
+///
+/// If the directory can't be removed after a few attempts, its contents are
+/// removed one by one, skipping files that are locked, so the directory is as
+/// clean as possible. Skipped files are returned, so the caller can warn.
+///
+/// # Errors
+///
+/// This function returns an error if the directory can't be read.
+pub fn remove_dir(dir: &Path) -> Result<Vec<Skipped>> {
+    let mut skipped = Vec::new();
+    remove_dir_with(&FileSystem, dir, &mut skipped)?;
+    Ok(skipped)
+}
+
+/// Clears the contents of a directory with the given removal implementation.
+fn clear_dir_with<R>(
+    remove: &R, dir: &Path, keep_hidden: bool, skipped: &mut Vec<Skipped>,
+) -> Result
+where
+    R: Remove,
+{
+    let unusable = |source| Error::Unusable {
+        path: dir.to_path_buf(),
+        source,
+    };
+    for entry in fs::read_dir(dir).map_err(unusable)? {
+        let path = entry.map_err(unusable)?.path();
+        if !(keep_hidden && is_hidden(&path)) {
+            remove_path(remove, &path, skipped);
+        }
+    }
+    Ok(())
+}
+
+/// Removes a directory with the given removal implementation.
+fn remove_dir_with<R>(
+    remove: &R, dir: &Path, skipped: &mut Vec<Skipped>,
+) -> Result
+where
+    R: Remove,
+{
+    if retry(|| remove.remove_dir_all(dir)).is_ok() {
+        return Ok(());
+    }
+
+    // Fall back to removing the contents of the directory one by one, and
+    // try to remove the directory itself, which only succeeds if it's empty
+    clear_dir_with(remove, dir, false, skipped)?;
+    let _ = remove.remove_dir(dir);
+    Ok(())
+}
+
+/// Removes a file or directory, skipping all files that can't be removed.
+fn remove_path<R>(remove: &R, path: &Path, skipped: &mut Vec<Skipped>)
+where
+    R: Remove,
+{
+    let is_dir = path.is_dir();
+    let res = retry(|| {
+        if is_dir {
+            remove.remove_dir_all(path)
+        } else {
+            remove.remove_file(path)
+        }
+    });
+
+    // If a file can't be removed, there's nothing more we can do about it
+    let Err(source) = res else { return };
+    if !is_dir {
+        skipped.push(Skipped {
+            path: path.to_path_buf(),
+            source,
+        });
+        return;
+    }
+
+    // If a directory can't be removed, remove its contents one by one, and
+    // then the directory itself, unless some of its contents were skipped
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            remove_path(remove, &entry.path(), skipped);
+        }
+    }
+    if let Err(source) = remove.remove_dir(path) {
+        let is_empty = fs::read_dir(path)
+            .is_ok_and(|mut entries| entries.next().is_none());
+        if is_empty {
+            skipped.push(Skipped {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    }
+}
+
+/// Runs the given removal function until it succeeds, with backoff.
+///
+/// Paths that don't exist anymore are considered to be removed, since they
+/// might have been removed concurrently, e.g., by a virus scanner. Only
+/// transient errors are retried, as all others would just fail again.
+fn retry<F>(mut f: F) -> io::Result<()>
+where
+    F: FnMut() -> io::Result<()>,
+{
+    let mut backoff = BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) if is_transient(&err) && attempt < ATTEMPTS => {
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Returns whether the given error is transient, i.e., might not recur.
+///
+/// Files that are held open by another process can't be removed on Windows,
+/// which is reported as a sharing or lock violation, and directories might
+/// be written to concurrently, so they're not empty when they're removed.
+fn is_transient(err: &io::Error) -> bool {
+    use io::ErrorKind::{
+        DirectoryNotEmpty, Interrupted, ResourceBusy, TimedOut, WouldBlock,
+    };
+    matches!(
+        err.kind(),
+        DirectoryNotEmpty | Interrupted | ResourceBusy | TimedOut | WouldBlock
+    ) || cfg!(windows) && matches!(err.raw_os_error(), Some(32 | 33))
+}
+
+/// Returns whether the given path is hidden, i.e., starts with a dot.
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::fs;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
+
+    use super::{clear_dir_with, remove_dir_with, retry, FileSystem, Remove};
+
+    /// File system that fails to remove locked paths.
+    struct Locked {
+        /// Locked paths.
+        paths: Vec<PathBuf>,
+        /// Number of failed attempts before removal succeeds, if any.
+        release_after: Option<u32>,
+        /// Number of failed attempts.
+        failures: Cell<u32>,
+    }
+
+    impl Locked {
+        /// Returns an error, if the given path is locked.
+        fn check(&self, path: &Path) -> io::Result<()> {
+            let locked =
+                self.paths.iter().any(|locked| locked.starts_with(path));
+            let released = self
+                .release_after
+                .is_some_and(|after| self.failures.get() >= after);
+            if locked && !released {
+                self.failures.set(self.failures.get() + 1);
+                return Err(io::Error::from(io::ErrorKind::ResourceBusy));
+            }
+            Ok(())
+        }
+    }
+
+    impl Remove for Locked {
+        fn remove_file(&self, path: &Path) -> io::Result<()> {
+            self.check(path)?;
+            FileSystem.remove_file(path)
+        }
+
+        fn remove_dir(&self, path: &Path) -> io::Result<()> {
+            self.check(path)?;
+            FileSystem.remove_dir(path)
+        }
+
+        fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+            self.check(path)?;
+            FileSystem.remove_dir_all(path)
+        }
+    }
+
+    #[test]
+    fn clear_dir_retries_until_released() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("index.html");
+        fs::write(&file, "hello").unwrap();
+
+        let remove = Locked {
+            paths: vec![file.clone()],
+            release_after: Some(2),
+            failures: Cell::new(0),
+        };
+        let mut skipped = Vec::new();
+        clear_dir_with(&remove, dir.path(), true, &mut skipped).unwrap();
+        assert!(!file.exists());
+        assert!(skipped.is_empty());
+        assert_eq!(remove.failures.get(), 2);
+    }
+
+    #[test]
+    fn retry_skips_permanent_errors() {
+        let attempts = Cell::new(0);
+        let res = retry(|| {
+            attempts.set(attempts.get() + 1);
+            Err(io::Error::from(io::ErrorKind::PermissionDenied))
+        });
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 1);
+
+        // Paths that don't exist are considered to be removed
+        attempts.set(0);
+        let res = retry(|| {
+            attempts.set(attempts.get() + 1);
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        });
+        assert!(res.is_ok());
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn clear_dir_skips_locked_files() {
+        let dir = tempdir().unwrap();
+        let subdir = dir.path().join("assets");
+        let locked = subdir.join("locked.css");
+        fs::create_dir(&subdir).unwrap();
+        fs::write(&locked, "locked").unwrap();
+        fs::write(subdir.join("other.css"), "other").unwrap();
+        fs::write(dir.path().join("index.html"), "hello").unwrap();
+
+        let remove = Locked {
+            paths: vec![locked.clone()],
+            release_after: None,
+            failures: Cell::new(0),
+        };
+        let mut skipped = Vec::new();
+        clear_dir_with(&remove, dir.path(), true, &mut skipped).unwrap();
+        assert!(locked.exists());
+        assert!(!subdir.join("other.css").exists());
+        assert!(!dir.path().join("index.html").exists());
+
+        // Locked files are returned, so the caller can warn about them
+        let paths = skipped.iter().map(|skipped| &skipped.path);
+        assert_eq!(paths.collect::<Vec<_>>(), [&locked]);
+    }
+
+    #[test]
+    fn clear_dir_fails_for_unusable_directory() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing");
+        let mut skipped = Vec::new();
+        let err = clear_dir_with(&FileSystem, &missing, true, &mut skipped)
+            .unwrap_err();
+        assert!(err.to_string().contains(&*missing.to_string_lossy()));
+    }
+
+    #[test]
+    fn remove_dir_falls_back_to_contents() {
+        let dir = tempdir().unwrap();
+        let cache = dir.path().join(".cache");
+        let locked = cache.join("locked.json");
+        fs::create_dir(&cache).unwrap();
+        fs::write(&locked, "locked").unwrap();
+        fs::write(cache.join(".hidden"), "hidden").unwrap();
+
+        let remove = Locked {
+            paths: vec![locked.clone()],
+            release_after: None,
+            failures: Cell::new(0),
+        };
+        let mut skipped = Vec::new();
+        remove_dir_with(&remove, &cache, &mut skipped).unwrap();
+        assert!(locked.exists());
+        assert!(!cache.join(".hidden").exists());
+    }
+}
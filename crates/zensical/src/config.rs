@@ -28,22 +28,41 @@
 use fluent_uri::Uri;
 use pyo3::types::PyAnyMethods;
 use pyo3::{PyErr, Python};
-use std::fs;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use zrx::path::PathExt;
+use std::{fs, io};
 
+use super::hash::{stable_hash_dirs, StableHasher};
+#[cfg(feature = "native-markdown")]
+use super::structure::markdown::native;
+use super::structure::url;
+use super::workflow::Selector;
+
+pub mod build;
+pub mod cache;
+pub mod diagnostics;
 mod error;
 pub mod extra;
+pub mod markdown;
 pub mod mdx;
+pub mod meta_schema;
+pub mod navigation;
+pub mod permalink;
 pub mod plugins;
 mod project;
+pub mod repository;
+pub mod serve;
 pub mod theme;
 pub mod validation;
 
 pub use error::Result;
+use extra::{flatten, is_missing, resolve_outside};
+use markdown::Engine;
+use permalink::Permalinks;
 pub use project::Project;
+use repository::Repository;
+use theme::{Asset, Feature};
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -65,6 +84,18 @@ pub struct Config {
     pub theme_dirs: Vec<PathBuf>,
     /// Configuration hash.
     pub hash: u64,
+    /// Hash of the contents of all files in the theme directories.
+    pub templates_hash: u64,
+    /// Theme files outside of the docs and theme directories, i.e., logo,
+    /// favicon and extra files, together with the site-relative URLs they must
+    /// be copied to.
+    pub theme_files: Vec<(PathBuf, String)>,
+    /// Notes on settings that were adjusted, included in the build summary.
+    pub notes: Vec<String>,
+    /// Warnings about settings, which are reported when building the site.
+    pub warnings: Vec<String>,
+    /// Permalinks, compiled from the permalink settings.
+    pub permalinks: Permalinks,
 }
 
 // ----------------------------------------------------------------------------
@@ -76,56 +107,316 @@ impl Config {
     ///
     /// This method supports `mkdocs.yml`, as well as `zensical.toml` files.
     /// Right now, parsing is done in Python for compatibility with MkDocs.
+    /// If the project builds multiple sites, the first site is returned.
     pub fn new<P>(path: P) -> Result<Self>
     where
         P: AsRef<Path>,
     {
         let path = path.as_ref();
-        Python::attach(|py| {
-            // Reset global data in compatibility modules
-            py.import("zensical.extensions.autorefs")?
-                .call_method0("reset")?;
-            py.import("zensical.compat.mkdocstrings")?
-                .call_method0("reset")?;
-
-            // Configuration is parsed in Python, since we must support certain
-            // YAML tags like `!ENV`, and allow to reference Python functions
-            // in configuration. For TOML, this is technically not necessary,
-            // but we'll move it through the same pipeline for consistency.
-            let module = py.import("zensical.config")?;
-            let config = module
-                .call_method1("parse_config", (path.to_string_lossy(),))?
-                .extract::<Project>()?;
-
-            // Return configuration and theme directory
-            Ok::<_, PyErr>(config)
-        })
-        .map_err(Into::into)
-        .and_then(|project| {
-            // Merge theme directories, giving precedence to custom directory
-            // over the main theme directory to allow for overrides
-            let iter = project.theme_dirs.clone().into_iter();
-            let theme_dirs = iter
-                .map(|path| path.canonicalize().expect("invariant"))
-                .collect();
-
-            // Precompute hash
-            let hash = {
-                let mut hasher = DefaultHasher::default();
-                project.hash(&mut hasher);
-                hasher.finish()
+        let mut projects = load(path, false)?;
+        Self::from_project(path, projects.swap_remove(0))
+    }
+
+    /// Creates the configurations of all sites of the file at given path.
+    ///
+    /// Projects can build multiple sites from a single configuration file, all
+    /// of which share the same settings, except for the docs and site directory,
+    /// navigation and site URL. Without sites, the project is the only site.
+    pub fn sites<P>(path: P) -> Result<Vec<Self>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let projects = load(path, true)?;
+        let iter = projects.into_iter();
+        iter.map(|project| Self::from_project(path, project))
+            .collect()
+    }
+
+    /// Creates a configuration from the parsed settings of a site.
+    #[allow(clippy::too_many_lines)]
+    fn from_project(path: &Path, mut project: Project) -> Result<Self> {
+        // Warn about settings that can't be expressed in the format of the
+        // configuration file, as they're removed during normalization
+        let mut warnings = Vec::new();
+        let format = path.file_name().unwrap_or_default().to_string_lossy();
+        for key in &project.unsupported_keys {
+            warnings
+                .push(format!("Setting '{key}' is not supported via {format}"));
+        }
+
+        // Warn about unknown feature flags, which are most likely typos
+        for (name, suggestion) in project.theme.features.unknown() {
+            let hint = suggestion
+                .map(|feature| format!(", did you mean '{}'?", feature.name()))
+                .unwrap_or_default();
+            warnings.push(format!("Unknown theme feature '{name}'{hint}"));
+        }
+
+        // Warn about encodings that are not supported by this build, as
+        // precompressed copies are not written for them
+        for encoding in &project.build.precompress {
+            if !encoding.is_supported() {
+                warnings.push(format!(
+                    "Precompression with '{encoding}' is not supported by \
+                     this build"
+                ));
+            }
+        }
+
+        // Warn about Markdown engines that are not supported by this build,
+        // and fall back to Python, which supports all extensions
+        let markdown = &mut project.markdown;
+        if !markdown.engine.is_supported() {
+            warnings.push(format!(
+                "Markdown engine '{}' is not supported by this build, using \
+                 'python'",
+                markdown.engine
+            ));
+            markdown.engine = Engine::Python;
+        }
+
+        // Warn about extensions the native engine doesn't support, or only
+        // partially, so it's clear how the output differs from Python
+        #[cfg(feature = "native-markdown")]
+        if markdown.engine == Engine::Native {
+            let (unsupported, partial) =
+                native::differences(&markdown.extensions);
+            if !unsupported.is_empty() {
+                warnings.push(format!(
+                    "Markdown extensions not supported by the native engine, \
+                     which are ignored: {}",
+                    unsupported.join(", ")
+                ));
+            }
+            for (name, note) in partial {
+                warnings.push(format!(
+                    "Markdown extension '{name}' is partially supported by the \
+                     native engine: {note}"
+                ));
+            }
+        }
+
+        // Warn about invalid patterns for hidden files, and ignore them,
+        // so that the workflow can rely on all patterns being valid
+        let docs_dir = project.docs_dir.clone();
+        project.build.include_hidden.retain(|pattern| {
+            let res = Selector::glob(pattern);
+            if let Err(err) = &res {
+                warnings.push(format!(
+                    "Invalid pattern '{pattern}' in 'build.include_hidden': \
+                     {err}"
+                ));
+            }
+            res.is_ok()
+        });
+
+        // Warn about invalid patterns for permalinks, and ignore them, as
+        // pages just keep the URLs derived from their locations then
+        project.permalinks.retain(|permalink| {
+            let pattern = &permalink.pattern;
+            let res = Selector::glob(pattern);
+            if let Err(err) = &res {
+                warnings.push(format!(
+                    "Invalid pattern '{pattern}' in 'permalinks': {err}"
+                ));
+            }
+            res.is_ok()
+        });
+        let permalinks = Permalinks::new(&docs_dir, &project.permalinks);
+
+        // Derive repository name and icon from the repository URL, where
+        // a repository name that is set explicitly always takes precedence
+        if let Some(url) = &project.repo_url {
+            match Repository::from_url(url) {
+                Some(repo) => {
+                    if project.repo_name.as_ref().is_none_or(String::is_empty) {
+                        project.repo_name = Some(repo.name);
+                    }
+                    project.repo_icon = Some(repo.host.icon().to_string());
+                }
+                None => warnings.push(format!(
+                    "Setting 'repo_url' must be an HTTP(S) URL, but got '{url}'"
+                )),
+            }
+        }
+
+        // Disable theme features that require a server, if the site is
+        // built for offline usage, and note it in the build summary
+        let mut notes = Vec::new();
+        if project.plugins.offline.config.enabled {
+            for feature in Feature::SERVER_ONLY {
+                if project.theme.features.remove(feature) {
+                    notes.push(format!(
+                        "Theme feature '{}' is disabled, as it doesn't \
+                         work offline",
+                        feature.name()
+                    ));
+                }
+            }
+        }
+
+        // Merge theme directories, giving precedence to custom directory
+        // over the main theme directory to allow for overrides
+        let iter = project.theme_dirs.clone().into_iter();
+        let theme_dirs = iter
+            .map(|path| path.canonicalize().expect("invariant"))
+            .collect::<Vec<_>>();
+
+        // Resolve extra CSS and JavaScript files outside of the docs
+        // directory, which are copied to the site directory, and rewrite
+        // them to site-relative URLs, as they're not copied otherwise.
+        // Files with the same name are given distinct URLs.
+        let root_dir = path.parent().unwrap_or(Path::new(""));
+        let docs_dir = root_dir.join(&project.docs_dir);
+        let mut theme_files = Vec::new();
+        let scripts = project.extra_javascript.iter_mut();
+        for (kind, value) in project
+            .extra_css
+            .iter_mut()
+            .map(|value| ("stylesheets", value))
+            .chain(scripts.map(|script| ("javascripts", &mut script.path)))
+        {
+            let Some(source) = resolve_outside(value, &docs_dir) else {
+                continue;
             };
+            *value = flatten(kind, &source, &theme_files);
+            if !theme_files.iter().any(|(from, _)| *from == source) {
+                theme_files.push((source, value.clone()));
+            }
+        }
 
-            // Return configuration
-            Ok(Config {
-                path: path.canonicalize()?,
-                project: Arc::new(project),
-                theme_dirs,
-                hash,
-            })
+        // Warn about extra CSS and JavaScript files that don't exist, as
+        // they would result in broken links in every page
+        let scripts = project.extra_javascript.iter();
+        for value in project
+            .extra_css
+            .iter()
+            .chain(scripts.map(|script| &script.path))
+            .filter(|value| !theme_files.iter().any(|(_, url)| url == *value))
+            .filter(|value| is_missing(value, &docs_dir, &theme_dirs))
+        {
+            warnings.push(format!("Extra file '{value}' not found"));
+        }
+
+        // Resolve logo and favicon, and rewrite them to site-relative URLs,
+        // so templates render them relative to the base path of each page
+        let theme = &mut project.theme;
+        for (name, value) in
+            [("logo", &mut theme.logo), ("favicon", &mut theme.favicon)]
+        {
+            let Some(value) = value.as_mut() else {
+                continue;
+            };
+            match Asset::resolve(
+                value,
+                root_dir,
+                &docs_dir,
+                &theme_dirs,
+                &theme_files,
+            ) {
+                Asset::Verbatim => {}
+                Asset::File(url) => *value = url,
+                Asset::Copy(source, url) => {
+                    if !theme_files.iter().any(|(from, _)| *from == source) {
+                        theme_files.push((source, url.clone()));
+                    }
+                    *value = url;
+                }
+                Asset::Missing => {
+                    warnings.push(format!("Theme {name} '{value}' not found"));
+                }
+            }
+        }
+
+        // Precompute hash - the site and cache directories are excluded,
+        // as the output location doesn't affect the contents of outputs
+        let hash = {
+            let mut hasher = StableHasher::default();
+            let mut hashed = project.clone();
+            hashed.site_dir.clear();
+            hashed.cache_dir.clear();
+            hashed.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        // Precompute hash of theme directory contents, as the template hash
+        // of the project only considers modification times, which have a
+        // granularity of one second, and might be preserved when copying
+        let templates_hash = stable_hash_dirs(&theme_dirs)?;
+
+        // Return configuration
+        Ok(Config {
+            path: path.canonicalize()?,
+            project: Arc::new(project),
+            theme_dirs,
+            hash,
+            templates_hash,
+            theme_files,
+            notes,
+            warnings,
+            permalinks,
         })
     }
 
+    /// Overrides the site and cache directories, e.g., from the command line.
+    ///
+    /// Both directories may be absolute paths outside of the project, which
+    /// allows to build from a read-only source checkout. Since neither of them
+    /// is part of the configuration hash, caches remain valid.
+    #[must_use]
+    pub fn with_dirs(
+        mut self, site_dir: Option<&str>, cache_dir: Option<&str>,
+    ) -> Self {
+        if site_dir.is_some() || cache_dir.is_some() {
+            let project = Arc::make_mut(&mut self.project);
+            if let Some(site_dir) = site_dir {
+                project.site_dir = site_dir.to_string();
+            }
+            if let Some(cache_dir) = cache_dir {
+                project.cache_dir = cache_dir.to_string();
+            }
+        }
+        self
+    }
+
+    /// Ensures that the site and cache directories are writable.
+    ///
+    /// This is checked before building, so that a read-only source checkout
+    /// results in a clear error, pointing to the options to relocate both.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if a directory can't be created, or a file
+    /// can't be written to it.
+    pub fn check_writable(&self) -> Result {
+        let root_dir = self.get_root_dir();
+        let mut dirs = vec![("cache", &self.project.cache_dir)];
+        if self.project.build.output.is_none() {
+            dirs.push(("site", &self.project.site_dir));
+        }
+
+        // Create each directory, and write and remove a probe file
+        for (name, dir) in dirs {
+            let path = root_dir.join(dir);
+            let probe = path.join(".zensical-probe");
+            fs::create_dir_all(&path)
+                .and_then(|()| fs::write(&probe, b""))
+                .and_then(|()| fs::remove_file(&probe))
+                .map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!(
+                            "The {name} directory '{}' is not writable: {err} \
+                             - use '--{name}-dir' to choose another location",
+                            path.display()
+                        ),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
     /// Returns the directory the configuration file is located in.
     pub fn get_root_dir(&self) -> PathBuf {
         let mut path = self.path.clone();
@@ -138,10 +429,13 @@ impl Config {
         let mut path = self.path.clone();
         path.pop();
 
-        // Ensure directory exists
+        // Ensure directory exists, but only create it if necessary, as the docs
+        // directory might be located in a read-only source checkout
         let path = path.join(&self.project.docs_dir);
-        fs::create_dir_all(&path)
-            .and_then(|()| path.canonicalize())
+        path.canonicalize()
+            .or_else(|_| {
+                fs::create_dir_all(&path).and_then(|()| path.canonicalize())
+            })
             .expect("invariant")
     }
 
@@ -163,7 +457,7 @@ impl Config {
         path.pop();
 
         // Ensure directory exists
-        let path = path.join(".cache");
+        let path = path.join(&self.project.cache_dir);
         fs::create_dir_all(&path)
             .and_then(|()| path.canonicalize())
             .inspect(|path| {
@@ -175,16 +469,14 @@ impl Config {
             .expect("invariant")
     }
 
-    /// Returns the base URL, derived from the site URL if available.
+    /// Returns the base URL, i.e., the site root relative to the given URL.
+    ///
+    /// The base URL never ends with a slash, so templates can append paths to
+    /// it, and is `.` for pages located at the site root.
     #[allow(clippy::unused_self)]
-    pub fn get_base_url<P>(&self, path: P) -> String
-    where
-        P: AsRef<Path>,
-    {
-        PathBuf::from(".")
-            .relative_to(path)
-            .to_string_lossy()
-            .replace('\\', "/")
+    pub fn get_base_url(&self, url: &str) -> String {
+        let base_url = url::relative_to("", url);
+        base_url.trim_end_matches('/').to_string()
     }
 
     /// Returns the base path, derived from the site URL if available.
@@ -225,3 +517,36 @@ impl Hash for Config {
         state.write_u64(self.hash);
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Loads and parses the configuration file at the given path.
+///
+/// If all is set, the settings of all sites are returned, and otherwise only
+/// the settings of the first site, which is the project itself, unless the
+/// project builds multiple sites.
+fn load(path: &Path, all: bool) -> Result<Vec<Project>> {
+    Python::attach(|py| {
+        // Reset global data in compatibility modules
+        py.import("zensical.extensions.autorefs")?
+            .call_method0("reset")?;
+        py.import("zensical.compat.mkdocstrings")?
+            .call_method0("reset")?;
+
+        // Configuration is parsed in Python, since we must support certain
+        // YAML tags like `!ENV`, and allow to reference Python functions
+        // in configuration. For TOML, this is technically not necessary,
+        // but we'll move it through the same pipeline for consistency.
+        let module = py.import("zensical.config")?;
+        let path = path.to_string_lossy();
+        if all {
+            module.call_method1("parse_sites", (path,))?.extract()
+        } else {
+            let project = module.call_method1("parse_config", (path,))?;
+            project.extract().map(|project| vec![project])
+        }
+    })
+    .map_err(Into::into)
+}
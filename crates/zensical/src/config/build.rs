@@ -0,0 +1,164 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Build settings.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Content encoding for precompressed copies.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    /// Gzip, written to `.gz` siblings.
+    Gzip,
+    /// Brotli, written to `.br` siblings.
+    Brotli,
+}
+
+/// Archive format of the output.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize)]
+pub enum Format {
+    /// Uncompressed tarball.
+    #[serde(rename = "tar")]
+    Tar,
+    /// Gzip-compressed tarball.
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    /// Zip archive.
+    #[serde(rename = "zip")]
+    Zip,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Build settings.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Build {
+    /// Encodings of precompressed copies of text outputs.
+    pub precompress: Vec<Encoding>,
+    /// Minimum size of text outputs to be precompressed, in bytes.
+    pub precompress_min_size: u64,
+    /// Archive to write the site into, instead of the site directory.
+    pub output: Option<Archive>,
+    /// Path of the site manifest, relative to the site directory.
+    pub manifest: Option<String>,
+    /// Front matter keys to include in the site manifest.
+    pub manifest_meta: Vec<String>,
+    /// Whether to embed content hashes into pages, and emit `pages.json`.
+    pub content_hashes: bool,
+    /// Patterns of hidden files to copy, relative to the docs directory.
+    pub include_hidden: Vec<String>,
+}
+
+/// Archive settings.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Archive {
+    /// Archive format.
+    pub format: Format,
+    /// Archive path, relative to the root directory.
+    pub path: PathBuf,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Encoding {
+    /// Returns the file extension of precompressed copies.
+    #[inline]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gz",
+            Encoding::Brotli => "br",
+        }
+    }
+
+    /// Returns whether the encoding is supported by this build.
+    ///
+    /// Brotli support is an optional feature, in order to keep the default
+    /// dependencies lean, so it might not be available.
+    #[inline]
+    pub fn is_supported(self) -> bool {
+        match self {
+            Encoding::Gzip => true,
+            Encoding::Brotli => cfg!(feature = "brotli"),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Encoding {
+    /// Formats the encoding for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Encoding::Gzip => f.write_str("gzip"),
+            Encoding::Brotli => f.write_str("brotli"),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Encoding {
+    type Error = PyErr;
+
+    /// Extracts an encoding from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "gzip" => Ok(Self::Gzip),
+            "brotli" => Ok(Self::Brotli),
+            _ => Err(PyValueError::new_err("Invalid encoding")),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Format {
+    type Error = PyErr;
+
+    /// Extracts an archive format from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "tar" => Ok(Self::Tar),
+            "tar.gz" => Ok(Self::TarGz),
+            "zip" => Ok(Self::Zip),
+            _ => Err(PyValueError::new_err("Invalid archive format")),
+        }
+    }
+}
@@ -27,6 +27,7 @@
 
 use pyo3::FromPyObject;
 use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -45,3 +46,171 @@ pub struct ExtraScript {
     /// Script defer attribute.
     pub defer: bool,
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns whether the given extra CSS or JavaScript file is missing.
+///
+/// Remote URLs and absolute paths are used verbatim by the `url` filter, so
+/// they're never considered missing. All other paths are relative to the docs
+/// directory, but might also be provided by one of the theme directories, as
+/// both are copied to the site directory. Query strings and fragments, e.g.,
+/// used for cache busting, are ignored.
+pub fn is_missing(
+    value: &str, docs_dir: &Path, theme_dirs: &[PathBuf],
+) -> bool {
+    if value.contains("://") || value.starts_with('/') {
+        return false;
+    }
+
+    // Remove query string and fragment, and look up file in docs and theme
+    // directories, as long as the path doesn't leave them
+    let value = value.split(['?', '#']).next().unwrap_or_default();
+    let path = Path::new(value);
+    let mut iter = std::iter::once(docs_dir)
+        .chain(theme_dirs.iter().map(PathBuf::as_path));
+    !(path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        && iter.any(|dir| dir.join(path).is_file()))
+}
+
+/// Resolves the given extra CSS or JavaScript file, if it's outside of the
+/// docs directory.
+///
+/// Paths are relative to the docs directory, which allows to reference shared
+/// files outside of it, e.g., `../shared/brand.css`. Those files are not part
+/// of the docs directory, so they must be copied to the site directory, which
+/// is why the canonical path is returned if the file exists.
+pub fn resolve_outside(value: &str, docs_dir: &Path) -> Option<PathBuf> {
+    if value.contains("://") || value.starts_with('/') {
+        return None;
+    }
+
+    // Remove query string and fragment, and resolve file, which must exist
+    let value = value.split(['?', '#']).next().unwrap_or_default();
+    let path = docs_dir.join(value).canonicalize().ok()?;
+    let docs_dir = docs_dir.canonicalize().ok()?;
+    (path.is_file() && !path.starts_with(docs_dir)).then_some(path)
+}
+
+/// Returns the site-relative URL for the given extra file outside of the docs
+/// directory.
+///
+/// Files are flattened to `assets/{kind}/{name}`, so files with the same name
+/// in different directories would overwrite each other. In this case, a
+/// counter is appended to the file stem until the URL is unique, while files
+/// that were already assigned a URL keep it.
+pub fn flatten(
+    kind: &str, source: &Path, files: &[(PathBuf, String)],
+) -> String {
+    if let Some((_, url)) = files.iter().find(|(file, _)| file == source) {
+        return url.clone();
+    }
+
+    // Determine a URL in the directory of the given kind that isn't taken yet
+    let stem = source.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = source
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    (0..)
+        .map(|n| match n {
+            0 => format!("assets/{kind}/{stem}{extension}"),
+            n => format!("assets/{kind}/{stem}-{n}{extension}"),
+        })
+        .find(|url| files.iter().all(|(_, taken)| taken != url))
+        .expect("invariant")
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
+
+    use super::{flatten, is_missing, resolve_outside};
+
+    #[test]
+    fn remote_urls_are_never_missing() {
+        let dir = tempdir().unwrap();
+        let docs_dir = dir.path().join("docs");
+        for value in [
+            "https://unpkg.com/mermaid/dist/mermaid.esm.mjs",
+            "//cdn.example.com/app.js",
+            "/assets/app.js",
+        ] {
+            assert!(!is_missing(value, &docs_dir, &[]), "{value}");
+        }
+    }
+
+    #[test]
+    fn local_files_are_looked_up_in_docs_and_theme_directories() {
+        let dir = tempdir().unwrap();
+        let docs_dir = dir.path().join("docs");
+        let theme_dir = dir.path().join("overrides");
+        fs::create_dir_all(docs_dir.join("javascripts")).unwrap();
+        fs::create_dir_all(&theme_dir).unwrap();
+        fs::write(docs_dir.join("javascripts/app.mjs"), "").unwrap();
+        fs::write(theme_dir.join("extra.css"), "").unwrap();
+        fs::write(dir.path().join("secret.js"), "").unwrap();
+        let theme_dirs = [theme_dir];
+        assert!(!is_missing("javascripts/app.mjs", &docs_dir, &theme_dirs));
+        assert!(!is_missing("./javascripts/app.mjs?v=1", &docs_dir, &[]));
+        assert!(!is_missing("extra.css", &docs_dir, &theme_dirs));
+        assert!(is_missing("extra.css", &docs_dir, &[]));
+        assert!(is_missing("javascripts/missing.js", &docs_dir, &[]));
+        assert!(is_missing("../secret.js", &docs_dir, &[]));
+    }
+
+    #[test]
+    fn files_outside_of_docs_directory_are_resolved() {
+        let dir = tempdir().unwrap();
+        let docs_dir = dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("stylesheets")).unwrap();
+        fs::create_dir_all(dir.path().join("shared")).unwrap();
+        fs::write(docs_dir.join("stylesheets/extra.css"), "").unwrap();
+        fs::write(dir.path().join("shared/brand.css"), "").unwrap();
+        let brand = dir.path().join("shared/brand.css").canonicalize().unwrap();
+        assert_eq!(
+            resolve_outside("../shared/brand.css?v=1", &docs_dir),
+            Some(brand)
+        );
+        assert_eq!(resolve_outside("stylesheets/extra.css", &docs_dir), None);
+        assert_eq!(resolve_outside("../shared/missing.css", &docs_dir), None);
+        assert_eq!(
+            resolve_outside("https://example.com/a.css", &docs_dir),
+            None
+        );
+    }
+
+    #[test]
+    fn flattened_files_with_the_same_name_are_disambiguated() {
+        let mut files = Vec::new();
+        for source in
+            ["/a/brand.css", "/b/brand.css", "/a/brand.css", "/c/brand"]
+        {
+            let url = flatten("stylesheets", Path::new(source), &files);
+            files.push((PathBuf::from(source), url));
+        }
+        let urls = files
+            .iter()
+            .map(|(_, url)| url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            urls,
+            [
+                "assets/stylesheets/brand.css",
+                "assets/stylesheets/brand-1.css",
+                "assets/stylesheets/brand.css",
+                "assets/stylesheets/brand",
+            ]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Markdown settings.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::fmt;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Engine used to render Markdown.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    /// Python Markdown, with all configured extensions.
+    #[default]
+    Python,
+    /// Native engine, which is experimental, and supports a subset.
+    Native,
+}
+
+/// Slugification mode for headings.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Slugify {
+    /// ASCII only, which is what Python Markdown uses by default.
+    #[default]
+    Default,
+    /// Unicode characters are retained.
+    Unicode,
+    /// Unicode characters are retained, and each space is replaced, like the
+    /// anchors that GitHub generates.
+    Github,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Markdown settings.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Markdown {
+    /// Engine used to render Markdown.
+    pub engine: Engine,
+    /// Names of configured Markdown extensions.
+    pub extensions: Vec<String>,
+    /// Slugification mode for headings, if configured.
+    pub slugify: Option<Slugify>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Engine {
+    /// Returns whether the engine is supported by this build.
+    ///
+    /// The native engine is an optional feature, as it's experimental, so it
+    /// might not be available.
+    #[inline]
+    pub fn is_supported(self) -> bool {
+        match self {
+            Engine::Python => true,
+            Engine::Native => cfg!(feature = "native-markdown"),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Engine {
+    /// Formats the engine for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Engine::Python => f.write_str("python"),
+            Engine::Native => f.write_str("native"),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Engine {
+    type Error = PyErr;
+
+    /// Extracts a Markdown engine from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "python" => Ok(Self::Python),
+            "native" => Ok(Self::Native),
+            _ => Err(PyValueError::new_err("Invalid Markdown engine")),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Slugify {
+    type Error = PyErr;
+
+    /// Extracts a slugification mode from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "default" => Ok(Self::Default),
+            "unicode" => Ok(Self::Unicode),
+            "github" => Ok(Self::Github),
+            _ => Err(PyValueError::new_err("Invalid slugify mode")),
+        }
+    }
+}
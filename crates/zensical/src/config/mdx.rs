@@ -27,6 +27,7 @@
 
 use pyo3::FromPyObject;
 use serde::Serialize;
+use std::path::PathBuf;
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -53,3 +54,22 @@ pub struct TableOfContents {
     /// Table of contents title.
     pub title: Option<String>,
 }
+
+// ----------------------------------------------------------------------------
+
+/// Snippets extension.
+///
+/// Snippets are included by the Python Markdown stack, but we need to know the
+/// base paths to track which files are included by pages, so pages are built
+/// again when any of the included files change. Note that this is not part of
+/// `mdx_configs`, as base paths are resolved by the configuration parser.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Snippets {
+    /// Whether the snippets extension is enabled.
+    pub enabled: bool,
+    /// Base paths to resolve snippets.
+    pub base_path: Vec<PathBuf>,
+    /// Whether missing snippets are errors.
+    pub check_paths: bool,
+}
@@ -0,0 +1,349 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Front matter schema.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::structure::dynamic::{Date, Dynamic};
+
+use super::theme::distance;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Type of a front matter value.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetaType {
+    /// String.
+    String,
+    /// List of values.
+    List,
+    /// Boolean.
+    Bool,
+    /// Date, optionally with time, in a format understood by [`Date::parse`].
+    Date,
+}
+
+/// Problem found when validating front matter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// Key is not part of the schema, with the most similar known key.
+    Unknown(String, Option<String>),
+    /// Value has the wrong type, and could not be coerced.
+    Mismatch(String, MetaType),
+    /// Value had the wrong type, and was coerced.
+    Coerced(String, MetaType),
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Front matter schema.
+///
+/// The schema declares the known front matter keys and their types, so typos
+/// like `ttile` or `tag` can be reported, instead of silently being ignored.
+/// Validation is opt-in - if no schema is configured, anything is allowed.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct MetaSchema {
+    /// Known keys and their types.
+    pub keys: BTreeMap<String, MetaType>,
+    /// Whether to allow keys that are not part of the schema.
+    pub allow_unknown: bool,
+    /// Whether to coerce values to the declared type, if possible.
+    pub coerce: bool,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl MetaType {
+    /// Returns whether the given value has this type.
+    pub fn matches(self, value: &Dynamic) -> bool {
+        match (self, value) {
+            (MetaType::Date, Dynamic::String(value)) => {
+                Date::parse(value).is_some()
+            }
+            (MetaType::Date, Dynamic::Date(_))
+            | (MetaType::String, Dynamic::String(_))
+            | (MetaType::List, Dynamic::List(_))
+            | (MetaType::Bool, Dynamic::Bool(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Coerces the given value to this type, if possible.
+    ///
+    /// Scalars are coerced to strings, strings like `true` or `no` to booleans,
+    /// and scalars to lists with a single item, e.g., `tags: foo`. Dates are
+    /// never coerced, as there's no unambiguous way to interpret them.
+    pub fn coerce(self, value: &Dynamic) -> Option<Dynamic> {
+        match (self, value) {
+            (
+                MetaType::String,
+                Dynamic::Bool(_) | Dynamic::Integer(_) | Dynamic::Float(_),
+            ) => Some(Dynamic::String(value.to_string())),
+            (MetaType::Bool, Dynamic::String(value)) => {
+                match value.to_ascii_lowercase().as_str() {
+                    "true" | "yes" | "on" => Some(Dynamic::Bool(true)),
+                    "false" | "no" | "off" => Some(Dynamic::Bool(false)),
+                    _ => None,
+                }
+            }
+            (MetaType::List, Dynamic::List(_) | Dynamic::Map(_)) => None,
+            (MetaType::List, _) => Some(Dynamic::List(vec![value.clone()])),
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl MetaSchema {
+    /// Validates the given front matter, coercing values, if enabled.
+    ///
+    /// Problems are returned in key order, so they're reported in a stable
+    /// order. Values are only changed if they are coerced.
+    pub fn validate(
+        &self, meta: &mut BTreeMap<String, Dynamic>,
+    ) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        for (key, value) in meta.iter_mut() {
+            let Some(kind) = self.keys.get(key).copied() else {
+                if !self.allow_unknown {
+                    let suggestion = self.suggest(key);
+                    problems.push(Problem::Unknown(key.clone(), suggestion));
+                }
+                continue;
+            };
+
+            // Check type, and coerce value, if enabled and possible
+            if kind.matches(value) {
+                continue;
+            }
+            match kind.coerce(value).filter(|_| self.coerce) {
+                Some(coerced) => {
+                    *value = coerced;
+                    problems.push(Problem::Coerced(key.clone(), kind));
+                }
+                None => problems.push(Problem::Mismatch(key.clone(), kind)),
+            }
+        }
+        problems
+    }
+
+    /// Returns the known key most similar to the given key, if any.
+    ///
+    /// Only keys that are at most two edits away are considered, as keys are
+    /// usually short, so larger distances would result in odd suggestions.
+    fn suggest(&self, key: &str) -> Option<String> {
+        self.keys
+            .keys()
+            .map(|known| (distance(key, known), known))
+            .filter(|(distance, _)| *distance <= 2)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, known)| known.clone())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for MetaType {
+    /// Formats the type for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MetaType::String => f.write_str("string"),
+            MetaType::List => f.write_str("list"),
+            MetaType::Bool => f.write_str("bool"),
+            MetaType::Date => f.write_str("date"),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for MetaType {
+    type Error = PyErr;
+
+    /// Extracts a type from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "string" => Ok(Self::String),
+            "list" => Ok(Self::List),
+            "bool" => Ok(Self::Bool),
+            "date" => Ok(Self::Date),
+            _ => Err(PyValueError::new_err("Invalid front matter type")),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    /// Formats the problem for display.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Unknown(key, Some(suggestion)) => write!(
+                f,
+                "Unknown front matter key '{key}', did you mean \
+                 '{suggestion}'?"
+            ),
+            Problem::Unknown(key, None) => {
+                write!(f, "Unknown front matter key '{key}'")
+            }
+            Problem::Mismatch(key, kind) => {
+                write!(f, "Front matter key '{key}' must be of type {kind}")
+            }
+            Problem::Coerced(key, kind) => {
+                write!(f, "Front matter key '{key}' was coerced to {kind}")
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::structure::dynamic::{Date, Dynamic};
+
+    use super::{MetaSchema, MetaType, Problem};
+
+    /// Creates a schema with title, tags, draft and date keys.
+    fn schema(allow_unknown: bool, coerce: bool) -> MetaSchema {
+        MetaSchema {
+            keys: BTreeMap::from([
+                (String::from("date"), MetaType::Date),
+                (String::from("draft"), MetaType::Bool),
+                (String::from("tags"), MetaType::List),
+                (String::from("title"), MetaType::String),
+            ]),
+            allow_unknown,
+            coerce,
+        }
+    }
+
+    /// Creates front matter from the given key-value pairs.
+    fn meta<const N: usize>(
+        items: [(&str, Dynamic); N],
+    ) -> BTreeMap<String, Dynamic> {
+        items
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect()
+    }
+
+    #[test]
+    fn reports_unknown_keys_with_suggestions() {
+        let mut meta = meta([
+            ("ttile", Dynamic::String(String::from("Setup"))),
+            ("tag", Dynamic::List(Vec::new())),
+            ("author", Dynamic::String(String::from("Jane"))),
+        ]);
+        assert_eq!(
+            schema(false, false).validate(&mut meta),
+            vec![
+                Problem::Unknown(String::from("author"), None),
+                Problem::Unknown(
+                    String::from("tag"),
+                    Some(String::from("tags"))
+                ),
+                Problem::Unknown(
+                    String::from("ttile"),
+                    Some(String::from("title"))
+                ),
+            ]
+        );
+        assert!(schema(true, false).validate(&mut meta).is_empty());
+    }
+
+    #[test]
+    fn reports_wrong_types() {
+        let mut meta = meta([
+            ("date", Dynamic::String(String::from("yesterday"))),
+            ("draft", Dynamic::String(String::from("true"))),
+            ("tags", Dynamic::List(Vec::new())),
+            ("title", Dynamic::String(String::from("Setup"))),
+        ]);
+        assert_eq!(
+            schema(false, false).validate(&mut meta),
+            vec![
+                Problem::Mismatch(String::from("date"), MetaType::Date),
+                Problem::Mismatch(String::from("draft"), MetaType::Bool),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_dates() {
+        for value in ["2024-01-01garbage", "2024-02-30", "2024-13-01"] {
+            let mut meta = meta([
+                ("date", Dynamic::String(String::from(value))),
+                ("title", Dynamic::String(String::from("Setup"))),
+            ]);
+            assert_eq!(
+                schema(false, false).validate(&mut meta),
+                vec![Problem::Mismatch(String::from("date"), MetaType::Date)],
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn coerces_values() {
+        let mut meta = meta([
+            ("date", Dynamic::String(String::from("2026-10-16"))),
+            ("draft", Dynamic::String(String::from("true"))),
+            ("tags", Dynamic::String(String::from("setup"))),
+            ("title", Dynamic::Integer(42)),
+        ]);
+        assert_eq!(
+            schema(false, true).validate(&mut meta),
+            vec![
+                Problem::Coerced(String::from("draft"), MetaType::Bool),
+                Problem::Coerced(String::from("tags"), MetaType::List),
+                Problem::Coerced(String::from("title"), MetaType::String),
+            ]
+        );
+        assert_eq!(meta["draft"], Dynamic::Bool(true));
+        assert_eq!(
+            meta["tags"],
+            Dynamic::List(vec![Dynamic::String(String::from("setup"))])
+        );
+        assert_eq!(meta["title"], Dynamic::String(String::from("42")));
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Navigation settings.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Title of sections collapsed into their single child.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CollapseTitle {
+    /// Keep the title of the section.
+    Parent,
+    /// Use the title of the child.
+    #[default]
+    Child,
+    /// Join the titles of the section and the child.
+    Joined,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Navigation settings.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Navigation {
+    /// Whether to collapse sections with a single child.
+    ///
+    /// This only applies to navigation generated from the docs directory, as
+    /// explicitly configured navigation is never modified.
+    pub collapse_single_children: bool,
+    /// Title of collapsed sections.
+    pub collapse_title: CollapseTitle,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Navigation {
+    /// Returns the title of collapsed sections, if collapsing is enabled.
+    #[must_use]
+    pub fn collapse(&self) -> Option<CollapseTitle> {
+        self.collapse_single_children.then_some(self.collapse_title)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<'a, 'py> FromPyObject<'a, 'py> for CollapseTitle {
+    type Error = PyErr;
+
+    /// Extracts a collapse title from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "parent" => Ok(Self::Parent),
+            "child" => Ok(Self::Child),
+            "joined" => Ok(Self::Joined),
+            _ => Err(PyValueError::new_err("Invalid collapse title")),
+        }
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Permalink settings.
+
+use pyo3::FromPyObject;
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::fmt;
+use std::sync::{Arc, LazyLock};
+use zrx::id::Id;
+
+use crate::structure::dynamic::{Date, Dynamic};
+use crate::structure::page::{page_date, PageMeta};
+use crate::workflow::Selector;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match placeholders, optionally with a format.
+static PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{([a-z]+)(?::([^}]*))?\}").expect("invariant")
+});
+
+/// Date format, if none is given for the `{date}` placeholder.
+const DATE_FORMAT: &str = "%Y/%m/%d";
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Permalink, mapping pages matching a glob pattern to a URL template.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Permalink {
+    /// Glob pattern, relative to the docs directory.
+    pub pattern: String,
+    /// URL template, e.g., `blog/{date:%Y/%m}/{slug}`.
+    pub template: String,
+}
+
+/// Permalinks, compiled for matching.
+///
+/// Permalinks are matched in order of definition, and the first permalink
+/// whose pattern matches the page determines its URL. If the template can't
+/// be expanded, e.g., because the page has no date, the page keeps the URL
+/// derived from its location, as if no permalink would match.
+#[derive(Clone, Default)]
+pub struct Permalinks {
+    /// Selectors and URL templates.
+    inner: Arc<Vec<(Selector, String)>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Permalinks {
+    /// Creates permalinks for the given docs directory.
+    ///
+    /// Invalid patterns are removed when the configuration is loaded, so all
+    /// patterns are guaranteed to be valid at this point.
+    pub fn new(docs_dir: &str, permalinks: &[Permalink]) -> Self {
+        let inner = permalinks
+            .iter()
+            .map(|permalink| {
+                let selector = Selector::glob(&permalink.pattern)
+                    .expect("invariant")
+                    .context(docs_dir);
+                (selector, permalink.template.clone())
+            })
+            .collect();
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the path of the page with the given identifier, if any.
+    ///
+    /// The returned path is relative to the site directory, and has neither
+    /// leading nor trailing slashes, e.g., `blog/2024/05/slug`.
+    pub fn resolve(&self, id: &Id, meta: &PageMeta) -> Option<String> {
+        self.inner
+            .iter()
+            .find(|(selector, _)| selector.is_match(id))
+            .and_then(|(_, template)| expand(template, &id.location(), meta))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for Permalinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let templates = self.inner.iter().map(|(_, template)| template);
+        f.debug_list().entries(templates).finish()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Expands the given URL template for the page at the given location.
+///
+/// The following placeholders are supported:
+///
+/// - `{slug}`: the `slug` from front matter, or the file name without date
+///   prefix and extension, e.g., `slug` for `2024-05-21-slug.md`.
+/// - `{date:format}`: the `date` from front matter, or the date prefix of the
+///   file name, formatted with `%Y`, `%m` and `%d`, e.g., `{date:%Y/%m}`.
+/// - `{path}`: the directory of the page, relative to the docs directory.
+///
+/// Returns [`None`] if a placeholder can't be expanded.
+pub fn expand(
+    template: &str, location: &str, meta: &PageMeta,
+) -> Option<String> {
+    let (dir, name) = location.rsplit_once('/').unwrap_or(("", location));
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let prefix = date_prefix(stem);
+
+    // Expand all placeholders, and bail out if any of them can't be expanded
+    let mut missing = false;
+    let path = PLACEHOLDER_RE.replace_all(template, |captures: &Captures| {
+        let value = match &captures[1] {
+            "slug" => Some(slug(meta, stem, prefix.as_ref())),
+            "path" => Some(dir.to_string()),
+            "date" => date(meta, prefix.as_ref()).map(|date| {
+                let format =
+                    captures.get(2).map_or(DATE_FORMAT, |f| f.as_str());
+                date.format(format)
+            }),
+            _ => None,
+        };
+        value.unwrap_or_else(|| {
+            missing = true;
+            String::new()
+        })
+    });
+    if missing {
+        return None;
+    }
+
+    // Remove empty components, e.g., if the page is in the docs directory
+    let path = path
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>();
+    Some(path.join("/"))
+}
+
+/// Returns the slug from front matter, or from the given file stem.
+fn slug(meta: &PageMeta, stem: &str, prefix: Option<&(Date, &str)>) -> String {
+    if let Some(Dynamic::String(slug)) = meta.get("slug") {
+        if !slug.is_empty() {
+            return slug.clone();
+        }
+    }
+    prefix
+        .and_then(|(_, rest)| rest.strip_prefix('-'))
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(stem)
+        .to_string()
+}
+
+/// Returns the date from front matter, or from the given file prefix.
+fn date(meta: &PageMeta, prefix: Option<&(Date, &str)>) -> Option<Date> {
+    if let Some(date) = page_date(meta) {
+        return Some(date.clone());
+    }
+
+    // Fall back to date prefix of the value in front matter or the file name
+    let value = match meta.get("date") {
+        Some(Dynamic::String(value)) => date_prefix(value),
+        _ => None,
+    };
+    value.as_ref().or(prefix).map(|(date, _)| date.clone())
+}
+
+/// Splits off a date from the first ten characters of the given value.
+fn date_prefix(value: &str) -> Option<(Date, &str)> {
+    let date = Date::parse(value.get(..10)?)?;
+    Some((date, &value[10..]))
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::structure::dynamic::{Date, Dynamic};
+    use crate::structure::page::PageMeta;
+
+    use super::expand;
+
+    /// Creates page metadata from the given key-value pairs.
+    fn meta(pairs: &[(&str, &str)]) -> PageMeta {
+        pairs
+            .iter()
+            .map(|(key, value)| {
+                (key.to_string(), Dynamic::String(value.to_string()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expand_date_prefixed_file_name() {
+        let template = "{path}/{date:%Y/%m}/{slug}";
+        let location = "blog/2024-05-21-slug.md";
+        assert_eq!(
+            expand(template, location, &meta(&[])).as_deref(),
+            Some("blog/2024/05/slug")
+        );
+        assert_eq!(
+            expand("{date}/{slug}", location, &meta(&[])).as_deref(),
+            Some("2024/05/21/slug")
+        );
+    }
+
+    #[test]
+    fn expand_prefers_front_matter() {
+        let template = "blog/{date:%Y}/{slug}";
+        let location = "blog/2024-05-21-slug.md";
+        let meta = meta(&[("date", "2023-01-02 10:00"), ("slug", "other")]);
+        assert_eq!(
+            expand(template, location, &meta).as_deref(),
+            Some("blog/2023/other")
+        );
+    }
+
+    #[test]
+    fn expand_without_date_falls_back() {
+        let location = "blog/slug.md";
+        assert_eq!(expand("{date:%Y}/{slug}", location, &meta(&[])), None);
+        assert_eq!(
+            expand("{path}/{slug}", location, &meta(&[])).as_deref(),
+            Some("blog/slug")
+        );
+        assert_eq!(
+            expand("{path}/{slug}", "slug.md", &meta(&[])).as_deref(),
+            Some("slug")
+        );
+    }
+
+    #[test]
+    fn expand_typed_dates() {
+        let template = "blog/{date:%Y/%b}/{slug}";
+        let location = "blog/2024-05-21-slug.md";
+        let created = Dynamic::Date(Date::parse("2023-01-02").unwrap());
+        let meta = PageMeta::from([(
+            String::from("date"),
+            Dynamic::Map([(String::from("created"), created)].into()),
+        )]);
+        assert_eq!(
+            expand(template, location, &meta).as_deref(),
+            Some("blog/2023/Jan/slug")
+        );
+    }
+}
@@ -48,6 +48,20 @@ pub struct Plugins {
     pub search: SearchPlugin,
     /// Offline plugin.
     pub offline: OfflinePlugin,
+    /// Internationalization plugin.
+    pub i18n: I18nPlugin,
+    /// Reading time plugin.
+    pub reading_time: ReadingTimePlugin,
+    /// Variables plugin.
+    pub variables: VariablesPlugin,
+    /// Images plugin.
+    pub images: ImagesPlugin,
+    /// Blog plugin.
+    pub blog: BlogPlugin,
+    /// Privacy plugin.
+    pub privacy: PrivacyPlugin,
+    /// Social plugin.
+    pub social: SocialPlugin,
 }
 
 // ----------------------------------------------------------------------------
@@ -90,3 +104,231 @@ pub struct OfflinePluginConfig {
     /// Whether the offline plugin is enabled.
     pub enabled: bool,
 }
+
+// ----------------------------------------------------------------------------
+
+/// Internationalization plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct I18nPlugin {
+    /// Plugin configuration.
+    pub config: I18nPluginConfig,
+}
+
+/// Internationalization plugin configuration.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct I18nPluginConfig {
+    /// Whether the internationalization plugin is enabled.
+    pub enabled: bool,
+    /// Languages.
+    pub languages: Vec<Language>,
+}
+
+/// Language.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Language {
+    /// Language locale, e.g., `en`.
+    pub locale: String,
+    /// Language name, e.g., `English`.
+    pub name: String,
+    /// Subdirectory in docs and site directory.
+    pub path: String,
+    /// Whether this is the default language.
+    pub default: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Reading time plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct ReadingTimePlugin {
+    /// Plugin configuration.
+    pub config: ReadingTimePluginConfig,
+}
+
+/// Reading time plugin configuration.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct ReadingTimePluginConfig {
+    /// Whether the reading time plugin is enabled.
+    pub enabled: bool,
+    /// Reading speed in words per minute.
+    pub words_per_minute: usize,
+    /// Whether to count words in code blocks.
+    pub count_code: bool,
+    /// Whether to add word count and reading time to page metadata.
+    pub meta: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Variables plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct VariablesPlugin {
+    /// Plugin configuration.
+    pub config: VariablesPluginConfig,
+}
+
+/// Variables plugin configuration.
+///
+/// In contrast to the macros plugin, which is implemented as a port of the
+/// MkDocs macros plugin in Python, this plugin uses MiniJinja and supports a
+/// restricted context only, rendering Markdown before it's converted.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct VariablesPluginConfig {
+    /// Whether the variables plugin is enabled.
+    pub enabled: bool,
+    /// Whether to render macros inside of code fences.
+    pub render_code: bool,
+    /// Environment variables exposed to Markdown.
+    pub env: Vec<String>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Images plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct ImagesPlugin {
+    /// Plugin configuration.
+    pub config: ImagesPluginConfig,
+}
+
+/// Images plugin configuration.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct ImagesPluginConfig {
+    /// Whether the images plugin is enabled.
+    pub enabled: bool,
+    /// Whether to add width and height of local images.
+    pub dimensions: bool,
+    /// Whether to add lazy loading and asynchronous decoding.
+    pub lazy_loading: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Blog plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct BlogPlugin {
+    /// Plugin configuration.
+    pub config: BlogPluginConfig,
+}
+
+/// Blog plugin configuration.
+///
+/// Directories are relative to the docs directory, and normalized by the
+/// configuration parser, so they have neither leading nor trailing slashes.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct BlogPluginConfig {
+    /// Whether the blog plugin is enabled.
+    pub enabled: bool,
+    /// Blog directory, which determines the URLs of index pages.
+    pub blog_dir: String,
+    /// Posts directory, from which posts are collected.
+    pub post_dir: String,
+    /// Number of posts per index page.
+    pub posts_per_page: usize,
+    /// Front matter key holding the categories of a post.
+    pub categories_key: String,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Privacy plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct PrivacyPlugin {
+    /// Plugin configuration.
+    pub config: PrivacyPluginConfig,
+}
+
+/// Privacy plugin configuration.
+///
+/// Patterns are matched against URLs of external assets without their scheme,
+/// e.g., `fonts.googleapis.com/*`, where `*` matches any sequence of
+/// characters. Assets referenced from downloaded style sheets, e.g., fonts,
+/// are always downloaded, as the style sheet would load them otherwise.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct PrivacyPluginConfig {
+    /// Whether the privacy plugin is enabled.
+    pub enabled: bool,
+    /// Patterns of external assets to download.
+    pub patterns: Vec<String>,
+    /// Maximum number of parallel downloads.
+    pub concurrency: usize,
+    /// Whether to only use previously downloaded assets from the cache.
+    pub offline: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Social plugin.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct SocialPlugin {
+    /// Plugin configuration.
+    pub config: SocialPluginConfig,
+}
+
+/// Social plugin configuration.
+///
+/// Font and logo are paths resolved relative to the configuration file, and
+/// default to the bundled font and no logo. Colors are given in hexadecimal
+/// notation, e.g., `#4051b5`.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct SocialPluginConfig {
+    /// Whether the social plugin is enabled.
+    pub enabled: bool,
+    /// Directory of social cards, relative to the site directory.
+    pub cards_dir: String,
+    /// Path to the TrueType font.
+    pub font_file: String,
+    /// Path to the PNG logo, if any.
+    pub logo: Option<String>,
+    /// Background color.
+    pub background_color: String,
+    /// Text color.
+    pub color: String,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl I18nPluginConfig {
+    /// Returns the default language, if any.
+    ///
+    /// The configuration parser ensures that exactly one language is marked as
+    /// the default, so this only returns nothing when no languages are set.
+    pub fn default_language(&self) -> Option<&Language> {
+        self.languages.iter().find(|language| language.default)
+    }
+
+    /// Returns the language of the given location or URL, together with the
+    /// language-neutral remainder, i.e., with the language prefix stripped.
+    pub fn split<'a>(&self, value: &'a str) -> Option<(&Language, &'a str)> {
+        if !self.enabled {
+            return None;
+        }
+
+        // Find language whose subdirectory is a prefix of the given value
+        self.languages.iter().find_map(|language| {
+            let rest = value.strip_prefix(language.path.as_str())?;
+            if rest.is_empty() {
+                Some((language, rest))
+            } else {
+                rest.strip_prefix('/').map(|rest| (language, rest))
+            }
+        })
+    }
+}
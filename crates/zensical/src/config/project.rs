@@ -27,14 +27,23 @@
 
 use pyo3::FromPyObject;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::structure::dynamic::Dynamic;
 use crate::structure::nav::NavigationItem;
 
+use super::build::Build;
+use super::cache::Cache;
+use super::diagnostics::Diagnostics;
 use super::extra::ExtraScript;
-use super::mdx::MdxConfigs;
+use super::markdown::Markdown;
+use super::mdx::{MdxConfigs, Snippets};
+use super::meta_schema::MetaSchema;
+use super::navigation::Navigation;
+use super::permalink::Permalink;
 use super::plugins::Plugins;
+use super::serve::Serve;
 use super::theme::Theme;
 use super::validation::Validation;
 
@@ -43,7 +52,7 @@ use super::validation::Validation;
 // ----------------------------------------------------------------------------
 
 /// Project settings.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct Project {
     /// Project root directory.
@@ -60,6 +69,8 @@ pub struct Project {
     pub docs_dir: String,
     /// Site directory (outputs).
     pub site_dir: String,
+    /// Cache directory.
+    pub cache_dir: String,
     /// Whether to use directory URLs.
     pub use_directory_urls: bool,
     /// Development server address.
@@ -68,8 +79,10 @@ pub struct Project {
     pub copyright: Option<String>,
     /// Repository URL.
     pub repo_url: Option<String>,
-    /// Repository name.
+    /// Repository name, derived from the repository URL, if not set.
     pub repo_name: Option<String>,
+    /// Repository icon, derived from the repository URL.
+    pub repo_icon: Option<String>,
     /// Edit URI template.
     pub edit_uri_template: Option<String>,
     /// Edit URI.
@@ -80,6 +93,8 @@ pub struct Project {
     pub theme_dirs: Vec<PathBuf>,
     /// Extra settings.
     pub extra: Dynamic,
+    /// Extra context, merged into the top-level context of every render.
+    pub extra_context: BTreeMap<String, Dynamic>,
     /// Extra CSS files.
     pub extra_css: Vec<String>,
     /// Extra JavaScript files.
@@ -90,16 +105,243 @@ pub struct Project {
     pub mdx_configs: MdxConfigs,
     /// Markdown extension configuration hash.
     pub mdx_configs_hash: u64,
+    /// Markdown settings.
+    pub markdown: Markdown,
     /// Plugins.
     pub plugins: Plugins,
     /// Navigation structure.
     pub nav: Vec<NavigationItem>,
+    /// Homepage location, if defined.
+    pub homepage: Option<String>,
+    /// Navigation settings.
+    pub navigation: Navigation,
     /// Validation settings.
     pub validation: Validation,
+    /// Diagnostics settings.
+    pub diagnostics: Diagnostics,
+    /// Front matter schema, if defined.
+    pub meta_schema: Option<MetaSchema>,
+    /// Permalinks, in order of precedence.
+    pub permalinks: Vec<Permalink>,
     /// Template hash.
     pub template_hash: u64,
     /// Plugins hash.
     pub plugins_hash: u64,
     /// Watched files.
     pub watched_files: Vec<(PathBuf, u64)>,
+    /// Snippets extension settings.
+    pub snippets: Snippets,
+    /// Cache settings.
+    pub cache: Cache,
+    /// Build settings.
+    pub build: Build,
+    /// Serve settings.
+    pub serve: Serve,
+    /// Settings not supported via the format of the configuration file.
+    pub unsupported_keys: Vec<String>,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+impl Default for Project {
+    /// Creates project settings with the defaults of the configuration parser,
+    /// so tests can set up projects without parsing them in Python.
+    #[allow(clippy::too_many_lines)]
+    fn default() -> Self {
+        use super::diagnostics::Format;
+        use super::markdown::Engine;
+        use super::mdx::TableOfContents;
+        use super::navigation::CollapseTitle;
+        use super::plugins::{
+            BlogPlugin, BlogPluginConfig, I18nPlugin, I18nPluginConfig,
+            ImagesPlugin, ImagesPluginConfig, OfflinePlugin,
+            OfflinePluginConfig, PrivacyPlugin, PrivacyPluginConfig,
+            ReadingTimePlugin, ReadingTimePluginConfig, SearchPlugin,
+            SearchPluginConfig, SocialPlugin, SocialPluginConfig,
+            VariablesPlugin, VariablesPluginConfig,
+        };
+        use super::serve::ReloadPathStyle;
+        use super::theme::{CustomFont, Features, Font, Icon};
+        use crate::workflow::Severity;
+
+        Self {
+            root_dir: PathBuf::new(),
+            site_name: String::new(),
+            site_url: None,
+            site_description: None,
+            site_author: None,
+            docs_dir: String::from("docs"),
+            site_dir: String::from("site"),
+            cache_dir: String::from(".cache"),
+            use_directory_urls: true,
+            dev_addr: String::from("localhost:8000"),
+            copyright: None,
+            repo_url: None,
+            repo_name: None,
+            repo_icon: None,
+            edit_uri_template: None,
+            edit_uri: None,
+            theme: Theme {
+                name: None,
+                custom_dir: None,
+                variant: Some(String::from("modern")),
+                language: String::from("en"),
+                direction: None,
+                features: Features::default(),
+                font: Font::Custom(CustomFont {
+                    text: String::from("Inter"),
+                    code: String::from("JetBrains Mono"),
+                }),
+                static_templates: vec![
+                    String::from("404.html"),
+                    String::from("sitemap.xml"),
+                ],
+                favicon: Some(String::from("assets/images/favicon.png")),
+                logo: None,
+                icon: Icon {
+                    edit: None,
+                    view: None,
+                    logo: None,
+                    repo: None,
+                    annotation: None,
+                    top: None,
+                    share: None,
+                    menu: None,
+                    alternate: None,
+                    search: None,
+                    close: None,
+                    previous: None,
+                    next: None,
+                    admonition: BTreeMap::new(),
+                    tag: BTreeMap::new(),
+                },
+                palette: Vec::new(),
+            },
+            theme_dirs: Vec::new(),
+            extra: Dynamic::Map(BTreeMap::new()),
+            extra_context: BTreeMap::new(),
+            extra_css: Vec::new(),
+            extra_javascript: Vec::new(),
+            extra_templates: Vec::new(),
+            mdx_configs: MdxConfigs {
+                toc: TableOfContents { title: None },
+            },
+            mdx_configs_hash: 0,
+            markdown: Markdown {
+                engine: Engine::default(),
+                extensions: Vec::new(),
+                slugify: None,
+            },
+            plugins: Plugins {
+                search: SearchPlugin {
+                    config: SearchPluginConfig {
+                        enabled: true,
+                        separator: String::from(
+                            r#"[\s\-_,:!=\[\]()\\"`/]+|\.(?!\d)"#,
+                        ),
+                    },
+                },
+                offline: OfflinePlugin {
+                    config: OfflinePluginConfig { enabled: false },
+                },
+                i18n: I18nPlugin {
+                    config: I18nPluginConfig {
+                        enabled: false,
+                        languages: Vec::new(),
+                    },
+                },
+                reading_time: ReadingTimePlugin {
+                    config: ReadingTimePluginConfig {
+                        enabled: false,
+                        words_per_minute: 200,
+                        count_code: false,
+                        meta: false,
+                    },
+                },
+                variables: VariablesPlugin {
+                    config: VariablesPluginConfig {
+                        enabled: false,
+                        render_code: false,
+                        env: Vec::new(),
+                    },
+                },
+                images: ImagesPlugin {
+                    config: ImagesPluginConfig {
+                        enabled: false,
+                        dimensions: true,
+                        lazy_loading: true,
+                    },
+                },
+                blog: BlogPlugin {
+                    config: BlogPluginConfig {
+                        enabled: false,
+                        blog_dir: String::from("blog"),
+                        post_dir: String::from("blog/posts"),
+                        posts_per_page: 10,
+                        categories_key: String::from("categories"),
+                    },
+                },
+                privacy: PrivacyPlugin {
+                    config: PrivacyPluginConfig {
+                        enabled: false,
+                        patterns: vec![String::from("*")],
+                        concurrency: 8,
+                        offline: false,
+                    },
+                },
+                social: SocialPlugin {
+                    config: SocialPluginConfig {
+                        enabled: false,
+                        cards_dir: String::from("assets/images/social"),
+                        font_file: String::new(),
+                        logo: None,
+                        background_color: String::from("#4051b5"),
+                        color: String::from("#ffffff"),
+                    },
+                },
+            },
+            nav: Vec::new(),
+            homepage: None,
+            navigation: Navigation {
+                collapse_single_children: false,
+                collapse_title: CollapseTitle::default(),
+            },
+            validation: Validation::default(),
+            diagnostics: Diagnostics {
+                level: Severity::Warning,
+                format: Format::Text,
+            },
+            meta_schema: None,
+            permalinks: Vec::new(),
+            template_hash: 0,
+            plugins_hash: 0,
+            watched_files: Vec::new(),
+            snippets: Snippets {
+                enabled: false,
+                base_path: Vec::new(),
+                check_paths: false,
+            },
+            cache: Cache { max_size_mb: None, max_age: 10 },
+            build: Build {
+                precompress: Vec::new(),
+                precompress_min_size: 1024,
+                output: None,
+                manifest: None,
+                manifest_meta: Vec::new(),
+                content_hashes: false,
+                include_hidden: Vec::new(),
+            },
+            serve: Serve {
+                scope: None,
+                editor_command: None,
+                slow_page_threshold: None,
+                reload_path_style: ReloadPathStyle::default(),
+                partials: false,
+            },
+            unsupported_keys: Vec::new(),
+        }
+    }
 }
@@ -0,0 +1,199 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Repository settings.
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Repository hosting service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Host {
+    /// GitHub.
+    GitHub,
+    /// GitLab.
+    GitLab,
+    /// Bitbucket.
+    Bitbucket,
+    /// Any other hosting service.
+    Other,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Repository, derived from the repository URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repository {
+    /// Repository name.
+    pub name: String,
+    /// Repository hosting service.
+    pub host: Host,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Repository {
+    /// Derives the repository from the given repository URL.
+    ///
+    /// For well-known hosting services, the name is `owner/repo`, taken from
+    /// the first two path segments, and the hostname for all others, as well
+    /// as for URLs that only point to an owner. Trailing slashes and `.git`
+    /// suffixes are ignored. URLs that are not HTTP(S) URLs, e.g., SSH-style
+    /// URLs like `git@github.com:owner/repo.git`, can't be linked to from the
+    /// site, which is why `None` is returned for them.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (scheme, rest) = url.trim().split_once("://")?;
+        if !scheme.eq_ignore_ascii_case("http")
+            && !scheme.eq_ignore_ascii_case("https")
+        {
+            return None;
+        }
+
+        // Split authority from path, and strip user information and port
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let authority = authority.rsplit('@').next().unwrap_or_default();
+        let hostname = authority.split(':').next().unwrap_or_default();
+        if hostname.is_empty() {
+            return None;
+        }
+
+        // Determine hosting service, ignoring case and the www subdomain
+        let hostname = hostname.to_ascii_lowercase();
+        let host = Host::from_hostname(&hostname);
+
+        // Derive owner and repository from path, if the hosting service is
+        // well-known, and fall back to the hostname otherwise
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let mut segments =
+            path.split('/').filter(|segment| !segment.is_empty());
+        let name = match (host, segments.next(), segments.next()) {
+            (Host::Other, _, _) | (_, _, None) | (_, None, _) => hostname,
+            (_, Some(owner), Some(repo)) => {
+                let repo = repo.strip_suffix(".git").unwrap_or(repo);
+                format!("{owner}/{repo}")
+            }
+        };
+
+        // Return repository
+        Some(Repository { name, host })
+    }
+}
+
+impl Host {
+    /// Determines the hosting service from the given hostname.
+    fn from_hostname(hostname: &str) -> Self {
+        match hostname.strip_prefix("www.").unwrap_or(hostname) {
+            "github.com" => Host::GitHub,
+            "gitlab.com" => Host::GitLab,
+            "bitbucket.org" => Host::Bitbucket,
+            _ => Host::Other,
+        }
+    }
+
+    /// Returns the icon for the hosting service, as a hint for templates.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Host::GitHub => "fontawesome/brands/github",
+            Host::GitLab => "fontawesome/brands/gitlab",
+            Host::Bitbucket => "fontawesome/brands/bitbucket",
+            Host::Other => "fontawesome/brands/git-alt",
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{Host, Repository};
+
+    #[test]
+    fn derives_name_for_well_known_hosts() {
+        for (url, name, host) in [
+            ("https://github.com/owner/repo", "owner/repo", Host::GitHub),
+            ("https://github.com/owner/repo/", "owner/repo", Host::GitHub),
+            (
+                "https://github.com/owner/repo.git",
+                "owner/repo",
+                Host::GitHub,
+            ),
+            (
+                "https://www.github.com/owner/repo",
+                "owner/repo",
+                Host::GitHub,
+            ),
+            ("https://GitHub.com/owner/repo", "owner/repo", Host::GitHub),
+            (
+                "https://github.com/owner/repo/tree/main/docs",
+                "owner/repo",
+                Host::GitHub,
+            ),
+            ("https://gitlab.com/owner/repo", "owner/repo", Host::GitLab),
+            (
+                "https://bitbucket.org/owner/repo.git/",
+                "owner/repo",
+                Host::Bitbucket,
+            ),
+            ("https://github.com/owner", "github.com", Host::GitHub),
+        ] {
+            let repo = Repository::from_url(url).unwrap();
+            assert_eq!(repo.name, name, "{url}");
+            assert_eq!(repo.host, host, "{url}");
+        }
+    }
+
+    #[test]
+    fn derives_hostname_for_other_hosts() {
+        for url in [
+            "https://git.example.com/owner/repo",
+            "https://git.example.com/owner/repo.git",
+            "http://user@git.example.com:8080/owner/repo",
+            "https://git.example.com",
+        ] {
+            let repo = Repository::from_url(url).unwrap();
+            assert_eq!(repo.name, "git.example.com", "{url}");
+            assert_eq!(repo.host, Host::Other, "{url}");
+        }
+    }
+
+    #[test]
+    fn rejects_non_http_urls() {
+        for url in [
+            "git@github.com:owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+            "github.com/owner/repo",
+            "https:///owner/repo",
+        ] {
+            assert_eq!(Repository::from_url(url), None, "{url}");
+        }
+    }
+}
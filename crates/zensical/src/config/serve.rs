@@ -0,0 +1,89 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Serve settings.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Style of paths sent to the client when reloading.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReloadPathStyle {
+    /// Trim the `index.html` suffix when directory URLs are enabled.
+    #[default]
+    Directory,
+    /// Send paths as files, always retaining the `index.html` suffix.
+    File,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Serve settings.
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
+#[pyo3(from_item_all)]
+pub struct Serve {
+    /// Glob pattern narrowing the pages that are built, if any.
+    pub scope: Option<String>,
+    /// Command to open files in an editor, if any.
+    ///
+    /// The placeholders `{path}`, `{line}` and `{column}` are replaced with
+    /// the location of the file to open, e.g. `code --goto {path}:{line}`.
+    pub editor_command: Option<String>,
+    /// Time in milliseconds above which pages are reported as slow, if any.
+    pub slow_page_threshold: Option<u64>,
+    /// Style of paths sent to the client when reloading.
+    pub reload_path_style: ReloadPathStyle,
+    /// Whether to serve single blocks of pages at `/__zensical/partial`.
+    ///
+    /// This is experimental, and allows to try out features like instant
+    /// navigation, which only need the content block of a page.
+    pub partials: bool,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<'a, 'py> FromPyObject<'a, 'py> for ReloadPathStyle {
+    type Error = PyErr;
+
+    /// Extracts a reload path style from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "directory" => Ok(Self::Directory),
+            "file" => Ok(Self::File),
+            _ => Err(PyValueError::new_err("Invalid reload path style")),
+        }
+    }
+}
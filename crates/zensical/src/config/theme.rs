@@ -30,12 +30,20 @@ use serde::Serialize;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+mod asset;
+mod features;
+pub mod resolve;
+
+pub use asset::Asset;
+pub(crate) use features::distance;
+pub use features::{Feature, Features};
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
 
 /// Theme settings.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct Theme {
     /// Theme name.
@@ -49,7 +57,7 @@ pub struct Theme {
     /// Text direction.
     pub direction: Option<String>,
     /// Feature flags.
-    pub features: Vec<String>,
+    pub features: Features,
     /// Font settings.
     pub font: Font,
     /// Static templates.
@@ -67,7 +75,7 @@ pub struct Theme {
 // ----------------------------------------------------------------------------
 
 /// Font settings.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[serde(untagged)]
 #[pyo3(from_item_all)]
 pub enum Font {
@@ -78,7 +86,7 @@ pub enum Font {
 }
 
 /// Custom fonts.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct CustomFont {
     /// Text font.
@@ -90,7 +98,7 @@ pub struct CustomFont {
 // ----------------------------------------------------------------------------
 
 /// Icon settings.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct Icon {
     /// Edit button icon.
@@ -128,7 +136,7 @@ pub struct Icon {
 // ----------------------------------------------------------------------------
 
 /// Color palette settings.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct Palette {
     /// Palette media query.
@@ -144,7 +152,7 @@ pub struct Palette {
 }
 
 /// Color palette toggle.
-#[derive(Debug, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, Hash, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct PaletteToggle {
     /// Palette toggle icon.
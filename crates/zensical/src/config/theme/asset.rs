@@ -0,0 +1,233 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Theme asset.
+
+use std::path::{Component, Path, PathBuf};
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Theme asset, i.e., logo or favicon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Asset {
+    /// Icon identifier or URL, which is used verbatim.
+    Verbatim,
+    /// File in the docs or theme directories, with its site-relative URL.
+    File(String),
+    /// File outside of the docs and theme directories, which must be copied
+    /// to the given site-relative URL, since it isn't copied otherwise.
+    Copy(PathBuf, String),
+    /// File that doesn't exist.
+    Missing,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Asset {
+    /// Resolves the given value of a logo or favicon.
+    ///
+    /// Values without file extension are considered to be icon identifiers,
+    /// e.g., `material/rocket`, which are not validated. Files are looked up in
+    /// the docs directory, then in the theme directories, and finally relative
+    /// to the project root directory. Leading slashes are removed, so the URL
+    /// is site-relative, and the base path is respected when rendering pages.
+    ///
+    /// Files outside of the docs and theme directories are copied to the images
+    /// directory, which must not overwrite files of the docs or theme, or the
+    /// given files that are already copied, so their URLs are suffixed with a
+    /// counter in case of a collision, e.g., `assets/images/favicon-1.png`.
+    pub fn resolve(
+        value: &str, root_dir: &Path, docs_dir: &Path, theme_dirs: &[PathBuf],
+        copies: &[(PathBuf, String)],
+    ) -> Self {
+        if is_url(value) || Path::new(value).extension().is_none() {
+            return Asset::Verbatim;
+        }
+
+        // Look up file in docs and theme directories, which are both copied to
+        // the site directory, as long as the path doesn't leave them
+        let url = value.trim_start_matches("./").trim_start_matches('/');
+        let path = Path::new(url);
+        let exists = |path: &Path| {
+            std::iter::once(docs_dir)
+                .chain(theme_dirs.iter().map(PathBuf::as_path))
+                .any(|dir| dir.join(path).is_file())
+        };
+        if path.components().all(|c| matches!(c, Component::Normal(_)))
+            && exists(path)
+        {
+            return Asset::File(url.to_string());
+        }
+
+        // Look up file relative to the project root directory, and copy it to
+        // the images directory, if it exists, unless it's already copied
+        let path = root_dir.join(value);
+        if !path.is_file() {
+            return Asset::Missing;
+        }
+        if let Some((_, url)) = copies.iter().find(|(from, _)| *from == path) {
+            return Asset::Copy(path, url.clone());
+        }
+
+        // Determine a URL in the images directory that isn't taken yet
+        let stem = path.file_stem().and_then(|stem| stem.to_str());
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let (Some(stem), Some(extension)) = (stem, extension) else {
+            return Asset::Missing;
+        };
+        let url = (0..)
+            .map(|n| match n {
+                0 => format!("assets/images/{stem}.{extension}"),
+                n => format!("assets/images/{stem}-{n}.{extension}"),
+            })
+            .find(|url| {
+                !exists(Path::new(url))
+                    && copies.iter().all(|(_, taken)| taken != url)
+            })
+            .expect("invariant");
+        Asset::Copy(path, url)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns whether the given value is a URL, which is used verbatim.
+fn is_url(value: &str) -> bool {
+    value.contains("://")
+        || value.starts_with("//")
+        || value.starts_with("data:")
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
+
+    use super::Asset;
+
+    /// Resolves the given value in a project with docs and theme directories.
+    fn resolve(root: &Path, value: &str) -> Asset {
+        let docs_dir = root.join("docs");
+        let theme_dirs = [root.join("theme")];
+        Asset::resolve(value, root, &docs_dir, &theme_dirs, &[])
+    }
+
+    /// Creates a file at the given path, including parent directories.
+    fn touch(path: PathBuf) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn resolves_icon_identifier() {
+        let dir = tempdir().unwrap();
+        assert_eq!(resolve(dir.path(), "material/rocket"), Asset::Verbatim);
+        assert_eq!(
+            resolve(dir.path(), "https://example.com/logo.png"),
+            Asset::Verbatim
+        );
+    }
+
+    #[test]
+    fn resolves_docs_relative_file() {
+        let dir = tempdir().unwrap();
+        touch(dir.path().join("docs/images/logo.png"));
+        touch(dir.path().join("theme/assets/favicon.png"));
+        assert_eq!(
+            resolve(dir.path(), "/images/logo.png"),
+            Asset::File(String::from("images/logo.png"))
+        );
+        assert_eq!(
+            resolve(dir.path(), "assets/favicon.png"),
+            Asset::File(String::from("assets/favicon.png"))
+        );
+    }
+
+    #[test]
+    fn resolves_file_outside_docs_directory() {
+        let dir = tempdir().unwrap();
+        touch(dir.path().join("branding/logo.svg"));
+        assert_eq!(
+            resolve(dir.path(), "branding/logo.svg"),
+            Asset::Copy(
+                dir.path().join("branding/logo.svg"),
+                String::from("assets/images/logo.svg")
+            )
+        );
+    }
+
+    #[test]
+    fn resolves_colliding_files_outside_docs_directory() {
+        let dir = tempdir().unwrap();
+        let docs_dir = dir.path().join("docs");
+        let theme_dirs = [dir.path().join("theme")];
+        touch(dir.path().join("theme/assets/images/favicon.png"));
+        touch(dir.path().join("a/favicon.png"));
+        touch(dir.path().join("b/favicon.png"));
+
+        // Files of the theme are never overwritten
+        let resolve = |value, copies: &[(PathBuf, String)]| {
+            Asset::resolve(value, dir.path(), &docs_dir, &theme_dirs, copies)
+        };
+        let a = dir.path().join("a/favicon.png");
+        let url = String::from("assets/images/favicon-1.png");
+        assert_eq!(
+            resolve("a/favicon.png", &[]),
+            Asset::Copy(a.clone(), url.clone())
+        );
+
+        // Files that are already copied are reused, others are suffixed
+        let copies = [(a.clone(), url.clone())];
+        assert_eq!(
+            resolve("a/favicon.png", &copies),
+            Asset::Copy(a.clone(), url)
+        );
+        assert_eq!(
+            resolve("b/favicon.png", &copies),
+            Asset::Copy(
+                dir.path().join("b/favicon.png"),
+                String::from("assets/images/favicon-2.png")
+            )
+        );
+    }
+
+    #[test]
+    fn resolves_missing_file() {
+        let dir = tempdir().unwrap();
+        touch(dir.path().join("docs/logo.png"));
+        assert_eq!(resolve(dir.path(), "images/logo.png"), Asset::Missing);
+    }
+}
@@ -0,0 +1,391 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Theme feature flags.
+
+use pyo3::prelude::*;
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Theme feature flag.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Feature {
+    /// Dismissable announcement bar.
+    AnnounceDismiss,
+    /// Edit button.
+    ContentActionEdit,
+    /// View source button.
+    ContentActionView,
+    /// Code annotations.
+    ContentCodeAnnotate,
+    /// Code copy button.
+    ContentCodeCopy,
+    /// Code selection button.
+    ContentCodeSelect,
+    /// Footnote tooltips.
+    ContentFootnoteTooltips,
+    /// Linked content tabs.
+    ContentTabsLink,
+    /// Improved tooltips.
+    ContentTooltips,
+    /// Automatically hidden header.
+    HeaderAutohide,
+    /// Expanded navigation.
+    NavigationExpand,
+    /// Previous and next page links in footer.
+    NavigationFooter,
+    /// Section index pages.
+    NavigationIndexes,
+    /// Instant navigation.
+    NavigationInstant,
+    /// Instant navigation with prefetching.
+    NavigationInstantPrefetch,
+    /// Instant navigation with previews.
+    NavigationInstantPreview,
+    /// Instant navigation with progress indicator.
+    NavigationInstantProgress,
+    /// Navigation path, i.e., breadcrumbs.
+    NavigationPath,
+    /// Navigation pruning.
+    NavigationPrune,
+    /// Navigation sections.
+    NavigationSections,
+    /// Navigation tabs.
+    NavigationTabs,
+    /// Sticky navigation tabs.
+    NavigationTabsSticky,
+    /// Back-to-top button.
+    NavigationTop,
+    /// Anchor tracking.
+    NavigationTracking,
+    /// Search highlighting.
+    SearchHighlight,
+    /// Search sharing.
+    SearchShare,
+    /// Search suggestions.
+    SearchSuggest,
+    /// Anchor following in table of contents.
+    TocFollow,
+    /// Table of contents integrated into navigation.
+    TocIntegrate,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Theme feature flags.
+///
+/// Feature flags are given as a list of strings like `navigation.tabs`, which
+/// are parsed into known flags, so we can check for them in a type safe way.
+/// Unknown flags are retained, so we can warn about them, e.g., in case of a
+/// typo. Feature flags are serialized as the original list of strings, since
+/// templates check for flags with expressions like `"x" in features`.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Features {
+    /// Feature flags, as given.
+    names: Vec<String>,
+    /// Known feature flags.
+    enabled: BTreeSet<Feature>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Feature {
+    /// All feature flags.
+    pub const ALL: [Feature; 29] = [
+        Feature::AnnounceDismiss,
+        Feature::ContentActionEdit,
+        Feature::ContentActionView,
+        Feature::ContentCodeAnnotate,
+        Feature::ContentCodeCopy,
+        Feature::ContentCodeSelect,
+        Feature::ContentFootnoteTooltips,
+        Feature::ContentTabsLink,
+        Feature::ContentTooltips,
+        Feature::HeaderAutohide,
+        Feature::NavigationExpand,
+        Feature::NavigationFooter,
+        Feature::NavigationIndexes,
+        Feature::NavigationInstant,
+        Feature::NavigationInstantPrefetch,
+        Feature::NavigationInstantPreview,
+        Feature::NavigationInstantProgress,
+        Feature::NavigationPath,
+        Feature::NavigationPrune,
+        Feature::NavigationSections,
+        Feature::NavigationTabs,
+        Feature::NavigationTabsSticky,
+        Feature::NavigationTop,
+        Feature::NavigationTracking,
+        Feature::SearchHighlight,
+        Feature::SearchShare,
+        Feature::SearchSuggest,
+        Feature::TocFollow,
+        Feature::TocIntegrate,
+    ];
+
+    /// Feature flags that require a server, as they fetch pages, and thus
+    /// don't work when the site is opened from the file system.
+    pub const SERVER_ONLY: [Feature; 4] = [
+        Feature::NavigationInstant,
+        Feature::NavigationInstantPrefetch,
+        Feature::NavigationInstantPreview,
+        Feature::NavigationInstantProgress,
+    ];
+
+    /// Returns the feature flag with the given name, if known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|feature| feature.name() == name)
+    }
+
+    /// Returns the name of the feature flag.
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::AnnounceDismiss => "announce.dismiss",
+            Feature::ContentActionEdit => "content.action.edit",
+            Feature::ContentActionView => "content.action.view",
+            Feature::ContentCodeAnnotate => "content.code.annotate",
+            Feature::ContentCodeCopy => "content.code.copy",
+            Feature::ContentCodeSelect => "content.code.select",
+            Feature::ContentFootnoteTooltips => "content.footnote.tooltips",
+            Feature::ContentTabsLink => "content.tabs.link",
+            Feature::ContentTooltips => "content.tooltips",
+            Feature::HeaderAutohide => "header.autohide",
+            Feature::NavigationExpand => "navigation.expand",
+            Feature::NavigationFooter => "navigation.footer",
+            Feature::NavigationIndexes => "navigation.indexes",
+            Feature::NavigationInstant => "navigation.instant",
+            Feature::NavigationInstantPrefetch => "navigation.instant.prefetch",
+            Feature::NavigationInstantPreview => "navigation.instant.preview",
+            Feature::NavigationInstantProgress => "navigation.instant.progress",
+            Feature::NavigationPath => "navigation.path",
+            Feature::NavigationPrune => "navigation.prune",
+            Feature::NavigationSections => "navigation.sections",
+            Feature::NavigationTabs => "navigation.tabs",
+            Feature::NavigationTabsSticky => "navigation.tabs.sticky",
+            Feature::NavigationTop => "navigation.top",
+            Feature::NavigationTracking => "navigation.tracking",
+            Feature::SearchHighlight => "search.highlight",
+            Feature::SearchShare => "search.share",
+            Feature::SearchSuggest => "search.suggest",
+            Feature::TocFollow => "toc.follow",
+            Feature::TocIntegrate => "toc.integrate",
+        }
+    }
+
+    /// Returns the key of the feature flag in the template context.
+    ///
+    /// Dots aren't valid in identifiers, so they are replaced with underscores,
+    /// e.g., `navigation.tabs` is exposed as `features.navigation_tabs`.
+    pub fn key(self) -> String {
+        self.name().replace('.', "_")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Features {
+    /// Returns whether the given feature flag is enabled.
+    #[inline]
+    pub fn contains(&self, feature: Feature) -> bool {
+        self.enabled.contains(&feature)
+    }
+
+    /// Removes the given feature flag, returning whether it was enabled.
+    pub fn remove(&mut self, feature: Feature) -> bool {
+        self.names.retain(|name| name != feature.name());
+        self.enabled.remove(&feature)
+    }
+
+    /// Returns all unknown feature flags, together with the most similar known
+    /// feature flag, if any is similar enough to be a likely typo.
+    pub fn unknown(&self) -> impl Iterator<Item = (&str, Option<Feature>)> {
+        self.names
+            .iter()
+            .filter(|name| Feature::from_name(name).is_none())
+            .map(|name| (name.as_str(), suggest(name)))
+    }
+
+    /// Returns all known feature flags as booleans, keyed by context key.
+    pub fn flags(&self) -> BTreeMap<String, bool> {
+        Feature::ALL
+            .into_iter()
+            .map(|feature| (feature.key(), self.contains(feature)))
+            .collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl<S> FromIterator<S> for Features
+where
+    S: Into<String>,
+{
+    /// Creates feature flags from an iterator of names.
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = S>,
+    {
+        let names = iter.into_iter().map(Into::into).collect::<Vec<_>>();
+        let enabled = names
+            .iter()
+            .filter_map(|name| Feature::from_name(name))
+            .collect();
+        Self { names, enabled }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Features {
+    type Error = PyErr;
+
+    /// Extracts feature flags from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        obj.extract::<Vec<String>>().map(Self::from_iter)
+    }
+}
+
+impl Serialize for Features {
+    /// Serializes feature flags as the list of names, as given.
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(&self.names)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the known feature flag most similar to the given name, if any.
+///
+/// Similarity is measured with the edit distance, and only feature flags that
+/// are at most three edits away are considered, to avoid odd suggestions.
+fn suggest(name: &str) -> Option<Feature> {
+    Feature::ALL
+        .into_iter()
+        .map(|feature| (distance(name, feature.name()), feature))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, feature)| feature)
+}
+
+/// Computes the edit distance between the given strings.
+pub fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, x) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let cost = usize::from(x != *y);
+            let next = (row[j] + 1).min(row[j + 1] + 1).min(prev + cost);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{Feature, Features};
+
+    #[test]
+    fn parses_known_features() {
+        let features =
+            Features::from_iter(["navigation.tabs", "content.code.copy"]);
+        assert!(features.contains(Feature::NavigationTabs));
+        assert!(features.contains(Feature::ContentCodeCopy));
+        assert!(!features.contains(Feature::NavigationFooter));
+        assert_eq!(features.unknown().count(), 0);
+    }
+
+    #[test]
+    fn names_are_unique_and_roundtrip() {
+        for feature in Feature::ALL {
+            assert_eq!(Feature::from_name(feature.name()), Some(feature));
+        }
+    }
+
+    #[test]
+    fn unknown_features_with_suggestions() {
+        let features = Features::from_iter(["navigation.tab", "unknown"]);
+        assert_eq!(
+            features.unknown().collect::<Vec<_>>(),
+            vec![
+                ("navigation.tab", Some(Feature::NavigationTabs)),
+                ("unknown", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_features() {
+        let mut features =
+            Features::from_iter(["navigation.instant", "toc.follow"]);
+        assert!(features.remove(Feature::NavigationInstant));
+        assert!(!features.remove(Feature::NavigationInstant));
+        assert!(!features.contains(Feature::NavigationInstant));
+        assert_eq!(
+            serde_json::to_string(&features).unwrap(),
+            r#"["toc.follow"]"#
+        );
+    }
+
+    #[test]
+    fn flags_expose_all_features() {
+        let features = Features::from_iter(["navigation.tabs.sticky"]);
+        let flags = features.flags();
+        assert_eq!(flags.len(), Feature::ALL.len());
+        assert_eq!(flags.get("navigation_tabs_sticky"), Some(&true));
+        assert_eq!(flags.get("navigation_tabs"), Some(&false));
+    }
+
+    #[test]
+    fn serializes_names_as_given() {
+        let features = Features::from_iter(["toc.follow", "custom.flag"]);
+        assert_eq!(
+            serde_json::to_string(&features).unwrap(),
+            r#"["toc.follow","custom.flag"]"#
+        );
+    }
+}
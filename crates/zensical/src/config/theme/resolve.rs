@@ -0,0 +1,378 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Theme resolution.
+
+use pyo3::types::PyAnyMethods;
+use pyo3::{Bound, PyAny, PyErr, PyResult, Python};
+use std::collections::BTreeMap;
+use std::path::{self, Path, PathBuf};
+use std::result;
+use thiserror::Error;
+
+// ----------------------------------------------------------------------------
+// Traits
+// ----------------------------------------------------------------------------
+
+/// Installed themes.
+///
+/// Themes are installed as Python packages, which register an entry point in
+/// the `mkdocs.themes` group, so they're looked up in the Python environment.
+/// This trait abstracts the lookup, which allows to test resolution without
+/// installing themes.
+pub trait Themes {
+    /// Returns the names of all installed themes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the themes could not be listed.
+    fn names(&self) -> PyResult<Vec<String>>;
+
+    /// Returns the directory of the installed theme with the given name.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the theme could not be loaded.
+    fn dir(&self, name: &str) -> PyResult<Option<PathBuf>>;
+
+    /// Returns the directory of the built-in theme.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the directory could not be determined.
+    fn builtin_dir(&self) -> PyResult<PathBuf>;
+
+    /// Returns the name of the theme the theme in the given directory extends.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the theme configuration is invalid.
+    fn extends(&self, dir: &Path) -> PyResult<Option<String>>;
+}
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Theme resolution error.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Theme is not installed.
+    #[error("Theme '{name}' is not installed{}", available(.installed))]
+    NotInstalled {
+        /// Theme name.
+        name: String,
+        /// Installed themes.
+        installed: Vec<String>,
+    },
+
+    /// Parent theme could not be resolved.
+    #[error("Parent theme of '{}' not found: {source}", .dir.display())]
+    Parent {
+        /// Theme directory.
+        dir: PathBuf,
+        /// Resolution error of the parent theme.
+        source: Box<Error>,
+    },
+
+    /// Theme extends itself, directly or transitively.
+    #[error("Theme '{name}' extends itself: {}", .chain.join(" -> "))]
+    Cycle {
+        /// Theme name.
+        name: String,
+        /// Inheritance chain.
+        chain: Vec<String>,
+    },
+
+    /// Python error.
+    #[error(transparent)]
+    PyO3(#[from] PyErr),
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Themes installed in the Python environment.
+pub struct PythonThemes<'py> {
+    /// Python interpreter.
+    py: Python<'py>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl<'py> PythonThemes<'py> {
+    /// Creates a lookup for themes installed in the Python environment.
+    #[must_use]
+    pub fn new(py: Python<'py>) -> Self {
+        Self { py }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Themes for PythonThemes<'_> {
+    /// Returns the names of all entry points in the `mkdocs.themes` group.
+    fn names(&self) -> PyResult<Vec<String>> {
+        let module = self.py.import("zensical.config")?;
+        let themes = module
+            .call_method0("get_themes")?
+            .extract::<BTreeMap<String, Bound<PyAny>>>()?;
+        Ok(themes.into_keys().collect())
+    }
+
+    /// Loads the entry point of the theme, and returns its directory.
+    fn dir(&self, name: &str) -> PyResult<Option<PathBuf>> {
+        let module = self.py.import("zensical.config")?;
+        let themes = module.call_method0("get_themes")?;
+        let Ok(entry_point) = themes.get_item(name) else {
+            return Ok(None);
+        };
+        let file = entry_point
+            .call_method0("load")?
+            .getattr("__file__")?
+            .extract::<PathBuf>()?;
+        let file = path::absolute(file)?;
+        Ok(file.parent().map(Path::to_path_buf))
+    }
+
+    /// Returns the directory of the built-in theme.
+    fn builtin_dir(&self) -> PyResult<PathBuf> {
+        let module = self.py.import("zensical.config")?;
+        module.call_method0("get_builtin_theme_dir")?.extract()
+    }
+
+    /// Reads the parent theme from the theme configuration, if any.
+    fn extends(&self, dir: &Path) -> PyResult<Option<String>> {
+        let module = self.py.import("zensical.config")?;
+        module.call_method1("get_theme_parent", (dir,))?.extract()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Resolves the theme directories, ordered by precedence.
+///
+/// The custom directory comes first, if given, followed by the directories of
+/// the theme it extends, or the theme with the given name, and all of their
+/// parent themes in inheritance order, so overrides always win. Themes named
+/// `material` or `zensical` resolve to the built-in theme. If a custom
+/// directory doesn't extend a theme, and no name is given, it's used alone.
+///
+/// # Errors
+///
+/// This function returns an error if a theme is not installed, or themes
+/// extend each other, naming the theme and all installed themes.
+pub fn resolve(
+    themes: &dyn Themes, name: Option<&str>, custom_dir: Option<&Path>,
+) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    let mut name = name.map(ToString::to_string);
+    if let Some(dir) = custom_dir {
+        dirs.push(dir.to_path_buf());
+        if let Some(parent) = themes.extends(dir)? {
+            resolve_parent(themes, dir, &parent, &mut dirs, Vec::new())?;
+            name = None;
+        }
+    }
+
+    // Resolve theme with the given name, and all of its parents
+    if let Some(name) = name {
+        resolve_chain(themes, &name, &mut dirs, vec![name.clone()])?;
+    }
+    Ok(dirs)
+}
+
+/// Resolves the theme with the given name, and all of its parents.
+fn resolve_chain(
+    themes: &dyn Themes, name: &str, dirs: &mut Vec<PathBuf>,
+    chain: Vec<String>,
+) -> Result {
+    let dir = if matches!(name, "material" | "zensical") {
+        themes.builtin_dir()?
+    } else if let Some(dir) = themes.dir(name)? {
+        dir
+    } else {
+        let mut installed = themes.names()?;
+        installed.sort();
+        let name = name.to_string();
+        return Err(Error::NotInstalled { name, installed });
+    };
+    dirs.push(dir.clone());
+    match themes.extends(&dir)? {
+        Some(parent) => resolve_parent(themes, &dir, &parent, dirs, chain),
+        None => Ok(()),
+    }
+}
+
+/// Resolves the parent theme of the theme in the given directory.
+fn resolve_parent(
+    themes: &dyn Themes, dir: &Path, parent: &str, dirs: &mut Vec<PathBuf>,
+    mut chain: Vec<String>,
+) -> Result {
+    if chain.iter().any(|name| name == parent) {
+        chain.push(parent.to_string());
+        let name = parent.to_string();
+        return Err(Error::Cycle { name, chain });
+    }
+    chain.push(parent.to_string());
+    resolve_chain(themes, parent, dirs, chain).map_err(|err| match err {
+        Error::NotInstalled { .. } => {
+            let dir = dir.to_path_buf();
+            Error::Parent { dir, source: Box::new(err) }
+        }
+        err => err,
+    })
+}
+
+/// Returns the list of available themes for error messages.
+fn available(names: &[String]) -> String {
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!(". Available themes are: {}", names.join(", "))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Theme resolution result.
+pub type Result<T = ()> = result::Result<T, Error>;
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use pyo3::PyResult;
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use super::{resolve, Themes};
+
+    /// Installed themes, mapping names to parents.
+    struct Installed(BTreeMap<&'static str, Option<&'static str>>);
+
+    impl Themes for Installed {
+        fn names(&self) -> PyResult<Vec<String>> {
+            Ok(self.0.keys().map(ToString::to_string).collect())
+        }
+
+        fn dir(&self, name: &str) -> PyResult<Option<PathBuf>> {
+            Ok(self.0.contains_key(name).then(|| PathBuf::from(name)))
+        }
+
+        fn builtin_dir(&self) -> PyResult<PathBuf> {
+            Ok(PathBuf::from("builtin"))
+        }
+
+        fn extends(&self, dir: &Path) -> PyResult<Option<String>> {
+            let name = dir.to_string_lossy();
+            let parent = match name.as_ref() {
+                "overrides" => Some("fork"),
+                name => self.0.get(name).copied().flatten(),
+            };
+            Ok(parent.map(ToString::to_string))
+        }
+    }
+
+    /// Creates installed themes from the given names and parents.
+    fn installed(themes: &[(&'static str, Option<&'static str>)]) -> Installed {
+        Installed(themes.iter().copied().collect())
+    }
+
+    /// Converts the given names to paths.
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn themes_are_ordered_by_inheritance() {
+        let themes = installed(&[
+            ("fork", Some("community")),
+            ("community", Some("material")),
+        ]);
+        let dirs = resolve(&themes, Some("fork"), None).unwrap();
+        assert_eq!(dirs, paths(&["fork", "community", "builtin"]));
+    }
+
+    #[test]
+    fn custom_dir_comes_first() {
+        let themes =
+            installed(&[("fork", Some("community")), ("community", None)]);
+        let custom_dir = Path::new("overrides");
+
+        // Custom directory extending a theme ignores the theme name
+        let dirs = resolve(&themes, Some("material"), Some(custom_dir));
+        assert_eq!(dirs.unwrap(), paths(&["overrides", "fork", "community"]));
+
+        // Custom directory without parent is followed by the named theme
+        let custom_dir = Path::new("custom");
+        let dirs = resolve(&themes, Some("community"), Some(custom_dir));
+        assert_eq!(dirs.unwrap(), paths(&["custom", "community"]));
+        let dirs = resolve(&themes, None, Some(custom_dir));
+        assert_eq!(dirs.unwrap(), paths(&["custom"]));
+    }
+
+    #[test]
+    fn missing_themes_list_installed_themes() {
+        let themes = installed(&[("community", None), ("base", None)]);
+        let err = resolve(&themes, Some("missing"), None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Theme 'missing' is not installed. \
+             Available themes are: base, community"
+        );
+        let err = resolve(&installed(&[]), Some("missing"), None).unwrap_err();
+        assert_eq!(err.to_string(), "Theme 'missing' is not installed");
+    }
+
+    #[test]
+    fn missing_parent_themes_name_the_child() {
+        let themes = installed(&[("fork", Some("missing"))]);
+        let err = resolve(&themes, Some("fork"), None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parent theme of 'fork' not found: Theme 'missing' is not \
+             installed. Available themes are: fork"
+        );
+    }
+
+    #[test]
+    fn cyclic_inheritance_is_rejected() {
+        let themes = installed(&[("a", Some("b")), ("b", Some("a"))]);
+        let err = resolve(&themes, Some("a"), None).unwrap_err();
+        assert_eq!(err.to_string(), "Theme 'a' extends itself: a -> b -> a");
+    }
+}
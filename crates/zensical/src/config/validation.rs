@@ -28,6 +28,8 @@
 use pyo3::FromPyObject;
 use serde::Serialize;
 
+use crate::workflow::Severity;
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
@@ -53,6 +55,12 @@ pub struct Validation {
     pub invalid_links: bool,
     /// Invalid link anchors.
     pub invalid_link_anchors: bool,
+    /// Report root-relative links that bypass the base path with severity.
+    pub root_relative_links: Option<Severity>,
+    /// Prefix root-relative links that bypass the base path with it.
+    pub fix_root_relative_links: bool,
+    /// Warn about absolute file system paths that leak into the output.
+    pub absolute_paths: bool,
 }
 
 // ----------------------------------------------------------------------------
@@ -91,6 +99,9 @@ impl Default for Validation {
             shadowed_footnotes: false,
             invalid_links: true,
             invalid_link_anchors: true,
+            root_relative_links: Some(Severity::Warning),
+            fix_root_relative_links: false,
+            absolute_paths: false,
         }
     }
 }
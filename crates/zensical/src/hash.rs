@@ -0,0 +1,252 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Stable hashing.
+
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// FNV-1a offset basis.
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a prime.
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Stable hasher.
+///
+/// The default hasher of the standard library is not guaranteed to produce the
+/// same hashes across Rust versions, which is why we use FNV-1a for all hashes
+/// that are persisted or end up in the output, e.g., for caching. Integers are
+/// always hashed as little-endian 64-bit values, so hashes are also identical
+/// across platforms, regardless of pointer width and endianness.
+#[derive(Clone, Debug)]
+pub struct StableHasher {
+    /// Current state.
+    state: u64,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Hasher for StableHasher {
+    /// Writes the given bytes into the hasher.
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(PRIME);
+        }
+    }
+
+    /// Writes a `usize` into the hasher as a 64-bit value.
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    /// Writes an `isize` into the hasher as a 64-bit value.
+    #[inline]
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+
+    /// Writes a `u16` into the hasher in little-endian byte order.
+    #[inline]
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Writes a `u32` into the hasher in little-endian byte order.
+    #[inline]
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Writes a `u64` into the hasher in little-endian byte order.
+    #[inline]
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Writes a `u128` into the hasher in little-endian byte order.
+    #[inline]
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Returns the hash.
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Default for StableHasher {
+    /// Creates a stable hasher.
+    #[inline]
+    fn default() -> Self {
+        Self { state: OFFSET }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Computes the stable hash of the given value.
+pub fn stable_hash<T>(value: &T) -> u64
+where
+    T: Hash + ?Sized,
+{
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Computes the stable hash of the contents of all files in the given
+/// directories.
+///
+/// Files are hashed in a deterministic order, together with their paths
+/// relative to the directory they're located in, and the position of that
+/// directory, since the order of theme directories determines precedence.
+/// Hidden files and directories, e.g., `.icons`, are skipped.
+pub fn stable_hash_dirs(dirs: &[PathBuf]) -> io::Result<u64> {
+    let mut hasher = StableHasher::default();
+    for (i, dir) in dirs.iter().enumerate() {
+        let mut files = Vec::new();
+        collect_files(dir, &mut files)?;
+        files.sort();
+
+        // Hash directory position, relative path and contents of each file
+        for path in files {
+            let location = path.strip_prefix(dir).expect("invariant");
+            i.hash(&mut hasher);
+            location
+                .to_string_lossy()
+                .replace('\\', "/")
+                .hash(&mut hasher);
+            fs::read(&path)?.hash(&mut hasher);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+/// Collects all non-hidden files in the given directory recursively.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::hash::Hasher;
+    use tempfile::tempdir;
+
+    use super::{stable_hash, stable_hash_dirs, StableHasher};
+
+    #[test]
+    fn matches_fnv1a_test_vectors() {
+        for (input, expected) in [
+            ("", 0xcbf2_9ce4_8422_2325),
+            ("a", 0xaf63_dc4c_8601_ec8c),
+            ("foobar", 0x8594_4171_f739_67e8),
+        ] {
+            let mut hasher = StableHasher::default();
+            hasher.write(input.as_bytes());
+            assert_eq!(hasher.finish(), expected);
+        }
+    }
+
+    #[test]
+    fn hashes_integers_independent_of_width() {
+        assert_eq!(stable_hash(&42usize), stable_hash(&42u64));
+        assert_eq!(stable_hash(&-42isize), stable_hash(&-42i64));
+    }
+
+    #[test]
+    fn hashes_dirs_by_content() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.html");
+        fs::write(&file, "aaaa").unwrap();
+        let dirs = [dir.path().to_path_buf()];
+        let before = stable_hash_dirs(&dirs).unwrap();
+
+        // Change contents, but retain size and modification time
+        let modified = fs::metadata(&file).unwrap().modified().unwrap();
+        fs::write(&file, "bbbb").unwrap();
+        File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_ne!(stable_hash_dirs(&dirs).unwrap(), before);
+    }
+
+    #[test]
+    fn hashes_dirs_without_hidden_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("main.html"), "main").unwrap();
+        let dirs = [dir.path().to_path_buf()];
+        let before = stable_hash_dirs(&dirs).unwrap();
+
+        // Add icon to hidden directory
+        fs::create_dir(dir.path().join(".icons")).unwrap();
+        fs::write(dir.path().join(".icons/icon.svg"), "<svg/>").unwrap();
+        assert_eq!(stable_hash_dirs(&dirs).unwrap(), before);
+    }
+}
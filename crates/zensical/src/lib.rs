@@ -31,27 +31,77 @@
 #![allow(clippy::needless_pass_by_value)]
 
 use crossbeam::channel::unbounded;
+use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use pyo3::Python;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::{fs, io, thread};
+use std::{fs, io, mem, thread};
 use zrx::id::Id;
 use zrx::scheduler::Scheduler;
 
+mod clean;
 mod config;
+mod hash;
+mod privacy;
 mod python;
+mod relocate;
 mod server;
+mod shutdown;
+mod social;
 mod structure;
 mod template;
 mod watcher;
 mod workflow;
 
+use clean::{clear_dir, remove_dir};
+use config::theme::resolve::{self, PythonThemes};
 use config::Config;
-use server::{create_server, ServeOptions};
+use hash::stable_hash;
+use server::{
+    create_server, server_url, Forwarder, Partials, ServeOptions, State,
+};
+use shutdown::shutdown;
+use structure::markdown::create_converter;
 use watcher::Watcher;
-use workflow::create_workflow;
+use workflow::{
+    copy_extra_file, create_workflow, discover_pages, open_sink, resolve_nav,
+    Branches, Cache, Cancellation, Diagnostics, Includes, OutputSink, Printer,
+    Registry, Report, Scope, Severity, Summary,
+};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Site built as part of the project.
+///
+/// Each site is built by its own workflow, which is attached to its own
+/// scheduler, so sites are built and rebuilt independently of each other.
+struct Site {
+    /// Configuration of the site.
+    config: Config,
+    /// Scheduler running the workflow.
+    scheduler: Scheduler<Id>,
+    /// Report of the current build.
+    report: Report,
+    /// Diagnostics of the current build.
+    diagnostics: Diagnostics,
+    /// Branches running in parallel to the scheduler.
+    branches: Branches,
+    /// Output sink.
+    sink: Arc<dyn OutputSink>,
+    /// Partial renderers.
+    partials: Partials,
+    /// Forwarder of diagnostics to clients.
+    forwarder: Forwarder,
+    /// Whether the site is currently being built.
+    building: bool,
+}
 
 // ----------------------------------------------------------------------------
 // Enums
@@ -65,6 +115,14 @@ pub struct BuildOptions {
     pub clean: Option<bool>,
     /// Whether to enable strict mode - abort the build on any warnings.
     pub strict: Option<bool>,
+    /// Whether to build twice and check that both outputs are identical.
+    pub reproducible: Option<bool>,
+    /// Whether to prune all cache entries that were not used in the build.
+    pub prune_cache: Option<bool>,
+    /// Site directory, overriding the configured one.
+    pub site_dir: Option<String>,
+    /// Cache directory, overriding the configured one.
+    pub cache_dir: Option<String>,
 }
 
 /// Build mode.
@@ -76,17 +134,69 @@ pub enum Mode {
     Serve(ServeOptions, u64),
 }
 
+/// Build outcome.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Build must be restarted, e.g., since the configuration changed.
+    Restart,
+    /// Build finished with the given summary.
+    Finished(Summary),
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Mode {
+    /// Returns the site and cache directories given on the command line.
+    fn dirs(&self) -> (Option<&str>, Option<&str>) {
+        match self {
+            Mode::Build(options) => {
+                (options.site_dir.as_deref(), options.cache_dir.as_deref())
+            }
+            Mode::Serve(options, _) => {
+                (options.site_dir.as_deref(), options.cache_dir.as_deref())
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Site {
+    /// Returns the heading of the site's report, if there are multiple sites.
+    fn heading(&self, count: usize) -> String {
+        if count == 1 {
+            String::new()
+        } else {
+            format!("{:<16}{}\n", "Site", self.config.project.site_dir)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Outcome {
+    /// Returns the summary of the build, which is empty if it was restarted.
+    fn into_summary(self) -> Summary {
+        match self {
+            Outcome::Restart => Summary::default(),
+            Outcome::Finished(summary) => summary,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
 
-/// Setup tracing if enabled.
+/// Setup tracing if enabled, writing a Chrome trace to the given path.
 #[cfg(feature = "tracing")]
-fn setup_tracing() -> tracing_chrome::FlushGuard {
+fn setup_tracing(path: &Path) -> tracing_chrome::FlushGuard {
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::Registry;
     let (chrome_layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
-        .file("trace.json")
+        .file(path)
         .include_args(true)
         .include_locations(true)
         .build();
@@ -100,7 +210,7 @@ fn setup_tracing() -> tracing_chrome::FlushGuard {
 /// Wait until the file at the given path is touched.
 ///
 /// During the wait we also poll for Python signal handling so a keyboard
-/// interrupt (Ctrl‑C) can abort the blocking loop.
+/// interrupt (Ctrl‑C) or termination signal can abort the blocking loop.
 fn wait_for_touch(path: &Path) -> io::Result<bool> {
     let last = fs::metadata(path)?.modified()?;
     loop {
@@ -114,120 +224,440 @@ fn wait_for_touch(path: &Path) -> io::Result<bool> {
             println!("Received interrupt, exiting");
             process::exit(1);
         }
+
+        // Exit gracefully on termination signals - there's nothing to clean
+        // up, since we're waiting for the configuration file to be fixed
+        if shutdown::TERMINATED.load(Ordering::SeqCst) {
+            println!("Received termination signal, exiting");
+            process::exit(0);
+        }
     }
     Ok(true)
 }
 
-/// Clears the contents of a directory without removing the directory itself.
-fn clear_dir(dir: &Path) -> io::Result<()> {
-    for entry in std::fs::read_dir(dir)? {
-        let path = entry?.path();
-
-        // Only remove non-hidden paths (not starting with `.`) to match
-        // MkDocs' behavior. This allows users to track the (empty) site folder
-        // by adding a `.gitkeep` file within it.
-        if !path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .is_some_and(|name| name.starts_with('.'))
-        {
+/// Builds the project twice and checks that both outputs are identical.
+///
+/// The second build always starts with a clean cache, so differences between
+/// cached and freshly computed artifacts are detected as well. This is useful
+/// for deployment systems that skip uploads when content is unchanged.
+fn check_reproducible(
+    config_file: &PathBuf, options: BuildOptions,
+) -> PyResult<Summary> {
+    let (site_dir, cache_dir) = (&options.site_dir, &options.cache_dir);
+    let configs =
+        load_sites(config_file, site_dir.as_deref(), cache_dir.as_deref())?;
+    if configs
+        .iter()
+        .any(|config| config.project.build.output.is_some())
+    {
+        return Err(PyRuntimeError::new_err(
+            "Reproducibility checks are not supported with archive output",
+        ));
+    }
+
+    // Build once, and take a snapshot of the site directories
+    run(config_file, Mode::Build(options.clone()))?;
+    let first = snapshot_sites(&configs)?;
+
+    // Build again with a clean cache, and compare both snapshots
+    let options = BuildOptions { clean: Some(true), ..options };
+    let outcome = run(config_file, Mode::Build(options))?;
+    let second = snapshot_sites(&configs)?;
+
+    // Collect all files that differ or only exist in one of both builds
+    let mut paths = first.keys().chain(second.keys()).collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    paths.retain(|path| first.get(*path) != second.get(*path));
+    if paths.is_empty() {
+        println!("Build is reproducible");
+        return Ok(outcome.into_summary());
+    }
+
+    // Report differences and fail
+    let diagnostics = Diagnostics::default();
+    for path in &paths {
+        diagnostics.error(format!(
+            "Output differs between builds: {}",
+            path.display()
+        ));
+    }
+    let settings = &configs[0].project.diagnostics;
+    let printer = Printer::new(settings.level)
+        .with_json(settings.format == config::diagnostics::Format::Json);
+    diagnostics.flush(&printer);
+    Err(PyRuntimeError::new_err("Build is not reproducible"))
+}
+
+/// Computes the stable hashes of all files in the site directories.
+///
+/// Paths are relative to the site directory, and prefixed with the configured
+/// site directory, if the project builds multiple sites.
+fn snapshot_sites(configs: &[Config]) -> io::Result<BTreeMap<PathBuf, u64>> {
+    let mut hashes = BTreeMap::new();
+    for config in configs {
+        let prefix = match configs.len() {
+            1 => PathBuf::new(),
+            _ => PathBuf::from(&config.project.site_dir),
+        };
+        for (path, hash) in snapshot(&config.get_site_dir())? {
+            hashes.insert(prefix.join(path), hash);
+        }
+    }
+    Ok(hashes)
+}
+
+/// Computes the stable hashes of all files in the given directory.
+fn snapshot(dir: &Path) -> io::Result<BTreeMap<PathBuf, u64>> {
+    let mut hashes = BTreeMap::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(path) = stack.pop() {
+        for entry in fs::read_dir(&path)? {
+            let path = entry?.path();
             if path.is_dir() {
-                std::fs::remove_dir_all(&path)?;
+                stack.push(path);
             } else {
-                std::fs::remove_file(&path)?;
+                let data = fs::read(&path)?;
+                let path = path.strip_prefix(dir).expect("invariant");
+                hashes.insert(path.to_path_buf(), stable_hash(&data));
             }
         }
     }
-    Ok(())
+    Ok(hashes)
+}
+
+/// Loads the configurations of all sites, overriding their directories.
+///
+/// Directories given on the command line override the configured ones. If the
+/// project builds multiple sites, the given site directory is the directory
+/// all configured site directories are located in, so they don't collide.
+fn load_sites(
+    config_file: &Path, site_dir: Option<&str>, cache_dir: Option<&str>,
+) -> config::Result<Vec<Config>> {
+    let configs = Config::sites(config_file)?;
+    let count = configs.len();
+    let iter = configs.into_iter().map(|config| {
+        let site_dir = site_dir.map(|dir| match count {
+            1 => dir.to_string(),
+            _ => Path::new(dir)
+                .join(&config.project.site_dir)
+                .to_string_lossy()
+                .into_owned(),
+        });
+        config.with_dirs(site_dir.as_deref(), cache_dir)
+    });
+    Ok(iter.collect())
 }
 
 /// Run the build process.
 #[allow(clippy::too_many_lines)]
-fn run(config_file: &PathBuf, mode: Mode) -> PyResult<bool> {
+fn run(config_file: &PathBuf, mode: Mode) -> PyResult<Outcome> {
     #[cfg(feature = "tracing")]
-    let _guard = setup_tracing();
+    let _guard = setup_tracing(Path::new("trace.json"));
 
     // In case the configuration changes, we recreate the entire workspace and
     // scheduler. Once we have the module system set up, this will be tightly
     // integrated and not necessary anymore, since partial rebuilds of the
     // network of tasks will be supported.
-    let config = match Config::new(config_file) {
-        Ok(config) => config,
+    let (site_dir, cache_dir) = mode.dirs();
+    let configs = match load_sites(config_file, site_dir, cache_dir) {
+        Ok(configs) => configs,
         // If we're already serving (seq > 0), a previous build succeeded, so
         // we can wait for the config file to be fixed and retry. On the first
         // run (seq == 0) we exit immediately, just like `build` does.
         Err(err) if matches!(&mode, Mode::Serve(_, seq) if *seq > 0) => {
             println!("[error] Failed to load configuration: {err}");
-            return wait_for_touch(config_file).map_err(Into::into);
+            return wait_for_touch(config_file)
+                .map(|_| Outcome::Restart)
+                .map_err(Into::into);
         }
         Err(err) => return Err(err.into()),
     };
 
-    // Clean cache directory if requested
+    // Archive output can't be served, since the server serves files from the
+    // site directory, so we reject it with a clear error
+    let serve = matches!(mode, Mode::Serve(..));
+    if serve
+        && configs
+            .iter()
+            .any(|config| config.project.build.output.is_some())
+    {
+        return Err(PyRuntimeError::new_err(
+            "Archive output is not supported when serving, remove \
+             'build.output' from the configuration",
+        ));
+    }
+
+    // Multiple sites are served under their base paths, which are derived from
+    // their site URLs, so they must be distinct, or sites would shadow others
+    let mut bases = configs
+        .iter()
+        .map(Config::get_base_path)
+        .collect::<Vec<_>>();
+    bases.sort();
+    bases.dedup();
+    if serve && bases.len() < configs.len() {
+        return Err(PyRuntimeError::new_err(
+            "Sites must be served under distinct base paths, set 'site_url' \
+             for each site, e.g., 'https://example.com/api/'",
+        ));
+    }
+
+    // Ensure that the site and cache directories are writable, before we start
+    // building, as the source checkout might be mounted read-only, e.g., in CI
+    for config in &configs {
+        config.check_writable()?;
+    }
+
+    // Clean cache directory if requested - files that are locked are skipped,
+    // and reported as warnings of the first site once its build is set up.
+    // All sites share the cache directory, as they share all other settings.
+    let config = &configs[0];
+    let mut skipped = Vec::new();
     if let Mode::Build(options) = &mode {
         if options.clean.unwrap_or(false) {
             let cache_dir = config.get_cache_dir();
             if cache_dir.exists() {
-                std::fs::remove_dir_all(&cache_dir)
-                    .expect("cache directory could not be removed");
+                skipped = remove_dir(&cache_dir)?;
             }
         }
     }
 
-    // Always clean site directory before building for now - we're working on
-    // true differential builds, which will also include cleaning up old files
-    // that are not needed anymore but for now, we just remove everything, like
-    // MkDocs does it, but not the directory itself, see https://t.ly/Lrjdx
-    let site_dir = config.get_site_dir();
-    if site_dir.exists() {
-        clear_dir(&site_dir).expect("site directory could not be cleaned");
-    }
-
     // Determine if strict mode is enabled
     let strict = match &mode {
         Mode::Build(options) => options.strict.unwrap_or(false),
         Mode::Serve(_, _) => false,
     };
 
-    // Create workspace and scheduler
-    let workflow = create_workflow(&config, strict);
-    let mut scheduler = Scheduler::<Id>::default();
-    scheduler.attach(workflow);
+    // Create the printer for diagnostics, as well as the cancellation of all
+    // workflows. Open cache, which starts with a clean cache if it was written
+    // by another version, and tracks which entries are used, so unused ones
+    // are pruned. It's shared by all sites, as entries are keyed by settings.
+    let settings = &config.project.diagnostics;
+    let printer = Printer::new(settings.level)
+        .with_json(settings.format == config::diagnostics::Format::Json);
+    let cancellation = Cancellation::default();
+    let cache = Cache::open(config.get_cache_dir())?;
+
+    // Create a workflow for each site, which is attached to its own scheduler,
+    // so sites are built and rebuilt independently of each other
+    let mut sites = Vec::with_capacity(configs.len());
+    let mut sessions = Vec::with_capacity(configs.len());
+    for config in configs {
+        // Create diagnostics of the site, which collect all warnings and errors
+        // that are emitted while the site is set up, built and rebuilt
+        let diagnostics = Diagnostics::default();
+
+        // Narrow scope of the build in serve mode, if requested, which is taken
+        // from the command line or configuration, and requires a snapshot of
+        // the last full build. Otherwise, we fall back to building all pages.
+        let glob = match &mode {
+            Mode::Serve(options, _) => options
+                .scope
+                .clone()
+                .or_else(|| config.project.serve.scope.clone()),
+            Mode::Build(_) => None,
+        };
+        let mut scope = Scope::default();
+        if let Some(glob) = glob {
+            match Scope::load(&config, &glob) {
+                Ok(narrowed) => {
+                    println!("Building pages in scope '{glob}' only");
+                    scope = narrowed;
+                }
+                Err(err) => {
+                    let reason = if err.kind() == io::ErrorKind::NotFound {
+                        "no full build to start from".to_string()
+                    } else {
+                        err.to_string()
+                    };
+                    diagnostics.warning(format!(
+                        "Can't narrow scope to '{glob}', building all pages: \
+                         {reason}"
+                    ));
+                }
+            }
+        }
 
-    // Create channel for reload notifications
-    let (sender, receiver) = unbounded();
+        // Always clean site directory before building for now - we're working
+        // on true differential builds, which will also include cleaning up old
+        // files that are not needed anymore but for now, we just remove
+        // everything, like MkDocs does it, but not the directory itself, see
+        // https://t.ly/Lrjdx. If the scope is narrowed, pages outside of it
+        // keep their output. Files that are locked, e.g., by a browser or virus
+        // scanner, are skipped, and reported as warnings.
+        let site_dir = config.get_site_dir();
+        if site_dir.exists() && !scope.is_narrowed() {
+            skipped.extend(clear_dir(&site_dir)?);
+        }
 
-    // Create session to connect file agent and scheduler - note that we must
-    // assign the agent to a variable right now, or it is dropped, and will
-    // automatically terminate. This is a temporary workaround until we could
-    // better integrate the scheduler with the agent.
-    let session = scheduler.session();
+        // Open output sink, which writes to the site directory, or streams all
+        // outputs into an archive, if configured, and reports outputs that
+        // would end up outside of the site directory as errors
+        let sink = open_sink(&config, &diagnostics)?;
+
+        // Report warnings about settings, which were collected when loading
+        // the configuration, so they're printed alongside all diagnostics,
+        // as well as about files that were skipped when cleaning
+        for warning in &config.warnings {
+            diagnostics.warning(warning.as_str());
+        }
+        for path in skipped.drain(..) {
+            diagnostics.warning(path.to_string());
+        }
+
+        // Create the report that collects the timings and item counts of all
+        // stages of the workflow, and the include graph, which is shared with
+        // the watcher to rebuild dependent pages. In serve mode, pages
+        // exceeding the configured threshold are reported. The output registry
+        // is shared with the watcher to prune removed pages.
+        let mut report = Report::default();
+        let threshold =
+            config.project.serve.slow_page_threshold.filter(|_| serve);
+        if let Some(threshold) = threshold {
+            let threshold = Duration::from_millis(threshold);
+            report = report.with_slow_page_threshold(threshold);
+        }
+        let includes = Includes::default();
+        let registry = Registry::default();
+        let branches = Branches::default();
+
+        // Copy logo, favicon and extra files, if they are located outside of
+        // the docs and theme directories, since they are not picked up otherwise
+        for (source, url) in &config.theme_files {
+            copy_extra_file(&config, source, url, &*sink, &report)?;
+        }
+
+        // Create partial renderers, which are only recorded in serve mode, and
+        // if partial rendering is enabled, as they retain all rendered pages
+        let partials = Partials::new(serve && config.project.serve.partials);
+        let converter = create_converter(&config);
+        let workflow = create_workflow(
+            &config,
+            strict,
+            &report,
+            &includes,
+            &registry,
+            &cache,
+            &diagnostics,
+            &sink,
+            &scope,
+            &branches,
+            &cancellation,
+            &partials,
+            &converter,
+        );
+        let mut scheduler = Scheduler::<Id>::default();
+        scheduler.attach(workflow);
+
+        // Create session to connect file agent and scheduler - note that we
+        // must assign the agent to a variable right now, or it is dropped, and
+        // will automatically terminate. This is a temporary workaround until
+        // we could better integrate the scheduler with the agent.
+        sessions.push((scheduler.session(), includes, registry));
+        sites.push(Site {
+            config,
+            scheduler,
+            report,
+            diagnostics,
+            branches,
+            sink,
+            partials,
+            forwarder: Forwarder::default(),
+            building: true,
+        });
+    }
+
+    // Create channel for reload notifications, as well as the build state,
+    // which is exposed by the server and updated when builds start or finish
+    let (sender, receiver) = unbounded();
+    let notify = sender.clone();
+    let state = State::default();
 
     // If site should be served, create HTTP server - note that we must assign
     // the agent to a variable right now or it's dropped and will automatically
     // terminate. This is a temporary workaround until we could better integrate
     // the scheduler with the agent.
-    let waker = match &mode {
-        Mode::Build(_) => None,
+    let (waker, mut server) = match &mode {
+        Mode::Build(_) => (None, None),
         Mode::Serve(options, seq) => {
+            let configs = sites
+                .iter()
+                .map(|site| site.config.clone())
+                .collect::<Vec<_>>();
+            let partials = sites
+                .iter()
+                .map(|site| site.partials.clone())
+                .collect::<Vec<_>>();
+            let bound = create_server(
+                &configs,
+                receiver,
+                options.clone(),
+                state.clone(),
+                &partials,
+            );
+
+            // Print banner once the server is bound, so the printed URL is
+            // the one the server is actually reachable at
             if *seq == 0 {
-                println!(
-                    "Serving {} on http://{}",
-                    site_dir.display(),
-                    options
-                        .dev_addr
-                        .as_ref()
-                        .unwrap_or_else(|| &config.project.dev_addr)
-                );
+                for config in &configs {
+                    let site_dir = config.get_site_dir();
+                    let base = config.get_base_path();
+                    for addr in &bound.addrs {
+                        println!(
+                            "Serving {} on {}",
+                            site_dir.display(),
+                            server_url(addr, &base)
+                        );
+                    }
+                    for path in &bound.paths {
+                        println!(
+                            "Serving {} on unix:{}{}",
+                            site_dir.display(),
+                            path.display(),
+                            if configs.len() == 1 {
+                                ""
+                            } else {
+                                base.as_str()
+                            }
+                        );
+                    }
+                    let count = config.theme_dirs.len();
+                    println!(
+                        "Watching {} with {count} theme {}",
+                        config.get_docs_dir().display(),
+                        if count == 1 {
+                            "directory"
+                        } else {
+                            "directories"
+                        }
+                    );
+                }
             } else {
                 println!("Reloading...");
             }
-            Some(create_server(&config, receiver, options.clone()))
+            (Some(bound.waker), Some(bound.handle))
         }
     };
 
-    let serve = matches!(mode, Mode::Serve(_, _));
-    let watcher = Watcher::new(&config, serve, session, sender, waker.clone())?;
+    // Create a watcher for each site, which watches its docs directory, and
+    // only triggers rebuilds of the site it belongs to
+    let mut watchers = Vec::with_capacity(sites.len());
+    for (site, (session, includes, registry)) in sites.iter().zip(sessions) {
+        watchers.push(Watcher::new(
+            &site.config,
+            serve,
+            session,
+            includes,
+            registry,
+            cache.clone(),
+            site.sink.clone(),
+            site.report.clone(),
+            site.diagnostics.clone(),
+            sender.clone(),
+            waker.clone(),
+        )?);
+    }
 
     // Hack: the scheduler and file agent are currently not synchronized, which
     // can lead to cases where the file agent is still busy reading the contents
@@ -235,65 +665,199 @@ fn run(config_file: &PathBuf, mode: Mode) -> PyResult<bool> {
     // starting off while having nothing to do. We need to improve communication
     // between both parts of the system. In the meantime, we wait until the
     // scheduler has something to do, before kicking off work.
-    while scheduler.is_empty() {
+    while sites.iter().any(|site| site.scheduler.is_empty()) {
         thread::sleep(Duration::from_millis(10));
     }
 
     // Start event loop after a short delay - once we tightly integrated the
-    // file agent with the scheduler, the sleep can be removed
+    // file agent with the scheduler, the sleep can be removed. All schedulers
+    // are ticked in turn, so the timeout is split between them.
     println!("Build started");
     let time = Instant::now();
-    let mut maybe_err = None;
-    loop {
+    let count = u32::try_from(sites.len()).unwrap_or(u32::MAX);
+    let timeout = Duration::from_millis(100) / count;
+    let mut maybe_err: Option<Box<dyn std::error::Error>> = None;
+    let mut pending = Vec::new();
+    state.start();
+    'run: loop {
         match mode {
-            // Build mode - just exit when we're done
+            // Build mode - just exit when all sites are done
             Mode::Build(..) => {
-                if let Err(err) =
-                    scheduler.tick_timeout(Duration::from_millis(100))
-                {
-                    maybe_err = Some(err);
-                    break;
+                for site in sites.iter_mut().filter(|site| site.building) {
+                    if let Err(err) = site.scheduler.tick_timeout(timeout) {
+                        maybe_err = Some(err.into());
+                        break 'run;
+                    }
+                    if site.scheduler.is_empty() {
+                        // Wait for branches, e.g., the search index, which run
+                        // in parallel to the scheduler, before finishing
+                        if let Err(err) = site.branches.join() {
+                            maybe_err = Some(err.into());
+                            break 'run;
+                        }
+                        site.building = false;
+                    }
                 }
-                if scheduler.is_empty() {
+                if sites.iter().all(|site| !site.building) {
                     let elapsed = time.elapsed().as_secs_f32();
                     println!("Build finished in {elapsed:.2}s");
+                    for site in &sites {
+                        print!("{}{}", site.heading(sites.len()), site.report);
+                    }
                     break;
                 }
             }
-            // Serve mode - keep watching, until the watcher terminates, which
+            // Serve mode - keep watching, until a watcher terminates, which
             // happens if the configuration file changed. After we've integrated
             // the scheduler with the agent, we can remove this temporary hack
             // and have immediate reloading.
             Mode::Serve(..) => {
-                if let Err(err) =
-                    scheduler.tick_timeout(Duration::from_millis(100))
-                {
-                    maybe_err = Some(err);
-                    break;
+                let building = sites.iter().any(|site| site.building);
+                for (site, watcher) in sites.iter_mut().zip(&watchers) {
+                    if let Err(err) = site.scheduler.tick_timeout(timeout) {
+                        maybe_err = Some(err.into());
+                        break 'run;
+                    }
+
+                    // A build is only finished once the scheduler is idle and
+                    // all branches have finished, which run in parallel to it
+                    let idle = match site.branches.poll() {
+                        Ok(done) => done && site.scheduler.is_empty(),
+                        Err(err) => {
+                            maybe_err = Some(err.into());
+                            break 'run;
+                        }
+                    };
+
+                    // Forward diagnostics to clients as they occur, so errors
+                    // are shown the moment a stage fails, and not only after
+                    // the build
+                    if let Some(message) =
+                        site.forwarder.poll(&site.diagnostics, Instant::now())
+                    {
+                        let _ = notify.send(message.encode());
+                        if let Some(waker) = &waker {
+                            waker.wake()?;
+                        }
+                    }
+
+                    // Print diagnostics of the site once its build finished,
+                    // and collect them until all sites are finished. The report
+                    // is reset, so the next build starts from scratch.
+                    if idle == site.building {
+                        if site.building {
+                            // Watch files included from the project directory,
+                            // which are only known after the build
+                            if let Err(err) = watcher.watch_includes() {
+                                site.diagnostics.warning(format!(
+                                    "Failed to watch included files: {err}"
+                                ));
+                            }
+                            site.diagnostics.flush(&printer);
+                            site.report.reset();
+                            site.forwarder.reset();
+                            pending.extend(site.diagnostics.take());
+                        }
+                        site.building = !site.building;
+                    }
                 }
-                if watcher.is_terminated() {
+
+                // Update build state when the first site starts building, or
+                // the last one finishes, and notify clients if the build
+                // produced errors, so they can display an overlay instead of
+                // reloading a page
+                if sites.iter().any(|site| site.building) != building {
+                    if building {
+                        if let Some(message) =
+                            state.finish(mem::take(&mut pending))
+                        {
+                            let _ = notify.send(message);
+                            if let Some(waker) = &waker {
+                                waker.wake()?;
+                            }
+                        }
+                    } else {
+                        state.start();
+                    }
+                }
+                if watchers.iter().any(Watcher::is_terminated) {
+                    prune_cache(&sites[0].config, &cache, false)?;
+
+                    // Wait for branches, so they don't write into the site
+                    // directory while the next build cleans it, and report
+                    // their errors, as the next build starts from scratch
+                    for site in &sites {
+                        if let Err(err) = site.branches.join() {
+                            site.diagnostics.error(format!("{err:#}"));
+                            site.diagnostics.flush(&printer);
+                        }
+                    }
+
                     // Wake the server
                     if let Some(waker) = &waker {
                         waker.wake()?;
                     }
-                    return Ok(true);
+                    return Ok(Outcome::Restart);
                 }
             }
         }
 
-        // Allow Python to handle signals (e.g., Ctrl+C)
-        if Python::attach(|py| py.check_signals().is_err()) {
-            println!("Received interrupt, exiting");
+        // Allow Python to handle signals (e.g., Ctrl+C), which also runs the
+        // handlers for termination signals, and shut down gracefully. Since
+        // signals are only handled between ticks, the workflow is cancelled,
+        // so remaining Markdown files are skipped, and conversions in flight
+        // are interrupted, which we wait for, unless interrupted again.
+        let interrupted = Python::attach(|py| {
+            let interrupted = py.check_signals().is_err();
+            if interrupted || shutdown::TERMINATED.load(Ordering::SeqCst) {
+                cancellation.cancel();
+                for location in cancellation.interrupt(py) {
+                    println!("Interrupting conversion of '{location}'");
+                }
+            }
+            interrupted
+        });
+        if cancellation.is_cancelled() {
+            while !cancellation.wait_timeout(Duration::from_millis(250)) {
+                if Python::attach(|py| py.check_signals().is_err()) {
+                    break;
+                }
+            }
+        }
+        if shutdown(interrupted, &shutdown::TERMINATED, &cache)? {
+            if interrupted {
+                println!("Received interrupt, exiting");
+            } else {
+                println!("Received termination signal, exiting");
+            }
+
+            // Shut the server down gracefully, so responses in flight are
+            // delivered, and wait for it to finish, which also removes the
+            // Unix domain socket files it's bound to. Then, print diagnostics,
+            // which would otherwise be lost when exiting the process.
+            if let Some(server) = server.take() {
+                if let Err(err) = server.shutdown() {
+                    let message = format!("Failed to shut down server: {err}");
+                    sites[0].diagnostics.error(message);
+                }
+            }
+            for site in &sites {
+                site.diagnostics.flush(&printer);
+            }
             std::process::exit(0);
         }
     }
 
-    // Exit with error, if any
+    // Exit with error, if any, after printing diagnostics emitted so far,
+    // as they often explain the error, and exiting would discard them
     if let Some(err) = maybe_err {
+        for site in &sites {
+            site.diagnostics.flush(&printer);
+        }
         println!("{err}");
         // Walk the error source chain so the root cause (e.g. a missing icon
         // name) is visible instead of only the outermost template error.
-        let mut cause: &dyn std::error::Error = &err;
+        let mut cause: &dyn std::error::Error = &*err;
         while let Some(source) = cause.source() {
             println!("  caused by: {source}");
             cause = source;
@@ -301,21 +865,78 @@ fn run(config_file: &PathBuf, mode: Mode) -> PyResult<bool> {
         std::process::exit(1);
     }
 
+    // Prune unused cache entries, and report them if pruning was requested,
+    // which removes all entries that were not used in this build
+    let all = matches!(
+        &mode, Mode::Build(options) if options.prune_cache.unwrap_or(false)
+    );
+    let count = prune_cache(&sites[0].config, &cache, all)?;
+    if all {
+        println!("Pruned {count} cache entries");
+    }
+
+    // Print diagnostics that were emitted during the build, grouped, so that
+    // they aren't scattered over the output, and don't repeat themselves.
+    // Then, finish output, and summarize the build of each site, including
+    // diagnostics and the size of the output, which is the size of the
+    // archive, if configured, and combine them into the summary of the build.
+    let mut summaries = Vec::with_capacity(sites.len());
+    for site in &sites {
+        site.diagnostics.flush(&printer);
+        let diagnostics = &site.diagnostics;
+        let mut summary = site.report.summary();
+        summary.output_bytes = site.sink.finish()?;
+        summary.warnings = diagnostics.count(Severity::Warning);
+        summary.errors = diagnostics.count(Severity::Error);
+        summary.notes.clone_from(&site.config.notes);
+        summary.diagnostics = diagnostics.take();
+        summary.site_dir.clone_from(&site.config.project.site_dir);
+        print!("{}{summary}", site.heading(sites.len()));
+        summaries.push(summary);
+    }
+
     // All good
-    Ok(false)
+    Ok(Outcome::Finished(Summary::combine(summaries)))
+}
+
+/// Prunes the cache according to the cache settings.
+///
+/// If all is set, every entry that was not used in the current build is
+/// removed, which is cheaper than starting with a clean cache.
+fn prune_cache(config: &Config, cache: &Cache, all: bool) -> io::Result<usize> {
+    let settings = &config.project.cache;
+    let max_age = if all { 1 } else { settings.max_age };
+    cache.prune(max_age, settings.max_size())
 }
 
 // ----------------------------------------------------------------------------
 
-/// Builds the project.
+/// Builds the project, and returns the summary of the build.
+///
+/// This is the entry point for building from Rust, which must not be called
+/// while holding the Python interpreter, as the build calls into Python from
+/// its own threads, e.g., to parse configuration and render Markdown.
+///
+/// # Errors
+///
+/// This function returns an error if the build fails.
+pub fn build_site(
+    config_file: &Path, options: BuildOptions,
+) -> PyResult<Summary> {
+    let config_file = config_file.to_path_buf();
+    if options.reproducible.unwrap_or(false) {
+        return check_reproducible(&config_file, options);
+    }
+    run(&config_file, Mode::Build(options)).map(Outcome::into_summary)
+}
+
+/// Builds the project, and returns the summary of the build.
 #[pyfunction]
 fn build(
     py: Python, config_file: PathBuf, options: BuildOptions,
-) -> PyResult<()> {
-    py.detach(|| {
-        run(&config_file, Mode::Build(options))?;
-        Ok(())
-    })
+) -> PyResult<Summary> {
+    let _handlers = shutdown::install(py)?;
+    py.detach(|| build_site(&config_file, options))
 }
 
 /// Builds and serves the project.
@@ -324,9 +945,10 @@ fn serve(
     py: Python, config_file: PathBuf, mut options: ServeOptions,
 ) -> PyResult<()> {
     let mut seq = 0;
+    let _handlers = shutdown::install(py)?;
     py.detach(|| loop {
         match run(&config_file, Mode::Serve(options.clone(), seq)) {
-            Ok(true) => {
+            Ok(Outcome::Restart) => {
                 options.open = false;
                 seq += 1;
             }
@@ -335,6 +957,95 @@ fn serve(
     })
 }
 
+/// Returns the resolved navigation as nested dictionaries and lists.
+///
+/// All pages are discovered and rendered, but no templates are rendered and
+/// no outputs are written. Each item carries its title, URL, children, whether
+/// it's an index page, and its page metadata, limited to the given keys.
+#[pyfunction]
+#[pyo3(signature = (config_file, meta = None))]
+fn get_navigation(
+    py: Python, config_file: PathBuf, meta: Option<Vec<String>>,
+) -> PyResult<Py<PyAny>> {
+    let data = py.detach(|| -> PyResult<String> {
+        let config = Config::new(&config_file)?;
+        let converter = create_converter(&config);
+        let pages = discover_pages(&config, converter.as_ref())
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+
+        // Resolve navigation, and serialize it, so it can be loaded in Python,
+        // together with the full metadata of the pages the items link to
+        let (nav, _) = resolve_nav(&config, &pages);
+        let lookup = pages
+            .iter()
+            .map(|(_, page)| (&*page.url, &page.meta))
+            .collect::<HashMap<_, _>>();
+        let get = |url: &str| lookup.get(url).copied();
+        serde_json::to_string(&nav.tree(get, meta.as_deref()))
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))
+    })?;
+
+    // Convert into Python objects
+    let json = py.import("json")?;
+    json.call_method1("loads", (data,)).map(Bound::unbind)
+}
+
+/// Returns the normalized configuration as nested dictionaries and lists.
+///
+/// Both configuration formats are normalized into the same settings, so this
+/// allows to compare a `mkdocs.yml` and a `zensical.toml` file for parity.
+#[pyfunction]
+fn get_config(py: Python, config_file: PathBuf) -> PyResult<Py<PyAny>> {
+    let data = py.detach(|| -> PyResult<String> {
+        let config = Config::new(&config_file)?;
+        serde_json::to_string(config.project.as_ref())
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))
+    })?;
+
+    // Convert into Python objects
+    let json = py.import("json")?;
+    json.call_method1("loads", (data,)).map(Bound::unbind)
+}
+
+/// Relocates a built site from the old to the new site URL.
+///
+/// Absolute self-references in HTML, XML, JSON and text files are rewritten
+/// in place, so the same build can be deployed to another host. Returns the
+/// number of rewritten occurrences by file type.
+#[pyfunction]
+fn relocate(
+    py: Python, site_dir: PathBuf, old_site_url: String, new_site_url: String,
+) -> PyResult<BTreeMap<String, usize>> {
+    py.detach(|| {
+        relocate::relocate(&site_dir, &old_site_url, &new_site_url)
+            .map_err(Into::into)
+    })
+}
+
+/// Resolves the theme directories, ordered by precedence.
+///
+/// The custom directory comes first, followed by the theme with the given
+/// name and all of its parents. Themes are looked up among the entry points
+/// installed in the Python environment.
+#[pyfunction]
+#[pyo3(signature = (name, custom_dir = None))]
+fn resolve_theme_dirs(
+    py: Python, name: Option<String>, custom_dir: Option<PathBuf>,
+) -> PyResult<Vec<PathBuf>> {
+    let themes = PythonThemes::new(py);
+    let dirs =
+        resolve::resolve(&themes, name.as_deref(), custom_dir.as_deref());
+    dirs.or_else(|err| {
+        let message = match err {
+            resolve::Error::PyO3(err) => return Err(err),
+            err => err.to_string(),
+        };
+        let module = py.import("zensical.config")?;
+        let err = module.call_method1("ConfigurationError", (message,))?;
+        Err(PyErr::from_value(err))
+    })
+}
+
 /// Returns the current version.
 #[pyfunction]
 fn version() -> String {
@@ -348,6 +1059,10 @@ fn version() -> String {
 fn zensical(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(build, m)?)?;
     m.add_function(wrap_pyfunction!(serve, m)?)?;
+    m.add_function(wrap_pyfunction!(get_navigation, m)?)?;
+    m.add_function(wrap_pyfunction!(get_config, m)?)?;
+    m.add_function(wrap_pyfunction!(relocate, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_theme_dirs, m)?)?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
     Ok(())
 }
@@ -360,8 +1075,54 @@ fn zensical(m: &Bound<'_, PyModule>) -> PyResult<()> {
 mod tests {
     use super::*;
     use std::fs;
+    use std::sync::Once;
     use tempfile::tempdir;
 
+    /// Prepares the Python interpreter, so projects can be built from tests.
+    ///
+    /// The Python package is loaded from the sources, and this crate is made
+    /// available as its native module, so the build runs against this code.
+    fn prepare_python() {
+        static PREPARE: Once = Once::new();
+        PREPARE.call_once(|| {
+            Python::initialize();
+            Python::attach(|py| -> PyResult<()> {
+                let package =
+                    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../python");
+                let sys = py.import("sys")?;
+                sys.getattr("path")?.call_method1("insert", (0, package))?;
+                let module = PyModule::new(py, "zensical.zensical")?;
+                zensical(&module)?;
+                let modules = sys.getattr("modules")?;
+                modules.set_item("zensical.zensical", module)
+            })
+            .unwrap();
+        });
+    }
+
+    /// Copies the fixture project with the given name to the given directory.
+    fn copy_fixture(name: &str, root: &Path) -> io::Result<()> {
+        let from = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name);
+        copy_dir(&from, root)
+    }
+
+    /// Copies the given directory recursively.
+    fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            let path = to.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                copy_dir(&entry.path(), &path)?;
+            } else {
+                fs::copy(entry.path(), path)?;
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn clear_dir_removes_non_hidden_file() {
         let dir = tempdir().unwrap();
@@ -454,4 +1215,77 @@ mod tests {
 
         assert!(dir.path().exists());
     }
+
+    #[test]
+    fn snapshot_is_stable() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("subdir")).unwrap();
+        fs::write(dir.path().join("index.html"), "hello").unwrap();
+        fs::write(dir.path().join("subdir/index.html"), "world").unwrap();
+
+        let first = snapshot(dir.path()).unwrap();
+        let second = snapshot(dir.path()).unwrap();
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn snapshot_detects_changes() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("index.html");
+        fs::write(&file, "hello").unwrap();
+        let first = snapshot(dir.path()).unwrap();
+
+        fs::write(&file, "world").unwrap();
+        let second = snapshot(dir.path()).unwrap();
+
+        let path = Path::new("index.html");
+        assert_ne!(first.get(path), second.get(path));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn setup_tracing_writes_trace_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+
+        let guard = setup_tracing(&path);
+        tracing::info_span!("render_pages").in_scope(|| {});
+        drop(guard);
+
+        let trace = fs::read_to_string(&path).unwrap();
+        assert!(trace.contains("render_pages"));
+    }
+
+    #[test]
+    fn build_site_builds_fixture_project() {
+        prepare_python();
+        let dir = tempdir().unwrap();
+        copy_fixture("site", dir.path()).unwrap();
+
+        let options = BuildOptions {
+            clean: Some(true),
+            strict: Some(false),
+            reproducible: Some(false),
+            prune_cache: Some(false),
+            site_dir: None,
+            cache_dir: None,
+        };
+        let config_file = dir.path().join("zensical.toml");
+        let summary = build_site(&config_file, options).unwrap();
+        assert_eq!(summary.pages, 2);
+        assert_eq!(summary.warnings, 0);
+        assert_eq!(summary.errors, 0);
+
+        let site_dir = dir.path().join("site");
+        let index = fs::read_to_string(site_dir.join("index.html")).unwrap();
+        assert!(index.contains("<title>Home</title>"));
+        assert!(index.contains(r#"<a href="about/">about page</a>"#));
+
+        let about = site_dir.join("about/index.html");
+        let about = fs::read_to_string(about).unwrap();
+        assert!(about.contains("<title>About</title>"));
+        assert!(about.contains(r#"<a href="../#home">home page</a>"#));
+    }
 }
@@ -0,0 +1,654 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Privacy plugin.
+
+use ahash::HashMap;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use std::thread;
+
+use super::config::Config;
+use super::hash::stable_hash;
+use super::structure::url;
+use super::workflow::{Diagnostics, OutputSink};
+
+mod fetch;
+mod store;
+
+use fetch::{fetch, Limit, Response};
+use store::Store;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Directory of downloaded assets, relative to the site directory.
+const ASSETS_DIR: &str = "assets/external";
+
+/// File extensions of downloaded assets, by content type.
+const EXTENSIONS: [(&str, &str); 13] = [
+    ("text/css", "css"),
+    ("text/javascript", "js"),
+    ("application/javascript", "js"),
+    ("font/woff2", "woff2"),
+    ("font/woff", "woff"),
+    ("font/ttf", "ttf"),
+    ("font/otf", "otf"),
+    ("image/svg+xml", "svg"),
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("image/x-icon", "ico"),
+];
+
+/// Link relations of style sheets, scripts, fonts and icons.
+const LINK_ASSETS: [&str; 5] = [
+    "stylesheet",
+    "preload",
+    "modulepreload",
+    "icon",
+    "apple-touch-icon",
+];
+
+/// Link relations of resource hints, which are removed for matching hosts.
+const LINK_HINTS: [&str; 2] = ["preconnect", "dns-prefetch"];
+
+/// Regular expression to match tags that reference assets.
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)<(link|script|img|source|audio|video)\b[^>]*>")
+        .expect("invariant")
+});
+
+/// Regular expression to match quoted attributes.
+static ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\s([a-z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .expect("invariant")
+});
+
+/// Regular expression to match style blocks.
+static STYLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)(<style\b[^>]*>)(.*?)(</style>)").expect("invariant")
+});
+
+/// Regular expression to match references in style sheets.
+static CSS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r#"url\(\s*(?:"([^"]*)"|'([^']*)'|([^)"'\s]*))\s*\)"#,
+        r#"|@import\s+(?:"([^"]*)"|'([^']*)')"#,
+    ))
+    .expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Privacy plugin.
+///
+/// External assets referenced by pages, e.g., fonts, style sheets and scripts
+/// loaded from third-party hosts, are downloaded and written to the site, and
+/// all references are rewritten, so the site doesn't leak visits to others.
+/// Each asset is downloaded at most once per build, even when it's referenced
+/// from several pages that are rendered concurrently.
+#[derive(Clone, Debug)]
+pub struct Privacy {
+    /// Shared state, if the plugin is enabled.
+    inner: Option<Arc<Inner>>,
+}
+
+/// Shared state of the privacy plugin.
+#[derive(Debug)]
+struct Inner {
+    /// Maximum number of parallel downloads.
+    concurrency: usize,
+    /// Whether to only use the cache.
+    offline: bool,
+    /// Patterns of external assets to download.
+    pattern: Regex,
+    /// Site directory.
+    site_dir: PathBuf,
+    /// Cache of external assets.
+    store: Store,
+    /// Limit for concurrent requests.
+    limit: Limit,
+    /// Output sink.
+    sink: Arc<dyn OutputSink>,
+    /// Workflow diagnostics.
+    diagnostics: Diagnostics,
+    /// External assets, by URL.
+    assets: Mutex<HashMap<String, Arc<Asset>>>,
+}
+
+/// External asset.
+#[derive(Debug, Default)]
+struct Asset {
+    /// Response, if the asset could be fetched.
+    response: OnceLock<Option<Response>>,
+    /// Location in the site directory, if the asset was written.
+    location: OnceLock<Option<String>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Privacy {
+    /// Creates the privacy plugin from the given configuration.
+    pub fn new(
+        config: &Config, sink: &Arc<dyn OutputSink>, diagnostics: &Diagnostics,
+    ) -> Self {
+        let plugin = &config.project.plugins.privacy.config;
+        if !plugin.enabled {
+            return Self { inner: None };
+        }
+
+        // Downloaded assets are cached in a hidden directory, so they survive
+        // the pruning of the cache directory after each build
+        let inner = Inner {
+            concurrency: plugin.concurrency.max(1),
+            offline: plugin.offline,
+            pattern: to_pattern(&plugin.patterns),
+            site_dir: config.get_site_dir(),
+            store: Store::new(config.get_cache_dir().join(".privacy")),
+            limit: Limit::new(plugin.concurrency),
+            sink: sink.clone(),
+            diagnostics: diagnostics.clone(),
+            assets: Mutex::default(),
+        };
+        Self { inner: Some(Arc::new(inner)) }
+    }
+
+    /// Returns whether the privacy plugin is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Self-hosts external assets referenced by the given HTML.
+    ///
+    /// References are rewritten relative to the given base URL, which is the
+    /// path from the page to the root of the site. Resource hints for hosts
+    /// of matching assets are removed, as they're not needed anymore.
+    pub fn process_html(&self, base: &str, data: String) -> String {
+        match &self.inner {
+            Some(inner) => inner.rewrite_html(base, &data),
+            None => data,
+        }
+    }
+
+    /// Self-hosts external assets referenced by the given style sheet.
+    ///
+    /// References are rewritten relative to the given location of the style
+    /// sheet in the site directory.
+    pub fn process_css(&self, location: &str, data: String) -> String {
+        match &self.inner {
+            Some(inner) => {
+                inner.rewrite_css(&data, None, &to_prefix(location), &[])
+            }
+            None => data,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Inner {
+    /// Returns whether the given URL matches the patterns.
+    fn is_match(&self, url: &str) -> bool {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        self.pattern.is_match(rest)
+    }
+
+    /// Returns the asset for the given URL, creating it if necessary.
+    fn asset(&self, url: &str) -> Arc<Asset> {
+        let mut assets = self.assets.lock().expect("invariant");
+        assets.entry(url.to_string()).or_default().clone()
+    }
+
+    /// Fetches the given URLs in parallel, with a bounded number of threads.
+    fn prefetch(&self, urls: &[String]) {
+        let next = AtomicUsize::new(0);
+        let threads = self.concurrency.min(urls.len());
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    while let Some(url) =
+                        urls.get(next.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let asset = self.asset(url);
+                        asset.response.get_or_init(|| self.fetch(url));
+                    }
+                });
+            }
+        });
+    }
+
+    /// Fetches the given URL, using the cache if possible.
+    ///
+    /// Cached assets are revalidated, unless offline mode is enabled, and are
+    /// used as a fallback when fetching fails. Assets that can't be obtained
+    /// are reported, and references to them are left as they are.
+    fn fetch(&self, url: &str) -> Option<Response> {
+        let hash = to_hash(url);
+        let cached = self.store.get(url, &hash);
+        let result = if self.offline {
+            cached.ok_or_else(|| "not cached, and offline".to_string())
+        } else {
+            let etag = cached.as_ref().and_then(|c| c.etag.as_deref());
+            let permit = self.limit.acquire();
+            let result = fetch(url, etag);
+            drop(permit);
+            match result {
+                Ok(response) if response.status == 304 => {
+                    cached.ok_or_else(|| "not modified, but not cached".into())
+                }
+                Ok(response) => {
+                    if let Err(err) = self.store.put(url, &hash, &response) {
+                        self.diagnostics.warning(format!(
+                            "Couldn't cache external asset '{url}': {err}"
+                        ));
+                    }
+                    Ok(response)
+                }
+                Err(err) => cached.ok_or(err),
+            }
+        };
+
+        // Report assets that couldn't be obtained
+        result
+            .inspect_err(|err| {
+                self.diagnostics.warning(format!(
+                    "Couldn't fetch external asset '{url}': {err}"
+                ));
+            })
+            .ok()
+    }
+
+    /// Resolves the given URL to the location of the self-hosted asset.
+    ///
+    /// Style sheets are rewritten before they're written, so that the assets
+    /// they reference are self-hosted as well. The given style sheets, which
+    /// are currently being resolved, are skipped to break cycles.
+    fn resolve(&self, url: &str, visited: &[&str]) -> Option<String> {
+        if visited.contains(&url) {
+            return None;
+        }
+
+        // Return location of asset, if it was already written
+        let asset = self.asset(url);
+        if let Some(location) = asset.location.get() {
+            return location.clone();
+        }
+
+        // Fetch asset, and compute location from the URL and content type
+        let response = asset.response.get_or_init(|| self.fetch(url));
+        let response = response.as_ref()?;
+        let extension = to_extension(url, &response.content_type);
+        let location = format!("{ASSETS_DIR}/{}.{extension}", to_hash(url));
+
+        // Rewrite references in style sheets, which are resolved relative to
+        // the URL of the style sheet, and always self-hosted
+        let data = if extension == "css" {
+            let mut visited = visited.to_vec();
+            visited.push(url);
+            let data = String::from_utf8_lossy(&response.body);
+            let prefix = to_prefix(&location);
+            let data = self.rewrite_css(&data, Some(url), &prefix, &visited);
+            Cow::Owned(data.into_bytes())
+        } else {
+            Cow::Borrowed(&response.body)
+        };
+
+        // Write asset, unless it was written concurrently
+        let location = asset.location.get_or_init(|| {
+            let path = self.site_dir.join(&location);
+            match self.sink.write(&path, &data) {
+                Ok(()) => Some(location),
+                Err(err) => {
+                    self.diagnostics.warning(format!(
+                        "Couldn't write external asset '{url}': {err}"
+                    ));
+                    None
+                }
+            }
+        });
+        location.clone()
+    }
+
+    /// Rewrites references to external assets in the given HTML.
+    fn rewrite_html(&self, base: &str, data: &str) -> String {
+        let urls = TAG_RE
+            .captures_iter(data)
+            .filter_map(|caps| self.source(&caps[0], &caps[1]))
+            .map(|(_, url)| url)
+            .collect::<Vec<_>>();
+
+        // Fetch assets in parallel, then rewrite tags and style blocks
+        self.prefetch(&urls);
+        let data = TAG_RE.replace_all(data, |caps: &Captures| {
+            self.rewrite_tag(base, &caps[0], &caps[1])
+        });
+        let prefix = format!("{base}/");
+        let data = STYLE_RE.replace_all(&data, |caps: &Captures| {
+            let css = self.rewrite_css(&caps[2], None, &prefix, &[]);
+            format!("{}{css}{}", &caps[1], &caps[3])
+        });
+        data.into_owned()
+    }
+
+    /// Rewrites the given tag, if it references an external asset.
+    fn rewrite_tag(&self, base: &str, tag: &str, name: &str) -> String {
+        if is_hint(tag, name) {
+            let href = attribute(tag, "href").and_then(to_external);
+            if href.is_some_and(|url| self.is_match(&url)) {
+                return String::new();
+            }
+        }
+
+        // Resolve external asset, and leave the tag as is if that fails
+        let Some((key, url)) = self.source(tag, name) else {
+            return tag.to_string();
+        };
+        let Some(location) = self.resolve(&url, &[]) else {
+            return tag.to_string();
+        };
+
+        // Replace reference, and remove the integrity hash of style sheets,
+        // since their references were rewritten, so the hash doesn't match
+        let is_style = url::extension(&location) == Some("css");
+        let mut result = String::with_capacity(tag.len());
+        let mut last = 0;
+        for caps in ATTR_RE.captures_iter(tag) {
+            let span = caps.get(0).expect("invariant");
+            let attr = &caps[1];
+            if attr.eq_ignore_ascii_case(key) {
+                result.push_str(&tag[last..span.start()]);
+                write!(result, " {attr}=\"{base}/{location}\"")
+                    .expect("invariant");
+                last = span.end();
+            } else if attr.eq_ignore_ascii_case("integrity") && is_style {
+                result.push_str(&tag[last..span.start()]);
+                last = span.end();
+            }
+        }
+        result.push_str(&tag[last..]);
+        result
+    }
+
+    /// Returns the attribute and URL of the external asset of the given tag.
+    fn source(&self, tag: &str, name: &str) -> Option<(&'static str, String)> {
+        let key = if name.eq_ignore_ascii_case("link") {
+            let rel = attribute(tag, "rel")?.to_ascii_lowercase();
+            let mut iter = rel.split_whitespace();
+            iter.any(|value| LINK_ASSETS.contains(&value))
+                .then_some("href")?
+        } else {
+            "src"
+        };
+
+        // Only consider external assets that match the patterns
+        let url = attribute(tag, key).and_then(to_external)?;
+        self.is_match(&url).then(|| (key, url.into_owned()))
+    }
+
+    /// Rewrites references to external assets in the given style sheet.
+    ///
+    /// If the style sheet was downloaded, its URL is given, and all references
+    /// are resolved relative to it and self-hosted. Otherwise, only absolute
+    /// references that match the patterns are self-hosted. Rewritten references
+    /// are prefixed with the given prefix.
+    fn rewrite_css(
+        &self, data: &str, source: Option<&str>, prefix: &str, visited: &[&str],
+    ) -> String {
+        let to_url = |value: &str| match source {
+            Some(source) => to_absolute(source, value),
+            None => to_external(value)
+                .filter(|url| self.is_match(url))
+                .map(Cow::into_owned),
+        };
+
+        // Fetch assets in parallel, then rewrite references
+        let urls = CSS_RE
+            .captures_iter(data)
+            .filter_map(|caps| to_url(reference(&caps).0))
+            .filter(|url| !visited.contains(&url.as_str()))
+            .collect::<Vec<_>>();
+        self.prefetch(&urls);
+        let data = CSS_RE.replace_all(data, |caps: &Captures| {
+            let (value, is_import) = reference(caps);
+            let location =
+                to_url(value).and_then(|url| self.resolve(&url, visited));
+            match (location, is_import) {
+                (Some(location), false) => {
+                    format!("url(\"{prefix}{location}\")")
+                }
+                (Some(location), true) => {
+                    format!("@import \"{prefix}{location}\"")
+                }
+                (None, _) => caps[0].to_string(),
+            }
+        });
+        data.into_owned()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Compiles the given patterns into a regular expression.
+fn to_pattern(patterns: &[String]) -> Regex {
+    let alternatives = patterns
+        .iter()
+        .map(|pattern| regex::escape(pattern).replace(r"\*", ".*"))
+        .collect::<Vec<_>>();
+    Regex::new(&format!("^(?:{})$", alternatives.join("|"))).expect("invariant")
+}
+
+/// Returns the hash of the given URL, which names the downloaded asset.
+fn to_hash(url: &str) -> String {
+    format!("{:016x}", stable_hash(&url))
+}
+
+/// Returns the file extension for the given URL and content type.
+///
+/// The content type takes precedence, since URLs of assets served by content
+/// delivery networks often don't have an extension, e.g., for fonts.
+fn to_extension<'a>(url: &'a str, content_type: &str) -> &'a str {
+    let known = EXTENSIONS.iter().find(|(kind, _)| *kind == content_type);
+    if let Some((_, extension)) = known {
+        return extension;
+    }
+
+    // Fall back to the extension in the path of the URL
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name.rsplit_once('.') {
+        Some((_, extension))
+            if !extension.is_empty()
+                && extension.len() <= 5
+                && extension.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            extension
+        }
+        _ => "bin",
+    }
+}
+
+/// Returns the prefix to reach the site root from the given location.
+fn to_prefix(location: &str) -> String {
+    "../".repeat(location.matches('/').count())
+}
+
+/// Returns the given reference as an absolute URL, if it's external.
+///
+/// Protocol-relative references are assumed to use HTTPS.
+fn to_external(value: &str) -> Option<Cow<'_, str>> {
+    let value = value.trim();
+    if value.starts_with("https://") || value.starts_with("http://") {
+        Some(Cow::Borrowed(value))
+    } else if value.starts_with("//") {
+        Some(Cow::Owned(format!("https:{value}")))
+    } else {
+        None
+    }
+}
+
+/// Resolves the given reference against the given absolute URL.
+///
+/// Returns nothing for data URIs and fragments, which are left as they are.
+fn to_absolute(base: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('#') || value.starts_with("data:")
+    {
+        return None;
+    }
+    if let Some(url) = to_external(value) {
+        return Some(url.into_owned());
+    }
+
+    // Split base URL into origin and path, without query and fragment
+    let (scheme, rest) = base.split_once("://")?;
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+    // Join paths, and normalize segments, retaining the query of the value
+    let (value, query) = value
+        .find(['?', '#'])
+        .map_or((value, ""), |index| value.split_at(index));
+    let joined = if value.starts_with('/') {
+        value.to_string()
+    } else {
+        let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        format!("{dir}/{value}")
+    };
+    let mut segments = Vec::new();
+    for segment in joined.split('/').skip(1) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    Some(format!("{scheme}://{host}/{}{query}", segments.join("/")))
+}
+
+/// Returns the value of the given attribute of the given tag, if any.
+fn attribute<'a>(tag: &'a str, key: &str) -> Option<&'a str> {
+    ATTR_RE
+        .captures_iter(tag)
+        .find(|caps| caps[1].eq_ignore_ascii_case(key))
+        .and_then(|caps| caps.get(2).or(caps.get(3)))
+        .map(|value| value.as_str())
+}
+
+/// Returns whether the given tag is a resource hint.
+fn is_hint(tag: &str, name: &str) -> bool {
+    name.eq_ignore_ascii_case("link")
+        && attribute(tag, "rel").is_some_and(|rel| {
+            let rel = rel.to_ascii_lowercase();
+            rel.split_whitespace()
+                .any(|value| LINK_HINTS.contains(&value))
+        })
+}
+
+/// Returns the value of the given reference in a style sheet, and whether
+/// it's an import.
+fn reference<'a>(caps: &Captures<'a>) -> (&'a str, bool) {
+    let group = (1..=5).find_map(|index| caps.get(index).map(|m| (index, m)));
+    group.map_or(("", false), |(index, value)| (value.as_str(), index > 3))
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_pattern_matches_without_scheme() {
+        let pattern = to_pattern(&["fonts.*.com/*".to_string()]);
+        assert!(pattern.is_match("fonts.googleapis.com/css?family=Roboto"));
+        assert!(!pattern.is_match("cdn.example.com/fonts.googleapis.com/"));
+    }
+
+    #[test]
+    fn to_absolute_resolves_references() {
+        let base = "https://example.com/css/main.css?v=1";
+        assert_eq!(
+            to_absolute(base, "../fonts/a.woff2?x#y").as_deref(),
+            Some("https://example.com/fonts/a.woff2?x#y")
+        );
+        assert_eq!(
+            to_absolute(base, "/b.css").as_deref(),
+            Some("https://example.com/b.css")
+        );
+        assert_eq!(
+            to_absolute(base, "//cdn.example.com/c.js").as_deref(),
+            Some("https://cdn.example.com/c.js")
+        );
+        assert_eq!(to_absolute(base, "data:font/woff2;base64,AA"), None);
+    }
+
+    #[test]
+    fn to_extension_prefers_content_type() {
+        let url = "https://fonts.googleapis.com/css?family=Roboto";
+        assert_eq!(to_extension(url, "text/css"), "css");
+        let url = "https://example.com/a/font.woff2";
+        assert_eq!(to_extension(url, "application/octet-stream"), "woff2");
+        let url = "https://example.com/a/font";
+        assert_eq!(to_extension(url, "application/octet-stream"), "bin");
+    }
+
+    #[test]
+    fn reference_distinguishes_imports() {
+        let data = r#"@import "a.css"; src: url('b.woff2'), url(c.woff)"#;
+        let references = CSS_RE
+            .captures_iter(data)
+            .map(|caps| reference(&caps))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            references,
+            [("a.css", true), ("b.woff2", false), ("c.woff", false)]
+        );
+    }
+
+    #[test]
+    fn is_hint_detects_resource_hints() {
+        let tag = r#"<link rel="preconnect" href="https://fonts.gstatic.com">"#;
+        assert!(is_hint(tag, "link"));
+        let tag = r#"<link rel="stylesheet" href="https://example.com/a.css">"#;
+        assert!(!is_hint(tag, "link"));
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Fetching of external assets.
+
+use pyo3::prelude::*;
+use std::sync::{Condvar, Mutex};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Response of a request for an external asset.
+#[derive(Clone, Debug)]
+pub struct Response {
+    /// Status code.
+    pub status: u16,
+    /// Entity tag, if any.
+    pub etag: Option<String>,
+    /// Content type.
+    pub content_type: String,
+    /// Body.
+    pub body: Vec<u8>,
+}
+
+/// Limit for concurrent requests.
+#[derive(Debug)]
+pub struct Limit {
+    /// Number of requests in flight.
+    count: Mutex<usize>,
+    /// Condition variable to wait for a free slot.
+    condvar: Condvar,
+    /// Maximum number of requests in flight.
+    max: usize,
+}
+
+/// Permit for a request, which frees its slot when dropped.
+pub struct Permit<'a> {
+    /// Limit the permit was acquired from.
+    limit: &'a Limit,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Limit {
+    /// Creates a limit with the given maximum number of requests in flight.
+    pub fn new(max: usize) -> Self {
+        Self {
+            count: Mutex::new(0),
+            condvar: Condvar::new(),
+            max: max.max(1),
+        }
+    }
+
+    /// Acquires a permit, blocking until a slot is free.
+    pub fn acquire(&self) -> Permit<'_> {
+        let mut count = self.count.lock().expect("invariant");
+        while *count >= self.max {
+            count = self.condvar.wait(count).expect("invariant");
+        }
+        *count += 1;
+        Permit { limit: self }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Drop for Permit<'_> {
+    /// Frees the slot of the permit.
+    fn drop(&mut self) {
+        *self.limit.count.lock().expect("invariant") -= 1;
+        self.limit.condvar.notify_one();
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Fetches the given URL, revalidating it with the given entity tag.
+///
+/// Requests are sent from Python, which releases the interpreter lock while
+/// waiting for the network, so requests from multiple threads are concurrent.
+/// Responses with error status codes, as well as network errors, are returned
+/// as errors, so they can be reported.
+pub fn fetch(url: &str, etag: Option<&str>) -> Result<Response, String> {
+    Python::attach(|py| {
+        let module = py.import("zensical.utilities.fetch")?;
+        module.call_method1("fetch", (url, etag))?.extract::<(
+            u16,
+            Option<String>,
+            String,
+            Vec<u8>,
+        )>()
+    })
+    .map(|(status, etag, content_type, body)| Response {
+        status,
+        etag,
+        content_type,
+        body,
+    })
+    .map_err(|err| err.to_string())
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn limit_bounds_concurrency() {
+        let limit = Limit::new(2);
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let _permit = limit.acquire();
+                    let count = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(count, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(5));
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Cache of external assets.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::workflow::write_atomic;
+
+use super::fetch::Response;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Cache of external assets.
+///
+/// Each asset is stored in two files named after the hash of its URL, one for
+/// the body of the response, and one for its metadata, i.e., the URL, entity
+/// tag and content type. Assets are revalidated using the entity tag, and the
+/// cache is used as a fallback when assets can't be fetched.
+#[derive(Debug)]
+pub struct Store {
+    /// Cache directory.
+    dir: PathBuf,
+    /// Lock, so body and metadata of an asset are written together.
+    lock: Mutex<()>,
+}
+
+/// Metadata of a cached asset.
+#[derive(Debug, Deserialize, Serialize)]
+struct Meta {
+    /// URL.
+    url: String,
+    /// Entity tag, if any.
+    etag: Option<String>,
+    /// Content type.
+    content_type: String,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Store {
+    /// Creates a cache in the given directory.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, lock: Mutex::default() }
+    }
+
+    /// Returns the cached response for the given URL, if any.
+    pub fn get(&self, url: &str, hash: &str) -> Option<Response> {
+        let meta = fs::read(self.dir.join(format!("{hash}.json"))).ok()?;
+        let meta = serde_json::from_slice::<Meta>(&meta).ok()?;
+        if meta.url != url {
+            return None;
+        }
+
+        // Read body, and return response
+        let body = fs::read(self.dir.join(hash)).ok()?;
+        Some(Response {
+            status: 200,
+            etag: meta.etag,
+            content_type: meta.content_type,
+            body,
+        })
+    }
+
+    /// Stores the given response for the given URL.
+    ///
+    /// The body is written before the metadata, so an interrupted write never
+    /// leaves metadata pointing to a missing or incomplete body. Both files are
+    /// written atomically, so concurrent readers never see truncated files.
+    pub fn put(
+        &self, url: &str, hash: &str, response: &Response,
+    ) -> io::Result<()> {
+        let _guard = self.lock.lock().expect("invariant");
+        fs::create_dir_all(&self.dir)?;
+        write_atomic(&self.dir.join(hash), &response.body)?;
+        let meta = Meta {
+            url: url.to_string(),
+            etag: response.etag.clone(),
+            content_type: response.content_type.clone(),
+        };
+        let meta = serde_json::to_vec(&meta).map_err(io::Error::other)?;
+        write_atomic(&self.dir.join(format!("{hash}.json")), &meta)
+    }
+}
@@ -39,6 +39,28 @@ use zrx::stream::Value;
 pub struct Anchors {
     /// Inner set of anchors.
     inner: Vec<String>,
+    /// Stable identifier of the page, if any.
+    stable_id: Option<String>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Anchors {
+    /// Sets the stable identifier of the page the anchors belong to.
+    #[inline]
+    #[must_use]
+    pub fn with_stable_id(mut self, stable_id: Option<String>) -> Self {
+        self.stable_id = stable_id;
+        self
+    }
+
+    /// Returns the stable identifier of the page, if any.
+    #[inline]
+    pub fn stable_id(&self) -> Option<&str> {
+        self.stable_id.as_deref()
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -67,6 +89,7 @@ impl FromStr for Anchors {
             // Collect anchors into an anchor set
             Ok(Anchors {
                 inner: iter.collect::<PyResult<_>>()?,
+                stable_id: None,
             })
         })
     }
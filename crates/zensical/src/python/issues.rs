@@ -111,6 +111,8 @@ pub enum Issue {
 pub struct Issues {
     /// Markdown contents for printing errors.
     contents: HashMap<String, String>,
+    /// Stable identifiers of pages by source path.
+    ids: HashMap<String, String>,
     /// Inner set of issues.
     inner: Vec<Issue>,
 }
@@ -132,6 +134,36 @@ impl Issue {
         }
     }
 
+    /// Returns whether the issue is reported with the given settings.
+    pub fn is_reported(&self, validation: &Validation) -> bool {
+        match self {
+            Issue::UnresolvedReference { .. } => {
+                validation.unresolved_references
+            }
+            Issue::UnresolvedFootnote { .. } => validation.unresolved_footnotes,
+            Issue::UnusedDefinition { .. } => validation.unused_definitions,
+            Issue::UnusedFootnote { .. } => validation.unused_footnotes,
+            Issue::ShadowedDefinition { .. } => validation.shadowed_definitions,
+            Issue::ShadowedFootnote { .. } => validation.shadowed_footnotes,
+            Issue::InvalidLink { .. } => validation.invalid_links,
+            Issue::InvalidLinkAnchor { .. } => validation.invalid_link_anchors,
+        }
+    }
+
+    /// Returns the message of the issue.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Issue::UnresolvedReference { .. } => "unresolved link reference",
+            Issue::UnresolvedFootnote { .. } => "unresolved footnote reference",
+            Issue::UnusedDefinition { .. } => "unused link definition",
+            Issue::UnusedFootnote { .. } => "unused footnote definition",
+            Issue::ShadowedDefinition { .. } => "shadowed link definition",
+            Issue::ShadowedFootnote { .. } => "shadowed footnote definition",
+            Issue::InvalidLink { .. } => "page does not exist",
+            Issue::InvalidLinkAnchor { .. } => "anchor does not exist",
+        }
+    }
+
     /// Returns the span of the issue.
     pub fn span(&self) -> &Span {
         match self {
@@ -160,6 +192,7 @@ impl Issues {
     {
         let mut issues = Vec::new();
         let mut contents = HashMap::default();
+        let mut ids = HashMap::default();
 
         // Create link map and anchor map and find inner-page issues
         let mut link_map = HashMap::default();
@@ -170,6 +203,9 @@ impl Issues {
 
             // Associate anchors with their location for lookup
             contents.insert(path.clone(), references.markdown().to_string());
+            if let Some(stable) = anchors.stable_id() {
+                ids.insert(path.clone(), stable.to_string());
+            }
             anchor_map.insert(
                 to_slash(&path),
                 anchors.into_iter().cloned().collect::<HashSet<_>>(),
@@ -395,108 +431,50 @@ impl Issues {
         });
 
         // Return issues
-        Self { contents, inner: issues }
+        Self { contents, ids, inner: issues }
     }
 
     /// Prints the issue to stderr.
-    #[allow(clippy::match_same_arms)]
-    #[allow(clippy::too_many_lines)]
-    pub fn print(&self, validation: &Validation, strict: bool) -> Result {
+    ///
+    /// The given map associates source paths of the previous build with the
+    /// stable identifiers of their pages, which is used to suggest the new
+    /// location of pages that were renamed or moved for invalid links.
+    pub fn print(
+        &self, validation: &Validation, strict: bool,
+        previous: &HashMap<String, String>,
+    ) -> Result {
         let mut count = 0;
         for issue in &self.inner {
-            // Determine the path and kind of report
+            // Skip issues that are not reported, and determine the message
+            if !issue.is_reported(validation) {
+                continue;
+            }
             let path = issue.path().to_string_lossy();
-            let kind = match issue {
-                Issue::UnresolvedReference { .. } => {
-                    if !validation.unresolved_references {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::UnresolvedFootnote { .. } => {
-                    if !validation.unresolved_footnotes {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::UnusedDefinition { .. } => {
-                    if !validation.unused_definitions {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::UnusedFootnote { .. } => {
-                    if !validation.unused_footnotes {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::ShadowedDefinition { .. } => {
-                    if !validation.shadowed_definitions {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::ShadowedFootnote { .. } => {
-                    if !validation.shadowed_footnotes {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::InvalidLink { .. } => {
-                    if !validation.invalid_links {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-                Issue::InvalidLinkAnchor { .. } => {
-                    if !validation.invalid_link_anchors {
-                        continue;
-                    }
-                    ReportKind::Warning
-                }
-            };
-
-            // Determine the label message and color
-            let (message, color) = match issue {
-                Issue::UnresolvedReference { .. } => {
-                    ("unresolved link reference", Color::Yellow)
-                }
-                Issue::UnresolvedFootnote { .. } => {
-                    ("unresolved footnote reference", Color::Yellow)
-                }
-                Issue::UnusedDefinition { .. } => {
-                    ("unused link definition", Color::Yellow)
-                }
-                Issue::UnusedFootnote { .. } => {
-                    ("unused footnote definition", Color::Yellow)
-                }
-                Issue::ShadowedDefinition { .. } => {
-                    ("shadowed link definition", Color::Yellow)
-                }
-                Issue::ShadowedFootnote { .. } => {
-                    ("shadowed footnote definition", Color::Yellow)
-                }
-                Issue::InvalidLink { .. } => {
-                    ("page does not exist", Color::Yellow)
-                }
-                Issue::InvalidLinkAnchor { .. } => {
-                    ("anchor does not exist", Color::Yellow)
-                }
-            };
+            let message = issue.message();
 
             // Create report
-            let builder = Report::build(
-                kind,
+            let mut builder = Report::build(
+                ReportKind::Warning,
                 (path.as_ref(), Range::from(*issue.span())),
             )
             .with_message(message)
             .with_label(
                 Label::new((path.as_ref(), Range::from(*issue.span())))
                     .with_message(message)
-                    .with_color(color),
+                    .with_color(Color::Yellow),
             );
 
+            // Suggest new location, if the linked page was moved
+            let moved = match issue {
+                Issue::InvalidLink { href, .. } => self.moved(href, previous),
+                _ => None,
+            };
+            if let Some((id, source)) = moved {
+                builder = builder.with_help(format!(
+                    "page with id '{id}' was moved to '{source}'"
+                ));
+            }
+
             // Obtain Markdown source
             let source = self
                 .contents()
@@ -525,6 +503,42 @@ impl Issues {
         Ok(())
     }
 
+    /// Returns the stable identifier and new source path of a moved page.
+    ///
+    /// If the given link points to a page of the previous build that has a
+    /// stable identifier, and a page with the same identifier exists now, the
+    /// page was renamed or moved, and the link should point to its new source.
+    pub fn moved<'a>(
+        &'a self, href: &str, previous: &'a HashMap<String, String>,
+    ) -> Option<(&'a str, &'a str)> {
+        let id = previous.get(href)?;
+        let mut sources = self
+            .ids
+            .iter()
+            .filter(|(source, stable)| *source != href && *stable == id)
+            .map(|(source, _)| source.as_str())
+            .collect::<Vec<_>>();
+
+        // Pick the first source, so the suggestion is stable
+        sources.sort_unstable();
+        sources.first().map(|source| (id.as_str(), *source))
+    }
+
+    /// Returns the line and column of the given issue, starting at 1.
+    ///
+    /// Columns are counted in characters, not bytes, so they match what is
+    /// shown in editors. If the Markdown source is unknown, the start of the
+    /// file is returned.
+    pub fn position(&self, issue: &Issue) -> (usize, usize) {
+        let path = issue.path().to_string_lossy();
+        let content = self.contents.get(&*path).map_or("", String::as_str);
+        let offset = issue.span().start.min(content.len());
+        let before = content.get(..offset).unwrap_or_default();
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[start..].chars().count() + 1)
+    }
+
     /// Returns the Markdown contents.
     pub fn contents(&self) -> &HashMap<String, String> {
         &self.contents
@@ -675,8 +689,13 @@ fn to_slash(path: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use ahash::HashMap;
+
+    use crate::config::validation::Validation;
+
     use super::{
         decode_markdown_href, is_invalid_markdown_path, is_markdown_path,
+        Error, Issue, Issues, Span,
     };
 
     #[test]
@@ -710,4 +729,74 @@ mod tests {
         assert_eq!(decode_markdown_href(r"a\:b"), r"a\:b");
         assert_eq!(decode_markdown_href(r"a\qb"), r"a\qb");
     }
+
+    #[test]
+    fn moved_page_is_found_by_stable_id() {
+        let issues = Issues {
+            contents: HashMap::default(),
+            ids: HashMap::from_iter([
+                ("index.md".into(), "index".into()),
+                ("start.md".into(), "setup".into()),
+            ]),
+            inner: Vec::new(),
+        };
+        let previous = HashMap::from_iter([
+            ("setup.md".to_string(), "setup".to_string()),
+            ("index.md".to_string(), "index".to_string()),
+        ]);
+        assert_eq!(
+            issues.moved("setup.md", &previous),
+            Some(("setup", "start.md"))
+        );
+        assert_eq!(issues.moved("index.md", &previous), None);
+        assert_eq!(issues.moved("other.md", &previous), None);
+    }
+
+    #[test]
+    fn position_counts_lines_and_characters() {
+        let issue = Issue::InvalidLink {
+            path: "index.md".into(),
+            span: Span::from(10..28),
+            href: "setup.md".into(),
+        };
+        let issues = Issues {
+            contents: HashMap::from_iter([(
+                "index.md".into(),
+                "# Hé\n\nä [Setup](setup.md)".into(),
+            )]),
+            ids: HashMap::default(),
+            inner: vec![issue.clone()],
+        };
+        assert_eq!(issues.position(&issue), (3, 3));
+    }
+
+    #[test]
+    fn print_fails_in_strict_mode() {
+        let issues = Issues {
+            contents: HashMap::from_iter([(
+                "index.md".into(),
+                "[Setup](setup.md)".into(),
+            )]),
+            ids: HashMap::default(),
+            inner: vec![Issue::InvalidLink {
+                path: "index.md".into(),
+                span: Span::from(0..17),
+                href: "setup.md".into(),
+            }],
+        };
+        let validation = Validation::default();
+        let previous = HashMap::default();
+        assert!(issues.print(&validation, false, &previous).is_ok());
+        assert!(matches!(
+            issues.print(&validation, true, &previous),
+            Err(Error::Strict)
+        ));
+
+        // Issues that are not reported never fail the build
+        let validation = Validation {
+            invalid_links: false,
+            ..validation
+        };
+        assert!(issues.print(&validation, true, &previous).is_ok());
+    }
 }
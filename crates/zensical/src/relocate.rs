@@ -0,0 +1,355 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Site relocation.
+
+use fluent_uri::Uri;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::workflow::{is_precompressed, recompress};
+
+mod error;
+
+pub use error::{Error, Result};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// File types that might contain absolute self-references, by extension.
+const FILE_TYPES: [(&str, &str); 5] = [
+    ("html", "html"),
+    ("htm", "html"),
+    ("xml", "xml"),
+    ("json", "json"),
+    ("txt", "txt"),
+];
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Site URL, split into base URL and path.
+struct Base<'a> {
+    /// Base URL, without trailing slash.
+    url: &'a str,
+    /// Path, without trailing slash.
+    path: &'a str,
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Relocates a built site from the old to the new site URL.
+///
+/// All absolute self-references in HTML, XML, JSON and text files are rewritten
+/// in place, which covers canonical URLs, social cards, the sitemap, feeds and
+/// the search index, as well as the sitemap reference in `robots.txt`, so the
+/// same build can be deployed to another host without rebuilding it. Returns
+/// the number of rewritten occurrences by file type.
+///
+/// Only the scheme, host and port may change, since root-relative links would
+/// break otherwise. References to other hosts are never touched, including
+/// hosts that only share a prefix with the old host, e.g., when relocating
+/// from `https://example.com`, then `https://example.com.evil.org` is kept.
+/// Precompressed copies of rewritten files are rewritten as well.
+pub fn relocate(
+    site_dir: &Path, old: &str, new: &str,
+) -> Result<BTreeMap<String, usize>> {
+    let old = base(old)?;
+    let new = base(new)?;
+    if old.path != new.path {
+        return Err(Error::PathMismatch {
+            old: old.url.to_string(),
+            new: new.url.to_string(),
+        });
+    }
+
+    // Collect all files, in order to rewrite them in a deterministic order
+    let mut files = Vec::new();
+    collect_files(site_dir, &mut files)?;
+    files.sort();
+
+    // Report all file types, even if no occurrences were rewritten
+    let mut counts = FILE_TYPES
+        .iter()
+        .map(|(_, kind)| (kind.to_string(), 0))
+        .collect::<BTreeMap<_, _>>();
+
+    // Rewrite all files of known types, skipping files that aren't UTF-8
+    for path in files {
+        let Some(kind) = file_type(&path) else {
+            continue;
+        };
+        let io = |source| Error::Io { path: path.clone(), source };
+        let Ok(content) = String::from_utf8(fs::read(&path).map_err(io)?)
+        else {
+            continue;
+        };
+
+        // Write file and its precompressed copies, if anything was rewritten
+        let (content, count) = rewrite(&content, old.url, new.url);
+        if count > 0 {
+            fs::write(&path, &content).map_err(io)?;
+            recompress(&path, content.as_bytes()).map_err(io)?;
+            *counts.entry(kind.to_string()).or_default() += count;
+        }
+    }
+
+    // Return counts by file type
+    Ok(counts)
+}
+
+/// Rewrites all occurrences of the old base URL in the given content.
+///
+/// Occurrences are only rewritten if they're not followed by a character that
+/// continues the host, port or path segment, e.g., `.` or `:`, which ensures
+/// that other hosts sharing a prefix are kept. Returns the rewritten content,
+/// and the number of rewritten occurrences.
+fn rewrite(content: &str, old: &str, new: &str) -> (String, usize) {
+    let mut output = String::with_capacity(content.len());
+    let mut count = 0;
+
+    // Find all occurrences, and check whether they end at a boundary
+    let mut rest = content;
+    while let Some(index) = rest.find(old) {
+        let end = index + old.len();
+        output.push_str(&rest[..index]);
+        if is_boundary(rest[end..].chars().next()) {
+            output.push_str(new);
+            count += 1;
+        } else {
+            output.push_str(old);
+        }
+        rest = &rest[end..];
+    }
+
+    // Append remaining content
+    output.push_str(rest);
+    (output, count)
+}
+
+/// Returns whether the given character ends a base URL.
+fn is_boundary(next: Option<char>) -> bool {
+    !next.is_some_and(|char| {
+        char.is_alphanumeric()
+            || matches!(char, '.' | '-' | '_' | ':' | '~' | '%' | '@')
+    })
+}
+
+/// Parses the given site URL, which must be an absolute HTTP(S) URL.
+fn base(url: &str) -> Result<Base<'_>> {
+    let invalid = || Error::InvalidUrl(url.to_string());
+    let uri = Uri::parse(url).map_err(|_| invalid())?;
+    let is_valid = matches!(uri.scheme().as_str(), "http" | "https")
+        && uri.authority().is_some_and(|auth| !auth.host().is_empty())
+        && uri.query().is_none()
+        && uri.fragment().is_none();
+    if !is_valid {
+        return Err(invalid());
+    }
+
+    // Strip trailing slashes, so both URLs are compared consistently
+    let url = url.trim_end_matches('/');
+    let path = uri.path().as_str().trim_end_matches('/');
+    Ok(Base {
+        url,
+        path: &url[url.len() - path.len()..],
+    })
+}
+
+/// Returns the file type of the given path, if it might contain self-references.
+fn file_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?;
+    FILE_TYPES
+        .iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(extension))
+        .map(|(_, kind)| *kind)
+}
+
+/// Collects all files in the given directory recursively, except for
+/// precompressed copies, which are rewritten together with their originals.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result {
+    let io = |source| Error::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    for entry in fs::read_dir(dir).map_err(io)? {
+        let path = entry.map_err(io)?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if !is_precompressed(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use flate2::read::GzDecoder;
+    use std::fs;
+    use std::io::Read;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    use super::{relocate, rewrite, Error};
+
+    /// Writes a small built site into the given directory.
+    fn fixture(dir: &Path) {
+        let files = [
+            (
+                "index.html",
+                r#"<html><head>
+                <link rel="canonical" href="https://staging.example.com/">
+                <meta property="og:url" content="https://staging.example.com/">
+                <meta property="og:image" content="https://staging.example.com/assets/card.png">
+                </head><body>
+                <a href="https://staging.example.com.evil.org/">Evil</a>
+                <a href="https://staging.example.community/">Community</a>
+                <a href="https://staging.example.com:8443/">Port</a>
+                <a href="http://staging.example.com/">Insecure</a>
+                <a href="https://github.com/zensical/zensical">GitHub</a>
+                </body></html>"#,
+            ),
+            (
+                "sitemap.xml",
+                "<urlset><url><loc>https://staging.example.com/</loc></url>\
+                 <url><loc>https://staging.example.com/guide/</loc></url>\
+                 </urlset>",
+            ),
+            (
+                "feed_rss_created.xml",
+                "<rss><channel><link>https://staging.example.com</link>\
+                 </channel></rss>",
+            ),
+            (
+                "search.json",
+                r#"{"items":[{"location":"guide/"}],"url":"https://staging.example.com/"}"#,
+            ),
+            (
+                "robots.txt",
+                "Sitemap: https://staging.example.com/sitemap.xml\n",
+            ),
+            ("assets/app.js", "fetch(\"https://staging.example.com/\")"),
+        ];
+        for (path, content) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn relocates_self_references() {
+        let dir = tempdir().unwrap();
+        fixture(dir.path());
+        let counts = relocate(
+            dir.path(),
+            "https://staging.example.com",
+            "https://example.com/",
+        )
+        .unwrap();
+        assert_eq!(counts["html"], 3);
+        assert_eq!(counts["xml"], 3);
+        assert_eq!(counts["json"], 1);
+        assert_eq!(counts["txt"], 1);
+
+        // Self-references are rewritten, all other URLs are kept
+        let html = fs::read_to_string(dir.path().join("index.html")).unwrap();
+        assert!(html.contains(r#"href="https://example.com/""#));
+        assert!(html.contains("https://example.com/assets/card.png"));
+        assert!(html.contains("https://staging.example.com.evil.org/"));
+        assert!(html.contains("https://staging.example.community/"));
+        assert!(html.contains("https://staging.example.com:8443/"));
+        assert!(html.contains("http://staging.example.com/"));
+        assert!(html.contains("https://github.com/zensical/zensical"));
+        let feed = dir.path().join("feed_rss_created.xml");
+        assert!(fs::read_to_string(feed)
+            .unwrap()
+            .contains("<link>https://example.com</link>"));
+
+        // Other file types are never touched
+        let js = fs::read_to_string(dir.path().join("assets/app.js")).unwrap();
+        assert_eq!(js, "fetch(\"https://staging.example.com/\")");
+    }
+
+    #[test]
+    fn rewrites_precompressed_copies() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("robots.txt");
+        fs::write(&path, "Sitemap: https://staging.example.com/sitemap.xml")
+            .unwrap();
+        fs::write(dir.path().join("robots.txt.gz"), b"stale").unwrap();
+        relocate(
+            dir.path(),
+            "https://staging.example.com",
+            "https://example.com",
+        )
+        .unwrap();
+
+        // Precompressed copy matches the rewritten file
+        let data = fs::read(dir.path().join("robots.txt.gz")).unwrap();
+        let mut content = String::new();
+        GzDecoder::new(data.as_slice())
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, fs::read_to_string(path).unwrap());
+    }
+
+    #[test]
+    fn rewrites_only_at_boundaries() {
+        let (output, count) = rewrite(
+            "https://a.com/docs/ https://a.com/docs https://a.com/docs-v2/",
+            "https://a.com/docs",
+            "https://b.com/docs",
+        );
+        assert_eq!(
+            output,
+            "https://b.com/docs/ https://b.com/docs https://a.com/docs-v2/"
+        );
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn rejects_invalid_site_urls() {
+        let dir = tempdir().unwrap();
+        let res = relocate(dir.path(), "staging.example.com", "https://a.com");
+        assert!(matches!(res, Err(Error::InvalidUrl(_))));
+        let res = relocate(dir.path(), "https://a.com", "ftp://b.com");
+        assert!(matches!(res, Err(Error::InvalidUrl(_))));
+
+        // Paths must not change, as root-relative links would break
+        let res = relocate(dir.path(), "https://a.com/docs/", "https://b.com/");
+        assert!(matches!(res, Err(Error::PathMismatch { .. })));
+    }
+}
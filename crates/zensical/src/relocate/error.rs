@@ -0,0 +1,82 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Relocation error.
+
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::PyErr;
+use std::path::PathBuf;
+use std::{io, result};
+use thiserror::Error;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Relocation error.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Site URL is not an absolute HTTP(S) URL with a host.
+    #[error("Site URL '{0}' is not an absolute URL")]
+    InvalidUrl(String),
+    /// Site URLs differ in their path, which relocation can't change.
+    #[error("Site URLs '{old}' and '{new}' must have the same path")]
+    PathMismatch {
+        /// Old site URL.
+        old: String,
+        /// New site URL.
+        new: String,
+    },
+    /// File can't be read or written.
+    #[error("File '{}' could not be relocated: {source}", path.display())]
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying I/O error.
+        source: io::Error,
+    },
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl From<Error> for PyErr {
+    /// Converts a relocation error to a [`PyErr`].
+    #[inline]
+    fn from(err: Error) -> PyErr {
+        match err {
+            Error::Io { .. } => PyErr::new::<PyIOError, _>(err.to_string()),
+            _ => PyErr::new::<PyValueError, _>(err.to_string()),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Relocation result.
+pub type Result<T = ()> = result::Result<T, Error>;
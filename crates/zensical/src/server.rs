@@ -28,17 +28,33 @@
 use crossbeam::channel::{unbounded, Receiver};
 use mio::Waker;
 use pyo3::FromPyObject;
-use std::sync::Arc;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::JoinHandle;
 use std::{fs, thread};
-use zensical_serve::handler::Stack;
-use zensical_serve::middleware;
-use zensical_serve::server::{Result, Server};
+use zensical_serve::handler::{Handler, Stack, TryIntoHandler};
+use zensical_serve::http::Request;
+use zensical_serve::middleware::{self, Lookup, Middleware};
+use zensical_serve::server::{Builder, Result, Server};
 
 use super::config::Config;
 
+mod cache;
 mod client;
+mod editor;
+mod message;
+mod partial;
+mod search;
+mod status;
 
+use cache::CacheControl;
 use client::Client;
+pub use message::{Forwarder, Message};
+pub use partial::Partials;
+pub use status::State;
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -52,61 +68,252 @@ pub struct ServeOptions {
     pub dev_addr: Option<String>,
     /// Whether to open the browser automatically.
     pub open: bool,
+    /// Glob pattern narrowing the pages that are built, if any.
+    pub scope: Option<String>,
+    /// Site directory, overriding the configured one.
+    pub site_dir: Option<String>,
+    /// Cache directory, overriding the configured one.
+    pub cache_dir: Option<String>,
+    /// Whether to rehearse the caching behavior of a production deployment.
+    pub production_cache: bool,
+}
+
+/// Site served under a base path.
+struct Mount {
+    /// Base path.
+    base: String,
+    /// Site directory.
+    site_dir: PathBuf,
+    /// Whether to use directory URLs.
+    use_directory_urls: bool,
+    /// Partial renderers.
+    partials: Partials,
+}
+
+/// Page a request refers to.
+#[derive(Debug, Deserialize)]
+struct Target {
+    /// URL of the page, if any.
+    page: Option<String>,
+}
+
+/// Bound server.
+pub struct Bound {
+    /// Waker to wake the server from its polling loop.
+    pub waker: Arc<Waker>,
+    /// Addresses the server is bound to.
+    pub addrs: Vec<SocketAddr>,
+    /// Unix domain socket paths the server is bound to.
+    pub paths: Vec<PathBuf>,
+    /// Handle to shut the server down.
+    pub handle: Handle,
+}
+
+/// Handle to a running server.
+pub struct Handle {
+    /// Whether the server should shut down.
+    shutdown: Arc<AtomicBool>,
+    /// Waker to wake the server from its polling loop.
+    waker: Arc<Waker>,
+    /// Thread running the server.
+    thread: JoinHandle<Result>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Mount {
+    /// Creates the stack serving the site under its base path.
+    ///
+    /// With directory URLs, all paths without extension are redirected to
+    /// directories, while file URLs are resolved like most static hosts do,
+    /// i.e., `/changelog` is served from `changelog.html`.
+    fn stack(self, cache_control: CacheControl) -> Stack {
+        let mut stack = Stack::new()
+            .with(partial::router(self.base.clone(), self.partials))
+            .with(search::router(self.site_dir.clone()));
+
+        // Serve files from the site directory, with the given cache policy
+        let files = middleware::StaticFiles::new(&self.site_dir)
+            .expect("invariant")
+            .with_cache_control(move |path, mime| {
+                Some(cache_control.value(path, mime))
+            });
+        let files = if self.use_directory_urls {
+            stack = stack.with(middleware::NormalizePath::default());
+            files
+        } else {
+            files.with_lookup([Lookup::Html, Lookup::Index])
+        };
+        stack
+            .with(middleware::BasePath::new(self.base).expect("invariant"))
+            .with(files)
+            .try_into_handler()
+            .expect("invariant")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Handle {
+    /// Shuts the server down gracefully, and waits for it to finish.
+    ///
+    /// The server stops accepting connections, and keeps polling until all
+    /// responses in flight are delivered, or the drain deadline has passed.
+    /// Once the server is dropped, the Unix domain socket files it's bound to
+    /// are removed. Errors that stopped the server before are returned.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the server can't be woken, or failed.
+    pub fn shutdown(self) -> Result {
+        self.shutdown.store(true, Ordering::SeqCst);
+        self.waker.wake()?;
+
+        // Panics are already reported by the server thread itself
+        self.thread.join().unwrap_or(Ok(()))
+    }
 }
 
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
 
-/// Creates an HTTP server to serve the site.
+/// Creates an HTTP server to serve the sites.
+///
+/// The given state is updated by the build process, and exposed to clients at
+/// `/__zensical/status`, which is available independent of the base path, as
+/// is `/__zensical/open`, which opens files in the configured editor,
+/// `/__zensical/partial`, which renders single blocks of pages, if enabled, and
+/// `/__zensical/search`, which previews results of queries on the search
+/// index. Each site is served under its own base path, together with the given
+/// partial renderers, which are ordered like the sites. Returns once the server
+/// is bound, together with the addresses it's bound to, as they might differ
+/// from the configured one, e.g., when binding to port 0.
 pub fn create_server(
-    config: &Config, receiver: Receiver<String>, options: ServeOptions,
-) -> Arc<Waker> {
-    let site_dir = config.get_site_dir();
-    fs::create_dir_all(&site_dir).expect("site directory could not be created");
+    configs: &[Config], receiver: Receiver<String>, options: ServeOptions,
+    state: State, partials: &[Partials],
+) -> Bound {
+    let mounts = configs
+        .iter()
+        .zip(partials)
+        .map(|(config, partials)| Mount {
+            base: config.get_base_path(),
+            site_dir: config.get_site_dir(),
+            use_directory_urls: config.project.use_directory_urls,
+            partials: partials.clone(),
+        })
+        .collect::<Vec<_>>();
+    for mount in &mounts {
+        fs::create_dir_all(&mount.site_dir)
+            .expect("site directory could not be created");
+    }
 
     // Create a one shot channel to extract waker - this is currently necessary,
     // so that the server wakes up when the file watcher emits new events
     let (tx, rx) = unbounded();
+    let config = &configs[0];
     let addr = options
         .dev_addr
         .unwrap_or_else(|| config.project.dev_addr.clone());
 
     // Create new thread to run the server
     let base = config.get_base_path();
-    thread::spawn({
+    let root_dir = config.get_root_dir();
+    // Files can only be opened in the editor when serving on a loopback
+    // address, as the route launches processes on behalf of the client
+    let editor_command = config
+        .project
+        .serve
+        .editor_command
+        .clone()
+        .filter(|_| editor::is_loopback(&addr));
+    let cache_control = if options.production_cache {
+        CacheControl::Production
+    } else {
+        CacheControl::Development
+    };
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let thread = thread::spawn({
+        let shutdown = shutdown.clone();
         let tx = tx.clone();
         move || -> Result {
-            // Ensure site directory exists
-            fs::create_dir_all(&site_dir).unwrap();
+            // The port is only known after binding, so the client script reads
+            // it lazily, which also covers binding to port 0
+            let port = Arc::new(OnceLock::new());
             let stack = Stack::new()
-                .with(Client::default())
+                .with(middleware::ServerOptions::default())
+                .with(middleware::InjectScript::new(client::MARKER, {
+                    let port = port.clone();
+                    move || client::script(port.get().copied())
+                }))
+                .with(Client::new(state.clone()))
                 .with(middleware::WebSocketHandshake::default())
-                .with(middleware::NormalizePath::default())
-                .with(middleware::BasePath::new(base).expect("invariant"))
-                .with(
-                    middleware::StaticFiles::new(&site_dir).expect("invariant"),
-                );
+                .with(status::router(state))
+                .with(editor::router(
+                    root_dir,
+                    editor_command,
+                    editor::Process,
+                ));
+
+            // Create a stack for each site, and dispatch requests to the site
+            // whose base path matches, which is the only one for most projects
+            let bases = mounts
+                .iter()
+                .map(|mount| mount.base.clone())
+                .collect::<Vec<_>>();
+            let sites = mounts
+                .into_iter()
+                .map(|mount| mount.stack(cache_control))
+                .collect::<Vec<_>>();
+            let stack = stack.with(move |req: Request, next: &dyn Handler| {
+                let target = req.uri.query.deserialize::<Target>().ok();
+                let page = target.and_then(|target| target.page);
+                let index = select(&bases, &req.uri.path, page.as_deref());
+                sites[index].process(req, next)
+            });
 
             // Start server and extract waker for interaction with event loop
-            let mut server = match Server::new(stack, &addr) {
+            let res = Server::builder(stack)
+                .and_then(|builder| bind(builder, &addr))
+                .and_then(Builder::listen);
+            let mut server = match res {
                 Ok(server) => server,
                 Err(err) => {
                     let _ = tx.send(Err(err));
                     return Ok(());
                 }
             };
+            let addrs = server.local_addrs();
+            if let Some(addr) = addrs.first() {
+                let _ = port.set(addr.port());
+            }
 
-            // Open browser, if desired
-            if options.open {
-                let url = format!("http://{addr}");
+            // Open browser, if desired - this is not possible when serving over
+            // a Unix domain socket, which is usually behind a reverse proxy
+            if options.open && socket_path(&addr).is_none() {
+                let url = addrs.first().map_or_else(
+                    || format!("http://{addr}"),
+                    |addr| server_url(addr, &base),
+                );
                 let _ = webbrowser::open(&url);
             }
 
-            // Send waker back to main thread
-            let _ = tx.send(Ok(server.waker()));
+            // Send waker and bound addresses back to main thread
+            let waker = server.waker();
+            let paths = local_paths(&server);
+            let _ = tx.send(Ok((waker, addrs, paths)));
+
+            // Poll until the server was shut down, and all responses in flight
+            // are drained, which is checked after the server was woken
             loop {
-                server.poll(Some(&receiver))?;
+                if shutdown.load(Ordering::SeqCst) {
+                    server.shutdown()?;
+                }
+                if !server.poll(Some(&receiver))? {
+                    return Ok(());
+                }
             }
         }
     });
@@ -115,10 +322,149 @@ pub fn create_server(
     // restructure this logic, but for now, it's quite safe to assume that when
     // the server thread could not be started, the address is already in use.
     match rx.recv().expect("invariant") {
-        Ok(waker) => waker,
+        Ok((waker, addrs, paths)) => Bound {
+            waker: waker.clone(),
+            addrs,
+            paths,
+            handle: Handle { shutdown, waker, thread },
+        },
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
     }
 }
+
+/// Adds the given address to the server builder.
+///
+/// Addresses starting with `unix:` denote Unix domain sockets, which are only
+/// supported on Unix, e.g., `unix:/run/zensical.sock`. All other addresses
+/// are resolved as socket addresses, e.g., `localhost:8000`.
+fn bind<H>(builder: Builder<H>, addr: &str) -> Result<Builder<H>>
+where
+    H: Handler,
+{
+    match socket_path(addr) {
+        #[cfg(unix)]
+        Some(path) => builder.bind_unix(path),
+        #[cfg(not(unix))]
+        Some(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Unix domain sockets are not supported on this platform",
+        )
+        .into()),
+        None => builder.bind(addr),
+    }
+}
+
+/// Returns the Unix domain socket path, if the address uses the `unix:` scheme.
+fn socket_path(addr: &str) -> Option<&Path> {
+    addr.strip_prefix("unix:")
+        .filter(|path| !path.is_empty())
+        .map(Path::new)
+}
+
+/// Returns the Unix domain socket paths the server is bound to.
+#[cfg(unix)]
+fn local_paths<H>(server: &Server<H>) -> Vec<PathBuf>
+where
+    H: Handler,
+{
+    server.local_paths()
+}
+
+/// Returns the Unix domain socket paths the server is bound to.
+#[cfg(not(unix))]
+fn local_paths<H>(_server: &Server<H>) -> Vec<PathBuf>
+where
+    H: Handler,
+{
+    Vec::new()
+}
+
+/// Returns the index of the site that serves the request to the given path.
+///
+/// Requests are served by the site with the longest base path the path starts
+/// with. Requests to `/__zensical/` are served by the site of the page they
+/// refer to, if any, e.g., for partial rendering. All other requests, as well
+/// as all requests to projects with a single site, are served by the first.
+fn select(bases: &[String], path: &str, page: Option<&str>) -> usize {
+    let path = match page {
+        Some(page) if path.starts_with("/__zensical/") => page,
+        _ => path,
+    };
+
+    // Find the longest base path that is a prefix of the path
+    let iter = bases.iter().enumerate().filter(|(_, base)| {
+        let base = base.trim_end_matches('/');
+        path.strip_prefix(base)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    });
+    iter.max_by_key(|(_, base)| base.len())
+        .map_or(0, |(index, _)| index)
+}
+
+/// Returns the URL under which the site is served at the given address.
+///
+/// The base path is derived from the site URL, so the URL points to the home
+/// page of the site, e.g., `http://127.0.0.1:8000/docs/`.
+pub fn server_url(addr: &SocketAddr, base: &str) -> String {
+    let base = base.trim_end_matches('/');
+    format!("http://{addr}{base}/")
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::path::Path;
+
+    use super::{select, server_url, socket_path};
+
+    #[test]
+    fn select_prefers_longest_base_path() {
+        let bases = ["/".to_string(), "/api".to_string(), "/docs".to_string()];
+        assert_eq!(select(&bases, "/docs/", None), 2);
+        assert_eq!(select(&bases, "/docs", None), 2);
+        assert_eq!(select(&bases, "/api/guide/", None), 1);
+        assert_eq!(select(&bases, "/apis/", None), 0);
+        assert_eq!(select(&bases, "/", None), 0);
+    }
+
+    #[test]
+    fn select_uses_page_for_internal_routes() {
+        let bases = ["/a".to_string(), "/b".to_string()];
+        assert_eq!(select(&bases, "/__zensical/partial", Some("/b/x/")), 1);
+        assert_eq!(select(&bases, "/__zensical/search", None), 0);
+        assert_eq!(select(&bases, "/b/", Some("/a/x/")), 1);
+        assert_eq!(select(&bases, "/c/", None), 0);
+    }
+
+    #[test]
+    fn server_url_includes_base_path() {
+        let addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        assert_eq!(server_url(&addr, "/"), "http://127.0.0.1:8001/");
+        assert_eq!(server_url(&addr, "/docs"), "http://127.0.0.1:8001/docs/");
+        assert_eq!(server_url(&addr, "/docs/"), "http://127.0.0.1:8001/docs/");
+    }
+
+    #[test]
+    fn server_url_brackets_ipv6_addresses() {
+        let addr: SocketAddr = "[::1]:8000".parse().unwrap();
+        assert_eq!(server_url(&addr, "/"), "http://[::1]:8000/");
+    }
+
+    #[test]
+    fn socket_path_requires_unix_scheme() {
+        assert_eq!(
+            socket_path("unix:/run/zensical.sock"),
+            Some(Path::new("/run/zensical.sock"))
+        );
+        assert_eq!(socket_path("unix:site.sock"), Some(Path::new("site.sock")));
+        assert_eq!(socket_path("unix:"), None);
+        assert_eq!(socket_path("localhost:8000"), None);
+    }
+}
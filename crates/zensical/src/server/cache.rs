@@ -0,0 +1,152 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Cache control.
+
+use crate::structure::url;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Time to live of fingerprinted assets, which never change, in seconds.
+const IMMUTABLE_TTL: u64 = 365 * 24 * 60 * 60;
+
+/// Time to live of HTML documents in seconds.
+const HTML_TTL: u64 = 60;
+
+/// Time to live of other files in seconds.
+const DEFAULT_TTL: u64 = 60 * 60;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Cache control policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheControl {
+    /// Require revalidation of all files.
+    ///
+    /// Browsers cache stylesheets and scripts aggressively, which is why edits
+    /// might seem to not apply during previews. With `no-cache`, the browser
+    /// still caches files, but revalidates them before use, so unchanged files
+    /// are answered with `304 Not Modified`, keeping transfers small.
+    Development,
+    /// Rehearse the behavior of a production deployment.
+    ///
+    /// Fingerprinted assets are cached for a year, as their names change with
+    /// their content, HTML documents for a minute, and other files for an hour.
+    Production,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl CacheControl {
+    /// Returns the `Cache-Control` header value for the given file.
+    #[must_use]
+    pub fn value(self, path: &str, mime: &str) -> String {
+        match self {
+            CacheControl::Development => String::from("no-cache"),
+            CacheControl::Production if is_fingerprinted(path) => {
+                format!("public, max-age={IMMUTABLE_TTL}, immutable")
+            }
+            CacheControl::Production => {
+                let ttl = if mime.starts_with("text/html") {
+                    HTML_TTL
+                } else {
+                    DEFAULT_TTL
+                };
+                format!("public, max-age={ttl}")
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns whether the file name at the given path contains a fingerprint.
+///
+/// Fingerprints are hexadecimal hashes of at least 8 characters, which are
+/// separated by dots from the other parts of the file name, as used for the
+/// assets of themes, e.g., `main.6543a935.min.css`.
+fn is_fingerprinted(path: &str) -> bool {
+    let Some(name) = url::file_name(path) else {
+        return false;
+    };
+    // Only consider parts between the base name and the extension
+    let parts = name.split('.').collect::<Vec<_>>();
+    parts.len() > 2
+        && parts[1..parts.len() - 1].iter().any(|part| {
+            part.len() >= 8 && part.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::CacheControl;
+
+    #[test]
+    fn development_requires_revalidation() {
+        for (path, mime) in [
+            ("/index.html", "text/html"),
+            ("/assets/main.6543a935.min.css", "text/css"),
+            ("/assets/logo.svg", "image/svg+xml"),
+        ] {
+            let value = CacheControl::Development.value(path, mime);
+            assert_eq!(value, "no-cache");
+        }
+    }
+
+    #[test]
+    fn production_caches_by_file_class() {
+        let policy = CacheControl::Production;
+        for (path, mime, expected) in [
+            (
+                "/assets/main.6543a935.min.css",
+                "text/css",
+                "public, max-age=31536000, immutable",
+            ),
+            (
+                "/assets/bundle.79ae519e.min.js",
+                "text/javascript",
+                "public, max-age=31536000, immutable",
+            ),
+            ("/", "text/html; charset=utf-8", "public, max-age=60"),
+            ("/guide/index.html", "text/html", "public, max-age=60"),
+            ("/assets/extra.css", "text/css", "public, max-age=3600"),
+            ("/assets/6543a935.css", "text/css", "public, max-age=3600"),
+        ] {
+            assert_eq!(policy.value(path, mime), expected, "{path}");
+        }
+    }
+}
@@ -29,6 +29,8 @@ use zensical_serve::handler::Handler;
 use zensical_serve::http::{Header, Request, Response, Status};
 use zensical_serve::middleware::Middleware;
 
+use super::status::State;
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
@@ -38,7 +40,13 @@ use zensical_serve::middleware::Middleware;
 /// This script connects to the WebSocket server and listens for messages. When
 /// a message is received, it will either reload the page or update a CSS file
 /// dynamically to reflect changes without a full page reload, allowing for
-/// very fast feedback loops when editing CSS files.
+/// very fast feedback loops when editing CSS files. If the build failed, the
+/// errors are shown in an overlay, instead of reloading a half-built page,
+/// with links to open the location of each error in the configured editor.
+/// Errors are also shown while the build is running, the moment they occur,
+/// and warnings are logged to the console.
+/// The `host` constant is prepended by [`script`], as it depends on the port
+/// the server is actually bound to.
 static CLIENT: &str = concat!(
     "(() => {\n",
     "  const title = document.title;\n",
@@ -46,10 +54,61 @@ static CLIENT: &str = concat!(
     "  function pending(state) {\n",
     "    document.title = state ? \"Waiting for connection\" : title;\n",
     "  }\n",
+    "  function overlay(diagnostics, title) {\n",
+    "    let el = document.getElementById(\"__zensical-overlay\");\n",
+    "    if (!el) {\n",
+    "      el = document.createElement(\"pre\");\n",
+    "      el.id = \"__zensical-overlay\";\n",
+    "      el.style.cssText = \"position:fixed;inset:0;z-index:99999;\" +\n",
+    "        \"margin:0;padding:2em;overflow:auto;white-space:pre-wrap;\" +\n",
+    "        \"background:rgba(0,0,0,.9);color:#f66;font:14px monospace\";\n",
+    "      el.addEventListener(\"click\", () => el.remove());\n",
+    "      document.body.appendChild(el);\n",
+    "    }\n",
+    "    el.textContent = `${title}\\n\\n`;\n",
+    "    diagnostics.forEach(diagnostic => {\n",
+    "      const item = document.createElement(\"div\");\n",
+    "      item.style.marginBottom = \"1em\";\n",
+    "      item.textContent = diagnostic.message;\n",
+    "      const at = diagnostic.location;\n",
+    "      if (at) {\n",
+    "        const query = new URLSearchParams({ path: at.path });\n",
+    "        if (at.line) query.set(\"line\", at.line);\n",
+    "        if (at.column) query.set(\"column\", at.column);\n",
+    "        const link = document.createElement(\"a\");\n",
+    "        link.href = \"#\";\n",
+    "        link.style.cssText = \"display:block;color:#6cf\";\n",
+    "        link.textContent = [at.path, at.line, at.column]\n",
+    "          .filter(Boolean).join(\":\");\n",
+    "        link.addEventListener(\"click\", ev => {\n",
+    "          ev.preventDefault();\n",
+    "          ev.stopPropagation();\n",
+    "          fetch(`/__zensical/open?${query}`, { method: \"POST\" });\n",
+    "        });\n",
+    "        item.appendChild(link);\n",
+    "      }\n",
+    "      el.appendChild(item);\n",
+    "    });\n",
+    "  }\n",
     "  function connect() {\n",
-    "    const socket = new WebSocket(`ws://${window.location.host}`);\n",
+    "    const socket = new WebSocket(`ws://${host}`);\n",
     "    pending(true);\n",
     "    socket.addEventListener(\"message\", ev => {\n",
+    "      if (ev.data.startsWith(\"{\")) {\n",
+    "        const data = JSON.parse(ev.data);\n",
+    "        if (data.type === \"build-error\") {\n",
+    "          overlay(data.diagnostics, \"Build failed\");\n",
+    "        }\n",
+    "        if (data.type === \"diagnostics\") {\n",
+    "          const errors = data.diagnostics\n",
+    "            .filter(diagnostic => diagnostic.severity === \"error\");\n",
+    "          data.diagnostics\n",
+    "            .filter(diagnostic => diagnostic.severity === \"warning\")\n",
+    "            .forEach(diagnostic => console.warn(diagnostic.message));\n",
+    "          if (errors.length) overlay(errors, \"Build error\");\n",
+    "        }\n",
+    "        return\n",
+    "      }\n",
     "      if (ev.data.endsWith(\".css\")) {\n",
     "        document.querySelectorAll(`link[rel=\"stylesheet\"]`)",
     "          .forEach(link => {\n",
@@ -63,7 +122,9 @@ static CLIENT: &str = concat!(
     "      if (ev.data.endsWith(\".js\")) {\n",
     "        window.location.reload()\n",
     "      }\n",
-    "      if (ev.data == decodeURI(window.location.pathname)) {\n",
+    "      const strip = path => path.replace(/\\/?(index\\.html)?$/, \"\")\n",
+    "      const path = decodeURI(window.location.pathname)\n",
+    "      if (strip(ev.data) == strip(path)) {\n",
     "        window.location.reload()\n",
     "      }\n",
     "    });\n",
@@ -83,13 +144,53 @@ static CLIENT: &str = concat!(
     "})()\n"
 );
 
+/// Holding page, served while the first build is in progress.
+///
+/// The livereload client script is injected into this page, so the browser
+/// reloads once the requested page is written, and the page refreshes itself
+/// after the given delay, so it eventually resolves to a 404 if the requested
+/// page doesn't exist after the first build has completed.
+static HOLDING: &str = concat!(
+    "<!doctype html><html><head><meta charset=\"utf-8\">",
+    "<meta http-equiv=\"refresh\" content=\"2\">",
+    "<title>Building…</title></head>",
+    "<body><p>Building…</p></body></html>"
+);
+
+/// Seconds after which clients should retry while the first build runs.
+const RETRY_AFTER: u64 = 2;
+
+/// Marker of the livereload client script.
+///
+/// This is used as the `id` of the injected script element, so responses that
+/// already contain the client are left untouched.
+pub static MARKER: &str = "__zensical-reload";
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
 
 /// Middleware for livereload client.
-#[derive(Default)]
-pub struct Client;
+///
+/// Until the first build has completed, requests for files that don't exist
+/// yet are answered with a holding page and `503 Service Unavailable`, since
+/// the files are likely still being written. Afterwards, misses are normal
+/// 404s, which are left untouched.
+pub struct Client {
+    /// Build state.
+    state: State,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Client {
+    /// Creates the middleware for the given build state.
+    pub fn new(state: State) -> Self {
+        Self { state }
+    }
+}
 
 // ----------------------------------------------------------------------------
 // Trait implementations
@@ -101,39 +202,93 @@ impl Middleware for Client {
         let uri = req.uri.path.clone();
         let mut res = next.handle(req);
 
-        // In case an HTML file is served, inject the client script
-        if let Some(value) = res.headers.get(Header::ContentType) {
-            if value.contains("text/html") {
-                res.body.extend(b"<script type=\"module\">");
-                res.body.extend(CLIENT.as_bytes());
-                res.body.extend(b"</script>");
-
-                // Update content length
-                res.headers.insert(Header::ContentLength, res.body.len());
-            }
-        }
-
         // Never cache JavaScript or CSS files, so reloading works smoothly
         if uri.ends_with(".js") || uri.ends_with(".css") {
             res.headers.insert(Header::CacheControl, "no-cache");
         }
 
-        // In case of a 404 on "/", we attach the WebSocket script, so it will
-        // automatically reload once the build has finished. This is temporary,
-        // since we're working on properly integrating all moving parts of
-        // the system into a coherent flow.
-        if res.status == Status::NotFound {
+        // In case of a 404 during the first build, we serve a holding page,
+        // into which the client script is injected, so it will automatically
+        // reload once the requested page was written. Internal endpoints are
+        // excluded, as they don't depend on the build.
+        if res.status == Status::NotFound
+            && !uri.starts_with("/__zensical/")
+            && !self.state.is_ready()
+        {
+            res.status = Status::ServiceUnavailable;
             res.body.clear();
-            res.body.extend(b"<script type=\"module\">");
-            res.body.extend(CLIENT.as_bytes());
-            res.body.extend(b"</script>");
+            res.body.extend(HOLDING.as_bytes());
 
-            // Update content length
+            // Update headers, so clients know when to retry
             res.headers.insert(Header::ContentType, "text/html");
             res.headers.insert(Header::ContentLength, res.body.len());
+            res.headers.insert(Header::RetryAfter, RETRY_AFTER);
+            res.headers.insert(Header::CacheControl, "no-cache");
         }
 
         // Return response
         res
     }
 }
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the livereload client script for the given port.
+///
+/// The WebSocket connects to the port the server is bound to, which might not
+/// be the configured one, e.g., when binding to port `0`. If the port is not
+/// known yet, the client falls back to the host the page was served from.
+pub fn script(port: Option<u16>) -> String {
+    let host = match port {
+        Some(port) => format!("`${{window.location.hostname}}:{port}`"),
+        None => String::from("window.location.host"),
+    };
+    format!("const host = {host};\n{CLIENT}")
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use zensical_serve::handler::NotFound;
+    use zensical_serve::http::{Header, Method, Request, Response, Status};
+    use zensical_serve::middleware::Middleware;
+
+    use crate::server::status::State;
+
+    use super::Client;
+
+    /// Requests a missing page from the middleware with the given state.
+    fn request(state: &State) -> Response {
+        let req = Request::new().method(Method::Get).uri("/missing/");
+        Client::new(state.clone()).process(req, &NotFound)
+    }
+
+    #[test]
+    fn serves_holding_page_during_first_build() {
+        let state = State::default();
+        state.start();
+        let res = request(&state);
+        assert_eq!(res.status, Status::ServiceUnavailable);
+        assert_eq!(res.headers.get(Header::RetryAfter), Some("2"));
+        assert!(String::from_utf8_lossy(&res.body).contains("Building"));
+    }
+
+    #[test]
+    fn serves_not_found_after_first_build() {
+        let state = State::default();
+        state.start();
+        state.finish(Vec::new());
+        let res = request(&state);
+        assert_eq!(res.status, Status::NotFound);
+        assert!(res.headers.get(Header::RetryAfter).is_none());
+
+        // Subsequent builds don't bring back the holding page
+        state.start();
+        assert_eq!(request(&state).status, Status::NotFound);
+    }
+}
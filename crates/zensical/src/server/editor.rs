@@ -0,0 +1,394 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Open in editor.
+
+use serde::Deserialize;
+use std::io;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use zensical_serve::http::response::ResponseExt;
+use zensical_serve::http::{Header, Request, Response, Status};
+use zensical_serve::router::{Params, Router};
+
+// ----------------------------------------------------------------------------
+// Traits
+// ----------------------------------------------------------------------------
+
+/// Editor launcher.
+///
+/// Launching is abstracted behind this trait, so the route can be tested
+/// without spawning processes.
+pub trait Launch: 'static {
+    /// Launches the given program with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the program could not be launched.
+    fn launch(&self, program: &str, args: &[String]) -> io::Result<()>;
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Editor launcher spawning a process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Process;
+
+/// Location to open.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+struct Target {
+    /// Path of the file.
+    path: String,
+    /// Line number, starting at 1.
+    line: Option<usize>,
+    /// Column number, starting at 1.
+    column: Option<usize>,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Launch for Process {
+    /// Spawns the program, and reaps it in the background once it exits.
+    fn launch(&self, program: &str, args: &[String]) -> io::Result<()> {
+        let mut child = Command::new(program).args(args).spawn()?;
+        thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a router to open files in the configured editor.
+///
+/// Requests to `/__zensical/open` must be `POST` requests from a page served
+/// by the server itself, and must pass the `path` of the file to open, and may
+/// pass a `line` and `column`. Only files located inside the given root
+/// directory can be opened, and if no editor command is configured, the route
+/// responds with `404 Not Found`. Since the route launches processes, the
+/// server must only pass a command if it's bound to a loopback address.
+pub fn router<L>(root: PathBuf, command: Option<String>, launcher: L) -> Router
+where
+    L: Launch,
+{
+    Router::default().post(
+        "/__zensical/open",
+        move |req: Request, _: Params| {
+            let Some(template) = command.as_deref() else {
+                return Response::from_status(Status::NotFound);
+            };
+
+            // Ensure the request originates from a page served by us, so other
+            // sites can't open files, neither directly nor via DNS rebinding
+            if !is_same_origin(&req) {
+                return Response::from_status(Status::Forbidden);
+            }
+            let Ok(target) = req.uri.query.deserialize::<Target>() else {
+                return Response::from_status(Status::BadRequest);
+            };
+
+            // Ensure the file exists and is located inside the root directory
+            let Some(path) = resolve(&root, &target.path) else {
+                return Response::from_status(Status::Forbidden);
+            };
+
+            // Render and launch the editor command
+            let args = render(template, &path, target.line, target.column);
+            let Some((program, args)) = args.split_first() else {
+                return Response::from_status(Status::NotFound);
+            };
+            match launcher.launch(program, args) {
+                Ok(()) => Response::new().status(Status::NoContent),
+                Err(err) => {
+                    eprintln!("Error: editor could not be launched: {err}");
+                    Response::from_status(Status::InternalServerError)
+                }
+            }
+        },
+    )
+}
+
+/// Returns whether the given address only resolves to loopback addresses.
+///
+/// Unix domain sockets are usually behind a reverse proxy, which is why they
+/// are not considered to be loopback addresses.
+pub fn is_loopback(addr: &str) -> bool {
+    addr.to_socket_addrs().is_ok_and(|addrs| {
+        let mut addrs = addrs.peekable();
+        addrs.peek().is_some() && addrs.all(|addr| addr.ip().is_loopback())
+    })
+}
+
+/// Returns whether the request originates from a page served by us.
+///
+/// The `Origin` header must match the `Host` header, which must name a
+/// loopback host, so pages of other hosts resolving to a loopback address,
+/// i.e., DNS rebinding, are refused as well.
+fn is_same_origin(req: &Request) -> bool {
+    let origin = req.headers.get(Header::Origin);
+    let Some(host) = req.headers.get(Header::Host) else {
+        return false;
+    };
+    origin.and_then(|origin| origin.strip_prefix("http://")) == Some(host)
+        && is_loopback_host(host)
+}
+
+/// Returns whether the given host, optionally with a port, is a loopback host.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split_once(']').map_or(rest, |(name, _)| name),
+        None => host.split_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Renders the editor command for the given location.
+///
+/// The command is split at whitespace, and the placeholders `{path}`, `{line}`
+/// and `{column}` are replaced in each argument, so paths containing spaces
+/// are passed as a single argument. Line and column default to 1.
+fn render(
+    template: &str, path: &Path, line: Option<usize>, column: Option<usize>,
+) -> Vec<String> {
+    let path = path.to_string_lossy();
+    let line = line.unwrap_or(1).to_string();
+    let column = column.unwrap_or(1).to_string();
+    template
+        .split_whitespace()
+        .map(|arg| {
+            arg.replace("{path}", &path)
+                .replace("{line}", &line)
+                .replace("{column}", &column)
+        })
+        .collect()
+}
+
+/// Resolves the given path, ensuring it is located inside the root directory.
+///
+/// Relative paths are resolved against the root directory. Both paths are
+/// canonicalized before comparison, so neither `..` components nor symbolic
+/// links can be used to escape the root directory. Returns [`None`] if the
+/// path does not exist or is located outside of the root directory.
+fn resolve(root: &Path, path: &str) -> Option<PathBuf> {
+    let root = root.canonicalize().ok()?;
+    let path = root.join(path).canonicalize().ok()?;
+    path.starts_with(&root).then_some(path)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use tempfile::tempdir;
+    use zensical_serve::handler::{Handler, TryIntoHandler};
+    use zensical_serve::http::{Header, Method, Request, Response, Status};
+
+    use super::{is_loopback, render, resolve, router, Launch};
+
+    /// Launcher recording launched commands.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Vec<String>>>>);
+
+    impl Launch for Recorder {
+        fn launch(&self, program: &str, args: &[String]) -> io::Result<()> {
+            let mut command = vec![program.to_string()];
+            command.extend_from_slice(args);
+            self.0.lock().unwrap().push(command);
+            Ok(())
+        }
+    }
+
+    /// Sends the given request to a router with the given command.
+    fn send(
+        root: &Path, command: Option<&str>, recorder: &Recorder, req: Request,
+    ) -> Response {
+        let command = command.map(ToString::to_string);
+        let router = router(root.to_path_buf(), command, recorder.clone());
+        let handler = router.try_into_handler().unwrap();
+        handler.handle(req)
+    }
+
+    /// Requests the given URI from a router with the given command.
+    fn open(
+        root: &Path, command: Option<&str>, recorder: &Recorder, uri: &str,
+    ) -> Response {
+        let req = Request::new()
+            .method(Method::Post)
+            .uri(uri)
+            .header(Header::Host, "localhost:8000")
+            .header(Header::Origin, "http://localhost:8000");
+        send(root, command, recorder, req)
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let path = PathBuf::from("/docs/my page.md");
+        assert_eq!(
+            render("code --goto {path}:{line}:{column}", &path, Some(3), None),
+            ["code", "--goto", "/docs/my page.md:3:1"]
+        );
+        assert_eq!(
+            render("vim +{line} {path}", &path, None, None),
+            ["vim", "+1", "/docs/my page.md"]
+        );
+    }
+
+    #[test]
+    fn resolves_paths_inside_root() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("project");
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("docs/index.md"), "").unwrap();
+        fs::write(temp.path().join("secret.md"), "").unwrap();
+
+        // Relative and absolute paths inside the root are allowed
+        let path = root.join("docs/index.md").canonicalize().unwrap();
+        assert_eq!(resolve(&root, "docs/index.md"), Some(path.clone()));
+        let absolute = root.join("docs/index.md");
+        assert_eq!(resolve(&root, &absolute.to_string_lossy()), Some(path));
+
+        // Paths outside of the root and missing paths are rejected
+        assert_eq!(resolve(&root, "../secret.md"), None);
+        let outside = temp.path().join("secret.md");
+        assert_eq!(resolve(&root, &outside.to_string_lossy()), None);
+        assert_eq!(resolve(&root, "docs/missing.md"), None);
+    }
+
+    #[test]
+    fn launches_editor() {
+        let temp = tempdir().unwrap();
+        fs::write(temp.path().join("index.md"), "").unwrap();
+        let recorder = Recorder::default();
+        let res = open(
+            temp.path(),
+            Some("code --goto {path}:{line}:{column}"),
+            &recorder,
+            "/__zensical/open?path=index.md&line=4&column=2",
+        );
+        assert_eq!(res.status, Status::NoContent);
+
+        // Editor is launched with the canonical path
+        let path = temp.path().join("index.md").canonicalize().unwrap();
+        let goto = format!("{}:4:2", path.display());
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [["code", "--goto", goto.as_str()]]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("project");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(temp.path().join("secret.md"), "").unwrap();
+        let recorder = Recorder::default();
+        let command = Some("code {path}");
+
+        // Paths outside of the root directory are forbidden
+        let uri = "/__zensical/open?path=../secret.md";
+        let res = open(&root, command, &recorder, uri);
+        assert_eq!(res.status, Status::Forbidden);
+
+        // Missing and malformed parameters are rejected
+        let uri = "/__zensical/open?line=1";
+        let res = open(&root, command, &recorder, uri);
+        assert_eq!(res.status, Status::BadRequest);
+        let uri = "/__zensical/open?path=index.md&line=x";
+        let res = open(&root, command, &recorder, uri);
+        assert_eq!(res.status, Status::BadRequest);
+
+        // Without an editor command, the route is not available
+        let uri = "/__zensical/open?path=../secret.md";
+        let res = open(&root, None, &recorder, uri);
+        assert_eq!(res.status, Status::NotFound);
+        assert!(recorder.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_cross_origin_requests() {
+        let temp = tempdir().unwrap();
+        fs::write(temp.path().join("index.md"), "").unwrap();
+        let recorder = Recorder::default();
+        let command = Some("code {path}");
+        let uri = "/__zensical/open?path=index.md";
+
+        // Requests without origin, or from another origin, are forbidden
+        let origins = [None, Some("http://example.com"), Some("null")];
+        for origin in origins {
+            let mut req = Request::new()
+                .method(Method::Post)
+                .uri(uri)
+                .header(Header::Host, "localhost:8000");
+            if let Some(origin) = origin {
+                req = req.header(Header::Origin, origin);
+            }
+            let res = send(temp.path(), command, &recorder, req);
+            assert_eq!(res.status, Status::Forbidden);
+        }
+
+        // Hosts that resolve to a loopback address via DNS are forbidden
+        let req = Request::new()
+            .method(Method::Post)
+            .uri(uri)
+            .header(Header::Host, "example.com:8000")
+            .header(Header::Origin, "http://example.com:8000");
+        let res = send(temp.path(), command, &recorder, req);
+        assert_eq!(res.status, Status::Forbidden);
+
+        // Files can't be opened via GET requests, e.g., from image tags
+        let req = Request::new()
+            .method(Method::Get)
+            .uri(uri)
+            .header(Header::Host, "localhost:8000")
+            .header(Header::Origin, "http://localhost:8000");
+        let res = send(temp.path(), command, &recorder, req);
+        assert_eq!(res.status, Status::NotFound);
+        assert!(recorder.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn detects_loopback_addresses() {
+        assert!(is_loopback("127.0.0.1:8000"));
+        assert!(is_loopback("[::1]:8000"));
+        assert!(!is_loopback("0.0.0.0:8000"));
+        assert!(!is_loopback("192.168.0.1:8000"));
+        assert!(!is_loopback("unix:/tmp/zensical.sock"));
+    }
+}
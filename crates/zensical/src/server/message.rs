@@ -0,0 +1,239 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Messages sent to clients.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::workflow::{Diagnostic, Diagnostics};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Minimum interval between diagnostics messages.
+const INTERVAL: Duration = Duration::from_millis(250);
+
+/// Maximum number of diagnostics per message.
+const LIMIT: usize = 20;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Message sent to clients over the reload channel.
+///
+/// Reload messages are sent as plain paths, which the client compares to the
+/// path of the current page, while all other messages are sent as JSON, and
+/// distinguished by their `type`, e.g., `build-error`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Message {
+    /// Path of the page, stylesheet or script to reload.
+    #[serde(skip)]
+    Reload(String),
+    /// Errors of a finished build, which failed.
+    BuildError {
+        /// Errors of the build.
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// Diagnostics of a running build, sent as they occur.
+    Diagnostics {
+        /// Diagnostics emitted since the last message.
+        diagnostics: Vec<Diagnostic>,
+        /// Number of diagnostics left out, due to throttling.
+        omitted: usize,
+    },
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Forwarder of diagnostics to clients.
+///
+/// Diagnostics are forwarded while a build is running, so clients can display
+/// errors the moment a stage fails, not only after the build has finished. To
+/// avoid flooding clients on mass failures, at most one message is sent per
+/// interval, which carries a limited number of diagnostics, and the number of
+/// diagnostics that were left out.
+#[derive(Debug, Default)]
+pub struct Forwarder {
+    /// Number of diagnostics already forwarded.
+    offset: usize,
+    /// Time the last message was sent.
+    last: Option<Instant>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Message {
+    /// Encodes the message for sending it to clients.
+    pub fn encode(&self) -> String {
+        match self {
+            Message::Reload(path) => path.clone(),
+            _ => serde_json::to_string(self).expect("invariant"),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Forwarder {
+    /// Returns a message with the diagnostics emitted since the last one.
+    ///
+    /// Returns [`None`] if there are no new diagnostics, or if the interval
+    /// since the last message has not yet passed.
+    pub fn poll(
+        &mut self, diagnostics: &Diagnostics, now: Instant,
+    ) -> Option<Message> {
+        if self.last.is_some_and(|last| now - last < INTERVAL) {
+            return None;
+        }
+
+        // Collect diagnostics emitted since the last message
+        let mut items = diagnostics.since(self.offset);
+        if items.is_empty() {
+            return None;
+        }
+        self.offset += items.len();
+        self.last = Some(now);
+
+        // Limit diagnostics, and report how many were left out
+        let omitted = items.len().saturating_sub(LIMIT);
+        items.truncate(LIMIT);
+        Some(Message::Diagnostics { diagnostics: items, omitted })
+    }
+
+    /// Resets the forwarder, which must be done when diagnostics are taken.
+    pub fn reset(&mut self) {
+        self.offset = 0;
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::time::Instant;
+
+    use crate::workflow::{Diagnostic, Diagnostics, Location, Severity};
+
+    use super::{Forwarder, Message, INTERVAL, LIMIT};
+
+    /// Parses the given encoded message.
+    fn parse(message: &Message) -> Value {
+        serde_json::from_str(&message.encode()).unwrap()
+    }
+
+    #[test]
+    fn encodes_reload_as_path() {
+        let message = Message::Reload(String::from("/guide/"));
+        assert_eq!(message.encode(), "/guide/");
+    }
+
+    #[test]
+    fn encodes_build_error() {
+        let message = Message::BuildError {
+            diagnostics: vec![Diagnostic {
+                severity: Severity::Error,
+                message: String::from("error"),
+                location: Some(Location::new("main.html").line(3)),
+            }],
+        };
+        assert_eq!(
+            parse(&message),
+            json!({
+                "type": "build-error",
+                "diagnostics": [{
+                    "severity": "error",
+                    "message": "error",
+                    "location": { "path": "main.html", "line": 3 }
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn forwards_diagnostics_as_they_occur() {
+        let diagnostics = Diagnostics::default();
+        let mut forwarder = Forwarder::default();
+        let now = Instant::now();
+        assert_eq!(forwarder.poll(&diagnostics, now), None);
+
+        // Diagnostics are forwarded once, including warnings
+        diagnostics.record(Severity::Error, "template error");
+        diagnostics.record(Severity::Warning, "warning");
+        let message = forwarder.poll(&diagnostics, now).unwrap();
+        assert_eq!(
+            parse(&message),
+            json!({
+                "type": "diagnostics",
+                "diagnostics": [
+                    { "severity": "error", "message": "template error" },
+                    { "severity": "warning", "message": "warning" }
+                ],
+                "omitted": 0
+            })
+        );
+
+        // Subsequent diagnostics are held back until the interval has passed
+        diagnostics.record(Severity::Error, "another error");
+        assert_eq!(forwarder.poll(&diagnostics, now), None);
+        let message = forwarder.poll(&diagnostics, now + INTERVAL).unwrap();
+        assert_eq!(
+            parse(&message)["diagnostics"][0]["message"],
+            "another error"
+        );
+    }
+
+    #[test]
+    fn throttles_mass_failures() {
+        let diagnostics = Diagnostics::default();
+        for n in 0..100 {
+            diagnostics.record(Severity::Error, format!("error {n}"));
+        }
+
+        // Only a limited number of diagnostics is sent
+        let mut forwarder = Forwarder::default();
+        let message = forwarder.poll(&diagnostics, Instant::now()).unwrap();
+        let value = parse(&message);
+        assert_eq!(value["diagnostics"].as_array().unwrap().len(), LIMIT);
+        assert_eq!(value["omitted"], 100 - LIMIT);
+
+        // After taking diagnostics, forwarding starts over
+        diagnostics.take();
+        forwarder.reset();
+        diagnostics.record(Severity::Error, "error");
+        let later = Instant::now() + INTERVAL;
+        assert!(forwarder.poll(&diagnostics, later).is_some());
+    }
+}
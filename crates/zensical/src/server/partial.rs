@@ -0,0 +1,295 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Partial rendering.
+
+use minijinja::{Error, ErrorKind};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use zensical_serve::http::response::ResponseExt;
+use zensical_serve::http::{Header, Request, Response, Status};
+use zensical_serve::router::{Params, Router};
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Renderer of a single block of a page.
+type Render = dyn Fn(&str) -> Result<String, Error> + Send + Sync;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Partial renderers.
+///
+/// When partial rendering is enabled, the build process records a renderer
+/// for each page it renders, which the preview server uses to render single
+/// blocks of the page's template on demand. When disabled, nothing is kept.
+#[derive(Clone, Default)]
+pub struct Partials {
+    /// Renderers by page URL.
+    inner: Arc<RwLock<BTreeMap<String, Arc<Render>>>>,
+    /// Whether partial rendering is enabled.
+    enabled: bool,
+}
+
+/// Partial to render.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+struct Target {
+    /// URL of the page.
+    page: String,
+    /// Name of the block, defaulting to `content`.
+    block: Option<String>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Partials {
+    /// Creates partial renderers.
+    pub fn new(enabled: bool) -> Self {
+        Self { inner: Arc::default(), enabled }
+    }
+
+    /// Returns whether partial rendering is enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records the renderer for the page with the given URL.
+    ///
+    /// This is a no-op if partial rendering is disabled, so the build process
+    /// doesn't need to keep pages around that are never rendered partially.
+    pub fn insert<F>(&self, url: &str, render: F)
+    where
+        F: Fn(&str) -> Result<String, Error> + Send + Sync + 'static,
+    {
+        if self.enabled {
+            let mut inner = self.inner.write().expect("invariant");
+            inner.insert(url.to_string(), Arc::new(render));
+        }
+    }
+
+    /// Returns the renderer for the page with the given URL, if any.
+    fn get(&self, url: &str) -> Option<Arc<Render>> {
+        let inner = self.inner.read().expect("invariant");
+        inner.get(url).cloned()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for Partials {
+    /// Formats the partial renderers for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.read().expect("invariant");
+        f.debug_struct("Partials")
+            .field("pages", &inner.keys().collect::<Vec<_>>())
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a router to render single blocks of pages.
+///
+/// Requests to `/__zensical/partial` must pass the URL of the `page`, which
+/// may include the given base path, and may pass the name of the `block` to
+/// render, which defaults to `content`. Unknown pages and blocks respond with
+/// `404 Not Found` and a diagnostic, and if partial rendering is disabled, the
+/// route isn't available at all.
+pub fn router(base: String, partials: Partials) -> Router {
+    Router::default().get(
+        "/__zensical/partial",
+        move |req: Request, _: Params| {
+            if !partials.is_enabled() {
+                return Response::from_status(Status::NotFound);
+            }
+            let Ok(target) = req.uri.query.deserialize::<Target>() else {
+                return Response::from_status(Status::BadRequest);
+            };
+
+            // Ensure the page was rendered, before rendering the block
+            let url = resolve(&base, &target.page);
+            let Some(render) = partials.get(url) else {
+                return diagnostic(format!("Page '{url}' not found"));
+            };
+            let block = target.block.as_deref().unwrap_or("content");
+            match render(block) {
+                Ok(html) => Response::new()
+                    .status(Status::Ok)
+                    .header(Header::ContentType, "text/html; charset=utf-8")
+                    .header(Header::CacheControl, "no-cache")
+                    .header(Header::ContentLength, html.len())
+                    .body(html),
+                Err(err) if err.kind() == ErrorKind::UnknownBlock => {
+                    diagnostic(format!(
+                        "Block '{block}' not found in template of '{url}'"
+                    ))
+                }
+                Err(err) => {
+                    eprintln!("Error: partial could not be rendered: {err:#}");
+                    Response::from_status(Status::InternalServerError)
+                }
+            }
+        },
+    )
+}
+
+/// Creates a `404 Not Found` response with the given diagnostic.
+fn diagnostic(message: String) -> Response {
+    Response::new().status(Status::NotFound).text(message)
+}
+
+/// Resolves the given page URL relative to the base path.
+///
+/// Page URLs are relative to the site, e.g., `guide/setup/`, but clients will
+/// usually pass the path of the current location, which includes the base
+/// path, e.g., `/docs/guide/setup/`, so both forms are accepted.
+fn resolve<'a>(base: &str, page: &'a str) -> &'a str {
+    page.strip_prefix(base.trim_end_matches('/'))
+        .filter(|url| url.is_empty() || url.starts_with('/'))
+        .unwrap_or(page)
+        .trim_start_matches('/')
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use minijinja::context;
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+    use zensical_serve::handler::{Handler, TryIntoHandler};
+    use zensical_serve::http::{Method, Request, Response, Status};
+
+    use crate::template::Template;
+
+    use super::{resolve, router, Partials};
+
+    /// Creates a theme directory with a main template extending a base one.
+    fn theme() -> TempDir {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.html"),
+            "<html>{% block content %}{% endblock %}</html>",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("main.html"),
+            "{% extends \"base.html\" %}\
+             {% block content %}{{ page.content }}{% endblock %}",
+        )
+        .unwrap();
+        dir
+    }
+
+    /// Records a renderer for the given page in the given theme directory.
+    fn insert(partials: &Partials, dir: &TempDir, url: &str, content: &str) {
+        let template = Template::new("main.html", [dir.path().to_path_buf()]);
+        let context = context! { page => context! { content => content } };
+        partials
+            .insert(url, move |block| template.render_block(block, &context));
+    }
+
+    /// Requests the given URI from a router with the given partials.
+    fn partial(partials: &Partials, uri: &str) -> Response {
+        let router = router(String::from("/docs"), partials.clone());
+        let handler = router.try_into_handler().unwrap();
+        handler.handle(Request::new().method(Method::Get).uri(uri))
+    }
+
+    #[test]
+    fn resolves_urls_relative_to_base_path() {
+        assert_eq!(resolve("/docs", "/docs/guide/"), "guide/");
+        assert_eq!(resolve("/docs", "/docs"), "");
+        assert_eq!(resolve("/docs", "guide/"), "guide/");
+        assert_eq!(resolve("/docs", "/docsite/"), "docsite/");
+        assert_eq!(resolve("/", "/guide/"), "guide/");
+    }
+
+    #[test]
+    fn renders_known_block() {
+        let dir = theme();
+        let partials = Partials::new(true);
+        insert(&partials, &dir, "guide/", "<p>Guide</p>");
+
+        // Only the content of the block is rendered, not the entire page
+        let uri = "/__zensical/partial?page=/docs/guide/&block=content";
+        let res = partial(&partials, uri);
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.body, b"<p>Guide</p>");
+
+        // The block defaults to the content block
+        let res = partial(&partials, "/__zensical/partial?page=guide/");
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.body, b"<p>Guide</p>");
+    }
+
+    #[test]
+    fn rejects_unknown_pages_and_blocks() {
+        let dir = theme();
+        let partials = Partials::new(true);
+        insert(&partials, &dir, "guide/", "<p>Guide</p>");
+
+        // Unknown blocks and pages respond with a diagnostic
+        let uri = "/__zensical/partial?page=guide/&block=sidebar";
+        let res = partial(&partials, uri);
+        assert_eq!(res.status, Status::NotFound);
+        let body = String::from_utf8(res.body).unwrap();
+        assert_eq!(body, "Block 'sidebar' not found in template of 'guide/'");
+        let res = partial(&partials, "/__zensical/partial?page=missing/");
+        assert_eq!(res.status, Status::NotFound);
+        assert_eq!(res.body, b"Page 'missing/' not found");
+
+        // Missing parameters are rejected
+        let res = partial(&partials, "/__zensical/partial?block=content");
+        assert_eq!(res.status, Status::BadRequest);
+    }
+
+    #[test]
+    fn disabled_partials_are_not_available() {
+        let dir = theme();
+        let partials = Partials::new(false);
+        insert(&partials, &dir, "guide/", "<p>Guide</p>");
+
+        // Renderers aren't recorded, and the route isn't available
+        assert!(partials.get("guide/").is_none());
+        let res = partial(&partials, "/__zensical/partial?page=guide/");
+        assert_eq!(res.status, Status::NotFound);
+    }
+}
@@ -0,0 +1,412 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Search preview.
+
+use fancy_regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::{fmt, fs};
+use zensical_serve::http::response::ResponseExt;
+use zensical_serve::http::{Header, Request, Response, Status};
+use zensical_serve::router::{Params, Router};
+
+use crate::structure::search::{SearchIndex, SearchItem};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Default number of results.
+const LIMIT: usize = 10;
+
+/// Weight of matches in titles, relative to matches in text.
+const TITLE_WEIGHT: usize = 10;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Search query.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+struct Query {
+    /// Query string.
+    q: String,
+    /// Maximum number of results, defaulting to 10.
+    limit: Option<usize>,
+}
+
+/// Search preview, returned as JSON.
+#[derive(Debug, Serialize)]
+struct Preview<'a> {
+    /// Terms of the query, as produced by the tokenizer.
+    terms: Vec<String>,
+    /// Number of matching items.
+    total: usize,
+    /// Top results, ordered by score.
+    results: Vec<Match<'a>>,
+}
+
+/// Matching search item.
+#[derive(Debug, Serialize)]
+struct Match<'a> {
+    /// Search location.
+    location: Option<&'a str>,
+    /// Section title, with matched terms highlighted.
+    title: String,
+    /// Section text, with matched terms highlighted.
+    text: String,
+    /// Score of the item.
+    score: usize,
+    /// Fields that contain at least one of the terms.
+    fields: Vec<&'static str>,
+}
+
+/// Tokenizer.
+///
+/// Splits text at the separator of the search configuration, exactly like the
+/// client does, so the preview matches what users see. The separator may make
+/// use of lookarounds, which is why it's compiled as a fancy regex.
+#[derive(Debug)]
+struct Tokenizer {
+    /// Separator.
+    separator: Regex,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Tokenizer {
+    /// Creates a tokenizer with the given separator.
+    fn new(separator: &str) -> Result<Self, Box<fancy_regex::Error>> {
+        match Regex::new(separator) {
+            Ok(separator) => Ok(Self { separator }),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    /// Returns the byte ranges of all tokens in the given text.
+    fn spans(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut spans = Vec::new();
+        let mut start = 0;
+        for res in self.separator.find_iter(text) {
+            let Ok(found) = res else { break };
+            if found.start() > start {
+                spans.push((start, found.start()));
+            }
+            start = start.max(found.end());
+        }
+        if start < text.len() {
+            spans.push((start, text.len()));
+        }
+        spans
+    }
+
+    /// Returns all tokens in the given text, lowercased.
+    fn tokens(&self, text: &str) -> Vec<String> {
+        self.spans(text)
+            .into_iter()
+            .map(|(start, end)| text[start..end].to_lowercase())
+            .collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a router to preview search results.
+///
+/// Requests to `/__zensical/search` must pass the query `q`, and may pass a
+/// `limit` for the number of results. The search index is loaded from the
+/// given site directory on every request, so it's always up to date, and
+/// items are scored by the frequency of the query terms in their titles and
+/// texts, using the tokenizer settings of the index. If the index doesn't
+/// exist, the route responds with `404 Not Found`, and if it's malformed,
+/// with `500 Internal Server Error` and a diagnostic.
+pub fn router(site_dir: PathBuf) -> Router {
+    Router::default().get(
+        "/__zensical/search",
+        move |req: Request, _: Params| {
+            let Ok(query) = req.uri.query.deserialize::<Query>() else {
+                return Response::from_status(Status::BadRequest);
+            };
+
+            // Load search index, which is written by the build
+            let data = match fs::read(site_dir.join("search.json")) {
+                Ok(data) => data,
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    return Response::new()
+                        .status(Status::NotFound)
+                        .text("Search index not found");
+                }
+                Err(err) => return malformed(err),
+            };
+            let index = match serde_json::from_slice::<SearchIndex>(&data) {
+                Ok(index) => index,
+                Err(err) => return malformed(err),
+            };
+            let tokenizer = match Tokenizer::new(&index.config.separator) {
+                Ok(tokenizer) => tokenizer,
+                Err(err) => return malformed(err),
+            };
+
+            // Search index and return top results
+            let limit = query.limit.unwrap_or(LIMIT);
+            let preview = search(&index, &tokenizer, &query.q, limit);
+            let data = serde_json::to_vec(&preview).expect("invariant");
+            Response::new()
+                .status(Status::Ok)
+                .header(Header::ContentType, "application/json")
+                .header(Header::CacheControl, "no-cache")
+                .header(Header::ContentLength, data.len())
+                .body(data)
+        },
+    )
+}
+
+/// Creates a `500 Internal Server Error` response for a malformed index.
+fn malformed<E>(err: E) -> Response
+where
+    E: fmt::Display,
+{
+    Response::new()
+        .status(Status::InternalServerError)
+        .text(format!("Search index is malformed: {err}"))
+}
+
+/// Searches the index for the given query, returning the top results.
+fn search<'a>(
+    index: &'a SearchIndex, tokenizer: &Tokenizer, query: &str, limit: usize,
+) -> Preview<'a> {
+    let terms = tokenizer.tokens(query);
+    let unique = terms.iter().map(String::as_str).collect::<BTreeSet<_>>();
+
+    // Score all items, and keep the ones matching at least one term - the
+    // sort is stable, so items with equal scores retain their order
+    let mut results = index
+        .items
+        .iter()
+        .filter_map(|item| score(item, tokenizer, &unique))
+        .collect::<Vec<_>>();
+    results.sort_by_key(|result| Reverse(result.score));
+    let total = results.len();
+    results.truncate(limit);
+    Preview { terms, total, results }
+}
+
+/// Scores the given item, returning a match if it contains any of the terms.
+fn score<'a>(
+    item: &'a SearchItem, tokenizer: &Tokenizer, terms: &BTreeSet<&str>,
+) -> Option<Match<'a>> {
+    let text = strip_tags(&item.text);
+    let count = |value: &str| {
+        tokenizer
+            .tokens(value)
+            .iter()
+            .filter(|token| terms.contains(token.as_str()))
+            .count()
+    };
+
+    // Count occurrences of terms in title and text, weighting the title
+    let (in_title, in_text) = (count(&item.title), count(&text));
+    let mut fields = Vec::new();
+    if in_title > 0 {
+        fields.push("title");
+    }
+    if in_text > 0 {
+        fields.push("text");
+    }
+    (!fields.is_empty()).then(|| Match {
+        location: item.location.as_deref(),
+        title: highlight(&item.title, tokenizer, terms),
+        text: highlight(&text, tokenizer, terms),
+        score: in_title * TITLE_WEIGHT + in_text,
+        fields,
+    })
+}
+
+/// Wraps all tokens of the given value that match any of the terms in `mark`.
+fn highlight(
+    value: &str, tokenizer: &Tokenizer, terms: &BTreeSet<&str>,
+) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut start = 0;
+    for (from, to) in tokenizer.spans(value) {
+        let token = &value[from..to];
+        if terms.contains(token.to_lowercase().as_str()) {
+            result.push_str(&value[start..from]);
+            result.push_str("<mark>");
+            result.push_str(token);
+            result.push_str("</mark>");
+            start = to;
+        }
+    }
+    result.push_str(&value[start..]);
+    result
+}
+
+/// Removes all HTML tags from the given value, as texts are stored as HTML.
+fn strip_tags(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut inside = false;
+    for char in value.chars() {
+        match char {
+            '<' => inside = true,
+            '>' if inside => inside = false,
+            _ if !inside => result.push(char),
+            _ => {}
+        }
+    }
+    result
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+    use zensical_serve::handler::{Handler, TryIntoHandler};
+    use zensical_serve::http::{Method, Request, Response, Status};
+
+    use super::{router, Tokenizer};
+
+    /// Default separator of the search plugin.
+    const SEPARATOR: &str = r#"[\s\-_,:!=\[\]()\\"`/]+|\.(?!\d)"#;
+
+    /// Creates a site directory with a small search index.
+    fn site() -> TempDir {
+        let dir = tempdir().unwrap();
+        let index = json!({
+            "config": { "separator": SEPARATOR },
+            "items": [
+                {
+                    "location": "",
+                    "level": 1,
+                    "title": "Home",
+                    "text": "<p>Welcome to the documentation.</p>",
+                    "path": [],
+                    "tags": []
+                },
+                {
+                    "location": "setup/",
+                    "level": 1,
+                    "title": "Setup",
+                    "text": "<p>Install version 1.2 and run the setup.</p>",
+                    "path": ["Setup"],
+                    "tags": []
+                },
+                {
+                    "location": "setup/#options",
+                    "level": 2,
+                    "title": "Options",
+                    "text": "<p>Options for the <code>setup</code>.</p>",
+                    "path": ["Setup"],
+                    "tags": []
+                }
+            ]
+        });
+        fs::write(dir.path().join("search.json"), index.to_string()).unwrap();
+        dir
+    }
+
+    /// Requests the given URI from a router for the given site directory.
+    fn search(dir: &TempDir, uri: &str) -> Response {
+        let router = router(dir.path().to_path_buf());
+        let handler = router.try_into_handler().unwrap();
+        handler.handle(Request::new().method(Method::Get).uri(uri))
+    }
+
+    #[test]
+    fn tokenizes_with_separator() {
+        let tokenizer = Tokenizer::new(SEPARATOR).unwrap();
+        let tokens = tokenizer.tokens("Install version 1.2, then run.");
+        assert_eq!(tokens, ["install", "version", "1.2", "then", "run"]);
+    }
+
+    #[test]
+    fn returns_results_for_present_terms() {
+        let dir = site();
+        let res = search(&dir, "/__zensical/search?q=Setup");
+        assert_eq!(res.status, Status::Ok);
+
+        // Matches in titles are ranked higher than matches in text
+        let data: Value = serde_json::from_slice(&res.body).unwrap();
+        assert_eq!(data["terms"], json!(["setup"]));
+        assert_eq!(data["total"], 2);
+        let results = data["results"].as_array().unwrap();
+        assert_eq!(results[0]["location"], "setup/");
+        assert_eq!(results[0]["title"], "<mark>Setup</mark>");
+        assert_eq!(results[0]["fields"], json!(["title", "text"]));
+        assert_eq!(
+            results[0]["text"],
+            "Install version 1.2 and run the <mark>setup</mark>."
+        );
+        assert_eq!(results[1]["location"], "setup/#options");
+        assert_eq!(results[1]["fields"], json!(["text"]));
+
+        // Results are limited to the given number
+        let res = search(&dir, "/__zensical/search?q=setup&limit=1");
+        let data: Value = serde_json::from_slice(&res.body).unwrap();
+        assert_eq!(data["total"], 2);
+        assert_eq!(data["results"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn returns_no_results_for_absent_terms() {
+        let dir = site();
+        let res = search(&dir, "/__zensical/search?q=missing");
+        assert_eq!(res.status, Status::Ok);
+        let data: Value = serde_json::from_slice(&res.body).unwrap();
+        assert_eq!(data["total"], 0);
+        assert_eq!(data["results"], json!([]));
+
+        // Missing queries are rejected
+        let res = search(&dir, "/__zensical/search");
+        assert_eq!(res.status, Status::BadRequest);
+    }
+
+    #[test]
+    fn reports_missing_and_malformed_index() {
+        let dir = tempdir().unwrap();
+        let res = search(&dir, "/__zensical/search?q=setup");
+        assert_eq!(res.status, Status::NotFound);
+
+        // Malformed indexes respond with a diagnostic
+        fs::write(dir.path().join("search.json"), "{\"items\": []}").unwrap();
+        let res = search(&dir, "/__zensical/search?q=setup");
+        assert_eq!(res.status, Status::InternalServerError);
+        let body = String::from_utf8(res.body).unwrap();
+        assert!(body.starts_with("Search index is malformed: "));
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Build status.
+
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zensical_serve::http::{Header, Request, Response, Status};
+use zensical_serve::router::{Params, Router};
+
+use crate::workflow::{Diagnostic, Severity};
+
+use super::message::Message;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Build status.
+///
+/// Times are given in milliseconds since the Unix epoch. While a build is in
+/// progress, the end time and success flag are not set, and the diagnostics
+/// of the previous build are discarded when the next build starts. Whether
+/// the first build has completed is retained across builds.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Build {
+    /// Whether the first build has completed.
+    pub ready: bool,
+    /// Start time of the build.
+    pub start: Option<u64>,
+    /// End time of the build.
+    pub end: Option<u64>,
+    /// Whether the build succeeded.
+    pub success: Option<bool>,
+    /// Diagnostics of the build.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Build state.
+///
+/// The build state is shared between the build process, which pushes updates
+/// when a build starts and finishes, and the preview server, which exposes the
+/// status of the last build to clients.
+#[derive(Clone, Debug, Default)]
+pub struct State {
+    /// Status of the last build.
+    inner: Arc<RwLock<Build>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl State {
+    /// Marks the start of a build.
+    pub fn start(&self) {
+        let mut build = self.inner.write().expect("invariant");
+        *build = Build {
+            ready: build.ready,
+            start: Some(now()),
+            ..Build::default()
+        };
+    }
+
+    /// Marks the end of a build with the given diagnostics.
+    ///
+    /// The build is considered to be successful if none of the diagnostics is
+    /// an error. Otherwise, a `build-error` message is returned, which should
+    /// be broadcast to all clients, so they can display an error overlay.
+    pub fn finish(&self, diagnostics: Vec<Diagnostic>) -> Option<String> {
+        let mut build = self.inner.write().expect("invariant");
+        let errors = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // Create message, if the build produced errors
+        let message = (!errors.is_empty())
+            .then(|| Message::BuildError { diagnostics: errors }.encode());
+
+        // Update status of build
+        build.ready = true;
+        build.end = Some(now());
+        build.success = Some(message.is_none());
+        build.diagnostics = diagnostics;
+        message
+    }
+
+    /// Returns the status of the last build.
+    pub fn get(&self) -> Build {
+        self.inner.read().expect("invariant").clone()
+    }
+
+    /// Returns whether the first build has completed.
+    pub fn is_ready(&self) -> bool {
+        self.inner.read().expect("invariant").ready
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a router exposing the build status.
+pub fn router(state: State) -> Router {
+    Router::default().get("/__zensical/status", move |_: Request, _: Params| {
+        let data = serde_json::to_vec(&state.get()).expect("invariant");
+        Response::new()
+            .status(Status::Ok)
+            .header(Header::ContentType, "application/json")
+            .header(Header::CacheControl, "no-cache")
+            .header(Header::ContentLength, data.len())
+            .body(data)
+    })
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+fn now() -> u64 {
+    let duration = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use zensical_serve::handler::{Handler, TryIntoHandler};
+    use zensical_serve::http::{Method, Request, Status};
+
+    use crate::workflow::{Diagnostic, Severity};
+
+    use super::{router, State};
+
+    /// Requests the build status from a router with the given state.
+    fn status(state: &State) -> Value {
+        let handler = router(state.clone()).try_into_handler().unwrap();
+        let req = Request::new().method(Method::Get).uri("/__zensical/status");
+        let res = handler.handle(req);
+        assert_eq!(res.status, Status::Ok);
+        serde_json::from_slice(&res.body).unwrap()
+    }
+
+    /// Creates a diagnostic with the given severity and message.
+    fn diagnostic(severity: Severity, message: &str) -> Diagnostic {
+        Diagnostic {
+            severity,
+            message: message.to_string(),
+            location: None,
+        }
+    }
+
+    #[test]
+    fn reports_build_in_progress() {
+        let state = State::default();
+        state.start();
+        let value = status(&state);
+        assert!(value["start"].is_u64());
+        assert!(value["end"].is_null());
+        assert!(value["success"].is_null());
+        assert_eq!(value["ready"], false);
+    }
+
+    #[test]
+    fn retains_readiness_across_builds() {
+        let state = State::default();
+        state.start();
+        state.finish(Vec::new());
+        assert!(state.is_ready());
+
+        // Subsequent builds don't reset readiness
+        state.start();
+        assert!(state.is_ready());
+        assert_eq!(status(&state)["ready"], true);
+    }
+
+    #[test]
+    fn reports_successful_build() {
+        let state = State::default();
+        state.start();
+        let message =
+            state.finish(vec![diagnostic(Severity::Warning, "warning")]);
+        assert_eq!(message, None);
+
+        // Warnings are reported, but don't fail the build
+        let value = status(&state);
+        assert!(value["end"].is_u64());
+        assert_eq!(value["success"], true);
+        assert_eq!(value["diagnostics"][0]["severity"], "warning");
+        assert_eq!(value["diagnostics"][0]["message"], "warning");
+    }
+
+    #[test]
+    fn reports_failed_build() {
+        let state = State::default();
+        state.start();
+        let message = state.finish(vec![
+            diagnostic(Severity::Warning, "warning"),
+            diagnostic(Severity::Error, "error"),
+        ]);
+
+        // Only errors are included in the broadcast message
+        let message: Value = serde_json::from_str(&message.unwrap()).unwrap();
+        assert_eq!(message["type"], "build-error");
+        assert_eq!(message["diagnostics"].as_array().unwrap().len(), 1);
+        assert_eq!(message["diagnostics"][0]["message"], "error");
+
+        // All diagnostics are reported in the status
+        let value = status(&state);
+        assert_eq!(value["success"], false);
+        assert_eq!(value["diagnostics"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Graceful shutdown.
+
+use pyo3::prelude::*;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::workflow::Cache;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Whether a termination signal was received.
+pub static TERMINATED: AtomicBool = AtomicBool::new(false);
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Previous handlers for termination signals.
+///
+/// When dropped, the handlers that were installed before are restored, so
+/// applications calling into Zensical get their own handlers back.
+pub struct Handlers {
+    /// Signal numbers and previous handlers.
+    previous: Vec<(Py<PyAny>, Py<PyAny>)>,
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Drop for Handlers {
+    /// Restores the previous handlers for termination signals.
+    fn drop(&mut self) {
+        Python::attach(|py| {
+            let Ok(signal) = py.import("signal") else {
+                return;
+            };
+            for (signum, handler) in self.previous.drain(..) {
+                let _ = signal.call_method1("signal", (signum, handler));
+            }
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Installs handlers for termination signals.
+///
+/// Handlers are registered through Python's signal module, which runs them on
+/// the main thread when signals are checked, i.e., alongside the check for
+/// keyboard interrupts. `SIGTERM` is sent by systemd or `docker stop`, while
+/// `SIGBREAK` is sent on Windows when pressing Ctrl+Break in the console.
+/// Installing handlers fails when not called from the main thread. Previous
+/// handlers are restored when the returned value is dropped.
+pub fn install(py: Python) -> PyResult<Handlers> {
+    let signal = py.import("signal")?;
+    let handler = wrap_pyfunction!(terminate, py)?;
+    let mut handlers = Handlers { previous: Vec::new() };
+    for name in ["SIGTERM", "SIGBREAK"] {
+        if let Ok(signum) = signal.getattr(name) {
+            let previous =
+                signal.call_method1("signal", (&signum, &handler))?;
+
+            // Handlers that weren't installed from Python are reported as
+            // `None`, and can't be restored, so they're left as they are
+            if !previous.is_none() {
+                handlers.previous.push((signum.unbind(), previous.unbind()));
+            }
+        }
+    }
+    Ok(handlers)
+}
+
+/// Handles a termination signal by requesting a graceful shutdown.
+#[pyfunction]
+fn terminate(_signum: i32, _frame: Bound<'_, PyAny>) {
+    TERMINATED.store(true, Ordering::SeqCst);
+}
+
+/// Shuts down gracefully, if interrupted or terminated.
+///
+/// This flushes the cache manifest, so that entries that were written in the
+/// current build are retained, and returns whether the process should exit.
+/// Since tasks are not interrupted, all cache entries are complete. When the
+/// process should exit, the caller drains the server, if any, before exiting.
+pub fn shutdown(
+    interrupted: bool, terminated: &AtomicBool, cache: &Cache,
+) -> io::Result<bool> {
+    if !interrupted && !terminated.load(Ordering::SeqCst) {
+        return Ok(false);
+    }
+
+    // Flush cache manifest
+    cache.flush()?;
+    Ok(true)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::tempdir;
+
+    use crate::workflow::Cache;
+
+    use super::shutdown;
+
+    #[test]
+    fn continues_without_shutdown_request() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        let terminated = AtomicBool::new(false);
+        assert!(!shutdown(false, &terminated, &cache).unwrap());
+        assert!(!dir.path().join("manifest.json").exists());
+    }
+
+    #[test]
+    fn shuts_down_and_flushes_cache_when_terminated() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        let terminated = AtomicBool::new(false);
+        terminated.store(true, Ordering::SeqCst);
+        assert!(shutdown(false, &terminated, &cache).unwrap());
+        assert!(dir.path().join("manifest.json").exists());
+    }
+
+    #[test]
+    fn shuts_down_when_interrupted() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        let terminated = AtomicBool::new(false);
+        assert!(shutdown(true, &terminated, &cache).unwrap());
+    }
+}
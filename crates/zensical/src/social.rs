@@ -0,0 +1,330 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Social plugin.
+
+use ab_glyph::FontVec;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::config::Config;
+use super::hash::StableHasher;
+use super::structure::dynamic::Dynamic;
+use super::structure::page::{Page, PageMeta};
+use super::structure::url;
+use super::workflow::{Counter, Diagnostics, OutputSink, Report};
+
+mod card;
+
+use card::{Card, Color, Image, HEIGHT, WIDTH};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Version of the card layout, which invalidates cached cards when changed.
+const VERSION: u32 = 1;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Social plugin.
+///
+/// A share image is rendered for each page, showing the site name and page
+/// title, and referenced from the Open Graph and Twitter meta tags of the
+/// page. Rendered cards are cached by page title and card template, i.e., the
+/// font, logo, colors and site name, so unchanged pages skip rendering.
+#[derive(Clone)]
+pub struct Social {
+    /// Shared state, if the plugin is enabled.
+    inner: Option<Arc<Inner>>,
+}
+
+/// Shared state of the social plugin.
+struct Inner {
+    /// Font for all text.
+    font: FontVec,
+    /// Logo, if any.
+    logo: Option<Image>,
+    /// Background color.
+    background: Color,
+    /// Text color.
+    color: Color,
+    /// Site name.
+    site_name: String,
+    /// Site URL, with trailing slash.
+    site_url: String,
+    /// Directory of cards, relative to the site directory.
+    cards_dir: String,
+    /// Site directory.
+    site_dir: PathBuf,
+    /// Cache directory.
+    cache_dir: PathBuf,
+    /// Hash of the card template.
+    hash: u64,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Social {
+    /// Creates the social plugin from the given configuration.
+    ///
+    /// Share images must be referenced with absolute URLs, so the plugin is
+    /// disabled with a warning if no site URL is set. If the font, logo, or
+    /// colors can't be loaded, an error is reported, and the plugin is
+    /// disabled as well.
+    pub fn new(config: &Config, diagnostics: &Diagnostics) -> Self {
+        let disabled = Self { inner: None };
+        let plugin = &config.project.plugins.social.config;
+        if !plugin.enabled {
+            return disabled;
+        }
+        let Some(site_url) = config.project.site_url.as_deref() else {
+            diagnostics.warning("Social cards require 'site_url', skipping");
+            return disabled;
+        };
+
+        // Hash everything that's part of the card template, including font and
+        // logo, so cards are rendered again when one of them changes
+        let mut hasher = StableHasher::default();
+        VERSION.hash(&mut hasher);
+        config.project.site_name.hash(&mut hasher);
+        plugin.background_color.hash(&mut hasher);
+        plugin.color.hash(&mut hasher);
+
+        // Load font and logo, both relative to the configuration file
+        let root_dir = config.get_root_dir();
+        let path = root_dir.join(&plugin.font_file);
+        let font =
+            fs::read(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|data| {
+                    data.hash(&mut hasher);
+                    FontVec::try_from_vec(data).map_err(|err| err.to_string())
+                });
+        let font = match font {
+            Ok(font) => font,
+            Err(err) => {
+                diagnostics.error(format!(
+                    "Couldn't load font '{}': {err}",
+                    path.display()
+                ));
+                return disabled;
+            }
+        };
+        let logo = match &plugin.logo {
+            Some(logo) => {
+                let path = root_dir.join(logo);
+                let logo = fs::read(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|data| {
+                        data.hash(&mut hasher);
+                        Image::decode(&data).map_err(|err| err.to_string())
+                    });
+                match logo {
+                    Ok(logo) => Some(logo),
+                    Err(err) => {
+                        diagnostics.error(format!(
+                            "Couldn't load logo '{}': {err}",
+                            path.display()
+                        ));
+                        return disabled;
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Parse colors, which are validated when loading the configuration
+        let colors = (
+            Color::parse(&plugin.background_color),
+            Color::parse(&plugin.color),
+        );
+        let (Some(background), Some(color)) = colors else {
+            diagnostics.error("Invalid colors in social plugin configuration");
+            return disabled;
+        };
+
+        // Cached cards are kept in a hidden directory, so they survive the
+        // pruning of the cache directory after each build
+        let inner = Inner {
+            font,
+            logo,
+            background,
+            color,
+            site_name: config.project.site_name.clone(),
+            site_url: url::ensure_dir_url(site_url),
+            cards_dir: plugin.cards_dir.clone(),
+            site_dir: config.get_site_dir(),
+            cache_dir: config.get_cache_dir().join(".social"),
+            hash: hasher.finish(),
+        };
+        Self { inner: Some(Arc::new(inner)) }
+    }
+
+    /// Writes the card of the given page, and references it in its metadata.
+    ///
+    /// Cards are looked up in the cache first, and only rendered if missing.
+    /// Meta tags are added to the `meta` list in the page metadata, which the
+    /// templates render into the head of the page.
+    pub fn process(
+        &self, page: &mut Page, sink: &dyn OutputSink, report: &Report,
+    ) -> io::Result<()> {
+        let Some(inner) = &self.inner else {
+            return Ok(());
+        };
+
+        // Compute cache key from the card template and page title
+        let mut hasher = StableHasher::default();
+        inner.hash.hash(&mut hasher);
+        page.title.hash(&mut hasher);
+        let path = inner
+            .cache_dir
+            .join(format!("{:016x}.png", hasher.finish()));
+
+        // Obtain card from cache, or render and cache it
+        let data = if let Ok(data) = fs::read(&path) {
+            report.add(Counter::CachedSocialCards, 1);
+            data
+        } else {
+            let card = Card {
+                font: &inner.font,
+                logo: inner.logo.as_ref(),
+                background: inner.background,
+                color: inner.color,
+            };
+            let data = card
+                .render(&inner.site_name, &page.title)
+                .map_err(io::Error::other)?;
+            fs::create_dir_all(&inner.cache_dir)?;
+            fs::write(&path, &data)?;
+            data
+        };
+
+        // Write card, and reference it with an absolute URL
+        let location =
+            format!("{}/{}.png", inner.cards_dir, to_name(&page.url));
+        sink.write(&inner.site_dir.join(&location), &data)?;
+        report.add(Counter::SocialCards, 1);
+        add_meta(&mut page.meta, &format!("{}{location}", inner.site_url));
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the name of the card for the page with the given URL.
+fn to_name(url: &str) -> &str {
+    let name = url.trim_end_matches('/');
+    let name = name.strip_suffix(".html").unwrap_or(name);
+    if name.is_empty() {
+        "index"
+    } else {
+        name
+    }
+}
+
+/// Adds meta tags referencing the card with the given URL to the metadata.
+///
+/// If the page already defines an Open Graph image, e.g., in front matter,
+/// it takes precedence, and no meta tags are added.
+fn add_meta(meta: &mut PageMeta, url: &str) {
+    let tags = meta
+        .entry(String::from("meta"))
+        .or_insert_with(|| Dynamic::List(Vec::new()));
+    let Dynamic::List(tags) = tags else {
+        return;
+    };
+    if tags.iter().any(|tag| {
+        matches!(tag, Dynamic::Map(tag) if matches!(
+            tag.get("property"),
+            Some(Dynamic::String(value)) if value == "og:image"
+        ))
+    }) {
+        return;
+    }
+
+    // Add Open Graph and Twitter meta tags
+    let (width, height) = (WIDTH.to_string(), HEIGHT.to_string());
+    for (key, name, content) in [
+        ("property", "og:image", url),
+        ("property", "og:image:type", "image/png"),
+        ("property", "og:image:width", &width),
+        ("property", "og:image:height", &height),
+        ("name", "twitter:card", "summary_large_image"),
+        ("name", "twitter:image", url),
+    ] {
+        tags.push(Dynamic::Map(BTreeMap::from([
+            (key.to_string(), Dynamic::String(name.to_string())),
+            (
+                String::from("content"),
+                Dynamic::String(content.to_string()),
+            ),
+        ])));
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_name_handles_url_styles() {
+        assert_eq!(to_name(""), "index");
+        assert_eq!(to_name("guide/setup/"), "guide/setup");
+        assert_eq!(to_name("guide/setup.html"), "guide/setup");
+        assert_eq!(to_name("index.html"), "index");
+    }
+
+    #[test]
+    fn add_meta_keeps_explicit_images() {
+        let mut meta = PageMeta::new();
+        add_meta(&mut meta, "https://example.com/card.png");
+        let Some(Dynamic::List(tags)) = meta.get("meta") else {
+            panic!("expected meta tags");
+        };
+        assert_eq!(tags.len(), 6);
+
+        // Adding meta tags again must not duplicate them
+        add_meta(&mut meta, "https://example.com/other.png");
+        let Some(Dynamic::List(tags)) = meta.get("meta") else {
+            panic!("expected meta tags");
+        };
+        assert_eq!(tags.len(), 6);
+    }
+}
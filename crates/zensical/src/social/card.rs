@@ -0,0 +1,463 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Social card rendering.
+
+use ab_glyph::{point, Font, FontVec, GlyphId, PxScale, ScaleFont};
+use std::io::Cursor;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Width of social cards in pixels.
+pub const WIDTH: u32 = 1200;
+
+/// Height of social cards in pixels.
+pub const HEIGHT: u32 = 630;
+
+/// Padding around the contents of social cards in pixels.
+const PADDING: f32 = 80.0;
+
+/// Font size of the site name in pixels.
+const NAME_SIZE: f32 = 40.0;
+
+/// Font size of the page title in pixels.
+const TITLE_SIZE: f32 = 80.0;
+
+/// Line height of the page title, relative to its font size.
+const LINE_HEIGHT: f32 = 1.2;
+
+/// Maximum number of lines of the page title.
+const MAX_LINES: usize = 3;
+
+/// Height of the logo in pixels.
+const LOGO_SIZE: u32 = 80;
+
+/// Gap between the logo and the site name in pixels.
+const LOGO_GAP: f32 = 24.0;
+
+/// Ellipsis appended to truncated lines.
+const ELLIPSIS: char = '\u{2026}';
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// RGB color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color([u8; 3]);
+
+/// Raster image with RGBA pixels.
+#[derive(Clone, Debug)]
+pub struct Image {
+    /// Width in pixels.
+    width: u32,
+    /// Height in pixels.
+    height: u32,
+    /// Pixels, row by row.
+    pixels: Vec<u8>,
+}
+
+/// Social card.
+///
+/// Cards show the site name, optionally next to the logo, at the top, and the
+/// page title at the bottom, which is wrapped to at most three lines, and is
+/// truncated with an ellipsis if it doesn't fit.
+pub struct Card<'a> {
+    /// Font for all text.
+    pub font: &'a FontVec,
+    /// Logo, if any.
+    pub logo: Option<&'a Image>,
+    /// Background color.
+    pub background: Color,
+    /// Text color.
+    pub color: Color,
+}
+
+/// Canvas with RGB pixels, with the dimensions of a social card.
+struct Canvas {
+    /// Pixels, row by row.
+    pixels: Vec<u8>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Color {
+    /// Parses a color in hexadecimal notation, e.g., `#4051b5`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.strip_prefix('#')?;
+        if value.len() != 6 || !value.is_ascii() {
+            return None;
+        }
+
+        // Parse each channel
+        let mut channels = [0; 3];
+        for (index, channel) in channels.iter_mut().enumerate() {
+            let range = index * 2..index * 2 + 2;
+            *channel = u8::from_str_radix(&value[range], 16).ok()?;
+        }
+        Some(Self(channels))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Image {
+    /// Decodes the given PNG image.
+    pub fn decode(data: &[u8]) -> Result<Self, png::DecodingError> {
+        let mut decoder = png::Decoder::new(Cursor::new(data));
+        decoder
+            .set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let size = reader.output_buffer_size().unwrap_or_default();
+        let mut buffer = vec![0; size];
+        let info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(info.buffer_size());
+
+        // Convert pixels to RGBA, which is the only format we draw
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .chunks_exact(2)
+                .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            png::ColorType::Grayscale | png::ColorType::Indexed => {
+                buffer.iter().flat_map(|&g| [g, g, g, u8::MAX]).collect()
+            }
+        };
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Card<'_> {
+    /// Renders the card for the given site name and page title as PNG.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(
+        &self, name: &str, title: &str,
+    ) -> Result<Vec<u8>, png::EncodingError> {
+        let mut canvas = Canvas::new(self.background);
+
+        // Draw logo, if any, and the site name next to it, both centered on
+        // the same horizontal axis
+        let mut x = PADDING;
+        if let Some(logo) = self.logo {
+            x += canvas.draw_image(logo, PADDING, PADDING) + LOGO_GAP;
+        }
+        let top = PADDING + (LOGO_SIZE as f32 - NAME_SIZE * LINE_HEIGHT) / 2.0;
+        let width = WIDTH as f32 - PADDING - x;
+        let name = wrap(name, width, 1, |text| self.measure(NAME_SIZE, text));
+        for line in name {
+            canvas.draw_text(self.font, NAME_SIZE, &line, x, top, self.color);
+        }
+
+        // Draw page title, aligned to the bottom of the card
+        let width = WIDTH as f32 - 2.0 * PADDING;
+        let lines = wrap(title, width, MAX_LINES, |text| {
+            self.measure(TITLE_SIZE, text)
+        });
+        let height = TITLE_SIZE * LINE_HEIGHT;
+        let mut top = HEIGHT as f32 - PADDING - height * lines.len() as f32;
+        for line in lines {
+            let font = self.font;
+            canvas.draw_text(font, TITLE_SIZE, &line, PADDING, top, self.color);
+            top += height;
+        }
+        canvas.encode()
+    }
+
+    /// Returns the width of the given text at the given font size.
+    fn measure(&self, size: f32, text: &str) -> f32 {
+        let font = self.font.as_scaled(PxScale::from(size));
+        let mut width = 0.0;
+        let mut last: Option<GlyphId> = None;
+        for character in text.chars() {
+            let id = font.glyph_id(character);
+            if let Some(last) = last {
+                width += font.kern(last, id);
+            }
+            width += font.h_advance(id);
+            last = Some(id);
+        }
+        width
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Canvas {
+    /// Creates a canvas filled with the given color.
+    fn new(color: Color) -> Self {
+        let size = (WIDTH * HEIGHT) as usize;
+        Self { pixels: color.0.repeat(size) }
+    }
+
+    /// Blends the given color into the pixel at the given position.
+    fn blend(&mut self, x: i64, y: i64, color: [u8; 3], alpha: f32) {
+        if x < 0 || y < 0 || x >= i64::from(WIDTH) || y >= i64::from(HEIGHT) {
+            return;
+        }
+
+        // Blend each channel, weighting the color by its coverage
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let index = (y as usize * WIDTH as usize + x as usize) * 3;
+        let alpha = alpha.clamp(0.0, 1.0);
+        for (channel, value) in
+            self.pixels[index..index + 3].iter_mut().zip(color)
+        {
+            let blended =
+                f32::from(*channel) * (1.0 - alpha) + f32::from(value) * alpha;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                *channel = blended.round() as u8;
+            }
+        }
+    }
+
+    /// Draws the given line of text with its top at the given position.
+    fn draw_text(
+        &mut self, font: &FontVec, size: f32, text: &str, x: f32, top: f32,
+        color: Color,
+    ) {
+        let scale = PxScale::from(size);
+        let scaled = font.as_scaled(scale);
+
+        // Center glyphs vertically within the line
+        let offset = (size * LINE_HEIGHT - scaled.height()) / 2.0;
+        let mut caret = point(x, top + offset + scaled.ascent());
+        let mut last: Option<GlyphId> = None;
+        for character in text.chars() {
+            let id = scaled.glyph_id(character);
+            if let Some(last) = last {
+                caret.x += scaled.kern(last, id);
+            }
+            let glyph = id.with_scale_and_position(scale, caret);
+            caret.x += scaled.h_advance(id);
+            last = Some(id);
+
+            // Rasterize glyph, and blend its coverage into the canvas
+            if let Some(outlined) = font.outline_glyph(glyph) {
+                let bounds = outlined.px_bounds();
+                #[allow(clippy::cast_possible_truncation)]
+                let (left, top) = (bounds.min.x as i64, bounds.min.y as i64);
+                outlined.draw(|gx, gy, coverage| {
+                    let (gx, gy) = (i64::from(gx), i64::from(gy));
+                    self.blend(left + gx, top + gy, color.0, coverage);
+                });
+            }
+        }
+    }
+
+    /// Draws the given image scaled to the logo size, and returns its width.
+    ///
+    /// Images are downscaled by averaging all source pixels that cover each
+    /// target pixel, which keeps edges smooth, unlike nearest neighbor.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn draw_image(&mut self, image: &Image, x: f32, y: f32) -> f32 {
+        if image.width == 0 || image.height == 0 {
+            return 0.0;
+        }
+        let scale = image.height as f32 / LOGO_SIZE as f32;
+        let width = (image.width as f32 / scale).round().max(1.0) as u32;
+
+        // Compute each target pixel from the source pixels it covers
+        for ty in 0..LOGO_SIZE {
+            let sy = (ty as f32 * scale) as u32;
+            let ey = (((ty + 1) as f32 * scale).ceil() as u32)
+                .clamp(sy + 1, image.height);
+            for tx in 0..width {
+                let sx = (tx as f32 * scale) as u32;
+                let ex = (((tx + 1) as f32 * scale).ceil() as u32)
+                    .clamp(sx + 1, image.width);
+
+                // Average premultiplied colors, and alpha
+                let mut sum = [0.0f32; 4];
+                for py in sy..ey {
+                    for px in sx..ex {
+                        let index = ((py * image.width + px) * 4) as usize;
+                        let pixel = &image.pixels[index..index + 4];
+                        let alpha = f32::from(pixel[3]) / 255.0;
+                        for channel in 0..3 {
+                            sum[channel] += f32::from(pixel[channel]) * alpha;
+                        }
+                        sum[3] += alpha;
+                    }
+                }
+                if sum[3] <= 0.0 {
+                    continue;
+                }
+                let count = ((ey - sy) * (ex - sx)) as f32;
+                let color = [0, 1, 2].map(|c| (sum[c] / sum[3]).round() as u8);
+                let (px, py) =
+                    (x as i64 + i64::from(tx), y as i64 + i64::from(ty));
+                self.blend(px, py, color, sum[3] / count);
+            }
+        }
+        width as f32
+    }
+
+    /// Encodes the canvas as PNG.
+    fn encode(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(data)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Wraps the given text into lines that fit into the given width.
+///
+/// Text is broken at whitespace. If it needs more than the given number of
+/// lines, the last line is truncated with an ellipsis, which is also used for
+/// single words that are too wide to fit into a line on their own.
+fn wrap<F>(text: &str, width: f32, max_lines: usize, measure: F) -> Vec<String>
+where
+    F: Fn(&str) -> f32,
+{
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+        if line.is_empty() || measure(&candidate) <= width {
+            line = candidate;
+        } else {
+            lines.push(line);
+            line = word.to_string();
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    // Truncate lines that don't fit, as well as the last line, if there are
+    // more lines than allowed
+    let overflow = lines.len() > max_lines;
+    lines.truncate(max_lines);
+    let count = lines.len();
+    for (index, line) in lines.iter_mut().enumerate() {
+        if (overflow && index + 1 == count) || measure(line) > width {
+            *line = ellipsize(line, width, &measure);
+        }
+    }
+    lines
+}
+
+/// Truncates the given line, so it fits into the given width with an ellipsis.
+fn ellipsize<F>(line: &str, width: f32, measure: &F) -> String
+where
+    F: Fn(&str) -> f32,
+{
+    let mut characters = line.chars().collect::<Vec<_>>();
+    loop {
+        let mut text = characters.iter().collect::<String>();
+        text.truncate(text.trim_end().len());
+        text.push(ELLIPSIS);
+        if characters.is_empty() || measure(&text) <= width {
+            return text;
+        }
+        characters.pop();
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn measure(text: &str) -> f32 {
+        text.chars().count() as f32
+    }
+
+    #[test]
+    fn color_parses_hexadecimal_notation() {
+        assert_eq!(Color::parse("#4051b5"), Some(Color([0x40, 0x51, 0xb5])));
+        assert_eq!(Color::parse("4051b5"), None);
+        assert_eq!(Color::parse("#4051b"), None);
+    }
+
+    #[test]
+    fn render_produces_card_dimensions() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../python/zensical/fonts/DejaVuSans-Bold.ttf"
+        );
+        let font = FontVec::try_from_vec(std::fs::read(path).unwrap()).unwrap();
+        let card = Card {
+            font: &font,
+            logo: None,
+            background: Color([0x40, 0x51, 0xb5]),
+            color: Color([0xff, 0xff, 0xff]),
+        };
+        let data = card.render("Zensical", "Getting started").unwrap();
+        let image = Image::decode(&data).unwrap();
+        assert_eq!((image.width, image.height), (WIDTH, HEIGHT));
+    }
+
+    #[test]
+    fn wrap_breaks_lines_at_whitespace() {
+        let lines = wrap("Getting started with Zensical", 16.0, 3, measure);
+        assert_eq!(lines, ["Getting started", "with Zensical"]);
+    }
+
+    #[test]
+    fn wrap_truncates_overflowing_lines() {
+        let lines = wrap("one two three four five", 9.0, 2, measure);
+        assert_eq!(lines, ["one two", "three\u{2026}"]);
+        let lines = wrap("Supercalifragilistic", 8.0, 3, measure);
+        assert_eq!(lines, ["Superca\u{2026}"]);
+    }
+}
@@ -25,10 +25,16 @@
 
 //! Site structure.
 
+#[cfg(test)]
+mod bench;
+pub mod blog;
 pub mod dynamic;
+pub mod i18n;
+pub mod manifest;
 pub mod markdown;
 pub mod nav;
 pub mod page;
 pub mod search;
 pub mod tag;
 pub mod toc;
+pub mod url;
@@ -0,0 +1,150 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Allocation benchmarks.
+//!
+//! Benchmarks are written as tests, which compare the number of allocations
+//! instead of timings, so they're deterministic and can run as part of CI.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use super::dynamic::Dynamic;
+use super::page::{PageMeta, PageSummary};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Counting allocator, installed for all tests.
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+thread_local! {
+    /// Number of allocations of the current thread.
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Counting allocator.
+///
+/// Allocations are counted per thread, since tests run concurrently, so each
+/// test only observes the allocations of the code it runs.
+struct Counting;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+unsafe impl GlobalAlloc for Counting {
+    /// Allocates memory, and counts the allocation.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        // SAFETY: the caller upholds the contract of the system allocator
+        unsafe { System.alloc(layout) }
+    }
+
+    /// Deallocates memory.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds the contract of the system allocator
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    /// Reallocates memory, and counts the reallocation.
+    unsafe fn realloc(
+        &self, ptr: *mut u8, layout: Layout, size: usize,
+    ) -> *mut u8 {
+        let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        // SAFETY: the caller upholds the contract of the system allocator
+        unsafe { System.realloc(ptr, layout, size) }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the result of the given function, and the allocations it made.
+///
+/// Reallocations are counted as well, as they're a sign of collections that
+/// grow without their capacity being reserved upfront.
+pub fn allocations<F, R>(f: F) -> (R, usize)
+where
+    F: FnOnce() -> R,
+{
+    let start = COUNT.with(Cell::get);
+    let value = f();
+    (value, COUNT.with(Cell::get) - start)
+}
+
+/// Creates a synthetic chunk of pages with the given number of metadata keys.
+///
+/// Pages are spread over sections of 100 pages each, and every page carries an
+/// icon, as well as the given number of additional keys in its front matter.
+pub fn chunk(size: usize, keys: usize) -> Vec<(Key<Id>, PageSummary)> {
+    (0..size)
+        .map(|n| {
+            let section = n / 100;
+            let id = Id::builder()
+                .provider("file")
+                .context("docs")
+                .location(format!("section-{section}/page-{n}.md"))
+                .build()
+                .expect("invariant");
+
+            // Create front matter with icon and additional keys
+            let mut meta = PageMeta::new();
+            let icon = Dynamic::String(String::from("lucide/book"));
+            meta.insert(String::from("icon"), icon);
+            for key in 0..keys {
+                let value = Dynamic::String(format!("value-{key}"));
+                meta.insert(format!("key-{key}"), value);
+            }
+
+            // Create page summary
+            let url = format!("section-{section}/page-{n}/");
+            let page = PageSummary {
+                url: url.into(),
+                canonical_url: None,
+                title: format!("Page {n}").into(),
+                title_html: format!("Page {n}").into(),
+                meta,
+                path: Arc::from(""),
+                src_path: Arc::from(""),
+                content_hash: Arc::from(""),
+                toc: Arc::from([]),
+                search: Arc::from([]),
+                tags: Vec::new(),
+            };
+            (Key::from_iter([id]), page)
+        })
+        .collect()
+}
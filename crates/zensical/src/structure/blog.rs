@@ -0,0 +1,656 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Blog.
+
+use minijinja::{context, Error, Value as TemplateValue};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use crate::config::markdown::Slugify;
+use crate::config::plugins::BlogPluginConfig;
+use crate::config::Config;
+use crate::template::{Template, GENERATOR};
+
+use super::dynamic::{Date, Dynamic};
+use super::markdown::slugify;
+use super::nav::{to_title, Navigation, NavigationItem};
+use super::page::{page_date, PageMeta, PageSummary};
+use super::url;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Listing kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListingKind {
+    /// Blog index.
+    Index,
+    /// Category index.
+    Category,
+    /// Archive index of a year.
+    Archive,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Blog.
+///
+/// Posts are collected from the posts directory and sorted by date, newest
+/// first, with undated posts last. They are listed on paginated index pages,
+/// as well as per category and per year, which are rendered with dedicated
+/// templates, mirroring the blog plugin of Material for MkDocs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Blog {
+    /// Blog title, derived from the blog directory.
+    pub title: String,
+    /// Posts, sorted by date.
+    pub posts: Vec<Post>,
+    /// Listings of posts.
+    pub listings: Vec<Listing>,
+}
+
+/// Blog post.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Post {
+    /// Post URL.
+    pub url: String,
+    /// Post title, as plain text.
+    pub title: String,
+    /// Post title, as HTML.
+    pub title_html: String,
+    /// Post date, if any.
+    pub date: Option<Date>,
+    /// Post categories.
+    pub categories: Vec<Category>,
+    /// Post metadata.
+    pub meta: PageMeta,
+}
+
+/// Blog category.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Category {
+    /// Category name.
+    pub name: String,
+    /// Category slug.
+    pub slug: String,
+    /// Category URL.
+    pub url: String,
+}
+
+/// Listing of posts, i.e., a single page of an index.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Listing {
+    /// Listing kind.
+    pub kind: ListingKind,
+    /// Listing title.
+    pub title: String,
+    /// Listing URL.
+    pub url: String,
+    /// Listing path, relative to the site directory.
+    pub path: String,
+    /// Posts on this page.
+    pub posts: Vec<Post>,
+    /// Pagination.
+    pub pagination: Pagination,
+}
+
+/// Pagination of a listing.
+///
+/// Links to the previous and next page are only set if those pages exist, so
+/// templates can render them unconditionally, e.g., for a single page.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Pagination {
+    /// Current page, starting at 1.
+    pub page: usize,
+    /// Total number of pages.
+    pub pages: usize,
+    /// URL of previous page, if any.
+    pub previous: Option<String>,
+    /// URL of next page, if any.
+    pub next: Option<String>,
+}
+
+/// Blog listings, shared between workflow stages.
+///
+/// Listings are created together with the navigation, and rendered after it,
+/// so pages located at the URL of a listing, e.g., the blog index, can look
+/// up the listing, in order to render it with their content as introduction.
+#[derive(Clone, Debug, Default)]
+pub struct Listings {
+    /// Listings of the last build.
+    inner: Arc<RwLock<Vec<Listing>>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Blog {
+    /// Creates a blog from the given pages.
+    ///
+    /// The blog index always has at least one page, even if there are no
+    /// posts, while categories and years only exist if they have posts.
+    pub fn new(
+        config: &BlogPluginConfig, use_directory_urls: bool,
+        pages: &[(Key<Id>, PageSummary)],
+    ) -> Self {
+        let prefix = format!("{}/", config.post_dir);
+
+        // Collect posts from the posts directory, and sort them by date, with
+        // the newest first - undated posts come last, as they sort first
+        let mut posts = pages
+            .iter()
+            .filter(|(id, _)| id[0].location().starts_with(&prefix))
+            .map(|(_, page)| Post {
+                url: page.url.to_string(),
+                title: page.title.to_string(),
+                title_html: page.title_html.to_string(),
+                date: page_date(&page.meta).cloned(),
+                categories: categories(&page.meta, &config.categories_key)
+                    .into_iter()
+                    .map(|name| {
+                        let slug = slugify(&name, Slugify::Unicode);
+                        let path =
+                            format!("{}/category/{slug}", config.blog_dir);
+                        let (url, _) = to_url(&path, use_directory_urls);
+                        Category { name, slug, url }
+                    })
+                    .collect(),
+                meta: page.meta.clone(),
+            })
+            .collect::<Vec<_>>();
+        posts.sort_by(|a, b| {
+            b.date.cmp(&a.date).then_with(|| a.url.cmp(&b.url))
+        });
+
+        // Group posts by category and year, keeping the order of posts
+        let mut by_category = BTreeMap::<&str, (&str, Vec<Post>)>::new();
+        let mut by_year = BTreeMap::<i64, Vec<Post>>::new();
+        for post in &posts {
+            for category in &post.categories {
+                let (_, list) = by_category
+                    .entry(category.slug.as_str())
+                    .or_insert((category.name.as_str(), Vec::new()));
+                list.push(post.clone());
+            }
+            if let Some(date) = &post.date {
+                let (year, _, _) = date.ymd();
+                by_year.entry(year).or_default().push(post.clone());
+            }
+        }
+
+        // Create listings for the index, all categories, and all years, with
+        // the most recent year first
+        let per_page = config.posts_per_page.max(1);
+        let title = to_title(&config.blog_dir);
+        let mut listings = paginate(
+            ListingKind::Index,
+            &title,
+            &config.blog_dir,
+            &posts,
+            per_page,
+            use_directory_urls,
+        );
+        for (slug, (name, list)) in &by_category {
+            listings.extend(paginate(
+                ListingKind::Category,
+                name,
+                &format!("{}/category/{slug}", config.blog_dir),
+                list,
+                per_page,
+                use_directory_urls,
+            ));
+        }
+        for (year, list) in by_year.iter().rev() {
+            listings.extend(paginate(
+                ListingKind::Archive,
+                &year.to_string(),
+                &format!("{}/archive/{year}", config.blog_dir),
+                list,
+                per_page,
+                use_directory_urls,
+            ));
+        }
+
+        // Return blog
+        Self { title, posts, listings }
+    }
+
+    /// Returns the URLs of all posts.
+    pub fn urls(&self) -> Vec<String> {
+        self.posts.iter().map(|post| post.url.clone()).collect()
+    }
+
+    /// Returns the navigation section of the blog.
+    ///
+    /// The section links to the first page of the blog index, as well as to
+    /// the first pages of all archive and category listings, if any.
+    pub fn section(&self) -> NavigationItem {
+        let index = self.first_pages(ListingKind::Index);
+        let mut children = index
+            .map(|item| NavigationItem { is_index: true, ..item })
+            .collect::<Vec<_>>();
+
+        // Omit empty sections, e.g., if no post has categories
+        for (title, kind) in [
+            ("Archive", ListingKind::Archive),
+            ("Categories", ListingKind::Category),
+        ] {
+            let items = self.first_pages(kind).collect::<Vec<_>>();
+            if !items.is_empty() {
+                children.push(section(title, items));
+            }
+        }
+        section(&self.title, children)
+    }
+
+    /// Returns navigation items for the first pages of the given kind.
+    fn first_pages(
+        &self, kind: ListingKind,
+    ) -> impl Iterator<Item = NavigationItem> + '_ {
+        self.listings
+            .iter()
+            .filter(move |listing| {
+                listing.kind == kind && listing.pagination.page == 1
+            })
+            .map(|listing| NavigationItem {
+                url: Some(listing.url.clone()),
+                ..section(&listing.title, Vec::new())
+            })
+    }
+}
+
+impl Listings {
+    /// Replaces the listings with the ones of the given blog.
+    pub fn replace(&self, blog: &Blog) {
+        let mut inner = self.inner.write().expect("invariant");
+        inner.clone_from(&blog.listings);
+    }
+
+    /// Returns the listing located at the given URL, if any.
+    pub fn get(&self, url: &str) -> Option<Listing> {
+        let inner = self.inner.read().expect("invariant");
+        inner.iter().find(|listing| listing.url == url).cloned()
+    }
+
+    /// Returns all listings.
+    pub fn all(&self) -> Vec<Listing> {
+        self.inner.read().expect("invariant").clone()
+    }
+}
+
+impl Listing {
+    /// Returns the name of the template to render the listing with.
+    pub fn template(&self) -> &'static str {
+        match self.kind {
+            ListingKind::Index => "blog.html",
+            ListingKind::Category => "blog-category.html",
+            ListingKind::Archive => "blog-archive.html",
+        }
+    }
+
+    /// Renders the listing.
+    ///
+    /// Templates receive the posts on this page as `posts`, and the links to
+    /// the previous and next page as part of `pagination`. Since listings
+    /// aren't pages, a minimal `page` is provided, so base templates work.
+    pub fn render(
+        &self, config: &Config, nav: Navigation,
+    ) -> Result<String, Error> {
+        let template =
+            Template::new(self.template(), config.theme_dirs.clone());
+
+        // Set active item in navigation, and compute ancestors
+        let nav = nav.with_active_url(&self.url);
+        let ancestors = nav.ancestors(&self.url);
+
+        // Create context and render template
+        let features = &config.project.theme.features;
+        template.render_with_context(context! {
+            generator => GENERATOR,
+            nav => nav,
+            base_url => config.get_base_url(&self.url),
+            extra_css => config.project.extra_css.clone(),
+            extra_javascript => config.project.extra_javascript.clone(),
+            config => config.project.clone(),
+            features => features.flags(),
+            page => context! {
+                title => self.title,
+                url => self.url,
+                meta => PageMeta::new(),
+                ancestors => ancestors,
+                toc => Vec::<()>::new()
+            },
+            posts => self.posts,
+            pagination => self.pagination,
+            ..TemplateValue::from_serialize(&config.project.extra_context)
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Splits the given posts into listings of the given size.
+///
+/// An empty list of posts still results in a single, empty listing, so the
+/// blog index exists before the first post is written. The first page is
+/// located at the given path, and all other pages at `page/<n>` below it.
+fn paginate(
+    kind: ListingKind, title: &str, path: &str, posts: &[Post],
+    per_page: usize, use_directory_urls: bool,
+) -> Vec<Listing> {
+    let mut chunks = posts.chunks(per_page).collect::<Vec<_>>();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+
+    // Compute URLs and paths of all pages upfront, to link them
+    let targets = (1..=chunks.len())
+        .map(|page| match page {
+            1 => to_url(path, use_directory_urls),
+            _ => to_url(&format!("{path}/page/{page}"), use_directory_urls),
+        })
+        .collect::<Vec<_>>();
+
+    // Create listings, linking to the previous and next page, if any
+    let iter = chunks.into_iter().zip(&targets).enumerate();
+    iter.map(|(index, (posts, (url, path)))| Listing {
+        kind,
+        title: title.to_string(),
+        url: url.clone(),
+        path: path.clone(),
+        posts: posts.to_vec(),
+        pagination: Pagination {
+            page: index + 1,
+            pages: targets.len(),
+            previous: index
+                .checked_sub(1)
+                .map(|index| targets[index].0.clone()),
+            next: targets.get(index + 1).map(|(url, _)| url.clone()),
+        },
+    })
+    .collect()
+}
+
+/// Returns the URL and path for the given path in the site directory.
+///
+/// Listings are always written to `index.html` files, so their URLs end with
+/// a slash, or with `index.html`, if directory URLs are disabled.
+fn to_url(path: &str, use_directory_urls: bool) -> (String, String) {
+    let file = if path.is_empty() {
+        String::from("index.html")
+    } else {
+        format!("{path}/index.html")
+    };
+    let url = if use_directory_urls {
+        url::strip_index(&file)
+    } else {
+        &file
+    };
+    (url::encode(url), file)
+}
+
+/// Returns the categories from the given front matter key.
+///
+/// Categories can be given as a list or as a single string, and duplicates
+/// are removed, keeping the order in which they're given.
+fn categories(meta: &PageMeta, key: &str) -> Vec<String> {
+    let values = match meta.get(key) {
+        Some(Dynamic::List(values)) => values.as_slice(),
+        Some(value) => std::slice::from_ref(value),
+        None => &[],
+    };
+    let mut names = Vec::<String>::new();
+    for value in values {
+        if let Dynamic::String(name) = value {
+            let name = name.trim();
+            if !name.is_empty() && !names.iter().any(|other| other == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Creates a navigation section with the given title and children.
+fn section(title: &str, children: Vec<NavigationItem>) -> NavigationItem {
+    NavigationItem {
+        title: Some(title.to_string()),
+        title_html: None,
+        url: None,
+        canonical_url: None,
+        meta: None,
+        icon: None,
+        attributes: BTreeMap::new(),
+        children,
+        is_index: false,
+        external: false,
+        active: false,
+        missing: false,
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use zrx::id::Id;
+    use zrx::scheduler::Key;
+
+    use crate::config::plugins::BlogPluginConfig;
+    use crate::structure::dynamic::{Date, Dynamic};
+    use crate::structure::page::{Page, PageMeta, PageSummary};
+
+    use super::{Blog, ListingKind, Listings};
+
+    /// Creates a blog configuration with the given number of posts per page.
+    fn config(posts_per_page: usize) -> BlogPluginConfig {
+        BlogPluginConfig {
+            enabled: true,
+            blog_dir: String::from("blog"),
+            post_dir: String::from("blog/posts"),
+            posts_per_page,
+            categories_key: String::from("categories"),
+        }
+    }
+
+    /// Creates a post with the given slug, date and categories.
+    fn post(
+        slug: &str, date: &str, categories: &[&str],
+    ) -> (Key<Id>, PageSummary) {
+        let id = Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(format!("blog/posts/{slug}.md"))
+            .build()
+            .unwrap();
+        let mut meta = PageMeta::new();
+        let date = Date::parse(date).unwrap();
+        meta.insert(String::from("date"), Dynamic::Date(date));
+        let categories = categories
+            .iter()
+            .map(|name| Dynamic::String((*name).to_string()))
+            .collect();
+        meta.insert(String::from("categories"), Dynamic::List(categories));
+        let page = Page {
+            url: format!("blog/posts/{slug}/"),
+            canonical_url: None,
+            edit_url: None,
+            title: slug.to_string(),
+            title_html: slug.to_string(),
+            meta,
+            path: String::new(),
+            src_path: String::new(),
+            content: String::new(),
+            content_hash: String::new(),
+            toc: Vec::new(),
+            search: Vec::new(),
+            ancestors: Vec::new(),
+            previous_page: None,
+            next_page: None,
+            word_count: 0,
+            reading_time: 0,
+            tags: Vec::new(),
+        };
+        (Key::from_iter([id]), PageSummary::from(&page))
+    }
+
+    /// Creates five posts in two categories, spread across two years.
+    fn posts() -> Vec<(Key<Id>, PageSummary)> {
+        vec![
+            post("first", "2023-11-02", &["Rust"]),
+            post("second", "2023-12-24", &["Python"]),
+            post("third", "2024-01-15", &["Rust", "Python"]),
+            post("fourth", "2024-02-01", &["Rust"]),
+            post("fifth", "2024-03-10", &[]),
+        ]
+    }
+
+    /// Returns kind, URL, previous and next URL of all listings.
+    fn outline(
+        blog: &Blog,
+    ) -> Vec<(ListingKind, &str, Option<&str>, Option<&str>)> {
+        blog.listings
+            .iter()
+            .map(|listing| {
+                let pagination = &listing.pagination;
+                (
+                    listing.kind,
+                    listing.url.as_str(),
+                    pagination.previous.as_deref(),
+                    pagination.next.as_deref(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn paginates_posts_by_category_and_year() {
+        let blog = Blog::new(&config(2), true, &posts());
+        let titles = blog
+            .posts
+            .iter()
+            .map(|post| post.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, ["fifth", "fourth", "third", "second", "first"]);
+        assert_eq!(
+            outline(&blog),
+            [
+                (ListingKind::Index, "blog/", None, Some("blog/page/2/")),
+                (
+                    ListingKind::Index,
+                    "blog/page/2/",
+                    Some("blog/"),
+                    Some("blog/page/3/")
+                ),
+                (
+                    ListingKind::Index,
+                    "blog/page/3/",
+                    Some("blog/page/2/"),
+                    None
+                ),
+                (ListingKind::Category, "blog/category/python/", None, None),
+                (
+                    ListingKind::Category,
+                    "blog/category/rust/",
+                    None,
+                    Some("blog/category/rust/page/2/")
+                ),
+                (
+                    ListingKind::Category,
+                    "blog/category/rust/page/2/",
+                    Some("blog/category/rust/"),
+                    None
+                ),
+                (
+                    ListingKind::Archive,
+                    "blog/archive/2024/",
+                    None,
+                    Some("blog/archive/2024/page/2/")
+                ),
+                (
+                    ListingKind::Archive,
+                    "blog/archive/2024/page/2/",
+                    Some("blog/archive/2024/"),
+                    None
+                ),
+                (ListingKind::Archive, "blog/archive/2023/", None, None),
+            ]
+        );
+        let listing = &blog.listings[1];
+        assert_eq!(listing.path, "blog/page/2/index.html");
+        assert_eq!((listing.pagination.page, listing.pagination.pages), (2, 3));
+    }
+
+    #[test]
+    fn paginates_single_page_without_links() {
+        let blog = Blog::new(&config(10), false, &posts());
+        let listing = &blog.listings[0];
+        assert_eq!(listing.url, "blog/index.html");
+        assert_eq!(listing.posts.len(), 5);
+        assert_eq!(listing.pagination.pages, 1);
+        assert_eq!(listing.pagination.previous, None);
+        assert_eq!(listing.pagination.next, None);
+    }
+
+    #[test]
+    fn paginates_empty_blog() {
+        let blog = Blog::new(&config(10), true, &[]);
+        assert_eq!(outline(&blog), [(ListingKind::Index, "blog/", None, None)]);
+        assert!(blog.listings[0].posts.is_empty());
+
+        // Only the index is part of the navigation
+        let section = blog.section();
+        assert_eq!(section.title.as_deref(), Some("Blog"));
+        assert_eq!(section.children.len(), 1);
+        assert!(section.children[0].is_index);
+    }
+
+    #[test]
+    fn listings_are_replaced_and_looked_up_by_url() {
+        let listings = Listings::default();
+        listings.replace(&Blog::new(&config(2), true, &posts()));
+        assert_eq!(listings.all().len(), 9);
+        let listing = listings.get("blog/page/3/").unwrap();
+        assert_eq!(listing.posts.len(), 1);
+
+        // Listings of the last build are replaced entirely
+        listings.replace(&Blog::new(&config(10), true, &posts()));
+        assert_eq!(listings.all().len(), 5);
+        assert_eq!(listings.get("blog/page/3/"), None);
+    }
+}
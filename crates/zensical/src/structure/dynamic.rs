@@ -30,8 +30,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fmt;
 
+mod date;
 mod float;
 
+pub use date::Date;
 use float::Float;
 
 // ----------------------------------------------------------------------------
@@ -43,7 +45,8 @@ use float::Float;
 /// This data type represents any valid value that can be used as part of the
 /// metadata of a page and the extra data of configuration, supporting strings,
 /// booleans, integers, floating point numbers, lists, and maps, so basically
-/// everything supported in YAML and TOML.
+/// everything supported in YAML and TOML. Dates are parsed from well-known
+/// front matter keys, and serialize to their original value.
 ///
 /// Null value are not supported, and currently represented as empty strings.
 /// We're aiming to provide a type safe way to define custom namespaces in the
@@ -67,6 +70,44 @@ pub enum Dynamic {
     List(Vec<Dynamic>),
     /// Map value.
     Map(BTreeMap<String, Dynamic>),
+    /// Date value.
+    Date(Date),
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Dynamic {
+    /// Merges the given value into the dynamic value.
+    ///
+    /// Maps are merged recursively, so nested keys that are not present in the
+    /// given value are retained. All other values are replaced, including
+    /// lists, which are not concatenated, as this would prevent overrides.
+    pub fn merge(&mut self, other: Dynamic) {
+        match (self, other) {
+            (Dynamic::Map(values), Dynamic::Map(other)) => {
+                for (key, value) in other {
+                    match values.get_mut(&key) {
+                        Some(current) => current.merge(value),
+                        None => {
+                            values.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (current, value) => *current = value,
+        }
+    }
+
+    /// Returns the date, if the value is one.
+    #[must_use]
+    pub fn as_date(&self) -> Option<&Date> {
+        match self {
+            Dynamic::Date(date) => Some(date),
+            _ => None,
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -91,6 +132,72 @@ impl fmt::Display for Dynamic {
                 let values: Vec<String> = iter.collect();
                 write!(f, "{{{}}}", values.join(", "))
             }
+            Dynamic::Date(value) => write!(f, "{value}"),
         }
     }
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Dynamic;
+
+    fn map<const N: usize>(entries: [(&str, Dynamic); N]) -> Dynamic {
+        Dynamic::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    fn string(value: &str) -> Dynamic {
+        Dynamic::String(value.to_string())
+    }
+
+    #[test]
+    fn merges_nested_maps() {
+        let mut value = map([
+            (
+                "banner",
+                map([("text", string("a")), ("show", Dynamic::Bool(true))]),
+            ),
+            ("version", string("1.0")),
+        ]);
+        value.merge(map([("banner", map([("text", string("b"))]))]));
+        assert_eq!(
+            value,
+            map([
+                (
+                    "banner",
+                    map([("text", string("b")), ("show", Dynamic::Bool(true))])
+                ),
+                ("version", string("1.0")),
+            ])
+        );
+    }
+
+    #[test]
+    fn replaces_lists_and_scalars() {
+        let mut value = map([
+            ("list", Dynamic::List(vec![string("a"), string("b")])),
+            ("map", map([("key", string("a"))])),
+        ]);
+        value.merge(map([
+            ("list", Dynamic::List(vec![string("c")])),
+            ("map", string("b")),
+        ]));
+        assert_eq!(
+            value,
+            map([
+                ("list", Dynamic::List(vec![string("c")])),
+                ("map", string("b")),
+            ])
+        );
+    }
+}
@@ -0,0 +1,475 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Date, optionally with time.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use regex::Regex;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::sync::LazyLock;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression for numeric dates, optionally followed by a time.
+static NUMERIC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(\d{4})[-/](\d{2})[-/](\d{2})",
+        r"(?:[T ](\d{2}):(\d{2})(?::(\d{2})(?:\.\d+)?)?",
+        r"\s*(Z|UTC|[+-]\d{2}(?::?\d{2})?)?)?$"
+    ))
+    .expect("invariant")
+});
+
+/// Regular expression for dates with month names, e.g., `January 5, 2024`.
+static TEXTUAL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(?i)(?:(\d{1,2})\s+([a-z]+)\.?|([a-z]+)\.?\s+(\d{1,2}),?)",
+        r"\s+(\d{4})$"
+    ))
+    .expect("invariant")
+});
+
+/// Month names.
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Weekday names, starting with Sunday.
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Date, optionally with time.
+///
+/// Dates are normalized to a timestamp in UTC, so they can be compared and
+/// sorted reliably, regardless of the offset they were given in. The offset
+/// is retained for formatting, and the original value for display, so dates
+/// are rendered exactly as authored, unless they are formatted explicitly.
+/// Dates without time are interpreted as midnight in UTC.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Date {
+    /// Seconds since the Unix epoch, in UTC.
+    timestamp: i64,
+    /// Offset from UTC in minutes.
+    offset: i32,
+    /// Whether the date includes a time.
+    has_time: bool,
+    /// Original value.
+    raw: String,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Date {
+    /// Parses a date from the given value.
+    ///
+    /// ISO 8601 dates and datetimes are supported, optionally with `Z`, `UTC`
+    /// or a numeric offset, as well as slashes as separators, and dates with
+    /// English month names, e.g., `January 5, 2024` or `5 Jan 2024`. Returns
+    /// [`None`] if the value has an unknown format or isn't a valid date.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(captures) = NUMERIC_RE.captures(value) {
+            let number = |n: usize| {
+                captures.get(n).map_or(Some(0), |m| m.as_str().parse().ok())
+            };
+            let [year, month, day, hour, minute, second] =
+                [1, 2, 3, 4, 5, 6].map(number);
+            let offset =
+                captures.get(7).map_or(Some(0), |m| offset(m.as_str()));
+            Self::new(
+                value,
+                [year?, month?, day?, hour?, minute?, second?],
+                offset?,
+                captures.get(4).is_some(),
+            )
+        } else if let Some(captures) = TEXTUAL_RE.captures(value) {
+            let name = captures.get(2).or(captures.get(3))?.as_str();
+            let month = MONTHS.iter().position(|month| {
+                name.len() >= 3
+                    && month.len() >= name.len()
+                    && month[..name.len()].eq_ignore_ascii_case(name)
+            })?;
+            let day = captures.get(1).or(captures.get(4))?.as_str();
+            let year = captures[5].parse().ok()?;
+            #[allow(clippy::cast_possible_wrap)]
+            let month = month as i64 + 1;
+            Self::new(
+                value,
+                [year, month, day.parse().ok()?, 0, 0, 0],
+                0,
+                false,
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Creates a date from the given components and offset in minutes.
+    fn new(
+        raw: &str, [year, month, day, hour, minute, second]: [i64; 6],
+        offset: i32, has_time: bool,
+    ) -> Option<Self> {
+        if !(1..=12).contains(&month)
+            || !(1..=days_in_month(year, month)).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+
+        // Compute timestamp in UTC, and subtract offset
+        let days = days_from_civil(year, month, day);
+        let seconds = hour * 3600 + minute * 60 + second;
+        Some(Self {
+            timestamp: days * 86_400 + seconds - i64::from(offset) * 60,
+            offset,
+            has_time,
+            raw: raw.to_string(),
+        })
+    }
+
+    /// Returns the number of seconds since the Unix epoch, in UTC.
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the original value.
+    #[must_use]
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns year, month and day in the original offset.
+    #[must_use]
+    pub fn ymd(&self) -> (i64, i64, i64) {
+        civil_from_days(self.local().div_euclid(86_400))
+    }
+
+    /// Formats the date with the given `strftime` format.
+    ///
+    /// The most common directives are supported, i.e., `%Y`, `%y`, `%m`, `%d`,
+    /// `%e`, `%j`, `%H`, `%I`, `%M`, `%S`, `%p`, `%b`, `%B`, `%a`, `%A`, `%z`,
+    /// `%F`, `%T` and `%%`. Unknown directives are left as they are.
+    #[must_use]
+    pub fn format(&self, format: &str) -> String {
+        let local = self.local();
+        let days = local.div_euclid(86_400);
+        let seconds = local.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        let (hour, minute) = (seconds / 3600, seconds / 60 % 60);
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let month_name = MONTHS[month as usize - 1];
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let weekday = WEEKDAYS[(days + 4).rem_euclid(7) as usize];
+
+        // Replace directives, and leave unknown ones as they are
+        let mut output = String::new();
+        let mut chars = format.chars();
+        while let Some(char) = chars.next() {
+            if char != '%' {
+                output.push(char);
+                continue;
+            }
+            let _ = match chars.next() {
+                Some('Y') => write!(output, "{year:04}"),
+                Some('y') => write!(output, "{:02}", year.rem_euclid(100)),
+                Some('m') => write!(output, "{month:02}"),
+                Some('d') => write!(output, "{day:02}"),
+                Some('e') => write!(output, "{day:>2}"),
+                Some('j') => {
+                    let start = days_from_civil(year, 1, 1);
+                    write!(output, "{:03}", days - start + 1)
+                }
+                Some('H') => write!(output, "{hour:02}"),
+                Some('I') => write!(output, "{:02}", (hour + 11) % 12 + 1),
+                Some('M') => write!(output, "{minute:02}"),
+                Some('S') => write!(output, "{:02}", seconds % 60),
+                Some('p') => {
+                    output.write_str(if hour < 12 { "AM" } else { "PM" })
+                }
+                Some('b') => output.write_str(&month_name[..3]),
+                Some('B') => output.write_str(month_name),
+                Some('a') => output.write_str(&weekday[..3]),
+                Some('A') => output.write_str(weekday),
+                Some('z') => {
+                    let sign = if self.offset < 0 { '-' } else { '+' };
+                    let offset = self.offset.abs();
+                    write!(output, "{sign}{:02}{:02}", offset / 60, offset % 60)
+                }
+                Some('F') => write!(output, "{year:04}-{month:02}-{day:02}"),
+                Some('T') => {
+                    let second = seconds % 60;
+                    write!(output, "{hour:02}:{minute:02}:{second:02}")
+                }
+                Some('%') | None => output.write_str("%"),
+                Some(char) => write!(output, "%{char}"),
+            };
+        }
+        output
+    }
+
+    /// Returns the number of seconds since the Unix epoch, in the offset.
+    fn local(&self) -> i64 {
+        self.timestamp + i64::from(self.offset) * 60
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl PartialOrd for Date {
+    /// Compares two dates.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Date {
+    /// Compares two dates by their timestamp, then by their original value.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.timestamp
+            .cmp(&other.timestamp)
+            .then_with(|| self.raw.cmp(&other.raw))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Date {
+    type Error = PyErr;
+
+    /// Extracts a date from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        let value: &str = obj.extract()?;
+        Self::parse(value).ok_or_else(|| PyValueError::new_err("Invalid date"))
+    }
+}
+
+impl Serialize for Date {
+    /// Serializes the date as its original value.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    /// Deserializes a date from its original value.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).ok_or_else(|| D::Error::custom("invalid date"))
+    }
+}
+
+impl fmt::Display for Date {
+    /// Formats the date for display.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Parses the given offset from UTC into minutes.
+fn offset(value: &str) -> Option<i32> {
+    if value == "Z" || value == "UTC" {
+        return Some(0);
+    }
+    let (sign, digits) = value.split_at(1);
+    let digits = digits.replace(':', "");
+    let hours: i32 = digits.get(..2)?.parse().ok()?;
+    let minutes: i32 = match digits.get(2..) {
+        Some("") | None => 0,
+        Some(minutes) => minutes.parse().ok()?,
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    let offset = hours * 60 + minutes;
+    Some(if sign == "-" { -offset } else { offset })
+}
+
+/// Returns the number of days in the given month.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the Unix epoch for the given date.
+///
+/// This is Howard Hinnant's algorithm for converting civil dates to days, as
+/// used in most date libraries, which is valid for all proleptic dates.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Returns the date for the given number of days since the Unix epoch.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::Date;
+
+    #[test]
+    fn parses_date_only() {
+        let date = Date::parse("2024-02-29").unwrap();
+        assert_eq!(date.timestamp(), 1_709_164_800);
+        assert_eq!(date.ymd(), (2024, 2, 29));
+        assert_eq!(date.format("%F %T"), "2024-02-29 00:00:00");
+        assert_eq!(date.to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn parses_datetimes_with_offsets() {
+        let utc = Date::parse("2024-01-02T10:00:00Z").unwrap();
+        let local = Date::parse("2024-01-02 12:00+02:00").unwrap();
+        assert_eq!(utc.timestamp(), local.timestamp());
+        assert_eq!(local.format("%H:%M %z"), "12:00 +0200");
+
+        // Offsets can move the date to the previous day in UTC
+        let date = Date::parse("2024-01-01T01:30:00.123+0530").unwrap();
+        let utc = Date::parse("2023-12-31T20:00:00Z").unwrap();
+        assert_eq!(date.timestamp(), utc.timestamp());
+        assert_eq!(date.ymd(), (2024, 1, 1));
+    }
+
+    #[test]
+    fn parses_common_formats() {
+        let iso = Date::parse("2024-01-05").unwrap();
+        for value in ["2024/01/05", "January 5, 2024", "5 Jan 2024"] {
+            let date = Date::parse(value).unwrap();
+            assert_eq!(date.timestamp(), iso.timestamp());
+            assert_eq!(date.raw(), value);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for value in [
+            "yesterday",
+            "2023-02-29",
+            "2024-13-01",
+            "2024-01-01T25:00",
+            "2024-01-01 10:00+25:00",
+            "5 Foo 2024",
+            "2024-01-01 and more",
+        ] {
+            assert_eq!(Date::parse(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn formats_with_strftime_directives() {
+        let date = Date::parse("2024-03-09T15:04:05Z").unwrap();
+        assert_eq!(
+            date.format("%A, %B %e, %Y at %I:%M %p (%j) %%"),
+            "Saturday, March  9, 2024 at 03:04 PM (069) %"
+        );
+        assert_eq!(
+            date.format("%a %d %b %y %F %T %Q"),
+            "Sat 09 Mar 24 2024-03-09 15:04:05 %Q"
+        );
+    }
+
+    #[test]
+    fn orders_by_timestamp() {
+        let mut dates = ["2024-01-02", "2024-01-01T23:00-02:00", "2023-12-31"]
+            .map(|value| Date::parse(value).unwrap());
+        dates.sort();
+        let dates = dates.map(|date| date.raw().to_string());
+        assert_eq!(
+            dates,
+            ["2023-12-31", "2024-01-02", "2024-01-01T23:00-02:00"]
+        );
+    }
+}
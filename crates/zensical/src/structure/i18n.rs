@@ -0,0 +1,305 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Internationalization.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use crate::config::navigation::CollapseTitle;
+use crate::config::plugins::{I18nPluginConfig, Language};
+use crate::config::Config;
+use crate::hash::StableHasher;
+
+use super::nav::{Navigation, NavigationItem};
+use super::page::{Page, PageSummary};
+use super::url;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Alternate version of a page in another language.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Alternate {
+    /// Language locale.
+    pub locale: String,
+    /// Language name.
+    pub name: String,
+    /// Page URL, relative to the site directory.
+    pub url: String,
+}
+
+/// Localization of a page.
+///
+/// This is exposed to templates, so themes can render a language switcher, as
+/// well as `hreflang` links, and mark pages that were not translated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Localization {
+    /// Page language.
+    pub language: Language,
+    /// Alternate versions of the page, including the page itself.
+    pub alternate: Vec<Alternate>,
+    /// Whether the page is translated, or falls back to the default language.
+    pub translated: bool,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Localization {
+    /// Creates the localization of the page with the given URL.
+    ///
+    /// Pages that are missing in a language fall back to the default language,
+    /// which is why each language has an alternate for pages that exist in the
+    /// default language. Returns nothing, if the URL doesn't have a language.
+    pub fn new(
+        config: &I18nPluginConfig, translations: &Translations, url: &str,
+    ) -> Option<Self> {
+        let (language, neutral) = config.split(url)?;
+        let default = config.default_language()?;
+
+        // Obtain all translations of the language-neutral URL
+        let empty = BTreeMap::new();
+        let urls = translations.get(neutral).unwrap_or(&empty);
+
+        // Compute alternates for all languages, in which the page exists
+        let alternate = config
+            .languages
+            .iter()
+            .filter(|candidate| {
+                urls.contains_key(&candidate.locale)
+                    || urls.contains_key(&default.locale)
+            })
+            .map(|candidate| Alternate {
+                locale: candidate.locale.clone(),
+                name: candidate.name.clone(),
+                url: urls
+                    .get(&candidate.locale)
+                    .cloned()
+                    .unwrap_or_else(|| url::join(&candidate.path, neutral)),
+            })
+            .collect();
+
+        // Return localization
+        Some(Self {
+            language: language.clone(),
+            alternate,
+            translated: urls.contains_key(&language.locale),
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Creates a navigation for each language.
+///
+/// Pages are partitioned by the language subdirectory they're located in, and
+/// the language prefix is stripped from their locations, so the navigation of
+/// each language is resolved as if the subdirectory was the docs directory.
+/// This allows to share an explicit navigation structure between languages.
+/// The navigation of the default language is returned, which contains the
+/// navigations of all other languages, as well as all translations.
+pub fn localize(
+    config: &I18nPluginConfig, items: &[NavigationItem],
+    pages: &[(Key<Id>, PageSummary)], homepage: Option<&str>,
+    collapse: Option<CollapseTitle>,
+) -> Option<Navigation> {
+    let default = config.default_language()?;
+
+    // Collect translations of all pages, keyed by language-neutral URL
+    let mut translations = Translations::new();
+    for (_, page) in pages {
+        if let Some((language, neutral)) = config.split(&page.url) {
+            translations
+                .entry(neutral.to_string())
+                .or_default()
+                .insert(language.locale.clone(), page.url.to_string());
+        }
+    }
+
+    // Compute hash of translations, which we mix into the navigation hash, as
+    // alternates of pages change whenever translations are added or removed
+    let hash = {
+        let mut hasher = StableHasher::default();
+        translations.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    // Create navigation for each language from the pages in its subdirectory
+    let mut languages = BTreeMap::new();
+    for language in &config.languages {
+        let subset = pages
+            .iter()
+            .filter_map(|(key, page)| {
+                let location = key[0].location();
+                let (candidate, rest) = config.split(&location)?;
+                if candidate != language {
+                    return None;
+                }
+
+                // Strip language prefix from location - only identifiers are
+                // created, as pages are borrowed for navigation creation
+                let builder = key[0].to_builder().location(rest);
+                let id = builder.build().expect("invariant");
+                Some((id, page))
+            })
+            .collect::<Vec<_>>();
+
+        // Create navigation, and ensure it carries all translations
+        let iter = subset.iter().map(|(id, page)| (id, *page));
+        let mut nav = Navigation::new(items.to_vec(), iter, homepage, collapse);
+        nav.translations.clone_from(&translations);
+        nav.hash ^= hash;
+        languages.insert(language.locale.clone(), nav);
+    }
+
+    // Return navigation of default language
+    let mut nav = languages.get(&default.locale).cloned()?;
+    nav.languages = languages;
+    Some(nav)
+}
+
+/// Creates a fallback page for the given language from a page of the default
+/// language, which is rendered into the subdirectory of the given language.
+pub fn fallback(
+    config: &Config, page: &Page, language: &Language, neutral: &str,
+) -> Page {
+    let mut page = page.clone();
+    page.url = url::join(&language.path, neutral);
+    page.canonical_url = config
+        .project
+        .site_url
+        .as_ref()
+        .map(|base| url::join(base, &page.url));
+
+    // Compute path in site directory from URL
+    let mut file = url::decode(&page.url).into_owned();
+    if file.ends_with('/') {
+        file.push_str("index.html");
+    }
+
+    // Return fallback page
+    let path = config.get_site_dir().join(file);
+    page.path = path.to_string_lossy().into_owned();
+    page
+}
+
+// ----------------------------------------------------------------------------
+// Type aliases
+// ----------------------------------------------------------------------------
+
+/// Translations, mapping language-neutral URLs to URLs by locale.
+pub type Translations = BTreeMap<String, BTreeMap<String, String>>;
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::config::plugins::{I18nPluginConfig, Language};
+
+    use super::{Localization, Translations};
+
+    /// Creates an i18n configuration with English and German.
+    fn config() -> I18nPluginConfig {
+        let language = |locale: &str, name: &str, default| Language {
+            locale: locale.to_string(),
+            name: name.to_string(),
+            path: locale.to_string(),
+            default,
+        };
+        I18nPluginConfig {
+            enabled: true,
+            languages: vec![
+                language("en", "English", true),
+                language("de", "Deutsch", false),
+            ],
+        }
+    }
+
+    /// Creates translations from the given URLs.
+    fn translations(urls: &[(&str, &str, &str)]) -> Translations {
+        let mut translations = Translations::new();
+        for (neutral, locale, url) in urls {
+            translations
+                .entry((*neutral).to_string())
+                .or_insert_with(BTreeMap::new)
+                .insert((*locale).to_string(), (*url).to_string());
+        }
+        translations
+    }
+
+    #[test]
+    fn split_strips_language_prefix() {
+        let config = config();
+        let (language, rest) = config.split("de/guide/").unwrap();
+        assert_eq!(language.locale, "de");
+        assert_eq!(rest, "guide/");
+        assert_eq!(config.split("en").unwrap().1, "");
+        assert!(config.split("english/guide/").is_none());
+    }
+
+    #[test]
+    fn localization_of_translated_page() {
+        let translations = translations(&[
+            ("guide/", "en", "en/guide/"),
+            ("guide/", "de", "de/guide/"),
+        ]);
+        let localization =
+            Localization::new(&config(), &translations, "de/guide/").unwrap();
+        assert!(localization.translated);
+        assert_eq!(localization.language.locale, "de");
+        assert_eq!(localization.alternate.len(), 2);
+        assert_eq!(localization.alternate[0].url, "en/guide/");
+        assert_eq!(localization.alternate[1].url, "de/guide/");
+    }
+
+    #[test]
+    fn localization_of_fallback_page() {
+        let translations = translations(&[("guide/", "en", "en/guide/")]);
+        let localization =
+            Localization::new(&config(), &translations, "de/guide/").unwrap();
+        assert!(!localization.translated);
+        assert_eq!(localization.alternate[1].url, "de/guide/");
+    }
+
+    #[test]
+    fn localization_without_language() {
+        let translations = Translations::new();
+        let localization =
+            Localization::new(&config(), &translations, "guide/");
+        assert!(localization.is_none());
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Site manifest.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use super::dynamic::Dynamic;
+use super::nav::Navigation;
+use super::page::PageSummary;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Manifest schema version, which is incremented on breaking changes.
+const VERSION: u32 = 1;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Site manifest.
+///
+/// The manifest is a machine-readable inventory of all pages of the site, for
+/// downstream tools like link checkers or search ingestion pipelines. Its JSON
+/// schema is stable, and looks as follows, with all keys in sorted order:
+///
+/// ```json
+/// {
+///   "pages": [
+///     {
+///       "description": "Page description, or null",
+///       "id": "Stable page identifier, omitted if not set",
+///       "meta": { "key": "Front matter value of allowed key" },
+///       "sections": ["Section", "Subsection"],
+///       "source": "path/to/page.md",
+///       "tags": ["Tag"],
+///       "title": "Page title",
+///       "url": "path/to/page/"
+///     }
+///   ],
+///   "version": 1
+/// }
+/// ```
+///
+/// Fields are declared in sorted order, since serialization follows the order
+/// of declaration, and pages are sorted by URL, so the output is reproducible.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Manifest pages.
+    pub pages: Vec<ManifestPage>,
+    /// Manifest schema version.
+    pub version: u32,
+}
+
+/// Manifest page.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestPage {
+    /// Page description, taken from front matter.
+    pub description: Option<String>,
+    /// Stable page identifier, taken from front matter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Page metadata, limited to allowed front matter keys.
+    pub meta: BTreeMap<String, Dynamic>,
+    /// Titles of sections containing the page, starting at the root.
+    pub sections: Vec<String>,
+    /// Source path, relative to the docs directory.
+    pub source: String,
+    /// Tag names.
+    pub tags: Vec<String>,
+    /// Page title.
+    pub title: String,
+    /// Page URL, relative to the site directory.
+    pub url: String,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Manifest {
+    /// Creates a manifest from pages.
+    ///
+    /// Only front matter keys that are part of the given allow-list are added
+    /// to the metadata of each page, as front matter might contain data that
+    /// is not meant to be published. Without allow-list, all keys are added,
+    /// which is used for snapshots that are never published.
+    pub fn new(
+        pages: &[(Key<Id>, PageSummary)], nav: &Navigation,
+        allow: Option<&[String]>,
+    ) -> Self {
+        let mut pages = pages
+            .iter()
+            .map(|(id, page)| {
+                let sections = nav
+                    .ancestors_of(&page.url)
+                    .iter()
+                    .rev()
+                    .filter_map(|item| item.display_title().map(Into::into))
+                    .collect();
+
+                // Select allowed front matter keys
+                let meta = page
+                    .meta
+                    .iter()
+                    .filter(|(key, _)| {
+                        allow.is_none_or(|allow| allow.contains(*key))
+                    })
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+
+                // Assemble manifest page
+                ManifestPage {
+                    description: page
+                        .meta
+                        .get("description")
+                        .map(ToString::to_string),
+                    id: page.stable_id(),
+                    meta,
+                    sections,
+                    source: id[0].location().into_owned(),
+                    tags: page
+                        .tags
+                        .iter()
+                        .map(|tag| tag.name.clone())
+                        .collect(),
+                    title: page.title.to_string(),
+                    url: page.url.to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Sort pages by URL, as they are processed concurrently
+        pages.sort_by(|a, b| a.url.cmp(&b.url));
+        Self { pages, version: VERSION }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::structure::dynamic::Dynamic;
+
+    use super::{Manifest, ManifestPage};
+
+    #[test]
+    fn serializes_with_sorted_keys() {
+        let manifest = Manifest {
+            pages: vec![ManifestPage {
+                description: None,
+                id: Some("guide".into()),
+                meta: BTreeMap::from([
+                    ("status".into(), Dynamic::String("new".into())),
+                    ("author".into(), Dynamic::String("jane".into())),
+                ]),
+                sections: vec!["Guide".into()],
+                source: "guide/index.md".into(),
+                tags: vec!["Setup".into()],
+                title: "Guide".into(),
+                url: "guide/".into(),
+            }],
+            version: 1,
+        };
+        assert_eq!(
+            serde_json::to_string(&manifest).unwrap(),
+            concat!(
+                r#"{"pages":[{"description":null,"id":"guide","#,
+                r#""meta":{"author":"jane","status":"new"},"#,
+                r#""sections":["Guide"],"source":"guide/index.md","#,
+                r#""tags":["Setup"],"title":"Guide","url":"guide/"}],"#,
+                r#""version":1}"#
+            )
+        );
+    }
+}
@@ -26,22 +26,48 @@
 //! Markdown rendering.
 
 use anyhow::Result;
-use pyo3::types::{PyAnyMethods, PyTracebackMethods};
+use pyo3::types::PyAnyMethods;
 use pyo3::{FromPyObject, Python};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use zrx::id::Id;
 use zrx::stream::Value;
 
+use crate::config::markdown::Engine;
+use crate::config::Config;
 use crate::structure::dynamic::Dynamic;
 use crate::structure::nav::to_title;
 use crate::structure::search::SearchItem;
+use crate::structure::tag::Tag;
 use crate::structure::toc::Section;
+use crate::workflow::Cancellation;
 
 mod autorefs;
+mod converter;
+mod decode;
+mod fence;
+mod lint;
+#[cfg(test)]
+mod minimal;
+#[cfg(feature = "native-markdown")]
+pub mod native;
+mod snippets;
+mod text;
+mod variables;
 
 pub use autorefs::Autorefs;
+pub use converter::{MarkdownConverter, PythonMarkdown};
+pub use decode::{decode, Decoded, Invalid};
+pub use fence::{check_fences, Fence};
+pub use lint::check_structure;
+#[cfg(test)]
+pub use minimal::MinimalMarkdown;
+#[cfg(feature = "native-markdown")]
+pub use native::NativeMarkdown;
+pub use snippets::Snippets;
+pub use text::{escape_html, slugify, to_plain_text};
+pub use variables::render_variables;
 
 // ----------------------------------------------------------------------------
 // Constants
@@ -64,10 +90,14 @@ pub struct Markdown {
     pub content: String,
     /// Search index.
     pub search: Vec<SearchItem>,
-    /// Page title extracted from Markdown.
+    /// Page title extracted from Markdown, as plain text.
     pub title: String,
+    /// Page title extracted from Markdown, as HTML.
+    pub title_html: String,
     /// Table of contents.
     pub toc: Vec<Section>,
+    /// Tags.
+    pub tags: Vec<Tag>,
 }
 
 // ----------------------------------------------------------------------------
@@ -75,37 +105,27 @@ pub struct Markdown {
 // ----------------------------------------------------------------------------
 
 impl Markdown {
-    /// Renders Markdown using Python Markdown.
-    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    pub fn new(id: &Id, url: String, content: String) -> Result<Markdown> {
-        let id = id.clone();
-        let guard = RENDER_LOCK.get_or_init(|| Mutex::new(())).lock().unwrap();
-        let res = Python::attach(|py| {
-            let module = py.import("zensical.markdown.render")?;
-            module
-                .call_method1("render", (content, id.location(), url))?
-                .extract::<Markdown>()
-        })
-        .map_err(|err| {
-            Python::attach(|py| {
-                let traceback = err
-                    .traceback(py)
-                    .and_then(|tb| tb.format().ok())
-                    .unwrap_or_default();
-                anyhow::anyhow!("Python error: {err}\n{traceback}")
-            })
-        });
+    /// Renders Markdown using the given converter.
+    ///
+    /// After conversion, the title is extracted from the metadata, the table
+    /// of contents, or the file name, regardless of the converter.
+    pub fn new(
+        converter: &dyn MarkdownConverter, id: &Id, url: String,
+        content: String, cancellation: &Cancellation,
+    ) -> Result<Markdown> {
+        converter
+            .convert(id, url, content, cancellation)
+            .map(|markdown| markdown.with_title(id))
+    }
 
-        // Explicitly drop the lock guard here, so we're sure to hold it just
-        // until after Python finished executing the rendering logic
-        drop(guard);
-        res.map(|markdown| Markdown {
-            title: extract_title(&id, &markdown),
-            meta: markdown.meta,
-            content: markdown.content,
-            search: markdown.search,
-            toc: markdown.toc,
-        })
+    /// Recomputes the title for the given identifier.
+    ///
+    /// As a last resort, the title is derived from the file name, which is why
+    /// it must be recomputed when a cached result is used for a renamed page.
+    #[must_use]
+    pub fn with_title(mut self, id: &Id) -> Self {
+        (self.title, self.title_html) = extract_title(id, &self);
+        self
     }
 }
 
@@ -129,23 +149,66 @@ impl Eq for Markdown {}
 // Functions
 // ----------------------------------------------------------------------------
 
+/// Creates the Markdown converter for the configured engine.
+pub fn create_converter(config: &Config) -> Arc<dyn MarkdownConverter> {
+    match config.project.markdown.engine {
+        Engine::Python => Arc::new(PythonMarkdown),
+        #[cfg(feature = "native-markdown")]
+        Engine::Native => Arc::new(NativeMarkdown::new(config)),
+        // Configuration falls back to Python if the engine is not supported
+        #[cfg(not(feature = "native-markdown"))]
+        Engine::Native => unreachable!(),
+    }
+}
+
+/// Extracts metadata from the front matter of the given Markdown.
+///
+/// Front matter is YAML, which is why we need to call into Python for parsing,
+/// so metadata is guaranteed to be identical to what rendering yields.
+pub fn front_matter(content: &str) -> Result<BTreeMap<String, Dynamic>> {
+    let guard = RENDER_LOCK.get_or_init(|| Mutex::new(())).lock().unwrap();
+    let res = Python::attach(|py| {
+        let module = py.import("zensical.markdown.render")?;
+        module
+            .call_method1("front_matter", (content,))?
+            .extract::<BTreeMap<String, Dynamic>>()
+    })
+    .map_err(|err| anyhow::anyhow!("Python error: {err}"));
+
+    // Explicitly drop the lock guard, see above
+    drop(guard);
+    res
+}
+
 /// Extract the title from the metadata or table of contents.
 ///
 /// MkDocs prioritizes the "title" metadata field over the actual title in the
 /// page. This has been a huge source of confusion, as can be read here:
 /// https://github.com/mkdocs/mkdocs/issues/3532
 ///
+/// The title is returned as plain text and as HTML, since the first top-level
+/// heading might contain markup, e.g., code spans or emojis, which must not
+/// leak into contexts that can't render it, like `<title>` tags or search.
+/// The file name is only used if there's neither a title nor a heading.
+///
 /// We'll fix this in our modular navigation proposal that will make title
 /// handling much more flexible in the near future.
-fn extract_title(id: &Id, markdown: &Markdown) -> String {
+fn extract_title(id: &Id, markdown: &Markdown) -> (String, String) {
     if let Some(value) = markdown.meta.get("title") {
-        return value.to_string();
+        let title = value.to_string();
+        if !title.trim().is_empty() {
+            let title_html = escape_html(&title);
+            return (title, title_html);
+        }
     }
 
     // Otherwise, fall back to the first top-level heading, if existent
     let mut iter = markdown.toc.iter();
     if let Some(item) = iter.find(|item| item.level == 1) {
-        return item.title.clone();
+        let title = to_plain_text(&item.content);
+        if !title.is_empty() {
+            return (title, item.content.clone());
+        }
     }
 
     // As a last resort, use the file name
@@ -159,5 +222,80 @@ fn extract_title(id: &Id, markdown: &Markdown) -> String {
 
     // Extract file, and return title
     let file = components.pop().expect("invariant");
-    to_title(&file)
+    let title = to_title(&file);
+    let title_html = escape_html(&title);
+    (title, title_html)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates Markdown with the given metadata and top-level heading.
+    fn markdown(meta: &[(&str, &str)], heading: Option<&str>) -> Markdown {
+        let toc = heading.map(|content| Section {
+            title: String::new(),
+            content: content.to_string(),
+            id: String::from("heading"),
+            url: String::from("#heading"),
+            children: Vec::new(),
+            level: 1,
+        });
+        Markdown {
+            meta: meta
+                .iter()
+                .map(|(key, value)| {
+                    (key.to_string(), Dynamic::String(value.to_string()))
+                })
+                .collect(),
+            content: String::new(),
+            search: Vec::new(),
+            title: String::new(),
+            title_html: String::new(),
+            toc: toc.into_iter().collect(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Creates an identifier for the given location.
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .expect("invariant")
+    }
+
+    #[test]
+    fn title_from_heading_strips_markup() {
+        let html = "The <code>build</code> command &amp; <em>more</em>";
+        let markdown = markdown(&[], Some(html));
+        assert_eq!(
+            extract_title(&id("build.md"), &markdown),
+            ("The build command & more".to_string(), html.to_string())
+        );
+    }
+
+    #[test]
+    fn title_from_metadata_takes_precedence() {
+        let markdown = markdown(&[("title", "A & B")], Some("Heading"));
+        assert_eq!(
+            extract_title(&id("page.md"), &markdown),
+            ("A & B".to_string(), "A &amp; B".to_string())
+        );
+    }
+
+    #[test]
+    fn title_from_file_name_without_title_or_heading() {
+        let markdown = markdown(&[("title", "")], None);
+        assert_eq!(
+            extract_title(&id("getting-started.md"), &markdown),
+            ("Getting started".to_string(), "Getting started".to_string())
+        );
+    }
 }
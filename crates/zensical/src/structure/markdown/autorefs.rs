@@ -25,14 +25,15 @@
 
 //! Autorefs (mkdocstrings).
 
-use ahash::HashMap;
 use pyo3::FromPyObject;
 use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
 use std::string::ToString;
 use std::sync::LazyLock;
-use zrx::path::PathExt;
+
+use crate::structure::toc::Section;
+use crate::structure::url;
 
 // ----------------------------------------------------------------------------
 // Constants
@@ -72,36 +73,6 @@ fn html_escape(text: &str) -> String {
         .replace('\'', "&#x27;")
 }
 
-/// Helper to check if a URL is relative to a base URL.
-fn is_relative_to(url: &str, base: &str) -> bool {
-    // Remove fragments and query strings for directory comparison
-    let url_path = url
-        .split('#')
-        .next()
-        .unwrap_or(url)
-        .split('?')
-        .next()
-        .unwrap_or(url);
-    let base_path = base
-        .split('#')
-        .next()
-        .unwrap_or(base)
-        .split('?')
-        .next()
-        .unwrap_or(base);
-
-    // Use Path::starts_with for proper path comparison
-    Path::new(url_path).starts_with(Path::new(base_path))
-}
-
-/// Gets the parent path of a URL.
-fn parent_path(url: &str) -> Option<String> {
-    Path::new(url)
-        .parent()
-        .and_then(|p| p.to_str())
-        .map(ToString::to_string)
-}
-
 /// Resolves the closest URL from a list relative to from_url.
 ///
 /// We do that when multiple URLs are found for an identifier.
@@ -125,7 +96,7 @@ fn resolve_closest_url(
     loop {
         let found: Vec<String> = urls
             .iter()
-            .filter(|url| is_relative_to(url, &base_url))
+            .filter(|url| url::starts_with(url, &base_url))
             .cloned()
             .collect();
 
@@ -134,9 +105,9 @@ fn resolve_closest_url(
             break;
         }
 
-        match parent_path(&base_url) {
+        match url::parent(&base_url) {
             Some(parent) if !parent.is_empty() => {
-                base_url = parent;
+                base_url = parent.to_string();
             }
             _ => {
                 // @todo Log warning using qualifier
@@ -156,42 +127,6 @@ fn resolve_closest_url(
     }
 }
 
-/// Computes a relative URL from from_url to to_url.
-fn relative_url(from_url: &str, to_url: &str) -> String {
-    let from_path = Path::new(from_url);
-
-    // Split URL and fragment for relative computation
-    let (to_path, to_fragment) = to_url
-        .split_once('#')
-        .map_or((Path::new(to_url), None), |(path, f)| {
-            (Path::new(path), Some(f))
-        });
-
-    // Make target URL relative to page
-    let mut rel_path = to_path
-        .relative_to(from_path)
-        .to_string_lossy()
-        .replace('\\', "/");
-
-    // Add fragment back if present
-    if let Some(frag) = to_fragment {
-        // If the relative path is "." and we have a fragment,
-        // just return the fragment
-        if rel_path == "." {
-            return format!("#{frag}");
-        }
-        // If `to_path` was empty (URL was just a fragment),
-        // add "/" before the fragment
-        if to_path.as_os_str().is_empty() {
-            rel_path.push('/');
-        }
-        rel_path.push('#');
-        rel_path.push_str(frag);
-    }
-
-    rel_path
-}
-
 /// Checks if a URL is relative (no scheme).
 fn is_relative_url(url: &str) -> bool {
     !(url.starts_with("http://") || url.starts_with("https://"))
@@ -236,19 +171,25 @@ fn is_relative_url(url: &str) -> bool {
 /// - Multiple secondary URLs mapped to an identifier? Use the first one, or closest one if configured as such.
 /// - No secondary URL mapped to an identifier? Try using absolute URLs
 ///   (typically registered by loading inventories in mkdocstrings).
+/// - No absolute URL mapped to an identifier? Try using heading URLs, which
+///   are registered from the tables of contents of all pages, so plain
+///   Markdown headings can be cross-referenced by their ids and titles.
 #[derive(
     Clone, Debug, Default, FromPyObject, Serialize, Deserialize, PartialEq, Eq,
 )]
 #[pyo3(from_item_all)]
 pub struct Autorefs {
     // Primary URLs.
-    pub primary: HashMap<String, Vec<String>>,
+    pub primary: BTreeMap<String, Vec<String>>,
     // Secondary URLs.
-    pub secondary: HashMap<String, Vec<String>>,
+    pub secondary: BTreeMap<String, Vec<String>>,
     // Inventory URLs.
-    pub inventory: HashMap<String, String>,
+    pub inventory: BTreeMap<String, String>,
     // Titles.
-    pub titles: HashMap<String, String>,
+    pub titles: BTreeMap<String, String>,
+    // Heading URLs.
+    #[serde(default)]
+    pub headings: BTreeMap<String, Vec<String>>,
 }
 
 // ----------------------------------------------------------------------------
@@ -261,12 +202,35 @@ impl Autorefs {
         Self::default()
     }
 
-    /// Parses HTML attributes string into a HashMap.
+    /// Registers the headings of a page from its table of contents.
+    ///
+    /// Each heading is registered by its id and its title, both mapping to the
+    /// anchor on the page with the given URL. The title of the heading is also
+    /// registered for the anchor, unless mkdocstrings already registered one.
+    pub fn register_headings(&mut self, url: &str, toc: &[Section]) {
+        for section in toc {
+            let anchor = format!("{url}#{}", section.id);
+            for key in [&section.id, &section.title] {
+                let urls = self.headings.entry(key.clone()).or_default();
+                if !urls.contains(&anchor) {
+                    urls.push(anchor.clone());
+                }
+            }
+
+            // Register title for anchor, and recurse into children
+            self.titles
+                .entry(anchor)
+                .or_insert_with(|| section.title.clone());
+            self.register_headings(url, &section.children);
+        }
+    }
+
+    /// Parses HTML attributes string into a map.
     ///
     /// @todo Document that this is not the most resilient HTML parser
     /// but since we control the autorefs elements, it's fine for now
-    fn parse_attributes(attrs_str: &str) -> HashMap<String, String> {
-        let mut attrs = HashMap::default();
+    fn parse_attributes(attrs_str: &str) -> BTreeMap<String, String> {
+        let mut attrs = BTreeMap::new();
         let mut chars = attrs_str.chars().peekable();
 
         while let Some(ch) = chars.peek() {
@@ -398,6 +362,16 @@ impl Autorefs {
             return Ok(url.clone());
         }
 
+        // Try heading URLs - multiple pages can have headings with the same id
+        // or title, which is why we always resolve closest, just like for
+        // secondary URLs, as authors can't disambiguate plain headings
+        if let Some(urls) = self.headings.get(identifier) {
+            if urls.len() > 1 {
+                return Ok(resolve_closest_url(from_url, urls, "heading"));
+            }
+            return Ok(urls[0].clone());
+        }
+
         Err(format!("Identifier '{identifier}' not found"))
     }
 
@@ -412,7 +386,7 @@ impl Autorefs {
 
         // If from_url is provided and URL is relative, compute relative URL
         if is_relative_url(&url) {
-            url = relative_url(from_url, &url);
+            url = url::relative_to(&url, from_url);
         }
 
         Ok((url, title))
@@ -434,6 +408,12 @@ impl Autorefs {
         ))
     }
 
+    /// Returns whether the given content contains autorefs.
+    #[inline]
+    pub fn is_used_in(content: &str) -> bool {
+        AUTOREF_RE.is_match(content)
+    }
+
     /// Replaces autorefs in the given content.
     pub fn replace_in(&self, content: String, from_url: &str) -> String {
         let output = AUTOREF_RE.replace_all(&content, |captures: &Captures| {
@@ -578,6 +558,69 @@ mod tests {
         }
     }
 
+    /// Creates a table of contents section with the given id and title.
+    fn section(id: &str, title: &str) -> Section {
+        Section {
+            title: title.to_string(),
+            content: title.to_string(),
+            id: id.to_string(),
+            url: format!("#{id}"),
+            children: Vec::new(),
+            level: 2,
+        }
+    }
+
+    #[test]
+    fn test_resolve_heading_across_pages() {
+        let mut autorefs = Autorefs::new();
+        autorefs.register_headings("guide/", &[section("setup", "Setup")]);
+
+        let content = "<autoref identifier=\"setup\">Setup</autoref>";
+        assert_eq!(
+            autorefs.replace_in(content.to_string(), "about/"),
+            "<a class=\"autorefs autorefs-internal\" \
+             href=\"../guide/#setup\">Setup</a>"
+        );
+
+        // Headings can also be referenced by their title
+        let content = "<autoref identifier=\"Setup\">Go</autoref>";
+        assert_eq!(
+            autorefs.replace_in(content.to_string(), "about/"),
+            "<a class=\"autorefs autorefs-internal\" title=\"Setup\" \
+             href=\"../guide/#setup\">Go</a>"
+        );
+    }
+
+    #[test]
+    fn test_resolve_heading_after_mkdocstrings_identifier() {
+        let mut autorefs = Autorefs::new();
+        autorefs.register_headings("guide/", &[section("setup", "Setup")]);
+        autorefs
+            .primary
+            .insert("setup".to_string(), vec!["api/#setup".to_string()]);
+
+        let content = "<autoref identifier=\"setup\">Setup</autoref>";
+        assert_eq!(
+            autorefs.replace_in(content.to_string(), "about/"),
+            "<a class=\"autorefs autorefs-internal\" \
+             href=\"../api/#setup\">Setup</a>"
+        );
+    }
+
+    #[test]
+    fn test_resolve_heading_closest_url() {
+        let mut autorefs = Autorefs::new();
+        autorefs.register_headings("x/", &[section("setup", "Setup")]);
+        autorefs.register_headings("a/b/", &[section("setup", "Setup")]);
+
+        let content = "<autoref identifier=\"setup\">Setup</autoref>";
+        assert_eq!(
+            autorefs.replace_in(content.to_string(), "a/"),
+            "<a class=\"autorefs autorefs-internal\" \
+             href=\"b/#setup\">Setup</a>"
+        );
+    }
+
     #[test]
     fn test_relative_url() {
         let test_cases = vec![
@@ -604,7 +647,7 @@ mod tests {
         ];
 
         for (current_url, to_url, expected_href) in test_cases {
-            let result = relative_url(current_url, to_url);
+            let result = url::relative_to(to_url, current_url);
             assert_eq!(
                 result, expected_href,
                 "Failed for relative_url('{current_url}', '{to_url}'), expected '{expected_href}' but got '{result}'"
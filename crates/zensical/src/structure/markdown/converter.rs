@@ -0,0 +1,122 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Markdown converters.
+
+use anyhow::Result;
+use pyo3::types::{PyAnyMethods, PyTracebackMethods};
+use pyo3::Python;
+use std::fmt;
+use std::sync::Mutex;
+use zrx::id::Id;
+
+use crate::workflow::{thread_ident, Cancellation};
+
+use super::{Markdown, RENDER_LOCK};
+
+// ----------------------------------------------------------------------------
+// Traits
+// ----------------------------------------------------------------------------
+
+/// Markdown converter.
+///
+/// Converters are selected when the workflow is created, and shared by all
+/// stages that convert Markdown, so implementations must be safe to use
+/// concurrently. The title is extracted by [`Markdown::new`] after conversion,
+/// which is why converters can leave it empty.
+pub trait MarkdownConverter: fmt::Debug + Send + Sync {
+    /// Converts the given Markdown content of the page at the given URL.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the content could not be converted, or
+    /// the conversion was skipped, as the given cancellation was cancelled.
+    fn convert(
+        &self, id: &Id, url: String, content: String,
+        cancellation: &Cancellation,
+    ) -> Result<Markdown>;
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Markdown converter using Python Markdown.
+///
+/// Conversions are serialized with a global lock, as Python Markdown and its
+/// extensions are not thread-safe.
+#[derive(Debug, Default)]
+pub struct PythonMarkdown;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl MarkdownConverter for PythonMarkdown {
+    /// Converts Markdown using Python Markdown.
+    ///
+    /// The conversion is registered with the given cancellation once it holds
+    /// the render lock, so it can be interrupted. If the workflow was cancelled
+    /// before, e.g., while waiting for the lock, the file is skipped.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn convert(
+        &self, id: &Id, url: String, content: String,
+        cancellation: &Cancellation,
+    ) -> Result<Markdown> {
+        let guard = RENDER_LOCK.get_or_init(|| Mutex::new(())).lock().unwrap();
+        let res = Python::attach(|py| {
+            let thread = thread_ident(py)?;
+            let Some(_conversion) = cancellation.start(id.location(), thread)
+            else {
+                return Ok(None);
+            };
+            let module = py.import("zensical.markdown.render")?;
+            module
+                .call_method1("render", (content, id.location(), url))?
+                .extract::<Markdown>()
+                .map(Some)
+        })
+        .map_err(|err| {
+            Python::attach(|py| {
+                let traceback = err
+                    .traceback(py)
+                    .and_then(|tb| tb.format().ok())
+                    .unwrap_or_default();
+                anyhow::anyhow!("Python error: {err}\n{traceback}")
+            })
+        });
+
+        // Explicitly drop the lock guard here, so we're sure to hold it just
+        // until after Python finished executing the rendering logic
+        drop(guard);
+        let Some(markdown) = res? else {
+            anyhow::bail!(
+                "Skipped '{}', as the build was cancelled",
+                id.location()
+            );
+        };
+        Ok(markdown)
+    }
+}
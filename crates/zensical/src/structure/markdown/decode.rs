@@ -0,0 +1,131 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Markdown decoding.
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Byte-Order-Mark (BOM) of UTF-8 encoded files.
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Decoded Markdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// Source content.
+    pub content: String,
+    /// Position of the first invalid UTF-8 sequence, if any.
+    pub invalid: Option<Invalid>,
+}
+
+/// Position of an invalid UTF-8 sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Invalid {
+    /// Byte offset in the file.
+    pub offset: usize,
+    /// Line number, starting at 1.
+    pub line: usize,
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Decodes the given bytes of a Markdown file.
+///
+/// The Byte-Order-Mark (BOM), which editors on Windows tend to add, is
+/// removed, so it doesn't end up in front matter or titles, and line
+/// endings are normalized to `\n`, just like Python Markdown does it. If
+/// the file is not valid UTF-8, e.g., because it was saved as Latin-1,
+/// invalid sequences are replaced with the replacement character, and the
+/// position of the first one is retained, so it can be reported.
+pub fn decode(bytes: &[u8]) -> Decoded {
+    let skip = if bytes.starts_with(BOM) { BOM.len() } else { 0 };
+    match std::str::from_utf8(&bytes[skip..]) {
+        Ok(content) => Decoded {
+            content: normalize(content.to_owned()),
+            invalid: None,
+        },
+        Err(err) => {
+            let offset = skip + err.valid_up_to();
+            let newlines = bytes[..offset].iter().filter(|&&b| b == b'\n');
+            let content = String::from_utf8_lossy(&bytes[skip..]);
+            Decoded {
+                content: normalize(content.into_owned()),
+                invalid: Some(Invalid {
+                    offset,
+                    line: newlines.count() + 1,
+                }),
+            }
+        }
+    }
+}
+
+/// Normalizes line endings to `\n`, including lone carriage returns.
+fn normalize(content: String) -> String {
+    if content.contains('\r') {
+        content.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        content
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, Invalid};
+
+    #[test]
+    fn removes_byte_order_mark() {
+        let source = decode(b"\xEF\xBB\xBF---\nid: setup\n---\n# Setup\n");
+        assert_eq!(source.content, "---\nid: setup\n---\n# Setup\n");
+        assert_eq!(source.invalid, None);
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        let source = decode(b"---\r\nid: setup\r\n---\r\n# Setup\r\n");
+        assert_eq!(source.content, "---\nid: setup\n---\n# Setup\n");
+        let source = decode(b"# Setup\rText\r");
+        assert_eq!(source.content, "# Setup\nText\n");
+    }
+
+    #[test]
+    fn replaces_invalid_sequences() {
+        let source = decode(b"\xEF\xBB\xBF# Caf\xE9\n\nNa\xEFve\n");
+        assert_eq!(source.content, "# Caf\u{FFFD}\n\nNa\u{FFFD}ve\n");
+        assert_eq!(source.invalid, Some(Invalid { offset: 8, line: 1 }));
+        let source = decode(b"# Title\n\nNa\xEFve\n");
+        assert_eq!(source.invalid, Some(Invalid { offset: 11, line: 3 }));
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Code block fences.
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match the opening line of a fenced code block.
+pub(super) static FENCE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[ \t]*(`{3,}|~{3,})[ \t]*(.*?)[ \t]*$").expect("invariant")
+});
+
+/// Regular expression to match options, e.g., `title="example.py"`, as well
+/// as classes and identifiers, which are only allowed inside of braces.
+static OPTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"([.#][\w-]+)|([a-zA-Z][\w-]*)(?:=(?:"([^"]*)"|'([^']*)'|(\S+)))?"#,
+    )
+    .expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Options of a fenced code block.
+///
+/// Options are given after the language, e.g., `py title="a.py"`, or inside
+/// of braces together with classes and identifiers, where the first class
+/// denotes the language, e.g., `{ .py .no-copy title="a.py" }`, which is the
+/// syntax that `pymdownx.superfences` supports.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fence {
+    /// Language, if any.
+    pub lang: Option<String>,
+    /// Identifier, if any.
+    pub id: Option<String>,
+    /// Classes, excluding the language.
+    pub classes: Vec<String>,
+    /// Title, rendered as the file name.
+    pub title: Option<String>,
+    /// Highlighted lines, starting at 1.
+    pub hl_lines: BTreeSet<usize>,
+    /// Line numbers, if enabled.
+    pub linenums: Option<Linenums>,
+    /// Other attributes, which are only allowed inside of braces.
+    pub attrs: Vec<(String, String)>,
+    /// Problems with options, e.g., unknown options or invalid values.
+    pub problems: Vec<String>,
+}
+
+/// Line numbers of a fenced code block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Linenums {
+    /// Number of the first line.
+    pub start: usize,
+    /// Interval at which line numbers are shown.
+    pub step: usize,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Fence {
+    /// Parses the options of a fenced code block from the given info string.
+    pub fn parse(info: &str) -> Self {
+        let mut fence = Fence::default();
+        let info = info.trim();
+        let inner = info
+            .strip_prefix('{')
+            .and_then(|info| info.strip_suffix('}'));
+        let (braces, rest) = if let Some(inner) = inner {
+            (true, inner)
+        } else {
+            let (lang, rest) =
+                info.split_once([' ', '\t']).unwrap_or((info, ""));
+            if lang.contains('=') {
+                (false, info)
+            } else {
+                if !lang.is_empty() {
+                    fence.lang = Some(lang.to_string());
+                }
+                (false, rest)
+            }
+        };
+
+        // Parse classes, identifiers and options - attributes are only allowed
+        // inside of braces, as they would otherwise not be recognized
+        for captures in OPTION_RE.captures_iter(rest) {
+            if let Some(token) = captures.get(1) {
+                let (kind, name) = token.as_str().split_at(1);
+                if !braces {
+                    fence.problems.push(format!(
+                        "'{kind}{name}' is only allowed inside of braces"
+                    ));
+                } else if kind == "#" {
+                    fence.id = Some(name.to_string());
+                } else if fence.lang.is_none() {
+                    fence.lang = Some(name.to_string());
+                } else {
+                    fence.classes.push(name.to_string());
+                }
+                continue;
+            }
+
+            // Obtain option and value, which might be quoted
+            let key = &captures[2];
+            let value = (3..=5)
+                .find_map(|n| captures.get(n))
+                .map_or("", |value| value.as_str());
+            match key {
+                "title" => fence.title = Some(value.to_string()),
+                "hl_lines" => match parse_hl_lines(value) {
+                    Some(lines) => fence.hl_lines = lines,
+                    None => fence.problems.push(format!(
+                        "invalid value '{value}' for option 'hl_lines'"
+                    )),
+                },
+                "linenums" => match parse_linenums(value) {
+                    Some(linenums) => fence.linenums = Some(linenums),
+                    None => fence.problems.push(format!(
+                        "invalid value '{value}' for option 'linenums'"
+                    )),
+                },
+                _ if braces => {
+                    fence.attrs.push((key.to_string(), value.to_string()));
+                }
+                _ => {
+                    fence.problems.push(format!("unknown option '{key}'"));
+                }
+            }
+        }
+        fence
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Checks the options of all fenced code blocks in the given Markdown.
+///
+/// Problems are returned together with the line of the opening fence, so they
+/// can be reported, as both engines would silently ignore them, which means
+/// that features of the theme like line highlighting just don't show up.
+pub fn check_fences(content: &str) -> Vec<(usize, String)> {
+    let mut problems = Vec::new();
+    let mut open: Option<(&str, usize, Fence, usize)> = None;
+    for (n, line) in content.lines().enumerate() {
+        // Count lines until the closing fence is found
+        if let Some((marker, start, fence, count)) = open.take() {
+            let trimmed = line.trim();
+            let closes = trimmed.starts_with(marker)
+                && trimmed.chars().all(|c| marker.starts_with(c));
+            if closes {
+                problems.extend(check_fence(start, &fence, count));
+            } else {
+                open = Some((marker, start, fence, count + 1));
+            }
+            continue;
+        }
+
+        // Parse options, if the line opens a fenced code block, except for
+        // backtick fences with backticks in their info string
+        let Some(captures) = FENCE_RE.captures(line) else {
+            continue;
+        };
+        let (Some(marker), Some(info)) = (captures.get(1), captures.get(2))
+        else {
+            continue;
+        };
+        let (marker, info) = (marker.as_str(), info.as_str());
+        if !(marker.starts_with('`') && info.contains('`')) {
+            open = Some((marker, n + 1, Fence::parse(info), 0));
+        }
+    }
+
+    // Check unclosed fence, which extends to the end of the document
+    if let Some((_, start, fence, count)) = open {
+        problems.extend(check_fence(start, &fence, count));
+    }
+    problems
+}
+
+/// Checks the given fence, which opens at the given line.
+fn check_fence(
+    start: usize, fence: &Fence, count: usize,
+) -> Vec<(usize, String)> {
+    let mut problems = fence
+        .problems
+        .iter()
+        .map(|problem| (start, format!("Code block: {problem}")))
+        .collect::<Vec<_>>();
+
+    // Highlighted lines must exist, or they're silently ignored
+    if let Some(line) = fence.hl_lines.last().filter(|line| **line > count) {
+        problems.push((
+            start,
+            format!(
+                "Code block: option 'hl_lines' refers to line {line}, but \
+                 the code block only has {count} lines"
+            ),
+        ));
+    }
+    problems
+}
+
+/// Parses highlighted lines, e.g., `1 3-5`.
+fn parse_hl_lines(value: &str) -> Option<BTreeSet<usize>> {
+    let mut lines = BTreeSet::new();
+    for part in value.split_whitespace() {
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        let start = start.parse::<usize>().ok().filter(|n| *n > 0)?;
+        let end = end.parse::<usize>().ok().filter(|n| *n >= start)?;
+        lines.extend(start..=end);
+    }
+    Some(lines)
+}
+
+/// Parses line numbers, e.g., `1` or `10 2`, i.e., start and step.
+fn parse_linenums(value: &str) -> Option<Linenums> {
+    let mut parts = value.split_whitespace().map(str::parse::<usize>);
+    let start = parts.next().unwrap_or(Ok(1)).ok()?;
+    let step = parts.next().unwrap_or(Ok(1)).ok().filter(|n| *n > 0)?;
+    (start > 0 && parts.next().is_none()).then_some(Linenums { start, step })
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_handles_options() {
+        let fence =
+            Fence::parse(r#"py title="a.py" hl_lines="1 3-4" linenums="5 2""#);
+        assert_eq!(fence.lang.as_deref(), Some("py"));
+        assert_eq!(fence.title.as_deref(), Some("a.py"));
+        assert_eq!(fence.hl_lines, BTreeSet::from([1, 3, 4]));
+        assert_eq!(fence.linenums, Some(Linenums { start: 5, step: 2 }));
+        assert!(fence.problems.is_empty());
+    }
+
+    #[test]
+    fn parse_handles_braces() {
+        let fence = Fence::parse(r#"{ .py .no-copy #code data-x="1" }"#);
+        assert_eq!(fence.lang.as_deref(), Some("py"));
+        assert_eq!(fence.classes, ["no-copy"]);
+        assert_eq!(fence.id.as_deref(), Some("code"));
+        assert_eq!(fence.attrs, [("data-x".to_string(), "1".to_string())]);
+        assert!(fence.problems.is_empty());
+    }
+
+    #[test]
+    fn parse_reports_problems() {
+        let fence = Fence::parse(r#"py hl_line="2" linenums="a" .copy"#);
+        assert_eq!(
+            fence.problems,
+            [
+                "unknown option 'hl_line'",
+                "invalid value 'a' for option 'linenums'",
+                "'.copy' is only allowed inside of braces",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_fences_reports_lines() {
+        let content = "# Title\n\n```py hl_lines=\"3\"\na\nb\n```\n\n\
+                       ````md\n```py hl_lines=\"9\"\n```\n````\n";
+        assert_eq!(
+            check_fences(content),
+            [(
+                3,
+                "Code block: option 'hl_lines' refers to line 3, but the \
+                 code block only has 2 lines"
+                    .to_string()
+            )]
+        );
+    }
+}
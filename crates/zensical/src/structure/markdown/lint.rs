@@ -0,0 +1,363 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Structural checks for Markdown.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use super::fence::FENCE_RE;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match the opening line of an admonition or details
+/// block, e.g., `!!! note "Title"` or `???+ tip`, without leading whitespace.
+static ADMONITION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:!!!|\?\?\?\+?)(?:[ \t]+(.*?))?[ \t]*$").expect("invariant")
+});
+
+/// Regular expression to match the opening line of a content tab, e.g.,
+/// `=== "Title"` or `===! "Title"`, without leading whitespace. Note that a
+/// line with only equal signs is a setext heading underline, not a tab.
+static TAB_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^===([!+]{0,2})[ \t]+(.+?)[ \t]*$").expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Block opened by a marker, waiting for its first line of content.
+struct Block {
+    /// Line of the marker.
+    line: usize,
+    /// Indentation of the marker.
+    indent: usize,
+    /// Label for problems.
+    label: String,
+}
+
+/// Set of content tabs at the same indentation.
+struct TabSet {
+    /// Indentation of the tab markers.
+    indent: usize,
+    /// Lines and titles of the tabs.
+    tabs: Vec<(usize, String)>,
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Checks the structure of admonitions, content tabs and fenced code blocks
+/// in the given Markdown, before it is converted.
+///
+/// Both engines silently render broken structures as garbled HTML, e.g., when
+/// the content of an admonition isn't indented by 4 spaces, or when tabs of a
+/// set are indented differently, so problems are returned together with the
+/// line they were found on, which allows to report them to the author.
+pub fn check_structure(content: &str) -> Vec<(usize, String)> {
+    let mut problems = Vec::new();
+    let mut fence: Option<(&str, usize)> = None;
+    let mut block: Option<Block> = None;
+    let mut sets: Vec<TabSet> = Vec::new();
+    for (n, line) in content.lines().enumerate() {
+        let n = n + 1;
+
+        // Skip content of fenced code blocks until the closing fence
+        if let Some((marker, start)) = fence.take() {
+            let trimmed = line.trim();
+            let closes = trimmed.starts_with(marker)
+                && trimmed.chars().all(|c| marker.starts_with(c));
+            if !closes {
+                fence = Some((marker, start));
+            }
+            continue;
+        }
+
+        // Skip blank lines, as they neither open nor close blocks
+        let trimmed = line.trim_start();
+        if trimmed.trim_end().is_empty() {
+            continue;
+        }
+
+        // Check that the content of the preceding marker is indented
+        let indent = indentation(line);
+        if let Some(block) = block.take() {
+            problems.extend(check_block(&block, indent));
+        }
+
+        // Close all tab sets that end before this line, which are the sets
+        // that are indented deeper, or at the same level, if it's no valid tab
+        let tab = TAB_RE.captures(trimmed);
+        let title = tab.as_ref().and_then(|captures| {
+            let title = captures.get(2)?.as_str().strip_prefix('"')?;
+            title.strip_suffix('"')
+        });
+        while let Some(set) = sets.last() {
+            if set.indent < indent || set.indent == indent && title.is_some() {
+                break;
+            }
+            if let Some(set) = sets.pop() {
+                problems.extend(check_tab_set(&set));
+            }
+        }
+
+        // Skip fenced code blocks until the closing fence
+        if let Some(marker) = open_fence(line) {
+            fence = Some((marker, n));
+            continue;
+        }
+
+        // Check admonitions, which must state their type
+        if let Some(captures) = ADMONITION_RE.captures(trimmed) {
+            let Some(kind) = captures
+                .get(1)
+                .and_then(|rest| rest.as_str().split_whitespace().next())
+            else {
+                problems.push((n, "Admonition: type is missing".to_string()));
+                continue;
+            };
+            let label = format!("Admonition '{kind}'");
+            problems.extend(check_alignment(n, indent, &label));
+            block = Some(Block { line: n, indent, label });
+
+        // Check content tabs, which must have a title in double quotes
+        } else if let Some(captures) = tab {
+            let Some(title) = title else {
+                problems.push((
+                    n,
+                    "Content tab: title must be enclosed in double quotes"
+                        .to_string(),
+                ));
+                continue;
+            };
+            let label = format!("Content tab '{title}'");
+            problems.extend(check_alignment(n, indent, &label));
+
+            // Start a new tab set, if the tab is not preceded by a sibling,
+            // or if it explicitly starts a new set via `!`
+            let sibling = sets.last().filter(|set| set.indent == indent);
+            if sibling.is_none() || captures[1].contains('!') {
+                if sibling.is_some() {
+                    problems
+                        .extend(sets.pop().iter().filter_map(check_tab_set));
+                }
+                sets.push(TabSet { indent, tabs: Vec::new() });
+            }
+
+            // Titles must be unique within a set, or tabs can't be told apart
+            if let Some(set) = sets.last_mut() {
+                if set.tabs.iter().any(|(_, other)| other == title) {
+                    problems.push((
+                        n,
+                        format!("{label}: title is used twice in the same set"),
+                    ));
+                }
+                set.tabs.push((n, title.to_string()));
+            }
+            block = Some(Block { line: n, indent, label });
+        }
+    }
+
+    // Check blocks and tab sets that extend to the end of the document
+    if let Some(block) = block {
+        problems.push((
+            block.line,
+            format!("{}: content is empty, as the document ends", block.label),
+        ));
+    }
+    while let Some(set) = sets.pop() {
+        problems.extend(check_tab_set(&set));
+    }
+    if let Some((_, start)) = fence {
+        problems.push((start, "Code block: fence is never closed".to_string()));
+    }
+
+    // Return problems in document order
+    problems.sort_by_key(|(line, _)| *line);
+    problems
+}
+
+/// Checks the content of the given block, which starts at the given indent.
+fn check_block(block: &Block, indent: usize) -> Option<(usize, String)> {
+    let problem = if indent <= block.indent {
+        "content is empty, as the following line is not indented".to_string()
+    } else if indent < block.indent + 4 {
+        format!(
+            "content must be indented by 4 spaces, but is indented by {}",
+            indent - block.indent
+        )
+    } else {
+        return None;
+    };
+    Some((block.line, format!("{}: {problem}", block.label)))
+}
+
+/// Checks that the marker at the given line is indented by a multiple of 4.
+fn check_alignment(
+    line: usize, indent: usize, label: &str,
+) -> Option<(usize, String)> {
+    (indent % 4 != 0).then(|| {
+        (
+            line,
+            format!(
+                "{label}: marker is indented by {indent} spaces, which is \
+                 not a multiple of 4"
+            ),
+        )
+    })
+}
+
+/// Checks the given tab set, which should contain more than one tab, as a
+/// single tab is most likely a sibling that ended up at the wrong level.
+fn check_tab_set(set: &TabSet) -> Option<(usize, String)> {
+    match set.tabs.as_slice() {
+        [(line, title)] => Some((
+            *line,
+            format!(
+                "Content tab '{title}': tab has no siblings, so check the \
+                 indentation of the tabs around it"
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Returns the marker, if the given line opens a fenced code block, except
+/// for backtick fences with backticks in their info string.
+fn open_fence(line: &str) -> Option<&str> {
+    let captures = FENCE_RE.captures(line)?;
+    let (marker, info) = (captures.get(1)?.as_str(), captures.get(2)?.as_str());
+    (!(marker.starts_with('`') && info.contains('`'))).then_some(marker)
+}
+
+/// Returns the indentation of the given line, expanding tabs to 4 spaces.
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .map_while(|char| match char {
+            ' ' => Some(1),
+            '\t' => Some(4),
+            _ => None,
+        })
+        .fold(0, |indent, width| {
+            if width == 4 {
+                indent + 4 - indent % 4
+            } else {
+                indent + 1
+            }
+        })
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_structure_accepts_valid() {
+        let content = "# Title\n\n\
+                       !!! note \"Note\"\n\n    Content\n\n\
+                       ???+ tip\n    Content\n\n    !!! info\n        Nested\n\n\
+                       === \"A\"\n\n    ```py\n    !!! no\n    ```\n\n\
+                       === \"B\"\n\n    === \"C\"\n        C\n    === \"D\"\n        D\n\n\
+                       ===! \"A\"\n    A\n===+ \"B\"\n    B\n\n\
+                       Heading\n===\n\n```\n=== \"Code\"\n```\n";
+        assert!(check_structure(content).is_empty());
+    }
+
+    #[test]
+    fn check_structure_reports_admonitions() {
+        let content = "!!! note\n  Content\n\n!!! tip\nContent\n\n\
+                       !!!\n    Content\n\n  !!! info\n      Content\n\n\
+                       ??? warning\n";
+        assert_eq!(
+            check_structure(content),
+            [
+                (
+                    1,
+                    "Admonition 'note': content must be indented by 4 \
+                     spaces, but is indented by 2"
+                ),
+                (
+                    4,
+                    "Admonition 'tip': content is empty, as the following \
+                     line is not indented"
+                ),
+                (7, "Admonition: type is missing"),
+                (
+                    10,
+                    "Admonition 'info': marker is indented by 2 spaces, \
+                      which is not a multiple of 4"
+                ),
+                (
+                    13,
+                    "Admonition 'warning': content is empty, as the \
+                      document ends"
+                ),
+            ]
+            .map(|(line, problem)| (line, problem.to_string()))
+        );
+    }
+
+    #[test]
+    fn check_structure_reports_content_tabs() {
+        let content = "=== \"A\"\n    A\n    === \"B\"\n        B\n\n\
+                       === C\n    C\n\n\
+                       === \"D\"\n    D\n=== \"D\"\n    D\n";
+        assert_eq!(
+            check_structure(content),
+            [
+                (
+                    1,
+                    "Content tab 'A': tab has no siblings, so check the \
+                     indentation of the tabs around it"
+                ),
+                (
+                    3,
+                    "Content tab 'B': tab has no siblings, so check the \
+                     indentation of the tabs around it"
+                ),
+                (6, "Content tab: title must be enclosed in double quotes"),
+                (11, "Content tab 'D': title is used twice in the same set"),
+            ]
+            .map(|(line, problem)| (line, problem.to_string()))
+        );
+    }
+
+    #[test]
+    fn check_structure_reports_unclosed_fences() {
+        let content = "!!! note\n    ```py\n    !!! tip\n\n=== \"A\"\n";
+        assert_eq!(
+            check_structure(content),
+            [(2, "Code block: fence is never closed".to_string())]
+        );
+    }
+}
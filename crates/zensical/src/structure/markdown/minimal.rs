@@ -0,0 +1,291 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Minimal Markdown converter for tests.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use zrx::id::Id;
+
+use crate::config::markdown::Slugify;
+use crate::structure::dynamic::Dynamic;
+use crate::structure::toc::Section;
+use crate::workflow::Cancellation;
+
+use super::{escape_html, slugify, Markdown, MarkdownConverter};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Minimal Markdown converter, written in Rust.
+///
+/// This is the test implementation of [`MarkdownConverter`], which doesn't
+/// require Python, so the workflow and fixtures can be tested without a
+/// configured Python Markdown stack. It only understands front matter with
+/// plain `key: value` pairs, ATX headings and paragraphs, which is enough to
+/// derive titles, metadata and the table of contents of pages.
+#[derive(Debug, Default)]
+pub struct MinimalMarkdown;
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl MarkdownConverter for MinimalMarkdown {
+    /// Converts Markdown using a minimal subset of Markdown.
+    fn convert(
+        &self, id: &Id, _url: String, content: String,
+        cancellation: &Cancellation,
+    ) -> Result<Markdown> {
+        if cancellation.is_cancelled() {
+            anyhow::bail!(
+                "Skipped '{}', as the build was cancelled",
+                id.location()
+            );
+        }
+
+        // Split front matter from content, if any
+        let (meta, content) = split_front_matter(&content);
+
+        // Convert blocks, i.e., headings and paragraphs separated by blank
+        // lines, and add headings to the table of contents
+        let mut html = Vec::new();
+        let mut toc = Vec::new();
+        let mut paragraph = Vec::new();
+        for line in content.lines().chain([""]) {
+            if let Some((level, text)) = parse_heading(line) {
+                flush(&mut paragraph, &mut html);
+                let id = slugify(text, Slugify::Unicode);
+                let content = escape_html(text);
+                html.push(format!(
+                    "<h{level} id=\"{id}\">{content}</h{level}>"
+                ));
+                append(
+                    &mut toc,
+                    Section {
+                        title: text.to_string(),
+                        content,
+                        url: format!("#{id}"),
+                        id,
+                        children: Vec::new(),
+                        level,
+                    },
+                );
+            } else if line.trim().is_empty() {
+                flush(&mut paragraph, &mut html);
+            } else {
+                paragraph.push(line.trim());
+            }
+        }
+
+        // Return Markdown, leaving the title to be extracted
+        Ok(Markdown {
+            meta,
+            content: html.join("\n"),
+            search: Vec::new(),
+            title: String::new(),
+            title_html: String::new(),
+            toc,
+            tags: Vec::new(),
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Splits front matter with plain `key: value` pairs from the given content.
+fn split_front_matter(content: &str) -> (BTreeMap<String, Dynamic>, &str) {
+    let mut meta = BTreeMap::new();
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return (meta, content);
+    };
+    let Some((front, rest)) = rest
+        .split_once("\n---\n")
+        .or_else(|| rest.strip_suffix("\n---").map(|front| (front, "")))
+    else {
+        return (meta, content);
+    };
+
+    // Parse pairs, stripping quotes from values
+    for line in front.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim().trim_matches(['"', '\'']);
+            meta.insert(
+                key.trim().to_string(),
+                Dynamic::String(value.to_string()),
+            );
+        }
+    }
+    (meta, rest)
+}
+
+/// Parses an ATX heading, returning its level and text.
+fn parse_heading(line: &str) -> Option<(u8, &str)> {
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+    if !(1..=6).contains(&level) || !text.starts_with(' ') {
+        return None;
+    }
+    let text = text.trim().trim_end_matches('#').trim_end();
+    u8::try_from(level).ok().map(|level| (level, text))
+}
+
+/// Appends the given paragraph lines as a paragraph, if any.
+fn flush(paragraph: &mut Vec<&str>, html: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        let text = escape_html(&paragraph.join(" "));
+        html.push(format!("<p>{text}</p>"));
+        paragraph.clear();
+    }
+}
+
+/// Appends the given section to the table of contents, nesting it under the
+/// last section with a lower level, if any.
+fn append(toc: &mut Vec<Section>, section: Section) {
+    match toc.last_mut() {
+        Some(last) if last.level < section.level => {
+            append(&mut last.children, section);
+        }
+        _ => toc.push(section),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    /// Markdown fixture with front matter, headings and paragraphs.
+    const FIXTURE: &str =
+        include_str!("../../../tests/fixtures/minimal/build.md");
+
+    /// Creates an identifier for the given location.
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .expect("invariant")
+    }
+
+    /// Converts the given content with the minimal converter.
+    fn convert(location: &str, content: &str) -> Markdown {
+        let cancellation = Cancellation::default();
+        let url = String::new();
+        let content = content.to_string();
+        Markdown::new(
+            &MinimalMarkdown,
+            &id(location),
+            url,
+            content,
+            &cancellation,
+        )
+        .expect("invariant")
+    }
+
+    /// Compares the output for all fixtures with the expected output.
+    ///
+    /// Each fixture is a Markdown file next to an HTML file with the expected
+    /// output, so fixtures run without Python, using the test implementation.
+    #[test]
+    fn golden_files_match() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/minimal");
+        let mut paths = fs::read_dir(&dir)
+            .expect("invariant")
+            .map(|entry| entry.expect("invariant").path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert!(!paths.is_empty());
+        for path in paths {
+            let name = path.file_name().expect("invariant").to_string_lossy();
+            let content = fs::read_to_string(&path).expect("invariant");
+            let expected = fs::read_to_string(path.with_extension("html"))
+                .expect("invariant");
+            let markdown = convert(&name, &content);
+            assert_eq!(markdown.content, expected.trim_end(), "{name}");
+        }
+    }
+
+    #[test]
+    fn minimal_parses_front_matter() {
+        let markdown = convert("build.md", FIXTURE);
+        assert_eq!(
+            markdown.meta.get("description"),
+            Some(&Dynamic::String("A & B".to_string()))
+        );
+    }
+
+    #[test]
+    fn minimal_nests_table_of_contents() {
+        let markdown = convert("build.md", FIXTURE);
+        let [section] = markdown.toc.as_slice() else {
+            panic!("expected a single top-level section");
+        };
+        assert_eq!(section.url, "#the-build-command");
+        let children = section
+            .children
+            .iter()
+            .map(|child| (child.id.as_str(), child.children.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(children, [("options", 1), ("output", 0)]);
+    }
+
+    #[test]
+    fn minimal_extracts_title() {
+        let markdown = convert("build.md", FIXTURE);
+        assert_eq!(markdown.title, "The <build> command");
+        assert_eq!(markdown.title_html, "The &lt;build&gt; command");
+
+        // Title falls back to the file name without a heading
+        let markdown = convert("getting-started.md", "Text\n");
+        assert_eq!(markdown.title, "Getting started");
+    }
+
+    #[test]
+    fn minimal_skips_when_cancelled() {
+        let cancellation = Cancellation::default();
+        cancellation.cancel();
+        let content = FIXTURE.to_string();
+        let res = MinimalMarkdown.convert(
+            &id("build.md"),
+            String::new(),
+            content,
+            &cancellation,
+        );
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,1048 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Native Markdown rendering.
+//!
+//! This engine is experimental, and renders Markdown with [`pulldown_cmark`]
+//! instead of Python Markdown, which is much faster, and isn't bound to the
+//! global interpreter lock. It covers the core feature set that most docs
+//! need, i.e., front matter, fenced code blocks, tables, admonitions, details,
+//! footnotes, definition lists and task lists, as well as headings with anchors
+//! and a table of contents, and rewrites links to Markdown files like the
+//! Python engine does.
+//!
+//! Output is kept as close as possible to the output of the Python engine, as
+//! the theme relies on its markup, but there are intentional differences:
+//!
+//! - Code blocks are not highlighted, but retain the markup that Pygments would
+//!   emit, including titles, highlighted lines and line numbers, so they're
+//!   styled consistently. When the copy button is enabled for all code blocks,
+//!   it's made explicit by adding the `copy` class to each code block.
+//! - The `default` slugification mode drops all characters that are not ASCII,
+//!   instead of transliterating them, e.g., `é` is dropped and not `e`.
+//! - Admonitions and details must start at the beginning of a line, so they
+//!   can't be nested in lists, only in other admonitions and details.
+//! - Tables and task lists use slightly different markup, e.g., alignment is
+//!   set without trailing semicolons, and task lists use plain checkboxes.
+//! - Repeated references to the same footnote all link to the first one.
+//! - Search sections contain plain text, without any markup.
+//!
+//! Extensions that are not supported by this engine, e.g., most extensions of
+//! `pymdownx`, are reported when the engine is selected, see [`differences`].
+
+use anyhow::Result;
+use pulldown_cmark::{
+    html, CodeBlockKind, CowStr, Event, HeadingLevel, Options, Parser, Tag,
+    TagEnd,
+};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::sync::LazyLock;
+use yaml_rust2::{Yaml, YamlLoader};
+use zrx::id::Id;
+
+use crate::config::markdown::Slugify;
+use crate::config::theme::Feature;
+use crate::config::Config;
+use crate::structure::dynamic::Dynamic;
+use crate::structure::search::SearchItem;
+use crate::structure::tag;
+use crate::structure::toc::Section;
+use crate::workflow::Cancellation;
+
+use super::{
+    escape_html, slugify, to_plain_text, Fence, Markdown, MarkdownConverter,
+};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Extensions that are fully supported by the native engine.
+const SUPPORTED: &[&str] = &[
+    "admonition",
+    "def_list",
+    "footnotes",
+    "tables",
+    "toc",
+    "pymdownx.details",
+];
+
+/// Extensions that are partially supported, and how the output differs.
+const PARTIAL: &[(&str, &str)] = &[
+    (
+        "attr_list",
+        "only identifiers and classes of headings are supported",
+    ),
+    (
+        "md_in_html",
+        "Markdown inside of HTML blocks is not rendered",
+    ),
+    ("pymdownx.highlight", "code blocks are not highlighted"),
+    (
+        "pymdownx.superfences",
+        "only the 'mermaid' custom fence is supported",
+    ),
+    ("pymdownx.tasklist", "task lists use plain checkboxes"),
+    ("pymdownx.tilde", "only strikethrough is supported"),
+];
+
+/// Parser options.
+const OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_DEFINITION_LIST)
+    .union(Options::ENABLE_FOOTNOTES)
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS)
+    .union(Options::ENABLE_HEADING_ATTRIBUTES);
+
+/// Regular expression to extract front matter, same as in the Python engine.
+static FRONT_MATTER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)^-{3}[ \r\t]*?\n(.*?\r?\n)(?:\.{3}|-{3})[ \r\t]*\n")
+        .expect("invariant")
+});
+
+/// Regular expression to detect admonitions and details.
+static ADMONITION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(!!!|\?\?\?\+?) ?([\w-]+(?: +[\w-]+)*)(?: +"(.*?)")? *$"#)
+        .expect("invariant")
+});
+
+/// Regular expression to remove links from table of contents labels.
+static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<a\s+[^>]+>(.*?)</a>").expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Markdown converter using the native engine.
+#[derive(Debug)]
+pub struct NativeMarkdown {
+    /// Slugification mode, if configured.
+    slugify: Option<Slugify>,
+    /// Whether to use directory URLs.
+    use_directory_urls: bool,
+    /// Whether the copy button is enabled for all code blocks.
+    copy: bool,
+}
+
+/// Renderer for a single page.
+struct Renderer<'a> {
+    /// Converter.
+    native: &'a NativeMarkdown,
+    /// Location of the page.
+    location: &'a str,
+    /// Table of contents.
+    toc: Vec<Section>,
+    /// Search sections.
+    search: Vec<SearchItem>,
+    /// Identifiers used for headings.
+    ids: BTreeSet<String>,
+    /// Number of rendered code blocks.
+    blocks: usize,
+    /// Footnote labels in order of definition.
+    labels: Vec<String>,
+    /// Rendered footnote definitions.
+    footnotes: BTreeMap<String, String>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl NativeMarkdown {
+    /// Creates a native Markdown converter for the given configuration.
+    pub fn new(config: &Config) -> Self {
+        let features = &config.project.theme.features;
+        Self {
+            slugify: config.project.markdown.slugify,
+            use_directory_urls: config.project.use_directory_urls,
+            copy: features.contains(Feature::ContentCodeCopy),
+        }
+    }
+
+    /// Returns the slug for the given value, without ensuring uniqueness.
+    fn slug(&self, value: &str) -> String {
+        slugify(value, self.slugify.unwrap_or_default())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl<'a> Renderer<'a> {
+    /// Creates a renderer for the page at the given location.
+    fn new(native: &'a NativeMarkdown, location: &'a str) -> Self {
+        Self {
+            native,
+            location,
+            toc: Vec::new(),
+            search: vec![SearchItem {
+                location: None,
+                level: 0,
+                title: String::new(),
+                text: String::new(),
+                path: Vec::new(),
+                tags: Vec::new(),
+            }],
+            ids: BTreeSet::new(),
+            blocks: 0,
+            labels: Vec::new(),
+            footnotes: BTreeMap::new(),
+        }
+    }
+
+    /// Renders the given Markdown into HTML.
+    fn render(&mut self, content: &str) -> String {
+        // Collect footnote labels first, as footnotes are numbered in order of
+        // their definition, which might come after their first reference
+        let iter = Parser::new_ext(content, OPTIONS);
+        self.labels = iter
+            .filter_map(|event| match event {
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    Some(label.to_string())
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Transform and render events, followed by footnotes, if any
+        let mut iter = Parser::new_ext(content, OPTIONS);
+        let events = self.transform(&mut iter);
+        let mut output = String::with_capacity(content.len() * 3 / 2);
+        html::push_html(&mut output, events.into_iter());
+        if !self.footnotes.is_empty() {
+            output.push_str("<div class=\"footnote\">\n<hr />\n<ol>\n");
+            for (n, label) in self.labels.iter().enumerate() {
+                if let Some(html) = self.footnotes.get(label) {
+                    output.push_str(&footnote(label, n + 1, html));
+                }
+            }
+            output.push_str("</ol>\n</div>\n");
+        }
+
+        // Collapse whitespace in search sections, and drop the section before
+        // the first heading, if it doesn't contain any text
+        for item in &mut self.search {
+            item.text =
+                item.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        if self.search[0].text.is_empty() {
+            self.search.remove(0);
+        }
+        output
+    }
+
+    /// Transforms the given events into events that can be rendered.
+    fn transform<'e, I>(&mut self, iter: &mut I) -> Vec<Event<'e>>
+    where
+        I: Iterator<Item = Event<'e>>,
+    {
+        let mut events = Vec::new();
+        while let Some(event) = iter.next() {
+            match event {
+                // Render headings with anchors, and add them to the table of
+                // contents, as well as use them to start a search section
+                Event::Start(Tag::Heading { level, id, classes, .. }) => {
+                    let inner = take_until(iter, |end| {
+                        matches!(end, TagEnd::Heading(_))
+                    });
+                    let html = self.heading(level, id, &classes, inner);
+                    events.push(Event::Html(html.into()));
+                }
+
+                // Render code blocks like Pygments, but without highlighting
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let inner = take_until(iter, |end| {
+                        matches!(end, TagEnd::CodeBlock)
+                    });
+                    let mut code = String::new();
+                    for event in inner {
+                        if let Event::Text(text) = event {
+                            code.push_str(&text);
+                        }
+                    }
+                    self.text(&code);
+                    let html = self.code_block(&kind, &code);
+                    events.push(Event::Html(html.into()));
+                }
+
+                // Render footnote definitions separately, as they're appended
+                // to the end of the page, like in the Python engine
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    let inner = take_until(iter, |end| {
+                        matches!(end, TagEnd::FootnoteDefinition)
+                    });
+                    let inner = self.transform(&mut inner.into_iter());
+                    let mut html = String::new();
+                    html::push_html(&mut html, inner.into_iter());
+                    self.footnotes.insert(label.to_string(), html);
+                }
+
+                // Render footnote references with the markup of Python Markdown
+                Event::FootnoteReference(label) => {
+                    let n = self.labels.iter().position(|l| *l == *label);
+                    let html = match n {
+                        Some(n) => format!(
+                            "<sup id=\"fnref:{label}\"><a \
+                             class=\"footnote-ref\" href=\"#fn:{label}\">{}</a>\
+                             </sup>",
+                            n + 1
+                        ),
+                        None => format!("[^{label}]"),
+                    };
+                    events.push(Event::InlineHtml(html.into()));
+                }
+
+                // Rewrite links and images to Markdown files and other assets
+                Event::Start(Tag::Link { link_type, dest_url, title, id }) => {
+                    let dest_url = self.rewrite_url(dest_url);
+                    events.push(Event::Start(Tag::Link {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    }));
+                }
+                Event::Start(Tag::Image { link_type, dest_url, title, id }) => {
+                    let dest_url = self.rewrite_url(dest_url);
+                    events.push(Event::Start(Tag::Image {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    }));
+                }
+
+                // Collect text for search, separating blocks with whitespace
+                Event::Text(ref text) | Event::Code(ref text) => {
+                    self.text(text);
+                    events.push(event);
+                }
+                Event::End(
+                    TagEnd::Emphasis
+                    | TagEnd::Strong
+                    | TagEnd::Strikethrough
+                    | TagEnd::Link
+                    | TagEnd::Image,
+                ) => events.push(event),
+                Event::SoftBreak | Event::HardBreak | Event::End(_) => {
+                    self.text(" ");
+                    events.push(event);
+                }
+                _ => events.push(event),
+            }
+        }
+        events
+    }
+
+    /// Renders the heading with the given events, and records it.
+    fn heading(
+        &mut self, level: HeadingLevel, id: Option<CowStr>, classes: &[CowStr],
+        inner: Vec<Event>,
+    ) -> String {
+        // Render content, but don't add its text to the current section, as
+        // the heading starts a new one, and is used as its title
+        let len = self.search.last().map_or(0, |item| item.text.len());
+        let inner = self.transform(&mut inner.into_iter());
+        if let Some(item) = self.search.last_mut() {
+            item.text.truncate(len);
+        }
+        let mut content = String::new();
+        html::push_html(&mut content, inner.into_iter());
+        let title = to_plain_text(&content);
+
+        // Use explicit identifier, if given, or derive a unique one
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => self.unique(self.native.slug(&title)),
+        };
+        self.ids.insert(id.clone());
+
+        // Start search section, and add heading to table of contents
+        let level = level as u8;
+        self.search.push(SearchItem {
+            location: Some(id.clone()),
+            level: u32::from(level),
+            title: title.clone(),
+            text: String::new(),
+            path: Vec::new(),
+            tags: Vec::new(),
+        });
+        append(
+            &mut self.toc,
+            Section {
+                title,
+                content: LINK_RE.replace_all(&content, "$1").into_owned(),
+                id: id.clone(),
+                url: format!("#{id}"),
+                children: Vec::new(),
+                level,
+            },
+        );
+
+        // Render heading with permalink
+        let class = if classes.is_empty() {
+            String::new()
+        } else {
+            format!(" class=\"{}\"", escape_html(&classes.join(" ")))
+        };
+        let id = escape_html(&id);
+        format!(
+            "<h{level} id=\"{id}\"{class}>{content}<a class=\"headerlink\" \
+             href=\"#{id}\" title=\"Permanent link\">&para;</a></h{level}>\n"
+        )
+    }
+
+    /// Renders the given code block with the markup of Pygments.
+    ///
+    /// Options of the fence are rendered like Python Markdown does with the
+    /// configuration of the theme, i.e., the title as the file name, as well
+    /// as highlighted lines and line numbers, with anchors for each line.
+    fn code_block(&mut self, kind: &CodeBlockKind, code: &str) -> String {
+        let fence = match kind {
+            CodeBlockKind::Fenced(info) => Fence::parse(info),
+            CodeBlockKind::Indented => Fence::default(),
+        };
+        if fence.lang.as_deref() == Some("mermaid") {
+            let code = escape_html(code);
+            return format!(
+                "<pre class=\"mermaid\"><code>{code}</code></pre>\n"
+            );
+        }
+
+        // Add class for copy button, if it's enabled for all code blocks, and
+        // the code block doesn't explicitly opt in or out, so it's explicit
+        let mut classes = fence
+            .lang
+            .iter()
+            .map(|lang| format!("language-{lang}"))
+            .chain(["highlight".to_string()])
+            .chain(fence.classes.iter().cloned())
+            .collect::<Vec<_>>();
+        let explicit =
+            fence.classes.iter().any(|c| c == "copy" || c == "no-copy");
+        if self.native.copy && !explicit {
+            classes.push("copy".to_string());
+        }
+
+        // Render identifier and attributes after classes
+        let mut attrs =
+            format!(" class=\"{}\"", escape_html(&classes.join(" ")));
+        if let Some(id) = &fence.id {
+            write!(attrs, " id=\"{}\"", escape_html(id)).expect("invariant");
+        }
+        for (key, value) in &fence.attrs {
+            write!(attrs, " {key}=\"{}\"", escape_html(value))
+                .expect("invariant");
+        }
+
+        // Render each line wrapped in a span and with an anchor, and highlight
+        // it, if requested, which is the markup the theme relies on
+        let block = self.blocks;
+        self.blocks += 1;
+        let start = fence.linenums.map_or(1, |linenums| linenums.start);
+        let mut lines = String::with_capacity(code.len() * 2);
+        let mut count = 0;
+        for (n, line) in code.lines().enumerate() {
+            let anchor = format!("__codelineno-{block}-{}", start + n);
+            let line = escape_html(line);
+            write!(
+                lines,
+                "<span id=\"__span-{block}-{}\"><a id=\"{anchor}\" \
+                 name=\"{anchor}\" href=\"#{anchor}\"></a>",
+                n + 1
+            )
+            .expect("invariant");
+            if fence.hl_lines.contains(&(n + 1)) {
+                write!(lines, "<span class=\"hll\">{line}\n</span></span>")
+            } else {
+                write!(lines, "{line}\n</span>")
+            }
+            .expect("invariant");
+            count += 1;
+        }
+
+        // Render line numbers in a separate column, if enabled, only showing
+        // numbers at the given interval, which link to the anchors
+        let code = format!("<pre><span></span><code>{lines}</code></pre>");
+        let code = match fence.linenums {
+            None => code,
+            Some(linenums) => {
+                let numbers = (start..start + count)
+                    .map(|number| {
+                        if number % linenums.step == 0 {
+                            format!(
+                                "<span class=\"normal\"><a \
+                                 href=\"#__codelineno-{block}-{number}\">\
+                                 {number}</a></span>"
+                            )
+                        } else {
+                            "<span class=\"normal\"></span>".to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!(
+                    "<table class=\"highlighttable\"><tr><td class=\"linenos\">\
+                     <div class=\"linenodiv\"><pre><span></span>{numbers}</pre>\
+                     </div></td><td class=\"code\"><div>{code}</div></td></tr>\
+                     </table>"
+                )
+            }
+        };
+
+        // Render title as file name, if any
+        let title = fence.title.as_deref().map_or_else(String::new, |title| {
+            format!("<span class=\"filename\">{}</span>", escape_html(title))
+        });
+        format!("<div{attrs}>{title}{code}</div>\n")
+    }
+
+    /// Returns a unique identifier for the given slug.
+    ///
+    /// Python Markdown suffixes duplicates with `_1`, `_2`, etc., while the
+    /// configured slugification modes use the separator, i.e., `-1`, `-2`.
+    fn unique(&self, slug: String) -> String {
+        let separator = if self.native.slugify.is_some() {
+            '-'
+        } else {
+            '_'
+        };
+        if !slug.is_empty() && !self.ids.contains(&slug) {
+            return slug;
+        }
+
+        // Append counter to slug until it's unique
+        let mut count = 1;
+        let mut candidate = format!("{slug}{separator}{count}");
+        while self.ids.contains(&candidate) {
+            count += 1;
+            candidate = format!("{slug}{separator}{count}");
+        }
+        candidate
+    }
+
+    /// Adds the given text to the current search section.
+    fn text(&mut self, text: &str) {
+        if let Some(item) = self.search.last_mut() {
+            item.text.push_str(text);
+        }
+    }
+
+    /// Rewrites the given relative URL, like the Python engine.
+    ///
+    /// Links to Markdown files are rewritten to the URLs of the pages, and
+    /// when directory URLs are used, relative URLs are prefixed with `../`
+    /// for all pages that are not index pages, as they become directories.
+    fn rewrite_url<'e>(&self, url: CowStr<'e>) -> CowStr<'e> {
+        let n = url.find(['?', '#']).unwrap_or(url.len());
+        let (path, rest) = url.split_at(n);
+        if path.is_empty()
+            || path.starts_with('/')
+            || path
+                .split('/')
+                .next()
+                .is_some_and(|part| part.contains(':'))
+        {
+            return url;
+        }
+
+        // Convert path to Markdown file to URL of the page
+        let use_directory_urls = self.native.use_directory_urls;
+        let mut value = match path.strip_suffix(".md") {
+            None => path.to_string(),
+            Some(stem) => {
+                let (dir, name) = stem.rsplit_once('/').unwrap_or(("", stem));
+                let dir = if dir.is_empty() {
+                    String::new()
+                } else {
+                    format!("{dir}/")
+                };
+                match (use_directory_urls, name) {
+                    (true, "index" | "README") => dir,
+                    (true, _) => format!("{dir}{name}/"),
+                    (false, "README") => format!("{dir}index.html"),
+                    (false, _) => format!("{dir}{name}.html"),
+                }
+            }
+        };
+
+        // Prefix URL for pages that are not index pages
+        let name = self.location.rsplit('/').next().unwrap_or_default();
+        if use_directory_urls && name != "index.md" && name != "README.md" {
+            value.insert_str(0, "../");
+        }
+        format!("{value}{rest}").into()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl MarkdownConverter for NativeMarkdown {
+    /// Converts Markdown using the native engine.
+    fn convert(
+        &self, id: &Id, _url: String, content: String,
+        cancellation: &Cancellation,
+    ) -> Result<Markdown> {
+        if cancellation.is_cancelled() {
+            anyhow::bail!(
+                "Skipped '{}', as the build was cancelled",
+                id.location()
+            );
+        }
+
+        // Extract front matter, and convert admonitions and details into HTML,
+        // which wraps their content, so it's still rendered as Markdown
+        let (meta, content) = extract_front_matter(&content);
+        let content = preprocess(content);
+
+        // Render Markdown, and obtain search data, unless page is excluded
+        let location = id.location();
+        let mut renderer = Renderer::new(self, &location);
+        let content = renderer.render(&content);
+        let exclude = meta
+            .get("search")
+            .and_then(|search| match search {
+                Dynamic::Map(map) => map.get("exclude"),
+                _ => None,
+            })
+            .is_some_and(|exclude| *exclude == Dynamic::Bool(true));
+        if exclude {
+            renderer.search.clear();
+        }
+
+        // Convert tags, slugified like headings
+        let tags = match meta.get("tags") {
+            Some(Dynamic::List(tags)) => tags
+                .iter()
+                .map(|tag| {
+                    let name = tag.to_string();
+                    let slug = self.slug(&name);
+                    tag::Tag { name, slug }
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        // Return Markdown, leaving the title to be extracted
+        Ok(Markdown {
+            meta,
+            content,
+            search: renderer.search,
+            title: String::new(),
+            title_html: String::new(),
+            toc: renderer.toc,
+            tags,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the differences of the native engine for the given extensions.
+///
+/// The first list contains all extensions that are not supported at all, and
+/// the second list contains all extensions that are partially supported, each
+/// with a note on how the output differs from the Python engine.
+pub fn differences(
+    extensions: &[String],
+) -> (Vec<&str>, Vec<(&str, &'static str)>) {
+    let mut unsupported = Vec::new();
+    let mut partial = Vec::new();
+    for name in extensions {
+        if let Some((_, note)) = PARTIAL.iter().find(|(ext, _)| ext == name) {
+            partial.push((name.as_str(), *note));
+        } else if !SUPPORTED.contains(&name.as_str()) {
+            unsupported.push(name.as_str());
+        }
+    }
+
+    // Sort for deterministic output
+    unsupported.sort_unstable();
+    partial.sort_unstable();
+    (unsupported, partial)
+}
+
+/// Extracts front matter from the given content, if any.
+///
+/// Front matter that is not a valid YAML mapping is left as part of the page,
+/// and null values are represented as empty strings, like in the Python engine.
+fn extract_front_matter(content: &str) -> (BTreeMap<String, Dynamic>, &str) {
+    let Some(captures) = FRONT_MATTER_RE.captures(content) else {
+        return (BTreeMap::new(), content);
+    };
+    let meta = YamlLoader::load_from_str(&captures[1])
+        .ok()
+        .and_then(|docs| docs.into_iter().next())
+        .and_then(|yaml| match to_json(yaml) {
+            value @ serde_json::Value::Object(_) => {
+                BTreeMap::deserialize(value).ok()
+            }
+            _ => None,
+        });
+
+    // Return metadata and remaining content
+    match meta {
+        Some(meta) => {
+            let rest = &content[captures[0].len()..];
+            (meta, rest.trim_start_matches('\n'))
+        }
+        None => (BTreeMap::new(), content),
+    }
+}
+
+/// Converts the given YAML value into a JSON value.
+fn to_json(yaml: Yaml) -> serde_json::Value {
+    match yaml {
+        Yaml::Boolean(value) => value.into(),
+        Yaml::Integer(value) => value.into(),
+        Yaml::Real(value) => value
+            .parse::<f64>()
+            .map_or_else(|_| value.into(), Into::into),
+        Yaml::String(value) => value.into(),
+        Yaml::Array(values) => values.into_iter().map(to_json).collect(),
+        Yaml::Hash(hash) => hash
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let key = match key {
+                    Yaml::String(key) | Yaml::Real(key) => key,
+                    Yaml::Integer(key) => key.to_string(),
+                    Yaml::Boolean(key) => key.to_string(),
+                    _ => return None,
+                };
+                Some((key, to_json(value)))
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        _ => String::new().into(),
+    }
+}
+
+/// Converts admonitions and details into HTML wrapping their content.
+///
+/// The content of admonitions and details is indented by four spaces, which
+/// is removed, and the content is processed recursively, so they can be
+/// nested. Blank lines around the content ensure that it's rendered as
+/// Markdown, as HTML blocks end at blank lines. Fenced code is left as is.
+fn preprocess(content: &str) -> String {
+    let lines = content.lines().collect::<Vec<_>>();
+    let mut output = String::with_capacity(content.len());
+    let mut fence = None;
+    let mut n = 0;
+    while n < lines.len() {
+        let line = lines[n];
+        n += 1;
+
+        // Track fenced code, in which nothing is converted
+        if let Some((marker, count)) = fence_marker(line) {
+            match fence {
+                None => fence = Some((marker, count)),
+                Some((open, min)) if open == marker && count >= min => {
+                    fence = None;
+                }
+                _ => {}
+            }
+        }
+        let captures = fence.is_none().then(|| ADMONITION_RE.captures(line));
+        let Some(Some(captures)) = captures else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        // Collect content, which is indented, including blank lines, but not
+        // trailing blank lines, as they might separate the next block
+        let start = n;
+        while n < lines.len()
+            && (lines[n].trim().is_empty()
+                || lines[n].starts_with("    ")
+                || lines[n].starts_with('\t'))
+        {
+            n += 1;
+        }
+        while n > start && lines[n - 1].trim().is_empty() {
+            n -= 1;
+        }
+        let inner = lines[start..n]
+            .iter()
+            .map(|line| {
+                line.strip_prefix("    ")
+                    .or_else(|| line.strip_prefix('\t'))
+                    .unwrap_or(line.trim_start())
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Determine classes and title, which defaults to the capitalized type,
+        // and is omitted if explicitly empty
+        let classes = escape_html(&captures[2]);
+        let kind = classes.split(' ').next().unwrap_or_default();
+        let title = match captures.get(3) {
+            Some(title) => escape_html(title.as_str()),
+            None => capitalize(kind),
+        };
+
+        // Render admonition or details
+        let (open, close) = match &captures[1] {
+            "!!!" => {
+                let title = if title.is_empty() {
+                    String::new()
+                } else {
+                    format!("<p class=\"admonition-title\">{title}</p>\n")
+                };
+                let open =
+                    format!("<div class=\"admonition {classes}\">\n{title}");
+                (open, "</div>")
+            }
+            marker => {
+                let attr = if marker == "???+" { " open" } else { "" };
+                let open = format!(
+                    "<details class=\"{classes}\"{attr}>\n\
+                     <summary>{title}</summary>\n"
+                );
+                (open, "</details>")
+            }
+        };
+        output.push_str(&open);
+        output.push('\n');
+        output.push_str(&preprocess(&inner));
+        output.push('\n');
+        output.push_str(close);
+        output.push_str("\n\n");
+    }
+    output
+}
+
+/// Returns the marker character and length, if the line starts a code fence.
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let count = trimmed.chars().take_while(|c| *c == marker).count();
+    (count >= 3).then_some((marker, count))
+}
+
+/// Takes events until the end tag matching the given predicate.
+fn take_until<'e, I, F>(iter: &mut I, f: F) -> Vec<Event<'e>>
+where
+    I: Iterator<Item = Event<'e>>,
+    F: Fn(&TagEnd) -> bool,
+{
+    let mut events = Vec::new();
+    for event in iter.by_ref() {
+        if matches!(&event, Event::End(end) if f(end)) {
+            break;
+        }
+        events.push(event);
+    }
+    events
+}
+
+/// Renders the footnote with the given label, number and content.
+fn footnote(label: &str, n: usize, html: &str) -> String {
+    let backref = format!(
+        "&#160;<a class=\"footnote-backref\" href=\"#fnref:{label}\" \
+         title=\"Jump back to footnote {n} in the text\">&#8617;</a>"
+    );
+
+    // Place backreference in the last paragraph, if any
+    let html = html.trim_end();
+    let html = match html.strip_suffix("</p>") {
+        Some(html) => format!("{html}{backref}</p>"),
+        None => format!("{html}{backref}"),
+    };
+    format!("<li id=\"fn:{label}\">\n{html}\n</li>\n")
+}
+
+/// Appends the given section to the table of contents, nesting it under the
+/// last section with a lower level, if any.
+fn append(toc: &mut Vec<Section>, section: Section) {
+    match toc.last_mut() {
+        Some(last) if last.level < section.level => {
+            append(&mut last.children, section);
+        }
+        _ => toc.push(section),
+    }
+}
+
+/// Returns the given value with its first character in uppercase.
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect()
+    })
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    /// Creates an identifier for the given location.
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .expect("invariant")
+    }
+
+    /// Converts the given content with the given slugification mode.
+    fn convert(slugify: Option<Slugify>, content: &str) -> Markdown {
+        let native = NativeMarkdown {
+            slugify,
+            use_directory_urls: true,
+            copy: false,
+        };
+        let cancellation = Cancellation::default();
+        native
+            .convert(
+                &id("guide/page.md"),
+                String::new(),
+                content.to_string(),
+                &cancellation,
+            )
+            .expect("invariant")
+    }
+
+    /// Compares the output for all fixtures with the expected output.
+    ///
+    /// Each fixture is a Markdown file next to an HTML file with the expected
+    /// output, rendered as a page in a subdirectory with directory URLs, and
+    /// the copy button enabled for fixtures starting with `copy`. The expected
+    /// output documents the intentional differences to the Python engine,
+    /// which are listed in the module documentation.
+    #[test]
+    fn golden_files_match() {
+        let dir =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/native");
+        let mut paths = fs::read_dir(&dir)
+            .expect("invariant")
+            .map(|entry| entry.expect("invariant").path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert!(!paths.is_empty());
+        for path in paths {
+            let name = path.file_name().expect("invariant").to_string_lossy();
+            let content = fs::read_to_string(&path).expect("invariant");
+            let expected = fs::read_to_string(path.with_extension("html"))
+                .expect("invariant");
+            let native = NativeMarkdown {
+                slugify: None,
+                use_directory_urls: true,
+                copy: name.starts_with("copy"),
+            };
+            let markdown = native
+                .convert(
+                    &id(&format!("guide/{name}")),
+                    String::new(),
+                    content,
+                    &Cancellation::default(),
+                )
+                .expect("invariant");
+            assert_eq!(markdown.content, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn headings_are_nested_in_toc() {
+        let markdown = convert(None, "# A\n\n## B\n\n### C\n\n## D\n\n# E\n");
+        let ids = |toc: &[Section]| {
+            toc.iter()
+                .map(|section| section.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&markdown.toc), ["a", "e"]);
+        assert_eq!(ids(&markdown.toc[0].children), ["b", "d"]);
+        assert_eq!(ids(&markdown.toc[0].children[0].children), ["c"]);
+    }
+
+    #[test]
+    fn headings_start_search_sections() {
+        let markdown = convert(None, "Intro\n\n# A\n\nText of *A*.\n\n## B\n");
+        let sections = markdown
+            .search
+            .iter()
+            .map(|item| (item.location.as_deref(), item.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            [(None, "Intro"), (Some("a"), "Text of A."), (Some("b"), "")]
+        );
+    }
+
+    #[test]
+    fn search_is_excluded_via_front_matter() {
+        let markdown =
+            convert(None, "---\nsearch:\n  exclude: true\n---\n# A\n");
+        assert!(markdown.search.is_empty());
+    }
+
+    #[test]
+    fn duplicate_headings_are_suffixed() {
+        let content = "# Setup\n\n# Setup\n";
+        let markdown = convert(None, content);
+        assert_eq!(markdown.toc[1].id, "setup_1");
+        let markdown = convert(Some(Slugify::Github), content);
+        assert_eq!(markdown.toc[1].id, "setup-1");
+    }
+
+    #[test]
+    fn invalid_front_matter_is_retained() {
+        let markdown = convert(None, "---\n- a\n- b\n---\n");
+        assert!(markdown.meta.is_empty());
+        assert!(markdown.content.starts_with("<hr />"));
+    }
+
+    #[test]
+    fn differences_lists_extensions() {
+        let extensions =
+            ["toc", "pymdownx.emoji", "attr_list", "abbr"].map(String::from);
+        let (unsupported, partial) = differences(&extensions);
+        assert_eq!(unsupported, ["abbr", "pymdownx.emoji"]);
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].0, "attr_list");
+    }
+}
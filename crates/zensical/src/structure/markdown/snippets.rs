@@ -0,0 +1,256 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Snippets.
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match single-line snippets.
+static LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^[ \t]*(;*)-+8<-+[ \t]+",
+        r#"("(?:\\"|[^"\r\n])+?"|'(?:\\'|[^'\r\n])+?')[ \t]*$"#,
+    ))
+    .expect("invariant")
+});
+
+/// Regular expression to match the start or end of block snippets.
+static BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[ \t]*(;*)-+8<-+[ \t]*$").expect("invariant")
+});
+
+/// Regular expression to strip line and section selectors from file names.
+static FILE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(.*?)(?:(?::[0-9]*){1,2}|:[a-z][-_0-9a-z]*)?$")
+        .expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Resolved snippets.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snippets {
+    /// Included files, including nested includes.
+    pub files: BTreeSet<PathBuf>,
+    /// Snippets that could not be found.
+    pub missing: Vec<String>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Snippets {
+    /// Resolves all snippets in the given Markdown against the base paths.
+    ///
+    /// Included files are read to resolve nested snippets, so the resulting set
+    /// contains all files the Markdown depends on, directly or transitively.
+    /// Files are only visited once, which also guards against cycles.
+    pub fn resolve(base_path: &[PathBuf], content: &str) -> Self {
+        let mut snippets = Self::default();
+        snippets.visit(base_path, content);
+        snippets
+    }
+
+    /// Resolves snippets in the given content, recursing into included files.
+    fn visit(&mut self, base_path: &[PathBuf], content: &str) {
+        for name in extract(content) {
+            let Some(path) = find(base_path, &name) else {
+                self.missing.push(name);
+                continue;
+            };
+
+            // Only visit files that were not visited before
+            if self.files.insert(path.clone()) {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    self.visit(base_path, &content);
+                }
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Extracts the names of all included files from the given Markdown.
+///
+/// This implements the syntax of `pymdownx.snippets`, which supports single-
+/// line snippets with quoted file names, as well as blocks listing one file
+/// per line. Snippets that are escaped with `;` and URLs are skipped, and line
+/// and section selectors are stripped from file names.
+pub fn extract(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut block = None;
+    for line in content.lines() {
+        if let Some(captures) = BLOCK_RE.captures(line) {
+            let escaped = !captures[1].is_empty();
+            block = match block {
+                Some(_) => None,
+                None => Some(escaped),
+            };
+            continue;
+        }
+
+        // Inside blocks, each line that is not empty or escaped is a file
+        // name, otherwise check for a single-line snippet
+        let name = match block {
+            Some(escaped) => {
+                let name = line.trim();
+                if escaped || name.is_empty() || name.starts_with(';') {
+                    continue;
+                }
+                name
+            }
+            None => {
+                let Some(captures) = LINE_RE.captures(line) else {
+                    continue;
+                };
+                if !captures[1].is_empty() {
+                    continue;
+                }
+                let quoted = captures.get(2).expect("invariant").as_str();
+                &quoted[1..quoted.len() - 1]
+            }
+        };
+
+        // Skip URLs, as they can't be tracked
+        if name.starts_with("http://") || name.starts_with("https://") {
+            continue;
+        }
+
+        // Strip line and section selectors
+        let name = FILE_RE
+            .captures(name)
+            .and_then(|captures| captures.get(1))
+            .map_or(name, |name| name.as_str());
+        names.push(name.to_string());
+    }
+    names
+}
+
+/// Finds the given file in the base paths, returning the first match.
+fn find(base_path: &[PathBuf], name: &str) -> Option<PathBuf> {
+    base_path.iter().find_map(|base| {
+        let path = base.join(Path::new(name));
+        path.is_file()
+            .then(|| fs::canonicalize(&path).unwrap_or(path))
+    })
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use super::{extract, Snippets};
+
+    #[test]
+    fn extract_handles_lines_and_blocks() {
+        let content = concat!(
+            "# Title\n",
+            "--8<-- \"includes/warning.md\"\n",
+            "  --8<-- 'includes/indented.md:2:4'\n",
+            ";--8<-- \"includes/escaped.md\"\n",
+            "--8<--\n",
+            "includes/a.md:section\n",
+            "\n",
+            ";includes/skipped.md\n",
+            "https://example.com/remote.md\n",
+            "--8<--\n",
+            ";--8<--\n",
+            "includes/escaped.md\n",
+            ";--8<--\n",
+        );
+        assert_eq!(
+            extract(content),
+            vec![
+                "includes/warning.md",
+                "includes/indented.md",
+                "includes/a.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_follows_nested_includes() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        fs::write(base.join("a.md"), "--8<-- \"b.md\"\n").unwrap();
+        fs::write(base.join("b.md"), "--8<-- \"c.md\"\n").unwrap();
+        fs::write(base.join("c.md"), "--8<-- \"a.md\"\n").unwrap();
+
+        // Transitive includes are resolved, and cycles terminate
+        let snippets = Snippets::resolve(&[base.clone()], "--8<-- \"a.md\"");
+        assert_eq!(
+            snippets.files.into_iter().collect::<Vec<_>>(),
+            vec![base.join("a.md"), base.join("b.md"), base.join("c.md")]
+        );
+        assert!(snippets.missing.is_empty());
+    }
+
+    #[test]
+    fn resolve_reports_missing_files() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        fs::write(base.join("a.md"), "--8<-- \"missing.md\"\n").unwrap();
+
+        // Missing files are reported, even if nested
+        let snippets = Snippets::resolve(&[base.clone()], "--8<-- \"a.md\"");
+        assert_eq!(snippets.files.len(), 1);
+        assert_eq!(snippets.missing, vec!["missing.md"]);
+    }
+
+    #[test]
+    fn resolve_searches_base_paths_in_order() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        fs::create_dir_all(base.join("x")).unwrap();
+        fs::create_dir_all(base.join("y")).unwrap();
+        fs::write(base.join("x/a.md"), "").unwrap();
+        fs::write(base.join("y/a.md"), "").unwrap();
+
+        // First base path containing the file wins
+        let base_path = [base.join("y"), base.join("x")];
+        let snippets = Snippets::resolve(&base_path, "--8<-- \"a.md\"");
+        assert!(snippets.files.contains(&base.join("y/a.md")));
+        assert_eq!(snippets.files.len(), 1);
+    }
+}
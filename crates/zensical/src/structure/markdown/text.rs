@@ -0,0 +1,251 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Plain text conversion.
+
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+
+use crate::config::markdown::Slugify;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to extract the alternative text of images.
+static IMG_ALT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^<img\b[^>]*?\balt\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .expect("invariant")
+});
+
+/// Regular expression to detect character references.
+static ENTITY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").expect("invariant")
+});
+
+/// Regular expression to match characters removed by the default modes.
+static NON_WORD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[^\w\s-]").expect("invariant"));
+
+/// Regular expression to match characters removed by the `github` mode.
+static NON_WORD_GITHUB_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[^\w\- ]").expect("invariant"));
+
+/// Regular expression to match runs of separators and whitespace.
+static SEPARATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[-\s]+").expect("invariant"));
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Converts the given HTML into plain text.
+///
+/// All tags are removed, except for images, which are replaced with their
+/// alternative text, so emojis rendered as images are retained. Character
+/// references are decoded, and whitespace is collapsed, so the result can
+/// be used anywhere markup can't be rendered, e.g., in `<title>` tags.
+pub fn to_plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // Remove tag, but retain the alternative text of images
+        let end = find_tag_end(rest).unwrap_or(rest.len());
+        if let Some(captures) = IMG_ALT_RE.captures(&rest[..end]) {
+            let alt = captures.get(1).or_else(|| captures.get(2));
+            text.push_str(alt.map_or("", |alt| alt.as_str()));
+        }
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+
+    // Decode character references and collapse whitespace
+    let text = decode_entities(&text);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Escapes HTML special characters in the given text.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Returns the slug for the given value in the given mode.
+///
+/// This mirrors the slugification modes of the Python engine, except for the
+/// `default` mode, which drops characters instead of transliterating them.
+pub fn slugify(value: &str, mode: Slugify) -> String {
+    match mode {
+        Slugify::Github => {
+            let value = value.trim().to_lowercase();
+            NON_WORD_GITHUB_RE.replace_all(&value, "").replace(' ', "-")
+        }
+        Slugify::Default | Slugify::Unicode => {
+            let value = if mode == Slugify::Default {
+                value.chars().filter(char::is_ascii).collect()
+            } else {
+                value.to_string()
+            };
+            let value = NON_WORD_RE.replace_all(&value, "");
+            let value = value.trim().to_lowercase();
+            SEPARATOR_RE.replace_all(&value, "-").into_owned()
+        }
+    }
+}
+
+/// Returns the index after the end of the tag at the start of the given HTML.
+///
+/// Quoted attribute values may contain `>`, which is why we need to skip them.
+fn find_tag_end(html: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, char) in html.char_indices() {
+        match (quote, char) {
+            (None, '"' | '\'') => quote = Some(char),
+            (Some(open), _) if open == char => quote = None,
+            (None, '>') => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Decodes character references in the given text.
+///
+/// Besides numeric references, only the named references that Python Markdown
+/// and its extensions commonly emit are decoded, all others are retained.
+fn decode_entities(text: &str) -> String {
+    let decoded = ENTITY_RE.replace_all(text, |captures: &Captures| {
+        let name = &captures[1];
+        let hex = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X"));
+        let char = if let Some(hex) = hex {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = name.strip_prefix('#') {
+            dec.parse().ok().and_then(char::from_u32)
+        } else {
+            decode_named(name)
+        };
+        char.map_or_else(|| captures[0].to_string(), String::from)
+    });
+    decoded.into_owned()
+}
+
+/// Decodes the named character reference with the given name.
+fn decode_named(name: &str) -> Option<char> {
+    let char = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201c}',
+        "rdquo" => '\u{201d}',
+        "laquo" => '\u{ab}',
+        "raquo" => '\u{bb}',
+        "copy" => '\u{a9}',
+        "reg" => '\u{ae}',
+        "trade" => '\u{2122}',
+        _ => return None,
+    };
+    Some(char)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_code_spans() {
+        assert_eq!(
+            to_plain_text("The <code>build</code> command"),
+            "The build command"
+        );
+        assert_eq!(to_plain_text("<code>a &lt; b</code>"), "a < b");
+    }
+
+    #[test]
+    fn strips_emphasis_and_links() {
+        assert_eq!(
+            to_plain_text(
+                "Read <em>the</em> <a href=\"guide/\" title=\"a > b\">\
+                 <strong>guide</strong></a>"
+            ),
+            "Read the guide"
+        );
+    }
+
+    #[test]
+    fn retains_emoji_alternative_text() {
+        assert_eq!(
+            to_plain_text(
+                "Hello <img alt=\"😄\" class=\"twemoji\" src=\"1f604.svg\" \
+                 title=\":smile:\" />"
+            ),
+            "Hello 😄"
+        );
+        assert_eq!(
+            to_plain_text(
+                "Hello <span class=\"twemoji\"><svg viewBox=\"0 0 24 24\">\
+                 <path d=\"M0 0\"/></svg></span>"
+            ),
+            "Hello"
+        );
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            to_plain_text("Q&amp;A &ndash; &#8220;FAQ&#x201D;&nbsp;&foo;"),
+            "Q&A \u{2013} \u{201c}FAQ\u{201d} &foo;"
+        );
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(escape_html("<a> & \"b\""), "&lt;a&gt; &amp; &quot;b&quot;");
+    }
+
+    #[test]
+    fn slugify_handles_modes() {
+        let value = "Café  &  Co -- Setup";
+        assert_eq!(slugify(value, Slugify::Default), "caf-co-setup");
+        assert_eq!(slugify(value, Slugify::Unicode), "café-co-setup");
+        assert_eq!(slugify(value, Slugify::Github), "café----co----setup");
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Variables.
+
+use anyhow::Result;
+use minijinja::{context, Environment};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::env;
+use zrx::id::Id;
+
+use crate::config::Config;
+
+use super::front_matter;
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Renders variables in the given Markdown, if enabled.
+///
+/// Markdown is rendered as a template before it's passed to the parser, which
+/// allows to interpolate variables, similar to the MkDocs macros plugin. The
+/// context is deliberately restricted to the extra data of the configuration,
+/// the metadata of the page, and environment variables that are allow-listed
+/// in the plugin configuration.
+pub fn render_variables(
+    config: &Config, id: &Id, content: String,
+) -> Result<String> {
+    let plugin = &config.project.plugins.variables.config;
+    if !plugin.enabled || !has_variables(&content) {
+        return Ok(content);
+    }
+
+    // Collect allow-listed environment variables, omitting unset ones
+    let vars = plugin
+        .env
+        .iter()
+        .filter_map(|name| {
+            env::var(name).ok().map(|value| (name.as_str(), value))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    // Create context and render variables
+    let meta = front_matter(&content)?;
+    let ctx = context! {
+        config => context! { extra => config.project.extra.clone() },
+        page => context! { meta => meta },
+        env => vars,
+    };
+    render(&id.location(), &content, ctx, plugin.render_code)
+}
+
+/// Renders the given content as a template with the given context.
+///
+/// Unless code should be rendered, code fences are wrapped in `raw` blocks, so
+/// templated code samples are passed through untouched. Errors include the
+/// name of the template and the line where rendering failed.
+fn render<C>(
+    name: &str, content: &str, ctx: C, render_code: bool,
+) -> Result<String>
+where
+    C: Serialize,
+{
+    let mut env = Environment::new();
+    env.set_keep_trailing_newline(true);
+
+    // Protect code fences, unless code should be rendered
+    let content = if render_code {
+        Cow::Borrowed(content)
+    } else {
+        Cow::Owned(protect_fences(content))
+    };
+
+    // Render template, and report location in case of errors
+    env.render_named_str(name, &content, ctx).map_err(|err| {
+        let line = err.line().unwrap_or(1);
+        let detail = err
+            .detail()
+            .map(|detail| format!(": {detail}"))
+            .unwrap_or_default();
+        anyhow::anyhow!("{name}:{line}: {}{detail}", err.kind())
+    })
+}
+
+/// Wraps code fences in `raw` blocks.
+///
+/// Tags are inserted on the same lines as the opening and closing fences, so
+/// line numbers reported in errors still match the original content. Fences
+/// can be indented, e.g., when nested in admonitions or content tabs.
+fn protect_fences(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut fence = None;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\r', '\n']);
+        let rest = text.trim_start();
+
+        // Determine fence character and width, if any
+        let char = rest.chars().next().filter(|&c| c == '`' || c == '~');
+        let width = char
+            .map_or(0, |char| rest.chars().take_while(|&c| c == char).count());
+
+        // Open fence, or close it if character and width match, and the fence
+        // is not followed by anything other than whitespace
+        match fence {
+            None if width >= 3 => {
+                fence = char.map(|char| (char, width));
+                output.push_str("{% raw %}");
+                output.push_str(line);
+            }
+            Some((c, w))
+                if char == Some(c)
+                    && width >= w
+                    && rest[width..].trim().is_empty() =>
+            {
+                fence = None;
+                output.push_str(text);
+                output.push_str("{% endraw %}");
+                output.push_str(&line[text.len()..]);
+            }
+            _ => output.push_str(line),
+        }
+    }
+
+    // Close unterminated fence, which runs until the end of the content
+    if fence.is_some() {
+        output.push_str("{% endraw %}");
+    }
+    output
+}
+
+/// Returns whether the given content might contain template syntax.
+fn has_variables(content: &str) -> bool {
+    content.contains("{{") || content.contains("{%") || content.contains("{#")
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use minijinja::context;
+
+    use super::render;
+
+    #[test]
+    fn render_substitutes_variables() {
+        let ctx = context! {
+            config => context! { extra => context! { version => "1.0" } },
+            page => context! { meta => context! { author => "Jane" } },
+        };
+        let content =
+            "Version {{ config.extra.version }} by {{ page.meta.author }}\n";
+        assert_eq!(
+            render("page.md", content, ctx, false).unwrap(),
+            "Version 1.0 by Jane\n"
+        );
+    }
+
+    #[test]
+    fn render_passes_raw_blocks_through() {
+        let content = "{% raw %}{{ value }} {% if %}{% endraw %}";
+        assert_eq!(
+            render("page.md", content, context! { value => 1 }, false).unwrap(),
+            "{{ value }} {% if %}"
+        );
+    }
+
+    #[test]
+    fn render_excludes_code_fences() {
+        let content = concat!(
+            "{{ value }}\n",
+            "```jinja\n",
+            "{{ value }}\n",
+            "```\n",
+            "    ~~~~\n",
+            "    {{ value }}\n",
+            "    ```\n",
+            "    ~~~~\n",
+            "{{ value }}",
+        );
+        assert_eq!(
+            render("page.md", content, context! { value => 1 }, false).unwrap(),
+            concat!(
+                "1\n",
+                "```jinja\n",
+                "{{ value }}\n",
+                "```\n",
+                "    ~~~~\n",
+                "    {{ value }}\n",
+                "    ```\n",
+                "    ~~~~\n",
+                "1",
+            )
+        );
+        assert_eq!(
+            render(
+                "page.md",
+                "```\n{{ value }}\n```\n",
+                context! { value => 1 },
+                true
+            )
+            .unwrap(),
+            "```\n1\n```\n"
+        );
+    }
+
+    #[test]
+    fn render_reports_location() {
+        let content = "# Title\n\n{{ value + }}\n";
+        let err = render("page.md", content, context! {}, false).unwrap_err();
+        assert!(err.to_string().starts_with("page.md:3: "));
+    }
+}
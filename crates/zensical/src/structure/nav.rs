@@ -25,24 +25,37 @@
 
 //! Navigation.
 
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use ahash::HashMap;
 use pyo3::types::PyAnyMethods;
 use pyo3::{FromPyObject, Python};
 use serde::Serialize;
 use zrx::id::Id;
-use zrx::scheduler::{Key, Value};
+use zrx::scheduler::Value;
 
+use crate::config::navigation::CollapseTitle;
+use crate::config::plugins::I18nPluginConfig;
+use crate::hash::StableHasher;
 use crate::structure::markdown::Autorefs;
 
-use super::page::Page;
+use super::dynamic::Dynamic;
+use super::i18n::Translations;
+use super::page::{is_noindex, Page, PageMeta, PageSummary};
+use super::toc::Section;
+use super::url;
 
 mod item;
 mod iter;
+mod tree;
 
+use item::select_meta;
 pub use item::NavigationItem;
 use iter::Iter;
+pub use tree::TreeItem;
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -64,6 +77,15 @@ pub struct Navigation {
     pub autorefs: Autorefs,
     /// Precomputed hash.
     pub hash: u64,
+    /// Precomputed hash of headings registered for autorefs.
+    #[serde(skip)]
+    pub headings_hash: u64,
+    /// Navigations by locale (i18n).
+    #[serde(skip)]
+    pub languages: BTreeMap<String, Navigation>,
+    /// Translations (i18n).
+    #[serde(skip)]
+    pub translations: Translations,
 }
 
 // ----------------------------------------------------------------------------
@@ -72,21 +94,51 @@ pub struct Navigation {
 
 impl Navigation {
     /// Creates a navigation from the given items.
-    pub fn new(
-        mut items: Vec<NavigationItem>, pages: Vec<(Key<Id>, Page)>,
-    ) -> Self {
+    ///
+    /// The homepage is resolved from the given location, if any, falling back
+    /// to `index.md` and `README.md` in the root of the docs directory. If no
+    /// items are given, the navigation is generated from the pages, and its
+    /// sections with a single child are collapsed, if a title is given.
+    pub fn new<'a, I>(
+        mut items: Vec<NavigationItem>, pages: I, homepage: Option<&str>,
+        collapse: Option<CollapseTitle>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = (&'a Id, &'a PageSummary)>,
+    {
         if items.is_empty() {
-            return Self::from(pages);
+            let pages = pages.into_iter().collect::<Vec<_>>();
+            let page = select_homepage(homepage, |location| {
+                let mut iter = pages.iter();
+                iter.find(|(id, _)| id.location() == location)
+                    .map(|(_, page)| *page)
+            });
+
+            // Auto-populate navigation, and collapse sections with a single
+            // child, which requires to compute the hash again
+            let mut nav = Self::from(pages);
+            if let Some(mode) = collapse {
+                let iter = nav.items.into_iter();
+                nav.items =
+                    iter.map(|item| collapse_item(item, mode)).collect();
+                nav.hash = {
+                    let mut hasher = StableHasher::default();
+                    nav.items.hash(&mut hasher);
+                    hasher.finish()
+                };
+            }
+
+            // Resolve homepage after navigation is complete
+            nav.homepage = find_homepage(&nav.items, page);
+            return nav;
         }
 
         // Create a map of pages for easy lookup, so we can resolve titles and
-        // icons from the file location of the respective page.
+        // icons from the file location of the respective page. Locations are
+        // borrowed from identifiers, so no page data is copied here.
         let pages = pages
             .into_iter()
-            .map(|(id, page)| {
-                let id = id[0].location().to_string();
-                (id, page)
-            })
+            .map(|(id, page)| (id.location(), page))
             .collect::<HashMap<_, _>>();
 
         // Since a navigation structure is given, we just need to add titles and
@@ -99,25 +151,26 @@ impl Navigation {
                 // same data type. This is definitely not the final design that
                 // we want, and we'll switch to a much more flexible approach
                 // once we work on modular navigation. The component system
-                // will also make things much easier here.
-                if let Some(url) = &item.url {
+                // will also make things much easier here. External URLs are
+                // never resolved, as they can't refer to pages.
+                if let Some(url) = item.url.clone().filter(|_| !item.external) {
                     // Try to obtain a page for the given url. Users might also
-                    // refer to non-existing pages, which we just ignore for now
-                    if let Some(page) = pages.get(url) {
-                        // Set URLs from page - we currently resolve the final
-                        // URL during rendering, so we just need to set it here.
-                        // Once we start working on the component and module
-                        // system, all of this is going to change anyway
-                        item.url = Some(page.url.clone());
-                        item.canonical_url = page.canonical_url.clone();
-
-                        // Set item title from page if not set
-                        if item.title.is_none() {
-                            item.title = Some(page.title.clone());
+                    // refer to non-existing pages, e.g., due to typos, which
+                    // we flag, so they're skipped for previous and next pages.
+                    // Query strings and fragments are not part of the location,
+                    // so we split them off, and attach them to the final URL.
+                    let (location, suffix) = url::split_suffix(&url);
+                    let page = pages.get(url::decode(location).as_ref());
+                    item.missing = page.is_none();
+                    if let Some(page) = page {
+                        resolve_item(item, page);
+                        if !suffix.is_empty() {
+                            item.url = Some(format!("{}{suffix}", page.url));
                         }
 
-                        // Extract page metadata for selected keys
-                        item.meta = Some(page.meta.clone());
+                        // Mark item as index page, if the location is one
+                        let file = location.rsplit('/').next();
+                        item.is_index = is_index(file.unwrap_or(location));
                     }
                 }
 
@@ -128,32 +181,15 @@ impl Navigation {
             }
         }
 
-        // Determine homepage - here, we mirror MkDocs behavior, which only
-        // considers index pages at the root level as potential homepages
-        let mut homepage = items.iter().find(|item| item.is_index).cloned();
-        if homepage.is_none() {
-            // However, if we couldn't find anything, but there's still an index
-            // page, we check if it's out of navigation, and if so, use it
-            if let Some(page) = pages.get("index.md") {
-                if !Iter::new(&items)
-                    .any(|item| item.url.as_deref() == Some(&page.url))
-                {
-                    homepage = Some(NavigationItem {
-                        title: Some(page.title.clone()),
-                        url: Some(page.url.clone()),
-                        canonical_url: page.canonical_url.clone(),
-                        meta: Some(page.meta.clone()),
-                        children: Vec::new(),
-                        is_index: true,
-                        active: false,
-                    });
-                }
-            }
-        }
+        // Determine homepage - the homepage might be nested inside a section,
+        // or not be part of the navigation at all, so we search the full tree
+        let page =
+            select_homepage(homepage, |location| pages.get(location).copied());
+        let homepage = find_homepage(&items, page);
 
         // Precompute hash
         let hash = {
-            let mut hasher = DefaultHasher::default();
+            let mut hasher = StableHasher::default();
             items.hash(&mut hasher);
             hasher.finish()
         };
@@ -166,6 +202,9 @@ impl Navigation {
             homepage,
             autorefs,
             hash,
+            headings_hash: 0,
+            languages: BTreeMap::new(),
+            translations: Translations::new(),
         }
     }
 
@@ -177,10 +216,16 @@ impl Navigation {
     /// new instance with the active state set. This is important, as we need
     /// to keep the original navigation structure intact for other pages.
     pub fn with_active(self, page: &Page) -> Self {
+        self.with_active_url(&page.url)
+    }
+
+    /// Returns a copy of the navigation with the active item set based on the
+    /// given URL, which allows to render generated pages, e.g., blog indexes.
+    pub fn with_active_url(self, url: &str) -> Self {
         /// Recursively set active state on navigation items.
         fn recurse(items: &mut [NavigationItem], url: &str) -> bool {
             for item in items.iter_mut() {
-                if item.url.as_deref() == Some(url) {
+                if is_match(item, url) {
                     item.active = true;
                     return true;
                 }
@@ -194,22 +239,123 @@ impl Navigation {
             false
         }
 
-        // Set active state starting from the root
+        // Set active state starting from the root, and if the page is not part
+        // of the navigation, set it on the sections containing the page
         let mut items = self.items;
-        recurse(&mut items, &page.url);
+        if !recurse(&mut items, url) {
+            let mut scope = items.as_mut_slice();
+            let mut prefix = String::new();
+            for component in components(url) {
+                prefix.push_str(component);
+                prefix.push('/');
+                let Some(index) = find_section(scope, &prefix, component)
+                else {
+                    continue;
+                };
+                scope[index].active = true;
+                scope = scope[index].children.as_mut_slice();
+            }
+        }
         Self {
             items,
             homepage: self.homepage,
             autorefs: self.autorefs,
             hash: self.hash,
+            headings_hash: self.headings_hash,
+            languages: self.languages,
+            translations: self.translations,
+        }
+    }
+
+    /// Injects the given section into the navigation, e.g., for the blog.
+    ///
+    /// Items linking to any of the given URLs are removed first, as well as
+    /// sections that end up empty, so generated pages replace the pages they
+    /// list. If a top-level section with the same title exists, the children
+    /// are merged into it, skipping those that are already part of it.
+    pub fn inject(&mut self, section: NavigationItem, remove: &[String]) {
+        /// Recursively remove items linking to the given URLs.
+        fn recurse(items: &mut Vec<NavigationItem>, remove: &[String]) {
+            items.retain_mut(|item| {
+                if item.url.as_ref().is_some_and(|url| remove.contains(url)) {
+                    return false;
+                }
+                let empty = item.children.is_empty();
+                recurse(&mut item.children, remove);
+                empty || !item.children.is_empty()
+            });
+        }
+        recurse(&mut self.items, remove);
+
+        // Items are the same, if they link to the same page, or if they are
+        // sections with the same title
+        let is_same =
+            |a: &NavigationItem, b: &NavigationItem| match (&a.url, &b.url) {
+                (Some(a), Some(b)) => a == b,
+                (None, None) => a.title == b.title,
+                _ => false,
+            };
+
+        // Merge section into the existing section with the same title, if any
+        let mut iter = self.items.iter();
+        match iter.position(|item| is_same(item, &section)) {
+            Some(index) => {
+                let children = &mut self.items[index].children;
+                for child in section.children {
+                    if children.iter().any(|item| is_same(item, &child)) {
+                        continue;
+                    }
+
+                    // Index pages must always be the first child
+                    if child.is_index {
+                        children.insert(0, child);
+                    } else {
+                        children.push(child);
+                    }
+                }
+            }
+            None => self.items.push(section),
+        }
+
+        // Update hash to account for injected section
+        let mut hasher = StableHasher::default();
+        self.items.hash(&mut hasher);
+        self.hash = hasher.finish();
+    }
+
+    /// Returns the navigation for the language of the given page (i18n).
+    ///
+    /// If the page has no language, or internationalization is not enabled,
+    /// the navigation is returned as is.
+    pub fn localized(self, config: &I18nPluginConfig, page: &Page) -> Self {
+        let Some((language, _)) = config.split(&page.url) else {
+            return self;
+        };
+
+        // Return navigation of language, if any
+        match self.languages.get(&language.locale) {
+            Some(nav) => nav.clone(),
+            None => self,
         }
     }
 
     /// Returns ancestors of the page with the given URL.
     ///
     /// Note that only the ancestors, not the page itself is returned, which
-    /// again, mirrors MkDocs' behavior, and is necessary for breadcrumbs.
-    pub fn ancestors(&self, page: &Page) -> Vec<NavigationItem> {
+    /// again, mirrors MkDocs' behavior, and is necessary for breadcrumbs. If
+    /// the page is not part of the navigation, ancestors are derived from the
+    /// components of its URL, so breadcrumbs don't vanish for unlisted pages.
+    pub fn ancestors(&self, url: &str) -> Vec<NavigationItem> {
+        let iter = self.ancestors_of(url).into_iter();
+        iter.map(Cow::into_owned).collect()
+    }
+
+    /// Returns borrowed ancestors of the page with the given URL.
+    ///
+    /// Only ancestors that are synthesized for pages that are not part of the
+    /// navigation are owned, so callers that merely inspect ancestors, e.g.,
+    /// to compute the path of search items, don't clone entire sections.
+    pub fn ancestors_of(&self, url: &str) -> Vec<Cow<'_, NavigationItem>> {
         // Recursively find ancestors of the page with the given URL.
         fn recurse<'a>(
             items: &'a [NavigationItem], url: &str,
@@ -217,7 +363,7 @@ impl Navigation {
         ) -> bool {
             for item in items {
                 // If this item's URL matches, we've found the page.
-                if item.url.as_deref() == Some(url) {
+                if is_match(item, url) {
                     return true;
                 }
 
@@ -235,11 +381,108 @@ impl Navigation {
             false
         }
 
-        // Clone the ancestors into owned items and reverse them, so we start
-        // at the ancestor closest to the page, not the root itself
+        // Reverse the ancestors, so we start at the ancestor closest to the
+        // page, not the root itself
         let mut items: Vec<&NavigationItem> = Vec::new();
-        let _ = recurse(&self.items, &page.url, &mut items);
-        items.into_iter().rev().cloned().collect()
+        if recurse(&self.items, url, &mut items) {
+            return items.into_iter().rev().map(Cow::Borrowed).collect();
+        }
+
+        // The page is not part of the navigation, so we map each directory to
+        // the matching section, or synthesize an unlinked item if there's none
+        let mut ancestors = Vec::new();
+        let mut scope = self.items.as_slice();
+        let mut prefix = String::new();
+        for component in components(url) {
+            prefix.push_str(component);
+            prefix.push('/');
+            match find_section(scope, &prefix, component) {
+                Some(index) => {
+                    ancestors.push(Cow::Borrowed(&scope[index]));
+                    scope = &scope[index].children;
+                }
+                None => ancestors.push(Cow::Owned(NavigationItem {
+                    title: Some(to_title(&url::decode(component))),
+                    title_html: None,
+                    url: None,
+                    canonical_url: None,
+                    meta: None,
+                    icon: None,
+                    attributes: BTreeMap::new(),
+                    children: Vec::new(),
+                    is_index: false,
+                    external: false,
+                    active: false,
+                    missing: false,
+                })),
+            }
+        }
+        ancestors.reverse();
+        ancestors
+    }
+
+    /// Returns all items that don't resolve to a page or an external URL.
+    ///
+    /// Resolved items always carry the metadata of their page, so internal
+    /// items without metadata refer to pages that don't exist.
+    pub fn unresolved(&self) -> impl Iterator<Item = &NavigationItem> {
+        self.iter().filter(|item| {
+            item.url.is_some() && !item.external && item.meta.is_none()
+        })
+    }
+
+    /// Registers the headings of all pages for autorefs.
+    ///
+    /// This allows to cross-reference plain Markdown headings, which are not
+    /// registered by mkdocstrings, given the URL and table of contents of each
+    /// page. Headings are hashed separately from the navigation, since only
+    /// pages containing autorefs depend on the headings of other pages.
+    pub fn register_headings(&mut self, pages: &[(Arc<str>, Arc<[Section]>)]) {
+        for (url, toc) in pages {
+            self.autorefs.register_headings(url, toc);
+        }
+
+        // Update hash of registered headings
+        let mut hasher = StableHasher::default();
+        self.autorefs.headings.hash(&mut hasher);
+        self.headings_hash = hasher.finish();
+
+        // Register headings for the navigations of all languages
+        for nav in self.languages.values_mut() {
+            nav.register_headings(pages);
+        }
+    }
+
+    /// Returns the hash of the navigation for rendering the given content.
+    ///
+    /// The hash of registered headings is only mixed in if the content contains
+    /// autorefs, so changing a heading doesn't render all other pages again.
+    pub fn hash_for(&self, content: &str) -> u64 {
+        if !Autorefs::is_used_in(content) {
+            return self.hash;
+        }
+
+        // Mix in hash of registered headings
+        let mut hasher = StableHasher::default();
+        self.hash.hash(&mut hasher);
+        self.headings_hash.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the navigation as a tree of reduced navigation items.
+    ///
+    /// The metadata of pages is obtained via the given function, keyed by URL,
+    /// and if allowed keys are given, it's limited to those keys.
+    pub fn tree<'a, F>(
+        &'a self, get: F, allow: Option<&[String]>,
+    ) -> Vec<TreeItem>
+    where
+        F: Fn(&str) -> Option<&'a PageMeta>,
+    {
+        self.items
+            .iter()
+            .map(|item| TreeItem::new(item, &get, allow))
+            .collect()
     }
 
     /// Returns an iterator over all navigation items in pre-order.
@@ -247,17 +490,26 @@ impl Navigation {
         Iter::new(&self.items)
     }
 
+    /// Returns an iterator over all navigation items that can be indexed.
+    ///
+    /// Items of pages that opt out of indexing in their front matter, e.g.,
+    /// via `robots: noindex`, are skipped, so they're excluded from sitemaps.
+    pub fn indexable(&self) -> impl Iterator<Item = &NavigationItem> {
+        self.iter()
+            .filter(|item| !item.meta.as_ref().is_some_and(is_noindex))
+    }
+
     /// Return the next page for the given page in pre-order, if any.
     pub fn next_page(&self, page: &Page) -> Option<NavigationItem> {
         let mut found = false;
         for item in self {
             if found {
-                if item.url.is_some() {
+                if is_page(item) && !is_match(item, &page.url) {
                     return Some(item.clone());
                 }
                 continue;
             }
-            if item.url.as_deref() == Some(&page.url) {
+            if is_match(item, &page.url) {
                 found = true;
             }
         }
@@ -268,10 +520,10 @@ impl Navigation {
     pub fn previous_page(&self, page: &Page) -> Option<NavigationItem> {
         let mut prev: Option<NavigationItem> = None;
         for item in self {
-            if item.url.as_deref() == Some(&page.url) {
+            if is_match(item, &page.url) {
                 return prev;
             }
-            if item.url.is_some() {
+            if is_page(item) {
                 prev = Some(item.clone());
             }
         }
@@ -287,80 +539,19 @@ impl Value for Navigation {}
 
 // ----------------------------------------------------------------------------
 
-impl From<Vec<(Key<Id>, Page)>> for Navigation {
+impl<'a> From<Vec<(&'a Id, &'a PageSummary)>> for Navigation {
     /// Creates a navigation from pages.
     ///
     /// This mirrors the functionality of auto-populated navigation that MkDocs
     /// provides. In the future, we intend to refactor this into a more flexible
     /// system that allows for custom and modular navigation structures, but for
     /// now, compatibility is key.
-    fn from(pages: Vec<(Key<Id>, Page)>) -> Self {
-        let mut items: Vec<NavigationItem> = Vec::new();
-
-        // Convert chunk into a vector for easier processing, and sort pages by
-        // the exact same method that MkDocs uses
-        let mut pages = Vec::from_iter(pages);
-        pages.sort_by_key(|(id, _)| file_sort_key(&id[0]));
-
-        // There can only be pages, no URLs, since we're auto-populating the
-        // navigation from the files in the docs directory
-        for (id, page) in pages {
-            let location = id[0].location();
-
-            // Split location into components at slashes
-            let mut components = location
-                .split('/')
-                .map(ToString::to_string)
-                .collect::<Vec<_>>();
-
-            // Extract file, and check, whether it's an index file
-            let file = components.pop().expect("invariant");
-
-            // Now, first obtain the subsection in which we need to insert the
-            // page. If there are no parents, we insert it at the top level.
-            let mut section = &mut items;
-            for component in components {
-                let title = to_title(&component);
-
-                // Next, we try to find an existing section with the same title.
-                // If we find one, we descend into it, otherwise, we create.
-                let mut iter = section.iter();
-                if let Some(index) =
-                    iter.position(|item| item.title.as_ref() == Some(&title))
-                {
-                    section = &mut section[index].children;
-                } else {
-                    section.push(NavigationItem {
-                        title: Some(title),
-                        url: None,
-                        canonical_url: None,
-                        meta: None,
-                        children: Vec::new(),
-                        is_index: false,
-                        active: false,
-                    });
-
-                    // We just inserted an item, so it's safe to unwrap
-                    let item = section.last_mut().expect("invariant");
-                    section = &mut item.children;
-                }
-            }
-
-            // Insert page into the section
-            section.push(NavigationItem {
-                title: Some(page.title),
-                url: Some(page.url),
-                canonical_url: page.canonical_url,
-                meta: Some(page.meta.clone()),
-                children: Vec::new(),
-                is_index: is_index(&file),
-                active: false,
-            });
-        }
+    fn from(pages: Vec<(&'a Id, &'a PageSummary)>) -> Self {
+        let items = generate_items(pages);
 
         // Precompute hash
         let hash = {
-            let mut hasher = DefaultHasher::default();
+            let mut hasher = StableHasher::default();
             items.hash(&mut hasher);
             hasher.finish()
         };
@@ -373,6 +564,9 @@ impl From<Vec<(Key<Id>, Page)>> for Navigation {
             autorefs,
             items,
             hash,
+            headings_hash: 0,
+            languages: BTreeMap::new(),
+            translations: Translations::new(),
         }
     }
 }
@@ -422,6 +616,261 @@ pub(crate) fn file_sort_key(id: &Id) -> (Vec<String>, bool, String) {
     (components, !is_index(&file), file)
 }
 
+/// Generates navigation items from the given pages.
+///
+/// Sections are created from the directories of the pages, and titles of
+/// sections are derived from directory names, as MkDocs does. Pages are only
+/// borrowed, and their metadata is limited to the keys that items carry.
+fn generate_items(mut pages: Vec<(&Id, &PageSummary)>) -> Vec<NavigationItem> {
+    let mut items: Vec<NavigationItem> = Vec::new();
+
+    // Sort pages by the exact same method that MkDocs uses - keys are only
+    // computed once per page, as computing them allocates
+    pages.sort_by_cached_key(|(id, _)| file_sort_key(id));
+
+    // There can only be pages, no URLs, since we're auto-populating the
+    // navigation from the files in the docs directory
+    for (id, page) in pages {
+        let location = id.location();
+
+        // Split location into components at slashes
+        let mut components = location
+            .split('/')
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        // Extract file, and check, whether it's an index file
+        let file = components.pop().expect("invariant");
+
+        // Now, first obtain the subsection in which we need to insert the
+        // page. If there are no parents, we insert it at the top level.
+        let mut section = &mut items;
+        for component in components {
+            let title = to_title(&component);
+
+            // Next, we try to find an existing section with the same title.
+            // If we find one, we descend into it, otherwise, we create.
+            let mut iter = section.iter();
+            if let Some(index) =
+                iter.position(|item| item.title.as_ref() == Some(&title))
+            {
+                section = &mut section[index].children;
+            } else {
+                section.push(NavigationItem {
+                    title: Some(title),
+                    title_html: None,
+                    url: None,
+                    canonical_url: None,
+                    meta: None,
+                    icon: None,
+                    attributes: BTreeMap::new(),
+                    children: Vec::new(),
+                    is_index: false,
+                    external: false,
+                    active: false,
+                    missing: false,
+                });
+
+                // We just inserted an item, so it's safe to unwrap
+                let item = section.last_mut().expect("invariant");
+                section = &mut item.children;
+            }
+        }
+
+        // Insert page into the section
+        section.push(NavigationItem {
+            title: Some(page.title.to_string()),
+            title_html: Some(page.title_html.to_string()),
+            url: Some(page.url.to_string()),
+            canonical_url: page.canonical_url.as_deref().map(Into::into),
+            meta: Some(select_meta(&page.meta)),
+            icon: page_icon(&page.meta),
+            attributes: BTreeMap::new(),
+            children: Vec::new(),
+            is_index: is_index(&file),
+            external: false,
+            active: false,
+            missing: false,
+        });
+    }
+
+    // Return navigation items
+    items
+}
+
+/// Returns whether the given item links to the given URL.
+///
+/// URLs are compared in their percent-decoded form, so that encoding doesn't
+/// affect matching, e.g., in case of umlauts, CJK characters, or spaces. Items
+/// linking to missing pages never match, as their URL is taken verbatim. Query
+/// strings and fragments are ignored, as they link to the same page.
+fn is_match(item: &NavigationItem, url: &str) -> bool {
+    !item.missing
+        && item
+            .url
+            .as_deref()
+            .is_some_and(|item| url::equivalent(url::split_suffix(item).0, url))
+}
+
+/// Returns the directory components of the given page URL.
+///
+/// The last component is the page itself, and thus omitted, which is why the
+/// `index.html` suffix must be stripped first, if directory URLs are disabled.
+fn components(url: &str) -> impl Iterator<Item = &str> {
+    let mut components = url::strip_index(url)
+        .trim_end_matches('/')
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>();
+    components.pop();
+    components.into_iter()
+}
+
+/// Returns the index of the section matching the given directory, if any.
+///
+/// A section matches if its index page is located at the directory, or if its
+/// title matches the title computed from the directory name, which is how the
+/// titles of sections are derived when navigation is generated from files.
+fn find_section(
+    items: &[NavigationItem], prefix: &str, component: &str,
+) -> Option<usize> {
+    let title = to_title(&url::decode(component));
+    items.iter().position(|item| {
+        !item.children.is_empty()
+            && (item
+                .children
+                .iter()
+                .any(|child| child.is_index && is_match(child, prefix))
+                || item.display_title() == Some(title.as_str()))
+    })
+}
+
+/// Selects the homepage from the given location, or the root index page.
+///
+/// If an explicit location is given, but no page exists for it, we fall back
+/// to `index.md` and `README.md`, in this order, mirroring MkDocs' behavior.
+fn select_homepage<'a, F>(
+    location: Option<&str>, get: F,
+) -> Option<&'a PageSummary>
+where
+    F: Fn(&str) -> Option<&'a PageSummary>,
+{
+    location
+        .into_iter()
+        .chain(["index.md", "README.md"])
+        .find_map(get)
+}
+
+/// Returns the navigation item for the given homepage.
+///
+/// If the homepage is part of the navigation, the corresponding item is used,
+/// regardless of how deeply it is nested. Otherwise, an item is created from
+/// the page. Without homepage, we mirror MkDocs' behavior, which considers
+/// index pages at the root level as potential homepages.
+fn find_homepage(
+    items: &[NavigationItem], page: Option<&PageSummary>,
+) -> Option<NavigationItem> {
+    let Some(page) = page else {
+        return items.iter().find(|item| item.is_index).cloned();
+    };
+
+    // Search the full navigation tree for the homepage, and if we couldn't find
+    // it, it's out of navigation, so we create an item from the page
+    let mut iter = Iter::new(items);
+    match iter.find(|item| is_match(item, &page.url)) {
+        Some(item) => Some(NavigationItem { is_index: true, ..item.clone() }),
+        None => Some(NavigationItem {
+            title: Some(page.title.to_string()),
+            title_html: Some(page.title_html.to_string()),
+            url: Some(page.url.to_string()),
+            canonical_url: page.canonical_url.as_deref().map(Into::into),
+            meta: Some(select_meta(&page.meta)),
+            icon: page_icon(&page.meta),
+            attributes: BTreeMap::new(),
+            children: Vec::new(),
+            is_index: true,
+            external: false,
+            active: false,
+            missing: false,
+        }),
+    }
+}
+
+/// Resolves the given navigation item from the page it links to.
+///
+/// Titles and icons given in the navigation take precedence over the ones that
+/// are defined by the page, while URLs and metadata are always set from it.
+fn resolve_item(item: &mut NavigationItem, page: &PageSummary) {
+    // Set URLs from page - we currently resolve the final URL during rendering,
+    // so we just need to set it here. Once we start working on the component
+    // and module system, all of this is going to change anyway
+    item.url = Some(page.url.to_string());
+    item.canonical_url = page.canonical_url.as_deref().map(Into::into);
+
+    // Set item title and icon from page if not set
+    if item.title.is_none() {
+        item.title = Some(page.title.to_string());
+        item.title_html = Some(page.title_html.to_string());
+    }
+    if item.icon.is_none() {
+        item.icon = page_icon(&page.meta);
+    }
+
+    // Extract page metadata for selected keys
+    item.meta = Some(select_meta(&page.meta));
+}
+
+/// Collapses the given item into its child, if it's a section with one child.
+///
+/// Children are collapsed first, so chains of sections with a single child are
+/// collapsed into the innermost item. Sections that link to a page are never
+/// collapsed. The collapsed item isn't an index page anymore, as it's not part
+/// of the section it was the index page of.
+fn collapse_item(
+    mut item: NavigationItem, mode: CollapseTitle,
+) -> NavigationItem {
+    let iter = item.children.into_iter();
+    item.children = iter.map(|child| collapse_item(child, mode)).collect();
+    if item.url.is_some() || item.children.len() != 1 {
+        return item;
+    }
+
+    // Select title of collapsed item - only the title of the child can carry
+    // HTML, since titles of sections are derived from directory names
+    let child = item.children.pop().expect("invariant");
+    let (title, title_html) = match (mode, item.title, child.title) {
+        (CollapseTitle::Child, _, title) => (title, child.title_html),
+        (CollapseTitle::Parent, parent, title) => (parent.or(title), None),
+        (CollapseTitle::Joined, Some(parent), Some(title)) => {
+            (Some(format!("{parent} / {title}")), None)
+        }
+        (CollapseTitle::Joined, parent, title) => (parent.or(title), None),
+    };
+
+    // Return child with selected title
+    NavigationItem {
+        title,
+        title_html,
+        is_index: false,
+        ..child
+    }
+}
+
+/// Returns the icon defined in the given page metadata, if any.
+fn page_icon(meta: &PageMeta) -> Option<String> {
+    match meta.get("icon") {
+        Some(Dynamic::String(icon)) if !icon.is_empty() => Some(icon.clone()),
+        _ => None,
+    }
+}
+
+/// Returns whether the given item links to a page, which is necessary for
+/// computing previous and next pages, as external URLs and items linking to
+/// missing pages must be skipped.
+fn is_page(item: &NavigationItem) -> bool {
+    item.url.is_some() && !item.external && !item.missing
+}
+
 /// Returns whether the given file name is an index file.
 fn is_index(component: &str) -> bool {
     component == "index.md" || component == "README.md"
@@ -441,15 +890,16 @@ pub(crate) fn to_title(component: &str) -> String {
     }
 }
 
+/// Returns the autorefs data, which is empty without a Python interpreter.
 fn get_autorefs() -> Autorefs {
-    match Python::attach(|py| {
+    match Python::try_attach(|py| {
         let module = py.import("zensical.extensions.autorefs")?;
         module
             .call_method0("get_autorefs_data")?
             .extract::<Autorefs>()
     }) {
-        Ok(autorefs) => autorefs,
-        Err(_) => Autorefs::new(),
+        Some(Ok(autorefs)) => autorefs,
+        _ => Autorefs::new(),
     }
 }
 
@@ -459,6 +909,11 @@ fn get_autorefs() -> Autorefs {
 
 #[cfg(test)]
 mod tests {
+    use zrx::scheduler::Key;
+
+    use crate::structure::bench::{allocations, chunk};
+    use crate::structure::page::PageMeta;
+
     use super::*;
 
     /// https://github.com/zensical/zensical/issues/66
@@ -467,4 +922,592 @@ mod tests {
         assert_eq!(to_title("hello-world"), "Hello world");
         assert_eq!(to_title("编译器笔记"), "编译器笔记");
     }
+
+    /// Creates a page with the given URL.
+    fn page(url: &str) -> Page {
+        Page {
+            url: url.to_string(),
+            canonical_url: None,
+            edit_url: None,
+            title: String::new(),
+            title_html: String::new(),
+            meta: PageMeta::default(),
+            path: String::new(),
+            src_path: String::new(),
+            content: String::new(),
+            content_hash: String::new(),
+            toc: Vec::new(),
+            search: Vec::new(),
+            ancestors: Vec::new(),
+            previous_page: None,
+            next_page: None,
+            word_count: 0,
+            reading_time: 0,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Creates a page summary with the given URL.
+    fn summary(url: &str) -> PageSummary {
+        PageSummary::from(&page(url))
+    }
+
+    /// Creates a page summary with the given URL and icon in its front matter.
+    fn page_with_icon(url: &str, icon: &str) -> PageSummary {
+        let mut page = summary(url);
+        let icon = Dynamic::String(icon.to_string());
+        page.meta.insert("icon".to_string(), icon);
+        page
+    }
+
+    /// Computes the hash of the given navigation items.
+    fn hash(items: &[NavigationItem]) -> u64 {
+        let mut hasher = StableHasher::default();
+        items.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Creates a navigation item with the given URL and children.
+    fn item(
+        url: Option<&str>, children: Vec<NavigationItem>,
+    ) -> NavigationItem {
+        NavigationItem {
+            title: None,
+            title_html: None,
+            url: url.map(ToString::to_string),
+            canonical_url: None,
+            meta: None,
+            icon: None,
+            attributes: BTreeMap::new(),
+            children,
+            is_index: false,
+            external: false,
+            active: false,
+            missing: false,
+        }
+    }
+
+    /// Creates a navigation item linking to the given external URL.
+    fn external(url: &str) -> NavigationItem {
+        NavigationItem {
+            external: true,
+            ..item(Some(url), vec![])
+        }
+    }
+
+    /// Creates a navigation from the given items.
+    fn nav(items: Vec<NavigationItem>) -> Navigation {
+        Navigation {
+            items,
+            homepage: None,
+            autorefs: Autorefs::new(),
+            hash: 0,
+            headings_hash: 0,
+            languages: BTreeMap::new(),
+            translations: Translations::new(),
+        }
+    }
+
+    #[test]
+    fn homepage_nested_in_section() {
+        let home = summary("");
+        let items = vec![item(
+            None,
+            vec![item(Some("about/"), vec![]), item(Some(""), vec![])],
+        )];
+        let homepage = find_homepage(&items, Some(&home)).unwrap();
+        assert_eq!(homepage.url.as_deref(), Some(""));
+        assert!(homepage.is_index);
+    }
+
+    #[test]
+    fn homepage_from_root_readme() {
+        let readme = summary("");
+        let homepage = select_homepage(None, |location| {
+            (location == "README.md").then_some(&readme)
+        });
+        assert_eq!(homepage, Some(&readme));
+
+        // Homepage is not part of the navigation, so an item is created
+        let items = vec![item(Some("about/"), vec![])];
+        let homepage = find_homepage(&items, homepage).unwrap();
+        assert_eq!(homepage.url.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn homepage_from_explicit_location() {
+        let index = summary("");
+        let intro = summary("intro/");
+        let get = |location: &str| match location {
+            "index.md" => Some(&index),
+            "intro.md" => Some(&intro),
+            _ => None,
+        };
+        assert_eq!(select_homepage(Some("intro.md"), get), Some(&intro));
+        assert_eq!(select_homepage(Some("missing.md"), get), Some(&index));
+
+        // Explicit homepage takes precedence over root index page
+        let items = vec![item(Some(""), vec![]), item(Some("intro/"), vec![])];
+        let homepage = find_homepage(&items, Some(&intro)).unwrap();
+        assert_eq!(homepage.url.as_deref(), Some("intro/"));
+    }
+
+    #[test]
+    fn with_active_ignores_encoding() {
+        let nav = nav(vec![item(
+            None,
+            vec![
+                item(Some("übersicht/"), vec![]),
+                item(Some("编译器/"), vec![]),
+            ],
+        )]);
+        let nav = nav.with_active(&page("%C3%BCbersicht/"));
+        assert!(nav.items[0].active);
+        assert!(nav.items[0].children[0].active);
+        assert!(!nav.items[0].children[1].active);
+    }
+
+    #[test]
+    fn ancestors_ignore_encoding() {
+        let nav = nav(vec![item(
+            None,
+            vec![item(Some("getting started/"), vec![])],
+        )]);
+        let ancestors = nav.ancestors("getting%20started/");
+        assert_eq!(ancestors.len(), 1);
+    }
+
+    #[test]
+    fn ancestors_of_unlisted_page() {
+        let mut setup = item(None, vec![item(Some("guide/setup/a/"), vec![])]);
+        setup.title = Some(String::from("Setup"));
+        let mut index = item(Some("guide/"), vec![]);
+        index.is_index = true;
+        let guide = item(None, vec![index, setup]);
+        let nav = nav(vec![guide]);
+
+        // Sections are matched by index page location and by title
+        let ancestors = nav.ancestors("guide/setup/extra/");
+        assert_eq!(ancestors.len(), 2);
+        assert_eq!(ancestors[0].title.as_deref(), Some("Setup"));
+        assert_eq!(ancestors[1].children[0].url.as_deref(), Some("guide/"));
+
+        // Nearest containing section is highlighted
+        let nav = nav.with_active(&page("guide/setup/extra/"));
+        assert!(nav.items[0].active);
+        assert!(nav.items[0].children[1].active);
+        assert!(!nav.items[0].children[1].children[0].active);
+    }
+
+    #[test]
+    fn ancestors_of_unknown_path() {
+        let nav = nav(vec![item(Some("about/"), vec![])]);
+        let ancestors = nav.ancestors("release-notes/v1/changes.html");
+        let titles = ancestors
+            .iter()
+            .map(|item| item.title.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec![Some("V1"), Some("Release notes")]);
+        assert!(ancestors.iter().all(|item| item.url.is_none()));
+
+        // Nothing is highlighted, as no section contains the page
+        let nav = nav.with_active(&page("release-notes/v1/changes.html"));
+        assert!(!nav.items[0].active);
+    }
+
+    #[test]
+    fn previous_and_next_page_ignore_encoding() {
+        let nav = nav(vec![
+            item(Some("%C3%BCbersicht/"), vec![]),
+            item(Some("%E7%BC%96%E8%AF%91%E5%99%A8/"), vec![]),
+            item(Some("getting%20started/"), vec![]),
+        ]);
+        let current = page("编译器/");
+        let prev = nav.previous_page(&current).and_then(|item| item.url);
+        let next = nav.next_page(&current).and_then(|item| item.url);
+        assert_eq!(prev.as_deref(), Some("%C3%BCbersicht/"));
+        assert_eq!(next.as_deref(), Some("getting%20started/"));
+    }
+
+    #[test]
+    fn with_active_ignores_fragments() {
+        let nav = nav(vec![item(
+            None,
+            vec![
+                item(Some("guide/#setup"), vec![]),
+                item(Some("faq/"), vec![]),
+            ],
+        )]);
+        let nav = nav.with_active(&page("guide/"));
+        assert!(nav.items[0].active);
+        assert!(nav.items[0].children[0].active);
+        assert!(!nav.items[0].children[1].active);
+    }
+
+    #[test]
+    fn new_resolves_locations_with_fragments() {
+        let id = Id::builder()
+            .provider("file")
+            .context("docs")
+            .location("reference/index.md")
+            .build()
+            .unwrap();
+        let mut reference = page("reference/");
+        reference.title = String::from("Reference");
+        let reference = PageSummary::from(&reference);
+
+        // Page is looked up without the fragment, which is attached again
+        let items = vec![item(Some("reference/index.md#quickstart"), vec![])];
+        let nav = Navigation::new(items, [(&id, &reference)], None, None);
+        let resolved = &nav.items[0];
+        assert_eq!(resolved.url.as_deref(), Some("reference/#quickstart"));
+        assert_eq!(resolved.title.as_deref(), Some("Reference"));
+        assert!(resolved.is_index);
+        assert!(!resolved.missing);
+    }
+
+    #[test]
+    fn previous_and_next_page_ignore_fragments() {
+        let nav = nav(vec![
+            item(Some("a/"), vec![]),
+            item(Some("b/"), vec![]),
+            item(Some("b/#usage"), vec![]),
+            item(Some("c/?tab=1"), vec![]),
+        ]);
+        let prev = nav.previous_page(&page("b/")).and_then(|item| item.url);
+        let next = nav.next_page(&page("b/")).and_then(|item| item.url);
+        assert_eq!(prev.as_deref(), Some("a/"));
+        assert_eq!(next.as_deref(), Some("c/?tab=1"));
+        let prev = nav.previous_page(&page("c/")).and_then(|item| item.url);
+        assert_eq!(prev.as_deref(), Some("b/#usage"));
+    }
+
+    #[test]
+    fn previous_and_next_page_skip_external() {
+        let nav = nav(vec![
+            item(Some("a/"), vec![]),
+            external("https://example.com"),
+            item(Some("b/"), vec![]),
+            external("mailto:hello@example.com"),
+        ]);
+        let prev = nav.previous_page(&page("b/")).and_then(|item| item.url);
+        let next = nav.next_page(&page("a/")).and_then(|item| item.url);
+        assert_eq!(prev.as_deref(), Some("a/"));
+        assert_eq!(next.as_deref(), Some("b/"));
+        assert_eq!(nav.next_page(&page("b/")), None);
+    }
+
+    #[test]
+    fn missing_pages_are_skipped() {
+        let mut typo = item(Some("instal.md"), vec![]);
+        typo.missing = true;
+        let nav = nav(vec![
+            item(Some("a/"), vec![]),
+            typo,
+            item(Some("b/"), vec![]),
+        ]);
+        let prev = nav.previous_page(&page("b/")).and_then(|item| item.url);
+        let next = nav.next_page(&page("a/")).and_then(|item| item.url);
+        assert_eq!(prev.as_deref(), Some("a/"));
+        assert_eq!(next.as_deref(), Some("b/"));
+
+        // Missing pages are never active, even if the URL is equivalent
+        let nav = nav.with_active(&page("instal.md"));
+        assert!(nav.items.iter().all(|item| !item.active));
+    }
+
+    #[test]
+    fn unresolved_skips_external() {
+        let mut resolved = item(Some("a/"), vec![]);
+        resolved.meta = Some(PageMeta::default());
+        let nav = nav(vec![
+            resolved,
+            external("https://example.com"),
+            item(Some("missing.md"), vec![]),
+        ]);
+        let unresolved = nav.unresolved().collect::<Vec<_>>();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].url.as_deref(), Some("missing.md"));
+    }
+
+    /// Creates a section with the given title and children.
+    fn section(title: &str, children: Vec<NavigationItem>) -> NavigationItem {
+        NavigationItem {
+            title: Some(title.to_string()),
+            ..item(None, children)
+        }
+    }
+
+    #[test]
+    fn collapse_chains_of_single_children() {
+        let mut tuning = item(Some("guides/advanced/tuning/cache/"), vec![]);
+        tuning.title = Some("Cache".to_string());
+        tuning.title_html = Some("<code>Cache</code>".to_string());
+        let guides = section(
+            "Guides",
+            vec![section("Advanced", vec![section("Tuning", vec![tuning])])],
+        );
+
+        // Collapse with title of innermost child
+        let collapsed = collapse_item(guides.clone(), CollapseTitle::Child);
+        assert_eq!(
+            collapsed.url.as_deref(),
+            Some("guides/advanced/tuning/cache/")
+        );
+        assert_eq!(collapsed.title.as_deref(), Some("Cache"));
+        assert_eq!(collapsed.title_html.as_deref(), Some("<code>Cache</code>"));
+        assert!(collapsed.children.is_empty());
+
+        // Collapse with title of outermost section
+        let collapsed = collapse_item(guides.clone(), CollapseTitle::Parent);
+        assert_eq!(collapsed.title.as_deref(), Some("Guides"));
+        assert_eq!(collapsed.title_html, None);
+
+        // Collapse with joined titles
+        let collapsed = collapse_item(guides, CollapseTitle::Joined);
+        let title = "Guides / Advanced / Tuning / Cache";
+        assert_eq!(collapsed.title.as_deref(), Some(title));
+    }
+
+    #[test]
+    fn collapse_keeps_sections_with_several_children() {
+        let guides = section(
+            "Guides",
+            vec![section(
+                "Advanced",
+                vec![item(Some("a/"), vec![]), item(Some("b/"), vec![])],
+            )],
+        );
+
+        // Only the outer section is collapsed into the inner one
+        let collapsed = collapse_item(guides, CollapseTitle::Parent);
+        assert_eq!(collapsed.title.as_deref(), Some("Guides"));
+        assert_eq!(collapsed.url, None);
+        assert_eq!(collapsed.children.len(), 2);
+    }
+
+    #[test]
+    fn collapse_section_index_pages() {
+        let mut index = item(Some("guide/"), vec![]);
+        index.is_index = true;
+        let other = item(Some("guide/setup/"), vec![]);
+
+        // Section with index page as its only child becomes a page
+        let guide = section("Guide", vec![index.clone()]);
+        let collapsed = collapse_item(guide, CollapseTitle::Parent);
+        assert_eq!(collapsed.url.as_deref(), Some("guide/"));
+        assert_eq!(collapsed.title.as_deref(), Some("Guide"));
+        assert!(!collapsed.is_index);
+
+        // Section with index page and other pages is kept
+        let guide = section("Guide", vec![index, other.clone()]);
+        let collapsed = collapse_item(guide.clone(), CollapseTitle::Parent);
+        assert_eq!(collapsed, guide);
+
+        // Sections linking to pages are never collapsed
+        let mut linked = section("Guide", vec![other]);
+        linked.url = Some("guide/".to_string());
+        assert_eq!(collapse_item(linked.clone(), CollapseTitle::Child), linked);
+    }
+
+    #[test]
+    fn icon_from_page_meta() {
+        let page = page_with_icon("guide/", "lucide/book");
+        let mut guide = item(Some("guide.md"), vec![]);
+        resolve_item(&mut guide, &page);
+        assert_eq!(guide.url.as_deref(), Some("guide/"));
+        assert_eq!(guide.icon.as_deref(), Some("lucide/book"));
+
+        // Homepage outside of navigation carries the icon as well
+        let homepage = find_homepage(&[], Some(&page)).unwrap();
+        assert_eq!(homepage.icon.as_deref(), Some("lucide/book"));
+    }
+
+    #[test]
+    fn icon_from_nav_takes_precedence() {
+        let mut guide = item(Some("guide.md"), vec![]);
+        guide.icon = Some("lucide/star".to_string());
+        guide
+            .attributes
+            .insert("class".to_string(), "featured".to_string());
+        resolve_item(&mut guide, &page_with_icon("guide/", "lucide/book"));
+        assert_eq!(guide.icon.as_deref(), Some("lucide/star"));
+        assert_eq!(guide.attributes["class"], "featured");
+
+        // Pages without icon leave the item without icon
+        let mut about = item(Some("about.md"), vec![]);
+        resolve_item(&mut about, &summary("about/"));
+        assert_eq!(about.icon, None);
+    }
+
+    #[test]
+    fn hash_includes_icon_and_attributes() {
+        let plain = item(Some("guide/"), vec![]);
+        let mut icon = plain.clone();
+        icon.icon = Some("lucide/book".to_string());
+        let mut attributes = plain.clone();
+        attributes
+            .attributes
+            .insert("rel".to_string(), "me".to_string());
+        assert_ne!(hash(&[plain.clone()]), hash(&[icon]));
+        assert_ne!(hash(&[plain]), hash(&[attributes]));
+    }
+
+    #[test]
+    fn hash_is_stable_across_processes() {
+        let mut home = item(Some(""), vec![]);
+        home.title = Some(String::from("Home"));
+        home.is_index = true;
+        home.meta = Some(PageMeta::from([
+            (String::from("icon"), Dynamic::String("lucide/house".into())),
+            (String::from("reading_time"), Dynamic::Integer(3)),
+        ]));
+        let mut setup = item(Some("guide/setup/"), vec![]);
+        setup.title = Some(String::from("Setup"));
+        setup.meta = Some(PageMeta::new());
+        let mut guide = item(None, vec![setup]);
+        guide.title = Some(String::from("Guide"));
+
+        // Golden value, which must only change if the hashed fields change
+        assert_eq!(hash(&[home, guide]), 0x8500_bdee_5efc_304d);
+    }
+
+    #[test]
+    fn hash_includes_nav_meta_only() {
+        let mut plain = item(Some("guide/"), vec![]);
+        plain.meta = Some(PageMeta::new());
+        let with = |key: &str, value: Dynamic| {
+            let mut item = plain.clone();
+            let meta = item.meta.as_mut().unwrap();
+            meta.insert(key.to_string(), value);
+            item
+        };
+
+        // Metadata consumed by navigation templates changes the hash
+        let status = with("status", Dynamic::String("new".into()));
+        assert_ne!(hash(&[plain.clone()]), hash(&[status]));
+
+        // Other metadata and the active state don't change the hash
+        let reading_time = with("reading_time", Dynamic::Integer(5));
+        assert_eq!(hash(&[plain.clone()]), hash(&[reading_time]));
+        let mut active = plain.clone();
+        active.active = true;
+        assert_eq!(hash(&[plain]), hash(&[active]));
+
+        // Order of items changes the hash
+        let a = item(Some("a/"), vec![]);
+        let b = item(Some("b/"), vec![]);
+        assert_ne!(hash(&[a.clone(), b.clone()]), hash(&[b, a]));
+    }
+
+    #[test]
+    fn indexable_skips_noindex_pages() {
+        let mut items = vec![
+            item(Some(""), vec![]),
+            item(Some("draft/"), vec![]),
+            item(Some("legacy/"), vec![]),
+        ];
+        let noindex =
+            PageMeta::from([(String::from("noindex"), Dynamic::Bool(true))]);
+        let robots = PageMeta::from([(
+            String::from("robots"),
+            Dynamic::String(String::from("noindex, follow")),
+        )]);
+        items[1].meta = Some(noindex);
+        items[2].meta = Some(robots);
+
+        // Only the home page can be indexed
+        let nav = nav(items);
+        let urls = nav
+            .indexable()
+            .map(|item| item.url.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(urls, vec![Some("")]);
+        assert_eq!(nav.iter().count(), 3);
+    }
+
+    #[test]
+    fn inject_replaces_items_and_merges_sections() {
+        let posts = section("Posts", vec![item(Some("blog/posts/a/"), vec![])]);
+        let mut index = item(Some("blog/"), vec![]);
+        index.is_index = true;
+        let mut nav =
+            nav(vec![item(Some(""), vec![]), section("Blog", vec![posts])]);
+
+        // Posts are removed, and so is the section that contained them
+        let archive =
+            section("Archive", vec![item(Some("blog/2024/"), vec![])]);
+        let blog = section("Blog", vec![index.clone(), archive.clone()]);
+        nav.inject(blog.clone(), &[String::from("blog/posts/a/")]);
+        assert_eq!(nav.items, vec![item(Some(""), vec![]), blog.clone()]);
+        assert_eq!(nav.hash, hash(&nav.items));
+
+        // Injecting again doesn't duplicate existing items
+        nav.inject(blog.clone(), &[]);
+        assert_eq!(nav.items[1].children, vec![index, archive]);
+    }
+
+    /// Returns the borrowed views of the pages in the given chunk.
+    fn views(pages: &[(Key<Id>, PageSummary)]) -> Vec<(&Id, &PageSummary)> {
+        pages.iter().map(|(id, page)| (&id[0], page)).collect()
+    }
+
+    #[test]
+    fn generate_items_allocations() {
+        let small = chunk(5000, 0);
+        let large = chunk(5000, 32);
+        let (items, count) = allocations(|| generate_items(views(&large)));
+        assert_eq!(items.len(), 50);
+
+        // Metadata is limited to selected keys, so its size doesn't matter
+        let (_, expected) = allocations(|| generate_items(views(&small)));
+        assert_eq!(count, expected);
+
+        // Generating items allocates less than cloning the chunk did before
+        let (_, cloned) = allocations(|| large.clone());
+        assert!(count < cloned, "{count} >= {cloned}");
+        let meta = items[0].children[0].meta.as_ref().unwrap();
+        assert_eq!(meta.keys().collect::<Vec<_>>(), ["icon"]);
+    }
+
+    #[test]
+    fn ancestors_of_allocations() {
+        let pages = chunk(5000, 0);
+        let nav = nav(generate_items(views(&pages)));
+
+        // Borrowing ancestors doesn't clone sections with all their children
+        let (_, owned) = allocations(|| {
+            for (_, page) in &pages {
+                nav.ancestors(&page.url);
+            }
+        });
+        let (_, borrowed) = allocations(|| {
+            for (_, page) in &pages {
+                nav.ancestors_of(&page.url);
+            }
+        });
+        assert!(borrowed * 10 < owned, "{borrowed} * 10 >= {owned}");
+        assert_eq!(nav.ancestors_of(&pages[0].1.url)[0].children.len(), 100);
+    }
+
+    #[test]
+    fn headings_only_affect_hash_of_content_with_autorefs() {
+        let mut nav = nav(vec![item(Some(""), vec![])]);
+        let before = nav.hash;
+        let toc = Arc::from([Section {
+            title: String::from("Setup"),
+            content: String::from("Setup"),
+            id: String::from("setup"),
+            url: String::from("#setup"),
+            children: Vec::new(),
+            level: 2,
+        }]);
+        nav.register_headings(&[(Arc::from("guide/"), toc)]);
+        assert_eq!(nav.hash, before);
+
+        // Only content containing autorefs depends on the headings
+        let content = "<autoref identifier=\"setup\">Setup</autoref>";
+        assert_eq!(nav.hash_for("<p>Home</p>"), before);
+        assert_ne!(nav.hash_for(content), before);
+    }
 }
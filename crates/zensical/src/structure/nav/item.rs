@@ -27,31 +27,53 @@
 
 use pyo3::FromPyObject;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 
 use crate::structure::page::PageMeta;
 
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Metadata keys that navigation templates consume.
+const META_KEYS: [&str; 3] = ["icon", "status", "subtitle"];
+
+/// Metadata keys that determine whether a page can be indexed.
+const INDEX_KEYS: [&str; 2] = ["noindex", "robots"];
+
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
 
 /// Navigation item.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, FromPyObject, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize)]
 #[pyo3(from_item_all)]
 pub struct NavigationItem {
-    /// Item title.
+    /// Item title, as plain text.
     pub title: Option<String>,
+    /// Item title, as HTML, if it's taken from a page.
+    pub title_html: Option<String>,
     /// Item URL.
     pub url: Option<String>,
     /// Item canonical URL.
     pub canonical_url: Option<String>,
     /// Item metadata.
     pub meta: Option<PageMeta>,
+    /// Item icon, from the navigation or the front matter of the page.
+    pub icon: Option<String>,
+    /// Item attributes, from the navigation.
+    pub attributes: BTreeMap<String, String>,
     /// Item children.
     pub children: Vec<NavigationItem>,
     /// Whether this item is an index page.
     pub is_index: bool,
+    /// Whether this item links to an external URL.
+    pub external: bool,
     /// Whether this item is currently active.
     pub active: bool,
+    /// Whether this item links to a page that doesn't exist.
+    pub missing: bool,
 }
 
 // ----------------------------------------------------------------------------
@@ -76,3 +98,55 @@ impl NavigationItem {
             })
     }
 }
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Hash for NavigationItem {
+    /// Hashes the navigation item.
+    ///
+    /// The hash is part of the cache key of rendered pages, so it must only
+    /// include the fields that templates consume. Metadata is limited to the
+    /// keys that navigation templates use, as other keys, e.g., the reading
+    /// time, change whenever the content of a page changes, which would force
+    /// all pages to be rendered again. The active state is set per page after
+    /// the hash is computed, so it's left out as well.
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.title.hash(state);
+        self.title_html.hash(state);
+        self.url.hash(state);
+        self.canonical_url.hash(state);
+        self.icon.hash(state);
+        self.attributes.hash(state);
+        self.is_index.hash(state);
+        self.external.hash(state);
+        self.missing.hash(state);
+        for key in META_KEYS {
+            let meta = self.meta.as_ref();
+            meta.and_then(|meta| meta.get(key)).hash(state);
+        }
+        self.children.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the subset of the given page metadata that navigation items carry.
+///
+/// Page metadata can be quite large, and navigation items are created for all
+/// pages, so only the keys that navigation templates consume, as well as the
+/// keys that determine whether a page can be indexed, are cloned.
+pub fn select_meta(meta: &PageMeta) -> PageMeta {
+    META_KEYS
+        .iter()
+        .chain(&INDEX_KEYS)
+        .filter_map(|key| meta.get_key_value(*key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
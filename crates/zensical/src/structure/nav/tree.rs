@@ -0,0 +1,213 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Navigation tree.
+
+use serde::Serialize;
+
+use crate::structure::page::PageMeta;
+
+use super::item::NavigationItem;
+use super::url;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Navigation tree item.
+///
+/// This is a reduced view of a [`NavigationItem`], which only carries what is
+/// necessary to inspect the resolved navigation, e.g., from Python, where we
+/// can assert on the order of pages without parsing the rendered HTML.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TreeItem {
+    /// Item title.
+    pub title: Option<String>,
+    /// Item URL.
+    pub url: Option<String>,
+    /// Item children.
+    pub children: Vec<TreeItem>,
+    /// Whether this item is an index page.
+    pub is_index: bool,
+    /// Item metadata, limited to the allowed keys.
+    pub meta: Option<PageMeta>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl TreeItem {
+    /// Creates a tree item from the given navigation item.
+    ///
+    /// Navigation items only carry the metadata keys that templates consume,
+    /// so the full metadata of the linked page is obtained via the given
+    /// function, falling back to the metadata of the item. If allowed keys
+    /// are given, metadata is limited to those keys, which is recommended, as
+    /// page metadata can be quite large. Otherwise, all keys are retained.
+    pub fn new<'a, F>(
+        item: &'a NavigationItem, get: &F, allow: Option<&[String]>,
+    ) -> Self
+    where
+        F: Fn(&str) -> Option<&'a PageMeta>,
+    {
+        let meta = item.meta.as_ref().map(|meta| {
+            let url = item.url.as_deref().map(|url| url::split_suffix(url).0);
+            url.and_then(get)
+                .unwrap_or(meta)
+                .iter()
+                .filter(|(key, _)| {
+                    allow.is_none_or(|allow| allow.contains(*key))
+                })
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        });
+        Self {
+            title: item.title.clone(),
+            url: item.url.clone(),
+            children: item
+                .children
+                .iter()
+                .map(|child| Self::new(child, get, allow))
+                .collect(),
+            is_index: item.is_index,
+            meta,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+
+    use crate::structure::dynamic::Dynamic;
+
+    use super::*;
+
+    /// Creates a navigation item with the given URL, metadata and children.
+    fn item(
+        url: Option<&str>, meta: Option<PageMeta>,
+        children: Vec<NavigationItem>,
+    ) -> NavigationItem {
+        NavigationItem {
+            title: url.map(|url| format!("Title of {url}")),
+            title_html: None,
+            url: url.map(ToString::to_string),
+            canonical_url: url.map(|url| format!("https://example.com/{url}")),
+            meta,
+            icon: None,
+            attributes: BTreeMap::new(),
+            children,
+            is_index: url == Some("section/"),
+            external: false,
+            active: false,
+            missing: false,
+        }
+    }
+
+    /// Projects a serialized navigation item onto the keys of a tree item.
+    fn project(value: &Value) -> Value {
+        json!({
+            "title": value["title"],
+            "url": value["url"],
+            "children": value["children"]
+                .as_array()
+                .expect("invariant")
+                .iter()
+                .map(project)
+                .collect::<Vec<_>>(),
+            "is_index": value["is_index"],
+            "meta": value["meta"],
+        })
+    }
+
+    #[test]
+    fn matches_serialized_navigation_item() {
+        let meta = PageMeta::from([
+            ("author".to_string(), Dynamic::String("Jane".to_string())),
+            ("draft".to_string(), Dynamic::Bool(false)),
+        ]);
+        let nav = item(
+            None,
+            None,
+            vec![
+                item(Some("section/"), Some(meta.clone()), vec![]),
+                item(Some("section/page/"), Some(meta), vec![]),
+            ],
+        );
+
+        let expected = project(&serde_json::to_value(&nav).unwrap());
+        let tree = TreeItem::new(&nav, &|_| None, None);
+        let actual = serde_json::to_value(tree).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn limits_metadata_to_allowed_keys() {
+        let meta = PageMeta::from([
+            ("author".to_string(), Dynamic::String("Jane".to_string())),
+            ("draft".to_string(), Dynamic::Bool(false)),
+        ]);
+        let nav = item(Some("page/"), Some(meta), vec![]);
+
+        let allow = ["author".to_string()];
+        let tree = TreeItem::new(&nav, &|_| None, Some(&allow));
+        assert_eq!(
+            serde_json::to_value(tree.meta).unwrap(),
+            json!({ "author": "Jane" })
+        );
+    }
+
+    #[test]
+    fn takes_metadata_from_linked_page() {
+        let page = PageMeta::from([
+            ("author".to_string(), Dynamic::String("Jane".to_string())),
+            (
+                "icon".to_string(),
+                Dynamic::String("lucide/book".to_string()),
+            ),
+        ]);
+        let selected = PageMeta::from([(
+            "icon".to_string(),
+            Dynamic::String("lucide/book".to_string()),
+        )]);
+        let nav = item(
+            None,
+            None,
+            vec![item(Some("page/#usage"), Some(selected), vec![])],
+        );
+
+        // Metadata is looked up without fragment, and only for resolved items
+        let get = |url: &str| (url == "page/").then_some(&page);
+        let tree = TreeItem::new(&nav, &get, None);
+        assert_eq!(tree.meta, None);
+        assert_eq!(tree.children[0].meta.as_ref(), Some(&page));
+    }
+}
@@ -25,24 +25,56 @@
 
 //! Page.
 
-use minijinja::{context, Error};
+use fluent_uri::Uri;
+use minijinja::{context, Error, Value as TemplateValue};
 use pyo3::FromPyObject;
 use serde::Serialize;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
-use zensical_serve::http::Uri;
+use std::sync::Arc;
 use zrx::id::Id;
 use zrx::scheduler::Value;
 
-use crate::config::Config;
+use crate::config::{Config, Project};
 use crate::template::{Output, Template, GENERATOR};
+use crate::workflow::Cancellation;
 
-use super::dynamic::Dynamic;
-use super::markdown::Markdown;
+use super::blog::Listing;
+use super::dynamic::{Date, Dynamic};
+use super::i18n::Localization;
+use super::markdown::{Markdown, MarkdownConverter};
 use super::nav::{Navigation, NavigationItem};
 use super::search::SearchItem;
 use super::tag::Tag;
 use super::toc::Section;
+use super::url;
+
+mod hashes;
+mod ids;
+mod images;
+mod leaks;
+mod links;
+mod reading;
+mod summary;
+
+use hashes::embed_content_hash;
+pub use hashes::{content_hash, ContentHashes};
+pub use ids::duplicate_ids;
+pub use images::process_images;
+pub use leaks::leaked_paths;
+pub use links::{
+    absolute_links, backslash_links, prefix_absolute_links,
+    relativize_absolute_links,
+};
+use reading::{reading_time, word_count};
+pub use summary::PageSummary;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Front matter keys holding dates.
+const DATE_KEYS: [&str; 3] = ["date", "created", "updated"];
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -64,14 +96,22 @@ pub struct Page {
     pub canonical_url: Option<String>,
     /// Page edit URL.
     pub edit_url: Option<String>,
-    /// Page title.
+    /// Page title, as plain text.
     pub title: String,
+    /// Page title, as HTML.
+    pub title_html: String,
     /// Page metadata.
     pub meta: PageMeta,
-    /// Page file system path.
+    /// Page file system path, which is absolute, and thus not exposed to
+    /// templates, as it must never leak into the output.
+    #[serde(skip)]
     pub path: String,
+    /// Page source path, relative to the docs directory.
+    pub src_path: String,
     /// Page content.
     pub content: String,
+    /// Stable hash of page content.
+    pub content_hash: String,
     /// Table of contents.
     pub toc: Vec<Section>,
     /// Search index.
@@ -82,6 +122,12 @@ pub struct Page {
     pub previous_page: Option<NavigationItem>,
     /// Next page.
     pub next_page: Option<NavigationItem>,
+    /// Number of words.
+    pub word_count: usize,
+    /// Estimated reading time in minutes.
+    pub reading_time: usize,
+    /// Tags.
+    pub tags: Vec<Tag>,
 }
 
 // ----------------------------------------------------------------------------
@@ -89,9 +135,26 @@ pub struct Page {
 // ----------------------------------------------------------------------------
 
 impl Page {
-    /// Creates a page.
+    /// Creates a page from the given Markdown content.
+    ///
+    /// The content is converted with the given converter, resolving links
+    /// relative to the URL derived from the location of the page, and the
+    /// page is then created from the result, see [`Page::from_markdown`].
+    pub fn new(
+        config: &Config, converter: &dyn MarkdownConverter, id: &Id,
+        content: String, cancellation: &Cancellation,
+    ) -> anyhow::Result<Page> {
+        let site_dir = &config.project.site_dir;
+        let use_directory_urls = config.project.use_directory_urls;
+        let (_, url) = to_location(id, site_dir, use_directory_urls);
+        let markdown =
+            Markdown::new(converter, id, url, content, cancellation)?;
+        Ok(Self::from_markdown(config, id, markdown))
+    }
+
+    /// Creates a page from converted Markdown.
     #[allow(clippy::similar_names)]
-    pub fn new(config: &Config, id: &Id, markdown: Markdown) -> Page {
+    pub fn from_markdown(config: &Config, id: &Id, markdown: Markdown) -> Page {
         let root_dir = config.get_root_dir();
 
         // Retrieve site directory and URL
@@ -104,58 +167,25 @@ impl Page {
 
         // Determine whether to use directory URLs
         let use_directory_urls = config.project.use_directory_urls;
-        let file_uri = id.location().into_owned();
-
-        // Create identifier builder, as we need to change the context in order
-        // to copy the file over to the site directory
-        let builder = id.to_builder().context(&site_dir);
-        let id = builder.clone().build().expect("invariant");
-
-        // Next, obtain the path, and check whether it is an index file, which
-        // is true for index.md, as well as README.md, as MkDocs handles both
-        let mut path: PathBuf = id.location().to_string().into();
-        let is_index =
-            path.ends_with("index.md") || path.ends_with("README.md");
-
-        // Ensure that README.md files are treated as index files
-        if path.ends_with("README.md") {
-            path.pop();
-            path = path.join("index.md");
-        }
+        let file_uri = id.location().replace('\\', "/");
 
-        // If directory URLs should not be used, and the page is an index page,
-        // we need to adjust the path accordingly
-        if !use_directory_urls || is_index {
-            path.set_extension("html");
-        } else {
-            path.set_extension("");
-            path.push("index.html");
-        }
-
-        // Set computed path in id, and compute final target path - once we add
-        // more convenience function to the id crate, we can make this shorter
-        let path = path.to_string_lossy().into_owned();
-        let id = builder
-            .location(path.replace('\\', "/"))
-            .build()
-            .expect("invariant");
-
-        // Compute URL of page, and strip the index.html suffix in case
-        // directory URLs should be used. The URL is relative.
-        let url = id.as_uri().to_string();
-        let url = if use_directory_urls {
-            url.trim_end_matches("index.html").to_string()
-        } else {
-            url
+        // Compute identifier in site directory and URL of page, which might
+        // be given by a permalink, e.g., to decouple URLs from file names
+        let (id, url) = match config.permalinks.resolve(id, &markdown.meta) {
+            Some(path) => {
+                to_permalink(id, &site_dir, &path, use_directory_urls)
+            }
+            None => to_location(id, &site_dir, use_directory_urls),
         };
 
-        // Ensure path encoding, and compute canonical URL. Note that we should
-        // definitely rethink this interface, it's a little inconvenient
-        let url = Uri::from(url.as_ref()).to_string();
-        let canonical_url = site_url.as_ref().map(|base| {
-            let base = base.trim_end_matches('/');
-            format!("{base}/{url}")
-        });
+        // Ensure path encoding, as URLs are percent-encoded at the output
+        // boundary, and compute canonical URL from the encoded URL
+        let url = url::encode(&url);
+        let canonical_url = site_url.as_ref().map(|base| url::join(base, &url));
+
+        // Pages duplicating content from another site might point their
+        // canonical URL there, which replaces the computed canonical URL
+        let canonical_url = to_canonical_url(canonical_url, &markdown.meta);
 
         // Compute edit URL - edit URIs can be relative or absolute, as both
         // variants are supported by MkDocs, so we mirror behavior for now
@@ -169,6 +199,33 @@ impl Page {
             })
         });
 
+        // Compute word count and reading time, if enabled, which might also be
+        // added to page metadata, unless explicitly set by the author
+        let plugin = &config.project.plugins.reading_time.config;
+        let mut meta = markdown.meta;
+        let (words, minutes) = if plugin.enabled {
+            let words = word_count(&markdown.content, plugin.count_code);
+            (words, reading_time(words, plugin.words_per_minute))
+        } else {
+            (0, 0)
+        };
+        if plugin.enabled && plugin.meta {
+            for (key, value) in
+                [("word_count", words), ("reading_time", minutes)]
+            {
+                let value = i64::try_from(value).unwrap_or(i64::MAX);
+                meta.entry(key.to_string())
+                    .or_insert(Dynamic::Integer(value));
+            }
+        }
+
+        // Expose `noindex: true` as a robots directive, so the theme emits the
+        // corresponding meta tag, unless the author set one explicitly
+        if meta.get("noindex") == Some(&Dynamic::Bool(true)) {
+            meta.entry(String::from("robots"))
+                .or_insert(Dynamic::String(String::from("noindex")));
+        }
+
         // Return page - note that ancestors, as well as previous and next
         // pages are populated when the navigation is created. This is also a
         // hint that it's not a good idea to centralize all propeties in a
@@ -177,19 +234,34 @@ impl Page {
         Page {
             url,
             title: markdown.title,
-            meta: markdown.meta,
+            title_html: markdown.title_html,
+            meta,
             canonical_url,
             edit_url,
+            content_hash: content_hash(&markdown.content),
             content: markdown.content,
             toc: markdown.toc,
             search: markdown.search,
             path: path.to_string_lossy().into_owned(),
+            src_path: file_uri,
             ancestors: Vec::new(),
             previous_page: None,
             next_page: None,
+            word_count: words,
+            reading_time: minutes,
+            tags: markdown.tags,
         }
     }
 
+    /// Returns the stable identifier of the page, if any.
+    ///
+    /// The identifier is taken from the `id` front matter key, and is retained
+    /// when the page is renamed or moved.
+    #[inline]
+    pub fn stable_id(&self) -> Option<String> {
+        ids::stable_id(&self.meta)
+    }
+
     /// Renders the page.
     #[cfg_attr(
         feature = "tracing",
@@ -198,44 +270,119 @@ impl Page {
     pub fn render(
         &mut self, config: &Config, nav: Navigation,
     ) -> Result<Output, Error> {
+        let (template, nav, context) = self.prepare(config, nav, None);
+        let output = template.render_with_context(context)?;
+        Ok(self.finish(config, &nav, output))
+    }
+
+    /// Renders the page as the given blog listing.
+    ///
+    /// The page is rendered with the template of the listing, unless set in
+    /// front matter, and receives its posts and pagination, so the content of
+    /// the page introduces the posts, e.g., on the blog index.
+    pub fn render_listing(
+        &mut self, config: &Config, nav: Navigation, listing: &Listing,
+    ) -> Result<Output, Error> {
+        let (template, nav, context) = self.prepare(config, nav, Some(listing));
+        let output = template.render_with_context(context)?;
+        Ok(self.finish(config, &nav, output))
+    }
+
+    /// Renders a single block of the page's template.
+    ///
+    /// The block is rendered with the same context as the entire page, which
+    /// allows to serve partial HTML, e.g., only the `content` block. If the
+    /// page is rendered as a blog listing, the listing must be given.
+    pub fn render_block(
+        &mut self, config: &Config, nav: Navigation, listing: Option<&Listing>,
+        block: &str,
+    ) -> Result<String, Error> {
+        let (template, nav, context) = self.prepare(config, nav, listing);
+        let output = template.render_block(block, context)?;
+
+        // Replace autorefs, if any
+        Ok(nav.autorefs.replace_in(output, &self.url))
+    }
+
+    /// Prepares the template, navigation and context for rendering.
+    ///
+    /// If a blog listing is given, its template is used, unless set in front
+    /// matter, and its posts and pagination are added to the context.
+    fn prepare(
+        &mut self, config: &Config, nav: Navigation, listing: Option<&Listing>,
+    ) -> (Template<'static>, Navigation, TemplateValue) {
         let name = self.meta.get("template").map(ToString::to_string);
+        let fallback = listing.map_or("main.html", Listing::template);
         let template = Template::new(
-            name.unwrap_or(String::from("main.html")),
+            name.unwrap_or_else(|| String::from(fallback)),
             config.theme_dirs.clone(),
         );
 
         // Set active page in navigation and compute ancestors, as well as next
-        // and previous page, all of which we need for rendering navigation
+        // and previous page, all of which we need for rendering navigation.
+        // Previous and next page are always computed, as templates might use
+        // them outside of the footer, e.g., for `rel="prev"` links
+        let features = &config.project.theme.features;
         let nav = nav.with_active(self);
-        self.ancestors = nav.ancestors(self);
+        self.ancestors = nav.ancestors(&self.url);
         self.previous_page = nav.previous_page(self);
         self.next_page = nav.next_page(self);
 
-        // Create context and render template
-        let output = template.render_with_context(context! {
+        // Compute localization of page, if internationalization is enabled
+        let i18n = &config.project.plugins.i18n.config;
+        let localization =
+            Localization::new(i18n, &nav.translations, &self.url);
+
+        // Merge page-level extra settings over global extra settings, so pages
+        // can override values like announcement banners or version pickers
+        let project = match self.meta.get("extra") {
+            Some(extra) => {
+                let mut project = Project::clone(&config.project);
+                project.extra.merge(extra.clone());
+                Arc::new(project)
+            }
+            None => config.project.clone(),
+        };
+
+        // Create context
+        let context = context! {
             generator => GENERATOR,
+            i18n => localization,
             nav => nav,
             base_url => config.get_base_url(&self.url),
             extra_css => config.project.extra_css.clone(),
             extra_javascript => config.project.extra_javascript.clone(),
-            config => config.project.clone(),
-            tags => self.tags(),
+            config => project,
+            features => features.flags(),
+            tags => self.tags.clone(),
             page => self,
-        })?;
+            ..TemplateValue::from_serialize(&config.project.extra_context)
+        };
 
-        // Replace autorefs, if any
-        Ok(Output::from(nav.autorefs.replace_in(output, &self.url)))
+        // Add posts and pagination, if the page is rendered as a listing
+        let context = match listing {
+            Some(listing) => context! {
+                posts => listing.posts,
+                pagination => listing.pagination,
+                ..context
+            },
+            None => context,
+        };
+        (template, nav, context)
     }
 
-    /// Returns the tags of the page.
-    pub fn tags(&self) -> Vec<Tag> {
-        let mut tags = Vec::new();
-        if let Some(Dynamic::List(values)) = self.meta.get("tags") {
-            for name in values {
-                tags.push(Tag { name: name.to_string() });
-            }
+    /// Finishes the rendered output of the page.
+    fn finish(
+        &self, config: &Config, nav: &Navigation, mut output: String,
+    ) -> Output {
+        // Embed content hash, if enabled, so clients implementing instant
+        // navigation can tell whether the content of a page changed
+        if config.project.build.content_hashes {
+            output = embed_content_hash(output, &self.content_hash);
         }
-        tags
+
+        // Replace autorefs, if any
+        Output::from(nav.autorefs.replace_in(output, &self.url))
     }
 }
 
@@ -245,9 +392,384 @@ impl Page {
 
 impl Value for Page {}
 
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the canonical URL of a page, honoring the front matter.
+///
+/// The `canonical_url` front matter key replaces the computed canonical URL,
+/// but only if it's an absolute URL, as search engines ignore all others. If
+/// it's not, the computed canonical URL is kept, and the value is reported by
+/// the workflow, see [`invalid_canonical_url`].
+pub fn to_canonical_url(
+    canonical_url: Option<String>, meta: &PageMeta,
+) -> Option<String> {
+    match meta.get("canonical_url") {
+        Some(Dynamic::String(value)) if is_absolute_url(value) => {
+            Some(value.clone())
+        }
+        _ => canonical_url,
+    }
+}
+
+/// Returns the `canonical_url` front matter value, if it's not an absolute URL.
+pub fn invalid_canonical_url(meta: &PageMeta) -> Option<&Dynamic> {
+    meta.get("canonical_url").filter(|value| {
+        !matches!(value, Dynamic::String(value) if is_absolute_url(value))
+    })
+}
+
+/// Returns whether the given value is an absolute HTTP(S) URL with a host.
+fn is_absolute_url(value: &str) -> bool {
+    Uri::parse(value).is_ok_and(|uri| {
+        matches!(uri.scheme().as_str(), "http" | "https")
+            && uri.authority().is_some_and(|auth| !auth.host().is_empty())
+    })
+}
+
+/// Returns whether the page with the given metadata opts out of indexing.
+///
+/// Pages can opt out via `noindex: true`, or a robots directive containing
+/// `noindex` or `none` in their front matter, e.g., `robots: noindex, follow`.
+pub fn is_noindex(meta: &PageMeta) -> bool {
+    match (meta.get("noindex"), meta.get("robots")) {
+        (Some(Dynamic::Bool(true)), _) => true,
+        (_, Some(Dynamic::String(robots))) => {
+            robots.split(',').map(str::trim).any(|directive| {
+                directive.eq_ignore_ascii_case("noindex")
+                    || directive.eq_ignore_ascii_case("none")
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Parses dates in the given page metadata into typed values.
+///
+/// Values of the `date`, `created` and `updated` keys are parsed, including
+/// those nested in a `date` map, as used by blogs, e.g., `date.created`. The
+/// keys of values in unknown formats are returned, so they can be reported,
+/// and the values are kept as strings, so templates can still render them.
+pub fn parse_dates(meta: &mut PageMeta) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut parse = |key: String, value: &mut Dynamic| {
+        if let Dynamic::String(raw) = value {
+            match Date::parse(raw) {
+                Some(date) => *value = Dynamic::Date(date),
+                None => unknown.push(key),
+            }
+        }
+    };
+
+    // Parse top-level keys, and keys nested in a date map
+    for key in DATE_KEYS {
+        match meta.get_mut(key) {
+            Some(Dynamic::Map(values)) if key == "date" => {
+                for nested in DATE_KEYS {
+                    if let Some(value) = values.get_mut(nested) {
+                        parse(format!("{key}.{nested}"), value);
+                    }
+                }
+            }
+            Some(value) => parse(key.to_string(), value),
+            None => {}
+        }
+    }
+    unknown
+}
+
+/// Returns the date of the page with the given metadata, if any.
+///
+/// This is the date used for ordering pages, e.g., in blogs and feeds, which
+/// is the creation date, taken from `date`, `date.created` or `created`.
+pub fn page_date(meta: &PageMeta) -> Option<&Date> {
+    let value = match meta.get("date") {
+        Some(Dynamic::Map(values)) => values.get("created"),
+        Some(value) => Some(value),
+        None => meta.get("created"),
+    };
+    value.and_then(Dynamic::as_date)
+}
+
+/// Computes the identifier in the site directory and the URL of a page.
+///
+/// This mirrors the behavior of MkDocs: `index.md` and `README.md` files are
+/// treated as index pages. If directory URLs are used, all other pages are
+/// moved into a folder of the same name, and the `index.html` suffix is
+/// stripped from the URL. Otherwise, index pages consistently keep their
+/// `index.html` suffix, so the URL always points to the generated file.
+/// The returned URL is relative and not yet percent-encoded.
+pub fn to_location(
+    id: &Id, site_dir: &str, use_directory_urls: bool,
+) -> (Id, String) {
+    // Create identifier builder, as we need to change the context in order to
+    // map the file to the site directory
+    let builder = id.to_builder().context(site_dir);
+    let id = builder.clone().build().expect("invariant");
+
+    // Next, obtain the path, and check whether it is an index file, which is
+    // true for index.md, as well as README.md, as MkDocs handles both
+    let mut path: PathBuf = id.location().to_string().into();
+    let is_index = path.ends_with("index.md") || path.ends_with("README.md");
+
+    // Ensure that README.md files are treated as index files
+    if path.ends_with("README.md") {
+        path.pop();
+        path = path.join("index.md");
+    }
+
+    // If directory URLs should not be used, or the page is an index page, we
+    // just replace the extension, otherwise we move the page into a folder
+    if !use_directory_urls || is_index {
+        path.set_extension("html");
+    } else {
+        path.set_extension("");
+        path.push("index.html");
+    }
+
+    // Set computed path in id - once we add more convenience function to the
+    // id crate, we can make this shorter
+    let path = path.to_string_lossy().into_owned();
+    let id = builder
+        .location(path.replace('\\', "/"))
+        .build()
+        .expect("invariant");
+
+    // Compute URL of page, and strip the index.html suffix exactly once in case
+    // directory URLs should be used. The URL is relative.
+    let url = id.as_uri().to_string();
+    let url = if use_directory_urls {
+        url.strip_suffix("index.html")
+            .map(ToString::to_string)
+            .unwrap_or(url)
+    } else {
+        url
+    };
+
+    // Return identifier and URL
+    (id, url)
+}
+
+/// Computes the identifier in the site directory and the URL of a permalink.
+///
+/// The path is relative to the site directory, and is treated like the path
+/// of a page without extension, i.e., if directory URLs are used, the page is
+/// written to `index.html` in a folder of the same name. An empty path maps
+/// to the index page of the site.
+pub fn to_permalink(
+    id: &Id, site_dir: &str, path: &str, use_directory_urls: bool,
+) -> (Id, String) {
+    let (location, url) = if path.is_empty() {
+        let url = if use_directory_urls { "" } else { "index.html" };
+        (String::from("index.html"), url.to_string())
+    } else if use_directory_urls {
+        (format!("{path}/index.html"), format!("{path}/"))
+    } else {
+        (format!("{path}.html"), format!("{path}.html"))
+    };
+
+    // Create identifier in site directory
+    let id = id
+        .to_builder()
+        .context(site_dir)
+        .location(location)
+        .build()
+        .expect("invariant");
+    (id, url)
+}
+
 // ----------------------------------------------------------------------------
 // Type alises
 // ----------------------------------------------------------------------------
 
 /// Page metadata.
 pub type PageMeta = BTreeMap<String, Dynamic>;
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use zrx::id::Id;
+
+    use crate::structure::dynamic::Dynamic;
+
+    use super::{
+        invalid_canonical_url, is_noindex, page_date, parse_dates,
+        to_canonical_url, to_location, to_permalink, PageMeta,
+    };
+
+    /// Creates an identifier for the given location in the docs directory.
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn to_location_matches_mkdocs() {
+        // Source file, directory URLs, URL and file, as documented by MkDocs
+        let fixtures = [
+            ("index.md", true, "", "index.html"),
+            ("README.md", true, "", "index.html"),
+            ("api-guide.md", true, "api-guide/", "api-guide/index.html"),
+            ("about/index.md", true, "about/", "about/index.html"),
+            ("about/README.md", true, "about/", "about/index.html"),
+            (
+                "about/license.md",
+                true,
+                "about/license/",
+                "about/license/index.html",
+            ),
+            ("index.md", false, "index.html", "index.html"),
+            ("README.md", false, "index.html", "index.html"),
+            ("api-guide.md", false, "api-guide.html", "api-guide.html"),
+            (
+                "about/index.md",
+                false,
+                "about/index.html",
+                "about/index.html",
+            ),
+            (
+                "about/README.md",
+                false,
+                "about/index.html",
+                "about/index.html",
+            ),
+            (
+                "about/license.md",
+                false,
+                "about/license.html",
+                "about/license.html",
+            ),
+        ];
+        for (location, use_directory_urls, url, file) in fixtures {
+            let (id, res) =
+                to_location(&id(location), "site", use_directory_urls);
+            assert_eq!(res, url, "{location} ({use_directory_urls})");
+            assert_eq!(
+                id.location(),
+                file,
+                "{location} ({use_directory_urls})"
+            );
+        }
+    }
+
+    #[test]
+    fn to_location_strips_index_suffix_once() {
+        let (_, url) = to_location(&id("myindex.md"), "site", true);
+        assert_eq!(url, "myindex/");
+    }
+
+    #[test]
+    fn to_permalink_respects_directory_urls() {
+        let id = id("blog/2024-05-21-slug.md");
+        let (res, url) = to_permalink(&id, "site", "blog/2024/05/slug", true);
+        assert_eq!(url, "blog/2024/05/slug/");
+        assert_eq!(res.location(), "blog/2024/05/slug/index.html");
+        let (res, url) = to_permalink(&id, "site", "blog/2024/05/slug", false);
+        assert_eq!(url, "blog/2024/05/slug.html");
+        assert_eq!(res.location(), "blog/2024/05/slug.html");
+    }
+
+    /// Creates page metadata from the given key and value.
+    fn meta(key: &str, value: Dynamic) -> PageMeta {
+        PageMeta::from([(key.to_string(), value)])
+    }
+
+    #[test]
+    fn canonical_url_defaults_to_computed() {
+        let computed = Some(String::from("https://example.com/about/"));
+        let canonical = to_canonical_url(computed.clone(), &PageMeta::new());
+        assert_eq!(canonical, computed);
+    }
+
+    #[test]
+    fn canonical_url_from_front_matter() {
+        let computed = Some(String::from("https://example.com/about/"));
+        let value = Dynamic::String(String::from("https://other.org/about/"));
+        let front_matter = meta("canonical_url", value);
+        let canonical = to_canonical_url(computed, &front_matter);
+        assert_eq!(canonical.as_deref(), Some("https://other.org/about/"));
+        assert!(invalid_canonical_url(&front_matter).is_none());
+
+        // Override also applies if no site URL is set
+        let value = Dynamic::String(String::from("https://other.org/"));
+        let canonical = to_canonical_url(None, &meta("canonical_url", value));
+        assert_eq!(canonical.as_deref(), Some("https://other.org/"));
+    }
+
+    #[test]
+    fn canonical_url_must_be_absolute() {
+        let computed = Some(String::from("https://example.com/about/"));
+        for value in [
+            Dynamic::String(String::from("/about/")),
+            Dynamic::String(String::from("mailto:hello@example.com")),
+            Dynamic::Bool(true),
+        ] {
+            let front_matter = meta("canonical_url", value);
+            let canonical = to_canonical_url(computed.clone(), &front_matter);
+            assert_eq!(canonical, computed);
+            assert!(invalid_canonical_url(&front_matter).is_some());
+        }
+    }
+
+    #[test]
+    fn noindex_from_front_matter() {
+        assert!(!is_noindex(&PageMeta::new()));
+        assert!(is_noindex(&meta("noindex", Dynamic::Bool(true))));
+        assert!(!is_noindex(&meta("noindex", Dynamic::Bool(false))));
+        for robots in ["noindex", "NoIndex, follow", "none"] {
+            let value = Dynamic::String(robots.to_string());
+            assert!(is_noindex(&meta("robots", value)));
+        }
+        let value = Dynamic::String(String::from("index, nofollow"));
+        assert!(!is_noindex(&meta("robots", value)));
+    }
+
+    #[test]
+    fn parse_dates_from_front_matter() {
+        let string = |value: &str| Dynamic::String(value.to_string());
+        let mut meta = PageMeta::from([
+            (String::from("date"), string("2024-01-02")),
+            (String::from("updated"), string("2024-01-03T10:00:00+01:00")),
+            (String::from("created"), string("next week")),
+            (String::from("title"), string("2024-01-04")),
+        ]);
+        assert_eq!(parse_dates(&mut meta), ["created"]);
+        assert!(matches!(meta["date"], Dynamic::Date(_)));
+        assert!(matches!(meta["updated"], Dynamic::Date(_)));
+
+        // Values in unknown formats and other keys are kept as they are
+        assert_eq!(meta["created"], string("next week"));
+        assert_eq!(meta["title"], string("2024-01-04"));
+        assert_eq!(meta["date"].to_string(), "2024-01-02");
+        let date = page_date(&meta).unwrap();
+        assert_eq!(date.timestamp(), 1_704_153_600);
+    }
+
+    #[test]
+    fn parse_dates_nested_in_date_map() {
+        let nested = Dynamic::Map(
+            [
+                (
+                    String::from("created"),
+                    Dynamic::String("2024-01-02".into()),
+                ),
+                (
+                    String::from("updated"),
+                    Dynamic::String("2024-13-01".into()),
+                ),
+            ]
+            .into(),
+        );
+        let mut meta = PageMeta::from([(String::from("date"), nested)]);
+        assert_eq!(parse_dates(&mut meta), ["date.updated"]);
+        assert_eq!(page_date(&meta).map(|date| date.ymd()), Some((2024, 1, 2)));
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Content hashes.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use crate::hash::stable_hash;
+
+use super::PageSummary;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Name of the meta tag carrying the content hash of a page.
+const META_NAME: &str = "zensical:content-hash";
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Content hashes of all pages.
+///
+/// Clients implementing instant navigation can use the mapping of page URLs to
+/// content hashes to decide whether a page changed, before fetching it. Pages
+/// are sorted by URL, so the output is reproducible, and looks as follows:
+///
+/// ```json
+/// {
+///   "path/to/page/": "9f1c3b0e4d2a7c85"
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ContentHashes(BTreeMap<Arc<str>, Arc<str>>);
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl ContentHashes {
+    /// Creates content hashes from the given pages.
+    pub fn new(pages: &[(Key<Id>, PageSummary)]) -> Self {
+        let iter = pages
+            .iter()
+            .map(|(_, page)| (page.url.clone(), page.content_hash.clone()));
+        Self(iter.collect())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Computes the content hash of the given page content.
+///
+/// The hash is computed with the stable hasher, so it's identical across builds
+/// and platforms, and rendered as 16 hexadecimal digits.
+pub fn content_hash(content: &str) -> String {
+    format!("{:016x}", stable_hash(content))
+}
+
+/// Embeds the given content hash as a meta tag into the given page.
+///
+/// The meta tag is inserted right before the closing `head` tag, so it doesn't
+/// depend on the theme. Pages without a `head` are returned unchanged.
+pub fn embed_content_hash(html: String, hash: &str) -> String {
+    let Some(index) = html.find("</head>") else {
+        return html;
+    };
+
+    // Insert meta tag before closing head tag
+    let tag = format!("<meta name=\"{META_NAME}\" content=\"{hash}\">");
+    let mut output = String::with_capacity(html.len() + tag.len());
+    output.push_str(&html[..index]);
+    output.push_str(&tag);
+    output.push_str(&html[index..]);
+    output
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use zrx::id::Id;
+    use zrx::scheduler::Key;
+
+    use crate::structure::page::{PageMeta, PageSummary};
+
+    use super::{content_hash, embed_content_hash, ContentHashes};
+
+    /// Creates a summary with the given URL and content.
+    fn summary(url: &str, content: &str) -> (Key<Id>, PageSummary) {
+        let id = Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(format!("{url}index.md"))
+            .build()
+            .unwrap();
+        let summary = PageSummary {
+            url: Arc::from(url),
+            canonical_url: None,
+            title: Arc::from(url),
+            title_html: Arc::from(url),
+            meta: PageMeta::default(),
+            path: Arc::from(format!("site/{url}index.html")),
+            src_path: Arc::from(format!("{url}index.md")),
+            content_hash: Arc::from(content_hash(content)),
+            toc: Arc::from([]),
+            search: Arc::from([]),
+            tags: Vec::new(),
+        };
+        (Key::from_iter([id]), summary)
+    }
+
+    #[test]
+    fn content_hash_is_stable() {
+        assert_eq!(content_hash("<p>Hello</p>"), content_hash("<p>Hello</p>"));
+        assert_ne!(content_hash("<p>Hello</p>"), content_hash("<p>World</p>"));
+        assert_eq!(content_hash("").len(), 16);
+        assert_eq!(content_hash(""), "af64724c8602eb6e");
+    }
+
+    #[test]
+    fn embeds_content_hash_into_head() {
+        let html = "<html><head><title>A</title></head><body></body></html>";
+        assert_eq!(
+            embed_content_hash(html.to_string(), "0123456789abcdef"),
+            "<html><head><title>A</title>\
+             <meta name=\"zensical:content-hash\" content=\"0123456789abcdef\">\
+             </head><body></body></html>"
+        );
+
+        // Pages without head are returned unchanged
+        let html = "<p>Partial</p>";
+        assert_eq!(embed_content_hash(html.to_string(), "0123"), html);
+    }
+
+    #[test]
+    fn editing_content_changes_only_its_hash() {
+        let pages = vec![
+            summary("", "<p>Home</p>"),
+            summary("guide/", "<p>Guide</p>"),
+            summary("about/", "<p>About</p>"),
+        ];
+        let before = ContentHashes::new(&pages);
+
+        // Edit the content of a single page
+        let mut edited = pages.clone();
+        edited[1] = summary("guide/", "<p>Guide, edited</p>");
+        let after = ContentHashes::new(&edited);
+
+        // Only the hash of the edited page changes
+        assert_ne!(before.0.get("guide/"), after.0.get("guide/"));
+        assert_eq!(before.0.get(""), after.0.get(""));
+        assert_eq!(before.0.get("about/"), after.0.get("about/"));
+        assert_eq!(
+            serde_json::to_value(&after).unwrap()["guide/"],
+            content_hash("<p>Guide, edited</p>")
+        );
+    }
+}
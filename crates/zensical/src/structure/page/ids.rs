@@ -0,0 +1,154 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Stable page identifiers.
+
+use std::collections::BTreeMap;
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use super::{PageMeta, PageSummary};
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Front matter key for stable page identifiers.
+const KEY: &str = "id";
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the stable identifier from the given page metadata, if any.
+///
+/// Pages can define a stable identifier via `id` in front matter, which is
+/// retained when the page is renamed or moved, so caches and links can follow
+/// the page to its new location. The `slug` is not an identifier, as it only
+/// determines the URL, e.g., of blog posts, and isn't required to be unique.
+pub fn stable_id(meta: &PageMeta) -> Option<String> {
+    meta.get(KEY)
+        .map(ToString::to_string)
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns all stable identifiers that are used by more than one page.
+///
+/// Each duplicate is returned with the source locations of the first and the
+/// conflicting page, which are ordered by location, so the result is stable.
+pub fn duplicate_ids(
+    pages: &[(Key<Id>, PageSummary)],
+) -> Vec<(String, String, String)> {
+    let mut pages = pages
+        .iter()
+        .filter_map(|(id, page)| {
+            let location = id[0].location().into_owned();
+            page.stable_id().map(|stable| (location, stable))
+        })
+        .collect::<Vec<_>>();
+    pages.sort();
+
+    // Record the first page for each identifier, and report all others
+    let mut seen = BTreeMap::new();
+    let mut duplicates = Vec::new();
+    for (location, stable) in pages {
+        match seen.get(&stable) {
+            Some(first) => {
+                duplicates.push((stable.clone(), first.clone(), location));
+            }
+            None => {
+                seen.insert(stable, location);
+            }
+        }
+    }
+    duplicates
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::structure::dynamic::Dynamic;
+
+    use super::*;
+
+    /// Creates a page with the given stable identifier.
+    fn page(location: &str, id: Option<&str>) -> (Key<Id>, PageSummary) {
+        let key = Key::from_iter([Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .unwrap()]);
+        let mut meta = PageMeta::new();
+        if let Some(id) = id {
+            meta.insert("id".into(), Dynamic::String(id.into()));
+        }
+        let page = PageSummary {
+            url: "".into(),
+            canonical_url: None,
+            title: "".into(),
+            title_html: "".into(),
+            meta,
+            path: "".into(),
+            src_path: "".into(),
+            content_hash: "".into(),
+            toc: Arc::from([]),
+            search: Arc::from([]),
+            tags: Vec::new(),
+        };
+        (key, page)
+    }
+
+    #[test]
+    fn stable_id_ignores_slug() {
+        let meta = PageMeta::from([
+            ("slug".into(), Dynamic::String("setup".into())),
+            ("id".into(), Dynamic::String("install".into())),
+        ]);
+        assert_eq!(stable_id(&meta), Some("install".into()));
+        let meta =
+            PageMeta::from([("slug".into(), Dynamic::String("setup".into()))]);
+        assert_eq!(stable_id(&meta), None);
+    }
+
+    #[test]
+    fn duplicate_ids_are_reported_once_per_conflict() {
+        let pages = [
+            page("b.md", Some("setup")),
+            page("a.md", Some("setup")),
+            page("c.md", None),
+            page("d.md", Some("other")),
+        ];
+        assert_eq!(
+            duplicate_ids(&pages),
+            vec![("setup".into(), "a.md".into(), "b.md".into())]
+        );
+    }
+}
@@ -0,0 +1,436 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Image processing.
+
+use regex::{Captures, Regex};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crate::config::plugins::ImagesPluginConfig;
+use crate::structure::url;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match image tags.
+static IMG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)<img\b([^>]*?)\s*(/?)>").expect("invariant")
+});
+
+/// Regular expression to match the source attribute.
+static SRC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\ssrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#)
+        .expect("invariant")
+});
+
+/// Regular expression to match attributes we might add.
+static ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\s(width|height|loading|decoding)\s*=").expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Adds dimensions and lazy loading to local images in the given content.
+///
+/// Image sources are resolved relative to the given page URL, and looked up in
+/// the given directories in order, e.g., the docs and site directory. Width
+/// and height are only added if neither is set, and attributes that are
+/// already present are never changed. External images are left untouched.
+pub fn process_images(
+    content: &str, url: &str, base: &str, dirs: &[PathBuf],
+    config: &ImagesPluginConfig,
+) -> String {
+    let content = IMG_RE.replace_all(content, |captures: &Captures| {
+        let attrs = &captures[1];
+        let present = ATTR_RE
+            .captures_iter(attrs)
+            .map(|attr| attr[1].to_ascii_lowercase())
+            .collect::<BTreeSet<_>>();
+
+        // Resolve image and read its intrinsic dimensions, if enabled
+        let src = SRC_RE.captures(attrs).and_then(|src| {
+            src.get(1).or_else(|| src.get(2)).or_else(|| src.get(3))
+        });
+        let location = src.and_then(|src| resolve(url, src.as_str(), base));
+        let mut extra = String::new();
+        if config.dimensions
+            && !present.contains("width")
+            && !present.contains("height")
+        {
+            let path = location.and_then(|location| {
+                dirs.iter()
+                    .map(|dir| dir.join(&location))
+                    .find(|path| path.is_file())
+            });
+            if let Some(path) = path {
+                let data = fs::read(&path).unwrap_or_default();
+                if let Some((width, height)) = dimensions(&data) {
+                    write!(extra, " width=\"{width}\" height=\"{height}\"")
+                        .expect("invariant");
+                } else {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        "Could not read dimensions of image '{}'",
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        // Add lazy loading and asynchronous decoding to local images
+        if config.lazy_loading && src.is_some() && location.is_some() {
+            if !present.contains("loading") {
+                extra.push_str(" loading=\"lazy\"");
+            }
+            if !present.contains("decoding") {
+                extra.push_str(" decoding=\"async\"");
+            }
+        }
+
+        // Return tag unchanged, unless we added attributes
+        if extra.is_empty() {
+            captures[0].to_string()
+        } else {
+            let slash = if captures[2].is_empty() { "" } else { " /" };
+            format!("<img{attrs}{extra}{slash}>")
+        }
+    });
+    content.into_owned()
+}
+
+/// Resolves the given image source relative to the given page URL.
+///
+/// Returns the location of the image relative to the site root, or nothing if
+/// the image is external, e.g., if the source has a scheme, is protocol-
+/// relative, or points outside of the site.
+fn resolve(url: &str, src: &str, base: &str) -> Option<String> {
+    if src.is_empty() || src.starts_with("//") {
+        return None;
+    }
+
+    // Check for a scheme, e.g., `https:` or `data:`
+    if let Some((scheme, _)) = src.split_once(':') {
+        if !scheme.contains(['/', '?', '#']) {
+            return None;
+        }
+    }
+
+    // Strip query and fragment, and decode path
+    let path = src.split(['?', '#']).next().unwrap_or_default();
+    let path = url::decode(path);
+
+    // Root-relative paths are resolved relative to the base path, and all
+    // other paths relative to the directory of the page URL
+    let path = if let Some(path) = path.strip_prefix('/') {
+        let base = base.trim_matches('/');
+        if base.is_empty() {
+            path.to_string()
+        } else {
+            path.strip_prefix(base)?.strip_prefix('/')?.to_string()
+        }
+    } else {
+        let dir = url::decode(url);
+        let dir = dir.rsplit_once('/').map_or("", |(dir, _)| dir);
+        format!("{dir}/{path}")
+    };
+
+    // Normalize path, and bail if it points outside of the site
+    let mut segments = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            _ => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+/// Returns the intrinsic dimensions of the given PNG, JPEG, GIF or WebP image.
+pub fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png(data)
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        jpeg(data)
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        gif(data)
+    } else if data.starts_with(b"RIFF")
+        && data.get(8..12) == Some(b"WEBP".as_slice())
+    {
+        webp(data)
+    } else {
+        None
+    }
+}
+
+/// Returns the dimensions of a PNG image from its header chunk.
+fn png(data: &[u8]) -> Option<(u32, u32)> {
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((be32(data, 16)?, be32(data, 20)?))
+}
+
+/// Returns the dimensions of a JPEG image from its start of frame segment.
+fn jpeg(data: &[u8]) -> Option<(u32, u32)> {
+    let mut i = 2;
+    loop {
+        // Skip fill bytes before marker
+        if *data.get(i)? != 0xFF {
+            return None;
+        }
+        while *data.get(i)? == 0xFF {
+            i += 1;
+        }
+
+        // Markers without a segment don't have a length
+        let marker = *data.get(i)?;
+        i += 1;
+        if matches!(marker, 0x01 | 0xD0..=0xD7) {
+            continue;
+        }
+
+        // Start of frame segments contain the dimensions, except for the
+        // markers that are used for Huffman and arithmetic coding tables
+        let length = usize::from(be16(data, i)?);
+        if matches!(marker, 0xC0..=0xCF)
+            && !matches!(marker, 0xC4 | 0xC8 | 0xCC)
+        {
+            let height = be16(data, i + 3)?;
+            let width = be16(data, i + 5)?;
+            return Some((u32::from(width), u32::from(height)));
+        }
+        i += length;
+    }
+}
+
+/// Returns the dimensions of a GIF image from its logical screen descriptor.
+fn gif(data: &[u8]) -> Option<(u32, u32)> {
+    Some((u32::from(le16(data, 6)?), u32::from(le16(data, 8)?)))
+}
+
+/// Returns the dimensions of a WebP image from its first chunk.
+fn webp(data: &[u8]) -> Option<(u32, u32)> {
+    match data.get(12..16)? {
+        // Lossy format
+        b"VP8 " => {
+            if data.get(23..26)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            let width = le16(data, 26)? & 0x3FFF;
+            let height = le16(data, 28)? & 0x3FFF;
+            Some((u32::from(width), u32::from(height)))
+        }
+
+        // Lossless format
+        b"VP8L" => {
+            if *data.get(20)? != 0x2F {
+                return None;
+            }
+            let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+
+        // Extended format
+        b"VP8X" => Some((le24(data, 24)? + 1, le24(data, 27)? + 1)),
+        _ => None,
+    }
+}
+
+/// Reads a big-endian 16-bit integer at the given offset.
+fn be16(data: &[u8], i: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(i..i + 2)?.try_into().ok()?))
+}
+
+/// Reads a big-endian 32-bit integer at the given offset.
+fn be32(data: &[u8], i: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(i..i + 4)?.try_into().ok()?))
+}
+
+/// Reads a little-endian 16-bit integer at the given offset.
+fn le16(data: &[u8], i: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(i..i + 2)?.try_into().ok()?))
+}
+
+/// Reads a little-endian 24-bit integer at the given offset.
+fn le24(data: &[u8], i: usize) -> Option<u32> {
+    let bytes = data.get(i..i + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use crate::config::plugins::ImagesPluginConfig;
+
+    use super::{dimensions, process_images, resolve};
+
+    /// Creates a PNG image header with the given dimensions.
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend(width.to_be_bytes());
+        data.extend(height.to_be_bytes());
+        data.extend([8, 6, 0, 0, 0]);
+        data
+    }
+
+    /// Creates a JPEG image header with the given dimensions.
+    fn jpeg(width: u16, height: u16) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        data.extend(b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+        data.extend([0xFF, 0xC0, 0x00, 0x11, 0x08]);
+        data.extend(height.to_be_bytes());
+        data.extend(width.to_be_bytes());
+        data.extend([0x03, 0x01, 0x22, 0x00]);
+        data
+    }
+
+    /// Creates a GIF image header with the given dimensions.
+    fn gif(width: u16, height: u16) -> Vec<u8> {
+        let mut data = b"GIF89a".to_vec();
+        data.extend(width.to_le_bytes());
+        data.extend(height.to_le_bytes());
+        data.extend([0, 0, 0]);
+        data
+    }
+
+    /// Creates a WebP image header with the given chunk.
+    fn webp(chunk: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut data = b"RIFF\0\0\0\0WEBP".to_vec();
+        data.extend(chunk);
+        data.extend(u32::try_from(payload.len()).unwrap().to_le_bytes());
+        data.extend(payload);
+        data
+    }
+
+    #[test]
+    fn reads_png_dimensions() {
+        assert_eq!(dimensions(&png(640, 480)), Some((640, 480)));
+    }
+
+    #[test]
+    fn reads_jpeg_dimensions() {
+        assert_eq!(dimensions(&jpeg(800, 600)), Some((800, 600)));
+    }
+
+    #[test]
+    fn reads_gif_dimensions() {
+        assert_eq!(dimensions(&gif(32, 16)), Some((32, 16)));
+    }
+
+    #[test]
+    fn reads_webp_dimensions() {
+        let mut payload = vec![0x00, 0x00, 0x00, 0x9D, 0x01, 0x2A];
+        payload.extend(300u16.to_le_bytes());
+        payload.extend(200u16.to_le_bytes());
+        assert_eq!(dimensions(&webp(b"VP8 ", &payload)), Some((300, 200)));
+
+        // Lossless format
+        let bits: u32 = (300 - 1) | ((200 - 1) << 14);
+        let mut payload = vec![0x2F];
+        payload.extend(bits.to_le_bytes());
+        assert_eq!(dimensions(&webp(b"VP8L", &payload)), Some((300, 200)));
+
+        // Extended format
+        let payload = [0, 0, 0, 0, 0x2B, 0x01, 0x00, 0xC7, 0x00, 0x00];
+        assert_eq!(dimensions(&webp(b"VP8X", &payload)), Some((300, 200)));
+    }
+
+    #[test]
+    fn ignores_unknown_formats() {
+        assert_eq!(dimensions(b"<svg></svg>"), None);
+        assert_eq!(dimensions(&png(640, 480)[..20]), None);
+    }
+
+    #[test]
+    fn resolves_sources() {
+        assert_eq!(
+            resolve("guide/setup/", "../img/a.png", "/"),
+            Some("guide/img/a.png".to_string())
+        );
+        assert_eq!(
+            resolve("guide.html", "img/a%20b.png?v=1", "/"),
+            Some("img/a b.png".to_string())
+        );
+        assert_eq!(
+            resolve("guide/", "/docs/img/a.png", "/docs"),
+            Some("img/a.png".to_string())
+        );
+        assert_eq!(resolve("guide/", "https://example.com/a.png", "/"), None);
+        assert_eq!(resolve("guide/", "//example.com/a.png", "/"), None);
+        assert_eq!(resolve("guide/", "data:image/png;base64,", "/"), None);
+        assert_eq!(resolve("guide/", "../../a.png", "/"), None);
+    }
+
+    #[test]
+    fn processes_images() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("img")).unwrap();
+        fs::write(dir.path().join("img/a.png"), png(640, 480)).unwrap();
+        fs::write(dir.path().join("img/b.jpg"), b"broken").unwrap();
+
+        // Process images of page with external and invalid images
+        let config = ImagesPluginConfig {
+            enabled: true,
+            dimensions: true,
+            lazy_loading: true,
+        };
+        let content = concat!(
+            "<img src=\"../img/a.png\" alt=\"A\">\n",
+            "<img src=\"../img/a.png\" width=\"100\" loading=\"eager\" />\n",
+            "<img src=\"../img/b.jpg\">\n",
+            "<img src=\"https://example.com/c.png\">\n",
+        );
+        let dirs = [dir.path().to_path_buf()];
+        let content = process_images(content, "guide/", "/", &dirs, &config);
+        assert_eq!(
+            content,
+            concat!(
+                "<img src=\"../img/a.png\" alt=\"A\" width=\"640\" ",
+                "height=\"480\" loading=\"lazy\" decoding=\"async\">\n",
+                "<img src=\"../img/a.png\" width=\"100\" loading=\"eager\" ",
+                "decoding=\"async\" />\n",
+                "<img src=\"../img/b.jpg\" loading=\"lazy\" ",
+                "decoding=\"async\">\n",
+                "<img src=\"https://example.com/c.png\">\n",
+            )
+        );
+    }
+}
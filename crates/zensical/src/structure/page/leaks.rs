@@ -0,0 +1,164 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Leaked file system paths.
+
+use std::path::Path;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Number of bytes of context to report before and after a leaked path.
+const CONTEXT: usize = 24;
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the contexts in which the given root directory appears in output.
+///
+/// Absolute paths of the project root might leak into the output, e.g., when
+/// templates render file system paths, which results in broken links and
+/// discloses details about the build machine. Since paths might have been
+/// converted or escaped along the way, the root directory is looked up with
+/// forward slashes, backslashes, and escaped backslashes, as used in JSON.
+pub fn leaked_paths(output: &str, root_dir: &Path) -> Vec<String> {
+    let root = root_dir.to_string_lossy();
+    let root = root.trim_end_matches(['/', '\\']);
+    if root.is_empty() {
+        return Vec::new();
+    }
+
+    // Collect all variants of the root directory, longest first, so matches
+    // of a variant are not reported again as part of another variant
+    let mut variants = vec![
+        root.replace('\\', "/"),
+        root.replace('/', "\\"),
+        root.replace(['/', '\\'], "\\\\"),
+    ];
+    variants.sort_by_key(|variant| usize::MAX - variant.len());
+    variants.dedup();
+
+    // Find all occurrences of all variants, skipping overlapping matches
+    let mut ranges = Vec::<(usize, usize)>::new();
+    for variant in &variants {
+        for (start, _) in output.match_indices(variant.as_str()) {
+            let end = start + variant.len();
+            if !ranges.iter().any(|&(s, e)| start < e && s < end) {
+                ranges.push((start, end));
+            }
+        }
+    }
+
+    // Return each occurrence with the surrounding context on the same line
+    ranges.sort_unstable();
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let line = output[..start].rfind('\n').map_or(0, |n| n + 1);
+            let start = floor(output, start.saturating_sub(CONTEXT).max(line));
+            let line =
+                output[end..].find('\n').map_or(output.len(), |n| end + n);
+            let end = ceil(output, (end + CONTEXT).min(line));
+            output[start..end].trim().to_string()
+        })
+        .collect()
+}
+
+/// Returns the closest character boundary at or before the given index.
+fn floor(value: &str, mut index: usize) -> usize {
+    while !value.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Returns the closest character boundary at or after the given index.
+fn ceil(value: &str, index: usize) -> usize {
+    let mut index = index.min(value.len());
+    while !value.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::leaked_paths;
+
+    #[test]
+    fn detects_root_directory_with_any_separator() {
+        let root = Path::new("/home/user/my project");
+        let output = concat!(
+            "<!-- /home/user/my project/docs/index.md -->\n",
+            "<a href=\"\\home\\user\\my project\\docs\">Edit</a>\n",
+            "<script>{\"path\":\"\\\\home\\\\user\\\\my project\"}</script>\n",
+        );
+        assert_eq!(
+            leaked_paths(output, root),
+            [
+                "<!-- /home/user/my project/docs/index.md -->",
+                "<a href=\"\\home\\user\\my project\\docs\">Edit</a>",
+                "<script>{\"path\":\"\\\\home\\\\user\\\\my project\"}</script>",
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_windows_root_directory() {
+        let root = Path::new("C:\\Users\\user\\my project\\");
+        let output = "<p>Built from C:/Users/user/my project/docs</p>";
+        assert_eq!(
+            leaked_paths(output, root),
+            ["<p>Built from C:/Users/user/my project/docs</p>"]
+        );
+    }
+
+    #[test]
+    fn clips_context_on_long_lines() {
+        let root = Path::new("/srv/docs");
+        let (a, b) = ("a".repeat(100), "b".repeat(100));
+        let output = format!("{a}/srv/docs/index.md{b}");
+        assert_eq!(
+            leaked_paths(&output, root),
+            [format!("{}/srv/docs/index.md{}", &a[..24], &b[..15])]
+        );
+    }
+
+    #[test]
+    fn ignores_output_without_root_directory() {
+        let root = Path::new("/home/user/my project");
+        let output = "<a href=\"../guide/\">/home/user/other</a>";
+        assert!(leaked_paths(output, root).is_empty());
+        assert!(leaked_paths(output, Path::new("/")).is_empty());
+    }
+}
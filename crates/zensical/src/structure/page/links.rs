@@ -0,0 +1,303 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Absolute links.
+
+use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match tags.
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9-]*)([^>]*)>").expect("invariant")
+});
+
+/// Regular expression to match link attributes.
+static ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\s(?:href|src)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .expect("invariant")
+});
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns all root-relative URLs that bypass the given base path.
+///
+/// Links, scripts and styles with root-relative URLs, e.g. `/getting-started/`,
+/// break when the site is deployed to a subpath, as they don't start with the
+/// base path. Code blocks and `data-` attributes are ignored.
+pub fn absolute_links(content: &str, base: &str) -> Vec<String> {
+    if base == "/" {
+        return Vec::new();
+    }
+    find(content, |url| bypasses(url, base))
+        .into_iter()
+        .map(|range| content[range].to_string())
+        .collect()
+}
+
+/// Returns all URLs that contain backslashes.
+///
+/// Backslashes are never valid path separators in URLs, so they're a telltale
+/// sign of file system paths computed on Windows that leaked into the output.
+pub fn backslash_links(content: &str) -> Vec<String> {
+    find(content, |url| url.contains('\\'))
+        .into_iter()
+        .map(|range| content[range].to_string())
+        .collect()
+}
+
+/// Prefixes all root-relative URLs that bypass the given base path with it.
+pub fn prefix_absolute_links(content: &str, base: &str) -> String {
+    if base == "/" {
+        return content.to_string();
+    }
+    replace(
+        content,
+        |url| bypasses(url, base),
+        |url| format!("{base}{url}"),
+    )
+}
+
+/// Rewrites all root-relative URLs to URLs relative to the given base URL.
+///
+/// When a site is opened from the file system, root-relative URLs resolve to
+/// the root of the file system, so they must be made relative to the page,
+/// which is what the base URL of the page is for. URLs starting with the base
+/// path are stripped of it, and URLs pointing to directories are pointed to
+/// their `index.html`, since there's no server to resolve them.
+pub fn relativize_absolute_links(
+    content: &str, base: &str, base_url: &str,
+) -> String {
+    let base = base.trim_end_matches('/');
+    replace(content, is_root_relative, |url| {
+        let path = match url.strip_prefix(base) {
+            Some(rest) if !bypasses(url, base) => rest,
+            _ => url,
+        };
+        let path = path.strip_prefix('/').unwrap_or(path);
+
+        // Split off query and fragment, and point directories to the index
+        let end = path.find(['?', '#']).unwrap_or(path.len());
+        let (path, suffix) = path.split_at(end);
+        if path.is_empty() || path.ends_with('/') {
+            format!("{base_url}/{path}index.html{suffix}")
+        } else {
+            format!("{base_url}/{path}{suffix}")
+        }
+    })
+}
+
+/// Replaces all URLs matching the given predicate with the given function.
+fn replace<P, F>(content: &str, predicate: P, f: F) -> String
+where
+    P: Fn(&str) -> bool,
+    F: Fn(&str) -> String,
+{
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    for range in find(content, predicate) {
+        output.push_str(&content[last..range.start]);
+        output.push_str(&f(&content[range.clone()]));
+        last = range.end;
+    }
+    output.push_str(&content[last..]);
+    output
+}
+
+/// Returns the ranges of all URLs matching the given predicate.
+fn find<P>(content: &str, predicate: P) -> Vec<Range<usize>>
+where
+    P: Fn(&str) -> bool,
+{
+    let mut ranges = Vec::new();
+
+    // Iterate over all tags, skipping everything inside of code blocks
+    let mut depth = 0usize;
+    for captures in TAG_RE.captures_iter(content) {
+        let name = captures[2].to_ascii_lowercase();
+        if name == "pre" || name == "code" {
+            if &captures[1] == "/" {
+                depth = depth.saturating_sub(1);
+            } else if !captures[3].ends_with('/') {
+                depth += 1;
+            }
+            continue;
+        }
+
+        // Collect all link attributes with URLs matching the predicate
+        if depth == 0 && captures[1].is_empty() {
+            let attrs = captures.get(3).expect("invariant");
+            for attr in ATTR_RE.captures_iter(attrs.as_str()) {
+                let value = attr.get(1).or_else(|| attr.get(2));
+                let value = value.expect("invariant");
+                if predicate(value.as_str()) {
+                    let start = attrs.start() + value.start();
+                    ranges.push(start..attrs.start() + value.end());
+                }
+            }
+        }
+    }
+    ranges
+}
+
+/// Returns whether the given URL is root-relative.
+fn is_root_relative(url: &str) -> bool {
+    url.starts_with('/') && !url.starts_with("//")
+}
+
+/// Returns whether the given URL is root-relative and bypasses the base path.
+fn bypasses(url: &str, base: &str) -> bool {
+    if !is_root_relative(url) {
+        return false;
+    }
+
+    // Check whether the URL starts with the base path
+    match url.strip_prefix(base) {
+        Some(rest) => !(rest.is_empty() || rest.starts_with(['/', '?', '#'])),
+        None => true,
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        absolute_links, backslash_links, prefix_absolute_links,
+        relativize_absolute_links,
+    };
+
+    /// Fixture HTML for a site deployed to a subpath.
+    const FIXTURE: &str = concat!(
+        "<p><a href=\"/getting-started/\">Getting started</a></p>\n",
+        "<p><a href=\"/docs/setup/\">Setup</a></p>\n",
+        "<p><a href=\"//cdn.example.com/x.js\">CDN</a></p>\n",
+        "<p><a href=\"https://example.com/\">External</a></p>\n",
+        "<p><a href=\"../relative/\">Relative</a></p>\n",
+        "<p><img src='/assets/logo.png' data-src=\"/assets/big.png\"></p>\n",
+        "<div data-href=\"/ignored/\"></div>\n",
+        "<pre><code><a href=\"/in-code/\">Code</a></code></pre>\n",
+        "<p><code>&lt;a href=\"/inline/\"&gt;</code></p>\n",
+        "<link rel=\"stylesheet\" href=\"/extra.css\">\n",
+        "<script src=\"/extra.js\"></script>\n",
+    );
+
+    #[test]
+    fn detects_absolute_links() {
+        assert_eq!(
+            absolute_links(FIXTURE, "/docs"),
+            [
+                "/getting-started/",
+                "/assets/logo.png",
+                "/extra.css",
+                "/extra.js"
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_absolute_links_without_subpath() {
+        assert!(absolute_links(FIXTURE, "/").is_empty());
+    }
+
+    #[test]
+    fn detects_links_sharing_base_path_prefix() {
+        let content = "<a href=\"/docsearch/\">Search</a>";
+        assert_eq!(absolute_links(content, "/docs"), ["/docsearch/"]);
+        let content = "<a href=\"/docs#top\">Top</a>";
+        assert!(absolute_links(content, "/docs").is_empty());
+    }
+
+    #[test]
+    fn detects_backslash_links() {
+        let content = concat!(
+            "<a href=\"guide\\setup\\\">Setup</a>\n",
+            "<img src='C:\\Users\\docs\\logo.png'>\n",
+            "<a href=\"guide/setup/\">Setup</a>\n",
+            "<pre><code><a href=\"C:\\in-code\">Code</a></code></pre>\n",
+        );
+        assert_eq!(
+            backslash_links(content),
+            ["guide\\setup\\", "C:\\Users\\docs\\logo.png"]
+        );
+    }
+
+    #[test]
+    fn prefixes_absolute_links() {
+        let content = prefix_absolute_links(FIXTURE, "/docs");
+        assert!(content.contains("href=\"/docs/getting-started/\""));
+        assert!(content.contains("href=\"/docs/setup/\""));
+        assert!(content.contains("src='/docs/assets/logo.png'"));
+        assert!(content.contains("data-src=\"/assets/big.png\""));
+        assert!(content.contains("data-href=\"/ignored/\""));
+        assert!(content.contains("<a href=\"/in-code/\">"));
+        assert!(content.contains("href=\"/docs/extra.css\""));
+        assert!(content.contains("src=\"/docs/extra.js\""));
+        assert!(absolute_links(&content, "/docs").is_empty());
+    }
+
+    #[test]
+    fn relativizes_absolute_links() {
+        let content = relativize_absolute_links(FIXTURE, "/docs", "../..");
+        assert!(content.contains("href=\"../../getting-started/index.html\""));
+        assert!(content.contains("href=\"../../setup/index.html\""));
+        assert!(content.contains("href=\"//cdn.example.com/x.js\""));
+        assert!(content.contains("src='../../assets/logo.png'"));
+        assert!(content.contains("data-src=\"/assets/big.png\""));
+        assert!(content.contains("<a href=\"/in-code/\">"));
+        assert!(content.contains("href=\"../../extra.css\""));
+        assert!(content.contains("src=\"../../extra.js\""));
+    }
+
+    #[test]
+    fn relativizes_links_to_site_root() {
+        let content = "<a href=\"/\">Home</a><a href=\"/#top\">Top</a>";
+        assert_eq!(
+            relativize_absolute_links(content, "/", "."),
+            concat!(
+                "<a href=\"./index.html\">Home</a>",
+                "<a href=\"./index.html#top\">Top</a>"
+            )
+        );
+        let content = "<a href=\"/docsearch/\">Search</a>";
+        assert_eq!(
+            relativize_absolute_links(content, "/docs", "."),
+            "<a href=\"./docsearch/index.html\">Search</a>"
+        );
+        let content = "<a href=\"/docs/page.html?q=1\">Page</a>";
+        assert_eq!(
+            relativize_absolute_links(content, "/docs", ".."),
+            "<a href=\"../page.html?q=1\">Page</a>"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Reading time.
+
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Regular expression to match code blocks.
+static CODE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<pre[\s>].*?</pre>").expect("invariant")
+});
+
+/// Regular expression to match tags.
+static TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<[^>]*>").expect("invariant"));
+
+/// Regular expression to match character references.
+static ENTITY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&(?:#([0-9]{1,7})|#[xX]([0-9a-fA-F]{1,6})|([a-zA-Z0-9]+));")
+        .expect("invariant")
+});
+
+/// Named character references that separate words.
+const SEPARATORS: &[&str] = &[
+    "amp", "apos", "gt", "hellip", "laquo", "ldquo", "lsquo", "lt", "mdash",
+    "nbsp", "ndash", "quot", "raquo", "rdquo", "rsquo",
+];
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Counts the words in the given HTML content.
+///
+/// Tags are stripped, character references are decoded, and words are split
+/// by whitespace. As languages like Chinese or Japanese don't separate words
+/// with whitespace, each character of those scripts is counted as a word,
+/// which is a common approximation. Code blocks can be excluded, since they
+/// tend to skew estimates.
+pub fn word_count(content: &str, count_code: bool) -> usize {
+    let content = if count_code {
+        TAG_RE.replace_all(content, " ")
+    } else {
+        let content = CODE_RE.replace_all(content, " ");
+        TAG_RE.replace_all(&content, " ").into_owned().into()
+    };
+    let content = decode_entities(&content);
+
+    // Count words, treating each CJK character as a word on its own
+    let mut count = 0;
+    for word in content.split_whitespace() {
+        let mut in_word = false;
+        for char in word.chars() {
+            if is_cjk(char) {
+                count += 1;
+                in_word = false;
+            } else if char.is_alphanumeric() {
+                if !in_word {
+                    count += 1;
+                }
+                in_word = true;
+            }
+        }
+    }
+    count
+}
+
+/// Computes the reading time in minutes for the given number of words.
+///
+/// The reading time is rounded up, so non-empty pages take at least a minute.
+pub fn reading_time(words: usize, words_per_minute: usize) -> usize {
+    words.div_ceil(words_per_minute.max(1))
+}
+
+/// Decodes character references in the given text.
+///
+/// Numeric references are decoded to their characters, and named references
+/// for punctuation and spaces become whitespace. All other named references,
+/// e.g., `&eacute;`, denote letters or symbols, so they're removed, which
+/// keeps them from splitting or adding words.
+fn decode_entities(text: &str) -> Cow<'_, str> {
+    ENTITY_RE.replace_all(text, |captures: &Captures| {
+        let code = captures
+            .get(1)
+            .and_then(|code| code.as_str().parse().ok())
+            .or_else(|| {
+                let code = captures.get(2)?;
+                u32::from_str_radix(code.as_str(), 16).ok()
+            });
+        match (code.and_then(char::from_u32), captures.get(3)) {
+            (Some(char), _) => char.to_string(),
+            (None, Some(name)) if SEPARATORS.contains(&name.as_str()) => {
+                String::from(" ")
+            }
+            _ => String::new(),
+        }
+    })
+}
+
+/// Returns whether the given character belongs to a CJK script.
+fn is_cjk(char: char) -> bool {
+    matches!(char,
+        '\u{3040}'..='\u{30FF}' // Hiragana and Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2FA1F}' // CJK Unified Ideographs Extension B-F
+    )
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{reading_time, word_count};
+
+    #[test]
+    fn word_count_strips_tags() {
+        let content = "<h1 id=\"a\">Hello world</h1><p>It's <em>nice</em></p>";
+        assert_eq!(word_count(content, false), 4);
+    }
+
+    #[test]
+    fn word_count_excludes_code_blocks() {
+        let content = concat!(
+            "<p>Install the package</p>",
+            "<pre><code>pip install zensical\nzensical serve</code></pre>",
+        );
+        assert_eq!(word_count(content, false), 3);
+        assert_eq!(word_count(content, true), 8);
+    }
+
+    #[test]
+    fn word_count_decodes_entities() {
+        let content = "<p>Tom &amp; Jerry&nbsp;say &quot;hi&quot;</p>";
+        assert_eq!(word_count(content, false), 4);
+        let content = "<p>na&iuml;ve caf&#233; &#x4E2D;&#25991;</p>";
+        assert_eq!(word_count(content, false), 4);
+    }
+
+    #[test]
+    fn word_count_counts_cjk_characters() {
+        assert_eq!(word_count("<p>编译器笔记</p>", false), 5);
+        assert_eq!(word_count("<p>Rust 编译器</p>", false), 4);
+    }
+
+    #[test]
+    fn reading_time_rounds_up() {
+        assert_eq!(reading_time(0, 200), 0);
+        assert_eq!(reading_time(1, 200), 1);
+        assert_eq!(reading_time(400, 200), 2);
+        assert_eq!(reading_time(401, 200), 3);
+    }
+}
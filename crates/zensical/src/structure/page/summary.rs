@@ -0,0 +1,188 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Page summary.
+
+use serde::Serialize;
+use std::sync::Arc;
+use zrx::scheduler::Value;
+
+use crate::structure::search::SearchItem;
+use crate::structure::tag::Tag;
+use crate::structure::toc::Section;
+
+use super::{ids, Page, PageMeta};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Page summary.
+///
+/// Summaries carry everything that is needed once all pages are built, i.e.,
+/// to generate the navigation, search index and manifest, but not the content
+/// of the page, which is only needed for rendering the page itself. On large
+/// sites, this keeps the chunk of all pages small. Strings are reference
+/// counted, so summaries are cheap to clone, e.g., when partitioning pages by
+/// language, or when adding pages outside of a narrowed build scope.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PageSummary {
+    /// Page target URL.
+    pub url: Arc<str>,
+    /// Page canonical URL.
+    pub canonical_url: Option<Arc<str>>,
+    /// Page title, as plain text.
+    pub title: Arc<str>,
+    /// Page title, as HTML.
+    pub title_html: Arc<str>,
+    /// Page metadata.
+    pub meta: PageMeta,
+    /// Page file system path, which is not exposed to templates.
+    #[serde(skip)]
+    pub path: Arc<str>,
+    /// Page source path, relative to the docs directory.
+    pub src_path: Arc<str>,
+    /// Stable hash of page content.
+    pub content_hash: Arc<str>,
+    /// Table of contents.
+    pub toc: Arc<[Section]>,
+    /// Search index.
+    pub search: Arc<[SearchItem]>,
+    /// Tags.
+    pub tags: Vec<Tag>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl PageSummary {
+    /// Returns the stable identifier of the page, if any.
+    #[inline]
+    pub fn stable_id(&self) -> Option<String> {
+        ids::stable_id(&self.meta)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Value for PageSummary {}
+
+// ----------------------------------------------------------------------------
+
+impl From<&Page> for PageSummary {
+    /// Creates a summary from a page, leaving out its content.
+    fn from(page: &Page) -> Self {
+        Self {
+            url: Arc::from(page.url.as_str()),
+            canonical_url: page.canonical_url.as_deref().map(Arc::from),
+            title: Arc::from(page.title.as_str()),
+            title_html: Arc::from(page.title_html.as_str()),
+            meta: page.meta.clone(),
+            path: Arc::from(page.path.as_str()),
+            src_path: Arc::from(page.src_path.as_str()),
+            content_hash: Arc::from(page.content_hash.as_str()),
+            toc: Arc::from(page.toc.as_slice()),
+            search: Arc::from(page.search.as_slice()),
+            tags: page.tags.clone(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use zrx::id::Id;
+    use zrx::scheduler::Key;
+
+    use crate::structure::page::{Page, PageMeta};
+
+    use super::PageSummary;
+
+    /// Creates a page with the given URL and content.
+    fn page(url: &str, content: String) -> Page {
+        Page {
+            url: url.to_string(),
+            canonical_url: None,
+            edit_url: None,
+            title: url.to_string(),
+            title_html: url.to_string(),
+            meta: PageMeta::default(),
+            path: format!("site/{url}index.html"),
+            src_path: format!("{url}index.md"),
+            content_hash: String::new(),
+            content,
+            toc: Vec::new(),
+            search: Vec::new(),
+            ancestors: Vec::new(),
+            previous_page: None,
+            next_page: None,
+            word_count: 0,
+            reading_time: 0,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn summaries_leave_out_content() {
+        let content = "<p>content</p>".repeat(1024);
+        let pages = (0..1_000)
+            .map(|n| {
+                let id = Id::builder()
+                    .provider("file")
+                    .context("docs")
+                    .location(format!("page-{n}.md"))
+                    .build()
+                    .unwrap();
+                let page = page(&format!("page-{n}/"), content.clone());
+                (Key::from_iter([id]), page)
+            })
+            .collect::<Vec<_>>();
+
+        // Summarize chunk, which must not contain the content of any page
+        let summaries = pages
+            .iter()
+            .map(|(id, page)| (id.clone(), PageSummary::from(page)))
+            .collect::<Vec<_>>();
+        assert_eq!(summaries.len(), pages.len());
+        for (_, summary) in &summaries {
+            let data = serde_json::to_string(summary).unwrap();
+            assert!(!data.contains("<p>content</p>"));
+            assert!(data.len() < 1024);
+        }
+
+        // Clones of summaries share their strings and search entries
+        let (_, summary) = &summaries[0];
+        let clone = summary.clone();
+        assert!(Arc::ptr_eq(&summary.url, &clone.url));
+        assert!(Arc::ptr_eq(&summary.search, &clone.search));
+    }
+}
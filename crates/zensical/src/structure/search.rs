@@ -26,14 +26,15 @@
 //! Search index.
 
 use pyo3::FromPyObject;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use zrx::id::Id;
-use zrx::scheduler::{Key, Value};
+use zrx::scheduler::Value;
 
 use crate::config::plugins::SearchPluginConfig;
 
 use super::nav::{file_sort_key, Navigation};
-use super::page::Page;
+use super::page::PageSummary;
+use super::url;
 
 mod item;
 
@@ -44,7 +45,7 @@ pub use item::SearchItem;
 // ----------------------------------------------------------------------------
 
 /// Search configuration.
-#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize, Deserialize)]
 pub struct SearchConfig {
     /// Separator for tokenizer.
     pub separator: String,
@@ -54,7 +55,7 @@ pub struct SearchConfig {
 ///
 /// Later, when the module system is available, we'll move search into a module
 /// of its own, but for now, we'll just keep it here for simplicity.
-#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize, Deserialize)]
 pub struct SearchIndex {
     /// Search configuration.
     pub config: SearchConfig,
@@ -68,47 +69,56 @@ pub struct SearchIndex {
 
 impl SearchIndex {
     /// Creates a search index from pages.
+    ///
+    /// Pages are only borrowed, and ancestors are taken from the navigation
+    /// without cloning them, as this runs for every page of the site.
     #[allow(clippy::assigning_clones)]
-    pub fn new(
-        pages: Vec<(Key<Id>, Page)>, nav: &Navigation,
-        config: SearchPluginConfig,
-    ) -> Self {
+    pub fn new<'a, I>(
+        pages: I, nav: &Navigation, config: SearchPluginConfig,
+    ) -> Self
+    where
+        I: IntoIterator<Item = (&'a Id, &'a PageSummary)>,
+    {
         let mut items: Vec<SearchItem> = Vec::new();
 
-        // Convert chunk into a vector for easier processing, and sort pages by
+        // Convert pages into a vector for easier processing, and sort pages by
         // the exact same method that MkDocs uses
         let mut pages = Vec::from_iter(pages);
-        pages.sort_by_key(|(id, _)| file_sort_key(&id[0]));
+        pages.sort_by_cached_key(|(id, _)| file_sort_key(id));
 
         // Assemble search index, combining all items from all pages into a
         // single, flat list, adjusting the location to include the page URL
         for (_id, page) in pages {
-            let iter = nav.ancestors(&page).into_iter().rev();
+            let iter = nav.ancestors_of(&page.url).into_iter().rev();
             let mut path = iter
-                .filter_map(|item| item.display_title().map(ToString::to_string))
+                .filter_map(|item| {
+                    item.display_title().map(ToString::to_string)
+                })
                 .collect::<Vec<_>>();
 
             // Add page title to path if not already present - this might be
             // the true in case of index pages
-            if path.last() != Some(&page.title) {
-                path.push(page.title.clone());
+            if path.last().map(String::as_str) != Some(&*page.title) {
+                path.push(page.title.to_string());
             }
 
             // Extract page tags, if any
             let tags: Vec<String> =
-                page.tags().into_iter().map(|tag| tag.name).collect();
+                page.tags.iter().map(|tag| tag.name.clone()).collect();
 
             // For each page, adjust the location of each item and add it to
             // the overall list
-            for mut item in page.search {
+            for mut item in page.search.iter().cloned() {
                 let location = match item.location {
-                    Some(id) => format!("{}#{}", page.url, id),
-                    _ => page.url.clone(),
+                    Some(id) => {
+                        format!("{}#{}", page.url, url::encode_fragment(&id))
+                    }
+                    _ => page.url.to_string(),
                 };
 
                 // Fall back to page title, if item title is empty
                 if item.title.is_empty() {
-                    item.title = page.title.clone();
+                    item.title = page.title.to_string();
                 }
 
                 // Update location and path and add item
@@ -138,3 +148,90 @@ impl From<SearchPluginConfig> for SearchConfig {
         Self { separator: config.separator }
     }
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use zrx::scheduler::Key;
+
+    use crate::structure::bench::{allocations, chunk};
+    use crate::structure::i18n::Translations;
+    use crate::structure::markdown::Autorefs;
+    use crate::structure::nav::NavigationItem;
+
+    use super::*;
+
+    /// Creates a navigation item with the given title, URL and children.
+    fn item(
+        title: &str, url: Option<&str>, children: Vec<NavigationItem>,
+    ) -> NavigationItem {
+        NavigationItem {
+            title: Some(title.to_string()),
+            title_html: None,
+            url: url.map(ToString::to_string),
+            canonical_url: None,
+            meta: None,
+            icon: None,
+            attributes: BTreeMap::new(),
+            children,
+            is_index: false,
+            external: false,
+            active: false,
+            missing: false,
+        }
+    }
+
+    /// Creates a navigation with a single section containing all pages.
+    fn nav(pages: &[(Key<Id>, PageSummary)]) -> Navigation {
+        let children = pages
+            .iter()
+            .map(|(_, page)| item(&page.title, Some(&page.url), vec![]))
+            .collect();
+        Navigation {
+            items: vec![item("Section", None, children)],
+            homepage: None,
+            autorefs: Autorefs::new(),
+            hash: 0,
+            headings_hash: 0,
+            languages: BTreeMap::new(),
+            translations: Translations::new(),
+        }
+    }
+
+    /// Returns the number of allocations to create a search index.
+    fn count(pages: &[(Key<Id>, PageSummary)], nav: &Navigation) -> usize {
+        let config = SearchPluginConfig {
+            enabled: true,
+            separator: String::from(r"[\s\-]+"),
+        };
+        let iter = pages.iter().map(|(id, page)| (&id[0], page));
+        let (_, count) = allocations(|| SearchIndex::new(iter, nav, config));
+        count
+    }
+
+    #[test]
+    fn allocations_independent_of_section_size() {
+        let pages = chunk(5000, 0);
+        let small = nav(&pages[..50]);
+        let large = nav(&pages);
+
+        // Ancestors are borrowed, so sections aren't cloned for every page
+        assert_eq!(count(&pages[..50], &large), count(&pages[..50], &small));
+    }
+
+    #[test]
+    fn allocations_independent_of_meta() {
+        let small = chunk(5000, 0);
+        let large = chunk(5000, 32);
+        let nav = nav(&small);
+
+        // Pages are borrowed, so their metadata is never cloned
+        assert_eq!(count(&large, &nav), count(&small, &nav));
+        let (_, cloned) = allocations(|| large.clone());
+        assert!(count(&large, &nav) < cloned);
+    }
+}
@@ -26,15 +26,21 @@
 //! Tag.
 
 use pyo3::FromPyObject;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
 
 /// Tag.
-#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize)]
+///
+/// The slug is computed with the same slugification function that is used for
+/// headings, so it can be used to create anchors, e.g., on a tags index page.
+#[derive(Clone, Debug, PartialEq, Eq, FromPyObject, Serialize, Deserialize)]
+#[pyo3(from_item_all)]
 pub struct Tag {
     /// Tag name.
     pub name: String,
+    /// Tag slug.
+    pub slug: String,
 }
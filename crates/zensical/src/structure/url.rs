@@ -0,0 +1,548 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! URL helpers.
+//!
+//! All URLs are percent-encoded at output boundaries, i.e., when they are
+//! written into pages, the navigation, or the search index, but compared in
+//! their decoded form internally, so that differences in encoding, e.g., of
+//! umlauts, CJK characters, or spaces, don't affect equality.
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet};
+use std::borrow::Cow;
+use zensical_serve::http::Uri;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Character set to be percent-encoded in fragments.
+#[rustfmt::skip]
+const FRAGMENT: &AsciiSet = &percent_encoding::CONTROLS
+    .add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Percent-encodes the given URL.
+///
+/// The URL is decoded first, which makes encoding idempotent, so it's safe to
+/// pass URLs that are already (partially) percent-encoded.
+#[must_use]
+pub fn encode(url: &str) -> String {
+    Uri::from(url).to_string()
+}
+
+/// Percent-encodes the given fragment.
+#[must_use]
+pub fn encode_fragment(fragment: &str) -> String {
+    let fragment = percent_decode_str(fragment).decode_utf8_lossy();
+    utf8_percent_encode(&fragment, FRAGMENT).to_string()
+}
+
+/// Percent-decodes the given URL.
+#[must_use]
+pub fn decode(url: &str) -> Cow<'_, str> {
+    percent_decode_str(url).decode_utf8_lossy()
+}
+
+/// Returns whether both URLs are equivalent, ignoring percent-encoding.
+#[must_use]
+pub fn equivalent(a: &str, b: &str) -> bool {
+    a == b || decode(a) == decode(b)
+}
+
+/// Splits the given URL into its path and its query string and fragment.
+///
+/// The suffix starts with `?` or `#`, whichever comes first, so it can be
+/// re-attached verbatim after the path was resolved or rewritten.
+#[must_use]
+pub fn split_suffix(url: &str) -> (&str, &str) {
+    match url.find(['?', '#']) {
+        Some(index) => url.split_at(index),
+        None => (url, ""),
+    }
+}
+
+/// Removes the fragment from the given URL, retaining the query string.
+#[must_use]
+pub fn strip_fragment(url: &str) -> &str {
+    url.split_once('#').map_or(url, |(url, _)| url)
+}
+
+/// Removes the `index.html` suffix from the given URL, if any.
+///
+/// Only entire segments are stripped, so `guide/index.html` becomes `guide/`,
+/// and `index.html` becomes the empty URL, but `myindex.html` is retained.
+#[must_use]
+pub fn strip_index(url: &str) -> &str {
+    match url.strip_suffix("index.html") {
+        Some(dir) if dir.is_empty() || dir.ends_with('/') => dir,
+        _ => url,
+    }
+}
+
+/// Ensures that the given URL ends with a slash, so it denotes a directory.
+///
+/// The empty URL denotes the root directory, and is returned as it is.
+#[must_use]
+pub fn ensure_dir_url(url: &str) -> String {
+    if url.is_empty() || url.ends_with('/') {
+        url.to_string()
+    } else {
+        format!("{url}/")
+    }
+}
+
+/// Returns the parent of the given URL, if any.
+///
+/// Trailing slashes are ignored, so the parent of both `a/b` and `a/b/` is
+/// `a`, and the parent of `a` is the empty URL, which has no parent.
+#[must_use]
+pub fn parent(url: &str) -> Option<&str> {
+    let url = url.trim_end_matches('/');
+    if url.is_empty() {
+        return None;
+    }
+    Some(url.rsplit_once('/').map_or("", |(parent, _)| parent))
+}
+
+/// Returns the file name of the given URL, i.e., its last segment, if any.
+///
+/// Query strings and fragments are ignored, and URLs ending with a slash
+/// denote directories, so they have no file name.
+#[must_use]
+pub fn file_name(url: &str) -> Option<&str> {
+    let (path, _) = split_suffix(url);
+    let name = path.rsplit('/').next().unwrap_or_default();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Returns the extension of the file name of the given URL, if any.
+///
+/// Like for file system paths, a leading dot doesn't start an extension, so
+/// `.htaccess` has no extension.
+#[must_use]
+pub fn extension(url: &str) -> Option<&str> {
+    let (stem, extension) = file_name(url)?.rsplit_once('.')?;
+    (!stem.is_empty()).then_some(extension)
+}
+
+/// Joins the given URL to the given base URL.
+///
+/// The base URL is treated as a directory, so both are always separated by a
+/// single slash, and `.` and `..` segments of the URL are resolved. Absolute
+/// URLs, i.e., URLs like `https://` or starting with a slash, are returned as
+/// they are, and so are query strings and fragments.
+#[must_use]
+pub fn join(base: &str, url: &str) -> String {
+    if is_absolute(url) {
+        return url.to_string();
+    }
+
+    // Resolve segments of the path, and retain a trailing slash
+    let (path, suffix) = split_suffix(url);
+    let (segments, is_dir) = resolve(path);
+    let mut path = segments.join("/");
+    if is_dir && !segments.is_empty() {
+        path.push('/');
+    }
+
+    // Separate base URL and path with a single slash
+    let base = base.trim_end_matches('/');
+    if base.is_empty() {
+        format!("{path}{suffix}")
+    } else {
+        format!("{base}/{path}{suffix}")
+    }
+}
+
+/// Returns the given URL relative to the given base URL.
+///
+/// Both URLs are relative to the site, e.g., `guide/setup/`. The base URL is
+/// the URL of a page, which is a directory if it ends with a slash, or if its
+/// last segment has no extension, and a file otherwise, e.g., `about.html`,
+/// in which case the URL is resolved relative to the file's directory. If the
+/// URL points to the page itself, only its query string and fragment are
+/// returned, if any. Absolute URLs are returned as they are, and segments are
+/// compared in their decoded form, while the URL's encoding is retained.
+#[must_use]
+pub fn relative_to(url: &str, base: &str) -> String {
+    if is_absolute(url) {
+        return url.to_string();
+    }
+
+    // Resolve segments of both paths, and determine directory of base URL
+    let (path, suffix) = split_suffix(url);
+    let (target, is_dir) = resolve(path);
+    let (page, _) = resolve(split_suffix(base).0);
+    let file = page.last().filter(|_| !is_dir_url(base));
+    let dir = &page[..page.len() - usize::from(file.is_some())];
+
+    // If the URL points to the page itself, only keep query and fragment, or
+    // point to the page, which must be done by name, if the page is a file
+    if target.len() == page.len() && common(&target, &page) == page.len() {
+        return match (suffix, file) {
+            ("", Some(file)) => (*file).to_string(),
+            ("", None) => String::from("./"),
+            _ => suffix.to_string(),
+        };
+    }
+
+    // Ascend to the common directory, then descend to the URL
+    let n = common(&target, dir);
+    let mut segments = vec![".."; dir.len() - n];
+    segments.extend_from_slice(&target[n..]);
+    let mut path = segments.join("/");
+    if segments.is_empty() {
+        path.push_str("./");
+    } else if is_dir {
+        path.push('/');
+    }
+    format!("{path}{suffix}")
+}
+
+/// Returns whether the given URL is located at or below the given base URL.
+///
+/// Query strings and fragments are ignored, and segments are compared in
+/// their decoded form, so `a/b/#c` starts with `a/`, but `ab/` doesn't.
+#[must_use]
+pub fn starts_with(url: &str, base: &str) -> bool {
+    let (url, _) = resolve(split_suffix(url).0);
+    let (base, _) = resolve(split_suffix(base).0);
+    base.len() <= url.len() && common(&url, &base) == base.len()
+}
+
+// ----------------------------------------------------------------------------
+
+/// Returns whether the given URL has an authority or starts with a slash.
+///
+/// Site-relative URLs may contain colons, e.g., `std::vec/`, which is why only
+/// schemes followed by an authority, e.g., `https://`, are considered.
+fn is_absolute(url: &str) -> bool {
+    if url.starts_with('/') {
+        return true;
+    }
+
+    // Check for a scheme followed by an authority
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+/// Returns whether the given page URL denotes a directory.
+fn is_dir_url(url: &str) -> bool {
+    let path = split_suffix(url).0;
+    path.is_empty()
+        || path.ends_with('/')
+        || path
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| !name.contains('.'))
+}
+
+/// Resolves the segments of the given path, and whether it's a directory.
+///
+/// Empty and `.` segments are dropped, and `..` segments remove the segment
+/// before them, or are retained, if they'd leave the path, so the result can
+/// still be joined to a base URL.
+fn resolve(path: &str) -> (Vec<&str>, bool) {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    // Paths denote directories if they end with a slash or a dot segment
+    let last = path.rsplit('/').next().unwrap_or_default();
+    (segments, matches!(last, "" | "." | ".."))
+}
+
+/// Returns the number of equivalent leading segments of both paths.
+fn common(a: &[&str], b: &[&str]) -> usize {
+    a.iter()
+        .zip(b)
+        .take_while(|(a, b)| equivalent(a, b))
+        .count()
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode, encode, encode_fragment, ensure_dir_url, equivalent, extension,
+        file_name, join, parent, relative_to, split_suffix, starts_with,
+        strip_fragment, strip_index,
+    };
+
+    #[test]
+    fn encode_is_idempotent() {
+        for url in ["übersicht/", "getting started/", "编译器/笔记/"] {
+            let encoded = encode(url);
+            assert_eq!(encode(&encoded), encoded);
+            assert_eq!(decode(&encoded), url);
+        }
+    }
+
+    #[test]
+    fn encode_handles_unicode_and_spaces() {
+        assert_eq!(encode("übersicht/"), "%C3%BCbersicht/");
+        assert_eq!(encode("getting started/"), "getting%20started/");
+    }
+
+    #[test]
+    fn encode_fragment_handles_unicode_and_spaces() {
+        assert_eq!(encode_fragment("über uns"), "%C3%BCber%20uns");
+        assert_eq!(encode_fragment("%C3%BCber"), "%C3%BCber");
+    }
+
+    #[test]
+    fn equivalent_ignores_encoding() {
+        assert!(equivalent("übersicht/", "%C3%BCbersicht/"));
+        assert!(equivalent("编译器/", "%E7%BC%96%E8%AF%91%E5%99%A8/"));
+        assert!(equivalent("getting started/", "getting%20started/"));
+        assert!(!equivalent("übersicht/", "uebersicht/"));
+    }
+
+    #[test]
+    fn split_suffix_separates_query_and_fragment() {
+        assert_eq!(split_suffix("guide/"), ("guide/", ""));
+        assert_eq!(split_suffix("guide/#setup"), ("guide/", "#setup"));
+        assert_eq!(split_suffix("guide/?q=a#setup"), ("guide/", "?q=a#setup"));
+        assert_eq!(split_suffix("#setup"), ("", "#setup"));
+    }
+
+    #[test]
+    fn strip_fragment_retains_query() {
+        let test_cases = [
+            ("guide/", "guide/"),
+            ("guide/#setup", "guide/"),
+            ("guide/?q=a#setup", "guide/?q=a"),
+            ("#setup", ""),
+            ("", ""),
+        ];
+        for (url, expected) in test_cases {
+            assert_eq!(strip_fragment(url), expected, "Failed for '{url}'");
+        }
+    }
+
+    #[test]
+    fn strip_index_strips_entire_segments() {
+        let test_cases = [
+            ("index.html", ""),
+            ("guide/index.html", "guide/"),
+            ("guide/", "guide/"),
+            ("myindex.html", "myindex.html"),
+            ("guide/myindex.html", "guide/myindex.html"),
+            ("", ""),
+        ];
+        for (url, expected) in test_cases {
+            assert_eq!(strip_index(url), expected, "Failed for '{url}'");
+        }
+    }
+
+    #[test]
+    fn ensure_dir_url_appends_slash() {
+        let test_cases = [
+            ("", ""),
+            ("guide", "guide/"),
+            ("guide/", "guide/"),
+            ("https://example.com", "https://example.com/"),
+            ("https://example.com/docs/", "https://example.com/docs/"),
+        ];
+        for (url, expected) in test_cases {
+            assert_eq!(ensure_dir_url(url), expected, "Failed for '{url}'");
+        }
+    }
+
+    #[test]
+    fn parent_ignores_trailing_slashes() {
+        let test_cases = [
+            ("a/b/c", Some("a/b")),
+            ("a/b/c/", Some("a/b")),
+            ("a", Some("")),
+            ("a/", Some("")),
+            ("", None),
+            ("/", None),
+        ];
+        for (url, expected) in test_cases {
+            assert_eq!(parent(url), expected, "Failed for '{url}'");
+        }
+    }
+
+    #[test]
+    fn file_name_ignores_query_and_fragment() {
+        let test_cases = [
+            ("a/b.css", Some("b.css")),
+            ("a/b.css?v=1#top", Some("b.css")),
+            ("b.css", Some("b.css")),
+            ("a/b/", None),
+            ("", None),
+        ];
+        for (url, expected) in test_cases {
+            assert_eq!(file_name(url), expected, "Failed for '{url}'");
+        }
+    }
+
+    #[test]
+    fn extension_ignores_leading_dot() {
+        let test_cases = [
+            ("a/b.min.css", Some("css")),
+            ("a/b.css?v=1", Some("css")),
+            ("a/.htaccess", None),
+            ("a.b/c", None),
+            ("a/b", None),
+        ];
+        for (url, expected) in test_cases {
+            assert_eq!(extension(url), expected, "Failed for '{url}'");
+        }
+    }
+
+    #[test]
+    fn join_resolves_segments() {
+        let test_cases = [
+            // Separators
+            (
+                "https://example.com",
+                "guide/",
+                "https://example.com/guide/",
+            ),
+            (
+                "https://example.com/",
+                "guide/",
+                "https://example.com/guide/",
+            ),
+            ("https://example.com/", "", "https://example.com/"),
+            ("de", "", "de/"),
+            ("de", "guide/", "de/guide/"),
+            ("", "guide/", "guide/"),
+            ("", "", ""),
+            // Dot segments
+            (".", "assets/style.css", "./assets/style.css"),
+            ("../..", "./assets/style.css", "../../assets/style.css"),
+            ("..", "a/../b/./c.html", "../b/c.html"),
+            (".", "a/..", "./"),
+            (".", "../a", "./../a"),
+            // Empty segments
+            ("de", "guide//setup/", "de/guide/setup/"),
+            // Queries and fragments
+            ("..", "guide/?q=a/b#c", "../guide/?q=a/b#c"),
+            ("..", "#setup", "../#setup"),
+            // Percent-encoding
+            ("de", "%C3%BCbersicht/", "de/%C3%BCbersicht/"),
+            // Absolute URLs
+            ("de", "/guide/", "/guide/"),
+            ("de", "https://example.com/", "https://example.com/"),
+            ("de", "std::vec/", "de/std::vec/"),
+        ];
+        for (base, url, expected) in test_cases {
+            assert_eq!(
+                join(base, url),
+                expected,
+                "Failed for join('{base}', '{url}')"
+            );
+        }
+    }
+
+    #[test]
+    fn relative_to_resolves_against_directory_of_base() {
+        let test_cases = [
+            // Directory URLs
+            ("b/", "a/", "../b/"),
+            ("a/b/", "a/", "b/"),
+            ("a/", "a/b/", "../"),
+            ("a/", "a/", "./"),
+            ("", "a/b/", "../../"),
+            ("", "", "./"),
+            ("a/", "", "a/"),
+            // File URLs
+            ("a/b.html", "a/b.html", "b.html"),
+            ("a/index.html", "a/b.html", "index.html"),
+            ("a/", "a/b.html", "./"),
+            ("c.html", "a/b.html", "../c.html"),
+            ("", "a.html", "./"),
+            // Dot and empty segments
+            ("./a/./b/", "a/", "b/"),
+            ("a/../b/", "a/", "../b/"),
+            ("a//b/", "a/", "b/"),
+            ("a/b/", "a//", "b/"),
+            // Queries and fragments
+            ("a/#b", "a/", "#b"),
+            ("a/?q=b", "a/", "?q=b"),
+            ("b/?q=c#d", "a/", "../b/?q=c#d"),
+            ("#b", "", "#b"),
+            ("b/", "a/#c", "../b/"),
+            // Percent-encoding
+            ("%C3%BCber/a/", "über/", "a/"),
+            ("über/a/", "%C3%BCber/", "a/"),
+            ("b%20c/", "a/", "../b%20c/"),
+            // Colons
+            ("std::vec/", "a/", "../std::vec/"),
+            // Absolute URLs
+            ("/a/", "b/", "/a/"),
+            ("https://example.com/", "b/", "https://example.com/"),
+        ];
+        for (url, base, expected) in test_cases {
+            assert_eq!(
+                relative_to(url, base),
+                expected,
+                "Failed for relative_to('{url}', '{base}')"
+            );
+        }
+    }
+
+    #[test]
+    fn starts_with_compares_segments() {
+        let test_cases = [
+            ("a/b/#c", "a/", true),
+            ("a/b/", "a/b", true),
+            ("a/b/", "a/b/", true),
+            ("a/b/", "", true),
+            ("ab/", "a/", false),
+            ("a/", "a/b/", false),
+            ("%C3%BCber/a/", "über/", true),
+            ("a/?q=b/c", "a/b/", false),
+        ];
+        for (url, base, expected) in test_cases {
+            assert_eq!(
+                starts_with(url, base),
+                expected,
+                "Failed for starts_with('{url}', '{base}')"
+            );
+        }
+    }
+}
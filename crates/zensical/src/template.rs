@@ -25,7 +25,7 @@
 
 //! MiniJinja template engine.
 
-use minijinja::{context, AutoEscape, Environment, Error};
+use minijinja::{context, AutoEscape, Environment, Error, Value};
 use minijinja_contrib::filters::striptags;
 use serde::Serialize;
 use std::path::PathBuf;
@@ -37,7 +37,9 @@ mod filter;
 mod loader;
 mod output;
 
-use filter::{script_tag_filter, url_filter};
+use filter::{
+    script_tag_filter, sort_by_date_filter, strftime_filter, url_filter,
+};
 use loader::Loader;
 pub use output::Output;
 
@@ -71,10 +73,13 @@ impl Template<'_> {
         env.set_loader(move |name| loader.load(name));
 
         // Register the striptags filter, which isn't part of MiniJinja's common
-        // filters, and add our custom filters to replicate MkDocs' behavior
+        // filters, and add our custom filters to replicate MkDocs' behavior,
+        // as well as filters to format and sort pages by front matter dates
         env.add_filter("striptags", striptags);
         env.add_filter("url", url_filter);
         env.add_filter("script_tag", script_tag_filter);
+        env.add_filter("strftime", strftime_filter);
+        env.add_filter("sort_by_date", sort_by_date_filter);
 
         // Reset auto-escaping, as we don't want to escape HTML in templates
         env.set_auto_escape_callback(|_| AutoEscape::None);
@@ -90,12 +95,34 @@ impl Template<'_> {
         template.render(context)
     }
 
+    /// Renders a single block of the template with the given context.
+    ///
+    /// Blocks are resolved through the template's inheritance chain, so a
+    /// block defined in a base template can be rendered from a template that
+    /// extends it. Rendering an unknown block fails with an error of kind
+    /// `UnknownBlock`.
+    pub fn render_block<C>(
+        &self, block: &str, context: C,
+    ) -> Result<String, Error>
+    where
+        C: Serialize,
+    {
+        let template = self.env.get_template(&self.name)?;
+        template.eval_to_state(context)?.render_block(block)
+    }
+
     /// Renders the template.
     pub fn render(
         &self, config: &Config, nav: &Navigation,
     ) -> Result<String, Error> {
         let template = self.env.get_template(&self.name)?;
-        let pages = nav.iter().collect::<Vec<_>>();
+
+        // Pages that opt out of indexing must not be listed in the sitemap
+        let pages = if self.name == "sitemap.xml" {
+            nav.indexable().collect::<Vec<_>>()
+        } else {
+            nav.iter().collect::<Vec<_>>()
+        };
 
         // Create context and render template
         template.render(context! {
@@ -106,12 +133,15 @@ impl Template<'_> {
             extra_css => config.project.extra_css.clone(),
             extra_javascript => config.project.extra_javascript.clone(),
             config => config.project.clone(),
+            features => config.project.theme.features.flags(),
             // MiniJinja does not allow to pass empty objects, so we create a
             // dummy page here - these won't be used in static templates
             page => context! {
                 ancestors => Vec::<()>::new(),
                 toc => Vec::<()>::new()
             },
+            // Merge extra context last, so it can't shadow the values above
+            ..Value::from_serialize(&config.project.extra_context)
         })
     }
 }
@@ -123,3 +153,108 @@ impl Template<'_> {
 /// Generator string.
 pub const GENERATOR: &str =
     concat!(env!("CARGO_PKG_NAME"), "-", env!("CARGO_PKG_VERSION"));
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use minijinja::{context, ErrorKind, Value};
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    use crate::structure::dynamic::Dynamic;
+
+    use super::Template;
+
+    /// Parses the given JSON into a dynamic value.
+    fn dynamic(value: &str) -> Dynamic {
+        serde_json::from_str(value).unwrap()
+    }
+
+    /// Renders the given template source with extra settings and context.
+    fn render(
+        source: &str, extra: &Dynamic, extra_context: &Dynamic,
+    ) -> String {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("main.html"), source).unwrap();
+        let template = Template::new("main.html", [dir.path().to_path_buf()]);
+        template
+            .render_with_context(context! {
+                config => context! { extra => extra },
+                ..Value::from_serialize(extra_context)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn renders_block_from_base_template() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.html"),
+            "<header>{% block header %}{% endblock %}</header>\
+             <main>{% block content %}{{ page.content }}{% endblock %}</main>",
+        )
+        .unwrap();
+        fs::write(dir.path().join("main.html"), "{% extends \"base.html\" %}")
+            .unwrap();
+
+        // Only the requested block is rendered
+        let template = Template::new("main.html", [dir.path().to_path_buf()]);
+        let context = context! { page => context! { content => "<p>Hi</p>" } };
+        let output = template.render_block("content", &context).unwrap();
+        assert_eq!(output, "<p>Hi</p>");
+
+        // Unknown blocks are reported as such
+        let err = template.render_block("sidebar", &context).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownBlock);
+    }
+
+    #[test]
+    fn renders_nested_extra_values() {
+        let extra = dynamic(
+            r#"{
+                "banner": { "text": "Global", "show": true },
+                "versions": [{ "name": "1.0" }, { "name": "2.0" }],
+                "released": "2026-01-01"
+            }"#,
+        );
+        let output = render(
+            "{{ config.extra.banner.text }}|{{ config.extra.banner.show }}|\
+             {{ config.extra.versions[1].name }}|{{ config.extra.released }}",
+            &extra,
+            &Dynamic::Map(BTreeMap::new()),
+        );
+        assert_eq!(output, "Global|true|2.0|2026-01-01");
+    }
+
+    #[test]
+    fn renders_page_level_extra_override() {
+        let mut extra = dynamic(
+            r#"{ "banner": { "text": "Global", "show": true }, "id": 1 }"#,
+        );
+        extra.merge(dynamic(r#"{ "banner": { "text": "Page" } }"#));
+        let output = render(
+            "{{ config.extra.banner.text }}|{{ config.extra.banner.show }}|\
+             {{ config.extra.id }}",
+            &extra,
+            &Dynamic::Map(BTreeMap::new()),
+        );
+        assert_eq!(output, "Page|true|1");
+    }
+
+    #[test]
+    fn renders_extra_context_at_top_level() {
+        let extra_context = dynamic(
+            r#"{ "announcement": { "text": "Hello" }, "config": "shadow" }"#,
+        );
+        let output = render(
+            "{{ announcement.text }}|{{ config.extra.id }}",
+            &dynamic(r#"{ "id": 1 }"#),
+            &extra_context,
+        );
+        assert_eq!(output, "Hello|1");
+    }
+}
@@ -25,13 +25,16 @@
 
 //! MiniJinja template filters.
 
+use minijinja::value::ValueKind;
 use minijinja::{State, Value};
 use std::fmt::Write;
 use std::path::Path;
-use zrx::path::PathExt;
 
 use zensical_serve::http::Uri;
 
+use crate::structure::dynamic::Date;
+use crate::structure::url;
+
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
@@ -41,11 +44,21 @@ use zensical_serve::http::Uri;
 /// This filter replicates the filter of the same name in MkDocs, resolving URLs
 /// relative to the current page. If no page object is given, a static template
 /// is rendered, which means that URLs must be resolved relative to base URL.
+/// Query strings and fragments are split off before resolving, and attached
+/// to the resolved URL again.
 pub fn url_filter(state: &State, url: String) -> String {
-    if url.starts_with('#') {
+    let (path, suffix) = url::split_suffix(&url);
+    if path.is_empty() {
         return url;
     }
 
+    // Resolve path, and attach query string and fragment again
+    let resolved = resolve_url(state, path.to_string());
+    format!("{resolved}{suffix}")
+}
+
+/// Resolves the given URL without query string and fragment.
+fn resolve_url(state: &State, url: String) -> String {
     // Leave absolute links unchanged
     if url.starts_with("http://") || url.starts_with("https://") {
         return url;
@@ -56,9 +69,6 @@ pub fn url_filter(state: &State, url: String) -> String {
         return encode_local_url(&url);
     }
 
-    // Create target URL
-    let target = Path::new(&url);
-
     // Render URLs in pages
     if let Some(source) = state
         .lookup("page")
@@ -66,47 +76,20 @@ pub fn url_filter(state: &State, url: String) -> String {
         .filter(|value| !value.is_undefined())
         .map(|value| value.to_string())
     {
-        // Make target URL relative to page
-        let mut relative_url = target
-            .relative_to(&source)
-            .to_string_lossy()
-            .replace('\\', "/");
-
-        // Since URLs can contain `:`, we could either URL-encode the path, or
-        // leave it as is, and just make sure that the URL starts with a `.`
-        // We should probably change this in the relative URL computation, but
-        // for now this should catch most cases.
-        if !relative_url.starts_with("../") {
-            relative_url = format!("./{relative_url}");
+        // Make target URL relative to page - since URLs can contain `:`, we
+        // could either URL-encode the path, or leave it as is, and just make
+        // sure that the URL starts with a `.`, which is what we do
+        let relative_url = url::relative_to(&url, &source);
+        if relative_url.starts_with("./") || relative_url.starts_with("../") {
+            encode_local_url(&relative_url)
+        } else {
+            encode_local_url(&format!("./{relative_url}"))
         }
 
-        // We can return if we don't stay on the same page
-        if relative_url != "./." {
-            return encode_local_url(&relative_url);
-        }
-
-        // If directory URLs are enabled, and the target is ".", we use need to
-        // fall back to the file name of the target file. This should probably
-        // be fixed in ZRX, but we must check whether this behavior generalizes
-        // to paths, or is exclusive to URLs.
-        #[allow(clippy::case_sensitive_file_extension_comparisons)]
-        #[allow(clippy::map_unwrap_or)]
-        if source.ends_with(".html") && url.ends_with(".html") {
-            return target
-                .file_name()
-                .map(|name| encode_local_url(&name.to_string_lossy()))
-                .unwrap_or_else(|| url);
-        }
-        encode_local_url(&url)
-
     // Render URLs in static templates
     } else {
         let source = state.lookup("base_url").expect("invariant");
-        let url = Path::new(&source.to_string())
-            .join(target.normalize())
-            .to_string_lossy()
-            .replace('\\', "/");
-        encode_local_url(&url)
+        encode_local_url(&url::join(&source.to_string(), &url))
     }
 }
 
@@ -156,14 +139,60 @@ pub fn script_tag_filter(state: &State, value: Value) -> String {
     html
 }
 
+/// MiniJinja `strftime` filter.
+///
+/// This filter formats dates from front matter, which are passed to templates
+/// in their original format, with the given `strftime` format. Values that
+/// aren't dates are returned unchanged, so templates don't break on dates in
+/// unknown formats, which are reported when the page is built.
+pub fn strftime_filter(value: Value, format: &str) -> Value {
+    match value.as_str().and_then(Date::parse) {
+        Some(date) => Value::from(date.format(format)),
+        None => value,
+    }
+}
+
+/// MiniJinja `sort_by_date` filter.
+///
+/// This filter sorts pages by the date in their front matter, which is taken
+/// from `date`, `date.created` or `created`, oldest first, unless `reverse`
+/// is set. Dates are compared as points in time, so offsets are respected.
+/// Pages without date are sorted before all others, and thus last if the
+/// order is reversed, which is what blogs and feeds need.
+pub fn sort_by_date_filter(
+    pages: Vec<Value>, reverse: Option<bool>,
+) -> Vec<Value> {
+    let mut pages = pages
+        .into_iter()
+        .map(|page| (page_date(&page), page))
+        .collect::<Vec<_>>();
+
+    // Sort pages by date, and reverse if requested
+    pages.sort_by(|a, b| a.0.cmp(&b.0));
+    if reverse.unwrap_or(false) {
+        pages.reverse();
+    }
+    pages.into_iter().map(|(_, page)| page).collect()
+}
+
 // ----------------------------------------------------------------------------
 
+/// Returns the date from the front matter of the given page, if any.
+fn page_date(page: &Value) -> Option<Date> {
+    let meta = page.get_attr("meta").ok()?;
+    let value = match meta.get_attr("date").ok() {
+        Some(date) if date.kind() == ValueKind::Map => {
+            date.get_attr("created").ok()?
+        }
+        Some(date) if !date.is_undefined() => date,
+        _ => meta.get_attr("created").ok()?,
+    };
+    value.as_str().and_then(Date::parse)
+}
+
 // Local URL encoding
 fn encode_local_url(url: &str) -> String {
-    let (path, suffix) = match url.find(['?', '#']) {
-        Some(index) => (&url[..index], &url[index..]),
-        None => (url, ""),
-    };
+    let (path, suffix) = url::split_suffix(url);
 
     // Encode the path using `Uri::from`
     let encoded = Uri::from(path).to_string();
@@ -176,7 +205,11 @@ fn encode_local_url(url: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::encode_local_url;
+    use minijinja::{context, Environment, Value};
+
+    use super::{
+        encode_local_url, sort_by_date_filter, strftime_filter, url_filter,
+    };
 
     #[test]
     fn local_urls_encode_ampersands_in_paths() {
@@ -202,4 +235,53 @@ mod tests {
             "./test%26test/page/"
         );
     }
+
+    #[test]
+    fn url_filter_preserves_query_and_fragment() {
+        let mut env = Environment::new();
+        env.add_filter("url", url_filter);
+        let render = |url: &str| {
+            let ctx = context! { url, page => context! { url => "a/" } };
+            env.render_str("{{ url | url }}", ctx).unwrap()
+        };
+        let path = render("b/");
+        assert_eq!(render("b/#setup"), format!("{path}#setup"));
+        assert_eq!(render("b/?q=a#setup"), format!("{path}?q=a#setup"));
+        assert_eq!(render("#setup"), "#setup");
+    }
+
+    #[test]
+    fn strftime_formats_dates() {
+        let value = Value::from("2024-03-09T15:04:05+01:00");
+        let value = strftime_filter(value, "%B %e, %Y %H:%M");
+        assert_eq!(value.as_str(), Some("March  9, 2024 15:04"));
+
+        // Values in unknown formats are returned unchanged
+        let value = strftime_filter(Value::from("soon"), "%Y");
+        assert_eq!(value.as_str(), Some("soon"));
+    }
+
+    #[test]
+    fn sort_by_date_uses_typed_dates() {
+        let page = |url: &str, meta: Value| context! { url, meta };
+        let pages = vec![
+            page("a/", context! { date => "2024-01-02T01:00+02:00" }),
+            page(
+                "b/",
+                context! { date => context! { created => "2024-01-01" } },
+            ),
+            page("c/", context! {}),
+            page("d/", context! { created => "January 2, 2024" }),
+        ];
+        let urls = |pages: Vec<Value>| {
+            pages
+                .iter()
+                .map(|page| page.get_attr("url").unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        let sorted = sort_by_date_filter(pages.clone(), None);
+        assert_eq!(urls(sorted), ["c/", "b/", "a/", "d/"]);
+        let sorted = sort_by_date_filter(pages, Some(true));
+        assert_eq!(urls(sorted), ["d/", "a/", "b/", "c/"]);
+    }
 }
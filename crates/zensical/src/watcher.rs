@@ -31,14 +31,22 @@ use std::collections::BTreeSet;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use zensical_watch::agent::Manager;
 use zensical_watch::event::{Event, Kind};
 use zensical_watch::{Agent, Error, Result};
 use zrx::id::Id;
 use zrx::scheduler::Session;
 
+use super::config::serve::ReloadPathStyle;
 use super::config::Config;
+use super::server::Message;
+use super::structure::url;
+use super::workflow::{
+    copy_extra_file, is_precompressed, Cache, Diagnostics, Hidden, Includes,
+    OutputSink, Registry, Report,
+};
 
 mod source;
 
@@ -55,6 +63,12 @@ pub use source::Source;
 pub struct Watcher {
     /// File agent.
     agent: Agent,
+    /// Include graph.
+    includes: Includes,
+    /// Snippet directories containing the project directory.
+    snippet_roots: Vec<PathBuf>,
+    /// Included files in snippet roots, which are watched individually.
+    watched: Mutex<BTreeSet<PathBuf>>,
 }
 
 // ----------------------------------------------------------------------------
@@ -63,9 +77,18 @@ pub struct Watcher {
 
 impl Watcher {
     /// Creates a file watcher.
-    #[allow(clippy::too_many_lines)]
+    ///
+    /// When pages are removed or renamed, their outputs are pruned from the
+    /// site directory using the given registry, which in turn triggers a
+    /// reload, as the site directory is watched. When files are renamed, their
+    /// cache entries are moved, so renamed pages are not rendered again. Files
+    /// outside of the docs and theme directories are copied through the given
+    /// sink when they change, just like during the build.
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
     pub fn new(
         config: &Config, serve: bool, session: Session<Id, Source>,
+        includes: Includes, registry: Registry, cache: Cache,
+        sink: Arc<dyn OutputSink>, report: Report, diagnostics: Diagnostics,
         reload: Sender<String>, waker: Option<Arc<Waker>>,
     ) -> Result<Self> {
         let mut sources = Vec::default();
@@ -85,6 +108,11 @@ impl Watcher {
         sources.push((config.get_site_dir(), config.project.site_dir.clone()));
         sources.push((path, String::from(".")));
 
+        // Add canonical forms of all sources, since the file agent might report
+        // resolved paths, e.g., if the docs directory is a symbolic link. On
+        // Windows, junctions are resolved to verbatim paths in the same way
+        let sources = with_canonical(sources);
+
         // Track seen files to restart on config or template change
         let mut seen = BTreeSet::new();
 
@@ -103,16 +131,62 @@ impl Watcher {
             .map(|(path, _)| canonical_or_clone(path))
             .collect::<BTreeSet<_>>();
 
+        // Determine snippet directories that need to be watched in addition,
+        // which are all base paths outside of the docs directory. Base paths
+        // containing the project directory, e.g., `.`, can't be watched, as
+        // they contain the site directory, so we watch included files instead
+        let docs_dir = canonical_or_clone(&config.get_docs_dir());
+        let root_dir = canonical_or_clone(&config.get_root_dir());
+        let snippets = &config.project.snippets;
+        let (snippet_roots, snippet_dirs) = if snippets.enabled {
+            snippets
+                .base_path
+                .iter()
+                .map(|path| canonical_or_clone(path))
+                .filter(|path| path.is_dir() && !path.starts_with(&docs_dir))
+                .partition::<Vec<_>, _>(|path| root_dir.starts_with(path))
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        // Determine directories of files outside of the docs and theme
+        // directories, which are copied to the site directory, i.e., logo,
+        // favicon and extra files, and must be copied again when changed
+        let site_dir = canonical_or_clone(&config.get_site_dir());
+        let mut roots = vec![docs_dir.clone(), site_dir];
+        roots.extend(theme_dirs.iter().cloned());
+        let theme_files = config
+            .theme_files
+            .iter()
+            .map(|(path, url)| (canonical_or_clone(path), url.clone()))
+            .collect::<Vec<_>>();
+        let extra_dirs = extra_dirs(&theme_files, &roots, &root_dir);
+
+        // Hidden directories are skipped by the file manager, except for those
+        // in the docs directory that contain hidden files that are included
+        let manager = Manager::new().with_hidden({
+            let hidden = Hidden::new(config);
+            let docs_dir = docs_dir.clone();
+            move |path| path.starts_with(&docs_dir) && hidden.admits_dir(path)
+        });
+
         // Initialize file agent - we use a debounce interval of 20ms, which
         // should be sufficient to correctly determine rename events
-        let agent = Agent::new(Duration::from_millis(20), serve, {
+        let interval = Duration::from_millis(20);
+        let agent = Agent::with_manager(interval, serve, manager, {
             let config = config.clone();
+            let includes = includes.clone();
+            let snippet_dirs = snippet_dirs.clone();
+            let extra_dirs = extra_dirs.clone();
             move |res| {
                 // For now, we just swallow the event, as the file agent should
                 // Skip anything other than files and symbolic links.
                 // Link events allow assets provided via editable installs
                 // (e.g. symlinked theme directories) to enter the build.
                 if let Ok(event) = res {
+                    #[cfg(feature = "tracing")]
+                    let _span =
+                        tracing::info_span!("watch", event = ?event).entered();
                     if !matches!(event.kind(), Kind::File | Kind::Link) {
                         return Ok(());
                     }
@@ -158,6 +232,51 @@ impl Watcher {
                         return Err(Error::Disconnected);
                     }
 
+                    // Submit all pages including the file again, directly or
+                    // transitively, as they must be built again to reflect the
+                    // changes, since the scheduler doesn't know about snippets
+                    let dependents = includes.dependents(&event_path);
+                    let is_included = !dependents.is_empty();
+                    for (id, source) in dependents {
+                        session.insert(id, source.into())?;
+                    }
+
+                    // Copy changed files outside of the docs and theme
+                    // directories again, which triggers a reload, since the
+                    // site directory is watched. Ignore all other events in
+                    // their directories, as they are not part of the build
+                    if let Some(url) =
+                        find_theme_file(&theme_files, &event_path)
+                    {
+                        let from = event_path.as_path();
+                        if from.is_file() {
+                            if let Err(err) = copy_extra_file(
+                                &config, from, url, &*sink, &report,
+                            ) {
+                                diagnostics.warning(format!(
+                                    "Failed to copy '{}': {err}",
+                                    from.display()
+                                ));
+                            }
+                        }
+                        return Ok(());
+                    }
+                    if extra_dirs.iter().any(|dir| event_path.starts_with(dir))
+                    {
+                        return Ok(());
+                    }
+
+                    // Ignore all other events in snippet directories, as they
+                    // are only watched to track changes to included files
+                    if !event_path.starts_with(&docs_dir)
+                        && (is_included
+                            || snippet_dirs
+                                .iter()
+                                .any(|dir| event_path.starts_with(dir)))
+                    {
+                        return Ok(());
+                    }
+
                     // Ignore events in the site directory, since they are files
                     // that were generated and should not trigger a rebuild. We
                     // forward them to the reload channel in the server instead,
@@ -165,30 +284,34 @@ impl Watcher {
                     let site_dir = config.get_site_dir();
                     let site_dir = canonical_or_clone(&site_dir);
                     if event_path.starts_with(&site_dir) {
+                        // Ignore precompressed copies, which are written next
+                        // to outputs, as they would trigger a second reload
+                        if is_precompressed(&event_path) {
+                            return Ok(());
+                        }
+
                         // Compute identifier, since we need the relative URL
                         // so we only reload the page the client is on.
-                        let id = to_id(event.path().clone(), &sources);
-
-                        // Compute path, and if directory URLs are enabled,
-                        // strip the `index.html` suffix, if present.
-                        let path = id.as_uri().to_string();
-                        let path = if config.project.use_directory_urls {
-                            path.trim_end_matches("index.html")
-                        } else {
-                            path.as_str()
+                        let path = event.path();
+                        let Some(id) = to_id(&path, &sources) else {
+                            warn_unmatched(&diagnostics, &path);
+                            return Ok(());
                         };
 
-                        // Prepend base path
-                        let base = config.get_base_path();
-                        let path = if base == "/" {
-                            format!("{base}{path}")
-                        } else {
-                            format!("{base}/{path}")
-                        };
+                        // Compute path in the configured style. Note that
+                        // the client normalizes both paths before comparing,
+                        // as index pages can be requested with and without
+                        // the suffix, regardless of the URL mode.
+                        let path = reload_path(
+                            &id.as_uri().to_string(),
+                            &config.get_base_path(),
+                            config.project.use_directory_urls,
+                            config.project.serve.reload_path_style,
+                        );
 
                         // Send path to reload channel and wake server polling
                         // loop, if available (i.e., serve mode is enabled)
-                        let _ = reload.send(path);
+                        let _ = reload.send(Message::Reload(path).encode());
                         if let Some(waker) = &waker {
                             waker.wake()?;
                         }
@@ -203,21 +326,72 @@ impl Watcher {
                         // File was created or modified
                         Event::Create { path, .. }
                         | Event::Modify { path, .. } => {
-                            let data = path.to_string_lossy().into_owned();
-                            session
-                                .insert(to_id(path, &sources), data.into())?;
+                            if let Some(id) = to_id(&path, &sources) {
+                                let data = path.to_string_lossy().into_owned();
+                                session.insert(id, data.into())?;
+                            } else {
+                                warn_unmatched(&diagnostics, &path);
+                            }
                         }
 
-                        // File was renamed
+                        // File was renamed - the cache entry is moved first,
+                        // so the renamed file is not rendered again
                         Event::Rename { from, to, .. } => {
-                            let data = to.to_string_lossy().into_owned();
-                            session.remove(to_id(from, &sources))?;
-                            session.insert(to_id(to, &sources), data.into())?;
+                            let ids =
+                                (to_id(&from, &sources), to_id(&to, &sources));
+                            if let (Some(old), Some(new)) = &ids {
+                                if let Err(err) =
+                                    cache.rename(old.as_str(), new.as_str())
+                                {
+                                    diagnostics.warning(format!(
+                                        "Failed to move cache entry of \
+                                         '{}': {err}",
+                                        old.location()
+                                    ));
+                                }
+                            }
+                            if let Some(id) = ids.0 {
+                                let rivals = prune(
+                                    &config,
+                                    &registry,
+                                    &*sink,
+                                    &diagnostics,
+                                    &id,
+                                    &sources,
+                                );
+                                session.remove(id)?;
+                                for (id, data) in rivals {
+                                    session.insert(id, data.into())?;
+                                }
+                            } else {
+                                warn_unmatched(&diagnostics, &from);
+                            }
+                            if let Some(id) = ids.1 {
+                                let data = to.to_string_lossy().into_owned();
+                                session.insert(id, data.into())?;
+                            } else {
+                                warn_unmatched(&diagnostics, &to);
+                            }
                         }
 
                         // File was removed
                         Event::Remove { path, .. } => {
-                            session.remove(to_id(path, &sources))?;
+                            if let Some(id) = to_id(&path, &sources) {
+                                let rivals = prune(
+                                    &config,
+                                    &registry,
+                                    &*sink,
+                                    &diagnostics,
+                                    &id,
+                                    &sources,
+                                );
+                                session.remove(id)?;
+                                for (id, data) in rivals {
+                                    session.insert(id, data.into())?;
+                                }
+                            } else {
+                                warn_unmatched(&diagnostics, &path);
+                            }
                         }
                     }
                 }
@@ -253,14 +427,46 @@ impl Watcher {
             agent.watch(path)?;
         }
 
-        // Watch site directory, ensuring it exists
-        let site_dir = config.get_site_dir();
-        fs::create_dir_all(&site_dir).unwrap();
-        agent.watch(&site_dir)?;
+        // Watch snippet directories
+        for path in &snippet_dirs {
+            agent.watch(path)?;
+        }
+
+        // Watch site directory, which is created when it's resolved, and might
+        // be located outside of the project, e.g., if the source is read-only
+        agent.watch(config.get_site_dir())?;
+
+        // Watch directories of files outside of the docs and theme directories
+        for path in &extra_dirs {
+            agent.watch(path)?;
+        }
 
         // Return file watcher
         agent.watch(config.get_docs_dir())?;
-        Ok(Self { agent })
+        Ok(Self {
+            agent,
+            includes,
+            snippet_roots,
+            watched: Mutex::default(),
+        })
+    }
+
+    /// Watches files included from snippet directories containing the project
+    /// directory, which must be called after each build.
+    ///
+    /// Those directories can't be watched as a whole, so each included file is
+    /// watched individually, once it's recorded in the include graph.
+    pub fn watch_includes(&self) -> Result {
+        let mut watched = self.watched.lock().expect("invariant");
+        for path in self.includes.files() {
+            if self.snippet_roots.iter().any(|dir| path.starts_with(dir))
+                && !watched.contains(&path)
+            {
+                self.agent.watch(&path)?;
+                watched.insert(path);
+            }
+        }
+        Ok(())
     }
 
     /// Returns whether the watcher is terminated.
@@ -277,29 +483,256 @@ impl Watcher {
 ///
 /// This will also be hoisted into the file provider, which will make sure that
 /// identifiers are platform independent by always ensuring forward slashes.
-fn to_id(path: Arc<PathBuf>, sources: &[(PathBuf, String)]) -> Id {
-    let option = sources.iter().find_map(|(prefix, context)| {
-        if let Ok(suffix) = path.strip_prefix(prefix) {
-            let location = suffix.to_str().unwrap_or("");
-            Some(
-                Id::builder()
-                    .provider("file")
-                    .context(context.replace('\\', "/"))
-                    .location(location.replace('\\', "/"))
-                    .build()
-                    .expect("invariant"),
-            )
+/// If no source matches the given path, [`None`] is returned, which can only
+/// happen if the path and the sources are in different forms, e.g., if one of
+/// them is resolved and the other isn't, so the event must be dropped.
+fn to_id(path: &Path, sources: &[(PathBuf, String)]) -> Option<Id> {
+    sources.iter().find_map(|(prefix, context)| {
+        let suffix = path.strip_prefix(prefix).ok()?;
+        let location = suffix.to_str().unwrap_or("");
+        let id = Id::builder()
+            .provider("file")
+            .context(context.replace('\\', "/"))
+            .location(location.replace('\\', "/"))
+            .build()
+            .expect("invariant");
+        Some(id)
+    })
+}
+
+/// Add the canonical form of each source after it, if it differs.
+///
+/// Sources keep their order, so more specific sources still take precedence
+/// over the project root, regardless of the form in which a path is given.
+fn with_canonical(sources: Vec<(PathBuf, String)>) -> Vec<(PathBuf, String)> {
+    let mut expanded = Vec::with_capacity(sources.len() * 2);
+    for (path, context) in sources {
+        let canonical = canonical_or_clone(&path);
+        if canonical != path {
+            expanded.push((path, context.clone()));
+            expanded.push((canonical, context));
         } else {
-            None
+            expanded.push((path, context));
         }
-    });
+    }
+    expanded
+}
+
+/// Returns the directories of the given files, which must be watched.
+///
+/// Directories inside of the given roots are already watched, and ancestors of
+/// the project directory can't be watched, so both are skipped. Directories
+/// nested in other directories are dropped, so no directory is watched twice.
+fn extra_dirs(
+    files: &[(PathBuf, String)], roots: &[PathBuf], root_dir: &Path,
+) -> Vec<PathBuf> {
+    let dirs = files
+        .iter()
+        .filter_map(|(path, _)| path.parent())
+        .filter(|dir| !roots.iter().any(|root| dir.starts_with(root)))
+        .filter(|dir| !root_dir.starts_with(dir))
+        .map(Path::to_path_buf)
+        .collect::<BTreeSet<_>>();
+
+    // Retain only top-level directories, which are watched recursively
+    dirs.iter()
+        .filter(|dir| {
+            !dirs
+                .iter()
+                .any(|other| other != *dir && dir.starts_with(other))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the site-relative URL of the given file, if it's one of the files
+/// that are located outside of the docs and theme directories.
+fn find_theme_file<'a>(
+    files: &'a [(PathBuf, String)], path: &Path,
+) -> Option<&'a str> {
+    files
+        .iter()
+        .find(|(file, _)| file == path)
+        .map(|(_, url)| url.as_str())
+}
+
+/// Prune the outputs of the page with the given identifier, if any.
+///
+/// Source files are recorded in the registry in the same way as when pages
+/// are generated, so only Markdown files will ever have outputs to prune.
+/// Outputs are removed through the given sink, which refuses to remove files
+/// outside of the site directory. Returns the identifiers and paths of pages
+/// that claimed the same outputs, which must be submitted again, as they might
+/// have been shadowed.
+fn prune(
+    config: &Config, registry: &Registry, sink: &dyn OutputSink,
+    diagnostics: &Diagnostics, id: &Id, sources: &[(PathBuf, String)],
+) -> Vec<(Id, String)> {
+    let source = config.get_root_dir().join(id.to_path());
+    let rivals = registry.rivals(&source);
+    let site_dir = config.get_site_dir();
+    if let Err(err) = registry.prune(&source, &site_dir, sink) {
+        diagnostics.warning(format!(
+            "Failed to remove outputs of '{}': {err}",
+            source.display()
+        ));
+    }
+
+    // Resolve identifiers of rivals that still exist
+    rivals
+        .into_iter()
+        .filter(|path| path.exists())
+        .filter_map(|path| {
+            let id = to_id(&path, sources)?;
+            Some((id, path.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
 
-    // Note that this cannot fail, since there must be a path in the source
-    // mapping that matches the given path, at least the project root
-    option.expect("invariant")
+/// Returns the path sent to the client to reload the page at the given URI.
+///
+/// If directory URLs are enabled, and the style is set to trim directories,
+/// the `index.html` suffix is trimmed. The path is decoded, since the client
+/// compares decoded paths, and prefixed with the base path of the site.
+fn reload_path(
+    uri: &str, base: &str, use_directory_urls: bool, style: ReloadPathStyle,
+) -> String {
+    let path = if use_directory_urls && style == ReloadPathStyle::Directory {
+        url::strip_index(uri)
+    } else {
+        uri
+    };
+
+    // Decode path and prepend base path
+    let path = url::decode(path);
+    if base == "/" {
+        format!("{base}{path}")
+    } else {
+        format!("{base}/{path}")
+    }
+}
+
+/// Warn about an event for a path that matches no source, which is dropped.
+fn warn_unmatched(diagnostics: &Diagnostics, path: &Path) {
+    diagnostics.warning(format!(
+        "Ignoring change to '{}', which is outside of all watched directories",
+        path.display()
+    ));
 }
 
 #[inline]
 fn canonical_or_clone(path: &Path) -> PathBuf {
     fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use zrx::id::Id;
+
+    use super::{
+        extra_dirs, find_theme_file, reload_path, to_id, with_canonical,
+    };
+    use crate::config::serve::ReloadPathStyle;
+
+    /// Creates an identifier for the given context and location.
+    fn id(context: &str, location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context(context)
+            .location(location)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn to_id_returns_none_for_unmatched_path() {
+        let sources = vec![(PathBuf::from("/project/docs"), "docs".into())];
+        let path = PathBuf::from("/project/docs/index.md");
+        assert_eq!(to_id(&path, &sources), Some(id("docs", "index.md")));
+        assert_eq!(to_id(&PathBuf::from("/other/index.md"), &sources), None);
+    }
+
+    #[test]
+    fn reload_path_trims_index_in_directory_style() {
+        let style = ReloadPathStyle::Directory;
+        assert_eq!(reload_path("a/index.html", "/", true, style), "/a/");
+        assert_eq!(reload_path("index.html", "/docs", true, style), "/docs/");
+        assert_eq!(
+            reload_path("a/index.html", "/", false, style),
+            "/a/index.html"
+        );
+        assert_eq!(reload_path("a%20b.html", "/", false, style), "/a b.html");
+    }
+
+    #[test]
+    fn reload_path_retains_index_in_file_style() {
+        let style = ReloadPathStyle::File;
+        for use_directory_urls in [true, false] {
+            assert_eq!(
+                reload_path("a/index.html", "/", use_directory_urls, style),
+                "/a/index.html"
+            );
+            assert_eq!(
+                reload_path("index.html", "/docs", use_directory_urls, style),
+                "/docs/index.html"
+            );
+        }
+    }
+
+    #[test]
+    fn extra_dirs_skip_watched_and_nested_dirs() {
+        let files = [
+            (PathBuf::from("/project/shared/brand.css"), String::new()),
+            (PathBuf::from("/project/shared/js/app.js"), String::new()),
+            (PathBuf::from("/project/docs/extra.css"), String::new()),
+            (PathBuf::from("/project/logo.png"), String::new()),
+            (PathBuf::from("/assets/favicon.png"), String::new()),
+        ];
+        let roots = [PathBuf::from("/project/docs")];
+        assert_eq!(
+            extra_dirs(&files, &roots, &PathBuf::from("/project")),
+            vec![PathBuf::from("/assets"), PathBuf::from("/project/shared")]
+        );
+    }
+
+    #[test]
+    fn find_theme_file_returns_url_of_registered_file() {
+        let url = String::from("assets/stylesheets/brand.css");
+        let files = [(PathBuf::from("/shared/brand.css"), url.clone())];
+        let source = Path::new("/shared/brand.css");
+        assert_eq!(find_theme_file(&files, source), Some(url.as_str()));
+        let other = Path::new("/shared/other.css");
+        assert_eq!(find_theme_file(&files, other), None);
+    }
+
+    // Junctions on Windows are resolved by canonicalization in the same way,
+    // but can't be created without additional tooling, so we only test Unix
+    #[cfg(unix)]
+    #[test]
+    fn to_id_handles_symlinked_docs_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("project");
+        let shared = temp.path().join("shared");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&shared).unwrap();
+        std::os::unix::fs::symlink(&shared, root.join("docs")).unwrap();
+
+        // Both the link and the resolved path must map to the docs context
+        let sources = with_canonical(vec![
+            (root.join("docs"), "docs".into()),
+            (root.clone(), ".".into()),
+        ]);
+        for path in [
+            root.join("docs/index.md"),
+            fs::canonicalize(&shared).unwrap().join("index.md"),
+        ] {
+            assert_eq!(to_id(&path, &sources), Some(id("docs", "index.md")));
+        }
+    }
+}
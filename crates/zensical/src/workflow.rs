@@ -25,31 +25,78 @@
 
 //! Workflow definitions
 
+use ahash::HashMap;
+use anyhow::Result;
 use pyo3::types::PyAnyMethods;
 use pyo3::Python;
 use regex::Regex;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::{Arc, LazyLock};
-use std::{fs, io};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::{fs, io, mem};
 use zrx::id::{id, Id, Matcher};
 use zrx::module::{self, Context, Module};
 use zrx::scheduler::Key;
 use zrx::stream::{Barrier, Stream, Workflow};
 
+use super::config::build::Encoding;
+use super::config::theme::distance;
 use super::config::Config;
+use super::hash::StableHasher;
+use super::privacy::Privacy;
 use super::python::{Anchors, Issues, References};
-use super::structure::markdown::Markdown;
+use super::server::Partials;
+use super::social::Social;
+use super::structure::blog::{Blog, Listings};
+use super::structure::i18n::{fallback, localize};
+use super::structure::manifest::Manifest;
+use super::structure::markdown::{
+    check_fences, check_structure, decode, render_variables, Decoded, Invalid,
+    Markdown, MarkdownConverter, Snippets,
+};
 use super::structure::nav::Navigation;
-use super::structure::page::Page;
+use super::structure::page::{
+    absolute_links, backslash_links, duplicate_ids, invalid_canonical_url,
+    leaked_paths, parse_dates, prefix_absolute_links, process_images,
+    relativize_absolute_links, to_location, ContentHashes, Page, PageSummary,
+};
 use super::structure::search::SearchIndex;
+use super::structure::url;
 use super::template::Template;
 use super::watcher::Source;
 
+mod assets;
+mod branches;
 mod cached;
-
+mod cancel;
+mod compress;
+mod diagnostics;
+mod hidden;
+mod includes;
+mod registry;
+mod report;
+mod scope;
+mod selector;
+mod sink;
+
+use assets::{copy_theme_asset, is_shadowed, is_template};
+pub use branches::Branches;
 use cached::cached;
+pub use cached::{write_atomic, Cache};
+pub use cancel::{thread_ident, Cancellation};
+pub use compress::{is_precompressed, recompress};
+use compress::{precompress, precompress_file};
+pub use diagnostics::{Diagnostic, Diagnostics, Location, Printer, Severity};
+pub use hidden::Hidden;
+pub use includes::Includes;
+pub use registry::Registry;
+pub use report::{Counter, Report, Stage, Summary};
+use scope::snapshot_path;
+pub use scope::Scope;
+pub use selector::Selector;
+pub use sink::{open as open_sink, OutputSink};
 
 // ----------------------------------------------------------------------------
 // Constants
@@ -57,7 +104,7 @@ use cached::cached;
 
 /// Regular expression to detect use of snippets
 static SNIPPET_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^[ \t]*-+8<-+").expect("invariant"));
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*-+8<-+").expect("invariant"));
 
 // ----------------------------------------------------------------------------
 // Structs
@@ -77,6 +124,30 @@ pub struct Main {
     config: Config,
     /// Strict mode.
     strict: bool,
+    /// Report.
+    report: Report,
+    /// Output registry.
+    registry: Registry,
+    /// Include graph.
+    includes: Includes,
+    /// Workflow cache.
+    cache: Cache,
+    /// Diagnostics.
+    diagnostics: Diagnostics,
+    /// Output sink.
+    sink: Arc<dyn OutputSink>,
+    /// Build scope.
+    scope: Scope,
+    /// Workflow branches.
+    branches: Branches,
+    /// Workflow cancellation.
+    cancellation: Cancellation,
+    /// Partial renderers.
+    partials: Partials,
+    /// Blog listings.
+    listings: Listings,
+    /// Markdown converter.
+    converter: Arc<dyn MarkdownConverter>,
 }
 
 // ----------------------------------------------------------------------------
@@ -90,32 +161,131 @@ impl Module for Main {
 
         // Set up workflow to process static assets, as well as Markdown files,
         // and create a barrier to wait for the completion of all Markdown files
-        process_theme_assets(&self.config, &files);
-        process_assets(&self.config, &files);
-        let markdown = process_markdown(&self.config, &files);
+        let (report, sink) = (&self.report, &self.sink);
+        let privacy = Privacy::new(&self.config, sink, &self.diagnostics);
+        let social = Social::new(&self.config, &self.diagnostics);
+        process_theme_assets(&self.config, &files, &privacy, sink, report);
+        process_assets(&self.config, &files, sink, report);
+        let (includes, cache) = (&self.includes, &self.cache);
+        let (diagnostics, scope) = (&self.diagnostics, &self.scope);
+        let markdown = process_markdown(
+            &self.config,
+            &files,
+            scope,
+            includes,
+            cache,
+            diagnostics,
+            report,
+            &self.cancellation,
+            &self.converter,
+        );
 
         // Generate pages, and use the barrier to ensure that all pages have been
-        // processed, in order to create the navigation and search index
-        let page = generate_page(&self.config, &markdown);
-        let pages = page.select([wait_for_markdown(&self.config)]);
-
-        // Collect all anchors and references from pages, to validate links
-        if self.config.project.validation.is_enabled() {
+        // processed, in order to create the navigation and search index. Only
+        // summaries are collected, as content is only needed for rendering.
+        let registry = &self.registry;
+        let page = generate_page(
+            &self.config,
+            &markdown,
+            registry,
+            diagnostics,
+            report,
+        );
+        let summary = summarize_page(&page);
+        let pages = summary.select([wait_for_markdown(&self.config, scope)]);
+        let pages = drop_shadowed(&self.config, &pages, registry);
+
+        // Collect all anchors and references from pages, to validate links,
+        // which is only possible if all pages are built
+        let narrowed = scope.is_narrowed();
+        if self.config.project.validation.is_enabled() && !narrowed {
             let references = collect_references(&files);
             let anchors = collect_anchors(&page);
-            validate(&self.config, self.strict, references, anchors);
+            validate(
+                &self.config,
+                self.strict,
+                references,
+                anchors,
+                diagnostics,
+            );
         }
 
-        // Generate navigation and search index
-        let nav = generate_nav(&self.config, &pages);
-        generate_search_index(&self.config, &nav, &pages);
-
-        // Generate object inventory
-        generate_object_inventory(&self.config, &pages);
+        // Generate navigation, which includes pages outside of the scope, and
+        // ensure that stable page identifiers are unique across all pages
+        let nav = generate_nav(
+            &self.config,
+            self.strict,
+            &pages,
+            scope,
+            &self.listings,
+            diagnostics,
+            report,
+        );
+        check_page_ids(&pages, scope, diagnostics);
+
+        // Generate search index, site manifest and object inventory, as well
+        // as a snapshot of all pages, but only if all pages are built - if the
+        // scope is narrowed, the outputs of the last full build are kept. The
+        // search index is generated in a branch, so it doesn't delay pages
+        if !narrowed {
+            let branches = &self.branches;
+            generate_search_index(
+                &self.config,
+                &nav,
+                &pages,
+                sink,
+                branches,
+                report,
+            );
+            if self.config.project.build.manifest.is_some() {
+                generate_manifest(&self.config, &nav, &pages, sink, report);
+            }
+            if self.config.project.build.content_hashes {
+                generate_content_hashes(&self.config, &pages, sink, report);
+            }
+            generate_object_inventory(&self.config, &pages, sink);
+            generate_snapshot(&self.config, &nav, &pages);
+        }
 
         // // Render static and extra templates, as well as pages
-        render_templates(&self.config, &files, &nav);
-        render_pages(&self.config, &page, &nav);
+        render_templates(&self.config, &files, &nav, &privacy, sink, report);
+        render_pages(
+            &self.config,
+            self.strict,
+            &page,
+            &nav,
+            registry,
+            cache,
+            diagnostics,
+            &privacy,
+            &social,
+            sink,
+            report,
+            &self.partials,
+            &self.listings,
+        );
+        render_fallbacks(
+            &self.config,
+            &page,
+            &nav,
+            registry,
+            cache,
+            &privacy,
+            sink,
+            report,
+        );
+        if self.config.project.plugins.blog.config.enabled {
+            render_blog(
+                &self.config,
+                &pages,
+                &nav,
+                scope,
+                &self.listings,
+                &privacy,
+                sink,
+                report,
+            );
+        }
         Ok(())
     }
 }
@@ -124,15 +294,16 @@ impl Module for Main {
 // Functions
 // ----------------------------------------------------------------------------
 
-// Return condition waiting for all Markdown files
-pub fn wait_for_markdown(config: &Config) -> (Key<Id>, Barrier<Id>) {
-    let docs_dir = config.project.docs_dir.clone();
-    let matcher = Matcher::from_str(&format!("zrs::::{docs_dir}:**/*.md:"))
-        .expect("invariant");
+// Return condition waiting for all Markdown files in the given scope
+pub fn wait_for_markdown(
+    config: &Config, scope: &Scope,
+) -> (Key<Id>, Barrier<Id>) {
+    let selector = markdown_selector(config);
 
     // Create barrier that waits for all Markdown files to be processed
+    let scope = scope.clone();
     let barrier = Barrier::new(move |id: &Key<Id>| {
-        matcher.is_match(&id[0]).expect("invariant")
+        selector.is_match(&id[0]) && scope.contains(&id[0])
     });
 
     // Create key for barrier
@@ -145,6 +316,209 @@ pub fn wait_for_markdown(config: &Config) -> (Key<Id>, Barrier<Id>) {
     (id, barrier)
 }
 
+/// Returns the selector for Markdown files in the docs directory.
+///
+/// This is the selection of files the workflow renders as pages, which is
+/// shared by all stages that need to know which files are pages.
+pub fn markdown_selector(config: &Config) -> Selector {
+    Selector::glob("**/*.md")
+        .expect("invariant")
+        .context(config.project.docs_dir.clone())
+}
+
+/// Discover all pages in the docs directory.
+///
+/// This reads and renders all Markdown files and creates pages from them, just
+/// like the workflow does, but without a scheduler, and without rendering any
+/// templates or writing any outputs. Files are selected like in the workflow,
+/// see [`find_pages`]. Pages are returned in file order, so the result can be
+/// passed to [`resolve_nav`] to obtain the navigation.
+pub fn discover_pages(
+    config: &Config, converter: &dyn MarkdownConverter,
+) -> Result<Vec<(Key<Id>, PageSummary)>> {
+    let includes = Includes::default();
+    let diagnostics = Diagnostics::default();
+
+    // Render each Markdown file, using the same logic as the workflow to
+    // create pages
+    let files = find_pages(config)?;
+    let mut pages = Vec::with_capacity(files.len());
+    for (path, id) in files {
+        // Read and render Markdown, and create page
+        let source = path.to_string_lossy();
+        let data =
+            read_markdown(config, &id, &source, &includes, &diagnostics)?;
+        let cancellation = Cancellation::default();
+        let page = Page::new(config, converter, &id, data, &cancellation)?;
+        pages.push((Key::from_iter([id]), PageSummary::from(&page)));
+    }
+    Ok(pages)
+}
+
+/// Find all Markdown files in the docs directory that are rendered as pages.
+///
+/// Hidden directories are skipped like in the file agent, unless included, and
+/// identifiers are created in the same way as the file watcher, so they must
+/// match the selector for Markdown files. Files are returned sorted by path,
+/// together with their identifiers, for deterministic results.
+fn find_pages(config: &Config) -> Result<Vec<(PathBuf, Id)>> {
+    let docs_dir = config.get_docs_dir();
+    let selector = markdown_selector(config);
+    let hidden = Hidden::new(config);
+
+    // Collect all files, skipping hidden directories like the file agent, and
+    // create identifiers in the same way as the file watcher
+    let mut files = Vec::new();
+    let mut stack = vec![docs_dir.clone()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                let name = path.file_name().unwrap_or_default();
+                if !name.to_string_lossy().starts_with('.')
+                    || hidden.admits_dir(&path)
+                {
+                    stack.push(path);
+                }
+                continue;
+            }
+
+            // Select Markdown files in the same way as the workflow
+            let location = path.strip_prefix(&docs_dir)?.to_string_lossy();
+            let id = Id::builder()
+                .provider("file")
+                .context(config.project.docs_dir.replace('\\', "/"))
+                .location(location.replace('\\', "/"))
+                .build()
+                .expect("invariant");
+            if selector.is_match(&id) {
+                files.push((path, id));
+            }
+        }
+    }
+
+    // Sort files for deterministic results
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(files)
+}
+
+/// Read a Markdown file, and prepare it for rendering.
+///
+/// This removes the Byte-Order-Mark (BOM), normalizes line endings, renders
+/// variables, if enabled, and records the files included via snippets in the
+/// include graph. Files that are not valid UTF-8 are still rendered, but with
+/// invalid sequences replaced, which is reported as a warning, and so are
+/// invalid options of code blocks.
+fn read_markdown(
+    config: &Config, id: &Id, path: &str, includes: &Includes,
+    diagnostics: &Diagnostics,
+) -> Result<String> {
+    let Decoded { content: data, invalid } = decode(&fs::read(path)?);
+    if let Some(Invalid { offset, line }) = invalid {
+        diagnostics.warning_at(
+            Location::new(path).line(line),
+            format!(
+                "File '{path}' is not valid UTF-8 at byte {offset}, so invalid \
+                 characters were replaced - please save it as UTF-8"
+            ),
+        );
+    }
+
+    // Render variables, if enabled, before Markdown is converted, so
+    // the rendered content is also what determines caching
+    let data = render_variables(config, id, data)?;
+
+    // Warn about invalid options of code blocks, as both engines would just
+    // ignore them, so theme features like line highlighting silently break,
+    // and about broken admonitions and content tabs, which render garbled
+    let problems = check_fences(&data)
+        .into_iter()
+        .chain(check_structure(&data));
+    for (line, problem) in problems {
+        diagnostics.warning_at(
+            Location::new(path).line(line),
+            format!("Page '{}': {problem}", id.location()),
+        );
+    }
+
+    // Resolve snippets and record the files included by the page, so
+    // the page is built again when any of the included files change
+    let snippets = &config.project.snippets;
+    if snippets.enabled {
+        let resolved = Snippets::resolve(&snippets.base_path, &data);
+        let location = id.location();
+        for name in &resolved.missing {
+            if snippets.check_paths {
+                anyhow::bail!(
+                    "Snippet '{name}' included in '{location}' not found"
+                );
+            }
+
+            // Point to the line that includes the snippet, if we find it
+            let mut source = Location::new(path);
+            if let Some(i) = data.lines().position(|line| line.contains(name)) {
+                source = source.line(i + 1);
+            }
+            diagnostics.warning_at(
+                source,
+                format!("Snippet '{name}' included in '{location}' not found"),
+            );
+        }
+        includes.insert(id, path, resolved.files);
+    }
+    Ok(data)
+}
+
+/// Resolve the navigation from the given pages.
+///
+/// If internationalization is enabled, a navigation is created for each
+/// language, which is resolved from its subdirectory. The headings of all
+/// pages are registered for autorefs, after the navigation is created. The
+/// blog is returned as well, if enabled, as the navigation links to it.
+pub fn resolve_nav(
+    config: &Config, pages: &[(Key<Id>, PageSummary)],
+) -> (Navigation, Option<Blog>) {
+    let homepage = config.project.homepage.as_deref();
+    let collapse = config.project.navigation.collapse();
+    let items = config.project.nav.clone();
+
+    // Extract the tables of contents of all pages in a deterministic order,
+    // so headings can be cross-referenced
+    let mut headings = pages
+        .iter()
+        .map(|(_, page)| (page.url.clone(), page.toc.clone()))
+        .collect::<Vec<_>>();
+    headings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Collect blog posts, if enabled
+    let blog = &config.project.plugins.blog.config;
+    let use_directory_urls = config.project.use_directory_urls;
+    let blog = blog
+        .enabled
+        .then(|| Blog::new(blog, use_directory_urls, pages));
+
+    // Create navigation, localized if internationalization is enabled - pages
+    // are only borrowed, so their metadata is never cloned as a whole
+    let i18n = &config.project.plugins.i18n.config;
+    let mut nav = i18n
+        .enabled
+        .then(|| localize(i18n, &items, pages, homepage, collapse))
+        .flatten()
+        .unwrap_or_else(|| {
+            let iter = pages.iter().map(|(id, page)| (&id[0], page));
+            Navigation::new(items, iter, homepage, collapse)
+        });
+
+    // Inject blog section into navigation, which replaces the posts
+    if let Some(blog) = &blog {
+        nav.inject(blog.section(), &blog.urls());
+    }
+
+    // Register headings of all pages for autorefs
+    nav.register_headings(&headings);
+    (nav, blog)
+}
+
 /// Create a stream to collect references from all Markdown files.
 pub fn collect_references(
     files: &Stream<Id, Source>,
@@ -159,35 +533,91 @@ pub fn collect_references(
 }
 
 /// Create a stream to collect anchors from pages.
+///
+/// Anchors carry the stable identifier of their page, taken from the parsed
+/// metadata, so links to renamed or moved pages can be resolved.
 pub fn collect_anchors(pages: &Stream<Id, Page>) -> Stream<Id, Anchors> {
-    pages.map(move |page: Page| page.content.parse())
+    pages.map(move |page: Page| {
+        let anchors = page.content.parse::<Anchors>()?;
+        Ok(anchors.with_stable_id(page.stable_id()))
+    })
 }
 
 /// Create a stream to validate references against anchors.
+///
+/// The snapshot of the last full build is read before it's replaced, so links
+/// to pages that were renamed or moved can point to their new location.
 pub fn validate(
     config: &Config, strict: bool, refs: Stream<Id, References>,
-    anchors: Stream<Id, Anchors>,
+    anchors: Stream<Id, Anchors>, diagnostics: &Diagnostics,
 ) {
-    let combined = refs.join(&anchors).select([wait_for_markdown(config)]);
+    let scope = Scope::default();
+    let combined = refs
+        .join(&anchors)
+        .select([wait_for_markdown(config, &scope)]);
     let validation = config.project.validation.clone();
-    combined
-        .map(Issues::new)
-        .inspect(move |issues: &Issues| issues.print(&validation, strict));
+    let previous = previous_page_ids(config);
+    let docs_dir = config.get_docs_dir();
+    let diagnostics = diagnostics.clone();
+    combined.map(Issues::new).inspect(move |issues: &Issues| {
+        // Record reported issues with their location, so the preview can
+        // point to the offending line in the Markdown source
+        for issue in issues {
+            if issue.is_reported(&validation) {
+                let (line, column) = issues.position(issue);
+                let path = issue.path();
+                let location = Location::new(docs_dir.join(path))
+                    .line(line)
+                    .column(column);
+                diagnostics.record_at(
+                    Severity::Warning,
+                    Some(location),
+                    format!("{}: {}", path.display(), issue.message()),
+                );
+            }
+        }
+
+        // Print issues, which fails the build in strict mode
+        issues.print(&validation, strict, &previous)
+    });
+}
+
+/// Returns the stable identifiers of pages of the last full build by source.
+fn previous_page_ids(config: &Config) -> HashMap<String, String> {
+    fs::read(snapshot_path(config))
+        .ok()
+        .and_then(|data| serde_json::from_slice::<Manifest>(&data).ok())
+        .map(|snapshot| {
+            snapshot
+                .pages
+                .into_iter()
+                .filter_map(|page| page.id.map(|id| (page.source, id)))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Create a stream to process static assets.
-pub fn process_assets(config: &Config, files: &Stream<Id, Source>) {
+pub fn process_assets(
+    config: &Config, files: &Stream<Id, Source>, sink: &Arc<dyn OutputSink>,
+    report: &Report,
+) {
     let extra_templates = config.project.extra_templates.clone();
-    let docs_dir = config.project.docs_dir.clone();
-    let matcher = Arc::new(
-        Matcher::from_str(&format!("zrs::::{docs_dir}::")).expect("invariant"),
-    );
+    let selector =
+        Selector::new("zrs::::::").context(config.project.docs_dir.clone());
+    let hidden = Hidden::new(config);
 
     // Create pipeline to copy static assets
     let site_dir = config.project.site_dir.clone();
     let root_dir = config.get_root_dir();
+    let build = config.project.build.clone();
+    let sink = sink.clone();
+    let report = report.clone();
     files.map(move |id: &Id, from: Source| {
-        if !matcher.is_match(id).expect("invariant") {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("process_assets", id = id.as_str()).entered();
+        if !selector.is_match(id) {
             return Ok(());
         }
 
@@ -196,6 +626,11 @@ pub fn process_assets(config: &Config, files: &Stream<Id, Source>) {
             return Ok(());
         }
 
+        // Don't copy hidden files, unless they're explicitly included
+        if !hidden.includes(id) {
+            return Ok(());
+        }
+
         // Don't copy template files that we render later
         if extra_templates.contains(&id.location().into_owned()) {
             return Ok(());
@@ -206,29 +641,54 @@ pub fn process_assets(config: &Config, files: &Stream<Id, Source>) {
         let builder = id.to_builder().context(&site_dir);
         let id = builder.build().expect("invariant");
 
-        // Compute parent path, create intermediate directories and copy files
+        // Compute path and copy file through sink
         let to = root_dir.join(id.to_path());
-        fs::create_dir_all(to.parent().expect("invariant"))?;
-        copy_file(&*from, to)?;
-        Ok::<(), anyhow::Error>(())
+        report.measure(Stage::Assets, || {
+            let from = Path::new(&*from);
+            sink.copy(from, &to)?;
+            precompress_file(from, &to, &build, &*sink, &report)?;
+            Ok::<(), anyhow::Error>(())
+        })
     });
 }
 
 /// Create a stream to process static assets in theme.
-pub fn process_theme_assets(config: &Config, files: &Stream<Id, Source>) {
+///
+/// Assets are deduplicated across theme directories, so if the same asset is
+/// part of multiple theme directories, only the one from the theme directory
+/// with the highest precedence is copied, e.g., from the custom directory.
+/// If the privacy plugin is enabled, style sheets are rewritten instead, so
+/// external assets they reference, e.g., fonts, are self-hosted.
+pub fn process_theme_assets(
+    config: &Config, files: &Stream<Id, Source>, privacy: &Privacy,
+    sink: &Arc<dyn OutputSink>, report: &Report,
+) {
     let matcher =
         Arc::new(Matcher::from_str("zrs::::templates/*::").expect("invariant"));
 
     // Create pipeline to copy static assets
     let site_dir = config.project.site_dir.clone();
     let root_dir = config.get_root_dir();
+    let theme_dirs = config.theme_dirs.clone();
+    let static_templates = config.project.theme.static_templates.clone();
+    let build = config.project.build.clone();
+    let privacy = privacy.clone();
+    let sink = sink.clone();
+    let report = report.clone();
     files.map(move |id: &Id, from: Source| {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("process_theme_assets", id = id.as_str())
+                .entered();
         if !matcher.is_match(id).expect("invariant") {
             return Ok(());
         }
 
-        // Don't copy templates - they will be rendered later
-        if id.location().ends_with(".html") {
+        // Don't copy templates and partials - they will be rendered later
+        let location = id.location();
+        if is_template(&location)
+            || static_templates.contains(&location.to_string())
+        {
             return Ok(());
         }
 
@@ -237,126 +697,293 @@ pub fn process_theme_assets(config: &Config, files: &Stream<Id, Source>) {
         let builder = id.to_builder().context(&site_dir);
         let id = builder.build().expect("invariant");
 
-        // Compute parent path, create intermediate directories and copy files
+        // Compute path and copy file, unless it's overridden or up to date
         let to = root_dir.join(id.to_path());
-        fs::create_dir_all(to.parent().expect("invariant"))?;
-        copy_file(&*from, to)?;
-        Ok::<_, anyhow::Error>(())
+        report.measure(Stage::Assets, || {
+            let from = Path::new(&*from);
+            let is_style = from.extension().is_some_and(|ext| ext == "css");
+            let copied = if privacy.is_enabled() && is_style {
+                let shadowed = is_shadowed(&theme_dirs, from, &location);
+                if !shadowed {
+                    let data = fs::read_to_string(from)?;
+                    let data = privacy.process_css(&location, data);
+                    sink.write(&to, data.as_bytes())?;
+                    precompress(&to, data.as_bytes(), &build, &*sink, &report)?;
+                }
+                !shadowed
+            } else {
+                let copied = copy_theme_asset(
+                    &theme_dirs,
+                    from,
+                    &location,
+                    &to,
+                    &*sink,
+                )?;
+                if copied {
+                    precompress_file(from, &to, &build, &*sink, &report)?;
+                }
+                copied
+            };
+            if !copied {
+                report.add(Counter::SkippedAssets, 1);
+            }
+            Ok::<_, anyhow::Error>(())
+        })
     });
 }
 
-/// Copy a file to a new location, without copying its permissions.
-fn copy_file(
-    from: impl AsRef<Path>, to: impl AsRef<Path>,
-) -> Result<(), io::Error> {
-    let mut from = fs::File::open(from)?;
-    let mut to = fs::File::create(to)?;
-    io::copy(&mut from, &mut to).map(|_| ())
-}
-
 /// Create a stream to process Markdown files.
+///
+/// Files included via snippets are recorded in the include graph, so the file
+/// watcher can submit pages again when one of their included files changes.
+/// Once the workflow is cancelled, files that are not yet converted are
+/// skipped, so the build can exit after the conversions in flight. Markdown
+/// is converted with the given converter.
+#[allow(clippy::too_many_arguments)]
 pub fn process_markdown(
-    config: &Config, files: &Stream<Id, Source>,
+    config: &Config, files: &Stream<Id, Source>, scope: &Scope,
+    includes: &Includes, cache: &Cache, diagnostics: &Diagnostics,
+    report: &Report, cancellation: &Cancellation,
+    converter: &Arc<dyn MarkdownConverter>,
 ) -> Stream<Id, Markdown> {
-    let matcher = Arc::new(
-        Matcher::from_str(&format!(
-            "zrs::::{}:**/*.md:",
-            config.project.docs_dir
-        ))
-        .expect("invariant"),
-    );
+    let selector = markdown_selector(config);
 
     // Create pipeline to render Markdown files
     let config = config.clone();
+    let includes = includes.clone();
+    let cache = cache.clone();
+    let diagnostics = diagnostics.clone();
+    let report = report.clone();
+    let scope = scope.clone();
+    let cancellation = cancellation.clone();
+    let converter = converter.clone();
     files
-        .filter(move |id: &Id| matcher.is_match(id).expect("invariant"))
+        .filter(move |id: &Id| selector.is_match(id) && scope.contains(id))
         // Render Markdown if we don't have a recent cached version at our own
         // disposal. Otherwise, just return that if the content did not change.
         // Note that we need to limit concurrency here, or we'll overwhelm the
         // Python interpreter with all tasks competing for the GIL.
         .map(move |id: &Id, path: Source| {
-            let data = fs::read_to_string(&*path)?;
-
-            // Remove Byte-Order-Mark (BOM)
-            let data = data.strip_prefix('\u{FEFF}').unwrap_or(&data);
-            let data = data.to_owned();
-
-            // Compute URL using same logic as Page::new()
-            let site_dir = config.project.site_dir.clone();
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::info_span!("process_markdown", id = id.as_str())
+                    .entered();
+            let timer = report.timer(Stage::Markdown);
+            let data =
+                read_markdown(&config, id, &path, &includes, &diagnostics)?;
+
+            // Compute URL using same logic as Page::new
+            let site_dir = &config.project.site_dir;
             let use_directory_urls = config.project.use_directory_urls;
-
-            let builder = id.to_builder().context(&site_dir);
-            let url_id = builder.clone().build().expect("invariant");
-
-            let mut url_path: PathBuf = url_id.location().to_string().into();
-            let is_index = url_path.ends_with("index.md")
-                || url_path.ends_with("README.md");
-
-            if url_path.ends_with("README.md") {
-                url_path.pop();
-                url_path = url_path.join("index.md");
-            }
-
-            if !use_directory_urls || is_index {
-                url_path.set_extension("html");
-            } else {
-                url_path.set_extension("");
-                url_path.push("index.html");
-            }
-
-            let url_path = url_path.to_string_lossy().into_owned();
-            let url_id = builder
-                .location(url_path.replace('\\', "/"))
-                .build()
-                .expect("invariant");
-
-            let url = url_id.as_uri().to_string();
-            let url = if use_directory_urls {
-                url.trim_end_matches("index.html").to_string()
-            } else {
-                url
-            };
+            let (_, url) = to_location(id, site_dir, use_directory_urls);
 
             // Don't cache page if it inserts (pymdownx) snippets.
             // This is a hack while waiting for CommonMark (AST) and components,
             // as well as topic-based authoring functionality.
-            if SNIPPET_RE.is_match(&data) {
-                Markdown::new(id, url, data)
+            let mut hit = false;
+            let markdown = if SNIPPET_RE.is_match(&data) {
+                Markdown::new(&*converter, id, url, data, &cancellation)
             } else {
-                cached(
-                    &config,
-                    id.as_str(),
-                    (config.hash, data.clone(), url.clone()),
-                    |(_, data, url)| Markdown::new(id, url, data),
+                convert_markdown(
+                    &cache,
+                    config.hash,
+                    id,
+                    url,
+                    data,
+                    &*converter,
+                    &cancellation,
                 )
-            }
+                .map(|(markdown, cached)| {
+                    hit = cached;
+                    markdown
+                })
+            };
+
+            // Attribute time spent on reading and rendering Markdown to the
+            // page, unless it was served from the cache
+            let elapsed = timer.elapsed();
+            let source = id.to_path();
+            let source = source.to_string_lossy();
+            let elapsed = (!hit).then_some(elapsed);
+            report.record_page(Stage::Markdown, &source, elapsed);
+            markdown
         })
 }
 
 /// Generate pages from Markdown files.
+///
+/// The output path of each page is recorded in the registry, so collisions of
+/// multiple source files mapping to the same output path can be detected. If
+/// a front matter schema is configured, front matter is validated against it
+/// before the page is created, so coerced values end up in the page. Dates in
+/// front matter are parsed beforehand, and reported if they can't be parsed.
 pub fn generate_page(
-    config: &Config, markdown: &Stream<Id, Markdown>,
+    config: &Config, markdown: &Stream<Id, Markdown>, registry: &Registry,
+    diagnostics: &Diagnostics, report: &Report,
 ) -> Stream<Id, Page> {
     let config = config.clone();
-    markdown.map(move |id: &Id, markdown| Page::new(&config, id, markdown))
+    let registry = registry.clone();
+    let diagnostics = diagnostics.clone();
+    let report = report.clone();
+    markdown.map(move |id: &Id, mut markdown: Markdown| {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("generate_page", id = id.as_str()).entered();
+        let source = config.get_root_dir().join(id.to_path());
+        let location = id.location();
+        for key in parse_dates(&mut markdown.meta) {
+            diagnostics.warning_at(
+                Location::new(&source),
+                format!("Page '{location}': unknown date format in '{key}'"),
+            );
+        }
+
+        // Report canonical URLs that are ignored, as they're not absolute
+        if let Some(value) = invalid_canonical_url(&markdown.meta) {
+            diagnostics.warning_at(
+                Location::new(&source),
+                format!(
+                    "Page '{location}': ignoring canonical URL '{value}', as \
+                     it's not an absolute URL"
+                ),
+            );
+        }
+
+        // Validate front matter against schema, if configured
+        if let Some(schema) = &config.project.meta_schema {
+            for problem in schema.validate(&mut markdown.meta) {
+                diagnostics.warning_at(
+                    Location::new(&source),
+                    format!("Page '{location}': {problem}"),
+                );
+            }
+        }
+
+        // Create page, and record output path for collision detection
+        let page = report.measure(Stage::Page, || {
+            Page::from_markdown(&config, id, markdown)
+        });
+        registry.insert(source, &page.path);
+        page
+    })
+}
+
+/// Summarize pages, leaving out their content.
+///
+/// Summaries are collected once all pages are built, in order to generate the
+/// navigation and search index, which is why they must be small, so that the
+/// memory footprint stays low on large sites. Only the stream of pages that
+/// are rendered carries the content of each page.
+pub fn summarize_page(page: &Stream<Id, Page>) -> Stream<Id, PageSummary> {
+    page.map(|page: Page| PageSummary::from(&page))
+}
+
+/// Drop pages that are shadowed by other pages.
+///
+/// Pages mapping to the same output path as another page are not rendered, so
+/// they must not show up in the navigation and search index either. All pages
+/// are recorded in the registry before the barrier is passed, so collisions
+/// are resolved in the same way as when pages are rendered.
+pub fn drop_shadowed(
+    config: &Config, pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+    registry: &Registry,
+) -> Stream<Id, Vec<(Key<Id>, PageSummary)>> {
+    let root_dir = config.get_root_dir();
+    let registry = registry.clone();
+    pages.map(move |mut pages: Vec<(Key<Id>, PageSummary)>| {
+        pages.retain(|(id, page)| {
+            let source = root_dir.join(id[0].to_path());
+            registry.shadowed_by(&source, &page.path).is_none()
+        });
+        pages
+    })
 }
 
 /// Generate navigation from all pages.
+///
+/// Navigation entries that match no page are reported together with the most
+/// similar page, if any, and abort the build in strict mode. The listings of
+/// the blog, if enabled, are recorded in the given listings for rendering.
 pub fn generate_nav(
-    config: &Config, pages: &Stream<Id, Vec<(Key<Id>, Page)>>,
+    config: &Config, strict: bool,
+    pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>, scope: &Scope,
+    listings: &Listings, diagnostics: &Diagnostics, report: &Report,
 ) -> Stream<Id, Navigation> {
     let config = config.clone();
-    pages.map(move |pages: Vec<(Key<Id>, Page)>| {
-        Navigation::new(config.project.nav.clone(), pages)
+    let scope = scope.clone();
+    let listings = listings.clone();
+    let diagnostics = diagnostics.clone();
+    let report = report.clone();
+    pages.map(move |mut pages: Vec<(Key<Id>, PageSummary)>| {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("generate_nav", pages = pages.len()).entered();
+        // Add pages outside of the scope, so the navigation is complete, and
+        // links to those pages resolve to their previously built URLs
+        pages.extend(scope.pages().iter().cloned());
+        let locations = pages
+            .iter()
+            .map(|(id, _)| id[0].location().to_string())
+            .collect::<Vec<_>>();
+        let (nav, blog) =
+            report.measure(Stage::Nav, || resolve_nav(&config, &pages));
+        if let Some(blog) = &blog {
+            listings.replace(blog);
+        }
+
+        // Warn about navigation items that don't match any page
+        if config.project.validation.invalid_links {
+            let mut unresolved = 0;
+            for item in nav.unresolved() {
+                let url = item.url.as_deref().unwrap_or_default();
+                match closest_location(&locations, url) {
+                    Some(location) => diagnostics.warning(format!(
+                        "Navigation entry '{url}' matches no page, did you \
+                         mean '{location}'?"
+                    )),
+                    None => diagnostics.warning(format!(
+                        "Navigation entry '{url}' matches no page"
+                    )),
+                }
+                unresolved += 1;
+            }
+            if strict && unresolved > 0 {
+                anyhow::bail!("Aborted because --strict flag is set");
+            }
+        }
+        Ok(nav)
     })
 }
 
+/// Check that stable page identifiers are unique across all pages.
+///
+/// Identifiers are used as cache keys and to follow pages across renames, so
+/// duplicates are reported as errors, which fail the build in strict mode.
+pub fn check_page_ids(
+    pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>, scope: &Scope,
+    diagnostics: &Diagnostics,
+) {
+    let scope = scope.clone();
+    let diagnostics = diagnostics.clone();
+    pages.inspect(move |pages: &Vec<(Key<Id>, PageSummary)>| {
+        let mut pages = pages.clone();
+        pages.extend(scope.pages().iter().cloned());
+        for (id, first, other) in duplicate_ids(&pages) {
+            diagnostics.error(format!(
+                "Page id '{id}' is used by both '{first}' and '{other}'"
+            ));
+        }
+    });
+}
+
 /// Generate object inventory
 pub fn generate_object_inventory(
-    config: &Config, pages: &Stream<Id, Vec<(Key<Id>, Page)>>,
+    config: &Config, pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+    sink: &Arc<dyn OutputSink>,
 ) {
     // Retrieve inventory from Python interpreter using pyo3
     let config = config.clone();
+    let sink = sink.clone();
     pages.map(move |_| {
         let data = Python::attach(|py| {
             let module = py.import("zensical.compat.mkdocstrings")?;
@@ -367,69 +994,323 @@ pub fn generate_object_inventory(
         let site_dir = config.get_site_dir();
         if let Ok(data) = data {
             let path = site_dir.join("objects.inv");
-            let _ = fs::create_dir_all(path.parent().expect("invariant"));
-            let _ = fs::write(path, &data);
+            let _ = sink.write(&path, &data);
         }
     });
 }
 
-/// Generate search index
+/// Generate search index.
+///
+/// The search index is serialized and written in a branch, since no other
+/// stage depends on it, so it runs in parallel to the rendering of pages.
 pub fn generate_search_index(
     config: &Config, nav: &Stream<Id, Navigation>,
-    pages: &Stream<Id, Vec<(Key<Id>, Page)>>,
+    pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+    sink: &Arc<dyn OutputSink>, branches: &Branches, report: &Report,
 ) {
     let config = config.clone();
-    pages.product(nav).map(move |pages, nav| {
-        let plugin = config.project.plugins.search.config.clone();
-        let search = SearchIndex::new(pages, &nav, plugin);
-
-        // Serialize search index to json, and obtain site directory
-        let data = serde_json::to_string(&search).expect("invariant");
-        let site_dir = config.get_site_dir();
+    let sink = sink.clone();
+    let branches = branches.clone();
+    let report = report.clone();
+    pages
+        .product(nav)
+        .map(move |pages: Vec<(Key<Id>, PageSummary)>, nav| {
+            let config = config.clone();
+            let sink = sink.clone();
+            let report = report.clone();
+            branches.spawn_exclusive("search_index", move || {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::info_span!(
+                    "generate_search_index",
+                    pages = pages.len()
+                )
+                .entered();
+                let _timer = report.timer(Stage::Search);
+                let plugin = config.project.plugins.search.config.clone();
+                let site_dir = config.get_site_dir();
+
+                // If internationalization is enabled, create a search index for
+                // each language in its subdirectory, only containing its pages
+                let i18n = &config.project.plugins.i18n.config;
+                for language in &i18n.languages {
+                    let Some(nav) = nav.languages.get(&language.locale) else {
+                        continue;
+                    };
+
+                    // Select pages of language, and write search index
+                    let subset = pages
+                        .iter()
+                        .filter(|(_, page)| {
+                            let split = i18n.split(&page.url);
+                            split.is_some_and(|(candidate, _)| {
+                                candidate == language
+                            })
+                        })
+                        .map(|(id, page)| (&id[0], page));
+                    let search = SearchIndex::new(subset, nav, plugin.clone());
+                    let dir = site_dir.join(&language.path);
+                    let size = write_search_index(
+                        &config, &dir, &search, &*sink, &report,
+                    )?;
+                    report.add(Counter::SearchIndexBytes, size);
+                }
+
+                // Write search index for all pages
+                let iter = pages.iter().map(|(id, page)| (&id[0], page));
+                let search = SearchIndex::new(iter, &nav, plugin);
+                let size = write_search_index(
+                    &config, &site_dir, &search, &*sink, &report,
+                )?;
+                report.add(Counter::SearchIndexBytes, size);
+
+                // All files were written successfully
+                Ok(())
+            });
+            Ok::<_, anyhow::Error>(())
+        });
+}
 
-        // Write search index to disk
-        let path = site_dir.join("search.json");
-        fs::create_dir_all(path.parent().expect("invariant"))?;
-        fs::write(path, &data)?;
+/// Generate site manifest.
+///
+/// The manifest is generated from all pages once the navigation is built, so
+/// it can include the section hierarchy of each page.
+pub fn generate_manifest(
+    config: &Config, nav: &Stream<Id, Navigation>,
+    pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+    sink: &Arc<dyn OutputSink>, report: &Report,
+) {
+    let config = config.clone();
+    let sink = sink.clone();
+    let report = report.clone();
+    pages
+        .product(nav)
+        .map(move |pages: Vec<(Key<Id>, PageSummary)>, nav| {
+            let build = &config.project.build;
+            let Some(name) = &build.manifest else {
+                return Ok(());
+            };
 
-        // If offline plugin is enabled, create search.js as well
-        if config.project.plugins.offline.config.enabled {
-            let path = site_dir.join("search.js");
-            fs::create_dir_all(path.parent().expect("invariant"))?;
-            fs::write(path, format!("var __index = {data};").as_str())?;
-        }
+            // Create manifest, and write it to disk
+            let manifest =
+                Manifest::new(&pages, &nav, Some(&build.manifest_meta));
+            let data = serde_json::to_string_pretty(&manifest)?;
+            let path = config.get_site_dir().join(name);
+            sink.write(&path, data.as_bytes())?;
+            precompress(&path, data.as_bytes(), build, &*sink, &report)?;
+            Ok::<_, anyhow::Error>(())
+        });
+}
 
-        // All files were written successfully
+/// Generate content hashes of all pages.
+///
+/// The mapping of page URLs to content hashes is written to `pages.json`, and
+/// regenerated whenever a page changes, together with the embedded hashes.
+pub fn generate_content_hashes(
+    config: &Config, pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+    sink: &Arc<dyn OutputSink>, report: &Report,
+) {
+    let config = config.clone();
+    let sink = sink.clone();
+    let report = report.clone();
+    pages.map(move |pages: Vec<(Key<Id>, PageSummary)>| {
+        let hashes = ContentHashes::new(&pages);
+        let data = serde_json::to_string(&hashes)?;
+        let path = config.get_site_dir().join("pages.json");
+        let build = &config.project.build;
+        sink.write(&path, data.as_bytes())?;
+        precompress(&path, data.as_bytes(), build, &*sink, &report)?;
         Ok::<_, anyhow::Error>(())
     });
 }
 
+/// Generate snapshot of all pages.
+///
+/// The snapshot is written to the cache directory after each full build, and
+/// used to synthesize the navigation if the scope of a build is narrowed.
+pub fn generate_snapshot(
+    config: &Config, nav: &Stream<Id, Navigation>,
+    pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+) {
+    let config = config.clone();
+    pages
+        .product(nav)
+        .map(move |pages: Vec<(Key<Id>, PageSummary)>, nav| {
+            let snapshot = Manifest::new(&pages, &nav, None);
+            let data = serde_json::to_vec(&snapshot)?;
+            fs::write(snapshot_path(&config), data)?;
+            Ok::<_, anyhow::Error>(())
+        });
+}
+
+/// Copies a file located outside of the docs and theme directories.
+///
+/// Logo, favicon and extra files located elsewhere are not picked up by the
+/// workflow, which is why they're copied before the build, and again by the
+/// file watcher when they change. Like all other assets, they're written
+/// through the sink, which also writes precompressed copies, if enabled.
+pub fn copy_extra_file(
+    config: &Config, from: &Path, url: &str, sink: &dyn OutputSink,
+    report: &Report,
+) -> io::Result<()> {
+    let to = config.get_site_dir().join(url);
+    sink.copy(from, &to)?;
+    precompress_file(from, &to, &config.project.build, sink, report)
+}
+
+/// Converts Markdown, or returns the cached result, and whether it was cached.
+///
+/// Pages are cached under their location, which is moved by the watcher on
+/// renames, while rendered pages are cached under their stable identifier, if
+/// any, which is only known after the front matter was parsed. Links are kept
+/// relative when converting, so the result only depends on the location of
+/// the page through the depths of its source and output directories, which
+/// are part of the key instead of the location and URL. Thus, renaming a page
+/// within its directory doesn't require converting it again, but turning it
+/// into an index page or vice versa does.
+fn convert_markdown(
+    cache: &Cache, hash: u64, id: &Id, url: String, data: String,
+    converter: &dyn MarkdownConverter, cancellation: &Cancellation,
+) -> Result<(Markdown, bool)> {
+    let depths = (depth(&id.location()), depth(&url));
+    let mut hit = true;
+    cached(cache, id.as_str(), (hash, data, depths), |(_, data, _)| {
+        hit = false;
+        Markdown::new(converter, id, url, data, cancellation)
+    })
+    .map(|markdown| (markdown.with_title(id), hit))
+}
+
+/// Returns the depth of the directory of the given location or URL.
+///
+/// With directory URLs, pages are moved into a folder of the same name, so
+/// the URL has a trailing slash, and its directory is one level deeper than
+/// the directory of the source file, unless the page is an index page.
+fn depth(path: &str) -> usize {
+    path.matches('/').count()
+}
+
+/// Returns the page location most similar to the given navigation URL, if any.
+///
+/// Only locations that are at most three edits away are considered, which is
+/// enough to catch typos, but avoids suggesting entirely unrelated pages.
+fn closest_location<'a>(locations: &'a [String], url: &str) -> Option<&'a str> {
+    let url = url::decode(url);
+    locations
+        .iter()
+        .map(|location| (distance(&url, location), location.as_str()))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, location)| location)
+}
+
+/// Returns the source location of the given render error.
+///
+/// Template errors point to the template and line where they occurred, if the
+/// template is found in one of the theme directories. Otherwise, the location
+/// of the error is the page that was rendered.
+fn render_error_location(
+    config: &Config, source: &Path, err: &anyhow::Error,
+) -> Location {
+    let template = err
+        .chain()
+        .find_map(|err| err.downcast_ref::<minijinja::Error>());
+    let Some(err) = template else {
+        return Location::new(source);
+    };
+
+    // Resolve template in theme directories, which take precedence in order
+    let path = err.name().and_then(|name| {
+        config
+            .theme_dirs
+            .iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    });
+    match (path, err.line()) {
+        (Some(path), Some(line)) => Location::new(path).line(line),
+        (Some(path), None) => Location::new(path),
+        (None, _) => Location::new(source),
+    }
+}
+
+/// Prepares the given rendered HTML for offline usage, if enabled.
+///
+/// When the site is opened from the file system, root-relative URLs don't
+/// resolve, so they're made relative to the page at the given URL. Moreover,
+/// the search worker can't fetch the search index, which is why the search
+/// index is loaded via `search.js` instead, which defines `__index`.
+fn prepare_offline(config: &Config, url: &str, data: String) -> String {
+    let plugins = &config.project.plugins;
+    if !plugins.offline.config.enabled {
+        return data;
+    }
+
+    // Rewrite root-relative URLs to be relative to the page
+    let base_url = config.get_base_url(url);
+    let base = config.get_base_path();
+    let mut data = relativize_absolute_links(&data, &base, &base_url);
+
+    // Load search index in the head, i.e., before the scripts of the theme
+    if plugins.search.config.enabled {
+        let script = format!("<script src=\"{base_url}/search.js\"></script>");
+        if !data.contains(&script) {
+            if let Some(index) = data.find("</head>") {
+                data.insert_str(index, &script);
+            }
+        }
+    }
+    data
+}
+
+/// Write search index to the given directory, and return its size in bytes.
+fn write_search_index(
+    config: &Config, dir: &Path, search: &SearchIndex, sink: &dyn OutputSink,
+    report: &Report,
+) -> Result<u64, io::Error> {
+    let build = &config.project.build;
+    let data = serde_json::to_string(search).expect("invariant");
+
+    // Write search index to disk
+    let path = dir.join("search.json");
+    sink.write(&path, data.as_bytes())?;
+    precompress(&path, data.as_bytes(), build, sink, report)?;
+
+    // If offline plugin is enabled, create search.js as well
+    if config.project.plugins.offline.config.enabled {
+        let path = dir.join("search.js");
+        let data = format!("var __index = {data};");
+        sink.write(&path, data.as_bytes())?;
+        precompress(&path, data.as_bytes(), build, sink, report)?;
+    }
+
+    // All files were written successfully, so return size of search index
+    Ok(data.len() as u64)
+}
+
 /// Render static and extra templates.
 pub fn render_templates(
     config: &Config, files: &Stream<Id, Source>, nav: &Stream<Id, Navigation>,
+    privacy: &Privacy, sink: &Arc<dyn OutputSink>, report: &Report,
 ) -> Stream<Id, ()> {
-    let docs_dir = config.project.docs_dir.clone();
-
-    // Retrieve template names
-    let static_templates = &config.project.theme.static_templates.join(",");
-    let extra_templates = &config.project.extra_templates.join(",");
+    let project = &config.project;
 
-    // Build matcher for static and extra templates - we just handle them the
+    // Build selectors for static and extra templates - we just handle them the
     // same. In MkDocs, extra templates can do even less than static templates,
     // not having access to the `url_filter`, but there's no need for us to
-    // differentiate here.
-    let mut builder = Matcher::builder();
-    builder
-        .add(&format!("zrs::::templates/*:{{{static_templates}}}:"))
-        .expect("invariant");
-    builder
-        .add(&format!("zrs::::{docs_dir}:{{{extra_templates}}}:"))
-        .expect("invariant");
-
-    // Create matcher from builder, and filter templates
-    let matcher = Arc::new(builder.build().expect("invariant"));
-    let templates =
-        files.filter(move |id: &Id| matcher.is_match(id).expect("invariant"));
+    // differentiate here. Template names are compared literally, as they
+    // might contain characters with special meaning in patterns.
+    let selectors = [
+        Selector::new("zrs::::templates/*::")
+            .locations(project.theme.static_templates.clone()),
+        Selector::new("zrs::::::")
+            .context(project.docs_dir.clone())
+            .locations(project.extra_templates.clone()),
+    ];
+
+    // Filter templates
+    let templates = files.filter(move |id: &Id| {
+        selectors.iter().any(|selector| selector.is_match(id))
+    });
 
     // Add docs directory to theme templates
     let mut theme_dirs = config.theme_dirs.clone();
@@ -437,62 +1318,607 @@ pub fn render_templates(
 
     // Create pipeline to render templates
     let config = config.clone();
+    let privacy = privacy.clone();
+    let sink = sink.clone();
+    let report = report.clone();
     templates.product(nav).map(move |template: Source, nav| {
+        let _timer = report.timer(Stage::Templates);
         let name = Path::new(&*template).file_name().expect("invariant");
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "render_templates",
+            path = %name.to_string_lossy()
+        )
+        .entered();
         let site_dir = config.get_site_dir();
 
         // Obtain template
         let template =
             Template::new(name.to_string_lossy(), theme_dirs.clone());
 
-        // Render template and write to disk
-        let data = template.render(&config, &nav)?;
+        // Render template and write to disk - only HTML templates must be
+        // prepared for offline usage, as other formats require absolute URLs
+        let mut data = template.render(&config, &nav)?;
+        let url = name.to_string_lossy();
+        if url.ends_with(".html") {
+            data = privacy.process_html(&config.get_base_url(&*url), data);
+            data = prepare_offline(&config, &url, data);
+        }
         let path = site_dir.join(name);
-        fs::create_dir_all(path.parent().expect("invariant"))?;
-        fs::write(path, &data)?;
+        sink.write(&path, data.as_bytes())?;
+        let build = &config.project.build;
+        precompress(&path, data.as_bytes(), build, &*sink, &report)?;
         Ok::<_, anyhow::Error>(())
     })
 }
 
 /// Render pages.
+///
+/// If another source file maps to the same output path and takes precedence,
+/// the page is skipped deterministically, or the build fails in strict mode.
+/// If partial rendering is enabled, a renderer is recorded for each page.
+#[allow(clippy::too_many_arguments)]
 pub fn render_pages(
-    config: &Config, page: &Stream<Id, Page>, nav: &Stream<Id, Navigation>,
+    config: &Config, strict: bool, page: &Stream<Id, Page>,
+    nav: &Stream<Id, Navigation>, registry: &Registry, cache: &Cache,
+    diagnostics: &Diagnostics, privacy: &Privacy, social: &Social,
+    sink: &Arc<dyn OutputSink>, report: &Report, partials: &Partials,
+    listings: &Listings,
 ) -> Stream<Id, ()> {
     let config = config.clone();
+    let partials = partials.clone();
+    let listings = listings.clone();
+    let privacy = privacy.clone();
+    let social = social.clone();
+    let sink = sink.clone();
+    let registry = registry.clone();
+    let cache = cache.clone();
+    let diagnostics = diagnostics.clone();
+    let report = report.clone();
     page.product(nav)
-        .map(move |mut page: Page, nav: Navigation| {
-            let id = page.url.clone();
+        .map(move |id: &Id, mut page: Page, nav: Navigation| {
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::info_span!("render_pages", id = id.as_str()).entered();
+            let source = config.get_root_dir().join(id.to_path());
+            if let Some(winner) = registry.shadowed_by(&source, &page.path) {
+                diagnostics.error(format!(
+                    "'{}' and '{}' both map to '{}', skipping '{}'",
+                    winner.display(),
+                    source.display(),
+                    page.url,
+                    source.display()
+                ));
+                if strict {
+                    anyhow::bail!("Aborted because --strict flag is set");
+                }
+                return Ok(());
+            }
+
+            let validation = &config.project.validation;
+            let base = config.get_base_path();
+
+            // Add dimensions and lazy loading to local images, if enabled,
+            // which are looked up in the docs directory, or in the site
+            // directory, in case they're provided by the theme
+            let images = &config.project.plugins.images.config;
+            if images.enabled {
+                let dirs = [config.get_docs_dir(), config.get_site_dir()];
+                page.content = process_images(
+                    &page.content,
+                    &page.url,
+                    &base,
+                    &dirs,
+                    images,
+                );
+            }
+
+            // Write social card, if enabled, which is referenced in the page
+            // metadata, so this must happen before computing the page hash
+            social.process(&mut page, &*sink, &report)?;
+
+            // Obtain navigation for the language of the page, if any
+            let i18n = &config.project.plugins.i18n.config;
+            let nav = nav.localized(i18n, &page);
+
+            // Obtain blog listing located at the URL of the page, if any, e.g.,
+            // for the blog index, as the page is rendered as the listing then
+            let listing = listings.get(&page.url);
+
+            // Record renderer for single blocks of the page, if enabled, which
+            // must happen before rendering, as cached pages are not rendered
+            if partials.is_enabled() {
+                partials.insert(&page.url, {
+                    let (config, page, nav, listing) = (
+                        config.clone(),
+                        page.clone(),
+                        nav.clone(),
+                        listing.clone(),
+                    );
+                    move |block| {
+                        let listing = listing.as_ref();
+                        let nav = nav.clone();
+                        page.clone().render_block(&config, nav, listing, block)
+                    }
+                });
+            }
 
-            // Compute hash of page content
+            // Render page, measuring the time it takes
+            let timer = report.timer(Stage::Render);
+            let key = page.stable_id().map_or_else(
+                || page.url.clone(),
+                |stable| format!("page:{stable}"),
+            );
+
+            // Compute hash of page content - the URL is part of the hash, as
+            // pages with a stable identifier aren't cached under their URL
             let hash = {
-                let mut hasher = DefaultHasher::new();
+                let mut hasher = StableHasher::default();
+                page.url.hash(&mut hasher);
                 page.content.hash(&mut hasher);
                 page.meta.hash(&mut hasher);
+                if let Some(listing) = &listing {
+                    listing.hash(&mut hasher);
+                }
                 hasher.finish()
             };
 
             // Render page if we don't have a recent cached version at our own
             // disposal. Otherwise, just return if the content did not change.
-            let args = (config.hash, nav.hash, hash);
-            cached(
-                &config,
-                id,
-                args,
-                |(_, _, _)| Ok(page.render(&config, nav)?),
-            )
+            // The configuration hash covers all project settings, including
+            // extra CSS and JavaScript, theme features, as well as paths and
+            // modification times of templates, and the templates hash covers
+            // the contents of all files in the theme directories
+            let nav_hash = nav.hash_for(&page.content);
+            let args = (config.hash, config.templates_hash, nav_hash, hash);
+            let mut hit = true;
+            cached(&cache, key, args, |_| {
+                hit = false;
+                Ok(match &listing {
+                    Some(listing) => {
+                        page.render_listing(&config, nav, listing)?
+                    }
+                    None => page.render(&config, nav)?,
+                })
+            })
+            // Record errors, so the preview server can report them - they
+            // are propagated to the scheduler, so we don't print them here
+            .inspect_err(|err| {
+                let message = format!("{}: {err:#}", source.display());
+                let location = render_error_location(&config, &source, err);
+                diagnostics.record_at(Severity::Error, Some(location), message);
+            })
             .and_then(|data| {
+                let base_url = config.get_base_url(&page.url);
+                let mut data = privacy.process_html(&base_url, data);
+
+                // Check for root-relative links that bypass the base path in
+                // the rendered page, including its templates, as they break
+                // when the site is deployed to a subpath, and prefix them with
+                // the base path if enabled, or report them otherwise
+                if validation.fix_root_relative_links {
+                    data = prefix_absolute_links(&data, &base);
+                } else if let Some(severity) = validation.root_relative_links {
+                    let links = absolute_links(&data, &base);
+                    for link in &links {
+                        diagnostics.record(
+                            severity,
+                            format!(
+                                "'{}' links to '{link}', which bypasses base \
+                                 path '{base}'",
+                                source.display()
+                            ),
+                        );
+                    }
+                    if strict && !links.is_empty() {
+                        anyhow::bail!("Aborted because --strict flag is set");
+                    }
+                }
+                let data = prepare_offline(&config, &page.url, data);
+
+                // Check for absolute paths and backslash separators that leak
+                // into the output, e.g., via templates, if enabled, or in
+                // strict mode - the root directory might be given relative to
+                // the working directory, and canonicalized, so we check both
+                if strict || validation.absolute_paths {
+                    let mut root_dirs = vec![
+                        config.get_root_dir(),
+                        config.project.root_dir.clone(),
+                    ];
+                    root_dirs.retain(|root_dir| root_dir.is_absolute());
+                    root_dirs.dedup();
+                    let mut leaks = Vec::new();
+                    for root_dir in &root_dirs {
+                        for context in leaked_paths(&data, root_dir) {
+                            diagnostics.warning(format!(
+                                "'{}' leaks '{}' into output: {context}",
+                                source.display(),
+                                root_dir.display()
+                            ));
+                            leaks.push(context);
+                        }
+                    }
+                    let links = backslash_links(&data);
+                    for link in &links {
+                        diagnostics.warning(format!(
+                            "'{}' links to '{link}', which contains \
+                             backslashes",
+                            source.display()
+                        ));
+                    }
+                    if strict && !(leaks.is_empty() && links.is_empty()) {
+                        anyhow::bail!("Aborted because --strict flag is set");
+                    }
+                }
+
+                // Write page
                 let path = Path::new(&page.path);
-                fs::create_dir_all(path.parent().expect("invariant"))?;
-                fs::write(path, &*data).map_err(Into::into)
+                sink.write(path, data.as_bytes())?;
+                let build = &config.project.build;
+                precompress(path, data.as_bytes(), build, &*sink, &report)?;
+                let elapsed = timer.elapsed();
+                if hit {
+                    report.add(Counter::CachedPages, 1);
+                }
+
+                // Attribute render time to the page, unless it was cached
+                let source = id.to_path();
+                let source = source.to_string_lossy();
+                let elapsed = (!hit).then_some(elapsed);
+                report.record_page(Stage::Render, &source, elapsed);
+                Ok(())
             })
         })
 }
 
+/// Render fallback pages for missing translations (i18n).
+///
+/// Pages of the default language that are missing in other languages are
+/// rendered into the subdirectories of those languages, so every language
+/// provides a complete site. Fallback pages are marked as not translated.
+#[allow(clippy::too_many_arguments)]
+pub fn render_fallbacks(
+    config: &Config, page: &Stream<Id, Page>, nav: &Stream<Id, Navigation>,
+    registry: &Registry, cache: &Cache, privacy: &Privacy,
+    sink: &Arc<dyn OutputSink>, report: &Report,
+) {
+    let config = config.clone();
+    let registry = registry.clone();
+    let cache = cache.clone();
+    let privacy = privacy.clone();
+    let sink = sink.clone();
+    let report = report.clone();
+    page.product(nav)
+        .map(move |id: &Id, page: Page, nav: Navigation| {
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::info_span!("render_fallbacks", url = %page.url)
+                    .entered();
+            let i18n = &config.project.plugins.i18n.config;
+            let Some(default) =
+                i18n.default_language().filter(|_| i18n.enabled)
+            else {
+                return Ok(());
+            };
+
+            // Only pages of the default language are rendered for all
+            // languages, in which a translation of the page is missing
+            let Some((language, neutral)) = i18n.split(&page.url) else {
+                return Ok(());
+            };
+            if language != default {
+                return Ok(());
+            }
+
+            // Skip languages in which the page is translated
+            let source = config.get_root_dir().join(id.to_path());
+            let urls = nav.translations.get(neutral);
+            for language in &i18n.languages {
+                if urls.is_some_and(|urls| urls.contains_key(&language.locale))
+                {
+                    continue;
+                }
+
+                // Record fallback page, so it's pruned together with the
+                // page of the default language it is rendered from
+                let mut page = fallback(&config, &page, language, neutral);
+                registry.insert_fallback(&source, &page.path);
+
+                // Compute hash of page content, which includes the URL, so
+                // the fallback is only rendered again if the page changed
+                let hash = {
+                    let mut hasher = StableHasher::default();
+                    page.url.hash(&mut hasher);
+                    page.content.hash(&mut hasher);
+                    page.meta.hash(&mut hasher);
+                    hasher.finish()
+                };
+
+                // Render page, measuring the time it takes
+                let _timer = report.timer(Stage::Render);
+                let nav = nav.clone().localized(i18n, &page);
+                let key = format!("fallback:{}", page.url);
+                let nav_hash = nav.hash_for(&page.content);
+                let args = (config.hash, config.templates_hash, nav_hash, hash);
+                let data = cached(&cache, key, args, |_| {
+                    Ok(page.render(&config, nav)?)
+                })?;
+                let base_url = config.get_base_url(&page.url);
+                let data = privacy.process_html(&base_url, data);
+                let data = prepare_offline(&config, &page.url, data);
+
+                // Write page to disk
+                let path = Path::new(&page.path);
+                sink.write(path, data.as_bytes())?;
+                let build = &config.project.build;
+                precompress(path, data.as_bytes(), build, &*sink, &report)?;
+            }
+
+            // All pages were written successfully
+            Ok::<_, anyhow::Error>(())
+        });
+}
+
+/// Render blog index pages.
+///
+/// Listings are created together with the navigation from all pages, including
+/// pages outside of the scope, so they are always complete. If a page maps to
+/// the same URL as one of the listings, the page is rendered as the listing,
+/// which is therefore skipped. Listings that vanished since the last build,
+/// e.g., pages of an index after posts were removed, are removed.
+#[allow(clippy::too_many_arguments)]
+pub fn render_blog(
+    config: &Config, pages: &Stream<Id, Vec<(Key<Id>, PageSummary)>>,
+    nav: &Stream<Id, Navigation>, scope: &Scope, listings: &Listings,
+    privacy: &Privacy, sink: &Arc<dyn OutputSink>, report: &Report,
+) {
+    let config = config.clone();
+    let scope = scope.clone();
+    let listings = listings.clone();
+    let privacy = privacy.clone();
+    let sink = sink.clone();
+    let report = report.clone();
+    let written = Arc::new(Mutex::new(BTreeMap::<String, PathBuf>::new()));
+    pages.product(nav).map(
+        move |mut pages: Vec<(Key<Id>, PageSummary)>, nav: Navigation| {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("render_blog", pages = pages.len())
+                .entered();
+            pages.extend(scope.pages().iter().cloned());
+            let is_page =
+                |url: &str| pages.iter().any(|(_, page)| *page.url == *url);
+
+            // Render listings and write them to disk, unless shadowed
+            let site_dir = config.get_site_dir();
+            let mut written = written.lock().expect("invariant");
+            let previous = mem::take(&mut *written);
+            for listing in listings.all() {
+                let path = site_dir.join(&listing.path);
+                written.insert(listing.url.clone(), path.clone());
+                if is_page(&listing.url) {
+                    continue;
+                }
+                let _timer = report.timer(Stage::Render);
+                let data = listing.render(&config, nav.clone())?;
+                let base_url = config.get_base_url(&listing.url);
+                let data = privacy.process_html(&base_url, data);
+                let data = prepare_offline(&config, &listing.url, data);
+                sink.write(&path, data.as_bytes())?;
+                let build = &config.project.build;
+                precompress(&path, data.as_bytes(), build, &*sink, &report)?;
+            }
+
+            // Remove listings of the last build that vanished, unless a page
+            // now maps to the same URL - precompressed copies are removed
+            // first, so directories are only removed after the last file
+            for (url, path) in previous {
+                if written.contains_key(&url) || is_page(&url) {
+                    continue;
+                }
+                for encoding in [Encoding::Gzip, Encoding::Brotli] {
+                    let mut file = path.clone().into_os_string();
+                    file.push(".");
+                    file.push(encoding.extension());
+                    sink.remove(Path::new(&file))?;
+                }
+                sink.remove(&path)?;
+            }
+            Ok::<_, anyhow::Error>(())
+        },
+    );
+}
+
 /// Creates a workflow for the given config.
-pub fn create_workflow(config: &Config, strict: bool) -> Workflow<Id> {
+///
+/// Timings and item counts of all stages are aggregated into the given report,
+/// which can be printed once the build is finished. All outputs are written
+/// through the given sink, i.e., to the site directory or into an archive,
+/// and only Markdown files in the given scope are built. The output paths of
+/// pages are recorded in the given registry, which is shared with the watcher
+/// to prune outputs of removed pages. Cancelling the given cancellation makes
+/// the workflow skip all Markdown files that are not yet converted. Renderers
+/// for single blocks of pages are recorded in the given partials, if enabled.
+/// Markdown is converted with the given converter, which allows to build
+/// without Python Markdown, e.g., in tests.
+#[allow(clippy::too_many_arguments)]
+pub fn create_workflow(
+    config: &Config, strict: bool, report: &Report, includes: &Includes,
+    registry: &Registry, cache: &Cache, diagnostics: &Diagnostics,
+    sink: &Arc<dyn OutputSink>, scope: &Scope, branches: &Branches,
+    cancellation: &Cancellation, partials: &Partials,
+    converter: &Arc<dyn MarkdownConverter>,
+) -> Workflow<Id> {
     let mut context = Context::default();
-    Main { config: config.clone(), strict }
-        .setup(&mut context)
-        .expect("invariant");
+    let report = report.clone();
+    Main {
+        config: config.clone(),
+        strict,
+        report,
+        registry: registry.clone(),
+        includes: includes.clone(),
+        cache: cache.clone(),
+        diagnostics: diagnostics.clone(),
+        sink: sink.clone(),
+        scope: scope.clone(),
+        branches: branches.clone(),
+        cancellation: cancellation.clone(),
+        partials: partials.clone(),
+        listings: Listings::default(),
+        converter: converter.clone(),
+    }
+    .setup(&mut context)
+    .expect("invariant");
     context.into()
 }
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::tempdir;
+    use zrx::id::Id;
+
+    use crate::config::permalink::Permalinks;
+    use crate::config::{Config, Project};
+    use crate::structure::markdown::{
+        Markdown, MarkdownConverter, MinimalMarkdown,
+    };
+    use crate::structure::page::Page;
+
+    use super::{convert_markdown, find_pages, Cache, Cancellation};
+
+    /// Markdown converter counting conversions.
+    #[derive(Debug, Default)]
+    struct Counting(AtomicUsize);
+
+    impl MarkdownConverter for Counting {
+        fn convert(
+            &self, id: &Id, url: String, content: String,
+            cancellation: &Cancellation,
+        ) -> Result<Markdown> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            MinimalMarkdown.convert(id, url, content, cancellation)
+        }
+    }
+
+    /// Creates an identifier for the given location in the docs directory.
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .unwrap()
+    }
+
+    /// Creates a configuration for the fixture project with the given name.
+    ///
+    /// Configuration files are parsed in Python, which tests can't call into,
+    /// so the project is set up with the defaults of the configuration parser.
+    fn fixture(name: &str) -> Config {
+        let root_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+            .canonicalize()
+            .unwrap();
+        Config {
+            path: root_dir.join("zensical.toml"),
+            project: Arc::new(Project { root_dir, ..Project::default() }),
+            theme_dirs: Vec::new(),
+            hash: 0,
+            templates_hash: 0,
+            theme_files: Vec::new(),
+            notes: Vec::new(),
+            warnings: Vec::new(),
+            permalinks: Permalinks::default(),
+        }
+    }
+
+    /// Converts the given page, and returns whether it was cached.
+    fn convert(
+        cache: &Cache, converter: &Counting, location: &str, url: &str,
+    ) -> bool {
+        let data = String::from("Hello");
+        let cancellation = Cancellation::default();
+        let id = id(location);
+        let url = url.to_string();
+        convert_markdown(cache, 0, &id, url, data, converter, &cancellation)
+            .map(|(_, hit)| hit)
+            .unwrap()
+    }
+
+    #[test]
+    fn renamed_pages_are_not_converted_again() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        let converter = Counting::default();
+        assert!(!convert(&cache, &converter, "guide/a.md", "guide/a/"));
+
+        // Renaming within the same directory keeps the cached result
+        cache
+            .rename(id("guide/a.md").as_str(), id("guide/b.md").as_str())
+            .unwrap();
+        assert!(convert(&cache, &converter, "guide/b.md", "guide/b/"));
+        assert_eq!(converter.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn renamed_pages_are_converted_again_if_depth_changes() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        let converter = Counting::default();
+        assert!(!convert(&cache, &converter, "x.md", "x/"));
+
+        // Index pages are not moved into a directory of their own, so links
+        // are resolved differently, even though both URLs have no parent
+        cache
+            .rename(id("x.md").as_str(), id("a/index.md").as_str())
+            .unwrap();
+        assert!(!convert(&cache, &converter, "a/index.md", "a/"));
+        assert_eq!(converter.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn pages_are_discovered_from_fixture_project() {
+        let config = fixture("project");
+        let cancellation = Cancellation::default();
+        let pages = find_pages(&config)
+            .unwrap()
+            .into_iter()
+            .map(|(path, id)| {
+                let content = fs::read_to_string(path).unwrap();
+                let converter = &MinimalMarkdown;
+                Page::new(&config, converter, &id, content, &cancellation)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Hidden directories and files other than Markdown are skipped
+        let urls = pages.iter().map(|page| page.url.as_str());
+        assert_eq!(urls.collect::<Vec<_>>(), ["guide/setup/", ""]);
+
+        // Titles are taken from front matter, or the first heading
+        assert_eq!(pages[0].title, "Setup guide");
+        assert_eq!(pages[1].title, "Home");
+
+        // Content and table of contents are rendered by the converter
+        assert_eq!(
+            pages[0].content,
+            "<h1 id=\"setup\">Setup</h1>\n\
+             <p>Install the project.</p>\n\
+             <h2 id=\"requirements\">Requirements</h2>"
+        );
+        assert_eq!(pages[0].toc[0].children[0].url, "#requirements");
+        assert_eq!(
+            pages[1].content,
+            "<h1 id=\"home\">Home</h1>\n<p>Welcome to the project.</p>"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Theme assets.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::sink::OutputSink;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// File extensions of templates, which are rendered and never copied.
+const TEMPLATE_EXTENSIONS: [&str; 4] = ["html", "jinja", "jinja2", "j2"];
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Copies a theme asset to the given path, if necessary.
+///
+/// Theme directories are ordered by precedence, so the custom directory comes
+/// before the main theme directory. If a theme directory with a higher
+/// precedence contains an asset at the same location, the asset is skipped,
+/// which ensures that each asset is copied at most once, and that overrides
+/// always win, regardless of the order in which assets are processed. Assets
+/// are also skipped if the destination is up to date. Returns whether the
+/// asset was copied.
+pub fn copy_theme_asset(
+    theme_dirs: &[PathBuf], from: &Path, location: &str, to: &Path,
+    sink: &dyn OutputSink,
+) -> io::Result<bool> {
+    if is_shadowed(theme_dirs, from, location) {
+        return Ok(false);
+    }
+
+    // Skip asset if the destination is up to date, which is determined by
+    // the sink, as only the site directory retains outputs between builds
+    if sink.is_fresh(from, to) {
+        return Ok(false);
+    }
+
+    // Copy file through sink, which creates intermediate directories
+    sink.copy(from, to).map(|()| true)
+}
+
+/// Returns whether the given theme asset is shadowed by another theme asset.
+///
+/// An asset is shadowed if a theme directory with a higher precedence than
+/// the one containing the asset contains an asset at the same location.
+pub fn is_shadowed(
+    theme_dirs: &[PathBuf], from: &Path, location: &str,
+) -> bool {
+    let index = theme_dirs.iter().position(|dir| from.starts_with(dir));
+    let index = index.unwrap_or(theme_dirs.len());
+    let mut iter = theme_dirs.iter().take(index);
+    iter.any(|dir| dir.join(location).is_file())
+}
+
+/// Returns whether the given location in a theme directory is a template.
+///
+/// Templates and partials are rendered, so they must never be copied. Besides
+/// HTML files, this includes files with Jinja extensions, as well as all files
+/// in the `partials` directory, which are only ever included by templates.
+pub fn is_template(location: &str) -> bool {
+    let path = Path::new(location);
+    path.starts_with("partials")
+        || path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| TEMPLATE_EXTENSIONS.contains(&extension))
+}
+
+/// Copy a file to a new location, without copying its permissions.
+pub fn copy_file(
+    from: impl AsRef<Path>, to: impl AsRef<Path>,
+) -> Result<(), io::Error> {
+    let mut from = fs::File::open(from)?;
+    let mut to = fs::File::create(to)?;
+    io::copy(&mut from, &mut to).map(|_| ())
+}
+
+/// Returns whether the destination is up to date with the source.
+///
+/// The destination is considered up to date, if it has the same size as the
+/// source, and was modified at the same time or after the source.
+pub fn is_fresh(from: &Path, to: &Path) -> bool {
+    let (Ok(from), Ok(to)) = (fs::metadata(from), fs::metadata(to)) else {
+        return false;
+    };
+
+    // Compare sizes and modification times
+    match (from.modified(), to.modified()) {
+        (Ok(a), Ok(b)) => from.len() == to.len() && a <= b,
+        _ => false,
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use crate::workflow::sink::Filesystem;
+
+    use super::{copy_theme_asset, is_template};
+
+    #[test]
+    fn override_wins_and_is_copied_once() {
+        let dir = tempdir().unwrap();
+        let custom = dir.path().join("custom");
+        let theme = dir.path().join("theme");
+        for (path, content) in [(&custom, "custom"), (&theme, "theme")] {
+            fs::create_dir_all(path.join("assets")).unwrap();
+            fs::write(path.join("assets/app.css"), content).unwrap();
+        }
+
+        // Process assets in both orders, which must yield the same result
+        let theme_dirs = [custom.clone(), theme.clone()];
+        for dirs in [[&theme, &custom], [&custom, &theme]] {
+            let site = tempdir().unwrap();
+            let to = site.path().join("assets/app.css");
+            let sink = Filesystem::new(site.path());
+            let copies = dirs
+                .into_iter()
+                .map(|from| {
+                    let from = from.join("assets/app.css");
+                    copy_theme_asset(
+                        &theme_dirs,
+                        &from,
+                        "assets/app.css",
+                        &to,
+                        &sink,
+                    )
+                    .unwrap()
+                })
+                .filter(|copied| *copied)
+                .count();
+            assert_eq!(copies, 1);
+            assert_eq!(fs::read_to_string(&to).unwrap(), "custom");
+        }
+    }
+
+    #[test]
+    fn up_to_date_assets_are_skipped() {
+        let dir = tempdir().unwrap();
+        let theme = dir.path().join("theme");
+        fs::create_dir_all(&theme).unwrap();
+        fs::write(theme.join("app.js"), "app").unwrap();
+
+        // Copy asset twice, where only the first copy is necessary
+        let theme_dirs = [theme.clone()];
+        let from = theme.join("app.js");
+        let to = dir.path().join("site/app.js");
+        let sink = Filesystem::new(dir.path().join("site"));
+        let copy =
+            || copy_theme_asset(&theme_dirs, &from, "app.js", &to, &sink);
+        assert!(copy().unwrap());
+        assert!(!copy().unwrap());
+    }
+
+    #[test]
+    fn templates_are_detected() {
+        assert!(is_template("main.html"));
+        assert!(is_template("blog.jinja"));
+        assert!(is_template("partials/footer.css"));
+        assert!(!is_template("assets/stylesheets/main.css"));
+        assert!(!is_template("assets/partials.js"));
+    }
+}
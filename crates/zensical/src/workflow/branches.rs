@@ -0,0 +1,267 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Workflow branches.
+
+use ahash::HashMap;
+use std::mem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Workflow branches.
+///
+/// Outputs that are derived from all pages, like the search index, are not
+/// needed by any other stage, so generating them must not delay the writing
+/// of pages. Such outputs are generated in branches, which run on their own
+/// threads in parallel to the scheduler. A build is only complete once the
+/// scheduler is idle and all branches have finished, which is why branches
+/// are tracked until they are joined.
+#[derive(Clone, Debug, Default)]
+pub struct Branches {
+    /// Running branches.
+    inner: Arc<Mutex<Vec<JoinHandle<anyhow::Result<()>>>>>,
+    /// Slots of exclusive branches by key.
+    slots: Arc<Mutex<HashMap<String, Arc<Slot>>>>,
+}
+
+/// Slot of exclusive branches.
+#[derive(Debug, Default)]
+struct Slot {
+    /// Lock held while a branch runs.
+    lock: Mutex<()>,
+    /// Generation of the most recently spawned branch.
+    generation: AtomicU64,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Branches {
+    /// Spawns a branch running the given function.
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce() -> anyhow::Result<()> + Send + 'static,
+    {
+        let handle = thread::spawn(f);
+        self.inner.lock().expect("invariant").push(handle);
+    }
+
+    /// Spawns a branch running the given function, exclusive to the given key.
+    ///
+    /// Branches writing the same outputs, e.g., the search index, are spawned
+    /// for every build, so they would overlap when builds follow each other
+    /// quickly. Branches with the same key never run concurrently, and each
+    /// branch is skipped if a newer one was spawned while it waited, so only
+    /// the outputs of the most recent build are written.
+    pub fn spawn_exclusive<F>(&self, key: &str, f: F)
+    where
+        F: FnOnce() -> anyhow::Result<()> + Send + 'static,
+    {
+        let slot = {
+            let mut slots = self.slots.lock().expect("invariant");
+            slots.entry(key.to_string()).or_default().clone()
+        };
+        let generation = slot.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.spawn(move || {
+            let _guard = slot.lock.lock().expect("invariant");
+            if slot.generation.load(Ordering::SeqCst) != generation {
+                return Ok(());
+            }
+            f()
+        });
+    }
+
+    /// Returns whether all branches have finished.
+    ///
+    /// Finished branches are joined, and the first error is returned, so it
+    /// can be handled like the errors of the scheduler. Other errors are lost,
+    /// as the build is aborted anyway.
+    pub fn poll(&self) -> anyhow::Result<bool> {
+        let mut inner = self.inner.lock().expect("invariant");
+        let (finished, running) = mem::take(&mut *inner)
+            .into_iter()
+            .partition::<Vec<_>, _>(JoinHandle::is_finished);
+        *inner = running;
+
+        // Join finished branches, which doesn't block
+        let done = inner.is_empty();
+        drop(inner);
+        finished.into_iter().try_for_each(join)?;
+        Ok(done)
+    }
+
+    /// Waits for all branches to finish, including branches that are spawned
+    /// while waiting, and returns the first error.
+    pub fn join(&self) -> anyhow::Result<()> {
+        let mut result = Ok(());
+        loop {
+            let handles =
+                mem::take(&mut *self.inner.lock().expect("invariant"));
+            if handles.is_empty() {
+                return result;
+            }
+            for handle in handles {
+                let outcome = join(handle);
+                if result.is_ok() {
+                    result = outcome;
+                }
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Joins the given branch, turning panics into errors.
+fn join(handle: JoinHandle<anyhow::Result<()>>) -> anyhow::Result<()> {
+    handle
+        .join()
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("Branch panicked")))
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    use super::Branches;
+
+    #[test]
+    fn join_waits_for_all_branches() {
+        let temp = tempdir().unwrap();
+        let branches = Branches::default();
+
+        // Branches finish in reverse order of spawning
+        for (name, delay) in [("a", 30), ("b", 20), ("c", 10)] {
+            let path = temp.path().join(name);
+            branches.spawn(move || {
+                thread::sleep(Duration::from_millis(delay));
+                fs::write(path, name)?;
+                Ok(())
+            });
+        }
+        branches.join().unwrap();
+        for name in ["a", "b", "c"] {
+            assert!(temp.path().join(name).exists());
+        }
+        assert!(branches.poll().unwrap());
+    }
+
+    #[test]
+    fn join_includes_nested_branches() {
+        let temp = tempdir().unwrap();
+        let branches = Branches::default();
+        let path = temp.path().join("nested");
+        branches.spawn({
+            let branches = branches.clone();
+            move || {
+                branches.spawn(move || {
+                    fs::write(path, "")?;
+                    Ok(())
+                });
+                Ok(())
+            }
+        });
+        branches.join().unwrap();
+        assert!(temp.path().join("nested").exists());
+    }
+
+    #[test]
+    fn exclusive_branches_skip_superseded_branches() {
+        let branches = Branches::default();
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        // First branch runs until released, while the others are spawned
+        branches.spawn_exclusive("search.json", {
+            let order = order.clone();
+            move || {
+                started_tx.send(())?;
+                release_rx.recv()?;
+                order.lock().unwrap().push("a");
+                Ok(())
+            }
+        });
+        started_rx.recv().unwrap();
+        for name in ["b", "c", "d"] {
+            let order = order.clone();
+            branches.spawn_exclusive("search.json", move || {
+                order.lock().unwrap().push(name);
+                Ok(())
+            });
+        }
+
+        // Only the most recent branch runs after the first one finished
+        release_tx.send(()).unwrap();
+        branches.join().unwrap();
+        assert_eq!(*order.lock().unwrap(), ["a", "d"]);
+    }
+
+    #[test]
+    fn errors_are_returned() {
+        let branches = Branches::default();
+        branches.spawn(|| Ok(()));
+        branches.spawn(|| anyhow::bail!("failed"));
+        let err = branches.join().unwrap_err();
+        assert_eq!(err.to_string(), "failed");
+    }
+
+    #[test]
+    fn poll_reports_errors_of_finished_branches() {
+        let branches = Branches::default();
+        branches.spawn(|| anyhow::bail!("failed"));
+        let err = loop {
+            match branches.poll() {
+                Ok(_) => thread::yield_now(),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.to_string(), "failed");
+        assert!(branches.poll().unwrap());
+    }
+
+    #[test]
+    fn panics_are_turned_into_errors() {
+        let branches = Branches::default();
+        branches.spawn(|| panic!("boom"));
+        assert!(branches.join().is_err());
+    }
+}
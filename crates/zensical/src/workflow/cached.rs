@@ -27,17 +27,80 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use zrx::scheduler::Value;
 
-use crate::config::Config;
+use crate::clear_dir;
+use crate::hash::StableHasher;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Cache format version, which must be bumped when the format changes.
+const FORMAT: u32 = 2;
+
+/// Name of the cache manifest.
+const MANIFEST: &str = "manifest.json";
+
+/// Counter for unique names of temporary files.
+static TEMPORARY: AtomicU64 = AtomicU64::new(0);
 
 // ----------------------------------------------------------------------------
 // Structs
 // ----------------------------------------------------------------------------
 
 /// Workflow cache.
+///
+/// The cache keeps a manifest next to the cached artifacts, which records the
+/// format version and the version of the executable that wrote the cache, as
+/// well as the size of each entry and the build in which it was last used.
+/// Entries for deleted pages or superseded hashes are not used anymore, which
+/// is why they're pruned after a configurable number of builds.
+///
+/// Entries and the manifest are written to a temporary file first, which is
+/// then renamed, so concurrent readers never observe partially written files.
+/// The manifest is only ever written while holding its lock, and each entry
+/// starts with a header containing its length and checksum, so entries that
+/// were corrupted nonetheless are detected, and computed again.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    /// Cache directory.
+    dir: PathBuf,
+    /// Cache manifest.
+    manifest: Arc<Mutex<Manifest>>,
+}
+
+/// Cache manifest.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// Cache format version.
+    format: u32,
+    /// Version of the executable that wrote the cache.
+    version: String,
+    /// Build counter.
+    build: u64,
+    /// Cache entries.
+    entries: BTreeMap<String, Entry>,
+}
+
+/// Cache entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    /// Size in bytes.
+    size: u64,
+    /// Build in which the entry was last used.
+    used: u64,
+}
+
+/// Cached artifact.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct Cached<T> {
     /// Cached data.
@@ -46,6 +109,187 @@ struct Cached<T> {
     pub hash: u64,
 }
 
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Cache {
+    /// Opens the cache in the given directory and starts a new build.
+    ///
+    /// If the manifest is missing, can't be read, or was written by another
+    /// version, all entries are removed, so we start with a clean cache.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with_version(dir, env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Opens the cache with the given executable version.
+    fn open_with_version(
+        dir: impl AsRef<Path>, version: &str,
+    ) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let manifest = fs::read(dir.join(MANIFEST))
+            .ok()
+            .and_then(|data| serde_json::from_slice::<Manifest>(&data).ok())
+            .filter(|manifest| {
+                manifest.format == FORMAT && manifest.version == version
+            });
+
+        // Start with a clean cache, if the manifest doesn't match, and start
+        // a new build, so we know which entries were used in this build
+        let mut manifest = match manifest {
+            Some(manifest) => manifest,
+            None => {
+                // Entries that can't be removed are skipped, as they're not
+                // part of the new manifest, and overwritten when written again
+                clear_dir(&dir)?;
+                Manifest {
+                    format: FORMAT,
+                    version: version.to_string(),
+                    build: 0,
+                    entries: BTreeMap::new(),
+                }
+            }
+        };
+        manifest.build += 1;
+
+        // Return cache
+        Ok(Self {
+            dir,
+            manifest: Arc::new(Mutex::new(manifest)),
+        })
+    }
+
+    /// Returns the path of the entry with the given key.
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+
+    /// Marks the entry with the given key as used in the current build.
+    fn touch(&self, key: &str, size: u64) {
+        let mut manifest = self.manifest.lock().expect("invariant");
+        let used = manifest.build;
+        manifest
+            .entries
+            .insert(key.to_string(), Entry { size, used });
+    }
+
+    /// Moves the entry for the given identifier to another identifier.
+    ///
+    /// This is used when files are renamed, so the entry follows the file, and
+    /// is used if the arguments of the computation didn't change. Nothing is
+    /// done if there's no entry for the given identifier.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the entry could not be moved.
+    pub fn rename<I>(&self, from: I, to: I) -> io::Result<()>
+    where
+        I: Hash,
+    {
+        let (from, to) = (to_key(&from), to_key(&to));
+        let mut manifest = self.manifest.lock().expect("invariant");
+        match fs::rename(self.path(&from), self.path(&to)) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+            Ok(()) => {
+                if let Some(entry) = manifest.entries.remove(&from) {
+                    manifest.entries.insert(to, entry);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Prunes the cache and persists the manifest.
+    ///
+    /// Entries that weren't used for the given number of builds are removed,
+    /// as well as files that are not tracked by the manifest. If a maximum
+    /// size is given, the least recently used entries are evicted until the
+    /// cache fits. Returns the number of removed entries.
+    pub fn prune(
+        &self, max_age: u64, max_size: Option<u64>,
+    ) -> io::Result<usize> {
+        let mut manifest = self.manifest.lock().expect("invariant");
+        let build = manifest.build;
+
+        // Remove entries that weren't used for the given number of builds
+        let mut removed = Vec::new();
+        manifest.entries.retain(|key, entry| {
+            let keep = build - entry.used < max_age.max(1);
+            if !keep {
+                removed.push(key.clone());
+            }
+            keep
+        });
+
+        // Evict least recently used entries until the cache fits, using the
+        // key as a tie breaker, so eviction is deterministic
+        if let Some(max_size) = max_size {
+            let mut entries = manifest
+                .entries
+                .iter()
+                .map(|(key, entry)| (entry.used, key.clone(), entry.size))
+                .collect::<Vec<_>>();
+            entries.sort();
+
+            // Sum up sizes, and evict entries until we're below the limit
+            let mut size = entries.iter().map(|(_, _, size)| size).sum::<u64>();
+            for (_, key, entry_size) in entries {
+                if size <= max_size {
+                    break;
+                }
+                manifest.entries.remove(&key);
+                removed.push(key);
+                size -= entry_size;
+            }
+        }
+
+        // Remove files of pruned entries, as well as orphaned files
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str())
+            else {
+                continue;
+            };
+            if name == MANIFEST
+                || name.starts_with('.')
+                || manifest.entries.contains_key(name)
+            {
+                continue;
+            }
+
+            // Count orphaned files as removed entries as well
+            if !removed.iter().any(|key| key == name) {
+                removed.push(name.to_string());
+            }
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+
+        // Persist manifest
+        self.write(&manifest)?;
+        Ok(removed.len())
+    }
+
+    /// Persists the manifest without pruning.
+    ///
+    /// This is used when shutting down, so entries used in the current build
+    /// are retained, even though the build didn't finish.
+    pub fn flush(&self) -> io::Result<()> {
+        let manifest = self.manifest.lock().expect("invariant");
+        self.write(&manifest)
+    }
+
+    /// Writes the given manifest to the cache directory.
+    fn write(&self, manifest: &Manifest) -> io::Result<()> {
+        let data = serde_json::to_vec(manifest)?;
+        write_atomic(&self.dir.join(MANIFEST), &data)
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Functions
 // ----------------------------------------------------------------------------
@@ -54,23 +298,16 @@ struct Cached<T> {
 /// input arguments. Note that this is only a preliminary implementation, and
 /// will be replaced with a more generic caching mechanism integrated into
 /// the runtime.
-pub fn cached<I, T, F, U>(config: &Config, id: I, args: T, f: F) -> Result<U>
+pub fn cached<I, T, F, U>(cache: &Cache, id: I, args: T, f: F) -> Result<U>
 where
     I: Hash,
     T: Hash,
     F: FnOnce(T) -> Result<U>,
     U: Value + Serialize + for<'de> Deserialize<'de>,
 {
-    // Compute hash of identifier
-    let id_hash = {
-        let mut hasher = DefaultHasher::default();
-        id.hash(&mut hasher);
-        hasher.finish()
-    };
-
     // Compute hash of content
     let hash = {
-        let mut hasher = DefaultHasher::default();
+        let mut hasher = StableHasher::default();
         args.hash(&mut hasher);
         hasher.finish()
     };
@@ -79,24 +316,299 @@ where
     // check if we already have a cached version of the artifact. If so, compare
     // the content hash and return cached version if it matches. Otherwise, we
     // continue and compute the artifact.
-    let path = config.get_cache_dir().join(id_hash.to_string());
+    let key = to_key(&id);
+    let path = cache.path(&key);
     if let Ok(data) = fs::read(&path) {
-        // Right now, the product operator might lead to a race condition, since
-        // a page might be double-processed for some reason. We need to fix this
-        // in ZRX, but in the meantime, we just ignore the cache if it happens.
-        if let Ok(cached) = serde_json::from_slice::<Cached<U>>(&data) {
+        // Entries are written atomically, but might still be corrupted, e.g.,
+        // when the disk is full, so we verify them before deserializing, and
+        // remove corrupted entries, so they're computed again below
+        let cached = unseal(&data)
+            .and_then(|body| serde_json::from_slice::<Cached<U>>(body).ok());
+        match cached {
             // In case content hashes match, return cached data
-            if cached.hash == hash {
+            Some(cached) if cached.hash == hash => {
+                cache.touch(&key, data.len() as u64);
                 return Ok(cached.data);
             }
+            Some(_) => {}
+            None => {
+                let _ = fs::remove_file(&path);
+            }
         }
     }
 
-    // Compute artifact and convert into report - note that we need to properly
-    // handle encoding and file I/O errors here as well
-    f(args).inspect(|data| {
-        serde_json::to_string_pretty(&Cached { data, hash })
-            .map(|content| fs::write(path, content).expect("invariant"))
-            .expect("invariant");
-    })
+    // Compute artifact, and write it to the cache - the same entry might be
+    // computed concurrently, in which case the last write wins, which is fine,
+    // as both writes are complete, and the hash tells whether it's current
+    let data = f(args)?;
+    let body = serde_json::to_vec_pretty(&Cached { data: &data, hash })?;
+    let content = seal(&body);
+    write_atomic(&path, &content)?;
+    cache.touch(&key, content.len() as u64);
+    Ok(data)
+}
+
+/// Writes data to the given path atomically.
+///
+/// Data is written to a uniquely named temporary file next to the given path,
+/// which is then renamed, so the file is either replaced entirely or not at
+/// all. Temporary files that are left behind are pruned as untracked files.
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_os_string();
+    let count = TEMPORARY.fetch_add(1, Ordering::Relaxed);
+    temp.push(format!(".{}.{count}.tmp", process::id()));
+    let temp = PathBuf::from(temp);
+    fs::write(&temp, data)
+        .and_then(|()| fs::rename(&temp, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+}
+
+/// Prepends a header with the length and checksum of the given entry body.
+fn seal(body: &[u8]) -> Vec<u8> {
+    let header = format!("{} {}\n", body.len(), checksum(body));
+    let mut content = header.into_bytes();
+    content.extend_from_slice(body);
+    content
+}
+
+/// Returns the body of the given entry, if its length and checksum match.
+fn unseal(content: &[u8]) -> Option<&[u8]> {
+    let index = content.iter().position(|&byte| byte == b'\n')?;
+    let (header, body) = (&content[..index], &content[index + 1..]);
+    let header = std::str::from_utf8(header).ok()?;
+    let (len, sum) = header.split_once(' ')?;
+    let valid = len.parse::<usize>().ok() == Some(body.len())
+        && sum.parse::<u64>().ok() == Some(checksum(body));
+    valid.then_some(body)
+}
+
+/// Returns the checksum of the given data.
+fn checksum(data: &[u8]) -> u64 {
+    let mut hasher = StableHasher::default();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the key of the entry for the given identifier.
+fn to_key<I>(id: &I) -> String
+where
+    I: Hash,
+{
+    let mut hasher = StableHasher::default();
+    id.hash(&mut hasher);
+    hasher.finish().to_string()
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::thread;
+    use tempfile::tempdir;
+
+    use crate::hash::stable_hash_dirs;
+    use crate::template::Output;
+
+    use super::{cached, to_key, unseal, Cache, MANIFEST};
+
+    /// Computes a cached value, and returns whether it was computed.
+    fn compute(cache: &Cache, id: &str, value: &str) -> bool {
+        let mut computed = false;
+        cached(cache, id, value, |value| {
+            computed = true;
+            Ok(Output::from(value.to_string()))
+        })
+        .unwrap();
+        computed
+    }
+
+    #[test]
+    fn version_mismatch_invalidates_cache() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open_with_version(dir.path(), "1.0.0").unwrap();
+        assert!(compute(&cache, "a", "value"));
+        cache.prune(10, None).unwrap();
+
+        // Same version uses cached entry
+        let cache = Cache::open_with_version(dir.path(), "1.0.0").unwrap();
+        assert!(!compute(&cache, "a", "value"));
+        cache.prune(10, None).unwrap();
+
+        // Other version starts with a clean cache
+        let cache = Cache::open_with_version(dir.path(), "2.0.0").unwrap();
+        assert!(compute(&cache, "a", "value"));
+    }
+
+    #[test]
+    fn rename_moves_entry() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        assert!(compute(&cache, "docs/old.md", "value"));
+
+        // Renamed entry is used, and the old entry is gone
+        cache.rename("docs/old.md", "docs/new.md").unwrap();
+        assert!(!compute(&cache, "docs/new.md", "value"));
+        assert!(compute(&cache, "docs/old.md", "value"));
+
+        // Renaming a missing entry does nothing
+        cache.rename("docs/missing.md", "docs/other.md").unwrap();
+        assert!(compute(&cache, "docs/other.md", "value"));
+    }
+
+    #[test]
+    fn corrupted_entry_is_computed_again() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        assert!(compute(&cache, "a", "value"));
+
+        // Truncate entry, which must be detected and computed again
+        let path = dir.path().join(to_key(&"a"));
+        let data = fs::read(&path).unwrap();
+        fs::write(&path, &data[..data.len() / 2]).unwrap();
+        assert!(compute(&cache, "a", "value"));
+        assert!(!compute(&cache, "a", "value"));
+    }
+
+    #[test]
+    fn concurrent_writes_are_atomic() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+
+        // Read and write the same keys with different values from multiple
+        // threads, which must always return the value for the given input
+        thread::scope(|scope| {
+            for n in 0..8 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for i in 0..50 {
+                        let id = format!("key-{}", i % 4);
+                        let value = format!("value-{}", (n + i) % 3);
+                        let output = cached(cache, &id, &value, |value| {
+                            Ok(Output::from(value.to_string()))
+                        })
+                        .unwrap();
+                        assert_eq!(output.to_string(), value);
+                    }
+                });
+            }
+        });
+
+        // All entries must be intact, and no temporary files are left behind
+        for entry in fs::read_dir(dir.path()).unwrap() {
+            let path = entry.unwrap().path();
+            if path.file_name().unwrap() != MANIFEST {
+                assert!(unseal(&fs::read(&path).unwrap()).is_some());
+            }
+        }
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 4);
+    }
+
+    #[test]
+    fn missing_manifest_invalidates_cache() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "*").unwrap();
+        fs::write(dir.path().join("123"), "stale").unwrap();
+
+        // Clean start removes stale entries, but keeps hidden files
+        Cache::open(dir.path()).unwrap();
+        assert!(!dir.path().join("123").exists());
+        assert!(dir.path().join(".gitignore").exists());
+    }
+
+    #[test]
+    fn prune_removes_orphaned_entries() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        compute(&cache, "a", "value");
+        compute(&cache, "b", "value");
+        cache.prune(2, None).unwrap();
+
+        // Only use entry "a" in subsequent builds
+        for _ in 0..2 {
+            let cache = Cache::open(dir.path()).unwrap();
+            assert!(!compute(&cache, "a", "value"));
+            cache.prune(2, None).unwrap();
+        }
+
+        // Entry "b" was pruned, and must be computed again
+        let cache = Cache::open(dir.path()).unwrap();
+        assert!(!compute(&cache, "a", "value"));
+        assert!(compute(&cache, "b", "value"));
+        assert!(dir.path().join(MANIFEST).exists());
+    }
+
+    #[test]
+    fn prune_removes_untracked_files() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        compute(&cache, "a", "value");
+        fs::write(dir.path().join("123"), "untracked").unwrap();
+
+        // Untracked files are removed
+        assert_eq!(cache.prune(10, None).unwrap(), 1);
+        assert!(!dir.path().join("123").exists());
+    }
+
+    #[test]
+    fn prune_evicts_least_recently_used_entries() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::open(dir.path()).unwrap();
+        compute(&cache, "a", "value");
+        cache.prune(10, None).unwrap();
+
+        // Use entry "b" in a later build, and limit size to a single entry
+        let cache = Cache::open(dir.path()).unwrap();
+        compute(&cache, "b", "value");
+        let manifest = cache.manifest.lock().unwrap();
+        let size = manifest.entries.values().map(|entry| entry.size).max();
+        drop(manifest);
+        assert_eq!(cache.prune(10, size).unwrap(), 1);
+
+        // Entry "a" was evicted, as it was used least recently
+        let cache = Cache::open(dir.path()).unwrap();
+        assert!(compute(&cache, "a", "value"));
+        assert!(!compute(&cache, "b", "value"));
+    }
+
+    #[test]
+    fn template_edit_invalidates_render() {
+        let dir = tempdir().unwrap();
+        let theme_dir = tempdir().unwrap();
+        let template = theme_dir.path().join("main.html");
+        fs::write(&template, "aaaa").unwrap();
+
+        // Render page with the templates hash as part of the key, returning
+        // the rendered output, and whether it was computed
+        let dirs = [theme_dir.path().to_path_buf()];
+        let render = || {
+            let cache = Cache::open(dir.path()).unwrap();
+            let args = (0, stable_hash_dirs(&dirs).unwrap());
+            let mut computed = false;
+            let output = cached(&cache, "index.html", args, |_| {
+                computed = true;
+                Ok(Output::from(fs::read_to_string(&template)?))
+            })
+            .unwrap();
+            cache.prune(10, None).unwrap();
+            (output.to_string(), computed)
+        };
+        assert_eq!(render(), ("aaaa".to_string(), true));
+        assert_eq!(render(), ("aaaa".to_string(), false));
+
+        // Change template, but retain size and modification time
+        let modified = fs::metadata(&template).unwrap().modified().unwrap();
+        fs::write(&template, "bbbb").unwrap();
+        File::options()
+            .write(true)
+            .open(&template)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(render(), ("bbbb".to_string(), true));
+    }
 }
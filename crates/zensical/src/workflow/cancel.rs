@@ -0,0 +1,237 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Workflow cancellation.
+
+use pyo3::types::PyAnyMethods;
+use pyo3::{ffi, PyResult, Python};
+use std::os::raw::c_long;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Workflow cancellation.
+///
+/// Signals are only handled by the run loop between ticks of the scheduler,
+/// so converting Markdown files, which is bound to the Python interpreter,
+/// would continue for all queued files after an interrupt. The run loop thus
+/// cancels the workflow, which makes the Markdown stage skip all files that
+/// are not yet converted, and raises `KeyboardInterrupt` in the conversions
+/// in flight, so the build can exit once they returned.
+///
+/// Note that Python only checks for the raised exception between bytecode
+/// instructions, so a conversion that is busy in native code, e.g., matching
+/// a regular expression, still runs until it returns to Python code.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+    /// Inner state.
+    inner: Arc<Inner>,
+}
+
+/// Cancellation state.
+#[derive(Debug, Default)]
+struct Inner {
+    /// Whether the workflow was cancelled.
+    cancelled: AtomicBool,
+    /// Conversions in flight.
+    current: Mutex<Vec<Conversion>>,
+    /// Condition variable to wait for conversions in flight.
+    done: Condvar,
+}
+
+/// Conversion in flight.
+#[derive(Debug)]
+struct Conversion {
+    /// Location of the converted file.
+    location: String,
+    /// Identifier of the Python thread running the conversion.
+    thread: u64,
+}
+
+/// Guard for a conversion in flight, which is finished when dropped.
+#[derive(Debug)]
+pub struct Guard {
+    /// Inner state.
+    inner: Arc<Inner>,
+    /// Identifier of the Python thread running the conversion.
+    thread: u64,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Cancellation {
+    /// Cancels the workflow.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the workflow was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Starts the conversion of the file at the given location, running on
+    /// the Python thread with the given identifier.
+    ///
+    /// If the workflow is already cancelled, the file must be skipped, which
+    /// is signalled by returning `None`. Otherwise, the conversion is in flight
+    /// until the returned guard is dropped.
+    pub fn start<S>(&self, location: S, thread: u64) -> Option<Guard>
+    where
+        S: Into<String>,
+    {
+        let mut current = self.inner.current.lock().expect("invariant");
+        if self.is_cancelled() {
+            return None;
+        }
+
+        // Record conversion in flight
+        let location = location.into();
+        current.push(Conversion { location, thread });
+        Some(Guard {
+            inner: Arc::clone(&self.inner),
+            thread,
+        })
+    }
+
+    /// Raises `KeyboardInterrupt` in all conversions in flight, and returns
+    /// the locations of the converted files.
+    pub fn interrupt(&self, _py: Python) -> Vec<String> {
+        let current = self.inner.current.lock().expect("invariant");
+        current
+            .iter()
+            .map(|conversion| {
+                #[allow(clippy::cast_possible_truncation)]
+                #[allow(clippy::cast_possible_wrap)]
+                let thread = conversion.thread as c_long;
+
+                // SAFETY: the GIL is held, as witnessed by the Python token,
+                // and the exception type lives as long as the interpreter.
+                // Identifiers of threads that already exited are ignored.
+                unsafe {
+                    ffi::PyThreadState_SetAsyncExc(
+                        thread,
+                        ffi::PyExc_KeyboardInterrupt,
+                    );
+                }
+                conversion.location.clone()
+            })
+            .collect()
+    }
+
+    /// Waits until all conversions in flight are finished, or the timeout
+    /// elapsed, and returns whether they are finished.
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let current = self.inner.current.lock().expect("invariant");
+        let (current, _) = self
+            .inner
+            .done
+            .wait_timeout_while(current, timeout, |current| !current.is_empty())
+            .expect("invariant");
+        current.is_empty()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Drop for Guard {
+    /// Finishes the conversion in flight.
+    fn drop(&mut self) {
+        let mut current = self.inner.current.lock().expect("invariant");
+        current.retain(|conversion| conversion.thread != self.thread);
+        self.inner.done.notify_all();
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the identifier of the current Python thread.
+pub fn thread_ident(py: Python) -> PyResult<u64> {
+    py.import("threading")?.call_method0("get_ident")?.extract()
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::Cancellation;
+
+    #[test]
+    fn skips_remaining_files_once_cancelled() {
+        let cancellation = Cancellation::default();
+        let mut converted = Vec::new();
+        for (location, thread) in [("a.md", 1), ("b.md", 2), ("c.md", 3)] {
+            if let Some(_guard) = cancellation.start(location, thread) {
+                converted.push(location);
+            }
+
+            // Cancel after the first file, e.g., from the run loop
+            if location == "a.md" {
+                cancellation.clone().cancel();
+            }
+        }
+        assert!(cancellation.is_cancelled());
+        assert_eq!(converted, ["a.md"]);
+    }
+
+    #[test]
+    fn waits_for_conversions_in_flight() {
+        let cancellation = Cancellation::default();
+        let guard = cancellation.start("a.md", 1).unwrap();
+        cancellation.cancel();
+        assert!(!cancellation.wait_timeout(Duration::from_millis(10)));
+
+        // Finish conversion on another thread
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(guard);
+        });
+        assert!(cancellation.wait_timeout(Duration::from_secs(5)));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn waits_for_nothing_if_idle() {
+        let cancellation = Cancellation::default();
+        assert!(cancellation.wait_timeout(Duration::ZERO));
+        drop(cancellation.start("a.md", 1));
+        assert!(cancellation.wait_timeout(Duration::ZERO));
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Precompressed copies of text outputs.
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::build::{Build, Encoding};
+
+use super::report::{Counter, Report};
+use super::sink::OutputSink;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// File extensions of text outputs, which are eligible for precompression.
+const TEXT_EXTENSIONS: [&str; 9] = [
+    "html", "htm", "css", "js", "json", "xml", "svg", "txt", "map",
+];
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Writes precompressed copies of the given text output, if enabled.
+///
+/// For each configured encoding, a sibling with the extension of the encoding
+/// is written next to the output, e.g., `index.html.gz`, so web servers can
+/// serve it directly. Outputs below the size threshold are skipped, as are
+/// copies that are not smaller than the output itself. Stale siblings left
+/// over from earlier builds are removed in both cases.
+pub fn precompress(
+    path: &Path, data: &[u8], build: &Build, sink: &dyn OutputSink,
+    report: &Report,
+) -> io::Result<()> {
+    if build.precompress.is_empty() || !is_text(path) {
+        return Ok(());
+    }
+
+    // Compress output for each supported encoding
+    let size = data.len() as u64;
+    for &encoding in &build.precompress {
+        if !encoding.is_supported() {
+            continue;
+        }
+
+        // Write sibling, unless it's too small or doesn't save anything
+        let sibling = sibling(path, encoding);
+        if size >= build.precompress_min_size {
+            let compressed = compress(data, encoding)?;
+            if (compressed.len() as u64) < size {
+                sink.write(&sibling, &compressed)?;
+                report.add(Counter::PrecompressedFiles, 1);
+                report.add(
+                    Counter::PrecompressedBytesSaved,
+                    size - compressed.len() as u64,
+                );
+                continue;
+            }
+        }
+
+        // Remove stale sibling, if any
+        sink.remove(&sibling)?;
+    }
+
+    // All siblings were written successfully
+    Ok(())
+}
+
+/// Writes precompressed copies of the given copied file, if enabled.
+///
+/// This is used for copied assets, which are only read from their source if
+/// they are text outputs, and precompression is enabled.
+pub fn precompress_file(
+    from: &Path, to: &Path, build: &Build, sink: &dyn OutputSink,
+    report: &Report,
+) -> io::Result<()> {
+    if build.precompress.is_empty() || !is_text(to) {
+        return Ok(());
+    }
+
+    // Read file and write precompressed copies
+    let data = fs::read(from)?;
+    precompress(to, &data, build, sink, report)
+}
+
+/// Rewrites existing precompressed copies of the given output.
+///
+/// This is necessary when outputs are modified after the build, e.g., when the
+/// site is relocated, so the copies served by web servers don't go stale. Only
+/// copies that exist are rewritten, as the build settings are not known here.
+pub fn recompress(path: &Path, data: &[u8]) -> io::Result<()> {
+    for encoding in [Encoding::Gzip, Encoding::Brotli] {
+        let sibling = sibling(path, encoding);
+        if encoding.is_supported() && sibling.exists() {
+            fs::write(&sibling, compress(data, encoding)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether the given path is a text output.
+pub fn is_text(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| TEXT_EXTENSIONS.contains(&extension))
+}
+
+/// Returns whether the given path is a precompressed copy.
+///
+/// The file watcher uses this to ignore precompressed copies, as they are
+/// written next to outputs, and would otherwise trigger reloads.
+pub fn is_precompressed(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            [Encoding::Gzip, Encoding::Brotli]
+                .iter()
+                .any(|encoding| encoding.extension() == extension)
+        })
+}
+
+/// Returns the path of the precompressed copy for the given encoding.
+fn sibling(path: &Path, encoding: Encoding) -> PathBuf {
+    let mut sibling = path.as_os_str().to_owned();
+    sibling.push(".");
+    sibling.push(encoding.extension());
+    PathBuf::from(sibling)
+}
+
+/// Compresses the given data with the given encoding.
+fn compress(data: &[u8], encoding: Encoding) -> io::Result<Vec<u8>> {
+    match encoding {
+        Encoding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        #[cfg(feature = "brotli")]
+        Encoding::Brotli => {
+            let mut encoder =
+                brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+            encoder.write_all(data)?;
+            Ok(encoder.into_inner())
+        }
+        #[cfg(not(feature = "brotli"))]
+        Encoding::Brotli => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "brotli support is not enabled",
+        )),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use crate::config::build::{Build, Encoding};
+    use crate::workflow::report::{Counter, Report};
+    use crate::workflow::sink::Filesystem;
+
+    use super::{is_precompressed, precompress};
+
+    /// Returns build settings with gzip precompression.
+    fn build(precompress_min_size: u64) -> Build {
+        Build {
+            precompress: vec![Encoding::Gzip],
+            precompress_min_size,
+            output: None,
+            manifest: None,
+            manifest_meta: Vec::new(),
+            include_hidden: Vec::new(),
+            content_hashes: false,
+        }
+    }
+
+    /// Returns pseudo-random data, which does not compress well.
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state: u32 = 0x2545_f491;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn writes_sibling_for_text_outputs() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("index.html");
+        let data = "<p>Hello</p>".repeat(200);
+        fs::write(&path, &data).unwrap();
+
+        // Precompress output, which must yield a smaller sibling
+        let report = Report::default();
+        let sink = Filesystem::new(dir.path());
+        precompress(&path, data.as_bytes(), &build(1024), &sink, &report)
+            .unwrap();
+        let sibling = dir.path().join("index.html.gz");
+        let size = fs::metadata(&sibling).unwrap().len();
+        assert!(size < data.len() as u64);
+        assert_eq!(report.get(Counter::PrecompressedFiles), 1);
+        assert_eq!(
+            report.get(Counter::PrecompressedBytesSaved),
+            data.len() as u64 - size
+        );
+    }
+
+    #[test]
+    fn skips_siblings_that_are_not_smaller() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.js");
+        let data = noise(4096);
+
+        // Precompress output, which must not yield a sibling
+        let report = Report::default();
+        let sink = Filesystem::new(dir.path());
+        precompress(&path, &data, &build(0), &sink, &report).unwrap();
+        assert!(!dir.path().join("app.js.gz").exists());
+        assert_eq!(report.get(Counter::PrecompressedFiles), 0);
+    }
+
+    #[test]
+    fn skips_outputs_below_threshold_and_removes_stale_siblings() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("search.json");
+        let sibling = dir.path().join("search.json.gz");
+        fs::write(&sibling, "stale").unwrap();
+
+        // Precompress output below threshold, which must remove the sibling
+        let report = Report::default();
+        let sink = Filesystem::new(dir.path());
+        let data = "[]".repeat(100);
+        precompress(&path, data.as_bytes(), &build(1024), &sink, &report)
+            .unwrap();
+        assert!(!sibling.exists());
+        assert_eq!(report.get(Counter::PrecompressedFiles), 0);
+    }
+
+    #[test]
+    fn skips_binary_outputs() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        let data = vec![0; 4096];
+
+        // Precompress binary output, which must not yield a sibling
+        let report = Report::default();
+        let sink = Filesystem::new(dir.path());
+        precompress(&path, &data, &build(0), &sink, &report).unwrap();
+        assert!(!dir.path().join("image.png.gz").exists());
+    }
+
+    #[test]
+    fn precompressed_copies_are_detected() {
+        assert!(is_precompressed("index.html.gz".as_ref()));
+        assert!(is_precompressed("assets/app.js.br".as_ref()));
+        assert!(!is_precompressed("index.html".as_ref()));
+    }
+}
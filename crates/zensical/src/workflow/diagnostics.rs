@@ -0,0 +1,324 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Workflow diagnostics.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use serde::Serialize;
+use std::convert::Infallible;
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+mod printer;
+
+pub use printer::Printer;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Diagnostic severity, ordered from least to most severe.
+#[derive(
+    Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Warning, which doesn't prevent the build from succeeding.
+    Warning,
+    /// Error, which means that the output is incomplete.
+    Error,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Diagnostic.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, IntoPyObject, Serialize)]
+pub struct Diagnostic {
+    /// Diagnostic severity.
+    pub severity: Severity,
+    /// Diagnostic message.
+    pub message: String,
+    /// Source location, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+/// Source location of a diagnostic.
+///
+/// Locations point to the file that caused the diagnostic, e.g., a Markdown
+/// file or template, so the preview server can open it in the editor. Lines
+/// and columns start at 1, and are only given if they are known.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, IntoPyObject, Serialize)]
+pub struct Location {
+    /// Path of the source file.
+    pub path: String,
+    /// Line number, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Column number, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+/// Workflow diagnostics.
+///
+/// Diagnostics are collected, so they can be consumed after a build, e.g., to
+/// report the build status in the preview server. Emitted diagnostics are also
+/// queued for printing, which happens when they are flushed, so repeated ones
+/// can be grouped. Since tasks are executed concurrently, they are shared.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    /// Collected diagnostics.
+    inner: Arc<Mutex<Vec<Diagnostic>>>,
+    /// Diagnostics queued for printing.
+    pending: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Location {
+    /// Creates a location for the given path.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            path: path.as_ref().to_string_lossy().into_owned(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Sets the line number of the location.
+    #[must_use]
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the column number of the location.
+    #[must_use]
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Diagnostics {
+    /// Records a warning, and queues it for printing.
+    pub fn warning<S>(&self, message: S)
+    where
+        S: Into<String>,
+    {
+        self.emit(Severity::Warning, None, message.into());
+    }
+
+    /// Records an error, and queues it for printing.
+    pub fn error<S>(&self, message: S)
+    where
+        S: Into<String>,
+    {
+        self.emit(Severity::Error, None, message.into());
+    }
+
+    /// Records a warning at the given location, and queues it for printing.
+    pub fn warning_at<S>(&self, location: Location, message: S)
+    where
+        S: Into<String>,
+    {
+        self.emit(Severity::Warning, Some(location), message.into());
+    }
+
+    /// Records a diagnostic without printing it.
+    ///
+    /// This is used for errors that are returned from tasks, since they are
+    /// already printed by the scheduler.
+    pub fn record<S>(&self, severity: Severity, message: S)
+    where
+        S: Into<String>,
+    {
+        self.record_at(severity, None, message);
+    }
+
+    /// Records a diagnostic at the given location without printing it.
+    pub fn record_at<S>(
+        &self, severity: Severity, location: Option<Location>, message: S,
+    ) where
+        S: Into<String>,
+    {
+        let message = message.into();
+        let mut inner = self.inner.lock().expect("invariant");
+        inner.push(Diagnostic { severity, message, location });
+    }
+
+    /// Returns the number of recorded diagnostics with the given severity.
+    pub fn count(&self, severity: Severity) -> u64 {
+        let inner = self.inner.lock().expect("invariant");
+        let iter = inner.iter().filter(|item| item.severity == severity);
+        iter.count() as u64
+    }
+
+    /// Returns all diagnostics recorded after the given number of diagnostics.
+    pub fn since(&self, offset: usize) -> Vec<Diagnostic> {
+        let inner = self.inner.lock().expect("invariant");
+        inner.get(offset..).unwrap_or_default().to_vec()
+    }
+
+    /// Takes all recorded diagnostics, leaving no diagnostics behind.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        let mut inner = self.inner.lock().expect("invariant");
+        std::mem::take(&mut *inner)
+    }
+
+    /// Prints all queued diagnostics with the given printer.
+    pub fn flush(&self, printer: &Printer) {
+        let mut pending = self.pending.lock().expect("invariant");
+        printer.print(&std::mem::take(&mut *pending));
+    }
+
+    /// Records a diagnostic, and queues it for printing.
+    fn emit(
+        &self, severity: Severity, location: Option<Location>, message: String,
+    ) {
+        let mut pending = self.pending.lock().expect("invariant");
+        pending.push(Diagnostic {
+            severity,
+            message: message.clone(),
+            location: location.clone(),
+        });
+        drop(pending);
+        self.record_at(severity, location, message);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Severity {
+    /// Formats the severity for display.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Severity {
+    type Error = PyErr;
+
+    /// Extracts a severity from a Python object.
+    #[inline]
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        match obj.extract()? {
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            _ => Err(PyValueError::new_err("Invalid severity")),
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Severity {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    /// Converts the severity into its name, as used for display.
+    fn into_pyobject(
+        self, py: Python<'py>,
+    ) -> Result<Self::Output, Infallible> {
+        Ok(PyString::new(py, &self.to_string()))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, Diagnostics, Location, Printer, Severity};
+
+    #[test]
+    fn take_returns_and_clears_diagnostics() {
+        let diagnostics = Diagnostics::default();
+        diagnostics.warning("first");
+        diagnostics.clone().record(Severity::Error, "second");
+        assert_eq!(diagnostics.count(Severity::Error), 1);
+        assert_eq!(
+            diagnostics.take(),
+            vec![
+                Diagnostic {
+                    severity: Severity::Warning,
+                    message: String::from("first"),
+                    location: None,
+                },
+                Diagnostic {
+                    severity: Severity::Error,
+                    message: String::from("second"),
+                    location: None,
+                },
+            ]
+        );
+        assert!(diagnostics.take().is_empty());
+    }
+
+    #[test]
+    fn flush_clears_queued_diagnostics() {
+        let diagnostics = Diagnostics::default();
+        diagnostics.warning("first");
+        diagnostics.record(Severity::Error, "second");
+        assert_eq!(diagnostics.pending.lock().unwrap().len(), 1);
+        diagnostics.flush(&Printer::new(Severity::Error));
+        assert!(diagnostics.pending.lock().unwrap().is_empty());
+        assert_eq!(diagnostics.take().len(), 2);
+    }
+
+    #[test]
+    fn records_location() {
+        let diagnostics = Diagnostics::default();
+        let location = Location::new("docs/index.md").line(3).column(7);
+        diagnostics.warning_at(location.clone(), "warning");
+        let diagnostic = diagnostics.take().remove(0);
+        assert_eq!(diagnostic.location, Some(location));
+        assert_eq!(
+            serde_json::to_string(&diagnostic).unwrap(),
+            concat!(
+                r#"{"severity":"warning","message":"warning","#,
+                r#""location":{"path":"docs/index.md","line":3,"column":7}}"#,
+            )
+        );
+    }
+}
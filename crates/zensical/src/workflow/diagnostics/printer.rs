@@ -0,0 +1,245 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Diagnostic printer.
+
+use ahash::HashMap;
+use std::env;
+use std::io::{self, IsTerminal};
+
+use super::{Diagnostic, Location, Severity};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Diagnostic printer.
+///
+/// The printer omits diagnostics below the minimum severity, and groups
+/// repeated diagnostics, so that a warning emitted for every page is printed
+/// once with a count. In machine-readable mode, diagnostics are printed as
+/// JSON, one per line, and not grouped, so consumers see every occurrence.
+#[derive(Clone, Debug)]
+pub struct Printer {
+    /// Minimum severity of printed diagnostics.
+    level: Severity,
+    /// Whether to colorize severity labels.
+    color: bool,
+    /// Whether to print diagnostics as JSON.
+    json: bool,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Printer {
+    /// Creates a printer with the given minimum severity.
+    ///
+    /// Severity labels are colorized if standard error is a terminal, unless
+    /// the `NO_COLOR` environment variable is set.
+    pub fn new(level: Severity) -> Self {
+        let color =
+            io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+        Self { level, color, json: false }
+    }
+
+    /// Sets whether to colorize severity labels.
+    #[must_use]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets whether to print diagnostics as JSON.
+    #[must_use]
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Prints the given diagnostics to standard error.
+    pub fn print(&self, diagnostics: &[Diagnostic]) {
+        for line in self.format(diagnostics) {
+            eprintln!("{line}");
+        }
+    }
+
+    /// Formats the given diagnostics, returning one line per diagnostic.
+    ///
+    /// Diagnostics are grouped by severity, message and location, retaining
+    /// the order in which they were first emitted, unless printing as JSON.
+    pub fn format(&self, diagnostics: &[Diagnostic]) -> Vec<String> {
+        let iter = diagnostics
+            .iter()
+            .filter(|item| item.severity >= self.level);
+        if self.json {
+            return iter
+                .map(|item| serde_json::to_string(item).expect("invariant"))
+                .collect();
+        }
+
+        // Group repeated diagnostics, retaining the position of the first one
+        let mut index = HashMap::default();
+        let mut groups: Vec<(&Diagnostic, usize)> = Vec::new();
+        for diagnostic in iter {
+            match index.get(diagnostic) {
+                Some(&n) => groups[n].1 += 1,
+                None => {
+                    index.insert(diagnostic, groups.len());
+                    groups.push((diagnostic, 1));
+                }
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(diagnostic, count)| self.format_group(diagnostic, count))
+            .collect()
+    }
+
+    /// Formats a diagnostic, which occurred the given number of times.
+    fn format_group(&self, diagnostic: &Diagnostic, count: usize) -> String {
+        let label = match (self.color, diagnostic.severity) {
+            (true, Severity::Warning) => "\x1b[33mwarning\x1b[0m".to_string(),
+            (true, Severity::Error) => "\x1b[31merror\x1b[0m".to_string(),
+            (false, severity) => severity.to_string(),
+        };
+        let mut line = format!("[{label}] ");
+        if let Some(location) = &diagnostic.location {
+            line.push_str(&format_location(location));
+            line.push_str(": ");
+        }
+        line.push_str(&diagnostic.message);
+        if count > 1 {
+            line.push_str(&format!(" ({count} times)"));
+        }
+        line
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Formats a location as `path:line:column`, omitting unknown parts.
+fn format_location(location: &Location) -> String {
+    let mut value = location.path.clone();
+    if let Some(line) = location.line {
+        value.push_str(&format!(":{line}"));
+        if let Some(column) = location.column {
+            value.push_str(&format!(":{column}"));
+        }
+    }
+    value
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Diagnostic, Location, Severity};
+    use super::Printer;
+
+    fn diagnostics() -> Vec<Diagnostic> {
+        let warning = |message: &str, location: Option<Location>| Diagnostic {
+            severity: Severity::Warning,
+            message: String::from(message),
+            location,
+        };
+        vec![
+            warning("Unresolved reference", None),
+            Diagnostic {
+                severity: Severity::Error,
+                message: String::from("Template not found"),
+                location: None,
+            },
+            warning("Unresolved reference", None),
+            warning(
+                "Invalid link",
+                Some(Location::new("docs/index.md").line(3).column(7)),
+            ),
+            warning("Unresolved reference", None),
+        ]
+    }
+
+    #[test]
+    fn groups_repeated_diagnostics() {
+        let printer = Printer::new(Severity::Warning).with_color(false);
+        assert_eq!(
+            printer.format(&diagnostics()),
+            vec![
+                "[warning] Unresolved reference (3 times)",
+                "[error] Template not found",
+                "[warning] docs/index.md:3:7: Invalid link",
+            ]
+        );
+    }
+
+    #[test]
+    fn filters_by_minimum_severity() {
+        let printer = Printer::new(Severity::Error).with_color(false);
+        assert_eq!(
+            printer.format(&diagnostics()),
+            vec!["[error] Template not found"]
+        );
+    }
+
+    #[test]
+    fn colorizes_severity_labels() {
+        let printer = Printer::new(Severity::Error).with_color(true);
+        assert_eq!(
+            printer.format(&diagnostics()),
+            vec!["[\x1b[31merror\x1b[0m] Template not found"]
+        );
+    }
+
+    #[test]
+    fn json_bypasses_grouping() {
+        let printer = Printer::new(Severity::Warning).with_json(true);
+        let lines = printer.format(&diagnostics());
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            r#"{"severity":"warning","message":"Unresolved reference"}"#
+        );
+        assert_eq!(lines[2], lines[0]);
+    }
+
+    #[test]
+    fn formats_partial_locations() {
+        let printer = Printer::new(Severity::Warning).with_color(false);
+        let diagnostic = Diagnostic {
+            severity: Severity::Warning,
+            message: String::from("Missing title"),
+            location: Some(Location::new("docs/about.md")),
+        };
+        assert_eq!(
+            printer.format(&[diagnostic]),
+            vec!["[warning] docs/about.md: Missing title"]
+        );
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Hidden files and directories.
+
+use std::path::Path;
+use std::sync::Arc;
+use zrx::id::Id;
+
+use crate::config::Config;
+
+use super::Selector;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Well-known hidden files, which are always copied to the site directory.
+///
+/// `.nojekyll` is needed for GitHub Pages, as otherwise, Jekyll would skip
+/// all files and directories starting with an underscore, and `.htaccess`
+/// configures Apache, e.g., to set up redirects or access control.
+const WELL_KNOWN: [&str; 2] = [".nojekyll", ".htaccess"];
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Hidden files and directories that are included in the build.
+///
+/// Files and directories starting with a dot are hidden, and skipped when
+/// copying assets from the docs directory, except for well-known hidden files,
+/// as well as those matching the patterns in `build.include_hidden`. Patterns
+/// are relative to the docs directory, e.g., `**/.well-known/**`.
+#[derive(Clone, Default)]
+pub struct Hidden {
+    /// Included patterns and matcher, if any.
+    inner: Option<Arc<Inner>>,
+}
+
+/// Included patterns and selectors.
+struct Inner {
+    /// Glob patterns, relative to the docs directory.
+    patterns: Vec<String>,
+    /// Selectors for identifiers matching any of the patterns.
+    selectors: Vec<Selector>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Hidden {
+    /// Creates the set of included hidden files from the given configuration.
+    #[inline]
+    pub fn new(config: &Config) -> Self {
+        let build = &config.project.build;
+        Self::from_patterns(&config.project.docs_dir, &build.include_hidden)
+    }
+
+    /// Creates the set of included hidden files for the given patterns.
+    ///
+    /// Invalid patterns are removed when the configuration is loaded, so all
+    /// patterns are guaranteed to be valid at this point.
+    pub fn from_patterns(docs_dir: &str, patterns: &[String]) -> Self {
+        if patterns.is_empty() {
+            return Self::default();
+        }
+
+        // Build selectors for all patterns
+        let selectors = patterns
+            .iter()
+            .map(|pattern| {
+                let selector = Selector::glob(pattern).expect("invariant");
+                selector.context(docs_dir)
+            })
+            .collect();
+        Self {
+            inner: Some(Arc::new(Inner {
+                patterns: patterns.to_vec(),
+                selectors,
+            })),
+        }
+    }
+
+    /// Returns whether the file with the given identifier is included.
+    ///
+    /// Files that are neither hidden nor inside a hidden directory are always
+    /// included, as are well-known hidden files outside of hidden directories.
+    pub fn includes(&self, id: &Id) -> bool {
+        let location = id.location();
+        let mut components = location.split('/').rev();
+        let name = components.next().unwrap_or_default();
+        if !components.any(is_hidden)
+            && (!is_hidden(name) || WELL_KNOWN.contains(&name))
+        {
+            return true;
+        }
+
+        // Otherwise, the file must match one of the included patterns
+        self.inner.as_ref().is_some_and(|inner| {
+            inner.selectors.iter().any(|selector| selector.is_match(id))
+        })
+    }
+
+    /// Returns whether the hidden directory at the given path must be walked.
+    ///
+    /// Since patterns can't be matched partially, a hidden directory must be
+    /// walked if its name is a literal component of any of the patterns, e.g.,
+    /// `.well-known` for `**/.well-known/**`, so the files inside of it can
+    /// be matched. Everything else, like `.git`, is skipped.
+    pub fn admits_dir(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return false;
+        };
+        self.inner.as_ref().is_some_and(|inner| {
+            inner.patterns.iter().any(|pattern| {
+                pattern.split('/').any(|component| component == name)
+            })
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns whether the given path component is hidden.
+#[inline]
+fn is_hidden(component: &str) -> bool {
+    component.starts_with('.')
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use zrx::id::Id;
+
+    use super::Hidden;
+
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn includes_well_known_files() {
+        let hidden = Hidden::default();
+        assert!(hidden.includes(&id("index.html")));
+        assert!(hidden.includes(&id(".nojekyll")));
+        assert!(hidden.includes(&id("blog/.htaccess")));
+        assert!(!hidden.includes(&id(".DS_Store")));
+        assert!(!hidden.includes(&id(".git/config")));
+        assert!(!hidden.includes(&id(".git/.htaccess")));
+    }
+
+    #[test]
+    fn includes_files_matching_patterns() {
+        let patterns = [String::from("**/.well-known/**")];
+        let hidden = Hidden::from_patterns("docs", &patterns);
+        assert!(hidden.includes(&id(".well-known/security.txt")));
+        assert!(hidden.includes(&id("en/.well-known/security.txt")));
+        assert!(!hidden.includes(&id(".git/config")));
+    }
+
+    #[test]
+    fn admits_directories_named_in_patterns() {
+        let patterns = [String::from("**/.well-known/**")];
+        let hidden = Hidden::from_patterns("docs", &patterns);
+        assert!(hidden.admits_dir(Path::new("/project/docs/.well-known")));
+        assert!(!hidden.admits_dir(Path::new("/project/docs/.git")));
+        assert!(!Hidden::default().admits_dir(Path::new("docs/.well-known")));
+    }
+}
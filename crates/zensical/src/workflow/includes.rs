@@ -0,0 +1,156 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Include graph.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use zrx::id::Id;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Include graph.
+///
+/// Pages can include other files via snippets, which the watcher doesn't know
+/// about, so editing an included file would not rebuild the pages including
+/// it. The include graph records the files each page includes, directly or
+/// transitively, so the watcher can submit all dependent pages again when an
+/// included file changes.
+#[derive(Clone, Debug, Default)]
+pub struct Includes {
+    /// Pages by identifier.
+    inner: Arc<Mutex<BTreeMap<String, Dependent>>>,
+}
+
+/// Page including files.
+#[derive(Debug)]
+struct Dependent {
+    /// Page identifier.
+    id: Id,
+    /// Page source.
+    source: String,
+    /// Included files.
+    files: BTreeSet<PathBuf>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Includes {
+    /// Records the files included by the given page.
+    ///
+    /// Previously recorded files are replaced, so includes that were removed
+    /// from the page don't trigger rebuilds anymore.
+    pub fn insert(&self, id: &Id, source: &str, files: BTreeSet<PathBuf>) {
+        let mut inner = self.inner.lock().expect("invariant");
+        if files.is_empty() {
+            inner.remove(id.as_str());
+        } else {
+            let id = id.clone();
+            let source = source.to_string();
+            inner.insert(
+                id.as_str().to_string(),
+                Dependent { id, source, files },
+            );
+        }
+    }
+
+    /// Returns all included files.
+    pub fn files(&self) -> BTreeSet<PathBuf> {
+        let inner = self.inner.lock().expect("invariant");
+        inner
+            .values()
+            .flat_map(|dependent| dependent.files.iter().cloned())
+            .collect()
+    }
+
+    /// Returns identifiers and sources of all pages including the given file.
+    pub fn dependents(&self, path: impl AsRef<Path>) -> Vec<(Id, String)> {
+        let path = path.as_ref();
+        let inner = self.inner.lock().expect("invariant");
+        inner
+            .values()
+            .filter(|dependent| dependent.files.contains(path))
+            .map(|dependent| (dependent.id.clone(), dependent.source.clone()))
+            .collect()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+    use zrx::id::id;
+
+    use crate::structure::markdown::Snippets;
+
+    use super::Includes;
+
+    #[test]
+    fn dependents_include_transitive_pages() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        fs::write(base.join("a.md"), "--8<-- \"b.md\"\n").unwrap();
+        fs::write(base.join("b.md"), "Nested\n").unwrap();
+
+        // Record includes of page, which includes a.md, which includes b.md
+        let id =
+            id!(provider = "file", context = "docs", location = "index.md")
+                .unwrap();
+        let snippets = Snippets::resolve(&[base.clone()], "--8<-- \"a.md\"");
+        let includes = Includes::default();
+        includes.insert(&id, "docs/index.md", snippets.files);
+
+        // Changing the nested file invalidates the page
+        let dependents = includes.dependents(base.join("b.md"));
+        assert_eq!(dependents, vec![(id.clone(), "docs/index.md".into())]);
+        assert!(includes.dependents(base.join("c.md")).is_empty());
+        let files = [base.join("a.md"), base.join("b.md")];
+        assert_eq!(includes.files(), files.into());
+    }
+
+    #[test]
+    fn insert_replaces_previous_includes() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        let id =
+            id!(provider = "file", context = "docs", location = "index.md")
+                .unwrap();
+
+        // Removing all includes removes the page from the graph
+        let includes = Includes::default();
+        includes.insert(&id, "docs/index.md", [base.join("a.md")].into());
+        includes.insert(&id, "docs/index.md", [].into());
+        assert!(includes.dependents(base.join("a.md")).is_empty());
+    }
+}
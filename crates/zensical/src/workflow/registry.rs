@@ -0,0 +1,429 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Output registry.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::config::build::Encoding;
+
+use super::sink::OutputSink;
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Output registry.
+///
+/// Multiple source files can map to the same output path, e.g., `a.md` and
+/// `a/index.md`, or `README.md` and `index.md` in the same folder. The registry
+/// records the source files for each output path during page generation, so
+/// collisions can be detected and resolved deterministically when rendering,
+/// regardless of the order in which pages were processed.
+///
+/// The winner of a collision is determined by sorting all claimants, where
+/// `index.md` takes precedence over `README.md`, just like in MkDocs, and all
+/// other source files are ordered by their path. Fallback pages for missing
+/// translations (i18n) are recorded as well, so they are pruned together with
+/// the page they are rendered from, but never shadow any other page.
+#[derive(Clone, Debug)]
+pub struct Registry {
+    /// Source files by output path.
+    inner: Arc<Mutex<BTreeMap<String, BTreeSet<Claim>>>>,
+    /// Whether output paths are compared case-insensitively.
+    case_insensitive: bool,
+}
+
+/// Output path claim.
+///
+/// Claims are ordered by their source file path as a string, and not by its
+/// components, so `a.md` takes precedence over `a/index.md`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Claim {
+    /// Whether the output is a fallback page rendered from the source.
+    is_fallback: bool,
+    /// Whether the source is a `README.md` file.
+    is_readme: bool,
+    /// Source file path.
+    path: PathBuf,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Registry {
+    /// Creates an output registry.
+    pub fn new(case_insensitive: bool) -> Self {
+        Self {
+            inner: Arc::default(),
+            case_insensitive,
+        }
+    }
+
+    /// Records that the given source file maps to the given output path.
+    pub fn insert(&self, source: impl AsRef<Path>, output: &str) {
+        self.claim(source.as_ref(), output, false);
+    }
+
+    /// Records that a fallback page is rendered from the given source file to
+    /// the given output path, which is pruned once the source file is removed.
+    pub fn insert_fallback(&self, source: impl AsRef<Path>, output: &str) {
+        self.claim(source.as_ref(), output, true);
+    }
+
+    /// Returns the source file that shadows the given source file, if any.
+    ///
+    /// Claims of source files that don't exist anymore are pruned first, which
+    /// is necessary in serve mode, as files might have been removed or renamed
+    /// since they were first recorded.
+    pub fn shadowed_by(
+        &self, source: impl AsRef<Path>, output: &str,
+    ) -> Option<PathBuf> {
+        let source = source.as_ref();
+        let mut inner = self.inner.lock().expect("invariant");
+        let claims = inner.get_mut(&self.key(output))?;
+        claims.retain(|claim| claim.path == source || claim.path.exists());
+
+        // Return the winning claim, if it's not the given source file
+        claims
+            .first()
+            .filter(|claim| claim.path != source)
+            .map(|claim| claim.path.clone())
+    }
+
+    /// Returns the other source files claiming outputs of the given source.
+    ///
+    /// This is necessary in serve mode, as pages shadowed by the given source
+    /// file must be rendered again once it's removed, or its output would be
+    /// kept, and the shadowed pages would be missing until the next build.
+    pub fn rivals(&self, source: impl AsRef<Path>) -> Vec<PathBuf> {
+        let source = source.as_ref();
+        let inner = self.inner.lock().expect("invariant");
+        let rivals = inner
+            .values()
+            .filter(|claims| claims.iter().any(|claim| claim.path == source))
+            .flatten()
+            .filter(|claim| claim.path != source && !claim.is_fallback)
+            .map(|claim| claim.path.clone())
+            .collect::<BTreeSet<_>>();
+
+        // Return rivals in deterministic order
+        rivals.into_iter().collect()
+    }
+
+    /// Removes the given source file, and prunes its outputs.
+    ///
+    /// This is necessary in serve mode, as outputs of removed or renamed pages
+    /// would otherwise be left in the site directory until the next full build.
+    /// Outputs that are still claimed by other source files are kept. Besides
+    /// the output, precompressed copies are pruned, all through the given sink,
+    /// which also removes directories that are empty after removal. Returns
+    /// the output paths that were pruned.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if an output could not be removed.
+    pub fn prune(
+        &self, source: impl AsRef<Path>, site_dir: &Path, sink: &dyn OutputSink,
+    ) -> io::Result<Vec<String>> {
+        let source = source.as_ref();
+        let mut outputs = Vec::new();
+        {
+            let mut inner = self.inner.lock().expect("invariant");
+            inner.retain(|output, claims| {
+                let len = claims.len();
+                claims.retain(|claim| claim.path != source);
+                if claims.is_empty() && claims.len() < len {
+                    outputs.push(output.clone());
+                }
+                !claims.is_empty()
+            });
+        }
+
+        // Remove precompressed copies first, and then outputs, so directories
+        // are only empty and removed after the last file in them is removed
+        for output in &outputs {
+            let path = site_dir.join(output);
+            for encoding in [Encoding::Gzip, Encoding::Brotli] {
+                let mut file = path.clone().into_os_string();
+                file.push(".");
+                file.push(encoding.extension());
+                sink.remove(Path::new(&file))?;
+            }
+            sink.remove(&path)?;
+        }
+        Ok(outputs)
+    }
+
+    /// Records a claim of the given source file for the given output path.
+    fn claim(&self, source: &Path, output: &str, is_fallback: bool) {
+        let claim = Claim {
+            is_fallback,
+            is_readme: source.ends_with("README.md"),
+            path: source.to_path_buf(),
+        };
+
+        // Add claim to the set of claims for the output path
+        let mut inner = self.inner.lock().expect("invariant");
+        inner.entry(self.key(output)).or_default().insert(claim);
+    }
+
+    /// Returns the key for the given output path.
+    fn key(&self, output: &str) -> String {
+        if self.case_insensitive {
+            output.to_lowercase()
+        } else {
+            output.to_string()
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl Default for Registry {
+    /// Creates an output registry for the current platform.
+    ///
+    /// Windows and macOS use case-insensitive file systems by default, which
+    /// is why output paths only differing in case collide on those platforms.
+    #[inline]
+    fn default() -> Self {
+        Self::new(cfg!(any(windows, target_os = "macos")))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Ord for Claim {
+    /// Orders claims by whether they are fallbacks or `README.md` files, and
+    /// by path, so pages always take precedence over fallback pages.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.is_fallback, self.is_readme, self.path.as_os_str()).cmp(&(
+            other.is_fallback,
+            other.is_readme,
+            other.path.as_os_str(),
+        ))
+    }
+}
+
+impl PartialOrd for Claim {
+    /// Orders claims by whether they are fallbacks or `README.md` files, and
+    /// by path, so pages always take precedence over fallback pages.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    use super::super::sink::Filesystem;
+    use super::Registry;
+
+    /// Creates the given files in the given directory.
+    fn touch(dir: &Path, files: &[&str]) {
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+    }
+
+    #[test]
+    fn index_shadows_readme() {
+        let dir = tempdir().unwrap();
+        touch(dir.path(), &["README.md", "index.md"]);
+
+        // Insert in both orders, which must yield the same result
+        for files in [["README.md", "index.md"], ["index.md", "README.md"]] {
+            let registry = Registry::new(false);
+            for file in files {
+                registry.insert(dir.path().join(file), "index.html");
+            }
+            let readme = dir.path().join("README.md");
+            let index = dir.path().join("index.md");
+            assert_eq!(
+                registry.shadowed_by(&readme, "index.html"),
+                Some(index.clone())
+            );
+            assert_eq!(registry.shadowed_by(&index, "index.html"), None);
+        }
+    }
+
+    #[test]
+    fn file_shadows_nested_index() {
+        let dir = tempdir().unwrap();
+        touch(dir.path(), &["a.md", "a/index.md"]);
+
+        let registry = Registry::new(false);
+        registry.insert(dir.path().join("a/index.md"), "a/index.html");
+        registry.insert(dir.path().join("a.md"), "a/index.html");
+        let file = dir.path().join("a.md");
+        let nested = dir.path().join("a/index.md");
+        assert_eq!(registry.shadowed_by(&file, "a/index.html"), None);
+        assert_eq!(registry.shadowed_by(&nested, "a/index.html"), Some(file));
+    }
+
+    #[test]
+    fn prune_removes_outputs_and_empty_dirs() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let sink = Filesystem::new(&site_dir);
+        touch(
+            &site_dir,
+            &[
+                "old/index.html",
+                "old/index.html.gz",
+                "a/index.html",
+                "a/b/index.html",
+            ],
+        );
+
+        // Remove a page with an output only claimed by itself
+        let registry = Registry::new(false);
+        registry.insert(dir.path().join("old.md"), "old/index.html");
+        let pruned =
+            registry.prune(dir.path().join("old.md"), &site_dir, &sink);
+        assert_eq!(pruned.unwrap(), vec![String::from("old/index.html")]);
+        assert!(!site_dir.join("old").exists());
+        assert!(site_dir.exists());
+
+        // Keep directories that are not empty after removal
+        registry.insert(dir.path().join("a.md"), "a/index.html");
+        registry
+            .prune(dir.path().join("a.md"), &site_dir, &sink)
+            .unwrap();
+        assert!(!site_dir.join("a/index.html").exists());
+        assert!(site_dir.join("a/b/index.html").exists());
+    }
+
+    #[test]
+    fn prune_keeps_outputs_claimed_by_others() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let sink = Filesystem::new(&site_dir);
+        touch(&site_dir, &["index.html"]);
+
+        let registry = Registry::new(false);
+        registry.insert(dir.path().join("README.md"), "index.html");
+        registry.insert(dir.path().join("index.md"), "index.html");
+        let pruned =
+            registry.prune(dir.path().join("index.md"), &site_dir, &sink);
+        assert!(pruned.unwrap().is_empty());
+        assert!(site_dir.join("index.html").exists());
+    }
+
+    #[test]
+    fn case_insensitive_collisions() {
+        let dir = tempdir().unwrap();
+        touch(dir.path(), &["Guide.md", "guide.md"]);
+        let upper = dir.path().join("Guide.md");
+        let lower = dir.path().join("guide.md");
+
+        // Case-sensitive file systems keep both pages
+        let registry = Registry::new(false);
+        registry.insert(&upper, "Guide/index.html");
+        registry.insert(&lower, "guide/index.html");
+        assert_eq!(registry.shadowed_by(&lower, "guide/index.html"), None);
+
+        // Case-insensitive file systems must detect the collision
+        let registry = Registry::new(true);
+        registry.insert(&upper, "Guide/index.html");
+        registry.insert(&lower, "guide/index.html");
+        assert_eq!(
+            registry.shadowed_by(&lower, "guide/index.html"),
+            Some(upper)
+        );
+    }
+
+    #[test]
+    fn rivals_are_sources_claiming_same_outputs() {
+        let dir = tempdir().unwrap();
+        let registry = Registry::new(false);
+        let readme = dir.path().join("README.md");
+        let index = dir.path().join("index.md");
+        let other = dir.path().join("other.md");
+        registry.insert(&readme, "index.html");
+        registry.insert(&index, "index.html");
+        registry.insert(&other, "other/index.html");
+        assert_eq!(registry.rivals(&index), vec![readme]);
+        assert!(registry.rivals(&other).is_empty());
+    }
+
+    #[test]
+    fn fallbacks_are_pruned_with_source_but_never_shadow() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let sink = Filesystem::new(&site_dir);
+        touch(dir.path(), &["en/guide.md", "fr/guide.md"]);
+        touch(&site_dir, &["de/guide/index.html", "fr/guide/index.html"]);
+
+        // Fallback pages never shadow translated pages
+        let registry = Registry::new(false);
+        let en = dir.path().join("en/guide.md");
+        let fr = dir.path().join("fr/guide.md");
+        registry.insert_fallback(&en, "de/guide/index.html");
+        registry.insert_fallback(&en, "fr/guide/index.html");
+        registry.insert(&fr, "fr/guide/index.html");
+        assert_eq!(registry.shadowed_by(&fr, "fr/guide/index.html"), None);
+        assert!(registry.rivals(&fr).is_empty());
+
+        // Fallback pages are pruned with their source, unless translated
+        let pruned = registry.prune(&en, &site_dir, &sink).unwrap();
+        assert_eq!(pruned, vec![String::from("de/guide/index.html")]);
+        assert!(!site_dir.join("de").exists());
+        assert!(site_dir.join("fr/guide/index.html").exists());
+    }
+
+    #[test]
+    fn removed_sources_are_pruned() {
+        let dir = tempdir().unwrap();
+        touch(dir.path(), &["README.md", "index.md"]);
+
+        let registry = Registry::new(false);
+        let readme = dir.path().join("README.md");
+        let index = dir.path().join("index.md");
+        registry.insert(&readme, "index.html");
+        registry.insert(&index, "index.html");
+
+        // Remove index file, so README.md is not shadowed anymore
+        fs::remove_file(&index).unwrap();
+        assert_eq!(registry.shadowed_by(&readme, "index.html"), None);
+    }
+}
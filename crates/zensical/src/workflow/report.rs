@@ -0,0 +1,871 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Workflow report.
+
+use ahash::HashMap;
+use pyo3::IntoPyObject;
+use std::cmp::Reverse;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::diagnostics::Diagnostic;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// Number of slowest pages included in the summary.
+const SLOWEST_PAGES: usize = 5;
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Workflow stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Markdown rendering.
+    Markdown,
+    /// Page generation.
+    Page,
+    /// Navigation generation.
+    Nav,
+    /// Search index generation.
+    Search,
+    /// Template rendering.
+    Templates,
+    /// Page rendering.
+    Render,
+    /// Asset copying.
+    Assets,
+}
+
+/// Workflow counter.
+///
+/// Counters complement the item counts of stages with numbers that are not
+/// tied to the number of processed items, e.g., cache hits or sizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    /// Pages served from the cache.
+    CachedPages,
+    /// Assets skipped, since they are shadowed or up to date.
+    SkippedAssets,
+    /// Size of search indexes in bytes.
+    SearchIndexBytes,
+    /// Precompressed copies of text outputs.
+    PrecompressedFiles,
+    /// Bytes saved by precompressed copies of text outputs.
+    PrecompressedBytesSaved,
+    /// Social cards written.
+    SocialCards,
+    /// Social cards served from the cache.
+    CachedSocialCards,
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Workflow report.
+///
+/// The report aggregates cumulative timings and item counts for each stage of
+/// the workflow. Since tasks are executed concurrently by the scheduler, all
+/// counters are atomic, so the report can be cheaply cloned and shared across
+/// all stages. Note that durations are summed up over all items of a stage,
+/// which means that they can exceed the wall-clock time of the build. Markdown
+/// conversion and page rendering are additionally tracked per page, to find
+/// the pages that slow down the build.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Timings, one per stage.
+    inner: Arc<[Timing; Stage::COUNT]>,
+    /// Counters.
+    counters: Arc<[AtomicU64; Counter::COUNT]>,
+    /// Timings by page.
+    pages: Arc<Mutex<HashMap<String, PageTimes>>>,
+    /// Threshold above which pages are reported as slow.
+    threshold: Option<Duration>,
+}
+
+/// Build summary.
+///
+/// The summary is printed at the end of a build, and returned to Python as a
+/// dictionary, so wrappers can assert on it, e.g., in CI. Pages include pages
+/// served from the cache, and assets include skipped assets.
+#[derive(Clone, Debug, Default, PartialEq, Eq, IntoPyObject)]
+pub struct Summary {
+    /// Number of rendered pages.
+    pub pages: u64,
+    /// Number of pages served from the cache.
+    pub cached_pages: u64,
+    /// Number of rendered templates.
+    pub templates: u64,
+    /// Number of copied assets.
+    pub assets_copied: u64,
+    /// Number of skipped assets.
+    pub assets_skipped: u64,
+    /// Size of search indexes in bytes.
+    pub search_index_bytes: u64,
+    /// Number of precompressed copies of text outputs.
+    pub precompressed_files: u64,
+    /// Bytes saved by precompressed copies of text outputs.
+    pub precompressed_bytes_saved: u64,
+    /// Number of written social cards.
+    pub social_cards: u64,
+    /// Number of social cards served from the cache.
+    pub cached_social_cards: u64,
+    /// Size of all files in the site directory in bytes.
+    pub output_bytes: u64,
+    /// Number of warnings.
+    pub warnings: u64,
+    /// Number of errors.
+    pub errors: u64,
+    /// Slowest pages, excluding pages served from the cache.
+    pub slowest_pages: Vec<PageTiming>,
+    /// Notes on settings that were adjusted for the build.
+    pub notes: Vec<String>,
+    /// Diagnostics of the build.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Site directory, as configured.
+    pub site_dir: String,
+    /// Summaries of all sites, if the project builds multiple sites.
+    pub sites: Vec<Summary>,
+}
+
+/// Page timing.
+///
+/// Durations are given in milliseconds. Stages that were served from the cache
+/// are reported as zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq, IntoPyObject)]
+pub struct PageTiming {
+    /// Path of the page, relative to the project root.
+    pub page: String,
+    /// Time spent on Markdown conversion.
+    pub markdown_ms: u64,
+    /// Time spent on page rendering.
+    pub render_ms: u64,
+}
+
+/// Timer for a single item of a stage.
+///
+/// The elapsed time is recorded once the timer is dropped, so items are also
+/// recorded when processing fails early, e.g., due to propagated errors.
+#[derive(Debug)]
+pub struct Timer {
+    /// Report to record into.
+    report: Report,
+    /// Stage of the item.
+    stage: Stage,
+    /// Start time.
+    time: Instant,
+}
+
+/// Size in bytes, formatted for display.
+struct Bytes(u64);
+
+/// Workflow stage timing.
+#[derive(Debug, Default)]
+struct Timing {
+    /// Number of processed items.
+    count: AtomicU64,
+    /// Cumulative duration in nanoseconds.
+    nanos: AtomicU64,
+}
+
+/// Page stage timings, which are unset for stages served from the cache.
+#[derive(Clone, Copy, Debug, Default)]
+struct PageTimes {
+    /// Time spent on Markdown conversion.
+    markdown: Option<Duration>,
+    /// Time spent on page rendering.
+    render: Option<Duration>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Stage {
+    /// Number of stages.
+    const COUNT: usize = Stage::ALL.len();
+
+    /// All stages in order of display, which is the order of declaration.
+    const ALL: &'static [Stage] = &[
+        Stage::Markdown,
+        Stage::Page,
+        Stage::Nav,
+        Stage::Search,
+        Stage::Templates,
+        Stage::Render,
+        Stage::Assets,
+    ];
+
+    /// Returns the name of the stage.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Markdown => "markdown",
+            Stage::Page => "page",
+            Stage::Nav => "nav",
+            Stage::Search => "search",
+            Stage::Templates => "templates",
+            Stage::Render => "render",
+            Stage::Assets => "assets",
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Counter {
+    /// Number of counters.
+    const COUNT: usize = Counter::ALL.len();
+
+    /// All counters in order of declaration.
+    const ALL: &'static [Counter] = &[
+        Counter::CachedPages,
+        Counter::SkippedAssets,
+        Counter::SearchIndexBytes,
+        Counter::PrecompressedFiles,
+        Counter::PrecompressedBytesSaved,
+        Counter::SocialCards,
+        Counter::CachedSocialCards,
+    ];
+}
+
+// ----------------------------------------------------------------------------
+
+impl Report {
+    /// Sets the threshold above which pages are reported as slow.
+    ///
+    /// This is used in serve mode, where no summary is printed after rebuilds,
+    /// so pages taking longer than the threshold are reported once rendered.
+    #[must_use]
+    pub fn with_slow_page_threshold(mut self, threshold: Duration) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Measures the execution of the given function for the given stage.
+    pub fn measure<F, T>(&self, stage: Stage, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        let time = Instant::now();
+        let value = f();
+        self.record(stage, time.elapsed());
+        value
+    }
+
+    /// Starts a timer for a single item of the given stage.
+    #[must_use]
+    pub fn timer(&self, stage: Stage) -> Timer {
+        Timer {
+            report: self.clone(),
+            stage,
+            time: Instant::now(),
+        }
+    }
+
+    /// Records a single item with the given duration for the given stage.
+    pub fn record(&self, stage: Stage, duration: Duration) {
+        let timing = &self.inner[stage as usize];
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        timing.count.fetch_add(1, Ordering::Relaxed);
+        timing.nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// Records the duration of the given stage for the given page.
+    ///
+    /// Only Markdown conversion and page rendering are tracked per page, other
+    /// stages are ignored. If the stage was served from the cache, no duration
+    /// should be given, so the page is excluded from the slowest pages, unless
+    /// the other stage was computed. Recording a stage again replaces it, e.g.,
+    /// when a page is rebuilt in serve mode.
+    pub fn record_page(
+        &self, stage: Stage, page: &str, duration: Option<Duration>,
+    ) {
+        let mut pages = self.pages.lock().expect("invariant");
+        let times = pages.entry(page.to_string()).or_default();
+        match stage {
+            Stage::Markdown => times.markdown = duration,
+            Stage::Render => times.render = duration,
+            _ => return,
+        }
+
+        // Rendering is the last stage, so we know the total time of the page
+        // once it's rendered, and can report it if it exceeds the threshold
+        let times = *times;
+        drop(pages);
+        if stage == Stage::Render && self.is_slow(times) {
+            println!(
+                "Slow page '{page}' took {:.2}s (markdown {:.2}s, render \
+                 {:.2}s)",
+                times.total().as_secs_f32(),
+                times.markdown.unwrap_or_default().as_secs_f32(),
+                times.render.unwrap_or_default().as_secs_f32(),
+            );
+        }
+    }
+
+    /// Returns the given number of slowest pages, slowest first.
+    ///
+    /// Pages of which all stages were served from the cache are excluded.
+    #[must_use]
+    pub fn slowest_pages(&self, limit: usize) -> Vec<PageTiming> {
+        let pages = self.pages.lock().expect("invariant");
+        let mut timings = pages
+            .iter()
+            .filter(|(_, times)| times.is_computed())
+            .collect::<Vec<_>>();
+
+        // Sort by total time, and by path for pages that took equally long
+        timings.sort_by_key(|(page, times)| (Reverse(times.total()), *page));
+        timings
+            .into_iter()
+            .take(limit)
+            .map(|(page, times)| PageTiming {
+                page: page.clone(),
+                markdown_ms: millis(times.markdown.unwrap_or_default()),
+                render_ms: millis(times.render.unwrap_or_default()),
+            })
+            .collect()
+    }
+
+    /// Returns whether the given page timings exceed the threshold.
+    fn is_slow(&self, times: PageTimes) -> bool {
+        self.threshold
+            .is_some_and(|threshold| times.total() > threshold)
+    }
+
+    /// Returns the number of items processed for the given stage.
+    #[must_use]
+    pub fn count(&self, stage: Stage) -> u64 {
+        self.inner[stage as usize].count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the cumulative duration for the given stage.
+    #[must_use]
+    pub fn duration(&self, stage: Stage) -> Duration {
+        let nanos = self.inner[stage as usize].nanos.load(Ordering::Relaxed);
+        Duration::from_nanos(nanos)
+    }
+
+    /// Adds the given value to the given counter.
+    pub fn add(&self, counter: Counter, value: u64) {
+        self.counters[counter as usize].fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Returns the value of the given counter.
+    #[must_use]
+    pub fn get(&self, counter: Counter) -> u64 {
+        self.counters[counter as usize].load(Ordering::Relaxed)
+    }
+
+    /// Resets all timings and counters.
+    ///
+    /// This must be called after each build in serve mode, as the report is
+    /// shared across rebuilds, so counts would accumulate otherwise.
+    pub fn reset(&self) {
+        for timing in self.inner.iter() {
+            timing.count.store(0, Ordering::Relaxed);
+            timing.nanos.store(0, Ordering::Relaxed);
+        }
+        for counter in self.counters.iter() {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.pages.lock().expect("invariant").clear();
+    }
+
+    /// Returns the summary of the build.
+    ///
+    /// Output size and diagnostics are not tracked by the report, so they must
+    /// be set on the summary by the caller.
+    #[must_use]
+    pub fn summary(&self) -> Summary {
+        let assets_skipped = self.get(Counter::SkippedAssets);
+        Summary {
+            pages: self.count(Stage::Render),
+            cached_pages: self.get(Counter::CachedPages),
+            templates: self.count(Stage::Templates),
+            assets_copied: self
+                .count(Stage::Assets)
+                .saturating_sub(assets_skipped),
+            assets_skipped,
+            search_index_bytes: self.get(Counter::SearchIndexBytes),
+            precompressed_files: self.get(Counter::PrecompressedFiles),
+            precompressed_bytes_saved: self
+                .get(Counter::PrecompressedBytesSaved),
+            social_cards: self.get(Counter::SocialCards),
+            cached_social_cards: self.get(Counter::CachedSocialCards),
+            slowest_pages: self.slowest_pages(SLOWEST_PAGES),
+            ..Summary::default()
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Summary {
+    /// Combines the summaries of all sites of a project into one summary.
+    ///
+    /// Counts are summed up, and the slowest pages, notes and diagnostics of
+    /// all sites are collected, while the summaries of the sites are retained.
+    /// The summary of a project with a single site is returned as it is.
+    #[must_use]
+    pub fn combine(mut sites: Vec<Summary>) -> Summary {
+        if sites.len() == 1 {
+            return sites.remove(0);
+        }
+
+        // Sum up counts, and collect slowest pages, notes and diagnostics
+        let mut summary = Summary::default();
+        for site in &sites {
+            summary.pages += site.pages;
+            summary.cached_pages += site.cached_pages;
+            summary.templates += site.templates;
+            summary.assets_copied += site.assets_copied;
+            summary.assets_skipped += site.assets_skipped;
+            summary.search_index_bytes += site.search_index_bytes;
+            summary.precompressed_files += site.precompressed_files;
+            summary.precompressed_bytes_saved += site.precompressed_bytes_saved;
+            summary.social_cards += site.social_cards;
+            summary.cached_social_cards += site.cached_social_cards;
+            summary.output_bytes += site.output_bytes;
+            summary.warnings += site.warnings;
+            summary.errors += site.errors;
+            summary
+                .slowest_pages
+                .extend(site.slowest_pages.iter().cloned());
+            for note in &site.notes {
+                if !summary.notes.contains(note) {
+                    summary.notes.push(note.clone());
+                }
+            }
+            summary.diagnostics.extend(site.diagnostics.iter().cloned());
+        }
+
+        // Retain the slowest pages of all sites, slowest first
+        summary.slowest_pages.sort_by_key(|timing| {
+            Reverse(timing.markdown_ms + timing.render_ms)
+        });
+        summary.slowest_pages.truncate(SLOWEST_PAGES);
+        summary.sites = sites;
+        summary
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Timer {
+    /// Returns the time elapsed since the timer was started.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.time.elapsed()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl PageTimes {
+    /// Returns the total time spent on the page.
+    fn total(self) -> Duration {
+        self.markdown.unwrap_or_default() + self.render.unwrap_or_default()
+    }
+
+    /// Returns whether any stage was computed, i.e., not served from the cache.
+    fn is_computed(self) -> bool {
+        self.markdown.is_some() || self.render.is_some()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Report {
+    /// Formats the report as a table.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<12}{:>8}{:>12}", "Stage", "Items", "Time")?;
+        for &stage in Stage::ALL {
+            let count = self.count(stage);
+            if count == 0 {
+                continue;
+            }
+
+            // Write stage with item count and cumulative duration
+            let elapsed = self.duration(stage).as_secs_f32();
+            writeln!(f, "{:<12}{count:>8}{elapsed:>11.2}s", stage.name())?;
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+}
+
+impl fmt::Display for Summary {
+    /// Formats the summary as a table.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pages = format!("{} ({} cached)", self.pages, self.cached_pages);
+        let assets =
+            format!("{} ({} skipped)", self.assets_copied, self.assets_skipped);
+        writeln!(f, "{:<16}{pages}", "Pages")?;
+        writeln!(f, "{:<16}{}", "Templates", self.templates)?;
+        writeln!(f, "{:<16}{assets}", "Assets")?;
+        writeln!(
+            f,
+            "{:<16}{}",
+            "Search index",
+            Bytes(self.search_index_bytes)
+        )?;
+        if self.precompressed_files > 0 {
+            let precompressed = format!(
+                "{} ({} saved)",
+                self.precompressed_files,
+                Bytes(self.precompressed_bytes_saved)
+            );
+            writeln!(f, "{:<16}{precompressed}", "Precompressed")?;
+        }
+        if self.social_cards > 0 {
+            let cards = format!(
+                "{} ({} cached)",
+                self.social_cards, self.cached_social_cards
+            );
+            writeln!(f, "{:<16}{cards}", "Social cards")?;
+        }
+        writeln!(f, "{:<16}{}", "Output", Bytes(self.output_bytes))?;
+        writeln!(f, "{:<16}{}", "Warnings", self.warnings)?;
+        writeln!(f, "{:<16}{}", "Errors", self.errors)?;
+
+        // Write slowest pages with the time spent on each stage
+        if !self.slowest_pages.is_empty() {
+            writeln!(f, "Slowest pages")?;
+        }
+        for timing in &self.slowest_pages {
+            let total = timing.markdown_ms + timing.render_ms;
+            writeln!(
+                f,
+                "  {total:>6}ms  {} (markdown {}ms, render {}ms)",
+                timing.page, timing.markdown_ms, timing.render_ms
+            )?;
+        }
+
+        // Write notes on adjusted settings
+        for note in &self.notes {
+            writeln!(f, "Note: {note}")?;
+        }
+
+        // No errors occurred
+        Ok(())
+    }
+}
+
+impl Drop for Timer {
+    /// Records the elapsed time for the stage.
+    fn drop(&mut self) {
+        self.report.record(self.stage, self.time.elapsed());
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl fmt::Display for Bytes {
+    /// Formats the size in human-readable units.
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "kB", "MB", "GB"];
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1000.0 && unit < UNITS.len() - 1 {
+            size /= 1000.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} B", self.0)
+        } else {
+            write!(f, "{size:.1} {}", UNITS[unit])
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the given duration in milliseconds.
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{
+        Bytes, Counter, PageTimes, PageTiming, Report, Stage, Summary,
+    };
+
+    #[test]
+    fn record_aggregates_counts_and_durations() {
+        let report = Report::default();
+        report.record(Stage::Markdown, Duration::from_millis(10));
+        report.record(Stage::Markdown, Duration::from_millis(20));
+        assert_eq!(report.count(Stage::Markdown), 2);
+        assert_eq!(report.duration(Stage::Markdown), Duration::from_millis(30));
+        assert_eq!(report.count(Stage::Render), 0);
+    }
+
+    #[test]
+    fn record_is_correct_under_concurrency() {
+        let report = Report::default();
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                let report = report.clone();
+                scope.spawn(move || {
+                    for _ in 0..100 {
+                        report.record(Stage::Render, Duration::from_micros(1));
+                    }
+                });
+            }
+        });
+        assert_eq!(report.count(Stage::Render), 800);
+        assert_eq!(report.duration(Stage::Render), Duration::from_micros(800));
+    }
+
+    #[test]
+    fn timer_records_on_early_return() {
+        let report = Report::default();
+        let process = |result: Result<(), ()>| {
+            let _timer = report.timer(Stage::Templates);
+            result?;
+            Ok::<_, ()>(())
+        };
+        assert!(process(Err(())).is_err());
+        assert_eq!(report.count(Stage::Templates), 1);
+    }
+
+    #[test]
+    fn reset_clears_timings_and_counters() {
+        let report = Report::default();
+        report.record(Stage::Render, Duration::from_millis(10));
+        report.record_page(Stage::Render, "index.md", None);
+        report.add(Counter::CachedPages, 1);
+        report.reset();
+        assert_eq!(report.count(Stage::Render), 0);
+        assert_eq!(report.duration(Stage::Render), Duration::ZERO);
+        assert_eq!(report.get(Counter::CachedPages), 0);
+        assert_eq!(report.summary(), Summary::default());
+    }
+
+    #[test]
+    fn summary_combines_stages_and_counters() {
+        let report = Report::default();
+        for _ in 0..3 {
+            report.record(Stage::Render, Duration::ZERO);
+            report.record(Stage::Assets, Duration::ZERO);
+        }
+        report.add(Counter::CachedPages, 2);
+        report.add(Counter::SkippedAssets, 1);
+        report.add(Counter::SearchIndexBytes, 512);
+        report.add(Counter::SearchIndexBytes, 512);
+        let summary = report.summary();
+        assert_eq!(summary.pages, 3);
+        assert_eq!(summary.cached_pages, 2);
+        assert_eq!(summary.templates, 0);
+        assert_eq!(summary.assets_copied, 2);
+        assert_eq!(summary.assets_skipped, 1);
+        assert_eq!(summary.search_index_bytes, 1024);
+    }
+
+    #[test]
+    fn variants_are_listed_in_order_of_declaration() {
+        for (index, &stage) in Stage::ALL.iter().enumerate() {
+            assert_eq!(stage as usize, index);
+        }
+        for (index, &counter) in Counter::ALL.iter().enumerate() {
+            assert_eq!(counter as usize, index);
+        }
+    }
+
+    #[test]
+    fn summary_reports_all_counters() {
+        let report = Report::default();
+        for (value, &counter) in (1..).zip(Counter::ALL) {
+            report.add(counter, value);
+        }
+        for (count, &stage) in (1..).zip(Stage::ALL) {
+            for _ in 0..count {
+                report.record(stage, Duration::ZERO);
+            }
+        }
+        let summary = report.summary();
+        assert_eq!(summary.pages, 6);
+        assert_eq!(summary.cached_pages, 1);
+        assert_eq!(summary.templates, 5);
+        assert_eq!(summary.assets_copied, 5);
+        assert_eq!(summary.assets_skipped, 2);
+        assert_eq!(summary.search_index_bytes, 3);
+        assert_eq!(summary.precompressed_files, 4);
+        assert_eq!(summary.precompressed_bytes_saved, 5);
+        assert_eq!(summary.social_cards, 6);
+        assert_eq!(summary.cached_social_cards, 7);
+    }
+
+    #[test]
+    fn slowest_pages_exclude_cached_pages() {
+        let report = Report::default();
+        let ms = Duration::from_millis;
+        report.record_page(Stage::Markdown, "docs/a.md", Some(ms(30)));
+        report.record_page(Stage::Render, "docs/a.md", Some(ms(10)));
+        report.record_page(Stage::Markdown, "docs/b.md", None);
+        report.record_page(Stage::Render, "docs/b.md", Some(ms(50)));
+        report.record_page(Stage::Markdown, "docs/c.md", None);
+        report.record_page(Stage::Render, "docs/c.md", None);
+        report.record_page(Stage::Nav, "docs/d.md", Some(ms(90)));
+        assert_eq!(
+            report.slowest_pages(5),
+            [
+                PageTiming {
+                    page: "docs/b.md".into(),
+                    markdown_ms: 0,
+                    render_ms: 50,
+                },
+                PageTiming {
+                    page: "docs/a.md".into(),
+                    markdown_ms: 30,
+                    render_ms: 10,
+                },
+            ]
+        );
+
+        // Slowest pages are limited, and part of the summary
+        let summary = report.summary();
+        assert_eq!(summary.slowest_pages.len(), 2);
+        assert_eq!(report.slowest_pages(1)[0].page, "docs/b.md");
+    }
+
+    #[test]
+    fn slowest_pages_use_latest_timings() {
+        let report = Report::default();
+        let ms = Duration::from_millis;
+        report.record_page(Stage::Render, "docs/a.md", Some(ms(80)));
+        report.record_page(Stage::Render, "docs/b.md", Some(ms(40)));
+        report.record_page(Stage::Render, "docs/a.md", None);
+        let slowest = report.slowest_pages(5);
+        assert_eq!(slowest.len(), 1);
+        assert_eq!(slowest[0].page, "docs/b.md");
+    }
+
+    #[test]
+    fn threshold_applies_to_total_time() {
+        let ms = Duration::from_millis;
+        let times = |markdown, render| PageTimes {
+            markdown: Some(ms(markdown)),
+            render: Some(ms(render)),
+        };
+        let report = Report::default().with_slow_page_threshold(ms(100));
+        assert!(!report.is_slow(times(60, 40)));
+        assert!(report.is_slow(times(60, 41)));
+        assert!(!Report::default().is_slow(times(600, 400)));
+    }
+
+    #[test]
+    fn summary_lists_slowest_pages() {
+        let report = Report::default();
+        let duration = Duration::from_millis(1200);
+        report.record_page(Stage::Render, "docs/big.md", Some(duration));
+        let summary = report.summary().to_string();
+        assert!(summary.contains("Slowest pages"));
+        assert!(summary.contains("1200ms  docs/big.md (markdown 0ms"));
+    }
+
+    #[test]
+    fn combined_summary_sums_up_sites() {
+        let timing = |page: &str, render_ms| PageTiming {
+            page: page.to_string(),
+            markdown_ms: 0,
+            render_ms,
+        };
+        let a = Summary {
+            pages: 2,
+            warnings: 1,
+            slowest_pages: vec![timing("a/index.md", 10)],
+            notes: vec!["note".to_string()],
+            site_dir: "site/a".to_string(),
+            ..Summary::default()
+        };
+        let b = Summary {
+            pages: 3,
+            slowest_pages: vec![timing("b/index.md", 20)],
+            notes: vec!["note".to_string()],
+            site_dir: "site/b".to_string(),
+            ..Summary::default()
+        };
+        let summary = Summary::combine(vec![a.clone(), b.clone()]);
+        assert_eq!(summary.pages, 5);
+        assert_eq!(summary.warnings, 1);
+        assert_eq!(
+            summary.slowest_pages,
+            [timing("b/index.md", 20), timing("a/index.md", 10)]
+        );
+        assert_eq!(summary.notes, ["note"]);
+        assert_eq!(summary.sites, [a.clone(), b]);
+        assert_eq!(Summary::combine(vec![a.clone()]), a);
+    }
+
+    #[test]
+    fn summary_lists_notes() {
+        let summary = Summary {
+            notes: vec!["Theme feature 'x' is disabled".into()],
+            ..Summary::default()
+        };
+        let summary = summary.to_string();
+        assert!(summary.ends_with("Note: Theme feature 'x' is disabled\n"));
+    }
+
+    #[test]
+    fn bytes_are_formatted_in_units() {
+        assert_eq!(Bytes(999).to_string(), "999 B");
+        assert_eq!(Bytes(1500).to_string(), "1.5 kB");
+        assert_eq!(Bytes(2_500_000).to_string(), "2.5 MB");
+    }
+
+    #[test]
+    fn display_skips_empty_stages() {
+        let report = Report::default();
+        report.measure(Stage::Nav, || ());
+        let table = report.to_string();
+        assert!(table.contains("nav"));
+        assert!(!table.contains("markdown"));
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Build scope.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use zrx::id::Id;
+use zrx::scheduler::Key;
+
+use crate::config::Config;
+use crate::structure::manifest::Manifest;
+use crate::structure::markdown::escape_html;
+use crate::structure::page::{parse_dates, PageSummary};
+
+use super::Selector;
+
+// ----------------------------------------------------------------------------
+// Constants
+// ----------------------------------------------------------------------------
+
+/// File name of the snapshot in the cache directory, which is hidden, so it's
+/// never considered an orphaned cache entry when the cache is pruned.
+const SNAPSHOT: &str = ".pages.json";
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Build scope.
+///
+/// By default, the scope contains all pages. In serve mode, it can be narrowed
+/// to a subtree of the docs directory, so only pages in this subtree are built.
+/// Since the navigation must still contain all pages, it's synthesized from the
+/// built pages and a snapshot of all other pages, which is taken after each
+/// full build. Pages outside of the scope keep their previously built output.
+#[derive(Clone, Default)]
+pub struct Scope {
+    /// Scope matcher and pages outside of the scope, if narrowed.
+    inner: Option<Arc<Inner>>,
+}
+
+/// Narrowed build scope.
+struct Inner {
+    /// Glob pattern, relative to the docs directory.
+    glob: String,
+    /// Selector for identifiers in the scope.
+    selector: Selector,
+    /// Pages outside of the scope, taken from the snapshot.
+    pages: Vec<(Key<Id>, PageSummary)>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Scope {
+    /// Creates a scope for the given glob pattern from a snapshot.
+    ///
+    /// The glob pattern is relative to the docs directory, but may also start
+    /// with the docs directory, e.g., `docs/team-a/**`. All pages of the given
+    /// snapshot that are outside of the scope are converted into stubs, which
+    /// carry everything necessary to synthesize the navigation.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the glob pattern is invalid.
+    pub fn new(
+        docs_dir: &str, glob: &str, snapshot: Manifest,
+    ) -> io::Result<Self> {
+        let glob = normalize(docs_dir, glob);
+        let selector = Selector::glob(&glob)
+            .map_err(|err| {
+                io::Error::new(io::ErrorKind::InvalidInput, err.to_string())
+            })?
+            .context(docs_dir);
+
+        // Convert all pages outside of the scope into stubs
+        let pages = snapshot
+            .pages
+            .into_iter()
+            .filter_map(|page| {
+                let id = Id::builder()
+                    .provider("file")
+                    .context(docs_dir)
+                    .location(&page.source)
+                    .build()
+                    .ok()?;
+                if selector.is_match(&id) {
+                    return None;
+                }
+
+                // Create stub, which only carries URL, title and metadata, as
+                // content is never rendered, but only used for navigation.
+                // Dates were already reported when the snapshot was taken.
+                let mut meta = page.meta;
+                parse_dates(&mut meta);
+                let page = PageSummary {
+                    url: page.url.into(),
+                    canonical_url: None,
+                    title_html: escape_html(&page.title).into(),
+                    title: page.title.into(),
+                    meta,
+                    path: "".into(),
+                    src_path: page.source.into(),
+                    content_hash: "".into(),
+                    toc: Arc::from([]),
+                    search: Arc::from([]),
+                    tags: Vec::new(),
+                };
+                Some((Key::from_iter([id]), page))
+            })
+            .collect();
+
+        // Return narrowed scope
+        Ok(Self {
+            inner: Some(Arc::new(Inner { glob, selector, pages })),
+        })
+    }
+
+    /// Loads the scope for the given glob pattern from the last snapshot.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if no snapshot of a full build exists, or
+    /// if the snapshot or glob pattern is invalid.
+    pub fn load(config: &Config, glob: &str) -> io::Result<Self> {
+        let data = fs::read(snapshot_path(config))?;
+        let snapshot = serde_json::from_slice(&data)?;
+        Self::new(&config.project.docs_dir, glob, snapshot)
+    }
+
+    /// Returns whether the given identifier is in the scope.
+    #[inline]
+    pub fn contains(&self, id: &Id) -> bool {
+        self.inner
+            .as_ref()
+            .is_none_or(|inner| inner.selector.is_match(id))
+    }
+
+    /// Returns whether the scope is narrowed.
+    #[inline]
+    pub fn is_narrowed(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns the glob pattern of the scope, if narrowed.
+    #[inline]
+    pub fn glob(&self) -> Option<&str> {
+        self.inner.as_ref().map(|inner| inner.glob.as_str())
+    }
+
+    /// Returns the pages outside of the scope, taken from the snapshot.
+    #[inline]
+    pub fn pages(&self) -> &[(Key<Id>, PageSummary)] {
+        self.inner.as_ref().map_or(&[], |inner| &inner.pages)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for Scope {
+    /// Formats the scope for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("glob", &self.glob())
+            .field("pages", &self.pages().len())
+            .finish()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Returns the path of the snapshot in the cache directory.
+pub fn snapshot_path(config: &Config) -> PathBuf {
+    config.get_cache_dir().join(SNAPSHOT)
+}
+
+/// Normalizes the given glob pattern to be relative to the docs directory.
+///
+/// Directories, i.e., patterns ending with a slash, include all files in them.
+fn normalize(docs_dir: &str, glob: &str) -> String {
+    let glob = glob.trim_start_matches("./").replace('\\', "/");
+    let prefix = format!("{}/", docs_dir.trim_start_matches("./"));
+    let glob = glob.strip_prefix(&prefix).unwrap_or(&glob);
+    if glob.is_empty() || glob.ends_with('/') {
+        format!("{glob}**")
+    } else {
+        glob.to_string()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use zrx::id::Id;
+
+    use crate::structure::manifest::{Manifest, ManifestPage};
+
+    use super::{normalize, Scope};
+
+    /// Returns a snapshot with the given source paths.
+    fn snapshot(sources: &[&str]) -> Manifest {
+        let pages = sources
+            .iter()
+            .map(|source| ManifestPage {
+                description: None,
+                id: None,
+                meta: BTreeMap::new(),
+                sections: Vec::new(),
+                source: (*source).to_string(),
+                tags: Vec::new(),
+                title: (*source).to_string(),
+                url: source.replace(".md", "/"),
+            })
+            .collect();
+        Manifest { pages, version: 1 }
+    }
+
+    /// Returns the identifier of the given file in the docs directory.
+    fn id(location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context("docs")
+            .location(location)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn default_scope_contains_everything() {
+        let scope = Scope::default();
+        assert!(!scope.is_narrowed());
+        assert!(scope.contains(&id("index.md")));
+        assert!(scope.pages().is_empty());
+    }
+
+    #[test]
+    fn narrowed_scope_keeps_pages_outside_as_stubs() {
+        let snapshot = snapshot(&["index.md", "team-a/a.md", "team-b/b.md"]);
+        let scope = Scope::new("docs", "docs/team-a/**", snapshot).unwrap();
+        assert!(scope.contains(&id("team-a/a.md")));
+        assert!(!scope.contains(&id("team-b/b.md")));
+        assert!(!scope.contains(&id("index.md")));
+
+        // Pages outside of the scope keep their cached URLs
+        let urls = scope
+            .pages()
+            .iter()
+            .map(|(_, page)| &*page.url)
+            .collect::<Vec<_>>();
+        assert_eq!(urls, ["index/", "team-b/b/"]);
+    }
+
+    #[test]
+    fn globs_are_normalized() {
+        assert_eq!(normalize("docs", "docs/team-a/**"), "team-a/**");
+        assert_eq!(normalize("docs", "./docs/team-a/"), "team-a/**");
+        assert_eq!(normalize("docs", "team-a/*.md"), "team-a/*.md");
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Identifier selectors.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use zrx::id::{Id, Matcher};
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Selector for identifiers, composed from structured components.
+///
+/// Matchers are created from patterns, in which braces, commas and colons have
+/// special meaning, so interpolating user-provided names like the docs
+/// directory or template names into patterns silently breaks matching, e.g.,
+/// for a template named `landing,legacy.html`. Selectors only compile globs
+/// into matchers, and compare contexts and locations literally.
+#[derive(Clone)]
+pub struct Selector {
+    /// Matcher for the glob pattern.
+    matcher: Arc<Matcher>,
+    /// Context, compared literally, if any.
+    context: Option<String>,
+    /// Locations, compared literally, if any.
+    locations: Option<Vec<String>>,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Selector {
+    /// Creates a selector for the given matcher pattern.
+    ///
+    /// The pattern must be a constant, e.g., `zrs::::templates/*::`, so it's
+    /// guaranteed to be valid. Use [`Selector::glob`] for user-provided globs.
+    pub fn new(pattern: &'static str) -> Self {
+        let matcher = Matcher::from_str(pattern).expect("invariant");
+        Self {
+            matcher: Arc::new(matcher),
+            context: None,
+            locations: None,
+        }
+    }
+
+    /// Creates a selector for locations matching the given glob pattern.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the glob pattern is invalid.
+    pub fn glob(glob: &str) -> Result<Self, <Matcher as FromStr>::Err> {
+        let matcher = Matcher::from_str(&format!("zrs:::::{glob}:"))?;
+        Ok(Self {
+            matcher: Arc::new(matcher),
+            context: None,
+            locations: None,
+        })
+    }
+
+    /// Restricts the selector to the given context.
+    #[must_use]
+    pub fn context<S>(mut self, context: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Restricts the selector to the given locations.
+    #[must_use]
+    pub fn locations<I, S>(mut self, locations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.locations = Some(locations.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns whether the given identifier is selected.
+    pub fn is_match(&self, id: &Id) -> bool {
+        self.context
+            .as_ref()
+            .is_none_or(|context| id.context() == *context)
+            && self.locations.as_ref().is_none_or(|locations| {
+                let location = id.location();
+                locations.iter().any(|value| *value == location)
+            })
+            && self.matcher.is_match(id).expect("invariant")
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use zrx::id::Id;
+
+    use super::Selector;
+
+    /// Creates an identifier for the given context and location.
+    fn id(context: &str, location: &str) -> Id {
+        Id::builder()
+            .provider("file")
+            .context(context)
+            .location(location)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn context_with_spaces_and_braces() {
+        for docs_dir in ["my docs", "docs {old}", "a,b"] {
+            let selector = Selector::glob("**/*.md").unwrap().context(docs_dir);
+            assert!(selector.is_match(&id(docs_dir, "index.md")));
+            assert!(selector.is_match(&id(docs_dir, "guide/index.md")));
+            assert!(!selector.is_match(&id(docs_dir, "style.css")));
+            assert!(!selector.is_match(&id("docs", "index.md")));
+        }
+    }
+
+    #[test]
+    fn locations_with_commas() {
+        let selector = Selector::new("zrs::::::")
+            .context("my docs")
+            .locations(["landing,legacy.html", "404.html"]);
+        assert!(selector.is_match(&id("my docs", "landing,legacy.html")));
+        assert!(selector.is_match(&id("my docs", "404.html")));
+        assert!(!selector.is_match(&id("my docs", "landing")));
+        assert!(!selector.is_match(&id("my docs", "legacy.html")));
+        assert!(!selector.is_match(&id("docs", "404.html")));
+    }
+
+    #[test]
+    fn theme_templates() {
+        let selector =
+            Selector::new("zrs::::templates/*::").locations(["sitemap.xml"]);
+        assert!(selector.is_match(&id("templates/material", "sitemap.xml")));
+        assert!(!selector.is_match(&id("templates/material", "main.html")));
+        assert!(!selector.is_match(&id("docs", "sitemap.xml")));
+    }
+}
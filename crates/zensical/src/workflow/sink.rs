@@ -0,0 +1,786 @@
+// Copyright (c) 2025-2026 Zensical and contributors
+
+// SPDX-License-Identifier: MIT
+// All contributions are certified under the DCO
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+// ----------------------------------------------------------------------------
+
+//! Output sinks.
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::config::build::Format;
+use crate::config::Config;
+
+use crate::hash::stable_hash;
+
+use super::assets::{copy_file, is_fresh};
+use super::diagnostics::Diagnostics;
+
+// ----------------------------------------------------------------------------
+// Traits
+// ----------------------------------------------------------------------------
+
+/// Output sink.
+///
+/// All outputs of the workflow are written through a sink, which is shared by
+/// all stages, so implementations must be safe to use concurrently. Paths are
+/// always located inside the site directory.
+pub trait OutputSink: fmt::Debug + Send + Sync {
+    /// Writes the given data to the given path.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the data could not be written.
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()>;
+
+    /// Copies the file at the given source path to the given path.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the file could not be copied.
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.write(to, &fs::read(from)?)
+    }
+
+    /// Returns whether the output at the given path is up to date with the
+    /// file at the given source path, so copying it can be skipped.
+    ///
+    /// Sinks that don't retain outputs between builds, e.g., archives, must
+    /// always copy files, which is why this returns `false` by default.
+    fn is_fresh(&self, _from: &Path, _to: &Path) -> bool {
+        false
+    }
+
+    /// Removes the output at the given path, if any.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the output could not be removed.
+    fn remove(&self, path: &Path) -> io::Result<()>;
+
+    /// Finishes the output, and returns its total size in bytes.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the output could not be finished.
+    fn finish(&self) -> io::Result<u64>;
+}
+
+// ----------------------------------------------------------------------------
+// Enums
+// ----------------------------------------------------------------------------
+
+/// Archive writer.
+enum Writer {
+    /// Uncompressed tarball.
+    Tar(tar::Builder<File>),
+    /// Gzip-compressed tarball.
+    TarGz(tar::Builder<GzEncoder<File>>),
+    /// Zip archive.
+    Zip(ZipWriter<File>),
+}
+
+// ----------------------------------------------------------------------------
+// Structs
+// ----------------------------------------------------------------------------
+
+/// Filesystem sink, writing outputs to the site directory.
+#[derive(Debug)]
+pub struct Filesystem {
+    /// Site directory.
+    site_dir: PathBuf,
+}
+
+/// Archive sink, streaming outputs into an archive.
+///
+/// Entries are appended as they are produced, which avoids writing all files
+/// to disk first. Since the workflow writes outputs concurrently, the writer
+/// is guarded by a mutex, so entries are never interleaved. Entries are only
+/// appended once, so writing the same output again is skipped, and writing
+/// different data to the same output is refused, as it can't be replaced.
+pub struct Archive {
+    /// Site directories, i.e., canonical and as configured.
+    site_dirs: [PathBuf; 2],
+    /// Archive path.
+    path: PathBuf,
+    /// Modification time of entries.
+    mtime: u64,
+    /// Archive writer, which is taken when finished.
+    writer: Mutex<Option<Writer>>,
+    /// Hashes of appended entries by name.
+    entries: Mutex<BTreeMap<String, u64>>,
+}
+
+/// Guarded sink, refusing outputs outside of the site directory.
+///
+/// Output paths are derived from identifiers, so an odd identifier, e.g., one
+/// with parent components, or a symbolic link in the site directory, might
+/// resolve to a path outside of the site directory. All outputs are checked
+/// before they're passed to the inner sink, and violations are reported as
+/// errors, skipping the output, so nothing is ever written elsewhere.
+#[derive(Debug)]
+pub struct Guard {
+    /// Inner sink.
+    inner: Arc<dyn OutputSink>,
+    /// Site directories, i.e., canonical and as configured.
+    site_dirs: [PathBuf; 2],
+    /// Diagnostics.
+    diagnostics: Diagnostics,
+}
+
+// ----------------------------------------------------------------------------
+// Implementations
+// ----------------------------------------------------------------------------
+
+impl Filesystem {
+    /// Creates a filesystem sink for the given site directory.
+    pub fn new<P>(site_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { site_dir: site_dir.into() }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Archive {
+    /// Creates an archive sink, creating the archive at the given path.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the archive could not be created.
+    pub fn new(
+        site_dirs: [PathBuf; 2], format: Format, path: PathBuf,
+    ) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Create archive writer for the given format
+        let file = File::create(&path)?;
+        let writer = match format {
+            Format::Tar => Writer::Tar(tar::Builder::new(file)),
+            Format::TarGz => {
+                let encoder = GzEncoder::new(file, Compression::default());
+                Writer::TarGz(tar::Builder::new(encoder))
+            }
+            Format::Zip => Writer::Zip(ZipWriter::new(file)),
+        };
+
+        // All entries share the time the archive was created
+        let mtime = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Ok(Self {
+            site_dirs,
+            path,
+            mtime,
+            writer: Mutex::new(Some(writer)),
+            entries: Mutex::default(),
+        })
+    }
+
+    /// Returns the name of the entry for the given path.
+    ///
+    /// Entry names are relative to the site directory, and always use forward
+    /// slashes as separators, regardless of the platform.
+    fn entry_name(&self, path: &Path) -> io::Result<String> {
+        let Some(path) = self
+            .site_dirs
+            .iter()
+            .find_map(|dir| path.strip_prefix(dir).ok())
+        else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Output outside of site directory: {}", path.display()),
+            ));
+        };
+
+        // Join components with forward slashes
+        let components = path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>();
+        Ok(components.join("/"))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl Guard {
+    /// Creates a guarded sink for the given site directories.
+    pub fn new(
+        inner: Arc<dyn OutputSink>, site_dirs: [PathBuf; 2],
+        diagnostics: Diagnostics,
+    ) -> Self {
+        Self { inner, site_dirs, diagnostics }
+    }
+
+    /// Returns the normalized path, if it's inside of the site directory.
+    ///
+    /// The path is first normalized lexically, so parent components must not
+    /// leave the site directory, and then resolved against the file system,
+    /// so symbolic links must not point outside of it either. Otherwise, an
+    /// error is reported, and [`None`] is returned.
+    fn check(&self, path: &Path) -> Option<PathBuf> {
+        let resolved = resolve(&self.site_dirs, path)
+            .filter(|path| is_contained(&self.site_dirs[0], path));
+        if resolved.is_none() {
+            self.diagnostics.error(format!(
+                "Output outside of site directory: {}",
+                path.display()
+            ));
+        }
+        resolved
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Trait implementations
+// ----------------------------------------------------------------------------
+
+impl fmt::Debug for Archive {
+    /// Formats the archive sink for debugging.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Archive")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+impl OutputSink for Filesystem {
+    /// Writes the given data to the given path, creating directories.
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(path.parent().expect("invariant"))?;
+        fs::write(path, data)
+    }
+
+    /// Copies the file, without reading it into memory.
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::create_dir_all(to.parent().expect("invariant"))?;
+        copy_file(from, to)
+    }
+
+    /// Returns whether the file in the site directory is up to date.
+    fn is_fresh(&self, from: &Path, to: &Path) -> bool {
+        is_fresh(from, to)
+    }
+
+    /// Removes the file at the given path, if it exists, as well as all of its
+    /// parent directories inside of the site directory that are now empty.
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                return Err(err);
+            }
+            _ => {}
+        }
+        let mut dir = path.parent();
+        while let Some(path) = dir.filter(|&path| {
+            path.starts_with(&self.site_dir) && path != self.site_dir
+        }) {
+            if fs::remove_dir(path).is_err() {
+                break;
+            }
+            dir = path.parent();
+        }
+        Ok(())
+    }
+
+    /// Returns the total size of all files in the site directory.
+    fn finish(&self) -> io::Result<u64> {
+        dir_size(&self.site_dir)
+    }
+}
+
+impl OutputSink for Archive {
+    /// Appends an entry with the given data to the archive.
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let name = self.entry_name(path)?;
+        let mut writer = self.writer.lock().expect("invariant");
+        let Some(writer) = writer.as_mut() else {
+            return Err(io::Error::other("Archive is already finished"));
+        };
+
+        // Skip entries that were already appended with the same data, and
+        // refuse different data, since entries can't be replaced
+        let hash = stable_hash(data);
+        let mut entries = self.entries.lock().expect("invariant");
+        if let Some(&prior) = entries.get(&name) {
+            return if prior == hash {
+                Ok(())
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("Output written twice with different data: {name}"),
+                ))
+            };
+        }
+        entries.insert(name.clone(), hash);
+
+        // Append entry, with permissions readable by everyone
+        match writer {
+            Writer::Tar(builder) => append(builder, &name, data, self.mtime),
+            Writer::TarGz(builder) => append(builder, &name, data, self.mtime),
+            Writer::Zip(zip) => {
+                let options = SimpleFileOptions::default()
+                    .compression_method(CompressionMethod::Deflated)
+                    .unix_permissions(0o644);
+                zip.start_file(name, options)?;
+                io::Write::write_all(zip, data)
+            }
+        }
+    }
+
+    /// Does nothing, since the archive only contains outputs of this build.
+    fn remove(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Finishes the archive, and returns its size in bytes.
+    fn finish(&self) -> io::Result<u64> {
+        let writer = self.writer.lock().expect("invariant").take();
+        match writer {
+            Some(Writer::Tar(builder)) => builder.into_inner()?.sync_all()?,
+            Some(Writer::TarGz(builder)) => {
+                builder.into_inner()?.finish()?.sync_all()?;
+            }
+            Some(Writer::Zip(zip)) => zip.finish()?.sync_all()?,
+            None => {}
+        }
+        fs::metadata(&self.path).map(|metadata| metadata.len())
+    }
+}
+
+impl OutputSink for Guard {
+    /// Writes the given data, if the path is inside of the site directory.
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        match self.check(path) {
+            Some(path) => self.inner.write(&path, data),
+            None => Ok(()),
+        }
+    }
+
+    /// Copies the given file, if the path is inside of the site directory.
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        match self.check(to) {
+            Some(to) => self.inner.copy(from, &to),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether the output of the inner sink is up to date.
+    fn is_fresh(&self, from: &Path, to: &Path) -> bool {
+        self.inner.is_fresh(from, to)
+    }
+
+    /// Removes the output, if the path is inside of the site directory.
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        match self.check(path) {
+            Some(path) => self.inner.remove(&path),
+            None => Ok(()),
+        }
+    }
+
+    /// Finishes the output of the inner sink.
+    fn finish(&self) -> io::Result<u64> {
+        self.inner.finish()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Functions
+// ----------------------------------------------------------------------------
+
+/// Opens the output sink for the given configuration.
+///
+/// If an archive is configured, outputs are streamed into the archive, which
+/// is resolved relative to the root directory. Otherwise, outputs are written
+/// to the site directory. Either way, outputs outside of the site directory
+/// are refused, and reported through the given diagnostics.
+///
+/// # Errors
+///
+/// This function returns an error if the archive could not be created.
+pub fn open(
+    config: &Config, diagnostics: &Diagnostics,
+) -> io::Result<Arc<dyn OutputSink>> {
+    let site_dir = config.get_site_dir();
+
+    // Accept paths relative to the configured site directory as well, since
+    // identifiers are not canonicalized
+    let root_dir = config.get_root_dir();
+    let site_dirs = [site_dir, root_dir.join(&config.project.site_dir)];
+    let sink: Arc<dyn OutputSink> = match &config.project.build.output {
+        None => Arc::new(Filesystem::new(site_dirs[0].clone())),
+        Some(archive) => {
+            let path = root_dir.join(&archive.path);
+            let format = archive.format;
+            Arc::new(Archive::new(site_dirs.clone(), format, path)?)
+        }
+    };
+    Ok(Arc::new(Guard::new(sink, site_dirs, diagnostics.clone())))
+}
+
+/// Resolves the given path lexically against the given site directories.
+///
+/// Parent and current directory components are normalized, and the resolved
+/// path is returned relative to the first site directory that contains it, or
+/// [`None`], if none of them does, or parent components would leave it.
+fn resolve(site_dirs: &[PathBuf], path: &Path) -> Option<PathBuf> {
+    site_dirs.iter().find_map(|dir| {
+        let suffix = path.strip_prefix(dir).ok()?;
+        let mut resolved = dir.clone();
+        let mut depth = 0_usize;
+        for component in suffix.components() {
+            match component {
+                Component::Normal(name) => {
+                    resolved.push(name);
+                    depth += 1;
+                }
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => {
+                    resolved.pop();
+                    depth -= 1;
+                }
+                _ => return None,
+            }
+        }
+        (depth > 0).then_some(resolved)
+    })
+}
+
+/// Returns whether the given path is inside of the given site directory, after
+/// resolving symbolic links.
+///
+/// Outputs usually don't exist yet, so the closest existing ancestor is
+/// resolved, which must be the site directory itself or inside of it.
+fn is_contained(site_dir: &Path, path: &Path) -> bool {
+    let Ok(site_dir) = site_dir.canonicalize() else {
+        return true;
+    };
+    path.ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())
+        .is_none_or(|ancestor| ancestor.starts_with(site_dir))
+}
+
+/// Computes the total size of all files in the given directory.
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(path) = stack.pop() {
+        for entry in fs::read_dir(&path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                stack.push(entry.path());
+            } else {
+                size += entry.metadata()?.len();
+            }
+        }
+    }
+    Ok(size)
+}
+
+/// Appends an entry to a tarball.
+fn append<W>(
+    builder: &mut tar::Builder<W>, name: &str, data: &[u8], mtime: u64,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    builder.append_data(&mut header, name, data)
+}
+
+// ----------------------------------------------------------------------------
+// Tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use flate2::read::GzDecoder;
+    use std::collections::BTreeMap;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::thread;
+    use tempfile::tempdir;
+    use zrx::id::Id;
+
+    use crate::config::build::Format;
+
+    use crate::workflow::{Diagnostics, Severity};
+
+    use super::{Archive, Filesystem, Guard, OutputSink};
+
+    /// Writes the same outputs concurrently to the given sink.
+    fn produce(sink: &dyn OutputSink, site_dir: &Path) {
+        thread::scope(|scope| {
+            for n in 0..4 {
+                scope.spawn(move || {
+                    for m in 0..25 {
+                        let path = site_dir.join(format!("{n}/{m}/index.html"));
+                        let data = format!("<p>{n}/{m}</p>").repeat(m + 1);
+                        sink.write(&path, data.as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+        sink.write(&site_dir.join("search.json"), b"{}").unwrap();
+        sink.finish().unwrap();
+    }
+
+    /// Reads all files in the given directory, keyed by relative path.
+    fn read_dir(dir: &Path) -> BTreeMap<String, Vec<u8>> {
+        let mut files = BTreeMap::new();
+        let mut stack = vec![dir.to_path_buf()];
+        while let Some(path) = stack.pop() {
+            for entry in fs::read_dir(&path).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else {
+                    let name = path.strip_prefix(dir).unwrap();
+                    let name = name.to_string_lossy().replace('\\', "/");
+                    files.insert(name, fs::read(&path).unwrap());
+                }
+            }
+        }
+        files
+    }
+
+    /// Reads all entries of the given tarball, keyed by name.
+    fn read_tar<R>(reader: R) -> BTreeMap<String, Vec<u8>>
+    where
+        R: Read,
+    {
+        let mut files = BTreeMap::new();
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            assert_eq!(entry.header().mode().unwrap(), 0o644);
+            let name = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            files.insert(name, data);
+        }
+        files
+    }
+
+    /// Builds the expected outputs with the filesystem sink.
+    fn expected() -> BTreeMap<String, Vec<u8>> {
+        let dir = tempdir().unwrap();
+        produce(&Filesystem::new(dir.path()), dir.path());
+        read_dir(dir.path())
+    }
+
+    /// Builds the outputs into an archive with the given format.
+    fn archive(format: Format, name: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let path = dir.path().join(name);
+        let site_dirs = [site_dir.clone(), site_dir.clone()];
+        let sink = Archive::new(site_dirs, format, path.clone()).unwrap();
+        produce(&sink, &site_dir);
+        (dir, path)
+    }
+
+    #[test]
+    fn tar_matches_filesystem() {
+        let (_dir, path) = archive(Format::Tar, "site.tar");
+        let files = read_tar(File::open(path).unwrap());
+        assert_eq!(files, expected());
+    }
+
+    #[test]
+    fn tar_gz_matches_filesystem() {
+        let (_dir, path) = archive(Format::TarGz, "site.tar.gz");
+        let files = read_tar(GzDecoder::new(File::open(path).unwrap()));
+        assert_eq!(files, expected());
+    }
+
+    #[test]
+    fn zip_matches_filesystem() {
+        let (_dir, path) = archive(Format::Zip, "site.zip");
+        let mut zip = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut files = BTreeMap::new();
+        for index in 0..zip.len() {
+            let mut entry = zip.by_index(index).unwrap();
+            assert_eq!(entry.unix_mode().unwrap() & 0o777, 0o644);
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            files.insert(entry.name().to_string(), data);
+        }
+        assert_eq!(files, expected());
+    }
+
+    #[test]
+    fn repeated_outputs_are_appended_once() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let site_dirs = [site_dir.clone(), site_dir.clone()];
+        let path = dir.path().join("site.tar");
+        let sink = Archive::new(site_dirs, Format::Tar, path.clone()).unwrap();
+        let output = site_dir.join("assets/style.css");
+        sink.write(&output, b"body {}").unwrap();
+        sink.write(&output, b"body {}").unwrap();
+        assert!(sink.write(&output, b"main {}").is_err());
+        sink.finish().unwrap();
+
+        // Archive contains a single entry
+        let file = File::open(path).unwrap();
+        let mut archive = tar::Archive::new(file);
+        assert_eq!(archive.entries().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn archives_never_consider_outputs_fresh() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        fs::create_dir_all(&site_dir).unwrap();
+        let from = dir.path().join("style.css");
+        let to = site_dir.join("style.css");
+        fs::write(&from, "body {}").unwrap();
+        fs::write(&to, "body {}").unwrap();
+        assert!(Filesystem::new(&site_dir).is_fresh(&from, &to));
+
+        // Archives always copy, regardless of the site directory
+        let site_dirs = [site_dir.clone(), site_dir];
+        let path = dir.path().join("site.tar");
+        let sink = Archive::new(site_dirs, Format::Tar, path).unwrap();
+        assert!(!sink.is_fresh(&from, &to));
+    }
+
+    #[test]
+    fn outputs_outside_of_site_directory_are_rejected() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let site_dirs = [site_dir.clone(), site_dir];
+        let path = dir.path().join("site.tar");
+        let sink = Archive::new(site_dirs, Format::Tar, path).unwrap();
+        let outside = dir.path().join("index.html");
+        assert!(sink.write(&outside, b"").is_err());
+    }
+
+    #[test]
+    fn removals_prune_empty_directories() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let sink = Filesystem::new(&site_dir);
+        sink.write(&site_dir.join("a/b/index.html"), b"").unwrap();
+        sink.write(&site_dir.join("a/index.html"), b"").unwrap();
+
+        // Directories are only removed once they're empty
+        sink.remove(&site_dir.join("a/b/index.html")).unwrap();
+        assert!(!site_dir.join("a/b").exists());
+        assert!(site_dir.join("a/index.html").exists());
+        sink.remove(&site_dir.join("a/index.html")).unwrap();
+        sink.remove(&site_dir.join("a/index.html.gz")).unwrap();
+        assert!(!site_dir.join("a").exists());
+        assert!(site_dir.exists());
+    }
+
+    /// Creates a guarded filesystem sink for the given site directory.
+    fn guard(site_dir: &Path, diagnostics: &Diagnostics) -> Guard {
+        fs::create_dir_all(site_dir).unwrap();
+        let inner = Arc::new(Filesystem::new(site_dir));
+        let site_dirs = [site_dir.canonicalize().unwrap(), site_dir.into()];
+        Guard::new(inner, site_dirs, diagnostics.clone())
+    }
+
+    #[test]
+    fn guard_refuses_parent_components() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let diagnostics = Diagnostics::default();
+        let sink = guard(&site_dir, &diagnostics);
+        let id = Id::builder()
+            .provider("file")
+            .context("site")
+            .location("assets/../../escape.html")
+            .build()
+            .unwrap();
+        let escape = dir.path().join(id.to_path());
+        sink.write(&escape, b"").unwrap();
+        sink.copy(&escape, &site_dir.join("..")).unwrap();
+        assert!(!dir.path().join("escape.html").exists());
+        assert_eq!(diagnostics.count(Severity::Error), 2);
+    }
+
+    #[test]
+    fn guard_refuses_removals_outside_of_site_directory() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let outside = dir.path().join("index.html");
+        fs::write(&outside, "").unwrap();
+        let diagnostics = Diagnostics::default();
+        let sink = guard(&site_dir, &diagnostics);
+        sink.remove(&site_dir.join("../index.html")).unwrap();
+        assert!(outside.exists());
+        assert_eq!(diagnostics.count(Severity::Error), 1);
+    }
+
+    #[test]
+    fn guard_normalizes_paths_inside_site_directory() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let diagnostics = Diagnostics::default();
+        let sink = guard(&site_dir, &diagnostics);
+        let path = site_dir.join("assets/./../index.html");
+        sink.write(&path, b"<p>Hello</p>").unwrap();
+        assert!(site_dir.join("index.html").exists());
+        assert_eq!(diagnostics.count(Severity::Error), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn guard_refuses_symbolic_links_leaving_site_directory() {
+        let dir = tempdir().unwrap();
+        let site_dir = dir.path().join("site");
+        let outside = dir.path().join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        let diagnostics = Diagnostics::default();
+        let sink = guard(&site_dir, &diagnostics);
+        std::os::unix::fs::symlink(&outside, site_dir.join("link")).unwrap();
+        sink.write(&site_dir.join("link/index.html"), b"").unwrap();
+        assert!(!outside.join("index.html").exists());
+        assert_eq!(diagnostics.count(Severity::Error), 1);
+    }
+}
@@ -0,0 +1,141 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import shutil
+from dataclasses import dataclass
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from bs4 import BeautifulSoup
+
+from zensical import build
+
+if TYPE_CHECKING:
+    from bs4.element import Tag
+
+# ---------------------------------------------------------------------------
+# Constants
+# ---------------------------------------------------------------------------
+
+FIXTURES = Path(__file__).parent / "fixtures"
+"""Directory containing the fixture projects."""
+
+# ---------------------------------------------------------------------------
+# Classes
+# ---------------------------------------------------------------------------
+
+
+@dataclass
+class BuiltSite:
+    """Site built from a fixture project."""
+
+    root: Path
+    """Root directory of the project."""
+
+    summary: dict[str, Any]
+    """Summary of the build, as returned by the build."""
+
+    @property
+    def site_dir(self) -> Path:
+        """Return the site directory."""
+        return self.root / "site"
+
+    @property
+    def diagnostics(self) -> list[dict[str, Any]]:
+        """Return the diagnostics of the build."""
+        return self.summary["diagnostics"]
+
+    def files(self) -> list[str]:
+        """Return the paths of all output files, relative to the site."""
+        site = self.site_dir
+        files = [path for path in site.rglob("*") if path.is_file()]
+        return sorted(path.relative_to(site).as_posix() for path in files)
+
+    def exists(self, path: str) -> bool:
+        """Return whether the output file at the given path exists."""
+        return (self.site_dir / path).is_file()
+
+    def read(self, path: str) -> str:
+        """Return the contents of the output file at the given path."""
+        return (self.site_dir / path).read_text(encoding="utf-8")
+
+    def html(self, path: str) -> BeautifulSoup:
+        """Return the parsed output file at the given path."""
+        return BeautifulSoup(self.read(path), "lxml")
+
+    def select(self, path: str, selector: str) -> list[Tag]:
+        """Return all elements matching the selector in the given file."""
+        return self.html(path).select(selector)
+
+    def select_one(self, path: str, selector: str) -> Tag:
+        """Return the first element matching the selector in the given file.
+
+        Fails, if no element matches, so assertions don't need to check.
+        """
+        element = self.html(path).select_one(selector)
+        assert element is not None, f"{selector!r} not found in {path}"
+        return element
+
+
+# ---------------------------------------------------------------------------
+# Functions
+# ---------------------------------------------------------------------------
+
+
+def build_fixture(name: str, root: Path, **options: bool) -> BuiltSite:
+    """Build the fixture project with the given name in the given directory.
+
+    The fixture is copied, so builds never write into the checked-in files.
+    Options are passed to the build, like the command line interface does.
+    """
+    copy_fixture(name, root)
+    return build_site(root, **options)
+
+
+def copy_fixture(name: str, root: Path) -> Path:
+    """Copy the fixture project with the given name to the given directory.
+
+    This allows to adjust the fixture before building it with `build_site`.
+    Returns the path to the configuration file.
+    """
+    shutil.copytree(FIXTURES / name, root, dirs_exist_ok=True)
+    return root / "zensical.toml"
+
+
+def build_site(root: Path, **options: bool) -> BuiltSite:
+    """Build the project in the given directory."""
+    summary = build(
+        str(root / "zensical.toml"),
+        {
+            "clean": True,
+            "strict": False,
+            "reproducible": False,
+            "prune_cache": False,
+            "site_dir": None,
+            "cache_dir": None,
+            **options,
+        },
+    )
+    return BuiltSite(root, summary)
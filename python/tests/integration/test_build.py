@@ -0,0 +1,344 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import json
+import os
+import re
+import stat
+import tarfile
+from typing import TYPE_CHECKING
+
+import pytest
+
+from zensical import build
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def _options(*, clean: bool) -> dict[str, bool | None]:
+    """Return build options, as passed by the command line interface."""
+    return {
+        "clean": clean,
+        "strict": False,
+        "reproducible": False,
+        "prune_cache": False,
+        "site_dir": None,
+        "cache_dir": None,
+    }
+
+
+def _create_project(root: Path) -> Path:
+    """Create a fixture project with two pages and an image."""
+    docs = root / "docs"
+    docs.mkdir()
+    (docs / "index.md").write_text("# Home\n\nWelcome.\n")
+    (docs / "about.md").write_text("# About\n\n![Image](image.png)\n")
+    (docs / "image.png").write_bytes(b"\x89PNG\r\n\x1a\n")
+    config_file = root / "zensical.toml"
+    config_file.write_text('[project]\nsite_name = "Test"\n')
+    return config_file
+
+
+def _output_size(root: Path) -> int:
+    """Return the total size of all files in the site directory."""
+    site = root / "site"
+    files = [path for path in site.rglob("*") if path.is_file()]
+    return sum(path.stat().st_size for path in files)
+
+
+def _read_site(root: Path) -> dict[str, bytes]:
+    """Return the contents of all files in the site directory."""
+    site = root / "site"
+    files = [path for path in site.rglob("*") if path.is_file()]
+    return {
+        path.relative_to(site).as_posix(): path.read_bytes() for path in files
+    }
+
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_build_returns_summary(tmp_path: Path) -> None:
+    """Build summary contains exact counts for the fixture project."""
+    config_file = _create_project(tmp_path)
+    summary = build(str(config_file), _options(clean=True))
+    assert summary["pages"] == 2
+    assert summary["cached_pages"] == 0
+    assert summary["templates"] == 2
+    assert summary["warnings"] == 0
+    assert summary["errors"] == 0
+    assert summary["search_index_bytes"] == (
+        (tmp_path / "site" / "search.json").stat().st_size
+    )
+    assert summary["output_bytes"] == _output_size(tmp_path)
+
+
+def test_build_summary_counts_cached_pages(tmp_path: Path) -> None:
+    """Pages are served from the cache when building again."""
+    config_file = _create_project(tmp_path)
+    build(str(config_file), _options(clean=True))
+    summary = build(str(config_file), _options(clean=False))
+    assert summary["pages"] == 2
+    assert summary["cached_pages"] == 2
+
+
+def test_build_writes_all_outputs_before_returning(tmp_path: Path) -> None:
+    """Pages and the search index exist once the build has finished."""
+    config_file = _create_project(tmp_path)
+    for clean in (True, False):
+        build(str(config_file), _options(clean=clean))
+        site = tmp_path / "site"
+        assert (site / "index.html").is_file()
+        assert (site / "about" / "index.html").is_file()
+        assert (site / "search.json").is_file()
+
+
+def test_build_offline_emits_relative_urls(tmp_path: Path) -> None:
+    """Offline builds don't contain root-relative URLs, and load search.js."""
+    config_file = _create_project(tmp_path)
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n'
+        'site_url = "https://example.com/docs/"\n'
+        "\n[project.plugins.offline]\n"
+        "\n[project.theme]\n"
+        'features = ["navigation.instant", "navigation.instant.prefetch"]\n'
+    )
+    summary = build(str(config_file), _options(clean=True))
+
+    # Root-relative URLs would resolve to the root of the file system
+    pattern = re.compile(r"""\s(?:href|src)=["']/(?!/)""")
+    site = _read_site(tmp_path)
+    pages = {
+        name: data for name, data in site.items() if name.endswith(".html")
+    }
+    assert "index.html" in pages
+    assert "about.html" in pages
+    for name, data in pages.items():
+        html = data.decode()
+        assert not pattern.search(html), name
+        assert "search.js" in html, name
+
+    # Search index is provided as a script, and instant navigation disabled
+    assert "search.js" in site
+    assert len(summary["notes"]) == 2
+
+
+def test_build_summary_lists_slowest_pages(tmp_path: Path) -> None:
+    """Slowest pages are reported, unless they are served from the cache."""
+    config_file = _create_project(tmp_path)
+    summary = build(str(config_file), _options(clean=True))
+    pages = sorted(timing["page"] for timing in summary["slowest_pages"])
+    assert pages == ["docs/about.md", "docs/index.md"]
+    summary = build(str(config_file), _options(clean=False))
+    assert summary["slowest_pages"] == []
+
+
+def test_build_multiple_sites(tmp_path: Path) -> None:
+    """Each site is built from its own docs into its own site directory."""
+    for name in ("guide", "api"):
+        docs = tmp_path / name
+        docs.mkdir()
+        (docs / "index.md").write_text(f"# {name.title()}\n")
+        (docs / f"{name}.md").write_text(f"# {name.title()} page\n")
+    config_file = tmp_path / "zensical.toml"
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n'
+        '[[project.sites]]\ndocs_dir = "guide"\nsite_dir = "site/guide"\n'
+        '[[project.sites]]\ndocs_dir = "api"\nsite_dir = "site/api"\n'
+    )
+    summary = build(str(config_file), _options(clean=True))
+    assert summary["pages"] == 4
+    assert [site["site_dir"] for site in summary["sites"]] == [
+        "site/guide",
+        "site/api",
+    ]
+    assert [site["pages"] for site in summary["sites"]] == [2, 2]
+    site = tmp_path / "site"
+    assert (site / "guide" / "guide" / "index.html").is_file()
+    assert not (site / "guide" / "api").exists()
+    assert (site / "api" / "api" / "index.html").is_file()
+    assert not (site / "api" / "guide").exists()
+
+
+def test_build_into_archive_matches_site_directory(tmp_path: Path) -> None:
+    """Archive contains the same files as the site directory."""
+    config_file = _create_project(tmp_path)
+    build(str(config_file), _options(clean=True))
+    expected = _read_site(tmp_path)
+
+    # Build again into a tarball, and extract all entries
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n\n'
+        '[project.build.output]\nformat = "tar.gz"\npath = "site.tar.gz"\n'
+    )
+    summary = build(str(config_file), _options(clean=True))
+    archive = tmp_path / "site.tar.gz"
+    assert summary["output_bytes"] == archive.stat().st_size
+    with tarfile.open(archive) as tar:
+        members = [member for member in tar.getmembers() if member.isfile()]
+        assert all(member.mode == 0o644 for member in members)
+        files = {}
+        for member in members:
+            file = tar.extractfile(member)
+            assert file is not None
+            files[member.name] = file.read()
+    assert files == expected
+
+
+def test_build_into_archive_without_clean_contains_assets(
+    tmp_path: Path,
+) -> None:
+    """Archive contains assets that are up to date in the site directory."""
+    config_file = _create_project(tmp_path)
+    build(str(config_file), _options(clean=True))
+    expected = _read_site(tmp_path)
+
+    # Build again into a tarball, keeping the site directory and cache
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n\n'
+        '[project.build.output]\nformat = "tar"\npath = "site.tar"\n'
+    )
+    build(str(config_file), _options(clean=False))
+    with tarfile.open(tmp_path / "site.tar") as tar:
+        members = [member for member in tar.getmembers() if member.isfile()]
+        names = [member.name for member in members]
+        assert len(names) == len(set(names))
+        files = {}
+        for member in members:
+            file = tar.extractfile(member)
+            assert file is not None
+            files[member.name] = file.read()
+    assert files == expected
+
+
+def test_build_writes_manifest(tmp_path: Path) -> None:
+    """Manifest lists all pages with a stable schema."""
+    config_file = _create_project(tmp_path)
+    (tmp_path / "docs" / "about.md").write_text(
+        "---\ndescription: About us\nauthor: Jane\nstatus: new\n---\n\n"
+        "# About\n"
+    )
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n\n'
+        '[project.build]\nmanifest = "manifest.json"\n'
+        'manifest_meta = ["author"]\n'
+    )
+    build(str(config_file), _options(clean=True))
+    manifest = json.loads((tmp_path / "site" / "manifest.json").read_text())
+    assert manifest["version"] == 1
+    assert [page["url"] for page in manifest["pages"]] == ["", "about/"]
+    about = manifest["pages"][1]
+    assert sorted(about) == [
+        "description",
+        "meta",
+        "sections",
+        "source",
+        "tags",
+        "title",
+        "url",
+    ]
+    assert about["description"] == "About us"
+    assert about["meta"] == {"author": "Jane"}
+    assert about["source"] == "about.md"
+    assert about["title"] == "About"
+    assert about["tags"] == []
+    assert about["sections"] == []
+
+
+def test_build_suggests_new_location_of_renamed_page(
+    tmp_path: Path, capfd: pytest.CaptureFixture[str]
+) -> None:
+    """Links to a renamed page with a stable id point to its new location."""
+    config_file = _create_project(tmp_path)
+    docs = tmp_path / "docs"
+    (docs / "index.md").write_text("# Home\n\n[Setup](setup.md)\n")
+    (docs / "setup.md").write_text("---\nid: setup\n---\n\n# Setup\n")
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n\n'
+        '[project.build]\nmanifest = "manifest.json"\n'
+    )
+    build(str(config_file), _options(clean=True))
+    manifest = json.loads((tmp_path / "site" / "manifest.json").read_text())
+    pages = manifest["pages"]
+    setup = next(page for page in pages if page.get("id") == "setup")
+    assert setup["source"] == "setup.md"
+    capfd.readouterr()
+
+    # Rename page, and build again with the snapshot of the first build
+    (docs / "setup.md").rename(docs / "getting-started.md")
+    build(str(config_file), _options(clean=False))
+    _, err = capfd.readouterr()
+    assert "page does not exist" in err
+    assert "page with id 'setup' was moved to 'getting-started.md'" in err
+
+
+def test_build_reports_duplicate_page_ids(tmp_path: Path) -> None:
+    """Duplicate page ids are reported as errors, but don't abort the build."""
+    config_file = _create_project(tmp_path)
+    docs = tmp_path / "docs"
+    (docs / "setup.md").write_text("---\nid: setup\n---\n\n# Setup\n")
+    (docs / "install.md").write_text("---\nid: setup\n---\n\n# Install\n")
+    (docs / "post.md").write_text("---\nslug: setup\n---\n\n# Post\n")
+    summary = build(str(config_file), _options(clean=True))
+    assert summary["errors"] == 1
+    assert (tmp_path / "site" / "setup" / "index.html").is_file()
+    assert (tmp_path / "site" / "install" / "index.html").is_file()
+
+
+@pytest.mark.skipif(os.name != "posix", reason="requires POSIX permissions")
+def test_build_from_read_only_source(tmp_path: Path) -> None:
+    """Site and cache directories can be moved out of a read-only source."""
+    root = tmp_path / "project"
+    root.mkdir()
+    config_file = _create_project(root)
+    paths = [root, *root.rglob("*")]
+    for path in paths:
+        mode = path.stat().st_mode
+        path.chmod(mode & ~(stat.S_IWUSR | stat.S_IWGRP | stat.S_IWOTH))
+    try:
+        site_dir, cache_dir = tmp_path / "site", tmp_path / "cache"
+        build(
+            str(config_file),
+            {
+                **_options(clean=True),
+                "site_dir": str(site_dir),
+                "cache_dir": str(cache_dir),
+            },
+        )
+        assert (site_dir / "index.html").is_file()
+        assert (site_dir / "about" / "index.html").is_file()
+        assert cache_dir.is_dir()
+        assert not (root / "site").exists()
+        assert not (root / ".cache").exists()
+    finally:
+        for path in paths:
+            path.chmod(path.stat().st_mode | stat.S_IWUSR)
@@ -0,0 +1,198 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import shutil
+from typing import TYPE_CHECKING
+
+import pytest
+
+from tests.integration.harness import FIXTURES
+
+from zensical import get_config
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_formats_normalize_to_equal_config(tmp_path: Path) -> None:
+    """Equivalent mkdocs.yml and zensical.toml files have equal settings."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    yaml = get_config(str(tmp_path / "mkdocs.yml"))
+    toml = get_config(str(tmp_path / "zensical.toml"))
+    assert yaml["extra"]["social"] == toml["extra"]["social"]
+    assert yaml["mdx_configs_hash"] == toml["mdx_configs_hash"]
+    assert yaml == toml
+
+
+def test_toml_reports_unsupported_keys(
+    tmp_path: Path, capfd: pytest.CaptureFixture[str]
+) -> None:
+    """Settings outside of the project table or only for MkDocs are reported."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    config_file = tmp_path / "zensical.toml"
+    content = config_file.read_text().replace(
+        "[project]\n", '[project]\nINHERIT = "base.yml"\n'
+    )
+    config_file.write_text(f'site_url = "https://example.com"\n{content}')
+    config = get_config(str(config_file))
+    assert config["unsupported_keys"] == ["site_url", "INHERIT"]
+    assert config["site_url"] is None
+    assert (
+        "[warning] Setting 'INHERIT' is not supported via zensical.toml"
+        in capfd.readouterr().err
+    )
+
+
+def test_yaml_reports_unsupported_keys(
+    tmp_path: Path, capfd: pytest.CaptureFixture[str]
+) -> None:
+    """The project table of zensical.toml is reported in mkdocs.yml."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    config_file = tmp_path / "mkdocs.yml"
+    with config_file.open("a") as f:
+        f.write("project:\n  site_name: Other\n")
+    config = get_config(str(config_file))
+    assert config["unsupported_keys"] == ["project"]
+    assert config["site_name"] == "Formats"
+    assert (
+        "[warning] Setting 'project' is not supported via mkdocs.yml"
+        in capfd.readouterr().err
+    )
+
+
+def test_sites_override_project_settings(tmp_path: Path) -> None:
+    """The first of multiple sites is returned, with its overrides applied."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    (tmp_path / "guide").mkdir()
+    (tmp_path / "api").mkdir()
+    config_file = tmp_path / "zensical.toml"
+    content = config_file.read_text()
+    for table in ("[[sites]]", "[[project.sites]]"):
+        config_file.write_text(
+            f'{content}\n{table}\nsite_name = "Guide"\ndocs_dir = "guide"\n'
+            f'site_dir = "site/guide"\n{table}\ndocs_dir = "api"\n'
+            'site_dir = "site/api"\n'
+        )
+        config = get_config(str(config_file))
+        assert config["site_name"] == "Guide"
+        assert config["docs_dir"] == "guide"
+        assert config["site_dir"] == "site/guide"
+        assert "sites" not in config
+
+
+def test_sites_reject_shared_settings(tmp_path: Path) -> None:
+    """Sites may only override their own settings, and not share outputs."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    config_file = tmp_path / "zensical.toml"
+    content = config_file.read_text()
+    shared = '[[sites]]\nsite_name = "A"\n[[sites]]\nsite_name = "B"\n'
+    for sites, message in (
+        ('[[sites]]\ntheme = "a"\n', "can't be overridden"),
+        (shared, "must not share the same docs_dir"),
+    ):
+        config_file.write_text(f"{content}\n{sites}")
+        with pytest.raises(Exception, match=message):
+            get_config(str(config_file))
+    config_file.write_text(f"sites = []\n{content}")
+    with pytest.raises(Exception, match="non-empty list"):
+        get_config(str(config_file))
+
+
+def test_repo_name_takes_precedence(tmp_path: Path) -> None:
+    """An explicitly set repository name is never replaced by a derived one."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    config_file = tmp_path / "zensical.toml"
+    content = config_file.read_text().replace(
+        'repo_url = "https://github.com/example/project"\n',
+        'repo_url = "https://github.com/zensical/zensical"\n'
+        'repo_name = "zensical"\n',
+    )
+    config_file.write_text(content)
+    config = get_config(str(config_file))
+    assert config["repo_name"] == "zensical"
+    assert config["repo_icon"] == "fontawesome/brands/github"
+
+
+@pytest.mark.parametrize(
+    ("repo_url", "repo_name", "repo_icon"),
+    [
+        (
+            "https://github.com/example/project",
+            "example/project",
+            "fontawesome/brands/github",
+        ),
+        (
+            "https://gitlab.com/example/project/",
+            "example/project",
+            "fontawesome/brands/gitlab",
+        ),
+        (
+            "https://bitbucket.org/example/project.git",
+            "example/project",
+            "fontawesome/brands/bitbucket",
+        ),
+        (
+            "https://git.example.com/example/project.git",
+            "git.example.com",
+            "fontawesome/brands/git-alt",
+        ),
+    ],
+)
+def test_repo_name_is_derived(
+    tmp_path: Path, repo_url: str, repo_name: str, repo_icon: str
+) -> None:
+    """Repository name and icon are derived from the repository URL."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    config_file = tmp_path / "zensical.toml"
+    content = config_file.read_text().replace(
+        "https://github.com/example/project", repo_url
+    )
+    config_file.write_text(content)
+    config = get_config(str(config_file))
+    assert config["repo_name"] == repo_name
+    assert config["repo_icon"] == repo_icon
+
+
+def test_repo_url_must_be_http(
+    tmp_path: Path, capfd: pytest.CaptureFixture[str]
+) -> None:
+    """SSH-style repository URLs are reported, as they can't be linked to."""
+    shutil.copytree(FIXTURES / "formats", tmp_path, dirs_exist_ok=True)
+    config_file = tmp_path / "zensical.toml"
+    content = config_file.read_text().replace(
+        "https://github.com/example/project", "git@github.com:example/project"
+    )
+    config_file.write_text(content)
+    config = get_config(str(config_file))
+    assert config["repo_name"] is None
+    assert config["repo_icon"] is None
+    assert (
+        "[warning] Setting 'repo_url' must be an HTTP(S) URL, but got "
+        "'git@github.com:example/project'" in capfd.readouterr().err
+    )
@@ -0,0 +1,567 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import shutil
+from typing import TYPE_CHECKING
+
+import pytest
+
+from tests.integration.harness import build_fixture, build_site, copy_fixture
+from zensical import get_navigation
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_minimal_fixture_builds_pages(tmp_path: Path) -> None:
+    """Pages are written to directory URLs, and the build is clean."""
+    site = build_fixture("minimal", tmp_path)
+    assert site.exists("index.html")
+    assert site.exists("about/index.html")
+    assert site.exists("search.json")
+    assert site.summary["pages"] == 2
+    assert site.diagnostics == []
+
+
+def test_minimal_fixture_resolves_links(tmp_path: Path) -> None:
+    """Links to Markdown files are resolved relative to each page."""
+    site = build_fixture("minimal", tmp_path)
+    assert site.select("index.html", 'a[href="about/"]')
+    assert site.select("about/index.html", 'a[href="../#home"]')
+
+
+def test_nav_fixture_follows_configured_order(tmp_path: Path) -> None:
+    """Navigation links appear in the configured order on every page."""
+    site = build_fixture("nav", tmp_path)
+    for path, prefix in [
+        ("index.html", ""),
+        ("guide/index.html", "../"),
+        ("guide/setup/index.html", "../../"),
+    ]:
+        hrefs = [link["href"] for link in site.select(path, "a[href]")]
+        expected = [f"{prefix}guide/", f"{prefix}guide/setup/"]
+        found = [href for href in hrefs if href in expected]
+        assert found[: len(expected)] == expected, path
+
+
+def test_nav_fixture_displays_repository_name(tmp_path: Path) -> None:
+    """Repository name is derived from the repository URL and displayed."""
+    site = build_fixture("nav", tmp_path)
+    repository = site.select_one("index.html", ".md-source__repository")
+    assert "example/project" in repository.get_text()
+
+
+@pytest.mark.parametrize(
+    ("use_directory_urls", "expected"),
+    [
+        (
+            True,
+            {
+                "index.md": "index.html",
+                "api-guide.md": "api-guide/index.html",
+                "about/index.md": "about/index.html",
+                "about/license.md": "about/license/index.html",
+                "help/README.md": "help/index.html",
+            },
+        ),
+        (
+            False,
+            {
+                "index.md": "index.html",
+                "api-guide.md": "api-guide.html",
+                "about/index.md": "about/index.html",
+                "about/license.md": "about/license.html",
+                "help/README.md": "help/index.html",
+            },
+        ),
+    ],
+)
+def test_urls_fixture_matches_mkdocs(
+    tmp_path: Path, use_directory_urls: bool, expected: dict[str, str]
+) -> None:
+    """Pages are written to the same files as in MkDocs."""
+    config_file = copy_fixture("urls", tmp_path)
+    value = str(use_directory_urls).lower()
+    config_file.write_text(
+        f'[project]\nsite_name = "URLs"\nuse_directory_urls = {value}\n'
+    )
+    site = build_site(tmp_path)
+    for source, file in expected.items():
+        assert site.exists(file), source
+    assert site.summary["pages"] == len(expected)
+    assert site.diagnostics == []
+
+
+@pytest.mark.parametrize(
+    ("use_directory_urls", "path", "hrefs"),
+    [
+        (
+            True,
+            "index.html",
+            ["api-guide/", "about/", "about/license/", "help/"],
+        ),
+        (True, "about/license/index.html", ["../../"]),
+        (True, "help/index.html", ["../api-guide/"]),
+        (
+            False,
+            "index.html",
+            [
+                "api-guide.html",
+                "about/index.html",
+                "about/license.html",
+                "help/index.html",
+            ],
+        ),
+        (False, "about/license.html", ["../index.html"]),
+        (False, "help/index.html", ["../api-guide.html"]),
+    ],
+)
+def test_urls_fixture_resolves_links(
+    tmp_path: Path, use_directory_urls: bool, path: str, hrefs: list[str]
+) -> None:
+    """Links to Markdown files are resolved to the URLs of their pages."""
+    config_file = copy_fixture("urls", tmp_path)
+    value = str(use_directory_urls).lower()
+    config_file.write_text(
+        f'[project]\nsite_name = "URLs"\nuse_directory_urls = {value}\n'
+    )
+    site = build_site(tmp_path)
+    found = [link["href"] for link in site.select(path, "a[href]")]
+    assert set(hrefs) <= set(found)
+
+
+def test_urls_fixture_links_all_pages_in_navigation(tmp_path: Path) -> None:
+    """Generated navigation links all pages relative to each page."""
+    site = build_fixture("urls", tmp_path)
+    urls = ["api-guide/", "about/", "about/license/", "help/"]
+    for path, prefix in [
+        ("index.html", ""),
+        ("about/license/index.html", "../../"),
+        ("help/index.html", "../"),
+    ]:
+        hrefs = {link["href"] for link in site.select(path, "a[href]")}
+        for url in urls:
+            if path != f"{url}index.html":
+                assert f"{prefix}{url}" in hrefs, (path, url)
+
+
+def test_paths_fixture_leaks_no_absolute_paths(tmp_path: Path) -> None:
+    """Absolute paths of a root containing spaces never appear in output."""
+    root = tmp_path / "my project"
+    site = build_fixture("paths", root, strict=True)
+    for path in site.files():
+        if path.endswith(".html"):
+            data = site.read(path)
+            assert str(root) not in data, path
+            assert root.as_posix() not in data, path
+    data = site.read("guide/setup/index.html")
+    assert "<!-- guide/setup.md| -->" in data
+    edit = site.select_one("guide/setup/index.html", 'a[href$="setup.md"]')
+    assert edit["href"] == (
+        "https://github.com/example/project/edit/main/docs/guide/setup.md"
+    )
+    assert site.diagnostics == []
+
+
+def test_paths_fixture_reports_leaked_paths(tmp_path: Path) -> None:
+    """Absolute paths leaking into output via templates are reported."""
+    root = tmp_path / "my project"
+    config_file = copy_fixture("paths", root)
+    with config_file.open("a") as f:
+        f.write("\n[project.validation]\nabsolute_paths = true\n")
+    template = root / "overrides" / "main.html"
+    content = template.read_text().replace("page.path", "config.root_dir")
+    template.write_text(content)
+    site = build_site(root)
+    warnings = [
+        item["message"]
+        for item in site.diagnostics
+        if item["severity"] == "warning"
+    ]
+    assert len(warnings) == 2
+    assert all("leaks" in message for message in warnings)
+    assert all("my project" in message for message in warnings)
+
+
+def test_math_fixture_renders_math_and_diagrams(tmp_path: Path) -> None:
+    """Math is left to the client, and diagrams are rendered as fences."""
+    site = build_fixture("math", tmp_path)
+    math = site.select("index.html", ".arithmatex")
+    assert [element.name for element in math] == ["span", "div"]
+    assert r"\(a^2 + b^2 = c^2\)" in math[0].get_text()
+    diagram = site.select_one("index.html", "pre.mermaid")
+    assert "graph LR" in diagram.get_text()
+    assert site.diagnostics == []
+
+
+def test_diagnostics_are_included_in_summary(tmp_path: Path) -> None:
+    """Diagnostics of the build can be inspected after the build."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('site_url = "https://example.com/docs/"\n')
+    (tmp_path / "docs" / "index.md").write_text("[Elsewhere](/elsewhere/)\n")
+    site = build_site(tmp_path)
+    warnings = [
+        item["message"]
+        for item in site.diagnostics
+        if item["severity"] == "warning"
+    ]
+    assert any("'/elsewhere/'" in message for message in warnings)
+    assert site.summary["warnings"] == len(warnings)
+
+
+def test_root_relative_links_in_templates_are_reported(tmp_path: Path) -> None:
+    """Rendered pages are checked, including links emitted by templates."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('site_url = "https://example.com/docs/"\n\n')
+        f.write('[project.theme]\ncustom_dir = "overrides"\n\n')
+        f.write('[project.validation]\nroot_relative_links = "error"\n')
+    (tmp_path / "overrides").mkdir()
+    (tmp_path / "overrides" / "main.html").write_text(
+        '<a href="/legal/">Legal</a>{{ page.content }}\n'
+    )
+    site = build_site(tmp_path)
+    errors = [
+        item["message"]
+        for item in site.diagnostics
+        if item["severity"] == "error"
+    ]
+    assert any("'/legal/'" in message for message in errors)
+    assert site.summary["warnings"] == 0
+
+
+def test_well_known_hidden_files_are_copied(tmp_path: Path) -> None:
+    """Well-known hidden files are copied, but hidden directories aren't."""
+    copy_fixture("minimal", tmp_path)
+    docs_dir = tmp_path / "docs"
+    (docs_dir / ".nojekyll").write_text("")
+    (docs_dir / ".htaccess").write_text("Options -Indexes\n")
+    (docs_dir / ".DS_Store").write_text("")
+    (docs_dir / ".git").mkdir()
+    (docs_dir / ".git" / "HEAD").write_text("ref: refs/heads/main\n")
+    site = build_site(tmp_path)
+    assert site.exists(".nojekyll")
+    assert site.exists(".htaccess")
+    assert not site.exists(".DS_Store")
+    assert not site.exists(".git/HEAD")
+
+
+def test_hidden_files_matching_patterns_are_copied(tmp_path: Path) -> None:
+    """Hidden files matching the configured patterns are copied."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('\n[project.build]\ninclude_hidden = ["**/.well-known/**"]\n')
+    well_known = tmp_path / "docs" / ".well-known"
+    well_known.mkdir()
+    (well_known / "security.txt").write_text("Contact: security@example.com\n")
+    (tmp_path / "docs" / ".git").mkdir()
+    (tmp_path / "docs" / ".git" / "HEAD").write_text("ref: refs/heads/main\n")
+    site = build_site(tmp_path)
+    assert site.exists(".well-known/security.txt")
+    assert not site.exists(".git/HEAD")
+
+
+def test_pages_with_odd_encodings_are_built(tmp_path: Path) -> None:
+    """BOMs and CRLF are handled, and invalid UTF-8 is reported."""
+    copy_fixture("minimal", tmp_path)
+    docs_dir = tmp_path / "docs"
+    (docs_dir / "bom.md").write_bytes(
+        b"\xef\xbb\xbf---\r\ntitle: Byte order\r\n---\r\n\r\n# Heading\r\n"
+    )
+    (docs_dir / "latin.md").write_bytes(b"# Caf\xe9\n")
+    site = build_site(tmp_path)
+    title = site.select_one("bom/index.html", "title")
+    assert title.get_text().startswith("Byte order")
+    assert site.exists("latin/index.html")
+    warnings = [
+        item
+        for item in site.diagnostics
+        if "not valid UTF-8 at byte 5" in item["message"]
+    ]
+    assert len(warnings) == 1
+    assert warnings[0]["location"]["line"] == 1
+
+
+def test_front_matter_is_validated_against_schema(tmp_path: Path) -> None:
+    """Unknown keys and wrong types are reported, if a schema is defined."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write(
+            "\n[project.meta_schema.keys]\n"
+            'title = "string"\ndraft = "bool"\n'
+        )
+    (tmp_path / "docs" / "typo.md").write_text(
+        "---\nttile: Typo\ndraft: maybe\n---\n\n# Typo\n"
+    )
+    site = build_site(tmp_path)
+    messages = [item["message"] for item in site.diagnostics]
+    assert any("did you mean 'title'?" in m for m in messages)
+    assert any("'draft' must be of type bool" in m for m in messages)
+
+
+def test_front_matter_is_permissive_without_schema(tmp_path: Path) -> None:
+    """Without a schema, any front matter is accepted."""
+    copy_fixture("minimal", tmp_path)
+    (tmp_path / "docs" / "typo.md").write_text(
+        "---\nttile: Typo\ndraft: maybe\n---\n\n# Typo\n"
+    )
+    site = build_site(tmp_path)
+    assert not any(
+        "front matter" in item["message"] for item in site.diagnostics
+    )
+
+
+def test_nav_entries_matching_no_page_are_reported(tmp_path: Path) -> None:
+    """Mistyped navigation entries are reported with the closest page."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('nav = ["index.md", "abuot.md", "about.md"]\n')
+    site = build_site(tmp_path)
+    messages = [item["message"] for item in site.diagnostics]
+    assert any(
+        "'abuot.md' matches no page, did you mean 'about.md'?" in message
+        for message in messages
+    )
+    assert site.exists("about/index.html")
+
+
+def test_extra_files_outside_of_docs_are_copied(tmp_path: Path) -> None:
+    """Extra files outside of the docs directory are copied to the site."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('extra_css = ["../shared/brand.css"]\n')
+    (tmp_path / "shared").mkdir()
+    (tmp_path / "shared" / "brand.css").write_text("body { color: red }\n")
+    site = build_site(tmp_path)
+    assert site.read("assets/stylesheets/brand.css") == "body { color: red }\n"
+    assert not any(
+        "not found" in item["message"] for item in site.diagnostics
+    )
+
+
+def test_extra_files_with_the_same_name_are_kept_apart(tmp_path: Path) -> None:
+    """Extra files with the same name in different directories are copied."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('extra_css = ["../a/brand.css", "../b/brand.css"]\n')
+    for name in ["a", "b"]:
+        (tmp_path / name).mkdir()
+        (tmp_path / name / "brand.css").write_text(f"/* {name} */\n")
+    site = build_site(tmp_path)
+    assert site.read("assets/stylesheets/brand.css") == "/* a */\n"
+    assert site.read("assets/stylesheets/brand-1.css") == "/* b */\n"
+    links = site.select("index.html", 'link[href*="assets/stylesheets/brand"]')
+    assert [link["href"] for link in links] == [
+        "assets/stylesheets/brand.css",
+        "assets/stylesheets/brand-1.css",
+    ]
+
+
+def test_relative_canonical_urls_are_reported(tmp_path: Path) -> None:
+    """Canonical URLs that are not absolute are ignored and reported."""
+    copy_fixture("minimal", tmp_path)
+    about = tmp_path / "docs" / "about.md"
+    about.write_text("---\ncanonical_url: /about/\n---\n\n" + about.read_text())
+    site = build_site(tmp_path)
+    warnings = [
+        item for item in site.diagnostics if "canonical URL" in item["message"]
+    ]
+    assert len(warnings) == 1
+    assert warnings[0]["severity"] == "warning"
+
+
+def test_permalinks_are_applied_to_matching_pages(tmp_path: Path) -> None:
+    """Pages matching a permalink are written to the expanded URL."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write(
+            "\n[project.permalinks]\n"
+            '"blog/**" = "{path}/{date:%Y/%m}/{slug}"\n'
+        )
+    (tmp_path / "docs" / "blog").mkdir()
+    (tmp_path / "docs" / "blog" / "2024-05-21-hello.md").write_text(
+        "# Hello\n"
+    )
+    (tmp_path / "docs" / "blog" / "undated.md").write_text("# Undated\n")
+    site = build_site(tmp_path)
+    assert site.exists("blog/2024/05/hello/index.html")
+    assert not site.exists("blog/2024-05-21-hello/index.html")
+    assert site.exists("blog/undated/index.html")
+    assert "blog/2024/05/hello/" in site.read("search.json")
+
+
+def test_permalinks_colliding_with_pages_are_reported(tmp_path: Path) -> None:
+    """Permalinks that map to the URL of another page are reported."""
+    config_file = copy_fixture("minimal", tmp_path)
+    with config_file.open("a") as f:
+        f.write('\n[project.permalinks]\n"blog/**" = "{slug}"\n')
+    (tmp_path / "docs" / "blog").mkdir()
+    (tmp_path / "docs" / "blog" / "about.md").write_text("# About\n")
+    site = build_site(tmp_path)
+    messages = [item["message"] for item in site.diagnostics]
+    assert any("both map to 'about/'" in message for message in messages)
+
+
+def test_blog_fixture_paginates_listings(tmp_path: Path) -> None:
+    """Posts are listed newest first on index, category and archive pages."""
+    site = build_fixture("blog", tmp_path)
+    for path in [
+        "blog/index.html",
+        "blog/page/2/index.html",
+        "blog/page/3/index.html",
+        "blog/category/python/index.html",
+        "blog/category/rust/index.html",
+        "blog/category/rust/page/2/index.html",
+        "blog/archive/2024/index.html",
+        "blog/archive/2024/page/2/index.html",
+        "blog/archive/2023/index.html",
+    ]:
+        assert site.exists(path), path
+    assert not site.exists("blog/page/4/index.html")
+    assert not site.exists("blog/category/python/page/2/index.html")
+    assert site.exists("blog/posts/first/index.html")
+    assert site.diagnostics == []
+
+    # Posts are sorted by date, and link to their categories
+    posts = site.select("blog/index.html", "a.post")
+    assert [post.get_text() for post in posts] == ["Fifth", "Fourth"]
+    assert posts[1]["href"] == "posts/fourth/"
+    categories = site.select("blog/page/2/index.html", "a.category")
+    assert [category["href"] for category in categories] == [
+        "../../category/rust/",
+        "../../category/python/",
+        "../../category/python/",
+    ]
+    titles = site.select("blog/category/rust/index.html", "a.post")
+    assert [title.get_text() for title in titles] == ["Fourth", "Third"]
+
+
+def test_blog_index_page_introduces_listing(tmp_path: Path) -> None:
+    """A page at the URL of the blog index is rendered as the listing."""
+    copy_fixture("blog", tmp_path)
+    index = tmp_path / "docs" / "blog" / "index.md"
+    index.write_text("# News\n\nLatest posts.\n")
+    site = build_site(tmp_path)
+    assert site.diagnostics == []
+    intro = site.select_one("blog/index.html", ".intro")
+    assert "Latest posts." in intro.get_text()
+    posts = site.select("blog/index.html", "a.post")
+    assert [post.get_text() for post in posts] == ["Fifth", "Fourth"]
+
+
+def test_blog_fixture_links_pages(tmp_path: Path) -> None:
+    """Only existing previous and next pages are linked."""
+    site = build_fixture("blog", tmp_path)
+    first = "blog/index.html"
+    assert not site.select(first, 'a[rel="prev"]')
+    assert site.select_one(first, 'a[rel="next"]')["href"] == "page/2/"
+    middle = "blog/page/2/index.html"
+    assert site.select_one(middle, 'a[rel="prev"]')["href"] == "../../"
+    assert site.select_one(middle, 'a[rel="next"]')["href"] == "../3/"
+    last = "blog/page/3/index.html"
+    assert site.select_one(last, 'a[rel="prev"]')["href"] == "../2/"
+    assert not site.select(last, 'a[rel="next"]')
+
+    # Single pages link to neither
+    single = "blog/archive/2023/index.html"
+    assert not site.select(single, 'a[rel="prev"], a[rel="next"]')
+
+
+def test_blog_fixture_without_posts_has_empty_index(tmp_path: Path) -> None:
+    """An empty blog has a single index page without pagination links."""
+    copy_fixture("blog", tmp_path)
+    shutil.rmtree(tmp_path / "docs" / "blog")
+    site = build_site(tmp_path)
+    assert site.exists("blog/index.html")
+    assert not site.exists("blog/page/2/index.html")
+    assert not site.select("blog/index.html", "a.post")
+    assert not site.select("blog/index.html", 'a[rel="prev"], a[rel="next"]')
+
+
+def test_blog_fixture_injects_navigation(tmp_path: Path) -> None:
+    """Posts are replaced by the blog section in the navigation."""
+    config_file = copy_fixture("blog", tmp_path)
+    nav = get_navigation(str(config_file))
+    assert [item["title"] for item in nav] == ["Home", "Blog"]
+    blog = nav[1]
+    assert [item["title"] for item in blog["children"]] == [
+        "Blog",
+        "Archive",
+        "Categories",
+    ]
+    archive, categories = blog["children"][1:]
+    assert [item["url"] for item in archive["children"]] == [
+        "blog/archive/2024/",
+        "blog/archive/2023/",
+    ]
+    assert [item["title"] for item in categories["children"]] == [
+        "Python",
+        "Rust",
+    ]
+
+
+def test_i18n_fixture_renders_fallbacks(tmp_path: Path) -> None:
+    """Pages missing in a language are rendered from the default language."""
+    site = build_fixture("i18n", tmp_path)
+    assert site.exists("en/index.html")
+    assert site.exists("en/guide/index.html")
+    assert site.exists("de/index.html")
+    assert site.exists("de/guide/index.html")
+
+    # Fallback contains the content of the default language
+    fallback = "de/guide/index.html"
+    assert "only available in English" in site.read(fallback)
+    assert site.select(fallback, 'meta[name="translated"]')
+    assert not site.select("de/index.html", 'meta[name="translated"]')
+    assert site.diagnostics == []
+
+
+def test_i18n_fixture_links_alternate_languages(tmp_path: Path) -> None:
+    """Translated pages link to each other as alternates."""
+    site = build_fixture("i18n", tmp_path)
+    alternates = site.select("en/index.html", 'link[rel="alternate"]')
+    assert {link["hreflang"] for link in alternates} == {"en", "de"}
+
+
+def test_builds_are_byte_identical(tmp_path: Path) -> None:
+    """Building the same project twice yields byte-identical outputs."""
+    first = build_fixture("blog", tmp_path)
+    outputs = {path: (first.site_dir / path).read_bytes() for path in first.files()}
+    second = build_site(tmp_path)
+    assert second.files() == sorted(outputs)
+    for path, data in outputs.items():
+        assert (second.site_dir / path).read_bytes() == data, path
+
+
+def test_reproducibility_check_passes(tmp_path: Path) -> None:
+    """The reproducibility check builds twice, and finds no differences."""
+    site = build_fixture("blog", tmp_path, reproducible=True)
+    assert site.exists("blog/index.html")
+    assert site.summary["errors"] == 0
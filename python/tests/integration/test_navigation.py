@@ -0,0 +1,106 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+from typing import TYPE_CHECKING, Any
+
+from zensical import get_navigation
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def _create_project(root: Path) -> Path:
+    """Create a fixture project with a homepage and a section."""
+    docs = root / "docs"
+    (docs / "guide").mkdir(parents=True)
+    (docs / "index.md").write_text("# Home\n")
+    (docs / "guide" / "index.md").write_text("# Guide\n")
+    (docs / "guide" / "setup.md").write_text(
+        "---\nauthor: Jane\nstatus: draft\n---\n\n# Setup\n"
+    )
+    config_file = root / "zensical.toml"
+    config_file.write_text('[project]\nsite_name = "Test"\n')
+    return config_file
+
+
+def _outline(items: list[dict[str, Any]]) -> list[Any]:
+    """Return titles, URLs and index flags of the given items, recursively."""
+    return [
+        (
+            item["title"],
+            item["url"],
+            item["is_index"],
+            _outline(item["children"]),
+        )
+        for item in items
+    ]
+
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_get_navigation_returns_tree(tmp_path: Path) -> None:
+    """Navigation contains all pages in order, without building the site."""
+    config_file = _create_project(tmp_path)
+    nav = get_navigation(str(config_file))
+    assert _outline(nav) == [
+        ("Home", "", True, []),
+        (
+            "Guide",
+            None,
+            False,
+            [
+                ("Guide", "guide/", True, []),
+                ("Setup", "guide/setup/", False, []),
+            ],
+        ),
+    ]
+    assert sorted(nav[0]) == ["children", "is_index", "meta", "title", "url"]
+    assert not (tmp_path / "site").exists()
+
+
+def test_get_navigation_limits_meta(tmp_path: Path) -> None:
+    """Page metadata is limited to the given keys."""
+    config_file = _create_project(tmp_path)
+    nav = get_navigation(str(config_file), meta=["author"])
+    section = nav[1]
+    assert section["meta"] is None
+    assert section["children"][1]["meta"] == {"author": "Jane"}
+
+
+def test_get_navigation_skips_hidden_directories(tmp_path: Path) -> None:
+    """Pages are selected like in the build, skipping hidden directories."""
+    config_file = _create_project(tmp_path)
+    hidden = tmp_path / "docs" / ".drafts"
+    hidden.mkdir()
+    (hidden / "draft.md").write_text("# Draft\n")
+    nav = get_navigation(str(config_file))
+    assert [item["title"] for item in nav] == ["Home", "Guide"]
@@ -0,0 +1,144 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+from functools import partial
+from http.server import SimpleHTTPRequestHandler, ThreadingHTTPServer
+from threading import Thread
+from typing import TYPE_CHECKING, Any
+
+import pytest
+
+from tests.integration.harness import FIXTURES, build_site, copy_fixture
+
+if TYPE_CHECKING:
+    from collections.abc import Iterator
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Classes
+# ---------------------------------------------------------------------------
+
+
+class _Handler(SimpleHTTPRequestHandler):
+    """Request handler serving the files of the fixture server quietly."""
+
+    def log_message(self, format: str, *args: Any) -> None:  # noqa: A002
+        """Suppress request logging."""
+
+
+# ---------------------------------------------------------------------------
+# Fixtures
+# ---------------------------------------------------------------------------
+
+
+@pytest.fixture(name="server")
+def _fixture_server() -> Iterator[str]:
+    """Serve the external assets of the privacy fixture on a free port."""
+    directory = str(FIXTURES / "privacy" / "server")
+    httpd = ThreadingHTTPServer(
+        ("127.0.0.1", 0), partial(_Handler, directory=directory)
+    )
+    thread = Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    yield f"http://127.0.0.1:{httpd.server_address[1]}"
+    httpd.shutdown()
+    httpd.server_close()
+
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def _configure(root: Path, server: str) -> Path:
+    """Copy the privacy fixture, and reference assets on the given server."""
+    config_file = copy_fixture("privacy", root)
+    with config_file.open("a") as f:
+        f.write(
+            f'extra_css = ["{server}/style.css"]\n'
+            f'extra_javascript = ["{server}/script.js", '
+            f'"{server}/missing.js"]\n'
+            "\n[project.plugins.privacy]\n"
+            'patterns = ["127.0.0.1:*"]\n'
+        )
+    with (root / "docs" / "index.md").open("a") as f:
+        f.write(f'\n<img src="{server}/logo.svg" alt="Logo">\n')
+    return config_file
+
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_privacy_fixture_self_hosts_assets(
+    tmp_path: Path, server: str
+) -> None:
+    """External assets are downloaded, and references are rewritten."""
+    _configure(tmp_path, server)
+    site = build_site(tmp_path)
+    hrefs = [link["href"] for link in site.select("index.html", "link[href]")]
+    assert f"{server}/style.css" not in hrefs
+    logo = site.select_one("index.html", 'img[alt="Logo"]')
+    assert logo["src"].startswith("assets/external/")
+
+    # Fonts referenced from the style sheet are self-hosted as well
+    styles = [href for href in hrefs if href.startswith("assets/external/")]
+    assert len(styles) == 1
+    style = site.read(styles[0])
+    assert server not in style
+    assert 'url("../../assets/external/' in style
+
+    # Scripts are self-hosted, unless they can't be fetched
+    scripts = site.select("index.html", "script[src]")
+    sources = [script["src"] for script in scripts]
+    assert f"{server}/script.js" not in sources
+    assert f"{server}/missing.js" in sources
+
+
+def test_privacy_fixture_reports_missing_assets(
+    tmp_path: Path, server: str
+) -> None:
+    """External assets that can't be fetched are reported once."""
+    _configure(tmp_path, server)
+    site = build_site(tmp_path)
+    messages = [item["message"] for item in site.diagnostics]
+    missing = [message for message in messages if "missing.js" in message]
+    assert len(missing) == 1
+    assert "Couldn't fetch external asset" in missing[0]
+
+
+def test_privacy_fixture_uses_cache_offline(
+    tmp_path: Path, server: str
+) -> None:
+    """Offline builds use previously downloaded assets from the cache."""
+    config_file = _configure(tmp_path, server)
+    expected = build_site(tmp_path).read("index.html")
+    with config_file.open("a") as f:
+        f.write("offline = true\n")
+    site = build_site(tmp_path, clean=False)
+    assert site.read("index.html") == expected
+    messages = [item["message"] for item in site.diagnostics]
+    assert not any("style.css" in message for message in messages)
@@ -0,0 +1,252 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import json
+import signal
+import socket
+import subprocess
+import sys
+import time
+from typing import TYPE_CHECKING
+from urllib.error import URLError
+from urllib.request import urlopen
+
+from tests.integration.harness import copy_fixture
+from zensical import build
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def _create_project(root: Path) -> Path:
+    """Create a fixture project with pages in two sections."""
+    docs = root / "docs"
+    for section in ("team-a", "team-b"):
+        (docs / section).mkdir(parents=True)
+        (docs / section / "index.md").write_text(f"# {section}\n")
+    (docs / "index.md").write_text(
+        "# Home\n\n[Team A](team-a/index.md) and [Team B](team-b/index.md)\n"
+    )
+    config_file = root / "zensical.toml"
+    config_file.write_text('[project]\nsite_name = "Test"\n')
+    return config_file
+
+
+def _free_port() -> int:
+    """Return a free port on the loopback interface."""
+    with socket.socket() as sock:
+        sock.bind(("127.0.0.1", 0))
+        return sock.getsockname()[1]
+
+
+def _wait_for_build(port: int, timeout: float = 60) -> dict:
+    """Wait until the first build of the preview server is finished."""
+    deadline = time.monotonic() + timeout
+    url = f"http://127.0.0.1:{port}/__zensical/status"
+    while time.monotonic() < deadline:
+        try:
+            with urlopen(url, timeout=1) as response:  # noqa: S310
+                status = json.load(response)
+                if status["success"] is not None:
+                    return status
+        except (URLError, ConnectionError):
+            pass
+        time.sleep(0.1)
+    raise TimeoutError("Build did not finish in time")
+
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_serve_scope_only_rebuilds_scoped_pages(tmp_path: Path) -> None:
+    """Pages outside of the scope keep the output of the last full build."""
+    config_file = _create_project(tmp_path)
+    build(
+        str(config_file),
+        {
+            "clean": True,
+            "strict": False,
+            "reproducible": False,
+            "prune_cache": False,
+            "site_dir": None,
+            "cache_dir": None,
+        },
+    )
+
+    # Record modification times of all pages after the full build
+    site = tmp_path / "site"
+    pages = ["index.html", "team-a/index.html", "team-b/index.html"]
+    before = {page: (site / page).stat().st_mtime_ns for page in pages}
+    time.sleep(0.1)
+
+    # Serve with a scope that only includes the pages of team A
+    port = _free_port()
+    process = subprocess.Popen(  # noqa: S603
+        [
+            sys.executable,
+            "-m",
+            "zensical",
+            "serve",
+            "-f",
+            str(config_file),
+            "-a",
+            f"127.0.0.1:{port}",
+            "--scope",
+            "docs/team-a/**",
+        ],
+        cwd=tmp_path,
+    )
+    try:
+        status = _wait_for_build(port)
+    finally:
+        process.send_signal(signal.SIGTERM)
+        process.wait(timeout=30)
+
+    # Only the scoped page must have been rendered again
+    assert status["success"] is True
+    after = {page: (site / page).stat().st_mtime_ns for page in pages}
+    assert after["team-a/index.html"] > before["team-a/index.html"]
+    assert after["index.html"] == before["index.html"]
+    assert after["team-b/index.html"] == before["team-b/index.html"]
+
+    # Navigation of the scoped page still links to pages outside the scope
+    html = (site / "team-a" / "index.html").read_text()
+    assert "team-b/" in html
+
+
+def test_serve_prunes_outputs_of_removed_pages(tmp_path: Path) -> None:
+    """Outputs of pages removed while serving are removed from the site."""
+    _create_project(tmp_path)
+    port = _free_port()
+    process = subprocess.Popen(  # noqa: S603
+        [
+            sys.executable,
+            "-m",
+            "zensical",
+            "serve",
+            "-f",
+            str(tmp_path / "zensical.toml"),
+            "-a",
+            f"127.0.0.1:{port}",
+        ],
+        cwd=tmp_path,
+    )
+    try:
+        _wait_for_build(port)
+        output = tmp_path / "site" / "team-b" / "index.html"
+        assert output.is_file()
+
+        # Remove page, and wait for its output to be pruned
+        (tmp_path / "docs" / "team-b" / "index.md").unlink()
+        deadline = time.monotonic() + 30
+        while output.exists() and time.monotonic() < deadline:
+            time.sleep(0.1)
+        assert not output.exists()
+        assert not output.parent.exists()
+        assert (tmp_path / "site" / "team-a" / "index.html").is_file()
+    finally:
+        process.send_signal(signal.SIGTERM)
+        process.wait(timeout=30)
+
+
+def test_serve_prunes_fallbacks_of_removed_pages(tmp_path: Path) -> None:
+    """Fallbacks of pages removed while serving are removed from the site."""
+    config_file = copy_fixture("i18n", tmp_path)
+    port = _free_port()
+    process = subprocess.Popen(  # noqa: S603
+        [
+            sys.executable,
+            "-m",
+            "zensical",
+            "serve",
+            "-f",
+            str(config_file),
+            "-a",
+            f"127.0.0.1:{port}",
+        ],
+        cwd=tmp_path,
+    )
+    try:
+        _wait_for_build(port)
+        fallback = tmp_path / "site" / "de" / "guide" / "index.html"
+        assert fallback.is_file()
+
+        # Remove page of default language, and wait for fallback to be pruned
+        (tmp_path / "docs" / "en" / "guide.md").unlink()
+        deadline = time.monotonic() + 30
+        while fallback.exists() and time.monotonic() < deadline:
+            time.sleep(0.1)
+        assert not fallback.exists()
+        assert (tmp_path / "site" / "de" / "index.html").is_file()
+    finally:
+        process.send_signal(signal.SIGTERM)
+        process.wait(timeout=30)
+
+
+def test_serve_rebuilds_pages_including_project_files(tmp_path: Path) -> None:
+    """Pages are rebuilt when a snippet in the project directory changes."""
+    (tmp_path / "docs").mkdir()
+    (tmp_path / "docs" / "index.md").write_text('# Home\n\n--8<-- "note.md"\n')
+    (tmp_path / "note.md").write_text("Before\n")
+    config_file = tmp_path / "zensical.toml"
+    config_file.write_text(
+        '[project]\nsite_name = "Test"\n\n'
+        "[project.markdown_extensions.pymdownx.snippets]\n"
+        'base_path = ["."]\n'
+    )
+    port = _free_port()
+    process = subprocess.Popen(  # noqa: S603
+        [
+            sys.executable,
+            "-m",
+            "zensical",
+            "serve",
+            "-f",
+            str(config_file),
+            "-a",
+            f"127.0.0.1:{port}",
+        ],
+        cwd=tmp_path,
+    )
+    try:
+        _wait_for_build(port)
+        output = tmp_path / "site" / "index.html"
+        assert "Before" in output.read_text()
+
+        # Change snippet, and wait for the page to be rebuilt
+        (tmp_path / "note.md").write_text("After\n")
+        deadline = time.monotonic() + 30
+        while "After" not in output.read_text():
+            assert time.monotonic() < deadline
+            time.sleep(0.1)
+    finally:
+        process.send_signal(signal.SIGTERM)
+        process.wait(timeout=30)
@@ -0,0 +1,80 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+from __future__ import annotations
+
+import struct
+from typing import TYPE_CHECKING
+
+from tests.integration.harness import build_site, copy_fixture
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+def _dimensions(data: bytes) -> tuple[int, int]:
+    """Read width and height from the header of a PNG image."""
+    assert data.startswith(b"\x89PNG\r\n\x1a\n")
+    return struct.unpack(">II", data[16:24])
+
+
+# ---------------------------------------------------------------------------
+# Tests
+# ---------------------------------------------------------------------------
+
+
+def test_social_fixture_renders_cards(tmp_path: Path) -> None:
+    """Cards are rendered for each page, and referenced in the metadata."""
+    copy_fixture("social", tmp_path)
+    site = build_site(tmp_path)
+    for name in ("index", "guide"):
+        path = site.site_dir / "assets/images/social" / f"{name}.png"
+        assert _dimensions(path.read_bytes()) == (1200, 630)
+
+    # Meta tags reference the card with an absolute URL
+    meta = site.select_one("index.html", 'meta[property="og:image"]')
+    assert meta["content"] == (
+        "https://example.com/docs/assets/images/social/index.png"
+    )
+
+
+def test_social_fixture_uses_cache(tmp_path: Path) -> None:
+    """Cards are rendered once, and taken from the cache on rebuilds."""
+    copy_fixture("social", tmp_path)
+    site = build_site(tmp_path)
+    assert site.summary["social_cards"] == 2
+    assert site.summary["cached_social_cards"] == 0
+    path = site.site_dir / "assets/images/social/index.png"
+    expected = path.read_bytes()
+
+    # Rebuild without cleaning - all cards must be taken from the cache
+    site = build_site(tmp_path, clean=False)
+    assert site.summary["social_cards"] == 2
+    assert site.summary["cached_social_cards"] == 2
+    assert path.read_bytes() == expected
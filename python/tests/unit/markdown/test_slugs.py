@@ -0,0 +1,128 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import pytest
+from markdown import Markdown
+
+from zensical.markdown.slugs import Slugify, SlugifyExtension, slugify
+
+# ---------------------------------------------------------------------------
+# Slugification
+# ---------------------------------------------------------------------------
+
+
+class TestSlugify:
+    @pytest.mark.parametrize(
+        ("value", "default", "unicode", "github"),
+        [
+            pytest.param(
+                "Hello World",
+                "hello-world",
+                "hello-world",
+                "hello-world",
+                id="plain",
+            ),
+            pytest.param(
+                "Café & Crème",
+                "cafe-creme",
+                "café-crème",
+                "café--crème",
+                id="accents",
+            ),
+            pytest.param(
+                "Emoji 🎉 Party",
+                "emoji-party",
+                "emoji-party",
+                "emoji--party",
+                id="emoji",
+            ),
+            pytest.param(
+                "What's new? (v2.0)",
+                "whats-new-v20",
+                "whats-new-v20",
+                "whats-new-v20",
+                id="punctuation",
+            ),
+            pytest.param(
+                "编译器 笔记",
+                "",
+                "编译器-笔记",
+                "编译器-笔记",
+                id="cjk",
+            ),
+            pytest.param(
+                "foo_bar--baz",
+                "foo_bar-baz",
+                "foo_bar-baz",
+                "foo_bar--baz",
+                id="hyphens",
+            ),
+        ],
+    )
+    def test_modes(
+        self, value: str, default: str, unicode: str, github: str
+    ) -> None:
+        assert slugify(value, "-", "default") == default
+        assert slugify(value, "-", "unicode") == unicode
+        assert slugify(value, "-", "github") == github
+
+    def test_unknown_mode(self) -> None:
+        with pytest.raises(ValueError, match="Unknown slugify mode"):
+            Slugify("unknown")
+
+
+class TestSlugifyUnique:
+    @pytest.mark.parametrize("mode", ["default", "unicode", "github"])
+    def test_duplicates(self, mode: str) -> None:
+        slugify = Slugify(mode)
+        assert slugify("Intro", "-") == "intro"
+        assert slugify("Intro", "-") == "intro-1"
+        assert slugify("Intro", "-") == "intro-2"
+
+    def test_duplicates_with_existing_suffix(self) -> None:
+        slugify = Slugify("default")
+        assert slugify("Intro 1", "-") == "intro-1"
+        assert slugify("Intro", "-") == "intro"
+        assert slugify("Intro", "-") == "intro-2"
+
+    def test_reset(self) -> None:
+        slugify = Slugify("default")
+        assert slugify("Intro", "-") == "intro"
+        slugify.reset()
+        assert slugify("Intro", "-") == "intro"
+
+
+class TestSlugifyExtension:
+    def test_explicit_ids_are_reserved(self) -> None:
+        slugify = Slugify("default")
+        md = Markdown(
+            extensions=["attr_list", "toc"],
+            extension_configs={"toc": {"slugify": slugify}},
+        )
+        SlugifyExtension(slugify).extendMarkdown(md)
+        html = md.convert("## Intro\n\n## Setup {#intro}\n\n## Intro\n")
+        assert '<h2 id="intro-1">Intro</h2>' in html
+        assert '<h2 id="intro">Setup</h2>' in html
+        assert '<h2 id="intro-2">Intro</h2>' in html
@@ -0,0 +1,266 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+from types import SimpleNamespace
+from typing import TYPE_CHECKING
+
+import pytest
+
+from zensical import config
+from zensical.config import (
+    ConfigurationError,
+    _convert_extra_javascript,
+    _convert_languages,
+    _convert_nav_item,
+)
+from zensical.zensical import resolve_theme_dirs
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+# ---------------------------------------------------------------------------
+# i18n
+# ---------------------------------------------------------------------------
+
+
+class TestConvertLanguages:
+    def test_defaults(self) -> None:
+        """Name and path default to the locale, first language is default."""
+        languages = _convert_languages([{"locale": "en"}, {"locale": "de"}])
+        assert languages == [
+            {"locale": "en", "name": "en", "path": "en", "default": True},
+            {"locale": "de", "name": "de", "path": "de", "default": False},
+        ]
+
+    def test_explicit_default(self) -> None:
+        """An explicitly marked default language is kept."""
+        languages = _convert_languages(
+            [{"locale": "en"}, {"locale": "de", "default": True}]
+        )
+        assert [language["default"] for language in languages] == [
+            False,
+            True,
+        ]
+
+    def test_multiple_defaults(self) -> None:
+        """Multiple default languages are rejected."""
+        with pytest.raises(ConfigurationError):
+            _convert_languages(
+                [
+                    {"locale": "en", "default": True},
+                    {"locale": "de", "default": True},
+                ]
+            )
+
+    def test_path_traversal(self) -> None:
+        """Language paths must not escape the docs directory."""
+        with pytest.raises(ConfigurationError):
+            _convert_languages([{"locale": "en", "path": "../en"}])
+
+
+# ---------------------------------------------------------------------------
+# Extra JavaScript
+# ---------------------------------------------------------------------------
+
+
+class TestConvertExtraJavascript:
+    def test_strings(self) -> None:
+        """Strings are converted into entries without attributes."""
+        scripts = _convert_extra_javascript(["javascripts/extra.js"])
+        assert scripts == [
+            {
+                "path": "javascripts/extra.js",
+                "type": None,
+                "async": False,
+                "defer": False,
+            }
+        ]
+
+    def test_tables(self) -> None:
+        """Tables keep their attributes, and missing ones are defaulted."""
+        scripts = _convert_extra_javascript(
+            [{"path": "javascripts/app.mjs", "type": "module", "defer": True}]
+        )
+        assert scripts == [
+            {
+                "path": "javascripts/app.mjs",
+                "type": "module",
+                "async": False,
+                "defer": True,
+            }
+        ]
+
+    def test_mixed(self) -> None:
+        """Strings and tables can be mixed, and order is preserved."""
+        scripts = _convert_extra_javascript(
+            [
+                "https://unpkg.com/mermaid/dist/mermaid.min.js",
+                {"path": "javascripts/app.js", "async": True},
+            ]
+        )
+        assert [script["path"] for script in scripts] == [
+            "https://unpkg.com/mermaid/dist/mermaid.min.js",
+            "javascripts/app.js",
+        ]
+        assert [script["async"] for script in scripts] == [False, True]
+
+    def test_invalid_attribute(self) -> None:
+        """Attributes must be booleans."""
+        with pytest.raises(ConfigurationError):
+            _convert_extra_javascript([{"path": "app.js", "defer": "yes"}])
+
+
+# ---------------------------------------------------------------------------
+# Navigation
+# ---------------------------------------------------------------------------
+
+
+class TestConvertNavItem:
+    def test_string(self) -> None:
+        """Plain strings carry no icon and no attributes."""
+        item = _convert_nav_item("index.md")
+        assert item["icon"] is None
+        assert item["attributes"] == {}
+
+    def test_title_url(self) -> None:
+        """Title and URL pairs are not mistaken for tables."""
+        item = _convert_nav_item({"Home": "index.md"})
+        assert item["title"] == "Home"
+        assert item["url"] == "index.md"
+        assert item["icon"] is None
+
+    def test_table(self) -> None:
+        """Tables can set icons and attributes."""
+        item = _convert_nav_item(
+            {
+                "title": "Blog",
+                "url": "blog/index.md",
+                "icon": "material/newspaper",
+                "attributes": {"data-track": "blog"},
+            }
+        )
+        assert item["title"] == "Blog"
+        assert item["url"] == "blog/index.md"
+        assert item["icon"] == "material/newspaper"
+        assert item["attributes"] == {"data-track": "blog"}
+        assert item["is_index"] is True
+
+    def test_table_with_children(self) -> None:
+        """Tables can define sections with children."""
+        item = _convert_nav_item(
+            {
+                "title": "Guide",
+                "icon": "material/book",
+                "children": ["guide/setup.md", {"Usage": "guide/usage.md"}],
+            }
+        )
+        assert item["url"] is None
+        assert item["icon"] == "material/book"
+        assert [child["url"] for child in item["children"]] == [
+            "guide/setup.md",
+            "guide/usage.md",
+        ]
+
+    def test_invalid_attributes(self) -> None:
+        """Attributes must be a mapping."""
+        with pytest.raises(ConfigurationError):
+            _convert_nav_item({"url": "index.md", "attributes": ["a"]})
+
+
+# ---------------------------------------------------------------------------
+# Themes
+# ---------------------------------------------------------------------------
+
+
+def _install_themes(
+    monkeypatch: pytest.MonkeyPatch, tmp_path: Path, themes: dict[str, str]
+) -> dict[str, str]:
+    """Install themes as fake entry points, mapping names to parents."""
+    entry_points = {}
+    theme_dirs = {}
+    for name, extends in themes.items():
+        theme_dir = tmp_path / name
+        theme_dir.mkdir()
+        if extends:
+            (theme_dir / "mkdocs_theme.yml").write_text(
+                f"extends: {extends}\nname: {name}\n"
+            )
+        module = SimpleNamespace(__file__=str(theme_dir / "__init__.py"))
+        entry_points[name] = SimpleNamespace(load=lambda m=module: m)
+        theme_dirs[name] = str(theme_dir)
+    monkeypatch.setattr(config, "get_themes", lambda: entry_points)
+    return theme_dirs
+
+
+class TestResolveThemeDirs:
+    def test_inheritance_order(
+        self, monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+    ) -> None:
+        """Theme directories are ordered from child to parent themes."""
+        themes = {"fork": "community", "community": "base", "base": ""}
+        dirs = _install_themes(monkeypatch, tmp_path, themes)
+        theme_dirs = [str(d) for d in resolve_theme_dirs("fork")]
+        assert theme_dirs == [dirs["fork"], dirs["community"], dirs["base"]]
+
+    def test_custom_dir_first(
+        self, monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+    ) -> None:
+        """Custom theme directories take precedence over parent themes."""
+        dirs = _install_themes(monkeypatch, tmp_path, {"community": ""})
+        custom_dir = tmp_path / "overrides"
+        custom_dir.mkdir()
+        (custom_dir / "mkdocs_theme.yml").write_text("extends: community\n")
+        theme_dirs = resolve_theme_dirs("material", str(custom_dir))
+        assert [str(d) for d in theme_dirs] == [
+            str(custom_dir),
+            dirs["community"],
+        ]
+
+    def test_missing_theme(
+        self, monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+    ) -> None:
+        """Missing themes are named, together with all installed themes."""
+        _install_themes(monkeypatch, tmp_path, {"community": ""})
+        with pytest.raises(ConfigurationError) as info:
+            resolve_theme_dirs("missing")
+        assert str(info.value) == (
+            "Theme 'missing' is not installed. Available themes are: community"
+        )
+
+    def test_missing_parent_theme(
+        self, monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+    ) -> None:
+        """Missing parent themes are reported with the extending theme."""
+        _install_themes(monkeypatch, tmp_path, {"fork": "missing"})
+        with pytest.raises(ConfigurationError, match="Parent theme of"):
+            resolve_theme_dirs("fork")
+
+    def test_cyclic_inheritance(
+        self, monkeypatch: pytest.MonkeyPatch, tmp_path: Path
+    ) -> None:
+        """Themes extending each other are rejected."""
+        _install_themes(monkeypatch, tmp_path, {"a": "b", "b": "a"})
+        with pytest.raises(ConfigurationError, match="extends itself"):
+            resolve_theme_dirs("a")
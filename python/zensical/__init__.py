@@ -21,6 +21,20 @@
 # FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
 # IN THE SOFTWARE.
 
-from zensical.zensical import build, serve, version
+from zensical.zensical import (
+    build,
+    get_config,
+    get_navigation,
+    relocate,
+    serve,
+    version,
+)
 
-__all__ = ["build", "serve", "version"]
+__all__ = [
+    "build",
+    "get_config",
+    "get_navigation",
+    "relocate",
+    "serve",
+    "version",
+]
@@ -28,6 +28,8 @@ import hashlib
 import importlib
 import os
 import pickle
+import re
+from datetime import date, datetime, time
 from importlib.metadata import EntryPoint, entry_points
 from importlib.util import find_spec
 from pathlib import Path
@@ -46,6 +48,8 @@ from zensical.extensions.autorefs import AutorefsExtension
 from zensical.extensions.emoji import to_svg, twemoji
 from zensical.extensions.glightbox import GlightboxExtension
 from zensical.extensions.macros import MacrosExtension
+from zensical.markdown.slugs import SLUGIFY_MODES, Slugify
+from zensical.zensical import resolve_theme_dirs
 
 if TYPE_CHECKING:
     from collections.abc import Iterator
@@ -72,6 +76,72 @@ side, and use it directly when needed. It's a hack but will do for now.
 # ----------------------------------------------------------------------------
 
 
+PRECOMPRESS_ENCODINGS = ("gzip", "brotli")
+"""
+Encodings supported for precompressed copies of text outputs.
+"""
+
+OUTPUT_FORMATS = ("tar", "tar.gz", "zip")
+"""
+Archive formats supported for writing the site into an archive.
+"""
+
+DIAGNOSTICS_LEVELS = ("warning", "error")
+"""
+Minimum severities of printed diagnostics.
+"""
+
+DIAGNOSTICS_FORMATS = ("text", "json")
+"""
+Output formats of printed diagnostics.
+"""
+
+RELOAD_PATH_STYLES = ("directory", "file")
+"""
+Styles of paths sent to the client when reloading in serve mode.
+"""
+
+MARKDOWN_ENGINES = ("python", "native")
+"""
+Engines for rendering Markdown, where the native engine is experimental.
+"""
+
+COLLAPSE_TITLES = ("parent", "child", "joined")
+"""
+Titles of sections collapsed into their single child in generated navigation.
+"""
+
+PERMALINK_PLACEHOLDERS = ("slug", "date", "path")
+"""
+Placeholders in URL templates of permalinks.
+"""
+
+META_SCHEMA_TYPES = ("string", "list", "bool", "date")
+"""
+Types of front matter values in the front matter schema.
+"""
+
+NAV_ITEM_KEYS = ("title", "url", "icon", "attributes", "children")
+"""
+Keys of navigation items that are given as tables with explicit keys.
+"""
+
+SITE_KEYS = ("site_name", "site_url", "docs_dir", "site_dir", "nav")
+"""
+Settings that can be overridden by each site of a project with multiple sites.
+"""
+
+UNSUPPORTED_KEYS = {
+    "toml": ("INHERIT",),
+    "yaml": ("project",),
+}
+"""
+Top-level settings that are only supported via the other configuration format.
+
+`INHERIT` is resolved by the YAML loader, and `project` is the table in which
+settings are scoped in `zensical.toml` - both are meaningless in the other one.
+"""
+
 DEFAULT_MARKDOWN_EXTENSIONS = {
     "abbr": {},
     "admonition": {},
@@ -125,36 +195,114 @@ class ConfigurationError(ClickException):
 
 
 def parse_config(path: str) -> dict:
-    """Parse configuration file."""
-    # Decide by extension; no need to convert to Path
+    """Parse configuration file.
+
+    Both formats are loaded into the same representation first, and are then
+    normalized through a single code path, so equivalent `mkdocs.yml` and
+    `zensical.toml` files result in equal settings. Settings that can't be
+    expressed in the format at hand are removed and reported, not dropped.
+    If the project builds multiple sites, the first site is returned.
+    """
+    return parse_sites(path)[0]
+
+
+def parse_sites(path: str) -> list[dict]:
+    """Parse configuration file into the configurations of all sites.
+
+    Projects can build multiple sites, which are given as an array of tables
+    in `sites`, each of which overrides the settings in `SITE_KEYS`, so all
+    sites share the same theme and Markdown settings. Without sites, the
+    project is the only site.
+    """
+    global _CONFIG  # noqa: PLW0603
+    config, unsupported = _load_config(path)
+    sites = config.pop("sites", None)
+    if sites is None:
+        sites = [{}]
+
+    # Ensure that sites are given as tables, which don't share directories,
+    # as they would overwrite each other's outputs
+    if not isinstance(sites, list) or not sites:
+        raise ConfigurationError("'sites' must be a non-empty list of tables")
+    for site in sites:
+        if not isinstance(site, dict):
+            raise ConfigurationError("'sites' entries must be tables")
+        for key in site:
+            if key not in SITE_KEYS:
+                raise ConfigurationError(
+                    f"Setting '{key}' can't be overridden in 'sites', only "
+                    f"{', '.join(SITE_KEYS)} can"
+                )
+    for key in ("docs_dir", "site_dir"):
+        values = [site.get(key, config.get(key)) for site in sites]
+        if len(set(values)) < len(values):
+            raise ConfigurationError(f"Sites must not share the same {key}")
+
+    # Apply defaults to each site, which is loaded from the file again, as
+    # defaults are applied in place, and the first site is kept for parsing
+    # Markdown, as all sites share the same Markdown settings
+    configs = []
+    for site in sites:
+        config, _ = _load_config(path)
+        config.pop("sites", None)
+        config.update(site)
+        config = _apply_defaults(config, path)
+        config["unsupported_keys"] = unsupported
+        configs.append(config)
+    _CONFIG = configs[0]
+    return configs
+
+
+def _load_config(path: str) -> tuple[dict, list[str]]:
+    """Load configuration file in either format, deciding by extension."""
     _, ext = os.path.splitext(path)
     if ext.lower() == ".toml":
-        return parse_zensical_config(path)
-    return parse_mkdocs_config(path)
+        return _load_zensical_config(path)
+    return _load_mkdocs_config(path)
 
 
-def parse_zensical_config(path: str) -> dict:
-    """Parse zensical.toml configuration file."""
-    global _CONFIG  # noqa: PLW0603
+def _load_zensical_config(path: str) -> tuple[dict, list[str]]:
+    """Load zensical.toml configuration file.
+
+    All settings are scoped under the `project` table, so settings outside of
+    it are not supported, as well as settings that are specific to MkDocs.
+    """
     with open(path, "rb") as f:
         config = toml_load(f)
+
+    # Hoist project settings, and collect everything that is left behind -
+    # sites may also be given outside of the project table, as `[[sites]]`
+    unsupported: list[str] = []
     if "project" in config:
+        if "sites" in config:
+            config["project"].setdefault("sites", config.pop("sites"))
+        unsupported = [key for key in config if key != "project"]
         config = config["project"]
 
-    # Apply defaults and return parsed configuration
-    _CONFIG = _apply_defaults(config, path)
-    return _CONFIG
+    # Remove settings that are only supported via mkdocs.yml
+    for key in UNSUPPORTED_KEYS["toml"]:
+        if key in config:
+            config.pop(key)
+            unsupported.append(key)
 
+    # Return configuration and unsupported settings
+    return config, unsupported
 
-def parse_mkdocs_config(path: str) -> dict:
-    """Parse mkdocs.yml configuration file."""
-    global _CONFIG  # noqa: PLW0603
+
+def _load_mkdocs_config(path: str) -> tuple[dict, list[str]]:
+    """Load mkdocs.yml configuration file."""
     with open(path, encoding="utf-8") as f:
         config = _yaml_load(f)
 
-    # Apply defaults and return parsed configuration
-    _CONFIG = _apply_defaults(config, path)
-    return _CONFIG
+    # Remove settings that are only supported via zensical.toml
+    unsupported: list[str] = []
+    for key in UNSUPPORTED_KEYS["yaml"]:
+        if key in config:
+            config.pop(key)
+            unsupported.append(key)
+
+    # Return configuration and unsupported settings
+    return config, unsupported
 
 
 def get_config() -> dict:
@@ -281,25 +429,10 @@ def get_builtin_theme_dir() -> str:
     return os.path.join(path, "templates")
 
 
-def get_theme_dir(name: str) -> str:
-    """Return the theme directory."""
-    # Zensical's default theme is the replacement for the `material` theme, so
-    # make sure to use the built-in theme when `material` is specified.
-    # We also reserve the `zensical` theme name to the same effect.
-    if name in ("material", "zensical"):
-        return get_builtin_theme_dir()
-
-    # Otherwise, check for installed themes - note that because of minijinja,
-    # theme might need to be adjusted. This functionality is primarily intended
-    # for extending the `material` theme, as some users did, and allow to move
-    # overrides into an installable python package.
-    themes = get_themes()
-    if name not in themes:
-        raise ConfigurationError(
-            f"Theme '{name}' is not installed. "
-            f"Available themes are: {', '.join(themes.keys())}"
-        )
-    return os.path.dirname(os.path.abspath(themes[name].load().__file__))
+def get_builtin_font_file() -> str:
+    """Return the font file bundled for rendering social cards."""
+    path = os.path.dirname(os.path.abspath(__file__))
+    return os.path.join(path, "fonts", "DejaVuSans-Bold.ttf")
 
 
 def get_custom_theme_dir(path: str, config_path: str) -> str:
@@ -327,31 +460,21 @@ def _yaml_load_theme_config(source: IO) -> dict[str, Any]:
     return config
 
 
-def _load_theme_config(theme_dir: str) -> tuple[dict[str, Any], list[str]]:
-    # Keep track of the theme directories,
-    # so that we can pass them up to Minijinja.
-    theme_dirs = [theme_dir]
-
-    if (theme_config_file := Path(theme_dir, "mkdocs_theme.yml")).is_file():
-        # We found a theme configuration file (mkdocs_theme.yml).
-        with theme_config_file.open(encoding="utf-8") as file:
-            theme_config = _yaml_load_theme_config(file)
-            if extends := theme_config.pop("extends", None):
-                # This theme extends another theme,
-                # so we load this parent theme's configuration (recursively)
-                # and merge the current theme's configuration into it.
-                parent_theme_dir = get_theme_dir(extends)
-                parent_config, parent_dirs = _load_theme_config(
-                    parent_theme_dir
-                )
-                parent_config.update(theme_config)
-                theme_dirs.extend(parent_dirs)
-                return parent_config, theme_dirs
-            # Otherwise we just return the current theme configuration.
-            return theme_config, theme_dirs
+def get_theme_config(theme_dir: str) -> dict[str, Any]:
+    """Return the configuration of the theme in the given directory."""
+    theme_config_file = Path(theme_dir, "mkdocs_theme.yml")
+    if not theme_config_file.is_file():
+        return {}
+    with theme_config_file.open(encoding="utf-8") as file:
+        return _yaml_load_theme_config(file)
 
-    # No theme configuration.
-    return {}, theme_dirs
+
+def get_theme_parent(theme_dir: str) -> str | None:
+    """Return the name of the theme the theme in the given directory extends.
+
+    This function is called from Rust, which resolves the theme directories.
+    """
+    return get_theme_config(theme_dir).get("extends") or None
 
 
 # ----------------------------------------------------------------------------
@@ -378,6 +501,12 @@ def _apply_defaults(config: dict, path: str) -> dict:
     if ".." in config.get("site_dir", ""):
         raise ConfigurationError("site_dir must not contain '..'")
 
+    # Set cache directory, which, like the site directory, may be an absolute
+    # path outside of the project, e.g., if the source checkout is read-only
+    set_default(config, "cache_dir", ".cache", str)
+    if ".." in config.get("cache_dir", ""):
+        raise ConfigurationError("cache_dir must not contain '..'")
+
     # Set docs directory
     set_default(config, "docs_dir", "docs", str)
     if ".." in config.get("docs_dir", ""):
@@ -413,13 +542,12 @@ def _apply_defaults(config: dict, path: str) -> dict:
     set_default(config, "edit_uri_template", None, str)
     set_default(config, "edit_uri", None, str)
 
-    # Set defaults for repository name settings
+    # Repository name and icon are derived from the repository URL in Rust,
+    # where the repository name takes precedence, if set explicitly
+    config["repo_icon"] = None
+
+    # Set defaults for edit URI settings
     docs_dir = config.get("docs_dir")
-    repo_names = {
-        "github.com": "GitHub",
-        "gitlab.com": "Gitlab",
-        "bitbucket.org": "Bitbucket",
-    }
     edit_uris = {
         "github.com": f"edit/master/{docs_dir}",
         "gitlab.com": f"edit/master/{docs_dir}",
@@ -428,11 +556,6 @@ def _apply_defaults(config: dict, path: str) -> dict:
     repo_url = config.get("repo_url")
     if repo_url:
         host = urlparse(repo_url).hostname or ""
-        if not config.get("repo_name"):
-            if host in repo_names:
-                set_default(config, "repo_name", repo_names[host], str)
-            elif host:
-                config["repo_name"] = host.split(".")[0].title()
         if host in edit_uris:
             set_default(config, "edit_uri", edit_uris[host], str)
 
@@ -449,26 +572,20 @@ def _apply_defaults(config: dict, path: str) -> dict:
     # Set defaults for custom theme directory
     set_default(config["theme"], "custom_dir", None, str)
 
-    # Load theme configuration
+    # Resolve theme directories, which is done in Rust, ordered by precedence,
+    # i.e., the custom theme directory first, followed by all parent themes
+    custom_dir = None
+    theme_name = config["theme"].get("name") or "material"
     if config["theme"].get("custom_dir"):
-        theme_dir = get_custom_theme_dir(config["theme"]["custom_dir"], path)
-        theme_config, theme_dirs = _load_theme_config(theme_dir)
-        if (
-            len(theme_dirs) == 1
-            and (theme_name := config["theme"].get("name", "material"))
-            is not None
-        ):
-            # Custom theme doesn't extend another theme,
-            # but user specified a theme name in configuration,
-            # so we use it as base theme.
-            theme_dir = get_theme_dir(theme_name)
-            base_theme_config, base_theme_dirs = _load_theme_config(theme_dir)
-            theme_config = {**base_theme_config, **theme_config}
-            theme_dirs = theme_dirs + base_theme_dirs
-    else:
-        # No custom theme directory
-        theme_dir = get_theme_dir(config["theme"].get("name") or "material")
-        theme_config, theme_dirs = _load_theme_config(theme_dir)
+        custom_dir = get_custom_theme_dir(config["theme"]["custom_dir"], path)
+        theme_name = config["theme"].get("name", "material")
+    theme_dirs = [str(d) for d in resolve_theme_dirs(theme_name, custom_dir)]
+
+    # Load theme configuration, merging child themes into their parents
+    theme_config: dict[str, Any] = {}
+    for theme_dir in reversed(theme_dirs):
+        theme_config.update(get_theme_config(theme_dir))
+    theme_config.pop("extends", None)
 
     # Store theme directories for Minijinja and merge theme configuration
     config["theme_dirs"] = theme_dirs
@@ -580,6 +697,17 @@ def _apply_defaults(config: dict, path: str) -> dict:
     # Ensure all non-existent values are all empty strings (for now)
     config["extra"] = _convert_extra(extra)
 
+    # Set defaults for extra context, which is merged into the top-level
+    # context of every page and template render
+    if "extra_context" in config and not isinstance(
+        config["extra_context"], dict
+    ):
+        raise ConfigurationError(
+            "The 'extra_context' setting must be a mapping/dictionary."
+        )
+    extra_context = set_default(config, "extra_context", {}, dict)
+    config["extra_context"] = _convert_extra(extra_context)
+
     # Set defaults for extra files
     set_default(config, "extra_css", [], list)
     set_default(config, "extra_templates", [], list)
@@ -590,6 +718,20 @@ def _apply_defaults(config: dict, path: str) -> dict:
         config.setdefault("extra_javascript", [])
     )
 
+    # Set homepage location, which falls back to index.md or README.md
+    set_default(config, "homepage", None, str)
+
+    # Initialize defaults for navigation settings - collapsing sections with a
+    # single child only applies to generated navigation, and is opt-in
+    navigation = set_default(config, "navigation", {}, dict)
+    set_default(navigation, "collapse_single_children", False, bool)
+    title = set_default(navigation, "collapse_title", "child", str)
+    if title not in COLLAPSE_TITLES:
+        raise ConfigurationError(
+            f"Unknown title '{title}' in 'navigation.collapse_title', "
+            f"expected one of: {', '.join(COLLAPSE_TITLES)}"
+        )
+
     # Initialize defaults for validation
     validation = {
         "unresolved_references": False,
@@ -600,6 +742,9 @@ def _apply_defaults(config: dict, path: str) -> dict:
         "shadowed_footnotes": False,
         "invalid_links": True,
         "invalid_link_anchors": True,
+        "root_relative_links": "warning",
+        "fix_root_relative_links": False,
+        "absolute_paths": False,
     }
 
     # Map MkDocs validation configuration to ours - note that we only support
@@ -626,12 +771,126 @@ def _apply_defaults(config: dict, path: str) -> dict:
         # Our own keys override the ones we map from MkDocs, so we apply them
         # after mapping the MkDocs keys
         for key in validation:
-            if key in input:
+            if key == "root_relative_links" and key in input:
+                validation[key] = _convert_severity(key, input[key])
+            elif key in input:
                 validation[key] = bool(input[key])
 
     # Set validation
     config["validation"] = validation
 
+    # Initialize defaults for diagnostics - all diagnostics are printed as text
+    # by default, and repeated ones are grouped, unless printed as JSON
+    diagnostics = set_default(config, "diagnostics", {}, dict)
+    level = set_default(diagnostics, "level", "warning", str)
+    if level not in DIAGNOSTICS_LEVELS:
+        raise ConfigurationError(
+            f"Unknown level '{level}' in 'diagnostics.level', "
+            f"expected one of: {', '.join(DIAGNOSTICS_LEVELS)}"
+        )
+    kind = set_default(diagnostics, "format", "text", str)
+    if kind not in DIAGNOSTICS_FORMATS:
+        raise ConfigurationError(
+            f"Unknown format '{kind}' in 'diagnostics.format', "
+            f"expected one of: {', '.join(DIAGNOSTICS_FORMATS)}"
+        )
+
+    # Initialize defaults for front matter schema - validation is opt-in, and
+    # once enabled, unknown keys are reported and values aren't coerced
+    meta_schema = set_default(config, "meta_schema", None, dict)
+    if meta_schema is not None:
+        keys = set_default(meta_schema, "keys", {}, dict)
+        for key, value in keys.items():
+            if value not in META_SCHEMA_TYPES:
+                raise ConfigurationError(
+                    f"Unknown type '{value}' for key '{key}' in "
+                    "'meta_schema.keys', expected one of: "
+                    f"{', '.join(META_SCHEMA_TYPES)}"
+                )
+        set_default(meta_schema, "allow_unknown", False, bool)
+        set_default(meta_schema, "coerce", False, bool)
+
+    # Initialize permalinks, which map glob patterns to URL templates, and are
+    # passed on as a list, since the first matching pattern takes precedence
+    permalinks = set_default(config, "permalinks", {}, dict)
+    for pattern, template in permalinks.items():
+        if not isinstance(template, str):
+            raise ConfigurationError(
+                f"Expected a URL template for '{pattern}' in 'permalinks'"
+            )
+        for match in re.finditer(r"\{([^}:]*)(?::[^}]*)?\}", template):
+            if match.group(1) not in PERMALINK_PLACEHOLDERS:
+                raise ConfigurationError(
+                    f"Unknown placeholder '{match.group(0)}' for '{pattern}' "
+                    "in 'permalinks', expected one of: "
+                    f"{', '.join(PERMALINK_PLACEHOLDERS)}"
+                )
+    config["permalinks"] = [
+        {"pattern": pattern, "template": template}
+        for pattern, template in permalinks.items()
+    ]
+
+    # Initialize defaults for cache - entries that weren't used for the given
+    # number of builds are pruned, and the size of the cache is unlimited
+    cache = set_default(config, "cache", {}, dict)
+    set_default(cache, "max_size_mb", None, int)
+    set_default(cache, "max_age", 10, int)
+
+    # Initialize defaults for build settings - precompression is disabled by
+    # default, and only applies to text outputs above the minimum size
+    build = set_default(config, "build", {}, dict)
+    precompress = set_default(build, "precompress", [], list)
+    for encoding in precompress:
+        if encoding not in PRECOMPRESS_ENCODINGS:
+            raise ConfigurationError(
+                f"Unknown encoding '{encoding}' in 'build.precompress', "
+                f"expected one of: {', '.join(PRECOMPRESS_ENCODINGS)}"
+            )
+    set_default(build, "precompress_min_size", 1024, int)
+
+    # Initialize archive output, if configured - by default, the site is written
+    # to the site directory, which is required for serving
+    output = set_default(build, "output", None, dict)
+    if output is not None:
+        set_default(output, "format", "tar.gz", str)
+        if output["format"] not in OUTPUT_FORMATS:
+            raise ConfigurationError(
+                f"Unknown format '{output['format']}' in 'build.output', "
+                f"expected one of: {', '.join(OUTPUT_FORMATS)}"
+            )
+        path = set_default(output, "path", f"site.{output['format']}", str)
+        if not path:
+            raise ConfigurationError("Missing path in 'build.output'")
+
+    # Initialize site manifest, which is opt-in, and only includes front matter
+    # keys that are explicitly allowed, as they might not be meant to be public
+    set_default(build, "manifest", None, str)
+    set_default(build, "manifest_meta", [], list)
+
+    # Initialize content hashes, which are embedded into pages, and written to
+    # pages.json, so clients can tell whether a page changed before fetching it
+    set_default(build, "content_hashes", False, bool)
+
+    # Initialize hidden files to copy - well-known hidden files like .nojekyll
+    # are always copied, and patterns allow to include more, e.g. .well-known
+    set_default(build, "include_hidden", [], list)
+
+    # Initialize defaults for serve settings - the scope narrows the pages that
+    # are built in serve mode, and can also be set from the command line
+    serve = set_default(config, "serve", {}, dict)
+    set_default(serve, "scope", None, str)
+    set_default(serve, "editor_command", None, str)
+    set_default(serve, "slow_page_threshold", None, int)
+    style = set_default(serve, "reload_path_style", "directory", str)
+    if style not in RELOAD_PATH_STYLES:
+        raise ConfigurationError(
+            f"Unknown style '{style}' in 'serve.reload_path_style', "
+            f"expected one of: {', '.join(RELOAD_PATH_STYLES)}"
+        )
+
+    # Partial rendering of pages is experimental, and thus disabled by default
+    set_default(serve, "partials", False, bool)
+
     # MkDocs will also set fenced_code, which is incompatible with SuperFences,
     # the extension that Material for MkDocs generally recommends. Note that we
     # decided to set defaults that make it easy to get started with sensible
@@ -652,10 +911,24 @@ def _apply_defaults(config: dict, path: str) -> dict:
     _resolve_pymdownx_blocks_tab(config)
     _resolve_toc(config)
 
+    # Use the configured slugification function for headings, if any, which
+    # takes precedence over the one that is configured for the toc extension
+    slugify = set_default(config, "slugify", None, str)
+    if slugify is not None:
+        if slugify not in SLUGIFY_MODES:
+            raise ConfigurationError(
+                f"Unknown slugify mode '{slugify}', expected one of: "
+                + ", ".join(SLUGIFY_MODES)
+            )
+        config["mdx_configs"]["toc"]["slugify"] = Slugify(slugify)
+
     # Ensure the table of contents title is initialized, as it's used inside
     # the template, and the table of contents extension is always defined
     config["mdx_configs"]["toc"].setdefault("title", None)
-    config["mdx_configs_hash"] = _hash(mdx_configs)
+
+    # Hash extension configuration independent of the order of extensions, as
+    # it differs between formats, e.g., when hoisting `pymdownx` in TOML
+    config["mdx_configs_hash"] = _hash(sorted(mdx_configs.items()))
 
     # Convert plugins configuration
     config["plugins"] = _convert_plugins(config.get("plugins", []), config)
@@ -666,6 +939,22 @@ def _apply_defaults(config: dict, path: str) -> dict:
     _shim_glightbox(config)
     _shim_macros(config)
 
+    # Initialize Markdown settings - the native engine is experimental, and only
+    # supports a subset of extensions, so their names are passed on in order to
+    # warn about extensions that are not supported, or only partially
+    markdown = set_default(config, "markdown", {}, dict)
+    engine = set_default(markdown, "engine", "python", str)
+    if engine not in MARKDOWN_ENGINES:
+        raise ConfigurationError(
+            f"Unknown engine '{engine}' in 'markdown.engine', "
+            f"expected one of: {', '.join(MARKDOWN_ENGINES)}"
+        )
+    markdown["extensions"] = [
+        _extension_name(extension)
+        for extension in config["markdown_extensions"]
+    ]
+    markdown["slugify"] = config["slugify"]
+
     # List files along with their hashes, so we can rebuild when they change
     watched_files = (
         _list_sources(config, path)  # mkdocstrings
@@ -681,6 +970,9 @@ def _apply_defaults(config: dict, path: str) -> dict:
     watched_files -= set(theme_files)
     config["watched_files"] = sorted(watched_files)
 
+    # Resolve snippet base paths, so we can track files included by pages
+    config["snippets"] = _convert_snippets(config, path)
+
     # Hash all templates, so we rebuild if something changes
     config["template_hash"] = _hash(theme_files)
 
@@ -845,6 +1137,14 @@ def _shim_macros(config: dict[str, Any]) -> None:
         config["mdx_configs"][MacrosExtension.name] = plugin
 
 
+def _extension_name(extension: Any) -> str:
+    # Extensions are given by name, except for extensions that are created by
+    # shims, e.g., mkdocstrings, for which we use the name of the package
+    if isinstance(extension, str):
+        return extension
+    return type(extension).__module__.split(".")[0]
+
+
 # ----------------------------------------------------------------------------
 
 
@@ -910,6 +1210,40 @@ def _list_snippet_files(config: dict, config_file: str) -> set[tuple[str, int]]:
     return files_with_mtime
 
 
+def _convert_severity(key: str, value: Any) -> str | None:
+    """Convert validation setting to a severity, or `None` to ignore."""
+    if isinstance(value, bool):
+        return "warning" if value else None
+    if value == "ignore":
+        return None
+
+    # We map info to warn for simplicity, like for MkDocs' validation settings
+    severity = {"info": "warning", "warn": "warning"}.get(value, value)
+    if severity not in DIAGNOSTICS_LEVELS:
+        raise ConfigurationError(
+            f"Unknown severity '{value}' in 'validation.{key}', "
+            f"expected one of: ignore, {', '.join(DIAGNOSTICS_LEVELS)}"
+        )
+    return severity
+
+
+def _convert_snippets(config: dict, config_file: str) -> dict:
+    """Convert pymdownx.snippets configuration to track included files."""
+    snippets_config = config["mdx_configs"].get("pymdownx.snippets", {})
+    base_paths = snippets_config.get("base_path", ["."])
+    if isinstance(base_paths, str):
+        base_paths = [base_paths]
+
+    # Resolve base paths relative to the project root
+    root = Path(config_file).parent.resolve()
+    base_paths = [str(root.joinpath(path).resolve()) for path in base_paths]
+    return {
+        "enabled": "pymdownx.snippets" in config["markdown_extensions"],
+        "base_path": base_paths,
+        "check_paths": bool(snippets_config.get("check_paths", False)),
+    }
+
+
 def _list_macros_files(config: dict, config_file: str) -> set[tuple[str, int]]:
     """List files referenced in macros plugin/extension."""
     root = Path(config_file).parent.resolve()
@@ -1000,6 +1334,11 @@ def _is_index(path: str) -> bool:
     return os.path.basename(path) in ("index.md", "README.md")
 
 
+def _is_external(url: str) -> bool:
+    """Returns, whether the given URL points to an external resource."""
+    return url.startswith(("http://", "https://", "mailto:"))
+
+
 def _convert_nav(nav: list) -> list:
     """Convert MkDocs navigation."""
     return [_convert_nav_item(entry) for entry in nav]
@@ -1013,36 +1352,56 @@ def _convert_nav_item(item: str | dict | list) -> dict | list:
     if isinstance(item, str):
         return {
             "title": None,
+            "title_html": None,
             "url": item,
             "canonical_url": None,
             "meta": None,
+            "icon": None,
+            "attributes": {},
             "children": [],
             "is_index": _is_index(item),
+            "external": _is_external(item),
             "active": False,
+            "missing": False,
         }
 
+    # Handle tables with explicit keys, which allow to set icons and custom
+    # attributes, and are told apart from `Title: URL` by their keys
+    if isinstance(item, dict) and _is_nav_table(item):
+        return _convert_nav_table(item)
+
     # Handle Title: URL
     if isinstance(item, dict):
         for title, value in item.items():
             if isinstance(value, str):
                 return {
                     "title": str(title),
+                    "title_html": None,
                     "url": value.strip(),
                     "canonical_url": None,
                     "meta": None,
+                    "icon": None,
+                    "attributes": {},
                     "children": [],
                     "is_index": _is_index(value.strip()),
+                    "external": _is_external(value.strip()),
                     "active": False,
+                    "missing": False,
                 }
             if isinstance(value, list):
                 return {
                     "title": str(title),
+                    "title_html": None,
                     "url": None,
                     "canonical_url": None,
                     "meta": None,
+                    "icon": None,
+                    "attributes": {},
                     "children": [_convert_nav_item(child) for child in value],
                     "is_index": False,
+                    "external": False,
                     "active": False,
+                    "missing": False,
                 }
             raise TypeError(f"Unknown nav item value type: {type(value)}")
 
@@ -1053,25 +1412,65 @@ def _convert_nav_item(item: str | dict | list) -> dict | list:
     raise TypeError(f"Unknown nav item type: {type(item)}")
 
 
-def _convert_extra(data: dict | list) -> dict | list:
-    """Recursively convert None values in a dictionary/list to empty strings."""
+def _is_nav_table(item: dict) -> bool:
+    """Return whether the navigation item is a table with explicit keys."""
+    return ("url" in item or "children" in item) and all(
+        key in NAV_ITEM_KEYS for key in item
+    )
+
+
+def _convert_nav_table(item: dict) -> dict:
+    """Convert navigation item given as a table with explicit keys."""
+    url = item.get("url")
+    if url is not None and not isinstance(url, str):
+        raise ConfigurationError("Navigation item 'url' must be a string.")
+    icon = item.get("icon")
+    if icon is not None and not isinstance(icon, str):
+        raise ConfigurationError("Navigation item 'icon' must be a string.")
+    attributes = item.get("attributes") or {}
+    if not isinstance(attributes, dict):
+        raise ConfigurationError(
+            "Navigation item 'attributes' must be a mapping/dictionary."
+        )
+
+    # Convert children, and return item
+    url = url.strip() if url else None
+    title = item.get("title")
+    return {
+        "title": str(title) if title is not None else None,
+        "title_html": None,
+        "url": url,
+        "canonical_url": None,
+        "meta": None,
+        "icon": icon,
+        "attributes": {
+            str(key): str(value) for key, value in attributes.items()
+        },
+        "children": [
+            _convert_nav_item(child) for child in item.get("children") or []
+        ],
+        "is_index": _is_index(url) if url else False,
+        "external": _is_external(url) if url else False,
+        "active": False,
+        "missing": False,
+    }
+
+
+def _convert_extra(data: Any) -> Any:
+    """Recursively convert values in a dictionary/list to supported types.
+
+    None values are converted to empty strings, and dates and times, which are
+    native types in TOML, are converted to ISO 8601 strings.
+    """
     if isinstance(data, dict):
         # Process each key-value pair in the dictionary
-        return {
-            key: _convert_extra(value)
-            if isinstance(value, (dict, list))
-            else ("" if value is None else value)
-            for key, value in data.items()
-        }
+        return {key: _convert_extra(value) for key, value in data.items()}
     if isinstance(data, list):
         # Process each item in the list
-        return [
-            _convert_extra(item)
-            if isinstance(item, (dict, list))
-            else ("" if item is None else item)
-            for item in data
-        ]
-    return data
+        return [_convert_extra(item) for item in data]
+    if isinstance(data, (date, datetime, time)):
+        return data.isoformat()
+    return "" if data is None else data
 
 
 def _convert_extra_javascript(value: list) -> list:
@@ -1089,6 +1488,20 @@ def _convert_extra_javascript(value: list) -> list:
             item.setdefault("type", None)
             item.setdefault("async", False)
             item.setdefault("defer", False)
+            if not isinstance(item["path"], str):
+                raise ConfigurationError(
+                    f"Invalid path in 'extra_javascript': {item['path']!r}"
+                )
+            if item["type"] is not None and not isinstance(item["type"], str):
+                raise ConfigurationError(
+                    f"Invalid type in 'extra_javascript': {item['type']!r}"
+                )
+            for key in ("async", "defer"):
+                if not isinstance(item[key], bool):
+                    raise ConfigurationError(
+                        f"Invalid value for '{key}' in 'extra_javascript', "
+                        "expected a boolean"
+                    )
         else:
             raise TypeError(f"Unknown extra_javascript item type: {type(item)}")
 
@@ -1132,7 +1545,8 @@ def _convert_markdown_extensions(value: Any) -> tuple[list[str], dict]:
             markdown_extensions.append(ext)
             mdx_configs[ext] = config or {}
 
-    # Extensions can also be defined as a list
+    # Extensions can also be defined as a list, in which case extensions that
+    # are given by name get empty settings, as if they were defined as a dict
     else:
         for item in value:
             if isinstance(item, dict):
@@ -1141,11 +1555,46 @@ def _convert_markdown_extensions(value: Any) -> tuple[list[str], dict]:
                 mdx_configs[ext] = config or {}
             elif isinstance(item, str):
                 markdown_extensions.append(item)
+                mdx_configs[item] = {}
 
     # Return extension list and configuration, after ensuring they're unique
     return list(set(markdown_extensions)), mdx_configs
 
 
+def _convert_languages(value: list) -> list:
+    """Convert languages of the i18n plugin.
+
+    Each language is built from a subdirectory of the docs directory, which
+    defaults to the locale. Exactly one language must be the default, which is
+    the first language, if none is explicitly marked as the default.
+    """
+    languages = []
+    for item in value:
+        if not isinstance(item, dict) or "locale" not in item:
+            raise ConfigurationError("i18n languages must define a 'locale'")
+
+        # Set defaults for language
+        language = dict(item)
+        locale = set_default(language, "locale", None, str)
+        set_default(language, "name", locale, str)
+        set_default(language, "path", locale, str)
+        set_default(language, "default", False, bool)
+        language["path"] = language["path"].strip("/")
+        if ".." in language["path"]:
+            raise ConfigurationError("i18n language path must not contain '..'")
+        languages.append(language)
+
+    # Ensure that exactly one language is the default
+    defaults = [language for language in languages if language["default"]]
+    if len(defaults) > 1:
+        raise ConfigurationError("i18n must define a single default language")
+    if languages and not defaults:
+        languages[0]["default"] = True
+
+    # Return languages
+    return languages
+
+
 def _convert_plugins(value: Any, config: dict) -> dict:
     """Convert plugins configuration to something we can work with."""
     plugins = {}
@@ -1174,6 +1623,75 @@ def _convert_plugins(value: Any, config: dict) -> dict:
     offline = set_default(plugins, "offline", {"enabled": False}, dict)
     set_default(offline, "enabled", True, bool)
 
+    # Define defaults for i18n plugin
+    i18n = set_default(plugins, "i18n", {"enabled": False}, dict)
+    set_default(i18n, "enabled", True, bool)
+    i18n["languages"] = _convert_languages(i18n.get("languages") or [])
+
+    # Define defaults for reading time plugin, which is opt-in
+    reading_time = set_default(plugins, "reading_time", {"enabled": False}, dict)
+    set_default(reading_time, "enabled", True, bool)
+    set_default(reading_time, "words_per_minute", 200, int)
+    set_default(reading_time, "count_code", False, bool)
+    set_default(reading_time, "meta", False, bool)
+
+    # Define defaults for variables plugin
+    variables = set_default(plugins, "variables", {"enabled": False}, dict)
+    set_default(variables, "enabled", True, bool)
+    set_default(variables, "render_code", False, bool)
+    set_default(variables, "env", [], list)
+
+    # Define defaults for images plugin
+    images = set_default(plugins, "images", {"enabled": False}, dict)
+    set_default(images, "enabled", True, bool)
+    set_default(images, "dimensions", True, bool)
+    set_default(images, "lazy_loading", True, bool)
+
+    # Define defaults for blog plugin - posts are located in a subdirectory of
+    # the blog directory by default, which can be referenced as `{blog}`
+    blog = set_default(plugins, "blog", {"enabled": False}, dict)
+    set_default(blog, "enabled", True, bool)
+    blog_dir = set_default(blog, "blog_dir", "blog", str).strip("/")
+    post_dir = set_default(blog, "post_dir", "{blog}/posts", str)
+    blog["blog_dir"] = blog_dir
+    blog["post_dir"] = post_dir.replace("{blog}", blog_dir).strip("/")
+    per_page = set_default(blog, "posts_per_page", 10, int)
+    if per_page < 1:
+        raise ConfigurationError(
+            f"Invalid value '{per_page}' in 'plugins.blog.posts_per_page', "
+            "expected at least 1"
+        )
+    set_default(blog, "categories_key", "categories", str)
+
+    # Define defaults for privacy plugin - all external assets are downloaded,
+    # unless patterns are given, and the offline mode only uses the cache
+    privacy = set_default(plugins, "privacy", {"enabled": False}, dict)
+    set_default(privacy, "enabled", True, bool)
+    set_default(privacy, "patterns", ["*"], list)
+    concurrency = set_default(privacy, "concurrency", 8, int)
+    if concurrency < 1:
+        raise ConfigurationError(
+            f"Invalid value '{concurrency}' in 'plugins.privacy.concurrency', "
+            "expected at least 1"
+        )
+    set_default(privacy, "offline", False, bool)
+
+    # Define defaults for social plugin - cards are rendered with the bundled
+    # font, unless another font is given, and only support hexadecimal colors
+    social = set_default(plugins, "social", {"enabled": False}, dict)
+    set_default(social, "enabled", True, bool)
+    cards_dir = set_default(social, "cards_dir", "assets/images/social", str)
+    social["cards_dir"] = cards_dir.strip("/")
+    set_default(social, "font_file", get_builtin_font_file(), str)
+    set_default(social, "logo", None, str)
+    for key, default in (("background_color", "#4051b5"), ("color", "#ffffff")):
+        color = set_default(social, key, default, str)
+        if not re.fullmatch(r"#[0-9a-fA-F]{6}", color):
+            raise ConfigurationError(
+                f"Invalid value '{color}' in 'plugins.social.{key}', "
+                "expected a hexadecimal color, e.g., '#4051b5'"
+            )
+
     # Ensure correct resolution of links when viewing the site from the
     # file system by disabling directory URLs
     if offline.get("enabled"):
@@ -42,6 +42,7 @@ from markdown.util import HTML_PLACEHOLDER_RE, INLINE_PLACEHOLDER_RE
 from markupsafe import Markup
 
 from zensical.extensions.context import ContextPreprocessor
+from zensical.markdown.slugs import Slugify
 
 if TYPE_CHECKING:
     from pathlib import Path
@@ -193,6 +194,18 @@ class AutorefsInlineProcessor(ReferenceInlineProcessor):
 
         return INLINE_PLACEHOLDER_RE.sub(_repl, identifier)
 
+    def _slugify(self, value: str) -> str:
+        """Slugify the given value like the table of contents extension.
+
+        If a slugification mode is configured, headings are slugified with it,
+        so we must use the same mode to compute the anchor of the heading.
+        """
+        if "toc" in self.md.treeprocessors:
+            toc = self.md.treeprocessors["toc"]
+            if isinstance(getattr(toc, "slugify", None), Slugify):
+                return toc.slugify.slug(value, "-")
+        return slugify(value, separator="-")
+
     def _eval_id(
         self, data: str, index: int, text: str
     ) -> tuple[str | None, str | None, int, bool]:
@@ -233,7 +246,7 @@ class AutorefsInlineProcessor(ReferenceInlineProcessor):
             # Examples: ``[`Foo` and `Bar`]``, `[The *Foo*][]`.
             else:
                 identifier = self._unstash(identifier)
-                slug = slugify(identifier, separator="-")
+                slug = self._slugify(identifier)
 
         end = m.end(0)
         return identifier, slug, end, True
@@ -419,6 +432,8 @@ def get_autorefs_data() -> dict[str, Any]:
             "secondary": AUTOREFS._secondary_url_map,
             "inventory": AUTOREFS._abs_url_map,
             "titles": AUTOREFS._title_map,
+            # Headings are registered from the tables of contents in Rust
+            "headings": {},
         }
     return {}
 
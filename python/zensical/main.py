@@ -23,6 +23,7 @@
 
 from __future__ import annotations
 
+import json
 import os
 import shutil
 from pathlib import Path
@@ -31,7 +32,7 @@ from typing import Any
 import click
 from click import ClickException
 
-from zensical import build, serve, version
+from zensical import build, get_config, serve, version
 
 # ----------------------------------------------------------------------------
 # Commands
@@ -66,6 +67,36 @@ def cli() -> None:
     is_flag=True,
     help="Enable strict mode - abort the build on warnings.",
 )
+@click.option(
+    "--reproducible",
+    default=False,
+    is_flag=True,
+    help="Build twice and check that both outputs are identical.",
+)
+@click.option(
+    "--prune-cache",
+    default=False,
+    is_flag=True,
+    help="Prune all cache entries that are not used in the build.",
+)
+@click.option(
+    "--site-dir",
+    type=click.Path(file_okay=False),
+    default=None,
+    help="Site directory, overriding the configured one.",
+)
+@click.option(
+    "--cache-dir",
+    type=click.Path(file_okay=False),
+    default=None,
+    help="Cache directory, overriding the configured one.",
+)
+@click.option(
+    "--dump-config",
+    default=False,
+    is_flag=True,
+    help="Print the normalized configuration as JSON and exit.",
+)
 def execute_build(config_file: str | None, **kwargs: Any) -> None:
     """Build a project."""
     if config_file is None:
@@ -76,6 +107,13 @@ def execute_build(config_file: str | None, **kwargs: Any) -> None:
         else:
             raise ClickException("No config file found in the current folder.")
 
+    # Print normalized configuration without building, which allows to compare
+    # a mkdocs.yml and a zensical.toml file, as both result in the same format
+    if kwargs.pop("dump_config"):
+        config = get_config(os.path.abspath(config_file))
+        print(json.dumps(config, indent=2, sort_keys=True))
+        return
+
     # Build project in Rust runtime, calling back into Python when necessary,
     # e.g., to parse MkDocs configuration format or render Markdown
     build(os.path.abspath(config_file), kwargs)
@@ -93,7 +131,10 @@ def execute_build(config_file: str | None, **kwargs: Any) -> None:
     "-a",
     "--dev-addr",
     metavar="<IP:PORT>",
-    help="IP address and port (default: localhost:8000).",
+    help=(
+        "IP address and port, or unix:<PATH> to serve over a Unix domain "
+        "socket (default: localhost:8000)."
+    ),
 )
 @click.option(
     "-o",
@@ -109,6 +150,29 @@ def execute_build(config_file: str | None, **kwargs: Any) -> None:
     is_flag=True,
     help="Strict mode (currently unsupported).",
 )
+@click.option(
+    "--scope",
+    metavar="<GLOB>",
+    help="Only build pages matching the glob, e.g., docs/team-a/**.",
+)
+@click.option(
+    "--site-dir",
+    type=click.Path(file_okay=False),
+    default=None,
+    help="Site directory, overriding the configured one.",
+)
+@click.option(
+    "--cache-dir",
+    type=click.Path(file_okay=False),
+    default=None,
+    help="Cache directory, overriding the configured one.",
+)
+@click.option(
+    "--production-cache",
+    default=False,
+    is_flag=True,
+    help="Send cache headers like a production deployment.",
+)
 def execute_serve(config_file: str | None, **kwargs: Any) -> None:
     """Build and serve a project."""
     if config_file is None:
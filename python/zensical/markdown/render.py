@@ -36,6 +36,8 @@ from zensical.extensions.autorefs import set_autorefs_page
 from zensical.extensions.context import ContextExtension, Page
 from zensical.extensions.links import LinksExtension
 from zensical.extensions.search import SearchExtension
+from zensical.markdown.slugs import Slugify, SlugifyExtension
+from zensical.markdown.slugs import slugify as slugify_default
 
 if TYPE_CHECKING:
     from zensical.extensions.search import SearchProcessor
@@ -70,16 +72,7 @@ def render(content: str, path: str, url: str) -> dict:
     # First, extract metadata - the Python Markdown parser brings a metadata
     # extension, but the implementation is broken, as it does not support full
     # YAML syntax, e.g. lists. Thus, we just parse the metadata with YAML.
-    meta: dict = {}
-    if match := FRONT_MATTER_RE.match(content):
-        try:
-            meta = yaml.load(match.group(1), SafeLoader)
-            if isinstance(meta, dict):
-                content = content[match.end() :].lstrip("\n")
-            else:
-                meta = {}
-        except Exception:  # noqa: BLE001
-            pass
+    meta, content = _extract_front_matter(content)
 
     # Create page context and set it for autorefs.
     # We can stop setting the page if/when we vendor mkdocstrings.
@@ -103,6 +96,11 @@ def render(content: str, path: str, url: str) -> dict:
             )
         )
 
+    # Reset used slugs, so duplicate headings are numbered per page
+    slugify = config["mdx_configs"]["toc"].get("slugify")
+    if isinstance(slugify, Slugify):
+        slugify.reset()
+
     # Initialize Markdown parser
     md = Markdown(
         extensions=config["markdown_extensions"],
@@ -120,6 +118,10 @@ def render(content: str, path: str, url: str) -> dict:
     search_extension = SearchExtension()
     search_extension.extendMarkdown(md)
 
+    # Register slugify extension, so slugs don't collide with explicit ids
+    if isinstance(slugify, Slugify):
+        SlugifyExtension(slugify).extendMarkdown(md)
+
     # Convert content to HTML
     content = md.convert(content)
 
@@ -135,12 +137,38 @@ def render(content: str, path: str, url: str) -> dict:
     return {
         "meta": meta,
         "title": "",
+        "title_html": "",
         "content": content,
         "search": search_processor.data,
         "toc": [_convert_toc(item) for item in getattr(md, "toc_tokens", [])],
+        "tags": _convert_tags(meta, slugify),
     }
 
 
+def front_matter(content: str) -> dict:
+    """Extract metadata from front matter.
+
+    This function is used to provide page metadata to macros, which are
+    rendered before the Markdown is passed to the parser.
+    """
+    meta, _ = _extract_front_matter(content)
+    return {k: _sanitize(v) for k, v in meta.items()}
+
+
+def _extract_front_matter(content: str) -> tuple[dict, str]:
+    """Extract metadata from front matter, and return the remaining content."""
+    if match := FRONT_MATTER_RE.match(content):
+        try:
+            meta = yaml.load(match.group(1), SafeLoader)
+            if isinstance(meta, dict):
+                return meta, content[match.end() :].lstrip("\n")
+        except Exception:  # noqa: BLE001
+            pass
+
+    # No valid front matter
+    return {}, content
+
+
 def _sanitize(value: Any) -> Any:
     # We currently don't have a null value for metadata in the Rust runtime
     if value is None:
@@ -154,6 +182,24 @@ def _sanitize(value: Any) -> Any:
     return value
 
 
+def _convert_tags(meta: dict, slugify: Any) -> list[dict]:
+    """Convert tags from metadata, slugified like headings."""
+    tags = meta.get("tags")
+    if not isinstance(tags, list):
+        return []
+
+    # Use configured slugification mode, or fall back to the default one
+    result = []
+    for tag in tags:
+        name = str(tag)
+        if isinstance(slugify, Slugify):
+            slug = slugify.slug(name)
+        else:
+            slug = slugify_default(name)
+        result.append({"name": name, "slug": slug})
+    return result
+
+
 def _convert_toc(item: Any) -> dict:
     """Convert a table of contents item to navigation item format."""
     toc_item = {
@@ -0,0 +1,166 @@
+# Copyright (c) 2025-2026 Zensical and contributors
+
+# SPDX-License-Identifier: MIT
+# All contributions are certified under the DCO
+
+# Permission is hereby granted, free of charge, to any person obtaining a copy
+# of this software and associated documentation files (the "Software"), to
+# deal in the Software without restriction, including without limitation the
+# rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+# sell copies of the Software, and to permit persons to whom the Software is
+# furnished to do so, subject to the following conditions:
+
+# The above copyright notice and this permission notice shall be included in
+# all copies or substantial portions of the Software.
+
+# THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+# IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+# FITNESS FOR A PARTICULAR PURPOSE AND NON-INFRINGEMENT. IN NO EVENT SHALL THE
+# AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+# LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+# FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+# IN THE SOFTWARE.
+
+from __future__ import annotations
+
+import re
+from typing import TYPE_CHECKING
+
+from markdown import Extension
+from markdown.extensions.toc import slugify as slugify_default
+from markdown.extensions.toc import slugify_unicode
+from markdown.treeprocessors import Treeprocessor
+
+if TYPE_CHECKING:
+    from xml.etree.ElementTree import Element
+
+    from markdown import Markdown
+
+# ----------------------------------------------------------------------------
+# Constants
+# ----------------------------------------------------------------------------
+
+
+SLUGIFY_MODES = ("default", "unicode", "github")
+"""
+Supported slugification modes.
+"""
+
+
+# ----------------------------------------------------------------------------
+# Classes
+# ----------------------------------------------------------------------------
+
+
+class Slugify:
+    """Slugification function for headings, ensuring unique slugs.
+
+    This is passed to the table of contents extension, which calls it for each
+    heading without an explicit identifier. Duplicate slugs are suffixed with
+    `-1`, `-2`, etc. in order of appearance, which is deterministic, as long as
+    the used slugs are reset before each page is rendered.
+    """
+
+    def __init__(self, mode: str) -> None:
+        if mode not in SLUGIFY_MODES:
+            raise ValueError(f"Unknown slugify mode: {mode}")
+        self.mode = mode
+        self.used: set[str] = set()
+
+    def __call__(self, value: str, separator: str) -> str:
+        """Return a unique slug for the given value."""
+        slug = self.slug(value, separator)
+
+        # Append counter to slug until it's unique
+        candidate, count = slug, 0
+        while candidate in self.used:
+            count += 1
+            candidate = f"{slug}{separator}{count}"
+
+        # Remember and return slug
+        self.used.add(candidate)
+        return candidate
+
+    def slug(self, value: str, separator: str = "-") -> str:
+        """Return the slug for the given value, without ensuring uniqueness."""
+        return slugify(value, separator, self.mode)
+
+    def reset(self) -> None:
+        """Reset used slugs, which must be done before rendering a page."""
+        self.used.clear()
+
+
+# ----------------------------------------------------------------------------
+
+
+class SlugifyTreeprocessor(Treeprocessor):
+    """Treeprocessor to reserve explicit identifiers of elements.
+
+    Headings can be given explicit identifiers, e.g., via attribute lists, so
+    generated slugs must not collide with them, or the table of contents would
+    suffix them once more, regardless of the slugification mode.
+    """
+
+    name = "zensical.markdown.slugs"
+
+    def __init__(self, md: Markdown, slugify: Slugify) -> None:
+        super().__init__(md)
+        self.slugify = slugify
+
+    def run(self, root: Element) -> None:
+        """Reserve all explicit identifiers in the document."""
+        for el in root.iter():
+            if "id" in el.attrib:
+                self.slugify.used.add(el.attrib["id"])
+
+
+# ----------------------------------------------------------------------------
+
+
+class SlugifyExtension(Extension):
+    """Markdown extension to keep slugs unique among explicit identifiers."""
+
+    name = "zensical.markdown.slugs"
+
+    def __init__(self, slugify: Slugify) -> None:
+        """Initialize the extension."""
+        self.slugify = slugify
+
+    def extendMarkdown(self, md: Markdown) -> None:
+        """Register Markdown extension."""
+        md.registerExtension(self)
+
+        # Register treeprocessor - run after `attr_list` (priority 8), which
+        # sets explicit identifiers, and before `toc` (priority 5)
+        treeprocessor = SlugifyTreeprocessor(md, self.slugify)
+        md.treeprocessors.register(treeprocessor, treeprocessor.name, 6)
+
+
+# ----------------------------------------------------------------------------
+# Functions
+# ----------------------------------------------------------------------------
+
+
+def slugify(value: str, separator: str = "-", mode: str = "default") -> str:
+    """Return the slug for the given value in the given mode.
+
+    - `default`: ASCII only, which is what Python Markdown uses by default
+    - `unicode`: Unicode characters are retained, like `uslugify`
+    - `github`: Unicode characters are retained, and each space is replaced
+      with the separator, which matches the anchors that GitHub generates
+    """
+    if mode == "github":
+        return _slugify_github(value, separator)
+    if mode == "unicode":
+        return slugify_unicode(value, separator)
+    return slugify_default(value, separator)
+
+
+def _slugify_github(value: str, separator: str) -> str:
+    """Return the slug for the given value like GitHub.
+
+    In contrast to the other modes, consecutive spaces and hyphens are not
+    collapsed, which is why removed punctuation may lead to multiple hyphens.
+    """
+    value = re.sub(r"[^\w\- ]", "", value.strip().lower())
+    return value.replace(" ", separator)
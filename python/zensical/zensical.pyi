@@ -21,19 +21,48 @@
 # FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
 # IN THE SOFTWARE.
 
+from pathlib import Path
+from typing import Any
+
 # ----------------------------------------------------------------------------
 # Functions
 # ----------------------------------------------------------------------------
 
-def build(config_file: str, options: dict) -> None:
-    """Builds the project."""
+def build(config_file: str, options: dict) -> dict[str, Any]:
+    """Builds the project, and returns the summary of the build."""
 
 def serve(config_file: str, options: dict) -> None:
     """Builds and serves the project."""
 
+def get_config(config_file: str) -> dict[str, Any]:
+    """Returns the normalized configuration as nested dictionaries and lists."""
+
+def get_navigation(
+    config_file: str, meta: list[str] | None = None
+) -> list[dict[str, Any]]:
+    """Returns the resolved navigation as nested dictionaries and lists."""
+
+def relocate(
+    site_dir: str, old_site_url: str, new_site_url: str
+) -> dict[str, int]:
+    """Relocates a built site from the old to the new site URL."""
+
+def resolve_theme_dirs(
+    name: str | None, custom_dir: str | None = None
+) -> list[Path]:
+    """Resolves the theme directories, ordered by precedence."""
+
 def version() -> str:
     """Returns the current version."""
 
 # ----------------------------------------------------------------------------
 
-__all__ = ["build", "serve", "version"]
+__all__ = [
+    "build",
+    "get_config",
+    "get_navigation",
+    "relocate",
+    "resolve_theme_dirs",
+    "serve",
+    "version",
+]